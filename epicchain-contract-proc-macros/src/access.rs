@@ -0,0 +1,112 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+use proc_macro2::{Span, TokenStream};
+use syn::{punctuated::Punctuated, Token};
+
+/// The check injected at the top of a method by an access attribute.
+pub(crate) enum AccessCheck {
+    Owner,
+    Role,
+    Contract,
+}
+
+/// Expands `#[only_owner]`, `#[only_role(..)]` and `#[only_contract(..)]` by prepending
+/// an abort-on-failure check to the method body. Other attributes, the visibility and
+/// the signature are kept untouched so the method is still picked up by `#[contract_impl]`.
+pub(crate) fn expand_access_check(
+    check: AccessCheck,
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let item = match syn::parse::<syn::ItemFn>(input) {
+        Ok(item) => item,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let guard = match check {
+        AccessCheck::Owner => {
+            if !args.is_empty() {
+                return syn::Error::new(Span::call_site(), "`#[only_owner]` takes no arguments")
+                    .to_compile_error()
+                    .into();
+            }
+            quote::quote! {
+                epicchain_contract::runtime::assert_with_code(
+                    epicchain_contract::runtime::access::is_owner(),
+                    epicchain_contract::runtime::ErrorCode::Unauthorized,
+                    "Only owner",
+                );
+            }
+        }
+        AccessCheck::Role => match expand_role_guard(args) {
+            Ok(guard) => guard,
+            Err(err) => return err.to_compile_error().into(),
+        },
+        AccessCheck::Contract => {
+            let expected = match syn::parse::<syn::Expr>(args) {
+                Ok(expected) => expected,
+                Err(_) => {
+                    return syn::Error::new(Span::call_site(), "`#[only_contract]` requires a contract hash expression")
+                        .to_compile_error()
+                        .into()
+                }
+            };
+            quote::quote! {
+                epicchain_contract::runtime::assert_with_code(
+                    epicchain_contract::runtime::access::is_calling_contract(#expected),
+                    epicchain_contract::runtime::ErrorCode::Unauthorized,
+                    "Only contract",
+                );
+            }
+        }
+    };
+
+    let attrs = &item.attrs;
+    let vis = &item.vis;
+    let sig = &item.sig;
+    let block = &item.block;
+
+    quote::quote! {
+        #(#attrs)*
+        #vis #sig {
+            #guard
+            #block
+        }
+    }
+    .into()
+}
+
+/// `#[only_role("MINTER")]` checks the calling script hash, `#[only_role("MINTER", account)]`
+/// checks that the `account` argument holds the role and witnessed the transaction.
+fn expand_role_guard(args: proc_macro::TokenStream) -> syn::Result<TokenStream> {
+    let parser = Punctuated::<syn::Expr, Token![,]>::parse_terminated;
+    let args = syn::parse::Parser::parse(parser, args)?;
+    let mut args = args.into_iter();
+
+    let role = match args.next() {
+        Some(syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(role), .. })) => role,
+        _ => return Err(syn::Error::new(Span::call_site(), "`#[only_role]` requires a role name string literal")),
+    };
+
+    let check = match args.next() {
+        Some(account) => quote::quote! {
+            epicchain_contract::runtime::access::is_role_witnessed(#role, #account)
+        },
+        None => quote::quote! {
+            epicchain_contract::runtime::access::is_caller_in_role(#role)
+        },
+    };
+
+    if args.next().is_some() {
+        return Err(syn::Error::new(Span::call_site(), "`#[only_role]` takes at most two arguments"));
+    }
+
+    Ok(quote::quote! {
+        epicchain_contract::runtime::assert_with_code(
+            #check,
+            epicchain_contract::runtime::ErrorCode::Unauthorized,
+            "Missing role",
+        );
+    })
+}
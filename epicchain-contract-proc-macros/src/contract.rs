@@ -5,6 +5,9 @@ use proc_macro2::{Span, TokenStream};
 
 const NEP11_TOKEN: &str = "Nep11Token";
 const NEP17_TOKEN: &str = "Nep17Token";
+const OWNABLE: &str = "Ownable";
+const PAUSABLE: &str = "Pausable";
+const ACCESS_CONTROL: &str = "AccessControl";
 
 pub(crate) fn expand_contract_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let mut src_impl: TokenStream = input.clone().into();
@@ -24,7 +27,7 @@ pub(crate) fn expand_contract_impl(input: proc_macro::TokenStream) -> proc_macro
 
 fn expand_impl_item(item: &syn::ItemImpl) -> TokenStream {
     let self_type = item.self_ty.as_ref();
-    let mut methods: TokenStream = item
+    let exported: Vec<_> = item
         .items
         .iter()
         .filter_map(|x| match x {
@@ -35,6 +38,10 @@ fn expand_impl_item(item: &syn::ItemImpl) -> TokenStream {
             // Only process methods marked with #[method] attribute
             has_method_attribute(method)
         })
+        .collect();
+
+    let mut methods: TokenStream = exported
+        .iter()
         .map(|method| {
             let name = &method.sig.ident;
             let returns = &method.sig.output;
@@ -68,10 +75,27 @@ fn expand_impl_item(item: &syn::ItemImpl) -> TokenStream {
             // Create argument list for method call (just parameter names)
             let arg_list = params.iter().map(|p| &p.pat);
 
+            // Deprecated entry points that may write state report their callers before
+            // routing to the method body; safe calls cannot emit notifications
+            let deprecation_notice = match crate::versioning::deprecation_of(method) {
+                Ok(Some(deprecation)) if !is_safe => {
+                    let name = name.to_string();
+                    let replacement = &deprecation.replacement;
+                    quote::quote! {
+                        epicchain_contract::runtime::versioning::notify_deprecated_call(#name, #replacement);
+                    }
+                }
+                _ => quote::quote! {},
+            };
+
+            // The export is the only entry point; methods calling each other on `contract`
+            // stay direct calls and share the invocation's storage context
             quote::quote! {
                 #[no_mangle]
                 #safe_comment
                 pub fn #name(#(#param_list),*) #returns {
+                    epicchain_contract::runtime::invocation::begin();
+                    #deprecation_notice
                     let contract = #self_type::init();
                     contract.#name(#(#arg_list),*)
                 }
@@ -79,6 +103,26 @@ fn expand_impl_item(item: &syn::ItemImpl) -> TokenStream {
         })
         .collect();
 
+    if let Some(deprecated) = crate::versioning::expand_deprecated_methods(item, &exported) {
+        methods.extend(deprecated);
+    }
+
+    methods.extend(crate::protocol::check_protocol_version(quote::quote! { #item }));
+
+    // Contracts that call out with a wildcard permission get a deprecation warning suggesting scoped ones
+    if item.generics.params.is_empty() && crate::permission::calls_contracts(quote::quote! { #item }) {
+        methods.extend(quote::quote! {
+            const _: () = {
+                use epicchain_contract::contract::permission::{ScopedCallScope as _, WildcardCallScope as _};
+
+                #[allow(dead_code)]
+                fn check_call_scope() {
+                    (&epicchain_contract::contract::permission::PermissionProbe::<#self_type>::new()).check_call_scope();
+                }
+            };
+        });
+    }
+
     if let Some((None, path, _for)) = &item.trait_ {
         // check path is Nep17Token or not
         if path.segments.last().map(|x| x.ident == NEP17_TOKEN).unwrap_or(false) {
@@ -88,6 +132,18 @@ fn expand_impl_item(item: &syn::ItemImpl) -> TokenStream {
         if path.segments.last().map(|x| x.ident == NEP11_TOKEN).unwrap_or(false) {
             methods.extend(expand_nep11_methods(item));
         }
+
+        if path.segments.last().map(|x| x.ident == OWNABLE).unwrap_or(false) {
+            methods.extend(expand_ownable_methods(item));
+        }
+
+        if path.segments.last().map(|x| x.ident == PAUSABLE).unwrap_or(false) {
+            methods.extend(expand_pausable_methods(item));
+        }
+
+        if path.segments.last().map(|x| x.ident == ACCESS_CONTROL).unwrap_or(false) {
+            methods.extend(expand_access_control_methods(item));
+        }
     }
 
     methods
@@ -124,6 +180,7 @@ fn expand_nep17_methods(item: &syn::ItemImpl) -> TokenStream {
         methods.extend(quote::quote! {
             #[no_mangle]
             pub fn _initialize() {
+                epicchain_contract::runtime::invocation::begin();
                 #self_type::_initialize()
             }
         });
@@ -135,6 +192,7 @@ fn expand_nep17_methods(item: &syn::ItemImpl) -> TokenStream {
             #[no_mangle]
             /* @safe */
             pub fn total_supply() -> epicchain_contract::types::Int256 {
+                epicchain_contract::runtime::invocation::begin();
                 #self_type::total_supply()
             }
         });
@@ -146,6 +204,7 @@ fn expand_nep17_methods(item: &syn::ItemImpl) -> TokenStream {
             #[no_mangle]
             /* @safe */
             pub fn balance_of(owner: epicchain_contract::types::H160) -> epicchain_contract::types::Int256 {
+                epicchain_contract::runtime::invocation::begin();
                 #self_type::balance_of(owner)
             }
         });
@@ -161,6 +220,7 @@ fn expand_nep17_methods(item: &syn::ItemImpl) -> TokenStream {
                 amount: epicchain_contract::types::Int256,
                 data: epicchain_contract::types::Array<epicchain_contract::types::Any>,
             ) -> bool {
+                epicchain_contract::runtime::invocation::begin();
                 #self_type::transfer(from, to, amount, data)
             }
         });
@@ -179,6 +239,7 @@ fn expand_nep11_methods(item: &syn::ItemImpl) -> TokenStream {
             #[no_mangle]
             /* @safe */
             pub fn symbol() -> epicchain_contract::types::ByteString {
+                epicchain_contract::runtime::invocation::begin();
                 #self_type::symbol()
             }
         });
@@ -190,6 +251,7 @@ fn expand_nep11_methods(item: &syn::ItemImpl) -> TokenStream {
             #[no_mangle]
             /* @safe */
             pub fn decimals() -> u8 {
+                epicchain_contract::runtime::invocation::begin();
                 #self_type::decimals()
             }
         });
@@ -201,6 +263,7 @@ fn expand_nep11_methods(item: &syn::ItemImpl) -> TokenStream {
             #[no_mangle]
             /* @safe */
             pub fn totalSupply() -> epicchain_contract::types::Int256 {
+                epicchain_contract::runtime::invocation::begin();
                 #self_type::total_supply()
             }
         });
@@ -212,6 +275,7 @@ fn expand_nep11_methods(item: &syn::ItemImpl) -> TokenStream {
             #[no_mangle]
             /* @safe */
             pub fn balanceOf(owner: epicchain_contract::types::H160) -> epicchain_contract::types::Int256 {
+                epicchain_contract::runtime::invocation::begin();
                 #self_type::balance_of(owner)
             }
         });
@@ -223,6 +287,7 @@ fn expand_nep11_methods(item: &syn::ItemImpl) -> TokenStream {
             #[no_mangle]
             /* @safe */
             pub fn tokensOf(owner: epicchain_contract::types::H160) -> epicchain_contract::types::Array<epicchain_contract::types::ByteString> {
+                epicchain_contract::runtime::invocation::begin();
                 #self_type::tokens_of(owner)
             }
         });
@@ -234,6 +299,7 @@ fn expand_nep11_methods(item: &syn::ItemImpl) -> TokenStream {
             #[no_mangle]
             /* @safe */
             pub fn ownerOf(tokenId: epicchain_contract::types::ByteString) -> epicchain_contract::types::H160 {
+                epicchain_contract::runtime::invocation::begin();
                 #self_type::owner_of(tokenId)
             }
         });
@@ -244,6 +310,7 @@ fn expand_nep11_methods(item: &syn::ItemImpl) -> TokenStream {
         methods.extend(quote::quote! {
             #[no_mangle]
             pub fn transfer(to: epicchain_contract::types::H160, tokenId: epicchain_contract::types::ByteString, data: epicchain_contract::types::Any) -> bool {
+                epicchain_contract::runtime::invocation::begin();
                 #self_type::transfer(to, tokenId, data)
             }
         });
@@ -252,6 +319,134 @@ fn expand_nep11_methods(item: &syn::ItemImpl) -> TokenStream {
     methods
 }
 
+fn expand_ownable_methods(item: &syn::ItemImpl) -> TokenStream {
+    let self_type = item.self_ty.as_ref();
+    let mut methods: TokenStream = quote::quote! {};
+
+    // `pending_owner` has default implementation
+    if !has_method(item, "pending_owner") {
+        methods.extend(quote::quote! {
+            #[no_mangle]
+            /* @safe */
+            pub fn pending_owner() -> epicchain_contract::types::H160 {
+                epicchain_contract::runtime::invocation::begin();
+                <#self_type as epicchain_contract::contract::Ownable>::pending_owner()
+            }
+        });
+    }
+
+    // `transfer_ownership` has default implementation
+    if !has_method(item, "transfer_ownership") {
+        methods.extend(quote::quote! {
+            #[no_mangle]
+            pub fn transfer_ownership(new_owner: epicchain_contract::types::H160) -> bool {
+                epicchain_contract::runtime::invocation::begin();
+                <#self_type as epicchain_contract::contract::Ownable>::transfer_ownership(new_owner)
+            }
+        });
+    }
+
+    // `accept_ownership` has default implementation
+    if !has_method(item, "accept_ownership") {
+        methods.extend(quote::quote! {
+            #[no_mangle]
+            pub fn accept_ownership() -> bool {
+                epicchain_contract::runtime::invocation::begin();
+                <#self_type as epicchain_contract::contract::Ownable>::accept_ownership()
+            }
+        });
+    }
+
+    // `renounce_ownership` has default implementation
+    if !has_method(item, "renounce_ownership") {
+        methods.extend(quote::quote! {
+            #[no_mangle]
+            pub fn renounce_ownership() -> bool {
+                epicchain_contract::runtime::invocation::begin();
+                <#self_type as epicchain_contract::contract::Ownable>::renounce_ownership()
+            }
+        });
+    }
+
+    methods
+}
+
+fn expand_pausable_methods(item: &syn::ItemImpl) -> TokenStream {
+    let self_type = item.self_ty.as_ref();
+    let mut methods: TokenStream = quote::quote! {};
+
+    // `is_paused` has default implementation
+    if !has_method(item, "is_paused") {
+        methods.extend(quote::quote! {
+            #[no_mangle]
+            /* @safe */
+            pub fn is_paused() -> bool {
+                epicchain_contract::runtime::invocation::begin();
+                <#self_type as epicchain_contract::contract::Pausable>::is_paused()
+            }
+        });
+    }
+
+    // `pause` has default implementation
+    if !has_method(item, "pause") {
+        methods.extend(quote::quote! {
+            #[no_mangle]
+            pub fn pause() -> bool {
+                epicchain_contract::runtime::invocation::begin();
+                <#self_type as epicchain_contract::contract::Pausable>::pause()
+            }
+        });
+    }
+
+    // `unpause` has default implementation
+    if !has_method(item, "unpause") {
+        methods.extend(quote::quote! {
+            #[no_mangle]
+            pub fn unpause() -> bool {
+                epicchain_contract::runtime::invocation::begin();
+                <#self_type as epicchain_contract::contract::Pausable>::unpause()
+            }
+        });
+    }
+
+    methods
+}
+
+fn expand_access_control_methods(item: &syn::ItemImpl) -> TokenStream {
+    let self_type = item.self_ty.as_ref();
+    let mut methods: TokenStream = quote::quote! {};
+
+    // `has_role` has default implementation
+    if !has_method(item, "has_role") {
+        methods.extend(quote::quote! {
+            #[no_mangle]
+            /* @safe */
+            pub fn has_role(role: epicchain_contract::types::ByteString, account: epicchain_contract::types::H160) -> bool {
+                epicchain_contract::runtime::invocation::begin();
+                <#self_type as epicchain_contract::contract::AccessControl>::has_role(role, account)
+            }
+        });
+    }
+
+    // `grant_role`, `revoke_role` and `renounce_role` have default implementations
+    for name in ["grant_role", "revoke_role", "renounce_role"] {
+        if has_method(item, name) {
+            continue;
+        }
+
+        let ident = syn::Ident::new(name, Span::call_site());
+        methods.extend(quote::quote! {
+            #[no_mangle]
+            pub fn #ident(role: epicchain_contract::types::ByteString, account: epicchain_contract::types::H160) -> bool {
+                epicchain_contract::runtime::invocation::begin();
+                <#self_type as epicchain_contract::contract::AccessControl>::#ident(role, account)
+            }
+        });
+    }
+
+    methods
+}
+
 fn has_method(item: &syn::ItemImpl, name: &str) -> bool {
     item.items.iter().any(|item| match item {
         syn::ImplItem::Fn(method) => method.sig.ident == name,
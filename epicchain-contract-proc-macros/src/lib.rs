@@ -1,8 +1,14 @@
 // Copyright @ 2024 - present, R3E Network
 // All Rights Reserved
 
+mod access;
 mod contract;
+mod metadata;
+mod permission;
+mod protocol;
+mod serialize;
 mod structs;
+mod versioning;
 
 /// It exports the MyContract methods as no_mangle methods:
 /// ```rust
@@ -153,6 +159,29 @@ pub fn safe(_args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> p
     input
 }
 
+/// Marks a method as a deprecated entry point kept for callers of an older ABI
+///
+/// The method stays exported and should adapt its arguments and forward to `replacement`,
+/// which must be another, non-deprecated `#[method]` of the contract. `#[contract_impl]`
+/// generates a safe `deprecated_methods()` method listing every deprecated entry point with
+/// its replacement, and unless the method is `#[safe]` each call emits a
+/// `DeprecatedMethodCalled` event naming the caller. `replacement` and the optional `since`
+/// are also recorded in the manifest.
+///
+/// # Example
+///
+/// ```ignore
+/// #[method]
+/// #[deprecated_method(replacement = "transfer_v2", since = "2.0.0")]
+/// pub fn transfer(&self, from: H160, to: H160, amount: Int256, data: Any) -> bool {
+///     self.transfer_v2(from, to, amount, ByteString::empty(), data)
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn deprecated_method(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    versioning::expand_deprecated_method(args, input)
+}
+
 /// Specifies the WASM export name for a method
 ///
 /// This allows mapping between the Rust method name and the name exported in the WASM file.
@@ -175,6 +204,9 @@ pub fn wasm_export(_args: proc_macro::TokenStream, input: proc_macro::TokenStrea
 
 /// Specifies the contract author in the manifest
 ///
+/// It also generates a safe `contract_author()` method returning the declared author,
+/// as `name <email>` when an email is given, so the metadata can be read on-chain.
+///
 /// # Example
 ///
 /// ```
@@ -182,25 +214,30 @@ pub fn wasm_export(_args: proc_macro::TokenStream, input: proc_macro::TokenStrea
 /// pub struct TokenContract;
 /// ```
 #[proc_macro_attribute]
-pub fn contract_author(_args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    // This attribute is only used as a marker for the manifest generator,
-    // it does not modify the code itself
-    input
+pub fn contract_author(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    metadata::expand_metadata_getter("contract_author", metadata::author, args, input)
 }
 
 /// Defines contract permissions in the manifest
 ///
+/// The contract is `*`, a `0x`-prefixed script hash or a group public key, and the methods
+/// are `*` or method names; malformed permissions are rejected at compile time. A full
+/// wildcard on a contract whose `#[contract_impl]` calls other contracts produces a warning
+/// unless it states a `reason`.
+///
 /// # Example
 ///
 /// ```
-/// #[contract_permission("*:*")]
+/// #[contract_permission("0xd2a4cff31913016155e38e474a2c06d08be276cf", "transfer", "balanceOf")]
+/// #[contract_permission("*", "onNEP17Payment")]
 /// pub struct TokenContract;
+///
+/// #[contract_permission("*", "*", reason = "executes arbitrary approved proposals")]
+/// pub struct Governance;
 /// ```
 #[proc_macro_attribute]
-pub fn contract_permission(_args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    // This attribute is only used as a marker for the manifest generator,
-    // it does not modify the code itself
-    input
+pub fn contract_permission(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    permission::expand_contract_permission(args, input)
 }
 
 /// Declares supported standards in the manifest
@@ -220,6 +257,9 @@ pub fn contract_standards(_args: proc_macro::TokenStream, input: proc_macro::Tok
 
 /// Specifies the contract version in the manifest
 ///
+/// It also generates a safe `contract_version()` method returning the declared version,
+/// so the metadata can be read on-chain.
+///
 /// # Example
 ///
 /// ```
@@ -227,10 +267,8 @@ pub fn contract_standards(_args: proc_macro::TokenStream, input: proc_macro::Tok
 /// pub struct TokenContract;
 /// ```
 #[proc_macro_attribute]
-pub fn contract_version(_args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    // This attribute is only used as a marker for the manifest generator,
-    // it does not modify the code itself
-    input
+pub fn contract_version(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    metadata::expand_metadata_getter("contract_version", metadata::first, args, input)
 }
 
 /// Adds extra metadata to the manifest
@@ -298,6 +336,63 @@ pub fn owner_only(_args: proc_macro::TokenStream, input: proc_macro::TokenStream
     TokenStream::from(expanded).into()
 }
 
+/// Restricts a method to the contract owner
+///
+/// Aborts with `ErrorCode::Unauthorized` unless the owner stored under the `"owner"`
+/// key has witnessed the transaction.
+///
+/// # Example
+///
+/// ```ignore
+/// #[method]
+/// #[only_owner]
+/// pub fn pause(&self) -> bool {
+///     // Implementation...
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn only_owner(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    access::expand_access_check(access::AccessCheck::Owner, args, input)
+}
+
+/// Restricts a method to holders of a role
+///
+/// With only a role name, the calling script hash must hold the role. With a second
+/// argument naming a method parameter, that account must hold the role and have
+/// witnessed the transaction. Roles are managed with `runtime::access::grant_role`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[method]
+/// #[only_role("MINTER", minter)]
+/// pub fn mint(&self, minter: H160, to: H160, amount: Int256) -> bool {
+///     // Implementation...
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn only_role(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    access::expand_access_check(access::AccessCheck::Role, args, input)
+}
+
+/// Restricts a method to calls coming from a specific contract
+///
+/// The argument is any expression evaluating to an `H160`; it may use `self`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[method]
+/// #[only_contract(self.get_staking_contract())]
+/// pub fn on_stake_released(&self, user: H160, amount: Int256) -> bool {
+///     // Implementation...
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn only_contract(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    access::expand_access_check(access::AccessCheck::Contract, args, input)
+}
+
 /// Requires witness verification for the calling script hash
 ///
 /// This annotation generates runtime checks to verify that the calling
@@ -487,4 +582,27 @@ pub fn init(_args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> p
     };
 
     TokenStream::from(expanded).into()
-}
\ No newline at end of file
+}
+
+/// Derives compact binary serialization for storage structs
+///
+/// Generates an `EpicChainSerializable` implementation that encodes the fields back to back
+/// in declaration order. Every field type must implement `EpicChainSerializable`
+/// (`H160`, `Int256`, `ByteString`, integers, `bool` or another derived struct).
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(NeoSerialize)]
+/// pub struct UserStake {
+///     pub amount: Int256,
+///     pub stake_time: u64,
+/// }
+///
+/// let data = serialize_to_bytestring(&stake)?;
+/// let stake: UserStake = deserialize_from_bytestring(&data)?;
+/// ```
+#[proc_macro_derive(NeoSerialize)]
+pub fn neo_serialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    serialize::expand_neo_serialize(input)
+}
@@ -0,0 +1,75 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+use proc_macro2::{Span, TokenStream};
+use syn::{punctuated::Punctuated, LitStr, Token};
+
+/// Expands a metadata attribute (`#[contract_version]`, `#[contract_author]`) into the
+/// original struct plus a safe getter named `getter` returning the literals as joined by `join`.
+///
+/// The getter is generated as an associated function on the contract struct and,
+/// for WASM targets, as an exported `#[no_mangle]` method so the declared metadata
+/// can be queried on-chain.
+pub(crate) fn expand_metadata_getter(
+    getter: &str,
+    join: fn(&[String]) -> String,
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut expanded: TokenStream = input.clone().into();
+
+    let item = match syn::parse::<syn::ItemStruct>(input) {
+        Ok(item) => item,
+        Err(_) => {
+            let message = format!("`#[{}]` can only be applied to `struct`", getter);
+            return syn::Error::new(Span::call_site(), message).to_compile_error().into();
+        }
+    };
+
+    let parser = Punctuated::<LitStr, Token![,]>::parse_terminated;
+    let values = match syn::parse::Parser::parse(parser, args) {
+        Ok(values) => values,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    if values.is_empty() {
+        let message = format!("`#[{}]` requires a string literal", getter);
+        return syn::Error::new(Span::call_site(), message).to_compile_error().into();
+    }
+    let value = join(&values.iter().map(LitStr::value).collect::<Vec<_>>());
+
+    let ident = &item.ident;
+    let (impls, types, wheres) = item.generics.split_for_impl();
+    let getter = syn::Ident::new(getter, Span::call_site());
+
+    expanded.extend(quote::quote! {
+        impl #impls #ident #types #wheres {
+            #[inline(always)]
+            pub fn #getter() -> epicchain_contract::types::ByteString {
+                epicchain_contract::types::ByteString::from_literal(#value)
+            }
+        }
+
+        #[cfg(target_family = "wasm")]
+        #[no_mangle]
+        /* @safe */
+        pub fn #getter() -> epicchain_contract::types::ByteString {
+            #ident::#getter()
+        }
+    });
+
+    expanded.into()
+}
+
+/// The declared version.
+pub(crate) fn first(values: &[String]) -> String {
+    values[0].clone()
+}
+
+/// The declared author as `name <email>`, the way the manifest records it.
+pub(crate) fn author(values: &[String]) -> String {
+    match values {
+        [name, email, ..] => format!("{} <{}>", name, email),
+        _ => values[0].clone(),
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::{parse::Parse, parse::ParseStream, punctuated::Punctuated, LitStr, Token};
+
+/// One argument of `#[contract_permission(..)]`: a string literal, or `reason = "..."`.
+enum PermissionArg {
+    Value(LitStr),
+    Reason(LitStr),
+}
+
+impl Parse for PermissionArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(PermissionArg::Value(input.parse()?));
+        }
+
+        let name: syn::Ident = input.parse()?;
+        if name != "reason" {
+            return Err(syn::Error::new(name.span(), "expected a string literal or `reason = \"...\"`"));
+        }
+        input.parse::<Token![=]>()?;
+        Ok(PermissionArg::Reason(input.parse()?))
+    }
+}
+
+/// Expands `#[contract_permission(..)]`.
+///
+/// Accepted forms:
+/// - `("contract:methods")`
+/// - `("contract", "methods")`
+/// - `("contract", "method1", "method2", ..)`
+///
+/// `contract` is `*`, a `0x`-prefixed script hash or a compressed group public key;
+/// `methods` is `*` or a comma separated list of method names. A full wildcard
+/// (`"*", "*"`) without a `reason` marks the struct so `#[contract_impl]` can warn when
+/// the contract calls other contracts and the permission could be scoped instead.
+pub(crate) fn expand_contract_permission(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut expanded: TokenStream = input.clone().into();
+
+    let item = match syn::parse::<syn::ItemStruct>(input) {
+        Ok(item) => item,
+        Err(_) => {
+            return syn::Error::new(Span::call_site(), "`#[contract_permission]` can only be applied to `struct`")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let parser = Punctuated::<PermissionArg, Token![,]>::parse_terminated;
+    let args = match syn::parse::Parser::parse(parser, args) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut values = Vec::new();
+    let mut reason = None;
+    for arg in args {
+        match arg {
+            PermissionArg::Value(value) if reason.is_none() => values.push(value),
+            PermissionArg::Value(value) => {
+                return syn::Error::new(value.span(), "`reason` must be the last argument")
+                    .to_compile_error()
+                    .into()
+            }
+            PermissionArg::Reason(value) if value.value().trim().is_empty() => {
+                return syn::Error::new(value.span(), "`reason` must not be empty").to_compile_error().into()
+            }
+            PermissionArg::Reason(value) => reason = Some(value),
+        }
+    }
+
+    let (contract, methods) = match split_permission(&values) {
+        Ok(permission) => permission,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    if let Err(message) = validate_contract(&contract.0) {
+        return syn::Error::new(contract.1, message).to_compile_error().into();
+    }
+    for (method, span) in methods.iter() {
+        if let Err(message) = validate_method(method) {
+            return syn::Error::new(*span, message).to_compile_error().into();
+        }
+    }
+    if methods.len() > 1 && methods.iter().any(|(method, _)| method == "*") {
+        return syn::Error::new(methods[0].1, "`*` cannot be combined with method names")
+            .to_compile_error()
+            .into();
+    }
+    for (i, (method, span)) in methods.iter().enumerate() {
+        if methods[..i].iter().any(|(other, _)| other == method) {
+            return syn::Error::new(*span, format!("method `{}` is listed twice", method))
+                .to_compile_error()
+                .into();
+        }
+    }
+
+    let is_wildcard = contract.0 == "*" && methods.iter().all(|(method, _)| method == "*");
+    if is_wildcard && reason.is_none() {
+        let ident = &item.ident;
+        let (impls, types, wheres) = item.generics.split_for_impl();
+        expanded.extend(quote::quote! {
+            impl #impls epicchain_contract::contract::permission::WildcardPermission for #ident #types #wheres {}
+        });
+    }
+
+    expanded.into()
+}
+
+type Spanned = (String, Span);
+
+/// Splits the literals into the contract part and the method list.
+fn split_permission(values: &[LitStr]) -> syn::Result<(Spanned, Vec<Spanned>)> {
+    match values {
+        [] => Err(syn::Error::new(Span::call_site(), "`#[contract_permission]` requires a contract and methods")),
+        [single] => {
+            let value = single.value();
+            match value.split_once(':') {
+                Some((contract, methods)) => {
+                    Ok(((contract.to_string(), single.span()), split_methods(methods, single.span())))
+                }
+                None => Err(syn::Error::new(single.span(), "expected `\"contract:methods\"`")),
+            }
+        }
+        [contract, methods @ ..] => {
+            let methods = methods.iter().flat_map(|lit| split_methods(&lit.value(), lit.span())).collect();
+            Ok(((contract.value(), contract.span()), methods))
+        }
+    }
+}
+
+fn split_methods(methods: &str, span: Span) -> Vec<Spanned> {
+    methods.split(',').map(|method| (method.trim().to_string(), span)).collect()
+}
+
+fn validate_contract(contract: &str) -> Result<(), String> {
+    if contract == "*" {
+        return Ok(());
+    }
+
+    if let Some(hash) = contract.strip_prefix("0x") {
+        if hash.len() == 40 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Ok(());
+        }
+        return Err(format!("`{}` is not a valid script hash (expected 0x + 40 hex digits)", contract));
+    }
+
+    // Compressed public key of a contract group
+    if contract.len() == 66
+        && (contract.starts_with("02") || contract.starts_with("03"))
+        && contract.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Ok(());
+    }
+
+    Err(format!(
+        "`{}` is not a valid permission contract (expected `*`, a 0x script hash or a group public key)",
+        contract
+    ))
+}
+
+fn validate_method(method: &str) -> Result<(), String> {
+    if method == "*" {
+        return Ok(());
+    }
+
+    let mut chars = method.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return Err(format!("`{}` is not a valid method name", method)),
+    }
+    if chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        Ok(())
+    } else {
+        Err(format!("`{}` is not a valid method name", method))
+    }
+}
+
+/// Whether a token stream calls another contract through `Contract::call*` or `contract::call`.
+pub(crate) fn calls_contracts(tokens: TokenStream) -> bool {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Group(group) if calls_contracts(group.stream()) => return true,
+            TokenTree::Ident(ident) if ident == "Contract" || ident == "contract" => {
+                let is_path = matches!(tokens.get(i + 1), Some(TokenTree::Punct(p)) if p.as_char() == ':')
+                    && matches!(tokens.get(i + 2), Some(TokenTree::Punct(p)) if p.as_char() == ':');
+                let is_call = matches!(tokens.get(i + 3), Some(TokenTree::Ident(next)) if next.to_string().starts_with("call"));
+                if is_path && is_call {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
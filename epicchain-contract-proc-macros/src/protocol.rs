@@ -0,0 +1,82 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+use proc_macro2::{TokenStream, TokenTree};
+
+/// An API only available from a protocol version on, see `epicchain_contract::protocol`.
+struct GatedApi {
+    name: &'static str,
+    syscall: &'static str,
+    version: &'static str,
+    feature: &'static str,
+    enabled: bool,
+}
+
+const GATED_APIS: &[GatedApi] = &[
+    GatedApi {
+        name: "current_signers",
+        syscall: "System.Runtime.CurrentSigners",
+        version: "3.6",
+        feature: "protocol-3-6",
+        enabled: cfg!(feature = "protocol-3-6"),
+    },
+    GatedApi {
+        name: "Secp256k1Keccak256",
+        syscall: "CryptoLib.verifyWithECDsa",
+        version: "3.7",
+        feature: "protocol-3-7",
+        enabled: cfg!(feature = "protocol-3-7"),
+    },
+    GatedApi {
+        name: "Secp256r1Keccak256",
+        syscall: "CryptoLib.verifyWithECDsa",
+        version: "3.7",
+        feature: "protocol-3-7",
+        enabled: cfg!(feature = "protocol-3-7"),
+    },
+    GatedApi {
+        name: "verify_ed25519",
+        syscall: "CryptoLib.verifyWithEd25519",
+        version: "3.8",
+        feature: "protocol-3-8",
+        enabled: cfg!(feature = "protocol-3-8"),
+    },
+];
+
+/// Reports every use of an API newer than the targeted protocol version in `tokens`.
+///
+/// Without this the gated API is simply missing, and rustc answers with an unhelpful
+/// "cannot find function"; here the error names the syscall and the feature to enable.
+pub(crate) fn check_protocol_version(tokens: TokenStream) -> TokenStream {
+    let mut errors = TokenStream::new();
+    collect_unsupported(tokens, &mut errors);
+    errors
+}
+
+fn collect_unsupported(tokens: TokenStream, errors: &mut TokenStream) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Group(group) => collect_unsupported(group.stream(), errors),
+            TokenTree::Ident(ident) => {
+                let Some(api) = GATED_APIS.iter().find(|api| !api.enabled && ident == api.name) else {
+                    continue;
+                };
+
+                // Method calls (`self.current_signers()`) and definitions name the contract's own items
+                let is_method = matches!(tokens.get(i.wrapping_sub(1)), Some(TokenTree::Punct(p)) if p.as_char() == '.')
+                    || matches!(tokens.get(i.wrapping_sub(1)), Some(TokenTree::Ident(prev)) if prev == "fn");
+                if is_method {
+                    continue;
+                }
+
+                let message = format!(
+                    "`{}` ({}) requires protocol {} or later; enable the `{}` feature of `epicchain-contract` if the target network supports it",
+                    api.name, api.syscall, api.version, api.feature,
+                );
+                errors.extend(syn::Error::new(ident.span(), message).to_compile_error());
+            }
+            _ => {}
+        }
+    }
+}
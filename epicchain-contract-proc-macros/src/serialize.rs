@@ -0,0 +1,66 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+use proc_macro2::{Span, TokenStream};
+
+/// Expands `#[derive(NeoSerialize)]` into an `EpicChainSerializable` implementation that
+/// writes the fields back to back in declaration order.
+pub(crate) fn expand_neo_serialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let item = match syn::parse::<syn::DeriveInput>(input) {
+        Ok(item) => item,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    match expand_item(&item) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_item(item: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &item.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`#[derive(NeoSerialize)]` can only be applied to `struct`",
+            ))
+        }
+    };
+
+    let ident = &item.ident;
+    let (impls, types, wheres) = item.generics.split_for_impl();
+
+    let (writes, construct) = match fields {
+        syn::Fields::Named(named) => {
+            let names: Vec<_> = named.named.iter().map(|field| field.ident.clone().unwrap()).collect();
+            let writes = quote::quote! { #(writer.write(&self.#names);)* };
+            let construct = quote::quote! { Self { #(#names: reader.read()?,)* } };
+            (writes, construct)
+        }
+        syn::Fields::Unnamed(unnamed) => {
+            let indexes: Vec<_> = (0..unnamed.unnamed.len()).map(syn::Index::from).collect();
+            let reads = indexes.iter().map(|_| quote::quote! { reader.read()? });
+            let writes = quote::quote! { #(writer.write(&self.#indexes);)* };
+            let construct = quote::quote! { Self(#(#reads,)*) };
+            (writes, construct)
+        }
+        syn::Fields::Unit => (quote::quote! {}, quote::quote! { Self }),
+    };
+
+    Ok(quote::quote! {
+        impl #impls epicchain_contract::serialize::EpicChainSerializable for #ident #types #wheres {
+            fn to_bytes(&self) -> epicchain_contract::types::Bytes {
+                let mut writer = epicchain_contract::serialize::FieldWriter::new();
+                #writes
+                writer.finish()
+            }
+
+            #[allow(unused_variables, unused_mut)]
+            fn from_bytes(bytes: &[u8]) -> core::result::Result<Self, epicchain_contract::serialize::SerializationError> {
+                let mut reader = epicchain_contract::serialize::FieldReader::new(bytes);
+                core::result::Result::Ok(#construct)
+            }
+        }
+    })
+}
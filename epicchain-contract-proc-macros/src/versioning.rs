@@ -0,0 +1,144 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+use proc_macro2::{Span, TokenStream};
+use syn::{punctuated::Punctuated, spanned::Spanned, LitStr, Token};
+
+const DEPRECATED_METHOD: &str = "deprecated_method";
+
+/// A `#[deprecated_method(replacement = "...", since = "...")]` declaration.
+///
+/// `since` is only read by the manifest generator, so it is validated but not kept.
+pub(crate) struct Deprecation {
+    pub(crate) replacement: LitStr,
+}
+
+impl syn::parse::Parse for Deprecation {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let args = Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated(input)?;
+
+        let mut replacement = None;
+        let mut since = false;
+        for arg in args {
+            let value = match &arg.value {
+                syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(value), .. }) => value.clone(),
+                other => return Err(syn::Error::new(other.span(), "expected a string literal")),
+            };
+
+            if arg.path.is_ident("replacement") && replacement.is_none() {
+                replacement = Some(value);
+            } else if arg.path.is_ident("since") && !since {
+                since = true;
+            } else {
+                return Err(syn::Error::new(arg.path.span(), "expected a single `replacement` and optional `since`"));
+            }
+        }
+
+        let Some(replacement) = replacement else {
+            return Err(syn::Error::new(Span::call_site(), "`#[deprecated_method]` requires `replacement = \"...\"`"));
+        };
+
+        Ok(Self { replacement })
+    }
+}
+
+/// Validates the arguments of a standalone `#[deprecated_method]` attribute.
+///
+/// The routing itself is generated by `#[contract_impl]`, which sees the attribute
+/// before it is expanded; here it only has to reject malformed declarations.
+pub(crate) fn expand_deprecated_method(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if let Err(err) = syn::parse::<Deprecation>(args) {
+        return err.to_compile_error().into();
+    }
+
+    input
+}
+
+/// Returns the deprecation declared on `method`, if any.
+pub(crate) fn deprecation_of(method: &syn::ImplItemFn) -> syn::Result<Option<Deprecation>> {
+    method
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(DEPRECATED_METHOD))
+        .map(|attr| attr.parse_args::<Deprecation>())
+        .transpose()
+}
+
+/// Checks every deprecated `#[method]` of `item` names another, non-deprecated `#[method]`
+/// as its replacement and generates the safe `deprecated_methods()` getter.
+///
+/// Returns `None` when the impl block declares no deprecated methods.
+pub(crate) fn expand_deprecated_methods(item: &syn::ItemImpl, methods: &[&syn::ImplItemFn]) -> Option<TokenStream> {
+    let mut deprecated = Vec::new();
+    for method in methods {
+        match deprecation_of(method) {
+            Ok(Some(deprecation)) => deprecated.push((&method.sig.ident, deprecation)),
+            Ok(None) => {}
+            Err(err) => return Some(err.to_compile_error()),
+        }
+    }
+
+    if deprecated.is_empty() {
+        return None;
+    }
+
+    for (name, deprecation) in &deprecated {
+        let replacement = deprecation.replacement.value();
+        let message = if *name == &replacement {
+            Some(format!("`{}` cannot be its own replacement", name))
+        } else if !methods.iter().any(|method| method.sig.ident == replacement) {
+            Some(format!("replacement `{}` of `{}` is not a `#[method]` of this contract", replacement, name))
+        } else if deprecated.iter().any(|(other, _)| *other == &replacement) {
+            Some(format!("replacement `{}` of `{}` is itself deprecated", replacement, name))
+        } else {
+            None
+        };
+
+        if let Some(message) = message {
+            return Some(syn::Error::new(deprecation.replacement.span(), message).to_compile_error());
+        }
+    }
+
+    // A contract may provide its own listing, e.g. to include entry points it removed
+    if item.items.iter().any(|x| matches!(x, syn::ImplItem::Fn(method) if method.sig.ident == "deprecated_methods")) {
+        return Some(TokenStream::new());
+    }
+
+    let self_type = item.self_ty.as_ref();
+    let (impls, _, wheres) = item.generics.split_for_impl();
+    let names = deprecated.iter().map(|(name, _)| name.to_string());
+    let replacements = deprecated.iter().map(|(_, deprecation)| &deprecation.replacement);
+
+    let mut expanded = quote::quote! {
+        impl #impls #self_type #wheres {
+            /// Deprecated entry points of the contract, mapped to the methods replacing them.
+            pub fn deprecated_methods() -> epicchain_contract::types::Map<epicchain_contract::types::ByteString, epicchain_contract::types::ByteString> {
+                let mut methods = epicchain_contract::types::Map::new();
+                #(
+                    methods.put(
+                        epicchain_contract::types::ByteString::from_literal(#names),
+                        epicchain_contract::types::ByteString::from_literal(#replacements),
+                    );
+                )*
+                methods
+            }
+        }
+    };
+
+    if item.generics.params.is_empty() {
+        expanded.extend(quote::quote! {
+            #[cfg(target_family = "wasm")]
+            #[no_mangle]
+            /* @safe */
+            pub fn deprecated_methods() -> epicchain_contract::types::Map<epicchain_contract::types::ByteString, epicchain_contract::types::ByteString> {
+                epicchain_contract::runtime::invocation::begin();
+                #self_type::deprecated_methods()
+            }
+        });
+    }
+
+    Some(expanded)
+}
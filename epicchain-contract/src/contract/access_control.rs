@@ -0,0 +1,103 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+use crate::{
+    contract::Ownable,
+    runtime::{self, access},
+    services::storage::Storage,
+    types::{
+        builtin::{
+            array::Array,
+            h160::H160,
+            string::ByteString,
+            any::IntoAny,
+        },
+        Any,
+    },
+};
+
+/// Role of accounts allowed to mint.
+pub const MINTER_ROLE: &str = "MINTER";
+
+/// Role of accounts allowed to pause, see [`Pausable::can_pause`](crate::contract::Pausable::can_pause).
+pub const PAUSER_ROLE: &str = "PAUSER";
+
+/// Role of accounts operating the contract on the owner's behalf.
+pub const OPERATOR_ROLE: &str = "OPERATOR";
+
+/// Role memberships managed through the contract ABI.
+///
+/// Memberships use the [`access`] storage layout, so roles granted here are the ones
+/// `#[only_role(..)]` checks. `#[contract_impl]` on `impl AccessControl for MyContract {}`
+/// exports `grant_role`, `revoke_role`, `renounce_role` and `has_role`. The owner manages
+/// every role by default; override `can_manage_role` to delegate some of them.
+// NOTE: epicchain-contract-proc-macros must be updated
+//if any method definition changed(add, remove, modify) in this trait
+pub trait AccessControl: Ownable {
+    /// Returns true if the caller may grant and revoke `role`.
+    #[inline(always)]
+    fn can_manage_role(_role: &ByteString) -> bool {
+        Self::is_owner()
+    }
+
+    #[inline(always)]
+    fn has_role(role: ByteString, account: H160) -> bool {
+        Storage::get(Storage::get_context(), access::role_key_of(&role, account)).is_some()
+    }
+
+    fn grant_role(role: ByteString, account: H160) -> bool {
+        if !Self::can_manage_role(&role) {
+            runtime::log(ByteString::from_literal("Unauthorized: role admin only"));
+            return false;
+        }
+
+        if role.is_empty() || account == H160::zero() || Self::has_role(role.clone(), account) {
+            runtime::log(ByteString::from_literal("Invalid role grant"));
+            return false;
+        }
+
+        let key = access::role_key_of(&role, account);
+        Storage::put(Storage::get_context(), key, ByteString::from_literal("1"));
+        notify_role("RoleGranted", role, account);
+        true
+    }
+
+    fn revoke_role(role: ByteString, account: H160) -> bool {
+        if !Self::can_manage_role(&role) {
+            runtime::log(ByteString::from_literal("Unauthorized: role admin only"));
+            return false;
+        }
+
+        remove_role(role, account)
+    }
+
+    /// Lets `account` give up its own `role`, e.g. after a key compromise.
+    fn renounce_role(role: ByteString, account: H160) -> bool {
+        if !runtime::check_witness_with_account(account) {
+            runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        remove_role(role, account)
+    }
+}
+
+fn remove_role(role: ByteString, account: H160) -> bool {
+    let key = access::role_key_of(&role, account);
+    let storage = Storage::get_context();
+    if Storage::get(storage.clone(), key.clone()).is_none() {
+        runtime::log(ByteString::from_literal("Role not held"));
+        return false;
+    }
+
+    Storage::delete(storage, key);
+    notify_role("RoleRevoked", role, account);
+    true
+}
+
+fn notify_role(event: &str, role: ByteString, account: H160) {
+    let mut event_data = Array::<Any>::new();
+    event_data.push(role.into_any());
+    event_data.push(account.into_any());
+    runtime::notify(ByteString::from_literal(event), event_data);
+}
@@ -0,0 +1,61 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Call data for deferred contract calls, e.g. the actions of governance or multisig
+//! proposals, executed later through [`Contract::call`](crate::services::contract::Contract::call).
+//!
+//! Layout: method length (u8) | method | per argument: length (u16 LE) | bytes. Arguments are
+//! passed as byte strings, which the VM converts for integer and hash parameters.
+
+use crate::types::{
+    builtin::{any::IntoAny, array::Array, string::ByteString},
+    Any,
+};
+
+/// Encodes a call of `method` with `args`; `None` if the method name is empty or longer than
+/// 255 bytes, or an argument is longer than 65535 bytes.
+pub fn encode(method: &ByteString, args: &[ByteString]) -> Option<ByteString> {
+    if method.is_empty() || method.len() > u8::MAX as usize {
+        return None;
+    }
+
+    let mut data = ByteString::from_bytes(&[method.len() as u8]).concat(method);
+    for arg in args {
+        if arg.len() > u16::MAX as usize {
+            return None;
+        }
+        data = data.concat(&ByteString::from_bytes(&(arg.len() as u16).to_le_bytes())).concat(arg);
+    }
+    Some(data)
+}
+
+/// Decodes call data into the method name and its arguments; `None` if it is malformed.
+pub fn decode(data: &ByteString) -> Option<(ByteString, Array<Any>)> {
+    let bytes = data.to_bytes();
+    if bytes.is_empty() {
+        return None;
+    }
+
+    let method_len = bytes[0] as usize;
+    if method_len == 0 || bytes.len() < 1 + method_len {
+        return None;
+    }
+    let method = ByteString::from_bytes(&bytes[1..1 + method_len]);
+
+    let mut args = Array::new();
+    let mut offset = 1 + method_len;
+    while offset < bytes.len() {
+        if offset + 2 > bytes.len() {
+            return None;
+        }
+        let arg_len = u16::from_le_bytes([bytes[offset], bytes[offset + 1]]) as usize;
+        offset += 2;
+        if offset + arg_len > bytes.len() {
+            return None;
+        }
+        args.push(ByteString::from_bytes(&bytes[offset..offset + arg_len]).into_any());
+        offset += arg_len;
+    }
+
+    Some((method, args))
+}
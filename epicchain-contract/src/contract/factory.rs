@@ -0,0 +1,131 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Deterministic child-contract deployment.
+//!
+//! A [`Factory`] deploys children from a NEF and a manifest template through
+//! `ContractManagement::deploy`. Every child gets a salted name (`<base>-<salt>`) that is
+//! substituted for [`NAME_PLACEHOLDER`] in the template. Because a contract hash is derived
+//! from the deployer, the NEF checksum and the manifest name, the same factory, template
+//! and salt always yield the same child. Deployed children are kept in a storage registry
+//! under the factory prefix so they can be looked up by name, by index or by hash.
+
+use crate::{
+    contract::native::ContractManagement,
+    services::{runtime::Runtime, storage::Storage},
+    types::{builtin::IntoAny, Array, ByteString, FromByteString, H160, Int256, IntoByteString},
+};
+
+/// Placeholder replaced by the salted child name in manifest templates.
+pub const NAME_PLACEHOLDER: &str = "{{name}}";
+
+/// Deploys child contracts and keeps a registry of them.
+pub struct Factory {
+    prefix: ByteString,
+}
+
+impl Factory {
+    /// Creates a factory whose registry lives under `prefix`.
+    pub fn new(prefix: &str) -> Self {
+        Self { prefix: ByteString::from_literal(prefix) }
+    }
+
+    /// Returns the salted name of a child: `<base>-<salt>`.
+    pub fn child_name(base: &ByteString, salt: &ByteString) -> ByteString {
+        base.concat(&ByteString::from_literal("-")).concat(salt)
+    }
+
+    /// Replaces every [`NAME_PLACEHOLDER`] in `template` with `name`.
+    pub fn render_manifest(template: &ByteString, name: &ByteString) -> ByteString {
+        let placeholder = ByteString::from_literal(NAME_PLACEHOLDER);
+        let width = placeholder.len();
+
+        let mut rendered = ByteString::empty();
+        let mut start = 0;
+        let mut i = 0;
+        while i + width <= template.len() {
+            if template.substr(i, width) == placeholder {
+                rendered = rendered.concat(&template.substr(start, i - start)).concat(name);
+                i += width;
+                start = i;
+            } else {
+                i += 1;
+            }
+        }
+
+        rendered.concat(&template.substr(start, template.len() - start))
+    }
+
+    /// Deploys the child `<base>-<salt>` and registers it.
+    ///
+    /// Returns None if a child with that name is already registered or the deployment failed.
+    /// Callers are responsible for authorization and for paying the deployment fee.
+    pub fn deploy(
+        &self,
+        nef: ByteString,
+        manifest_template: &ByteString,
+        base: &ByteString,
+        salt: &ByteString,
+    ) -> Option<H160> {
+        let name = Self::child_name(base, salt);
+        if self.child_by_name(&name).is_some() {
+            return None;
+        }
+
+        let manifest = Self::render_manifest(manifest_template, &name);
+        let hash = ContractManagement::deploy(nef, manifest).hash();
+        if hash == H160::zero() {
+            return None;
+        }
+
+        self.register(&name, hash);
+        Some(hash)
+    }
+
+    /// Returns the child deployed as `<base>-<salt>`, if any.
+    pub fn child_of(&self, base: &ByteString, salt: &ByteString) -> Option<H160> {
+        self.child_by_name(&Self::child_name(base, salt))
+    }
+
+    /// Returns the child registered under the full salted `name`, if any.
+    pub fn child_by_name(&self, name: &ByteString) -> Option<H160> {
+        Storage::get(Storage::get_context(), self.key("name_", name)).map(H160::from_byte_string)
+    }
+
+    /// Returns the `index`-th deployed child, in deployment order.
+    pub fn child_at(&self, index: Int256) -> Option<H160> {
+        Storage::get(Storage::get_context(), self.key("idx_", &index.into_byte_string())).map(H160::from_byte_string)
+    }
+
+    /// Returns the number of deployed children.
+    pub fn child_count(&self) -> Int256 {
+        match Storage::get(Storage::get_context(), self.key("count", &ByteString::empty())) {
+            Some(count) => Int256::from_byte_string(count),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Returns true if `hash` was deployed by this factory.
+    pub fn is_child(&self, hash: H160) -> bool {
+        Storage::get(Storage::get_context(), self.key("hash_", &hash.into_byte_string())).is_some()
+    }
+
+    fn register(&self, name: &ByteString, hash: H160) {
+        let context = Storage::get_context();
+        let index = self.child_count();
+
+        Storage::put(context.clone(), self.key("name_", name), hash.into_byte_string());
+        Storage::put(context.clone(), self.key("hash_", &hash.into_byte_string()), name.clone());
+        Storage::put(context.clone(), self.key("idx_", &index.into_byte_string()), hash.into_byte_string());
+        Storage::put(context, self.key("count", &ByteString::empty()), index.checked_inc().into_byte_string());
+
+        let mut state = Array::new();
+        state.push(name.clone().into_any());
+        state.push(hash.into_any());
+        Runtime::notify(ByteString::from_literal("ChildDeployed"), state);
+    }
+
+    fn key(&self, kind: &str, suffix: &ByteString) -> ByteString {
+        self.prefix.concat(&ByteString::from_literal(kind)).concat(suffix)
+    }
+}
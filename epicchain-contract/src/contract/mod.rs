@@ -1,15 +1,21 @@
 // Copyright @ 2024 - present, R3E Network
 // All Rights Reserved.
 
+pub mod calldata;
 pub mod native;
+pub mod permission;
 
+pub(crate) mod access_control;
 pub(crate) mod event;
+pub(crate) mod factory;
 pub(crate) mod nep;
 pub(crate) mod nep11;
 pub(crate) mod nep17;
+pub(crate) mod ownable;
+pub(crate) mod pausable;
 pub(crate) mod token;
 
-pub use {event::*, nep::*, nep11::*, nep17::*};
+pub use {access_control::*, event::*, factory::*, nep::*, nep11::*, nep17::*, ownable::*, pausable::*};
 
 use crate::types::*;
 
@@ -17,16 +23,11 @@ use crate::types::*;
 use crate::env;
 
 #[inline(always)]
-pub fn call(
-    #[cfg_attr(not(target_family = "wasm"), allow(unused_variables))] contract: H160,
-    #[cfg_attr(not(target_family = "wasm"), allow(unused_variables))] method: ByteString,
-    #[cfg_attr(not(target_family = "wasm"), allow(unused_variables))] call_flags: CallFlags,
-    #[cfg_attr(not(target_family = "wasm"), allow(unused_variables))] args: Array<Any>,
-) -> Any {
+pub fn call(contract: H160, method: ByteString, call_flags: CallFlags, args: Array<Any>) -> Any {
     #[cfg(target_family = "wasm")]
     unsafe { env::syscall::system_contract_call(contract, method, call_flags, args) }
     #[cfg(not(target_family = "wasm"))]
-    Any::default()
+    crate::host::call_contract(contract, method, call_flags, args)
 }
 
 #[inline(always)]
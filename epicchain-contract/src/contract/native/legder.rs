@@ -23,7 +23,7 @@ impl Ledger {
         unsafe { env::contract::native_ledger_current_block_index() }
 
         #[cfg(not(target_family = "wasm"))]
-        0
+        crate::host::block_index()
     }
 
     #[inline(always)]
@@ -31,7 +31,7 @@ impl Ledger {
         #[cfg(target_family = "wasm")]
         unsafe { env::contract::native_ledger_current_block_hash() }
         #[cfg(not(target_family = "wasm"))]
-        H256::zero()
+        crate::host::block_hash()
     }
 
     #[inline(always)]
@@ -50,6 +50,23 @@ impl ContractManagement {
         Contract::default() // Mock implementation for non-WASM targets
     }
 
+    /// Returns true if `hash` belongs to a deployed contract rather than a plain account.
+    #[inline(always)]
+    #[rustfmt::skip]
+    pub fn is_contract(hash: H160) -> bool {
+        #[cfg(target_family = "wasm")]
+        {
+            use crate::types::builtin::IntoAny;
+
+            let mut args = Array::new();
+            args.push(hash.into_any());
+            !crate::contract::call(Self::hash(), ByteString::from_literal("getContract"), CallFlags::ReadStates, args).is_null()
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        crate::host::is_contract(hash)
+    }
+
     #[inline(always)]
     #[rustfmt::skip]
     #[allow(unused_variables)]
@@ -83,17 +100,22 @@ impl ContractManagement {
         Contract::default() // Mock implementation for non-WASM targets
     }
 
+    /// Replaces the code and manifest of the calling contract, keeping its hash and storage.
+    ///
+    /// The new code's `_deploy(data, true)` runs before this returns, in the same
+    /// transaction, so it can validate the migrated state and abort the update.
     #[inline(always)]
     #[rustfmt::skip]
     #[allow(unused_variables)]
-    pub fn update(nef: ByteString, manifest: ByteString) {
+    pub fn update(nef: ByteString, manifest: ByteString, data: Any) {
         #[cfg(target_family = "wasm")]
-        unsafe { env::contract::native_contract_management_update(nef, manifest) }
+        unsafe { env::contract::native_contract_management_update_with_data(nef, manifest, data) }
 
         #[cfg(not(target_family = "wasm"))]
         () // Mock implementation for non-WASM targets
     }
 
+    /// Removes the calling contract and its storage for good.
     #[inline(always)]
     #[rustfmt::skip]
     pub fn destroy() {
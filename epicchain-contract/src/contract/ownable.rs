@@ -0,0 +1,120 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+use crate::{
+    runtime::{self, access},
+    services::storage::Storage,
+    types::{
+        builtin::{
+            array::Array,
+            h160::H160,
+            string::{ByteString, FromByteString, IntoByteString},
+            any::IntoAny,
+        },
+        Any,
+    },
+};
+
+/// Default pending owner key.
+pub const PENDING_OWNER_KEY: &str = "pending_owner";
+
+/// Two-step handoff of the contract owner.
+///
+/// The current owner nominates a successor with `transfer_ownership`, and ownership only
+/// moves once the successor witnesses `accept_ownership`, so a mistyped address cannot
+/// lock the owner out. Nominating the zero hash cancels a pending handoff.
+///
+/// `#[contract_impl]` on `impl Ownable for MyContract {}` exports `transfer_ownership`,
+/// `accept_ownership`, `renounce_ownership` and `pending_owner`. Contracts storing their
+/// owner under another key override `OWNER_KEY`.
+// NOTE: epicchain-contract-proc-macros must be updated
+//if any method definition changed(add, remove, modify) in this trait
+pub trait Ownable {
+    /// Storage key holding the owner `H160`.
+    const OWNER_KEY: &'static str = access::OWNER_KEY;
+
+    /// Storage key holding the nominated owner `H160`.
+    const PENDING_OWNER_KEY: &'static str = PENDING_OWNER_KEY;
+
+    #[inline(always)]
+    fn owner() -> H160 {
+        read_account(Self::OWNER_KEY)
+    }
+
+    #[inline(always)]
+    fn pending_owner() -> H160 {
+        read_account(Self::PENDING_OWNER_KEY)
+    }
+
+    /// Returns true if the owner is set and has witnessed the current transaction.
+    #[inline(always)]
+    fn is_owner() -> bool {
+        let owner = Self::owner();
+        owner != H160::zero() && runtime::check_witness_with_account(owner)
+    }
+
+    fn transfer_ownership(new_owner: H160) -> bool {
+        if !Self::is_owner() {
+            runtime::log(ByteString::from_literal("Unauthorized: owner only"));
+            return false;
+        }
+        let owner = Self::owner();
+
+        let storage = Storage::get_context();
+        if new_owner == H160::zero() {
+            Storage::delete(storage, ByteString::from_literal(Self::PENDING_OWNER_KEY));
+        } else {
+            Storage::put(storage, ByteString::from_literal(Self::PENDING_OWNER_KEY), new_owner.into_byte_string());
+        }
+
+        notify_ownership("OwnershipTransferStarted", owner, new_owner);
+        true
+    }
+
+    fn accept_ownership() -> bool {
+        let pending = Self::pending_owner();
+        if pending == H160::zero() || !runtime::check_witness_with_account(pending) {
+            runtime::log(ByteString::from_literal("Unauthorized: pending owner only"));
+            return false;
+        }
+
+        let previous = Self::owner();
+        let storage = Storage::get_context();
+        Storage::put(storage.clone(), ByteString::from_literal(Self::OWNER_KEY), pending.into_byte_string());
+        Storage::delete(storage, ByteString::from_literal(Self::PENDING_OWNER_KEY));
+
+        notify_ownership("OwnershipTransferred", previous, pending);
+        true
+    }
+
+    /// Gives up ownership for good. The owner key keeps the zero hash rather than being
+    /// deleted, so deploy guards checking for an existing owner stay closed.
+    fn renounce_ownership() -> bool {
+        if !Self::is_owner() {
+            runtime::log(ByteString::from_literal("Unauthorized: owner only"));
+            return false;
+        }
+        let owner = Self::owner();
+
+        let storage = Storage::get_context();
+        Storage::put(storage.clone(), ByteString::from_literal(Self::OWNER_KEY), H160::zero().into_byte_string());
+        Storage::delete(storage, ByteString::from_literal(Self::PENDING_OWNER_KEY));
+
+        notify_ownership("OwnershipTransferred", owner, H160::zero());
+        true
+    }
+}
+
+fn read_account(key: &str) -> H160 {
+    match Storage::get(Storage::get_context(), ByteString::from_literal(key)) {
+        Some(account) => H160::from_byte_string(account),
+        None => H160::zero(),
+    }
+}
+
+fn notify_ownership(event: &str, previous: H160, new_owner: H160) {
+    let mut event_data = Array::<Any>::new();
+    event_data.push(previous.into_any());
+    event_data.push(new_owner.into_any());
+    runtime::notify(ByteString::from_literal(event), event_data);
+}
@@ -0,0 +1,76 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+use crate::{
+    contract::Ownable,
+    runtime::{self, ErrorCode},
+    services::storage::Storage,
+    types::{
+        builtin::{array::Array, string::ByteString},
+        Any,
+    },
+};
+
+/// Default paused flag key.
+pub const PAUSED_KEY: &str = "paused";
+
+/// Emergency stop for the contract's state-changing methods.
+///
+/// `#[contract_impl]` on `impl Pausable for MyContract {}` exports `pause`, `unpause` and
+/// `is_paused`; methods that must stop while paused start with `Self::when_not_paused()`.
+/// Only the owner may pause by default, override `can_pause` to widen that, e.g. to
+/// holders of [`PAUSER_ROLE`](crate::contract::PAUSER_ROLE).
+// NOTE: epicchain-contract-proc-macros must be updated
+//if any method definition changed(add, remove, modify) in this trait
+pub trait Pausable: Ownable {
+    /// Storage key holding the paused flag.
+    const PAUSED_KEY: &'static str = PAUSED_KEY;
+
+    /// Returns true if the caller may pause and unpause the contract.
+    #[inline(always)]
+    fn can_pause() -> bool {
+        Self::is_owner()
+    }
+
+    #[inline(always)]
+    fn is_paused() -> bool {
+        Storage::get(Storage::get_context(), ByteString::from_literal(Self::PAUSED_KEY)).is_some()
+    }
+
+    /// Aborts with [`ErrorCode::Paused`] while the contract is paused.
+    #[inline(always)]
+    fn when_not_paused() {
+        runtime::assert_with_code(!Self::is_paused(), ErrorCode::Paused, "Contract is paused");
+    }
+
+    fn pause() -> bool {
+        set_paused::<Self>(true)
+    }
+
+    fn unpause() -> bool {
+        set_paused::<Self>(false)
+    }
+}
+
+fn set_paused<T: Pausable + ?Sized>(paused: bool) -> bool {
+    if !T::can_pause() {
+        runtime::log(ByteString::from_literal("Unauthorized: pauser only"));
+        return false;
+    }
+
+    if T::is_paused() == paused {
+        runtime::log(ByteString::from_literal(if paused { "Already paused" } else { "Not paused" }));
+        return false;
+    }
+
+    let storage = Storage::get_context();
+    if paused {
+        Storage::put(storage, ByteString::from_literal(T::PAUSED_KEY), ByteString::from_literal("true"));
+    } else {
+        Storage::delete(storage, ByteString::from_literal(T::PAUSED_KEY));
+    }
+
+    let event = if paused { "Paused" } else { "Unpaused" };
+    runtime::notify(ByteString::from_literal(event), Array::<Any>::new());
+    true
+}
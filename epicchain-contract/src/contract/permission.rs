@@ -0,0 +1,42 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Support for `#[contract_permission]` checks.
+//!
+//! `#[contract_permission("*", "*")]` without a `reason` implements [`WildcardPermission`]
+//! for the contract struct. `#[contract_impl]` probes every contract that calls other
+//! contracts: the probe resolves to the deprecated [`WildcardCallScope::check_call_scope`]
+//! only when the struct carries the marker, which surfaces as a build warning.
+
+use core::marker::PhantomData;
+
+/// Marker for contracts declaring an unrestricted `*:*` permission without a reason
+pub trait WildcardPermission {}
+
+#[doc(hidden)]
+pub struct PermissionProbe<T>(PhantomData<T>);
+
+impl<T> PermissionProbe<T> {
+    #[doc(hidden)]
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait WildcardCallScope {
+    #[deprecated(
+        note = "this contract calls other contracts under `#[contract_permission(\"*\", \"*\")]`; \
+                scope the permission to the called contracts and methods, or add `reason = \"...\"`"
+    )]
+    fn check_call_scope(&self) {}
+}
+
+impl<T: WildcardPermission> WildcardCallScope for PermissionProbe<T> {}
+
+#[doc(hidden)]
+pub trait ScopedCallScope {
+    fn check_call_scope(&self) {}
+}
+
+impl<T> ScopedCallScope for &PermissionProbe<T> {}
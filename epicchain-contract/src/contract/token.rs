@@ -5,8 +5,7 @@ use crate::contract::{PREFIX_BALANCE, TOTAL_SUPPLY_KEY};
 
 #[allow(unused_imports)]
 use crate::{
-    env,
-    storage::StorageMap,
+    storage::{prefixed_key, StorageMap},
     types::{
         builtin::{
             h160::H160,
@@ -17,11 +16,7 @@ use crate::{
 };
 
 pub(crate) fn total_supply() -> Int256 {
-    #[cfg(target_family = "wasm")]
-    let key = unsafe { env::extension::concat_u8_byte_string(TOTAL_SUPPLY_KEY, ByteString::empty()) };
-
-    #[cfg(not(target_family = "wasm"))]
-    let key = ByteString::with_bytes(&[TOTAL_SUPPLY_KEY]);
+    let key = prefixed_key(TOTAL_SUPPLY_KEY, ByteString::empty());
 
     let storage = StorageMap::new();
     let value = storage.get(key.clone());
@@ -33,11 +28,7 @@ pub(crate) fn total_supply() -> Int256 {
 }
 
 pub(crate) fn balance_of(account: H160) -> Int256 {
-    #[cfg(target_family = "wasm")]
-    let key = unsafe { env::extension::concat_u8_byte_string(PREFIX_BALANCE, account.into_byte_string()) };
-
-    #[cfg(not(target_family = "wasm"))]
-    let key = ByteString::with_bytes(&[PREFIX_BALANCE]).concat(&account.into_byte_string());
+    let key = prefixed_key(PREFIX_BALANCE, account.into_byte_string());
 
     let storage = StorageMap::new();
     let value = storage.get(key.clone());
@@ -52,11 +43,7 @@ pub(crate) fn balance_of(account: H160) -> Int256 {
 // Otherwise, the compiled wasm ops cannot transfer to EpicChain ops.
 #[inline(always)]
 pub(crate) fn update_balance<const PREFIX: u8>(storage: &mut StorageMap, account: H160, amount: Int256) -> bool {
-    #[cfg(target_family = "wasm")]
-    let key = unsafe { env::extension::concat_u8_byte_string(PREFIX, account.into_byte_string()) };
-
-    #[cfg(not(target_family = "wasm"))]
-    let key = ByteString::with_bytes(&[PREFIX]).concat(&account.into_byte_string());
+    let key = prefixed_key(PREFIX, account.into_byte_string());
 
     let value = storage.get(key.clone());
     let balance = if value.is_null() {
@@ -2,8 +2,8 @@
 // All Rights Reserved.
 
 use crate::{
-    contract::token,
-    runtime,
+    contract::{native::ContractManagement, token},
+    runtime::{self, ErrorCode},
     storage::{Iter, StorageMap},
     types::{
         builtin::{
@@ -192,8 +192,10 @@ pub trait Nep11Token<T: TokenState + FromPlaceholder> {
         let mut event_data = Array::<Any>::new();
         event_data.push(from.into_any());
         event_data.push(to.into_any());
-        event_data.push(token_id.into_any());
+        event_data.push(token_id.clone().into_any());
         runtime::notify(ByteString::from_literal("Transfer"), event_data);
+
+        invoke_on_nep11_payment(from, to, Int256::one(), token_id, Any::default());
     }
 
     fn mint(token_id: ByteString, _token_state: T) {
@@ -278,8 +280,10 @@ pub trait Nep11Token<T: TokenState + FromPlaceholder> {
         let mut event_data = Array::<Any>::new();
         event_data.push(H160::zero().into_any());
         event_data.push(owner.into_any());
-        event_data.push(token_id.into_any());
+        event_data.push(token_id.clone().into_any());
         runtime::notify(ByteString::from_literal("Transfer"), event_data);
+
+        invoke_on_nep11_payment(H160::zero(), owner, Int256::one(), token_id, Any::default());
     }
 
     fn burn(token_id: ByteString) {
@@ -347,6 +351,30 @@ pub trait Nep11Token<T: TokenState + FromPlaceholder> {
     }
 }
 
+/// Calls `onNEP11Payment(from, amount, token_id, data)` on `to` if it is a deployed contract,
+/// as NEP-11 requires after the `Transfer` event. `from` is passed as null for mints (zero address).
+///
+/// A recipient that returns `false` rejects the token and aborts the transfer.
+pub fn invoke_on_nep11_payment(from: H160, to: H160, amount: Int256, token_id: ByteString, data: Any) {
+    if to == H160::zero() || !ContractManagement::is_contract(to) {
+        return;
+    }
+
+    let mut args = Array::<Any>::new();
+    args.push(if from == H160::zero() { Any::default() } else { from.into_any() });
+    args.push(amount.into_any());
+    args.push(token_id.into_any());
+    args.push(data);
+
+    let accepted = crate::services::contract::Contract::call_bool(
+        to,
+        ByteString::from_literal("onNEP11Payment"),
+        crate::types::CallFlags::All,
+        args,
+    );
+    runtime::assert_with_code(accepted, ErrorCode::CallFailed, "onNEP11Payment rejected");
+}
+
 pub fn update_nep11_balance(owner: H160, token_id: ByteString, increment: Int256) {
     let mut storage = StorageMap::new();
     let ok = token::update_balance::<PREFIX_ACCOUNT_TOKEN>(&mut storage, owner, increment);
@@ -2,8 +2,8 @@
 // All Rights Reserved.
 
 use crate::{
-    contract::token,
-    runtime,
+    contract::{native::ContractManagement, token},
+    runtime::{self, ErrorCode},
     storage::StorageMap,
     types::{
         builtin::{
@@ -45,7 +45,7 @@ pub trait Nep17Token {
         token::balance_of(owner)
     }
 
-    fn transfer(from: H160, to: H160, amount: Int256, _data: Array<Any>) -> bool {
+    fn transfer(from: H160, to: H160, amount: Int256, data: Array<Any>) -> bool {
         if amount.is_negative() {
             runtime::abort();
             return false;
@@ -69,6 +69,7 @@ pub trait Nep17Token {
             runtime::notify(ByteString::from_literal("Transfer"), event_data);
         }
 
+        invoke_on_nep17_payment(from, to, amount, data.into_any());
         return true;
     }
 
@@ -93,6 +94,8 @@ pub trait Nep17Token {
         event_data.push(account.into_any());
         event_data.push(amount.into_any());
         runtime::notify(ByteString::from_literal("Transfer"), event_data);
+
+        invoke_on_nep17_payment(H160::zero(), account, amount, Any::default());
     }
 
     fn burn(account: H160, amount: Int256) {
@@ -118,6 +121,29 @@ pub trait Nep17Token {
     }
 }
 
+/// Calls `onNEP17Payment(from, amount, data)` on `to` if it is a deployed contract, as NEP-17
+/// requires after the `Transfer` event. `from` is passed as null for mints (zero address).
+///
+/// A recipient that returns `false` rejects the payment and aborts the transfer.
+pub fn invoke_on_nep17_payment(from: H160, to: H160, amount: Int256, data: Any) {
+    if to == H160::zero() || !ContractManagement::is_contract(to) {
+        return;
+    }
+
+    let mut args = Array::<Any>::new();
+    args.push(if from == H160::zero() { Any::default() } else { from.into_any() });
+    args.push(amount.into_any());
+    args.push(data);
+
+    let accepted = crate::services::contract::Contract::call_bool(
+        to,
+        ByteString::from_literal("onNEP17Payment"),
+        crate::types::CallFlags::All,
+        args,
+    );
+    runtime::assert_with_code(accepted, ErrorCode::CallFailed, "onNEP17Payment rejected");
+}
+
 pub fn update_nep17_balance<const PREFIX: u8>(account: H160, amount: Int256) -> bool {
     let mut storage = StorageMap::new();
     token::update_balance::<PREFIX>(&mut storage, account, amount)
@@ -10,6 +10,19 @@ use crate::types::*;
 #[cfg(target_family = "wasm")]
 use crate::env;
 
+/// Computes the SHA-256 hash of `data`.
+#[inline(always)]
+pub fn sha256(data: ByteString) -> H256 {
+    #[cfg(target_family = "wasm")]
+    unsafe { env::crypto::sha256(crate::types::placeholder::IntoPlaceholder::into_placeholder(data)) }
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        use sha2::Digest;
+        H256::from_bytes(&sha2::Sha256::digest(data.as_bytes()))
+    }
+}
+
 #[inline(always)]
 pub fn check_sign(_public_key: PublicKey, _sign: ByteString) -> bool {
     #[cfg(target_family = "wasm")]
@@ -51,6 +64,8 @@ pub fn verify_ecdsa(
     }
 }
 
+/// Requires protocol 3.8, see [`crate::protocol`].
+#[cfg(feature = "protocol-3-8")]
 #[inline(always)]
 pub fn verify_ed25519(_message: ByteString, _public_key: PublicKey, _sign: ByteString) -> bool {
     #[cfg(target_family = "wasm")]
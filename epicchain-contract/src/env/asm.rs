@@ -76,6 +76,9 @@ extern "C" {
     /// `map_remove` removes a key from the map.
     pub(crate) fn map_remove(map: Placeholder, key: Placeholder) -> Placeholder;
 
+    /// `is_type` checks the stack item type of the given value (`ISTYPE`).
+    pub(crate) fn is_type(value: Placeholder, item_type: StackItemType) -> bool;
+
     /// `map_has_key` checks if the map contains the given key.
     pub(crate) fn map_has_key(map: Placeholder, key: Placeholder) -> bool;
 
@@ -154,11 +154,11 @@ extern "C" {
 
     pub(crate) fn native_contract_management_update(nef: ByteString, manifest: ByteString);
 
-    // pub(crate) fn native_contract_management_update_with_data(
-    //     nef: ByteString,
-    //     manifest: ByteString,
-    //     data: Any,
-    // );
+    pub(crate) fn native_contract_management_update_with_data(
+        nef: ByteString,
+        manifest: ByteString,
+        data: Any,
+    );
 
     pub(crate) fn native_contract_management_destroy();
 }
@@ -0,0 +1,516 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Host simulation backing the non-WASM implementations of `Runtime`, `Storage` and the
+//! native `Ledger` bindings.
+//!
+//! On native targets there is no VM to answer syscalls, so the framework keeps an emulated
+//! execution environment here: a clock and block height, the script hashes of the current
+//! invocation, the accounts that witnessed the transaction, contract storage, the contracts
+//! reachable through `Contract::call`, and the logs and notifications emitted so far. Tests and fuzzers drive it through the functions in
+//! this module; contract code keeps calling the regular services.
+//!
+//! As on chain, storage belongs to the executing contract: every key is scoped by the executing
+//! script hash, so several deployments of the same contract can run side by side.
+//!
+//! The state is thread-local, so every test (which runs on its own thread) starts from a
+//! fresh environment. Call [`reset`] to start over within a thread.
+//!
+//! ```ignore
+//! host::set_witnesses(&[owner]);
+//! host::advance_blocks(10);
+//! contract.claim(owner);
+//! assert_eq!(host::event_names(), vec![ByteString::from_literal("Claimed")]);
+//! ```
+//!
+//! Every storage write and delete is also accounted per contract, so tests can attribute
+//! storage fees to the calls that cause them with [`measure_storage`]:
+//!
+//! ```ignore
+//! let (_, usage) = host::measure_storage(|| contract.claim(owner));
+//! assert!(usage.bytes_written <= 64);
+//! ```
+//!
+//! [`scenario`] builds on these functions to script whole integration flows in plain text.
+
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use crate::types::{Any, Array, ByteString, CallFlags, H160, H256, Int256, TriggerType};
+
+pub mod scenario;
+
+/// Timestamp of the emulated clock on a fresh host: 2022-01-01 00:00:00 UTC, in seconds.
+pub const DEFAULT_TIME: u64 = 1_640_995_200;
+
+/// Seconds the clock advances per block in [`advance_blocks`].
+pub const BLOCK_TIME_SECS: u64 = 15;
+
+/// Network magic reported on a fresh host.
+pub const DEFAULT_NETWORK: u32 = 860833102;
+
+/// A notification captured by the host.
+pub struct EmittedNotification {
+    pub script_hash: H160,
+    pub event_name: ByteString,
+    pub state: Array<Any>,
+}
+
+/// Storage activity of a contract, or of several contracts summed up.
+///
+/// Sizes count key and value bytes, like the storage fee of the VM.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StorageUsage {
+    /// Number of writes, including overwrites of existing keys.
+    pub puts: u64,
+    /// Number of deletes of existing keys.
+    pub deletes: u64,
+    /// Key and value bytes of every write.
+    pub bytes_written: u64,
+    /// Key and value bytes of the deleted entries.
+    pub bytes_deleted: u64,
+    /// Change in the total size of stored entries.
+    pub net_bytes: i64,
+}
+
+impl StorageUsage {
+    fn add(&mut self, other: &StorageUsage) {
+        self.puts += other.puts;
+        self.deletes += other.deletes;
+        self.bytes_written += other.bytes_written;
+        self.bytes_deleted += other.bytes_deleted;
+        self.net_bytes += other.net_bytes;
+    }
+
+    /// Activity recorded since `earlier`, a previous reading of the same counters.
+    pub fn since(&self, earlier: &StorageUsage) -> StorageUsage {
+        StorageUsage {
+            puts: self.puts - earlier.puts,
+            deletes: self.deletes - earlier.deletes,
+            bytes_written: self.bytes_written - earlier.bytes_written,
+            bytes_deleted: self.bytes_deleted - earlier.bytes_deleted,
+            net_bytes: self.net_bytes - earlier.net_bytes,
+        }
+    }
+}
+
+/// A call made through `Contract::call`.
+pub struct ContractCall {
+    pub contract: H160,
+    pub method: ByteString,
+    pub call_flags: CallFlags,
+}
+
+type ContractHandler = Rc<dyn Fn(&ByteString, Array<Any>) -> Any>;
+
+struct HostState {
+    time: u64,
+    block_index: u32,
+    block_hash: H256,
+    trigger: TriggerType,
+    network: u32,
+    executing_script_hash: H160,
+    calling_script_hash: H160,
+    entry_script_hash: H160,
+    invocation_counter: u32,
+    // `None` accepts every witness check, matching the behaviour before witnesses are set
+    witnesses: Option<Vec<H160>>,
+    epicpulse_left: Int256,
+    random_state: u64,
+    storage: BTreeMap<Vec<u8>, Vec<u8>>,
+    storage_usage: Vec<(H160, StorageUsage)>,
+    // Whether the current invocation already fetched its storage context, and how often one was fetched
+    storage_context_cached: bool,
+    storage_context_fetches: u32,
+    contracts: Vec<(H160, ContractHandler)>,
+    contract_calls: Vec<ContractCall>,
+    logs: Vec<ByteString>,
+    notifications: Vec<EmittedNotification>,
+}
+
+impl HostState {
+    fn new() -> Self {
+        Self {
+            time: DEFAULT_TIME,
+            block_index: 0,
+            block_hash: H256::zero(),
+            trigger: TriggerType::Application,
+            network: DEFAULT_NETWORK,
+            executing_script_hash: H160::zero(),
+            calling_script_hash: H160::zero(),
+            entry_script_hash: H160::zero(),
+            invocation_counter: 1,
+            witnesses: None,
+            epicpulse_left: Int256::new(1_000_000_000),
+            random_state: 42,
+            storage: BTreeMap::new(),
+            storage_usage: Vec::new(),
+            storage_context_cached: false,
+            storage_context_fetches: 0,
+            contracts: Vec::new(),
+            contract_calls: Vec::new(),
+            logs: Vec::new(),
+            notifications: Vec::new(),
+        }
+    }
+}
+
+thread_local! {
+    static HOST: RefCell<HostState> = RefCell::new(HostState::new());
+}
+
+fn with<R>(f: impl FnOnce(&mut HostState) -> R) -> R {
+    HOST.with(|host| f(&mut host.borrow_mut()))
+}
+
+/// Restores the default environment and clears storage, logs and notifications.
+pub fn reset() {
+    with(|host| *host = HostState::new());
+}
+
+// Clock and block context
+
+/// Current block timestamp, in seconds.
+pub fn time() -> u64 {
+    with(|host| host.time)
+}
+
+pub fn set_time(time: u64) {
+    with(|host| host.time = time);
+}
+
+/// Moves the clock forward without producing blocks.
+pub fn advance_time(seconds: u64) {
+    with(|host| host.time += seconds);
+}
+
+/// Index of the current block.
+pub fn block_index() -> u32 {
+    with(|host| host.block_index)
+}
+
+pub fn set_block_index(index: u32) {
+    with(|host| host.block_index = index);
+}
+
+/// Hash of the current block.
+pub fn block_hash() -> H256 {
+    with(|host| host.block_hash)
+}
+
+pub fn set_block_hash(hash: H256) {
+    with(|host| host.block_hash = hash);
+}
+
+/// Produces `count` blocks, advancing the clock by [`BLOCK_TIME_SECS`] per block.
+pub fn advance_blocks(count: u32) {
+    with(|host| {
+        host.block_index += count;
+        host.time += count as u64 * BLOCK_TIME_SECS;
+    });
+}
+
+pub fn trigger() -> TriggerType {
+    with(|host| host.trigger)
+}
+
+pub fn set_trigger(trigger: TriggerType) {
+    with(|host| host.trigger = trigger);
+}
+
+pub fn network() -> u32 {
+    with(|host| host.network)
+}
+
+pub fn set_network(network: u32) {
+    with(|host| host.network = network);
+}
+
+// Invocation context
+
+pub fn executing_script_hash() -> H160 {
+    with(|host| host.executing_script_hash)
+}
+
+pub fn set_executing_script_hash(hash: H160) {
+    with(|host| host.executing_script_hash = hash);
+}
+
+pub fn calling_script_hash() -> H160 {
+    with(|host| host.calling_script_hash)
+}
+
+pub fn set_calling_script_hash(hash: H160) {
+    with(|host| host.calling_script_hash = hash);
+}
+
+pub fn entry_script_hash() -> H160 {
+    with(|host| host.entry_script_hash)
+}
+
+pub fn set_entry_script_hash(hash: H160) {
+    with(|host| host.entry_script_hash = hash);
+}
+
+pub fn invocation_counter() -> u32 {
+    with(|host| host.invocation_counter)
+}
+
+pub fn set_invocation_counter(counter: u32) {
+    with(|host| host.invocation_counter = counter);
+}
+
+// Witnesses
+
+/// Restricts witness checks to `accounts`.
+pub fn set_witnesses(accounts: &[H160]) {
+    with(|host| host.witnesses = Some(accounts.to_vec()));
+}
+
+/// Makes every witness check succeed again (the default).
+pub fn allow_all_witnesses() {
+    with(|host| host.witnesses = None);
+}
+
+/// Returns true if `account` witnessed the emulated transaction.
+pub fn has_witness(account: H160) -> bool {
+    with(|host| match &host.witnesses {
+        Some(witnesses) => witnesses.contains(&account),
+        None => true,
+    })
+}
+
+// Fees and randomness
+
+pub fn epicpulse_left() -> Int256 {
+    with(|host| host.epicpulse_left)
+}
+
+pub fn set_epicpulse_left(amount: Int256) {
+    with(|host| host.epicpulse_left = amount);
+}
+
+pub(crate) fn burn_epicpulse(amount: Int256) {
+    with(|host| host.epicpulse_left = host.epicpulse_left.checked_sub(&amount));
+}
+
+/// Seeds the deterministic generator behind `Runtime::get_random`.
+pub fn set_random_seed(seed: u64) {
+    with(|host| host.random_state = seed);
+}
+
+/// Next value of a splitmix64 sequence, so fuzz runs are reproducible from their seed.
+pub(crate) fn next_random() -> Int256 {
+    with(|host| {
+        host.random_state = host.random_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = host.random_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        Int256::from_u64(z ^ (z >> 31))
+    })
+}
+
+// Storage
+
+/// `key` within the storage of the executing contract
+fn scoped_key(host: &HostState, key: &[u8]) -> Vec<u8> {
+    let mut scoped = host.executing_script_hash.to_bytes();
+    scoped.extend_from_slice(key);
+    scoped
+}
+
+pub fn storage_get(key: &[u8]) -> Option<Vec<u8>> {
+    with(|host| host.storage.get(&scoped_key(host, key)).cloned())
+}
+
+pub fn storage_put(key: &[u8], value: &[u8]) {
+    with(|host| {
+        let previous = host.storage.insert(scoped_key(host, key), value.to_vec());
+        let written = (key.len() + value.len()) as i64;
+        let replaced = previous.map_or(0, |old| (key.len() + old.len()) as i64);
+        let usage = usage_of(host);
+        usage.puts += 1;
+        usage.bytes_written += written as u64;
+        usage.net_bytes += written - replaced;
+    });
+}
+
+pub fn storage_delete(key: &[u8]) {
+    with(|host| {
+        if let Some(old) = host.storage.remove(&scoped_key(host, key)) {
+            let deleted = (key.len() + old.len()) as u64;
+            let usage = usage_of(host);
+            usage.deletes += 1;
+            usage.bytes_deleted += deleted;
+            usage.net_bytes -= deleted as i64;
+        }
+    });
+}
+
+/// Usage counters of the executing contract
+fn usage_of(host: &mut HostState) -> &mut StorageUsage {
+    let contract = host.executing_script_hash;
+    let index = match host.storage_usage.iter().position(|(hash, _)| *hash == contract) {
+        Some(index) => index,
+        None => {
+            host.storage_usage.push((contract, StorageUsage::default()));
+            host.storage_usage.len() - 1
+        }
+    };
+    &mut host.storage_usage[index].1
+}
+
+/// Storage activity of `contract` since the host was created or [`reset_storage_usage`].
+pub fn storage_usage(contract: H160) -> StorageUsage {
+    with(|host| {
+        host.storage_usage
+            .iter()
+            .find(|(hash, _)| *hash == contract)
+            .map(|(_, usage)| *usage)
+            .unwrap_or_default()
+    })
+}
+
+/// Storage activity of all contracts since the host was created or [`reset_storage_usage`].
+pub fn total_storage_usage() -> StorageUsage {
+    with(|host| {
+        let mut total = StorageUsage::default();
+        for (_, usage) in &host.storage_usage {
+            total.add(usage);
+        }
+        total
+    })
+}
+
+/// Clears the storage counters without touching storage.
+pub fn reset_storage_usage() {
+    with(|host| host.storage_usage.clear());
+}
+
+/// Runs `f` and returns its result with the storage activity it caused across all contracts,
+/// including contracts it called.
+pub fn measure_storage<R>(f: impl FnOnce() -> R) -> (R, StorageUsage) {
+    let before = total_storage_usage();
+    let result = f();
+    (result, total_storage_usage().since(&before))
+}
+
+/// Entries of the executing contract whose key starts with `prefix`, in ascending key order.
+pub fn storage_entries_with_prefix(prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+    with(|host| {
+        let scoped_prefix = scoped_key(host, prefix);
+        host.storage
+            .range(scoped_prefix.clone()..)
+            .take_while(|(key, _)| key.starts_with(&scoped_prefix))
+            .map(|(key, value)| (key[H160::SIZE..].to_vec(), value.clone()))
+            .collect()
+    })
+}
+
+/// Number of `System.Storage.GetContext` syscalls made so far. `Storage::get_context` only
+/// fetches the context once per invocation, whatever the number of methods involved.
+pub fn storage_context_fetches() -> u32 {
+    with(|host| host.storage_context_fetches)
+}
+
+pub(crate) fn fetch_storage_context() {
+    with(|host| {
+        if !host.storage_context_cached {
+            host.storage_context_cached = true;
+            host.storage_context_fetches += 1;
+        }
+    });
+}
+
+pub(crate) fn forget_storage_context() {
+    with(|host| host.storage_context_cached = false);
+}
+
+/// Number of entries in contract storage, across all contracts.
+pub fn storage_len() -> usize {
+    with(|host| host.storage.len())
+}
+
+pub fn clear_storage() {
+    with(|host| host.storage.clear());
+}
+
+// Contract calls
+
+/// Makes `handler` answer calls to `hash`. It receives the method name and the arguments and
+/// runs with `hash` as the executing script hash. Registering a hash again replaces the handler.
+pub fn register_contract(hash: H160, handler: impl Fn(&ByteString, Array<Any>) -> Any + 'static) {
+    with(|host| {
+        host.contracts.retain(|(registered, _)| *registered != hash);
+        host.contracts.push((hash, Rc::new(handler)));
+    });
+}
+
+/// Returns true if a handler is registered for `hash`, i.e. it is a deployed contract.
+pub fn is_contract(hash: H160) -> bool {
+    with(|host| host.contracts.iter().any(|(registered, _)| *registered == hash))
+}
+
+/// Calls made through `Contract::call` so far, in order.
+pub fn contract_calls() -> Vec<ContractCall> {
+    with(|host| {
+        host.contract_calls
+            .iter()
+            .map(|call| ContractCall { contract: call.contract, method: call.method.clone(), call_flags: call.call_flags })
+            .collect()
+    })
+}
+
+/// Dispatches a call to the registered handler. Unknown contracts return null.
+pub(crate) fn call_contract(contract: H160, method: ByteString, call_flags: CallFlags, args: Array<Any>) -> Any {
+    let (handler, caller) = with(|host| {
+        host.contract_calls.push(ContractCall { contract, method: method.clone(), call_flags });
+        let handler = host.contracts.iter().find(|(hash, _)| *hash == contract).map(|(_, handler)| handler.clone());
+        (handler, host.executing_script_hash)
+    });
+
+    let Some(handler) = handler else {
+        return Any::default();
+    };
+
+    // The handler may call back into the host, so no borrow is held while it runs
+    let previous_caller = calling_script_hash();
+    with(|host| {
+        host.calling_script_hash = caller;
+        host.executing_script_hash = contract;
+        host.invocation_counter += 1;
+    });
+    let result = handler(&method, args);
+    with(|host| {
+        host.executing_script_hash = caller;
+        host.calling_script_hash = previous_caller;
+    });
+    result
+}
+
+// Logs and notifications
+
+pub(crate) fn push_log(message: ByteString) {
+    with(|host| host.logs.push(message));
+}
+
+pub(crate) fn push_notification(event_name: ByteString, state: Array<Any>) {
+    with(|host| {
+        let script_hash = host.executing_script_hash;
+        host.notifications.push(EmittedNotification { script_hash, event_name, state });
+    });
+}
+
+/// Messages written with `Runtime::log` so far.
+pub fn logs() -> Vec<ByteString> {
+    with(|host| host.logs.clone())
+}
+
+/// Names of the events emitted so far, in order.
+pub fn event_names() -> Vec<ByteString> {
+    with(|host| host.notifications.iter().map(|n| n.event_name.clone()).collect())
+}
+
+/// Removes and returns the notifications emitted so far.
+pub fn take_notifications() -> Vec<EmittedNotification> {
+    with(|host| core::mem::take(&mut host.notifications))
+}
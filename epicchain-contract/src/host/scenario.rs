@@ -0,0 +1,472 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Scenario scripts for integration tests on the emulated host.
+//!
+//! A scenario names the accounts taking part, binds verbs to contract calls and then runs a
+//! plain-text script, one step per line:
+//!
+//! ```ignore
+//! let token = Token::init();
+//! let mut scenario = Scenario::new()
+//!     .actor("alice", alice)
+//!     .actor("bob", bob)
+//!     .action("mints", move |actor, args| { token.mint(actor, args.int(0)); })
+//!     .action("transfers", move |actor, args| { token.transfer(actor, args.account(1), args.int(0), Any::default()); })
+//!     .query("balance", move |args| token.balance_of(args.account(0)).into());
+//!
+//! scenario.run("
+//!     alice mints 100
+//!     expect event Transfer(null, alice, 100)
+//!     advance 1 day
+//!     alice transfers 40 to bob
+//!     expect event Transfer(alice, bob, 40)
+//!     expect balance bob 40
+//! ");
+//! ```
+//!
+//! Steps:
+//! - `<actor> <verb> <args...>` runs the action bound to `verb` with only `actor` witnessing;
+//!   every witness is accepted again afterwards. The words `to`, `from`, `of`, `for` and `by`
+//!   are skipped so steps read as sentences.
+//! - `advance <n> <unit>` moves the clock by seconds, minutes, hours, days or weeks, or the
+//!   chain by blocks.
+//! - `expect event <Name>(<args>)` consumes the first matching event emitted by the last
+//!   action; `expect event <Name>` matches any arguments and `_` matches any single argument.
+//! - `expect no event <Name>` checks the last action emitted no such event.
+//! - `expect log "<text>"` checks the last action wrote a log message containing `text`.
+//! - `expect <query> <args...> <value>` compares a registered query with its expected value.
+//!
+//! Arguments are actor names, integers (underscores allowed), `"quoted"` byte strings,
+//! `true`/`false` and `null`, which also matches the zero address. Blank lines and lines
+//! starting with `#` are ignored. A failing step panics with its line number and text.
+
+use std::fmt;
+
+use super::{advance_blocks, advance_time, allow_all_witnesses, logs, set_witnesses, take_notifications};
+use crate::types::{Any, ByteString, H160, Int256};
+
+/// Connective words dropped from action arguments
+const FILLER_WORDS: [&str; 5] = ["to", "from", "of", "for", "by"];
+
+/// A step argument, or a value read back from an event or a query.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Value {
+    Account(H160),
+    Int(Int256),
+    Bytes(ByteString),
+    Bool(bool),
+    Null,
+    /// `_` in an expectation, matching anything
+    Wildcard,
+    /// A captured value of a type scenarios cannot compare
+    Other,
+}
+
+impl Value {
+    /// Whether this expected value accepts `actual`.
+    pub fn matches(&self, actual: &Value) -> bool {
+        match (self, actual) {
+            (Value::Wildcard, _) => true,
+            (Value::Null, Value::Account(account)) => *account == H160::zero(),
+            (Value::Int(expected), Value::Bool(actual)) => *expected == Int256::new(*actual as i64),
+            (expected, actual) => expected == actual,
+        }
+    }
+
+    fn from_any(value: Any) -> Value {
+        if value.is_null() {
+            Value::Null
+        } else if value.is::<H160>() {
+            Value::Account(value.downcast_into())
+        } else if value.is::<Int256>() {
+            Value::Int(value.downcast_into())
+        } else if value.is::<ByteString>() {
+            Value::Bytes(value.downcast_into())
+        } else if value.is::<bool>() {
+            Value::Bool(value.downcast_into())
+        } else {
+            Value::Other
+        }
+    }
+}
+
+impl From<H160> for Value {
+    fn from(value: H160) -> Self {
+        Value::Account(value)
+    }
+}
+
+impl From<Int256> for Value {
+    fn from(value: Int256) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<ByteString> for Value {
+    fn from(value: ByteString) -> Self {
+        Value::Bytes(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Account(account) => write!(f, "{:?}", account),
+            Value::Int(value) => write!(f, "{:?}", value),
+            Value::Bytes(bytes) => write!(f, "\"{}\"", String::from_utf8_lossy(&bytes.to_bytes())),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Null => write!(f, "null"),
+            Value::Wildcard => write!(f, "_"),
+            Value::Other => write!(f, "<other>"),
+        }
+    }
+}
+
+/// The arguments of a step, with accessors that fail the step on a type mismatch.
+pub struct Args {
+    step: String,
+    values: Vec<Value>,
+}
+
+impl Args {
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> &Value {
+        self.values
+            .get(index)
+            .unwrap_or_else(|| panic!("`{}`: missing argument {}", self.step, index))
+    }
+
+    pub fn account(&self, index: usize) -> H160 {
+        match self.get(index) {
+            Value::Account(account) => *account,
+            Value::Null => H160::zero(),
+            other => panic!("`{}`: argument {} is {:?}, not an account", self.step, index, other),
+        }
+    }
+
+    pub fn int(&self, index: usize) -> Int256 {
+        match self.get(index) {
+            Value::Int(value) => *value,
+            other => panic!("`{}`: argument {} is {:?}, not an integer", self.step, index, other),
+        }
+    }
+
+    pub fn bytes(&self, index: usize) -> ByteString {
+        match self.get(index) {
+            Value::Bytes(bytes) => bytes.clone(),
+            other => panic!("`{}`: argument {} is {:?}, not a string", self.step, index, other),
+        }
+    }
+
+    pub fn bool(&self, index: usize) -> bool {
+        match self.get(index) {
+            Value::Bool(value) => *value,
+            other => panic!("`{}`: argument {} is {:?}, not a boolean", self.step, index, other),
+        }
+    }
+}
+
+/// An event captured from the host, with its arguments decoded.
+pub struct CapturedEvent {
+    pub name: String,
+    pub args: Vec<Value>,
+}
+
+impl fmt::Debug for CapturedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{:?}", self.name, self.args)
+    }
+}
+
+type Action = Box<dyn Fn(H160, &Args)>;
+type Query = Box<dyn Fn(&Args) -> Value>;
+
+/// Actors, verbs and queries shared by the scripts of a test suite.
+#[derive(Default)]
+pub struct Scenario {
+    actors: Vec<(String, H160)>,
+    actions: Vec<(String, Action)>,
+    queries: Vec<(String, Query)>,
+    events: Vec<CapturedEvent>,
+    /// Number of logs written before the last action started
+    log_mark: usize,
+}
+
+impl Scenario {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Names an account so steps can refer to it.
+    pub fn actor(mut self, name: &str, account: H160) -> Self {
+        self.actors.push((name.to_string(), account));
+        self
+    }
+
+    /// Binds `verb` to a call made by the acting account with the step's arguments.
+    pub fn action(mut self, verb: &str, action: impl Fn(H160, &Args) + 'static) -> Self {
+        self.actions.push((verb.to_string(), Box::new(action)));
+        self
+    }
+
+    /// Binds `name` to a read whose result `expect <name> <args...> <value>` checks.
+    pub fn query(mut self, name: &str, query: impl Fn(&Args) -> Value + 'static) -> Self {
+        self.queries.push((name.to_string(), Box::new(query)));
+        self
+    }
+
+    /// Account of the actor called `name`.
+    pub fn account(&self, name: &str) -> H160 {
+        self.find_actor(name).unwrap_or_else(|| panic!("unknown actor `{}`", name))
+    }
+
+    /// Events emitted by the last action that no expectation has consumed yet.
+    pub fn pending_events(&mut self) -> &[CapturedEvent] {
+        self.capture_events();
+        &self.events
+    }
+
+    /// Runs every step of `script` in order.
+    pub fn run(&mut self, script: &str) {
+        for (index, line) in script.lines().enumerate() {
+            let step = line.trim();
+            if step.is_empty() || step.starts_with('#') {
+                continue;
+            }
+            if let Err(message) = self.step(step) {
+                panic!("scenario line {} `{}`: {}", index + 1, step, message);
+            }
+        }
+    }
+
+    fn step(&mut self, step: &str) -> Result<(), String> {
+        let words = split_words(step)?;
+        match words[0].as_str() {
+            "advance" => self.advance(&words[1..]),
+            "expect" => self.expect(step, &words[1..]),
+            _ => self.act(step, &words),
+        }
+    }
+
+    fn act(&mut self, step: &str, words: &[String]) -> Result<(), String> {
+        let actor = self.find_actor(&words[0]).ok_or_else(|| format!("unknown actor or step `{}`", words[0]))?;
+        let verb = words.get(1).ok_or("missing verb")?;
+        let index = self
+            .actions
+            .iter()
+            .position(|(name, _)| name == verb)
+            .ok_or_else(|| format!("no action bound to `{}`", verb))?;
+        let args = self.args(step, words[2..].iter().filter(|word| !FILLER_WORDS.contains(&word.as_str())))?;
+
+        // Expectations refer to what this action emits
+        self.capture_events();
+        self.events.clear();
+        self.log_mark = logs().len();
+
+        set_witnesses(&[actor]);
+        (self.actions[index].1)(actor, &args);
+        allow_all_witnesses();
+        Ok(())
+    }
+
+    fn advance(&mut self, words: &[String]) -> Result<(), String> {
+        let (amount, unit) = match words {
+            [amount, unit] => (amount, unit.trim_end_matches('s')),
+            _ => return Err("expected `advance <n> <unit>`".to_string()),
+        };
+        let amount: u64 = amount.replace('_', "").parse().map_err(|_| format!("invalid amount `{}`", amount))?;
+
+        let seconds = match unit {
+            "block" => {
+                advance_blocks(amount as u32);
+                return Ok(());
+            }
+            "second" => 1,
+            "minute" => 60,
+            "hour" => 3_600,
+            "day" => 86_400,
+            "week" => 604_800,
+            _ => return Err(format!("unknown unit `{}`", unit)),
+        };
+        advance_time(amount * seconds);
+        Ok(())
+    }
+
+    fn expect(&mut self, step: &str, words: &[String]) -> Result<(), String> {
+        match words.first().map(String::as_str) {
+            Some("event") => self.expect_event(step, words.get(1).ok_or("missing event")?),
+            Some("no") if words.get(1).map(String::as_str) == Some("event") => {
+                let name = words.get(2).ok_or("missing event")?;
+                self.capture_events();
+                match self.events.iter().find(|event| event.name == *name) {
+                    Some(event) => Err(format!("unexpected event {:?}", event)),
+                    None => Ok(()),
+                }
+            }
+            Some("log") => {
+                let text = match self.args(step, words[1..].iter())?.values.as_slice() {
+                    [Value::Bytes(text)] => String::from_utf8_lossy(&text.to_bytes()).into_owned(),
+                    _ => return Err("expected `expect log \"<text>\"`".to_string()),
+                };
+                let written: Vec<String> = logs()
+                    .iter()
+                    .skip(self.log_mark)
+                    .map(|log| String::from_utf8_lossy(&log.to_bytes()).into_owned())
+                    .collect();
+                if written.iter().any(|log| log.contains(&text)) {
+                    Ok(())
+                } else {
+                    Err(format!("no log of the last action contains \"{}\", logs: {:?}", text, written))
+                }
+            }
+            Some(name) => {
+                let index = self
+                    .queries
+                    .iter()
+                    .position(|(query, _)| query == name)
+                    .ok_or_else(|| format!("no query bound to `{}`", name))?;
+                let mut args = self.args(step, words[1..].iter().filter(|word| !FILLER_WORDS.contains(&word.as_str())))?;
+                let expected = args.values.pop().ok_or("missing expected value")?;
+                let actual = (self.queries[index].1)(&args);
+                if expected.matches(&actual) {
+                    Ok(())
+                } else {
+                    Err(format!("expected {:?}, got {:?}", expected, actual))
+                }
+            }
+            None => Err("missing expectation".to_string()),
+        }
+    }
+
+    fn expect_event(&mut self, step: &str, pattern: &str) -> Result<(), String> {
+        let (name, expected) = match pattern.find('(') {
+            Some(open) => {
+                let inner = pattern[open + 1..].strip_suffix(')').ok_or("unclosed event arguments")?;
+                let words = inner.split(',').map(|word| word.trim().to_string()).filter(|word| !word.is_empty());
+                (&pattern[..open], Some(self.args(step, words.collect::<Vec<_>>().iter())?.values))
+            }
+            None => (pattern, None),
+        };
+
+        self.capture_events();
+        let position = self.events.iter().position(|event| {
+            event.name == name
+                && match &expected {
+                    Some(expected) => {
+                        expected.len() == event.args.len() && expected.iter().zip(&event.args).all(|(e, a)| e.matches(a))
+                    }
+                    None => true,
+                }
+        });
+        match position {
+            Some(position) => {
+                self.events.remove(position);
+                Ok(())
+            }
+            None => Err(format!("no matching event, pending events: {:?}", self.events)),
+        }
+    }
+
+    fn args<'a>(&self, step: &str, words: impl Iterator<Item = &'a String>) -> Result<Args, String> {
+        let values = words.map(|word| self.parse_value(word)).collect::<Result<Vec<_>, _>>()?;
+        Ok(Args { step: step.to_string(), values })
+    }
+
+    fn parse_value(&self, word: &str) -> Result<Value, String> {
+        if let Some(text) = word.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+            return Ok(Value::Bytes(ByteString::from_literal(text)));
+        }
+        match word {
+            "_" => return Ok(Value::Wildcard),
+            "null" => return Ok(Value::Null),
+            "true" => return Ok(Value::Bool(true)),
+            "false" => return Ok(Value::Bool(false)),
+            _ => {}
+        }
+        if let Some(account) = self.find_actor(word) {
+            return Ok(Value::Account(account));
+        }
+        parse_int(word).map(Value::Int).ok_or_else(|| format!("unknown actor or value `{}`", word))
+    }
+
+    fn find_actor(&self, name: &str) -> Option<H160> {
+        self.actors.iter().find(|(actor, _)| actor == name).map(|(_, account)| *account)
+    }
+
+    fn capture_events(&mut self) {
+        for notification in take_notifications() {
+            let mut state = notification.state;
+            let mut args = Vec::with_capacity(state.size());
+            while state.size() > 0 {
+                args.push(Value::from_any(state.pop()));
+            }
+            args.reverse();
+
+            let name = String::from_utf8_lossy(&notification.event_name.to_bytes()).into_owned();
+            self.events.push(CapturedEvent { name, args });
+        }
+    }
+}
+
+/// Splits a step into words, keeping quoted strings and parenthesised event arguments whole.
+fn split_words(step: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let (mut quoted, mut depth) = (false, 0);
+
+    for c in step.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            c if c.is_whitespace() && !quoted && depth == 0 => {
+                if !word.is_empty() {
+                    words.push(core::mem::take(&mut word));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        word.push(c);
+    }
+
+    if quoted || depth != 0 {
+        return Err("unbalanced quotes or parentheses".to_string());
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Decimal integer of any size, with an optional sign and `_` separators.
+fn parse_int(word: &str) -> Option<Int256> {
+    let (negative, digits) = match word.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, word),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit() || c == '_') || digits.starts_with('_') {
+        return None;
+    }
+
+    let ten = Int256::new(10);
+    let value = digits
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .fold(Int256::zero(), |value, digit| value.checked_mul(&ten).checked_add(&Int256::new(digit as i64)));
+    Some(if negative { value.checked_neg() } else { value })
+}
@@ -5,8 +5,12 @@ pub mod contract;
 pub mod crypto;
 pub mod env;
 pub mod event;
+#[cfg(not(target_family = "wasm"))]
+pub mod host;
 #[macro_use]
 pub mod macros;
+pub mod math;
+pub mod protocol;
 pub mod runtime;
 pub mod serialize;
 pub mod services;
@@ -21,6 +25,7 @@ pub use epicchain_contract_proc_macros::{contract, structs};
 pub use epicchain_contract_proc_macros::{
     method,
     safe as safe_attr,
+    deprecated_method,
     contract_author,
     contract_permission,
     contract_standards,
@@ -35,22 +40,41 @@ pub mod prelude {
     pub use crate::contract::{call, create_multi_signs_account, create_standard_account, get_call_flags};
     pub use crate::contract::nep17::{Nep17Token, PREFIX_BALANCE, TOTAL_SUPPLY_KEY};
     pub use crate::contract::nep11::{Nep11Token, TokenState};
+    pub use crate::contract::access_control::{AccessControl, MINTER_ROLE, OPERATOR_ROLE, PAUSER_ROLE};
+    pub use crate::contract::ownable::Ownable;
+    pub use crate::contract::pausable::Pausable;
 
     // Crypto module
-    pub use crate::crypto::{check_multi_signs, check_sign};
+    pub use crate::crypto::{check_multi_signs, check_sign, sha256};
 
     // Native contracts
     pub use crate::native::{gas, neo};
 
     // Runtime services
     pub use crate::runtime::{
-        abort, abort_with_message, burn_epicpulse, get_calling_script_hash,
-        check_witness_with_account, check_witness_with_public_key, current_signers,
+        abort, abort_with_code, abort_with_message, assert_with_code, bounded_iter, burn_epicpulse, get_calling_script_hash,
+        check_witness_with_account, check_witness_with_public_key,
         get_entry_script_hash, get_executing_script_hash,
         get_epicpulse_left, get_address_version, get_invocation_counter, get_network,
         get_notifications, get_platform, get_random, get_time, get_trigger, load_script, log, notify,
-        get_tx as tx,
+        get_tx as tx, ErrorCode,
+        notify_checked, EventPayload,
     };
+    #[cfg(feature = "protocol-3-6")]
+    pub use crate::runtime::current_signers;
+    pub use crate::runtime::time::{
+        days, days_since_epoch, deadline, elapsed, hours, is_expired, is_expired_at, period_index,
+        period_index_at, period_start,
+        SECONDS_PER_DAY, SECONDS_PER_HOUR, SECONDS_PER_MINUTE, SECONDS_PER_WEEK, SECONDS_PER_YEAR,
+    };
+    pub use crate::runtime::heartbeat::DeadManSwitch;
+    pub use crate::runtime::pubsub::{PublishReport, Subscriptions};
+
+    // Error handling macros
+    pub use crate::require;
+
+    // Math helpers
+    pub use crate::math;
 
     // Smart contract base
     pub use crate::smart_contract::SmartContract;
@@ -64,12 +88,12 @@ pub mod prelude {
     pub use crate::services::storage::Storage;
 
     // Storage
-    pub use crate::storage::{StorageContext, StorageItem, StorageMap};
+    pub use crate::storage::{delete_changed, prefixed_key, put_changed, KeyBuilder, StorageContext, StorageEntry, StorageItem, StorageMap, StorageVec, TypedStorageMap};
 
     // Types
     pub use crate::types::{
         Array, Bytes, H160, Int256, Map, ByteString,
-        Any, CallFlags, FindOptions, Notification, PublicKey, Signer, TriggerType, Tx,
+        Any, CallFlags, FindOptions, Notification, Page, PublicKey, Signer, TriggerType, Tx,
     };
 
     // Macros
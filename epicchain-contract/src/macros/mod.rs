@@ -7,8 +7,30 @@ mod safe;
 pub use safe::safe;
 
 /// Macro to define a function that is only available in WASM target
+///
+/// An optional `else` block gives the non-WASM implementation, typically backed by the
+/// emulated environment in [`host`](crate::host); without it the function returns
+/// `Default::default()` off-chain.
 #[macro_export]
 macro_rules! wasm_func {
+    ($(#[$attr:meta])* pub fn $name:ident($($arg:ident: $type:ty),*) -> $ret:ty $body:block else $host:block) => {
+        #[cfg(target_family = "wasm")]
+        $(#[$attr])*
+        pub fn $name($($arg: $type),*) -> $ret $body
+
+        #[cfg(not(target_family = "wasm"))]
+        $(#[$attr])*
+        pub fn $name($($arg: $type),*) -> $ret $host
+    };
+    ($(#[$attr:meta])* pub fn $name:ident($($arg:ident: $type:ty),*) $body:block else $host:block) => {
+        #[cfg(target_family = "wasm")]
+        $(#[$attr])*
+        pub fn $name($($arg: $type),*) $body
+
+        #[cfg(not(target_family = "wasm"))]
+        $(#[$attr])*
+        pub fn $name($($arg: $type),*) $host
+    };
     ($(#[$attr:meta])* pub fn $name:ident($($arg:ident: $type:ty),*) -> $ret:ty $body:block) => {
         #[cfg(target_family = "wasm")]
         $(#[$attr])*
@@ -34,3 +56,20 @@ macro_rules! wasm_func {
         }
     };
 }
+
+/// Aborts with a standardized [`ErrorCode`](crate::runtime::ErrorCode) prefix unless the
+/// condition holds.
+///
+/// ```ignore
+/// require!(amount.is_positive(), ErrorCode::InvalidArgument, "Amount must be positive");
+/// require!(Runtime::check_witness(owner), ErrorCode::Unauthorized);
+/// ```
+#[macro_export]
+macro_rules! require {
+    ($cond:expr, $code:expr, $msg:expr) => {
+        $crate::runtime::assert_with_code($cond, $code, $msg)
+    };
+    ($cond:expr, $code:expr) => {
+        $crate::runtime::assert_with_code($cond, $code, $code.name())
+    };
+}
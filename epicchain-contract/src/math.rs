@@ -0,0 +1,73 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Integer math helpers for token amounts.
+//!
+//! Contract amounts are non-negative `Int256` values, so every helper here works on those
+//! and aborts with an `InvalidArgument` error on a negative input or a zero divisor instead
+//! of returning a wrong result.
+//!
+//! ```ignore
+//! let liquidity = math::isqrt(amount_a.checked_mul(&amount_b));
+//! let price = math::mul_div(reserve_b, math::pow10(8), reserve_a);
+//! ```
+
+use crate::runtime::{abort_with_code, ErrorCode};
+use crate::types::Int256;
+
+/// Largest integer whose square does not exceed `value`.
+#[inline(always)]
+pub fn isqrt(value: Int256) -> Int256 {
+    if value.is_negative() {
+        abort_with_code(ErrorCode::InvalidArgument, "Square root of a negative value");
+    }
+    value.checked_sqrt()
+}
+
+/// The smaller of `a` and `b`.
+#[inline(always)]
+pub fn min(a: Int256, b: Int256) -> Int256 {
+    if a < b {
+        a
+    } else {
+        b
+    }
+}
+
+/// The larger of `a` and `b`.
+#[inline(always)]
+pub fn max(a: Int256, b: Int256) -> Int256 {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/// `a * b / denominator`, rounded down.
+///
+/// The product is never formed in full: both factors are split into quotient and remainder
+/// by `denominator`, so the result is exact whenever it fits in an `Int256` and
+/// `denominator²` does, even if `a * b` would overflow.
+pub fn mul_div(a: Int256, b: Int256, denominator: Int256) -> Int256 {
+    if a.is_negative() || b.is_negative() || !denominator.is_positive() {
+        abort_with_code(ErrorCode::InvalidArgument, "Invalid mul_div operands");
+    }
+
+    let (quotient_a, remainder_a) = (a.checked_div(&denominator), a.checked_mod(&denominator));
+    let (quotient_b, remainder_b) = (b.checked_div(&denominator), b.checked_mod(&denominator));
+
+    // a * b / d = qa * qb * d + qa * rb + ra * qb + ra * rb / d, with only the last term inexact
+    quotient_a
+        .checked_mul(&quotient_b)
+        .checked_mul(&denominator)
+        .checked_add(&quotient_a.checked_mul(&remainder_b))
+        .checked_add(&remainder_a.checked_mul(&quotient_b))
+        .checked_add(&remainder_a.checked_mul(&remainder_b).checked_div(&denominator))
+}
+
+/// `10^exponent`, e.g. the scaling factor of a token with `exponent` decimals.
+#[inline(always)]
+pub fn pow10(exponent: u32) -> Int256 {
+    Int256::new(10).checked_pow(exponent)
+}
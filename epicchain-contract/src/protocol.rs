@@ -0,0 +1,36 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Protocol version targeted by the contract.
+//!
+//! Syscalls and native methods added by a protocol upgrade are only available on
+//! networks running that version or later. The `protocol-*` crate features select the
+//! target version (`protocol-3-8` by default); APIs newer than the target are compiled
+//! out, and `#[contract_impl]` reports their use with an error naming the feature to
+//! enable instead of letting the deployed contract fault on-chain.
+//!
+//! | API                                          | Backed by                            | Feature        |
+//! |----------------------------------------------|--------------------------------------|----------------|
+//! | `current_signers`                            | `System.Runtime.CurrentSigners`      | `protocol-3-6` |
+//! | `NamedCurveHash::Secp256k1Keccak256`, `NamedCurveHash::Secp256r1Keccak256` | `CryptoLib.verifyWithECDsa` | `protocol-3-7` |
+//! | `crypto::verify_ed25519`                     | `CryptoLib.verifyWithEd25519`        | `protocol-3-8` |
+//!
+//! A contract for an older network depends on the framework with
+//! `default-features = false, features = ["protocol-3-6"]`.
+
+/// The targeted protocol version as `(major, minor)`, `(3, 5)` when no `protocol-*` feature is enabled.
+pub const VERSION: (u8, u8) = if cfg!(feature = "protocol-3-8") {
+    (3, 8)
+} else if cfg!(feature = "protocol-3-7") {
+    (3, 7)
+} else if cfg!(feature = "protocol-3-6") {
+    (3, 6)
+} else {
+    (3, 5)
+};
+
+/// Whether the targeted protocol version is `major.minor` or later.
+#[inline(always)]
+pub const fn supports(major: u8, minor: u8) -> bool {
+    VERSION.0 > major || (VERSION.0 == major && VERSION.1 >= minor)
+}
@@ -0,0 +1,77 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Access checks backing the `#[only_owner]`, `#[only_role]` and `#[only_contract]`
+//! method attributes.
+//!
+//! The owner is the `H160` stored under [`OWNER_KEY`]; role members are stored under
+//! `role_<ROLE>_<account>` (see [`role_key`]). Contracts that keep their owner or roles
+//! elsewhere should keep using explicit checks.
+
+use crate::{
+    services::{runtime::Runtime, storage::Storage},
+    types::{ByteString, FromByteString, H160, IntoByteString},
+};
+
+/// Storage key holding the contract owner.
+pub const OWNER_KEY: &str = "owner";
+
+/// Storage prefix of role memberships.
+pub const ROLE_PREFIX: &str = "role_";
+
+/// Returns the owner stored under [`OWNER_KEY`], or the zero hash if none is set.
+pub fn contract_owner() -> H160 {
+    match Storage::get(Storage::get_context(), ByteString::from_literal(OWNER_KEY)) {
+        Some(owner) => H160::from_byte_string(owner),
+        None => H160::zero(),
+    }
+}
+
+/// Returns true if the owner is set and has witnessed the current transaction.
+pub fn is_owner() -> bool {
+    let owner = contract_owner();
+    owner != H160::zero() && Runtime::check_witness(owner)
+}
+
+/// Returns the storage key of a role membership.
+pub fn role_key(role: &str, account: H160) -> ByteString {
+    role_key_of(&ByteString::from_literal(role), account)
+}
+
+/// Returns the storage key of a role membership for a role name only known at runtime.
+pub fn role_key_of(role: &ByteString, account: H160) -> ByteString {
+    ByteString::from_literal(ROLE_PREFIX)
+        .concat(role)
+        .concat(&ByteString::from_literal("_"))
+        .concat(&account.into_byte_string())
+}
+
+/// Returns true if `account` holds `role`.
+pub fn has_role(role: &str, account: H160) -> bool {
+    Storage::get(Storage::get_context(), role_key(role, account)).is_some()
+}
+
+/// Grants `role` to `account`. Callers are responsible for authorization.
+pub fn grant_role(role: &str, account: H160) {
+    Storage::put(Storage::get_context(), role_key(role, account), ByteString::from_literal("1"));
+}
+
+/// Revokes `role` from `account`. Callers are responsible for authorization.
+pub fn revoke_role(role: &str, account: H160) {
+    Storage::delete(Storage::get_context(), role_key(role, account));
+}
+
+/// Returns true if `account` holds `role` and has witnessed the current transaction.
+pub fn is_role_witnessed(role: &str, account: H160) -> bool {
+    has_role(role, account) && Runtime::check_witness(account)
+}
+
+/// Returns true if the calling script (contract or entry script) holds `role`.
+pub fn is_caller_in_role(role: &str) -> bool {
+    has_role(role, Runtime::get_calling_script_hash())
+}
+
+/// Returns true if the current invocation comes from the contract `expected`.
+pub fn is_calling_contract(expected: H160) -> bool {
+    Runtime::get_calling_script_hash() == expected
+}
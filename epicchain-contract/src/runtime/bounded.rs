@@ -0,0 +1,31 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Loop guards for arrays whose length the caller controls.
+//!
+//! A loop over an owner list, a royalty recipient list or a batch argument costs EpicPulse per
+//! item, so an oversized array can push a method past the transaction's fee or stack limits
+//! halfway through. [`bounded_iter`] checks the length up front and aborts with a
+//! `LimitExceeded` error before any item is processed:
+//!
+//! ```ignore
+//! for i in bounded_iter(&recipients, MAX_RECIPIENTS) {
+//!     let recipient = recipients.get(i);
+//!     ...
+//! }
+//! ```
+
+use core::ops::Range;
+
+use super::{abort_with_code, ErrorCode};
+use crate::types::Array;
+
+/// The indices of `array`, aborting with `LimitExceeded` if it holds more than `max` items.
+#[inline(always)]
+pub fn bounded_iter<T>(array: &Array<T>, max: usize) -> Range<usize> {
+    let size = array.size();
+    if size > max {
+        abort_with_code(ErrorCode::LimitExceeded, "Array exceeds iteration limit");
+    }
+    0..size
+}
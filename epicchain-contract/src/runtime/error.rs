@@ -0,0 +1,119 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Standardized error codes for contract aborts.
+//!
+//! Every abort raised through [`abort_with_code`], [`assert_with_code`] or the
+//! `require!` macro carries a fixed, machine-readable prefix of the form
+//! `E<code>:<Name>: `, e.g. `E03:NotFound: Pool not found`. Clients and tests can
+//! match on the prefix instead of the free-form message that follows it.
+
+use crate::types::ByteString;
+
+/// Shared error codes emitted by the abort machinery.
+///
+/// The numeric values are part of the contract ABI: once published they must
+/// never be renumbered. New codes are appended at the end.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// The caller did not provide the required witness or role.
+    Unauthorized = 1,
+    /// The contract or feature is paused.
+    Paused = 2,
+    /// The requested entity does not exist.
+    NotFound = 3,
+    /// The account balance is too low for the operation.
+    InsufficientBalance = 4,
+    /// An argument is out of range or malformed.
+    InvalidArgument = 5,
+    /// A deadline or validity period has passed.
+    Expired = 6,
+    /// The entity already exists or was already initialized.
+    AlreadyExists = 7,
+    /// The entity is not in a state that allows the operation.
+    InvalidState = 8,
+    /// A configured limit (count, size, supply) would be exceeded.
+    LimitExceeded = 9,
+    /// A cross-contract call failed or returned an unexpected result.
+    CallFailed = 10,
+}
+
+impl ErrorCode {
+    /// Returns the numeric value of the error code.
+    #[inline(always)]
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the symbolic name of the error code.
+    pub const fn name(self) -> &'static str {
+        match self {
+            ErrorCode::Unauthorized => "Unauthorized",
+            ErrorCode::Paused => "Paused",
+            ErrorCode::NotFound => "NotFound",
+            ErrorCode::InsufficientBalance => "InsufficientBalance",
+            ErrorCode::InvalidArgument => "InvalidArgument",
+            ErrorCode::Expired => "Expired",
+            ErrorCode::AlreadyExists => "AlreadyExists",
+            ErrorCode::InvalidState => "InvalidState",
+            ErrorCode::LimitExceeded => "LimitExceeded",
+            ErrorCode::CallFailed => "CallFailed",
+        }
+    }
+
+    /// Returns the machine-readable prefix prepended to abort messages.
+    ///
+    /// Kept as literals so no formatting code ends up in the WASM binary.
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            ErrorCode::Unauthorized => "E01:Unauthorized: ",
+            ErrorCode::Paused => "E02:Paused: ",
+            ErrorCode::NotFound => "E03:NotFound: ",
+            ErrorCode::InsufficientBalance => "E04:InsufficientBalance: ",
+            ErrorCode::InvalidArgument => "E05:InvalidArgument: ",
+            ErrorCode::Expired => "E06:Expired: ",
+            ErrorCode::AlreadyExists => "E07:AlreadyExists: ",
+            ErrorCode::InvalidState => "E08:InvalidState: ",
+            ErrorCode::LimitExceeded => "E09:LimitExceeded: ",
+            ErrorCode::CallFailed => "E10:CallFailed: ",
+        }
+    }
+
+    /// Converts a numeric value back into an error code.
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(ErrorCode::Unauthorized),
+            2 => Some(ErrorCode::Paused),
+            3 => Some(ErrorCode::NotFound),
+            4 => Some(ErrorCode::InsufficientBalance),
+            5 => Some(ErrorCode::InvalidArgument),
+            6 => Some(ErrorCode::Expired),
+            7 => Some(ErrorCode::AlreadyExists),
+            8 => Some(ErrorCode::InvalidState),
+            9 => Some(ErrorCode::LimitExceeded),
+            10 => Some(ErrorCode::CallFailed),
+            _ => None,
+        }
+    }
+
+    /// Builds the full abort message: prefix followed by `message`.
+    #[inline(always)]
+    pub fn message(self, message: &str) -> ByteString {
+        ByteString::from_literal(self.prefix()).concat(&ByteString::from_literal(message))
+    }
+}
+
+/// Aborts execution with a coded error message.
+#[inline(always)]
+pub fn abort_with_code(code: ErrorCode, message: &str) {
+    super::abort_with_message(code.message(message));
+}
+
+/// Aborts execution with a coded error message if `condition` is false.
+#[inline(always)]
+pub fn assert_with_code(condition: bool, code: ErrorCode, message: &str) {
+    if !condition {
+        abort_with_code(code, message);
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Dead-man switch: an owner proves liveness with periodic heartbeats, and once it has been
+//! silent for longer than the configured period a designated recovery account gains the
+//! capabilities the owner granted it.
+//!
+//! Capabilities are a bitmask whose meaning is up to the contract, so one switch can, for
+//! example, let the recovery account withdraw funds but not change the configuration.
+//! Everything is stored under the switch's prefix:
+//!
+//! | key               | value                      |
+//! |-------------------|----------------------------|
+//! | `prefix + "own"`  | owner `H160`               |
+//! | `prefix + "rec"`  | recovery account `H160`    |
+//! | `prefix + "per"`  | silence period (u64 LE)    |
+//! | `prefix + "cap"`  | capabilities (u32 LE)      |
+//! | `prefix + "hb"`   | last heartbeat (u64 LE)    |
+//!
+//! ```ignore
+//! const CAN_WITHDRAW: u32 = 1;
+//!
+//! let switch = DeadManSwitch::new("dms_");
+//! switch.configure(owner, heir, days(180), CAN_WITHDRAW);
+//! // ...
+//! require!(switch.heartbeat(), ErrorCode::Unauthorized);
+//! // ...
+//! require!(switch.can_exercise(CAN_WITHDRAW), ErrorCode::Unauthorized, "Owner still active");
+//! ```
+
+use crate::{
+    runtime::time::{deadline, is_expired_at},
+    services::{runtime::Runtime, storage::Storage},
+    types::{ByteString, FromByteString, H160, IntoByteString},
+};
+
+/// A heartbeat-based dead-man switch stored under a key prefix.
+pub struct DeadManSwitch {
+    prefix: ByteString,
+}
+
+impl DeadManSwitch {
+    pub fn new(prefix: &str) -> Self {
+        Self::with_prefix(ByteString::from_literal(prefix))
+    }
+
+    pub fn with_prefix(prefix: ByteString) -> Self {
+        Self { prefix }
+    }
+
+    pub fn prefix(&self) -> &ByteString {
+        &self.prefix
+    }
+
+    /// Arms the switch and records a heartbeat. Callers are responsible for authorization.
+    ///
+    /// Reconfiguring an armed switch replaces its settings and restarts the silence period.
+    pub fn configure(&self, owner: H160, recovery: H160, silence_period: u64, capabilities: u32) {
+        let storage = Storage::get_context();
+        Storage::put(storage.clone(), self.key("own"), owner.into_byte_string());
+        Storage::put(storage.clone(), self.key("rec"), recovery.into_byte_string());
+        Storage::put(storage.clone(), self.key("per"), ByteString::from_bytes(&silence_period.to_le_bytes()));
+        Storage::put(storage, self.key("cap"), ByteString::from_bytes(&capabilities.to_le_bytes()));
+        self.record_heartbeat();
+    }
+
+    /// Disarms the switch, removing all of its state. Callers are responsible for authorization.
+    pub fn disarm(&self) {
+        let storage = Storage::get_context();
+        for name in ["own", "rec", "per", "cap", "hb"] {
+            Storage::delete(storage.clone(), self.key(name));
+        }
+    }
+
+    /// Whether the switch has been configured.
+    pub fn is_armed(&self) -> bool {
+        Storage::get(Storage::get_context(), self.key("own")).is_some()
+    }
+
+    /// Records a heartbeat if the owner has witnessed the current transaction.
+    pub fn heartbeat(&self) -> bool {
+        let owner = self.owner();
+        if owner == H160::zero() || !Runtime::check_witness(owner) {
+            return false;
+        }
+        self.record_heartbeat();
+        true
+    }
+
+    /// Records a heartbeat without checks, e.g. on any other action taken by the owner.
+    pub fn record_heartbeat(&self) {
+        let now = Runtime::get_time();
+        Storage::put(Storage::get_context(), self.key("hb"), ByteString::from_bytes(&now.to_le_bytes()));
+    }
+
+    /// Returns the owner, or the zero hash if the switch is not armed.
+    pub fn owner(&self) -> H160 {
+        self.get_hash("own")
+    }
+
+    /// Returns the recovery account, or the zero hash if the switch is not armed.
+    pub fn recovery(&self) -> H160 {
+        self.get_hash("rec")
+    }
+
+    pub fn silence_period(&self) -> u64 {
+        self.get_u64("per")
+    }
+
+    pub fn capabilities(&self) -> u32 {
+        match Storage::get(Storage::get_context(), self.key("cap")) {
+            Some(value) => {
+                let bytes = value.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            }
+            None => 0,
+        }
+    }
+
+    pub fn last_heartbeat(&self) -> u64 {
+        self.get_u64("hb")
+    }
+
+    /// Timestamp after which the switch triggers unless the owner sends a heartbeat.
+    pub fn triggers_at(&self) -> u64 {
+        deadline(self.last_heartbeat(), self.silence_period())
+    }
+
+    /// Whether the owner has been silent for longer than the silence period at `timestamp`.
+    pub fn is_triggered_at(&self, timestamp: u64) -> bool {
+        self.is_armed() && is_expired_at(self.triggers_at(), timestamp)
+    }
+
+    /// Whether the owner has been silent for longer than the silence period.
+    pub fn is_triggered(&self) -> bool {
+        self.is_triggered_at(Runtime::get_time())
+    }
+
+    /// Whether the switch has triggered and grants every bit of `capability`.
+    pub fn grants(&self, capability: u32) -> bool {
+        capability != 0 && self.capabilities() & capability == capability && self.is_triggered()
+    }
+
+    /// Whether the recovery account has witnessed the current transaction and may exercise
+    /// `capability`.
+    pub fn can_exercise(&self, capability: u32) -> bool {
+        self.grants(capability) && Runtime::check_witness(self.recovery())
+    }
+
+    fn key(&self, name: &str) -> ByteString {
+        self.prefix.concat(&ByteString::from_literal(name))
+    }
+
+    fn get_hash(&self, name: &str) -> H160 {
+        match Storage::get(Storage::get_context(), self.key(name)) {
+            Some(value) => H160::from_byte_string(value),
+            None => H160::zero(),
+        }
+    }
+
+    fn get_u64(&self, name: &str) -> u64 {
+        match Storage::get(Storage::get_context(), self.key(name)) {
+            Some(value) => {
+                let bytes = value.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([
+                        bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]
+                    ])
+                } else {
+                    0
+                }
+            }
+            None => 0,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Entry of an exported contract method.
+//!
+//! `#[contract_impl]` starts every exported method with [`begin`]. Calls between methods of
+//! the same contract are plain Rust calls and do not go through it again, so one invocation
+//! installs the panic hook and fetches its storage context once, however many methods it
+//! runs (e.g. `mint` calling `balance_of` calling `total_supply`).
+
+use crate::{runtime::panic, services::storage::Storage};
+
+/// Set up an invocation of the contract.
+///
+/// The storage context cached by an earlier invocation is dropped: the contract may be
+/// re-entered by a contract it calls, and each invocation fetches its own context.
+#[inline(always)]
+pub fn begin() {
+    panic::install_panic_hook();
+    Storage::forget_context();
+}
@@ -1,19 +1,37 @@
 // Copyright @ 2024 - present, R3E Network
 // All Rights Reserved.
 
+pub mod access;
 pub mod assert;
+pub mod bounded;
+pub mod error;
+pub mod heartbeat;
+pub mod invocation;
+pub mod notification;
+pub mod panic;
+pub mod pubsub;
+pub mod time;
+pub mod versioning;
 
 pub use assert::*;
+pub use bounded::*;
+pub use error::*;
+pub use notification::*;
 
 use crate::{types::*, wasm_func};
 
 #[cfg(target_family = "wasm")]
 use crate::env;
 
+#[cfg(not(target_family = "wasm"))]
+use crate::host;
+
 wasm_func! {
     #[inline(always)]
     pub fn get_trigger() -> TriggerType {
         unsafe { env::syscall::system_runtime_trigger() }
+    } else {
+        host::trigger()
     }
 }
 
@@ -35,6 +53,8 @@ wasm_func! {
     #[inline(always)]
     pub fn get_executing_script_hash() -> H160 {
         unsafe { env::syscall::system_runtime_executing_script_hash() }
+    } else {
+        host::executing_script_hash()
     }
 }
 
@@ -42,6 +62,8 @@ wasm_func! {
     #[inline(always)]
     pub fn get_calling_script_hash() -> H160 {
         unsafe { env::syscall::system_runtime_calling_script_hash() }
+    } else {
+        host::calling_script_hash()
     }
 }
 
@@ -49,6 +71,8 @@ wasm_func! {
     #[inline(always)]
     pub fn get_entry_script_hash() -> H160 {
         unsafe { env::syscall::system_runtime_entry_script_hash() }
+    } else {
+        host::entry_script_hash()
     }
 }
 
@@ -56,6 +80,8 @@ wasm_func! {
     #[inline(always)]
     pub fn get_time() -> u64 {
         unsafe { env::syscall::system_runtime_time() }
+    } else {
+        host::time()
     }
 }
 
@@ -63,6 +89,8 @@ wasm_func! {
     #[inline(always)]
     pub fn get_invocation_counter() -> u32 {
         unsafe { env::syscall::system_runtime_invocation_counter() }
+    } else {
+        host::invocation_counter()
     }
 }
 
@@ -70,6 +98,8 @@ wasm_func! {
     #[inline(always)]
     pub fn get_epicpulse_left() -> Int256 {
         unsafe { env::syscall::system_runtime_epicpulse_left() }
+    } else {
+        host::epicpulse_left()
     }
 }
 
@@ -98,6 +128,8 @@ wasm_func! {
     #[inline(always)]
     pub fn check_witness_with_account(account: H160) -> bool {
         unsafe { env::syscall::system_runtime_check_witness_with_account(account) }
+    } else {
+        host::has_witness(account)
     }
 }
 
@@ -112,6 +144,8 @@ wasm_func! {
     #[inline(always)]
     pub fn log(message: ByteString) {
         unsafe { env::syscall::system_runtime_log(message) }
+    } else {
+        host::push_log(message)
     }
 }
 
@@ -119,6 +153,8 @@ wasm_func! {
     #[inline(always)]
     pub fn burn_epicpulse(amount: Int256) {
         unsafe { env::syscall::system_runtime_burn_epicpulse(amount) }
+    } else {
+        host::burn_epicpulse(amount)
     }
 }
 
@@ -126,6 +162,8 @@ wasm_func! {
     #[inline(always)]
     pub fn get_random() -> Int256 {
         unsafe { env::syscall::system_runtime_get_random() }
+    } else {
+        host::next_random()
     }
 }
 
@@ -133,6 +171,8 @@ wasm_func! {
     #[inline(always)]
     pub fn get_network() -> u32 {
         unsafe { env::syscall::system_runtime_get_network() }
+    } else {
+        host::network()
     }
 }
 
@@ -144,6 +184,8 @@ wasm_func! {
 }
 
 wasm_func! {
+    /// Requires protocol 3.6, see [`crate::protocol`].
+    #[cfg(feature = "protocol-3-6")]
     #[inline(always)]
     pub fn current_signers() -> Array<Signer> {
         unsafe { env::syscall::system_runtime_current_signers() }
@@ -154,5 +196,7 @@ wasm_func! {
     #[inline(always)]
     pub fn notify(name: ByteString, data: Array<Any>) {
         unsafe { env::syscall::system_runtime_notify(name, data) }
+    } else {
+        host::push_notification(name, data)
     }
 }
@@ -0,0 +1,147 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Size-checked notifications.
+//!
+//! The VM faults when an event name exceeds [`MAX_EVENT_NAME_LENGTH`] bytes or when the
+//! serialized state exceeds [`MAX_NOTIFICATION_SIZE`] bytes or [`MAX_NOTIFICATION_ITEMS`]
+//! items. [`EventPayload`] tracks the serialized size while the state is built, so
+//! [`notify_checked`] can abort with a clear message instead, and free-text fields can be
+//! truncated to fit with [`EventPayload::push_bytes_truncated`].
+
+use crate::{
+    runtime::{abort_with_code, ErrorCode},
+    types::{builtin::IntoAny, Any, Array, ByteString, H160, Int256, IntoByteString},
+};
+
+/// Maximum length of an event name, in bytes.
+pub const MAX_EVENT_NAME_LENGTH: usize = 32;
+
+/// Maximum serialized size of a notification state, in bytes.
+pub const MAX_NOTIFICATION_SIZE: usize = 1024;
+
+/// Maximum number of stack items in a notification state.
+pub const MAX_NOTIFICATION_ITEMS: usize = 2048;
+
+/// Notification state that keeps track of its serialized size.
+pub struct EventPayload {
+    items: Array<Any>,
+    count: usize,
+    size: usize,
+}
+
+impl EventPayload {
+    pub fn new() -> Self {
+        Self { items: Array::new(), count: 0, size: 0 }
+    }
+
+    /// Number of items pushed so far.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Serialized size of the state: array header followed by the items.
+    pub fn size(&self) -> usize {
+        1 + var_size(self.count) + self.size
+    }
+
+    /// Bytes that can still be added before reaching [`MAX_NOTIFICATION_SIZE`].
+    pub fn remaining(&self) -> usize {
+        MAX_NOTIFICATION_SIZE.saturating_sub(self.size())
+    }
+
+    pub fn push_int(&mut self, value: Int256) {
+        let len = value.into_byte_string().len();
+        self.push_item(value.into_any(), 1 + var_size(len) + len);
+    }
+
+    pub fn push_hash160(&mut self, value: H160) {
+        self.push_item(value.into_any(), 1 + 1 + 20);
+    }
+
+    pub fn push_bytes(&mut self, value: ByteString) {
+        let len = value.len();
+        self.push_item(value.into_any(), 1 + var_size(len) + len);
+    }
+
+    /// Pushes `value`, cut so that the payload fits in [`MAX_NOTIFICATION_SIZE`].
+    ///
+    /// Returns true if `value` was truncated.
+    pub fn push_bytes_truncated(&mut self, value: ByteString) -> bool {
+        // Account for the item count header growing with this item
+        let header_growth = var_size(self.count + 1) - var_size(self.count);
+        let available = self.remaining().saturating_sub(header_growth + 1);
+
+        let mut len = value.len();
+        while len > 0 && var_size(len) + len > available {
+            len -= 1;
+        }
+
+        let truncated = len < value.len();
+        if truncated {
+            self.push_bytes(value.substr(0, len));
+        } else {
+            self.push_bytes(value);
+        }
+        truncated
+    }
+
+    /// Returns the state, ready for `Runtime::notify`.
+    pub fn into_array(self) -> Array<Any> {
+        self.items
+    }
+
+    fn push_item(&mut self, item: Any, size: usize) {
+        self.items.push(item);
+        self.count += 1;
+        self.size += size;
+    }
+}
+
+impl Default for EventPayload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns true if `name` is a valid event name. Usable in constant contexts to reject
+/// oversized literal names at compile time:
+///
+/// ```ignore
+/// const _: () = assert!(is_valid_event_name("LiquidityZapped"));
+/// ```
+pub const fn is_valid_event_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= MAX_EVENT_NAME_LENGTH
+}
+
+/// Aborts with `ErrorCode::LimitExceeded` unless `name` and `payload` are within the VM limits.
+pub fn check_notification(name: &ByteString, payload: &EventPayload) {
+    if name.is_empty() || name.len() > MAX_EVENT_NAME_LENGTH {
+        abort_with_code(ErrorCode::LimitExceeded, "Event name must be 1-32 bytes");
+    } else if payload.len() > MAX_NOTIFICATION_ITEMS {
+        abort_with_code(ErrorCode::LimitExceeded, "Too many notification items");
+    } else if payload.size() > MAX_NOTIFICATION_SIZE {
+        abort_with_code(ErrorCode::LimitExceeded, "Notification payload too large");
+    }
+}
+
+/// Emits `name(payload)` after checking it against the VM limits.
+pub fn notify_checked(name: ByteString, payload: EventPayload) {
+    check_notification(&name, &payload);
+    super::notify(name, payload.into_array());
+}
+
+/// Size of the variable-length integer prefixing `n` bytes or items.
+fn var_size(n: usize) -> usize {
+    if n < 0xFD {
+        1
+    } else if n <= 0xFFFF {
+        3
+    } else {
+        5
+    }
+}
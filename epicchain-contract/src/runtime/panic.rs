@@ -0,0 +1,46 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Forwarding of Rust panics to the VM.
+//!
+//! Without a hook a panic in contract code ends in an opaque `unreachable` trap. The hook
+//! installed by [`install_panic_hook`] aborts with the panic message instead, so a failed
+//! `assert!` or `unwrap()` shows up in the transaction's exception. Debug builds append the
+//! source location. `#[contract_impl]` installs the hook at the start of every exported method.
+//!
+//! Off-wasm the hook is not installed, so tests keep the standard panic output.
+
+use std::{any::Any, panic::Location};
+
+/// Install the panic hook; later calls are no-ops.
+#[inline(always)]
+pub fn install_panic_hook() {
+    #[cfg(target_family = "wasm")]
+    {
+        static INSTALL: std::sync::Once = std::sync::Once::new();
+        INSTALL.call_once(|| {
+            std::panic::set_hook(Box::new(|info| {
+                let message = panic_message(info.payload(), info.location());
+                unsafe { crate::env::asm::abort_with_message(crate::types::ByteString::from_bytes(message.as_bytes())) }
+            }));
+        });
+    }
+}
+
+/// Message reported for a panic with `payload`, followed by its location in debug builds.
+pub fn panic_message(payload: &(dyn Any + Send), location: Option<&Location<'_>>) -> String {
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.as_str()
+    } else {
+        "panic"
+    };
+
+    match location {
+        Some(location) if cfg!(debug_assertions) => {
+            format!("{} at {}:{}:{}", message, location.file(), location.line(), location.column())
+        }
+        _ => message.to_string(),
+    }
+}
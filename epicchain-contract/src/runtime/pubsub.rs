@@ -0,0 +1,214 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Contract-to-contract event subscriptions.
+//!
+//! A producer keeps a [`Subscriptions`] registry of consumer contracts interested in its
+//! logical events (topics), and [`Subscriptions::publish`] calls each consumer's callback
+//! with `(producer, topic, payload)`. Every subscription carries a gas budget:
+//!
+//! - a callback is only invoked if at least its budget is left, so a publish that runs low
+//!   skips it instead of faulting partway through the callback;
+//! - a subscriber whose callback burns more than its budget is unsubscribed, so a
+//!   misbehaving consumer cannot keep taxing the producer's callers.
+//!
+//! A callback that faults aborts the whole transaction, like any other contract call, so
+//! producers should only let trusted consumers subscribe or publish from a dedicated
+//! method rather than from their core state transitions.
+//!
+//! Everything is stored under the registry's prefix, keyed by topic and subscriber:
+//!
+//! | key                                               | value                                |
+//! |---------------------------------------------------|--------------------------------------|
+//! | `prefix + topic length (u8) + topic + subscriber` | method length (u8), method, budget   |
+//!
+//! ```ignore
+//! let subscriptions = Subscriptions::new("subs_");
+//!
+//! // In `subscribe(topic, method, budget)`: a consumer registers itself
+//! let consumer = Runtime::get_calling_script_hash();
+//! require!(subscriptions.subscribe(topic, consumer, method, budget), ErrorCode::InvalidArgument);
+//!
+//! // After a price update
+//! let mut payload = Array::new();
+//! payload.push(symbol.into_any());
+//! payload.push(price.into_any());
+//! subscriptions.publish(ByteString::from_literal("PriceUpdated"), payload);
+//! ```
+
+use crate::{
+    services::{contract::Contract, runtime::Runtime, storage::Storage},
+    storage::find::StorageEntry,
+    types::{builtin::IntoAny, Any, Array, ByteString, CallFlags, FromByteString, H160, Int256, IntoByteString},
+};
+
+/// Maximum length of a topic, in bytes.
+pub const MAX_TOPIC_LENGTH: usize = 32;
+
+/// Maximum length of a callback method name, in bytes.
+pub const MAX_CALLBACK_METHOD_LENGTH: usize = 32;
+
+/// Maximum number of subscribers per topic, bounding the work of one publish.
+pub const MAX_SUBSCRIBERS_PER_TOPIC: u32 = 16;
+
+/// Outcome of [`Subscriptions::publish`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PublishReport {
+    /// Callbacks invoked.
+    pub delivered: u32,
+    /// Subscribers removed because their callback burned more than its budget.
+    pub evicted: u32,
+    /// Subscribers not called because too little gas was left for their budget.
+    pub skipped: u32,
+}
+
+/// A registry of contracts subscribed to the topics of a producer, stored under a key prefix.
+pub struct Subscriptions {
+    prefix: ByteString,
+}
+
+impl Subscriptions {
+    pub fn new(prefix: &str) -> Self {
+        Self::with_prefix(ByteString::from_literal(prefix))
+    }
+
+    pub fn with_prefix(prefix: ByteString) -> Self {
+        Self { prefix }
+    }
+
+    pub fn prefix(&self) -> &ByteString {
+        &self.prefix
+    }
+
+    /// Subscribes `subscriber` to `topic`, replacing its callback and budget if it was
+    /// already subscribed. Callers are responsible for authorization, typically by
+    /// requiring `subscriber` to be the calling contract.
+    ///
+    /// Returns false if the topic, method or budget is invalid or the topic is full.
+    pub fn subscribe(&self, topic: ByteString, subscriber: H160, method: ByteString, gas_budget: Int256) -> bool {
+        if topic.is_empty() || topic.len() > MAX_TOPIC_LENGTH {
+            return false;
+        }
+        if method.is_empty() || method.len() > MAX_CALLBACK_METHOD_LENGTH {
+            return false;
+        }
+        if subscriber == H160::zero() || gas_budget <= Int256::zero() {
+            return false;
+        }
+
+        let key = self.subscription_key(&topic, subscriber);
+        let storage = Storage::get_context();
+        let is_new = Storage::get(storage.clone(), key.clone()).is_none();
+        if is_new && self.subscriber_count(topic) >= MAX_SUBSCRIBERS_PER_TOPIC {
+            return false;
+        }
+
+        let value = ByteString::from_bytes(&[method.len() as u8])
+            .concat(&method)
+            .concat(&gas_budget.into_byte_string());
+        Storage::put(storage, key, value);
+        true
+    }
+
+    /// Removes the subscription of `subscriber` to `topic`. Returns false if there was none.
+    pub fn unsubscribe(&self, topic: ByteString, subscriber: H160) -> bool {
+        let key = self.subscription_key(&topic, subscriber);
+        let storage = Storage::get_context();
+        if Storage::get(storage.clone(), key.clone()).is_none() {
+            return false;
+        }
+        Storage::delete(storage, key);
+        true
+    }
+
+    pub fn is_subscribed(&self, topic: ByteString, subscriber: H160) -> bool {
+        Storage::get(Storage::get_context(), self.subscription_key(&topic, subscriber)).is_some()
+    }
+
+    /// Returns the callback method and gas budget of a subscription.
+    pub fn subscription(&self, topic: ByteString, subscriber: H160) -> Option<(ByteString, Int256)> {
+        let value = Storage::get(Storage::get_context(), self.subscription_key(&topic, subscriber))?;
+        decode_subscription(&value)
+    }
+
+    /// Subscribers of `topic`, in ascending hash order.
+    pub fn subscribers(&self, topic: ByteString) -> Array<H160> {
+        let mut subscribers = Array::new();
+        for entry in self.entries(&topic) {
+            subscribers.push(H160::from_byte_string(entry.key));
+        }
+        subscribers
+    }
+
+    pub fn subscriber_count(&self, topic: ByteString) -> u32 {
+        self.entries(&topic).count() as u32
+    }
+
+    /// Calls every subscriber of `topic` with `(producer, topic, payload)`, where the
+    /// producer is the executing contract. See the module documentation for how gas
+    /// budgets are enforced.
+    pub fn publish(&self, topic: ByteString, payload: Array<Any>) -> PublishReport {
+        let mut report = PublishReport::default();
+        let mut evicted = Array::new();
+        let producer = Runtime::get_executing_script_hash();
+        let payload = payload.into_any();
+
+        for entry in self.entries(&topic) {
+            let Some((method, budget)) = decode_subscription(&entry.value) else {
+                continue;
+            };
+
+            let gas_before = Runtime::get_epicpulse_left();
+            if gas_before < budget {
+                report.skipped += 1;
+                continue;
+            }
+
+            let subscriber = H160::from_byte_string(entry.key);
+            let mut args = Array::new();
+            args.push(producer.into_any());
+            args.push(topic.clone().into_any());
+            args.push(payload.clone());
+            Contract::call(subscriber, method, CallFlags::All, args);
+            report.delivered += 1;
+
+            if gas_before.checked_sub(&Runtime::get_epicpulse_left()) > budget {
+                evicted.push(subscriber);
+            }
+        }
+
+        // Removed after the scan so the storage iterator never sees its own deletions
+        for index in 0..evicted.size() {
+            self.unsubscribe(topic.clone(), evicted.get(index));
+            report.evicted += 1;
+        }
+
+        report
+    }
+
+    fn topic_prefix(&self, topic: &ByteString) -> ByteString {
+        self.prefix
+            .concat(&ByteString::from_bytes(&[topic.len() as u8]))
+            .concat(topic)
+    }
+
+    fn subscription_key(&self, topic: &ByteString, subscriber: H160) -> ByteString {
+        self.topic_prefix(topic).concat(&subscriber.into_byte_string())
+    }
+
+    /// Subscriptions to `topic`, keyed by subscriber hash.
+    fn entries(&self, topic: &ByteString) -> impl Iterator<Item = StorageEntry> {
+        // The length byte keeps one topic from matching keys of a longer topic
+        Storage::find_prefix(Storage::get_context(), self.topic_prefix(topic))
+            .remove_prefix()
+            .filter(|entry| entry.key.len() == H160::SIZE)
+    }
+}
+
+fn decode_subscription(value: &ByteString) -> Option<(ByteString, Int256)> {
+    let bytes = value.to_bytes();
+    let method_end = 1 + *bytes.first()? as usize;
+    let method = ByteString::from_bytes(bytes.get(1..method_end)?);
+    let budget = Int256::from_byte_string(ByteString::from_bytes(&bytes[method_end..]));
+    Some((method, budget))
+}
@@ -0,0 +1,90 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Duration constants and helpers for block timestamps.
+//!
+//! Block timestamps are whole seconds since the Unix epoch, as returned by [`get_time`].
+//! The helpers saturate instead of underflowing, so a timestamp earlier than a start time
+//! yields zero elapsed time (or no period) rather than wrapping around.
+//!
+//! ```ignore
+//! if lock_period > SECONDS_PER_YEAR { ... }
+//! let epoch = period_index(program_start, SECONDS_PER_WEEK).unwrap_or(0);
+//! ```
+
+use super::get_time;
+
+pub const SECONDS_PER_MINUTE: u64 = 60;
+pub const SECONDS_PER_HOUR: u64 = 60 * SECONDS_PER_MINUTE;
+pub const SECONDS_PER_DAY: u64 = 24 * SECONDS_PER_HOUR;
+pub const SECONDS_PER_WEEK: u64 = 7 * SECONDS_PER_DAY;
+
+/// A 365-day year, the basis of annual rates.
+pub const SECONDS_PER_YEAR: u64 = 365 * SECONDS_PER_DAY;
+
+/// `count` hours in seconds.
+#[inline(always)]
+pub const fn hours(count: u64) -> u64 {
+    count.saturating_mul(SECONDS_PER_HOUR)
+}
+
+/// `count` days in seconds.
+#[inline(always)]
+pub const fn days(count: u64) -> u64 {
+    count.saturating_mul(SECONDS_PER_DAY)
+}
+
+/// Whole days between the Unix epoch and `timestamp`.
+#[inline(always)]
+pub const fn days_since_epoch(timestamp: u64) -> u64 {
+    timestamp / SECONDS_PER_DAY
+}
+
+/// Seconds from `start` to `now`, or zero if `now` is earlier.
+#[inline(always)]
+pub const fn elapsed(start: u64, now: u64) -> u64 {
+    now.saturating_sub(start)
+}
+
+/// Index of the `length`-second period containing `timestamp`, counting from `start`.
+///
+/// Returns `None` before `start` or when `length` is zero.
+#[inline(always)]
+pub const fn period_index_at(timestamp: u64, start: u64, length: u64) -> Option<u64> {
+    if length == 0 || timestamp < start {
+        return None;
+    }
+    Some((timestamp - start) / length)
+}
+
+/// Index of the `length`-second period containing the current block time.
+#[inline(always)]
+pub fn period_index(start: u64, length: u64) -> Option<u64> {
+    period_index_at(get_time(), start, length)
+}
+
+/// Timestamp at which period `index` begins.
+#[inline(always)]
+pub const fn period_start(start: u64, length: u64, index: u64) -> u64 {
+    start.saturating_add(length.saturating_mul(index))
+}
+
+/// Timestamp `duration` seconds after `start`, saturating at `u64::MAX`.
+#[inline(always)]
+pub const fn deadline(start: u64, duration: u64) -> u64 {
+    start.saturating_add(duration)
+}
+
+/// Whether `deadline` has passed at `timestamp`.
+///
+/// A deadline is inclusive: an action is still allowed at exactly `deadline`.
+#[inline(always)]
+pub const fn is_expired_at(deadline: u64, timestamp: u64) -> bool {
+    timestamp > deadline
+}
+
+/// Whether `deadline` has passed at the current block time.
+#[inline(always)]
+pub fn is_expired(deadline: u64) -> bool {
+    is_expired_at(deadline, get_time())
+}
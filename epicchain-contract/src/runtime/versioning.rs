@@ -0,0 +1,46 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Versioned entry points.
+//!
+//! Once a method is part of a deployed contract's ABI, wallets and other contracts call it
+//! by name and argument list, so changing its signature breaks them. Instead, the new
+//! signature is published under a new name and the old method stays as an adapter that
+//! converts its arguments and forwards to it:
+//!
+//! ```ignore
+//! #[method]
+//! pub fn transfer_v2(&self, from: H160, to: H160, amount: Int256, memo: ByteString, data: Any) -> bool {
+//!     // ...
+//! }
+//!
+//! #[method]
+//! #[deprecated_method(replacement = "transfer_v2", since = "2.0.0")]
+//! pub fn transfer(&self, from: H160, to: H160, amount: Int256, data: Any) -> bool {
+//!     self.transfer_v2(from, to, amount, ByteString::empty(), data)
+//! }
+//! ```
+//!
+//! `#[contract_impl]` rejects a replacement that is not another, non-deprecated `#[method]`
+//! of the contract, and generates a safe `deprecated_methods()` method mapping each
+//! deprecated entry point to its replacement. The exported entry point of a deprecated
+//! method that is not `#[safe]` calls [`notify_deprecated_call`] before running the adapter,
+//! so integrators still on the old ABI can be found before it is removed.
+
+use crate::{
+    runtime::{get_calling_script_hash, notify_checked, EventPayload},
+    types::ByteString,
+};
+
+/// Event emitted when a deprecated entry point is called, with the method name, its
+/// replacement and the calling script hash.
+pub const DEPRECATED_CALL_EVENT: &str = "DeprecatedMethodCalled";
+
+/// Emits [`DEPRECATED_CALL_EVENT`] for a call to `method` by the calling script.
+pub fn notify_deprecated_call(method: &str, replacement: &str) {
+    let mut payload = EventPayload::new();
+    payload.push_bytes(ByteString::from_literal(method));
+    payload.push_bytes(ByteString::from_literal(replacement));
+    payload.push_hash160(get_calling_script_hash());
+    notify_checked(ByteString::from_literal(DEPRECATED_CALL_EVENT), payload);
+}
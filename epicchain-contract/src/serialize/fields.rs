@@ -0,0 +1,61 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Field-by-field encoding used by `#[derive(NeoSerialize)]`.
+//!
+//! Fields are written back to back with their [`EpicChainSerializable`] encoding, so the
+//! layout of a derived struct is the concatenation of its fields in declaration order.
+
+use crate::types::builtin::bytes::Bytes;
+use super::neo_serializable::{EpicChainSerializable, SerializationError};
+
+/// Appends encoded fields to a buffer.
+pub struct FieldWriter {
+    buffer: Vec<u8>,
+}
+
+impl FieldWriter {
+    pub fn new() -> Self {
+        Self { buffer: Vec::new() }
+    }
+
+    pub fn write<T: EpicChainSerializable>(&mut self, value: &T) {
+        self.buffer.extend_from_slice(value.to_bytes().as_slice());
+    }
+
+    pub fn finish(self) -> Bytes {
+        Bytes::from_slice(&self.buffer)
+    }
+}
+
+impl Default for FieldWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads encoded fields back in the order they were written.
+pub struct FieldReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> FieldReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    pub fn read<T: EpicChainSerializable>(&mut self) -> Result<T, SerializationError> {
+        let value = T::from_bytes(&self.bytes[self.offset..])?;
+        self.offset += value.serialized_size();
+        if self.offset > self.bytes.len() {
+            return Err(SerializationError::InsufficientData);
+        }
+        Ok(value)
+    }
+
+    /// Number of bytes consumed so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
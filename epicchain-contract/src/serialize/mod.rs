@@ -2,9 +2,11 @@
 // All Rights Reserved.
 
 pub mod deserialize;
+pub mod fields;
 pub mod serialize;
 pub mod neo_serializable;
 
 pub use deserialize::*;
+pub use fields::*;
 pub use serialize::*;
 pub use neo_serializable::*;
@@ -7,17 +7,36 @@ use crate::{env, types::{Any, Array, ByteString, CallFlags, H160, PublicKey}};
 #[cfg(not(target_family = "wasm"))]
 use crate::types::{Any, Array, ByteString, CallFlags, H160, PublicKey};
 
+#[cfg(target_family = "wasm")]
+use crate::types::placeholder::FromPlaceholder;
+
 /// Provides functionality for interacting with contracts.
 pub struct Contract;
 
+impl Contract {
+    /// Calls a method on another contract and reports whether it succeeded.
+    ///
+    /// Methods returning `false` or zero count as failed; void methods succeed once they return,
+    /// since a fault in the callee aborts the whole transaction.
+    #[inline(always)]
+    pub fn call_bool(contract: H160, method: ByteString, call_flags: CallFlags, args: Array<Any>) -> bool {
+        Self::call(contract, method, call_flags, args).is_success()
+    }
+}
+
 #[cfg(not(target_family = "wasm"))]
 impl Contract {
     /// Calls a method on another contract.
     #[inline(always)]
-    #[allow(unused_variables)]
-    pub fn call(_contract: H160, _method: ByteString, _call_flags: CallFlags, _args: Array<Any>) -> Any {
-        // For non-WASM targets (tests), return default Any
-        Any::default()
+    pub fn call(contract: H160, method: ByteString, call_flags: CallFlags, args: Array<Any>) -> Any {
+        // For non-WASM targets (tests), dispatch to the contracts registered with the host
+        crate::host::call_contract(contract, method, call_flags, args)
+    }
+
+    /// Calls a method on another contract and converts its return value to `T`.
+    #[inline(always)]
+    pub fn call_as<T: 'static>(contract: H160, method: ByteString, call_flags: CallFlags, args: Array<Any>) -> T {
+        Self::call(contract, method, call_flags, args).downcast_into()
     }
 
     /// Gets the call flags of the current execution context.
@@ -52,6 +71,12 @@ impl Contract {
         unsafe { env::syscall::system_contract_call(contract, method, call_flags, args) }
     }
 
+    /// Calls a method on another contract and converts its return value to `T`.
+    #[inline(always)]
+    pub fn call_as<T: 'static + FromPlaceholder>(contract: H160, method: ByteString, call_flags: CallFlags, args: Array<Any>) -> T {
+        Self::call(contract, method, call_flags, args).downcast_into()
+    }
+
     /// Gets the call flags of the current execution context.
     #[inline(always)]
     pub fn get_call_flags() -> CallFlags {
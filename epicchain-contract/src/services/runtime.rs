@@ -5,7 +5,7 @@
 use crate::{env, types::{Any, Array, ByteString, CallFlags, H160, Int256, Notification, PublicKey, Signer, TriggerType, Tx, FromByteString}};
 
 #[cfg(not(target_family = "wasm"))]
-use crate::types::{Any, Array, ByteString, CallFlags, H160, Int256, Notification, PublicKey, Signer, TriggerType, Tx};
+use crate::{host, types::{Any, Array, ByteString, CallFlags, H160, Int256, Notification, PublicKey, Signer, TriggerType, Tx}};
 
 /// Provides access to the execution environment of smart contracts.
 pub struct Runtime;
@@ -15,8 +15,7 @@ impl Runtime {
     /// Gets the trigger type of the execution.
     #[inline(always)]
     pub fn get_trigger() -> TriggerType {
-        // For non-WASM targets (tests), return Application trigger
-        TriggerType::Application
+        host::trigger()
     }
 
     /// Gets the platform name of the execution environment.
@@ -36,43 +35,37 @@ impl Runtime {
     /// Gets the script hash of the current executing script.
     #[inline(always)]
     pub fn get_executing_script_hash() -> H160 {
-        // For non-WASM targets (tests), return zero hash
-        H160::zero()
+        host::executing_script_hash()
     }
 
     /// Gets the script hash of the calling script.
     #[inline(always)]
     pub fn get_calling_script_hash() -> H160 {
-        // For non-WASM targets (tests), return zero hash
-        H160::zero()
+        host::calling_script_hash()
     }
 
     /// Gets the script hash of the entry script.
     #[inline(always)]
     pub fn get_entry_script_hash() -> H160 {
-        // For non-WASM targets (tests), return zero hash
-        H160::zero()
+        host::entry_script_hash()
     }
 
     /// Gets the timestamp of the current block.
     #[inline(always)]
     pub fn get_time() -> u64 {
-        // For non-WASM targets (tests), return current timestamp
-        1640995200 // 2022-01-01 00:00:00 UTC as default
+        host::time()
     }
 
     /// Gets the invocation counter of the current contract.
     #[inline(always)]
     pub fn get_invocation_counter() -> u32 {
-        // For non-WASM targets (tests), return 1
-        1
+        host::invocation_counter()
     }
 
     /// Gets the remaining GAS that can be spent in this execution.
     #[inline(always)]
     pub fn get_epicpulse_left() -> Int256 {
-        // For non-WASM targets (tests), return a large amount
-        Int256::new(1_000_000_000i64)
+        host::epicpulse_left()
     }
 
     /// Gets the address version of the current network.
@@ -92,10 +85,8 @@ impl Runtime {
 
     /// Determines whether the specified account has witnessed the current transaction.
     #[inline(always)]
-    #[allow(unused_variables)]
     pub fn check_witness(account: H160) -> bool {
-        // For non-WASM targets (tests), return true (allow all)
-        true
+        host::has_witness(account)
     }
 
     /// Determines whether the specified public key has witnessed the current transaction.
@@ -108,37 +99,32 @@ impl Runtime {
 
     /// Writes a log message to the execution log.
     #[inline(always)]
-    #[allow(unused_variables)]
     pub fn log(message: ByteString) {
-        // For non-WASM targets (tests), do nothing (no logging)
+        host::push_log(message)
     }
 
     /// Sends a notification to the execution environment.
     #[inline(always)]
-    #[allow(unused_variables)]
     pub fn notify(event_name: ByteString, state: Array<Any>) {
-        // For non-WASM targets (tests), do nothing (no notifications)
+        host::push_notification(event_name, state)
     }
 
     /// Burns the specified amount of GAS.
     #[inline(always)]
-    #[allow(unused_variables)]
     pub fn burn_epicpulse(amount: Int256) {
-        // For non-WASM targets (tests), do nothing (no gas burning)
+        host::burn_epicpulse(amount)
     }
 
     /// Gets a random number.
     #[inline(always)]
     pub fn get_random() -> Int256 {
-        // For non-WASM targets (tests), return a fixed "random" number
-        Int256::new(42)
+        host::next_random()
     }
 
     /// Gets the network ID of the current network.
     #[inline(always)]
     pub fn get_network() -> u32 {
-        // For non-WASM targets (tests), return mainnet ID
-        860833102 // EpicChain mainnet
+        host::network()
     }
 
     /// Load and execute a script with the specified call flags and arguments
@@ -156,7 +142,8 @@ impl Runtime {
         Any::default()
     }
 
-    /// Gets the current signers of the transaction.
+    /// Gets the current signers of the transaction. Requires protocol 3.6.
+    #[cfg(feature = "protocol-3-6")]
     #[inline(always)]
     pub fn current_signers() -> Array<Signer> {
         // For non-WASM targets (tests), return empty array
@@ -301,7 +288,8 @@ impl Runtime {
         }
     }
 
-    /// Gets the current signers of the transaction.
+    /// Gets the current signers of the transaction. Requires protocol 3.6.
+    #[cfg(feature = "protocol-3-6")]
     #[inline(always)]
     pub fn current_signers() -> Array<Signer> {
         unsafe { env::syscall::system_runtime_current_signers() }
@@ -5,20 +5,33 @@
 use crate::{env, types::{ByteString, Bytes, FindOptions, placeholder::FromPlaceholder}, storage::{StorageContext, ReadOnlyStorageContext, Iter as StorageIterator}};
 
 #[cfg(not(target_family = "wasm"))]
-use crate::{types::{ByteString, Bytes, FindOptions}, storage::{StorageContext, ReadOnlyStorageContext, Iter as StorageIterator}};
+use crate::{host, types::{ByteString, Bytes, FindOptions}, storage::{StorageContext, ReadOnlyStorageContext, Iter as StorageIterator}};
 
 /// Provides functionality for reading and writing to persistent storage.
 pub struct Storage;
 
+#[cfg(target_family = "wasm")]
+thread_local! {
+    // Context of the current invocation; a contract re-entered by one of its callees fetches it again
+    static CONTEXT: core::cell::RefCell<Option<StorageContext>> = core::cell::RefCell::new(None);
+}
+
 #[cfg(not(target_family = "wasm"))]
 impl Storage {
-    /// Gets the storage context for the current contract.
+    /// Gets the storage context for the current contract, fetched once per invocation.
     #[inline(always)]
     pub fn get_context() -> StorageContext {
-        // For non-WASM targets (tests), return a mock context
+        // For non-WASM targets (tests), return a mock context and count the fetch
+        host::fetch_storage_context();
         StorageContext::new()
     }
 
+    /// Drops the cached storage context, see [`crate::runtime::invocation::begin`].
+    #[inline(always)]
+    pub(crate) fn forget_context() {
+        host::forget_storage_context()
+    }
+
     /// Gets the read-only storage context for the current contract.
     #[inline(always)]
     pub fn get_read_only_context() -> ReadOnlyStorageContext {
@@ -35,40 +48,38 @@ impl Storage {
 
     /// Gets the value corresponding to the given key from storage.
     #[inline(always)]
-    pub fn get(_context: StorageContext, _key: ByteString) -> Option<ByteString> {
-        // For non-WASM targets (tests), return None (no persistent storage)
-        None
+    pub fn get(_context: StorageContext, key: ByteString) -> Option<ByteString> {
+        host::storage_get(key.as_bytes()).map(|value| ByteString::from_bytes(&value))
     }
 
     /// Gets the value corresponding to the given key from storage.
     #[inline(always)]
-    pub fn get_with_bytes_key(_context: StorageContext, _key: Bytes) -> Option<ByteString> {
-        // For non-WASM targets (tests), return None (no persistent storage)
-        None
+    pub fn get_with_bytes_key(_context: StorageContext, key: Bytes) -> Option<ByteString> {
+        host::storage_get(key.as_slice()).map(|value| ByteString::from_bytes(&value))
     }
 
     /// Puts the key-value pair into storage.
     #[inline(always)]
-    pub fn put(_context: StorageContext, _key: ByteString, _value: ByteString) {
-        // For non-WASM targets (tests), do nothing (no persistent storage)
+    pub fn put(_context: StorageContext, key: ByteString, value: ByteString) {
+        host::storage_put(key.as_bytes(), value.as_bytes())
     }
 
     /// Puts the key-value pair into storage.
     #[inline(always)]
-    pub fn put_with_bytes_key(_context: StorageContext, _key: Bytes, _value: ByteString) {
-        // For non-WASM targets (tests), do nothing (no persistent storage)
+    pub fn put_with_bytes_key(_context: StorageContext, key: Bytes, value: ByteString) {
+        host::storage_put(key.as_slice(), value.as_bytes())
     }
 
     /// Deletes the key-value pair from storage.
     #[inline(always)]
-    pub fn delete(_context: StorageContext, _key: ByteString) {
-        // For non-WASM targets (tests), do nothing (no persistent storage)
+    pub fn delete(_context: StorageContext, key: ByteString) {
+        host::storage_delete(key.as_bytes())
     }
 
     /// Deletes the key-value pair from storage.
     #[inline(always)]
-    pub fn delete_with_bytes_key(_context: StorageContext, _key: Bytes) {
-        // For non-WASM targets (tests), do nothing (no persistent storage)
+    pub fn delete_with_bytes_key(_context: StorageContext, key: Bytes) {
+        host::storage_delete(key.as_slice())
     }
 
     /// Finds the key-value pairs in storage that match the given prefix.
@@ -88,10 +99,21 @@ impl Storage {
 
 #[cfg(target_family = "wasm")]
 impl Storage {
-    /// Gets the storage context for the current contract.
+    /// Gets the storage context for the current contract, fetched once per invocation.
     #[inline(always)]
     pub fn get_context() -> StorageContext {
-        unsafe { env::syscall::system_storage_get_context() }
+        CONTEXT.with(|context| {
+            context
+                .borrow_mut()
+                .get_or_insert_with(|| unsafe { env::syscall::system_storage_get_context() })
+                .clone()
+        })
+    }
+
+    /// Drops the cached storage context, see [`crate::runtime::invocation::begin`].
+    #[inline(always)]
+    pub(crate) fn forget_context() {
+        CONTEXT.with(|context| *context.borrow_mut() = None)
     }
 
     /// Gets the read-only storage context for the current contract.
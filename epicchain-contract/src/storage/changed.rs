@@ -0,0 +1,55 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Storage writes coupled with a standardized `*Changed(old, new)` notification.
+//!
+//! Parameter setters (owner, fees, limits, ...) should use these helpers so that every
+//! update is observable by indexers. The event state is always `[old, new]`; a value
+//! that did not exist (or was deleted) is reported as null.
+
+use crate::{
+    services::{runtime::Runtime, storage::Storage},
+    storage::StorageContext,
+    types::{builtin::IntoAny, Any, Array, ByteString, FromByteString, IntoByteString},
+};
+
+/// Writes `value` under `key` and emits `event(old, new)` if the stored value changed.
+///
+/// Returns false, without writing or notifying, if `value` equals the stored value.
+pub fn put_changed<T>(context: StorageContext, key: ByteString, event: &str, value: T) -> bool
+where
+    T: IntoByteString + FromByteString + IntoAny + Clone,
+{
+    let encoded = value.clone().into_byte_string();
+    let old = Storage::get(context.clone(), key.clone());
+    if old.as_ref() == Some(&encoded) {
+        return false;
+    }
+
+    Storage::put(context, key, encoded);
+    notify_changed(event, old.map(|old| T::from_byte_string(old).into_any()), value.into_any());
+    true
+}
+
+/// Deletes `key` and emits `event(old, null)` if a value was stored.
+///
+/// Returns false if nothing was stored under `key`.
+pub fn delete_changed<T>(context: StorageContext, key: ByteString, event: &str) -> bool
+where
+    T: FromByteString + IntoAny,
+{
+    let Some(old) = Storage::get(context.clone(), key.clone()) else {
+        return false;
+    };
+
+    Storage::delete(context, key);
+    notify_changed(event, Some(T::from_byte_string(old).into_any()), Any::default());
+    true
+}
+
+fn notify_changed(event: &str, old: Option<Any>, new: Any) {
+    let mut state = Array::new();
+    state.push(old.unwrap_or_default());
+    state.push(new);
+    Runtime::notify(ByteString::from_literal(event), state);
+}
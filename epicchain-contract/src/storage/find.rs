@@ -0,0 +1,130 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Prefix scans over contract storage.
+//!
+//! [`Storage::find_prefix`] walks every entry whose key starts with a prefix, in ascending
+//! key order, through the `System.Storage.Find` syscall. The returned [`PrefixIter`] yields
+//! [`StorageEntry`] pairs and can be narrowed to keys, raw values or decoded values:
+//!
+//! ```ignore
+//! let holders = Storage::find_prefix(Storage::get_context(), prefix)
+//!     .remove_prefix()
+//!     .keys()
+//!     .count();
+//! ```
+
+#[cfg(target_family = "wasm")]
+use crate::{
+    env,
+    storage::Iter,
+    types::{placeholder::FromPlaceholder, FindOptions},
+};
+
+use crate::{
+    services::storage::Storage,
+    storage::StorageContext,
+    types::{ByteString, FromByteString},
+};
+
+/// A key/value pair returned by a storage scan.
+pub struct StorageEntry {
+    pub key: ByteString,
+    pub value: ByteString,
+}
+
+impl StorageEntry {
+    /// Decodes the value with [`FromByteString`].
+    pub fn value_as<T: FromByteString>(&self) -> T {
+        T::from_byte_string(self.value.clone())
+    }
+}
+
+/// Iterator over the entries under a storage prefix.
+pub struct PrefixIter {
+    prefix_len: usize,
+    remove_prefix: bool,
+    #[cfg(target_family = "wasm")]
+    entries: Iter<ByteString>,
+    #[cfg(not(target_family = "wasm"))]
+    entries: std::vec::IntoIter<(Vec<u8>, Vec<u8>)>,
+}
+
+impl PrefixIter {
+    fn new(
+        #[cfg_attr(not(target_family = "wasm"), allow(unused_variables))] context: StorageContext,
+        prefix: ByteString,
+    ) -> Self {
+        Self {
+            prefix_len: prefix.len(),
+            remove_prefix: false,
+            #[cfg(target_family = "wasm")]
+            entries: Storage::find(context, prefix, FindOptions::None),
+            #[cfg(not(target_family = "wasm"))]
+            entries: crate::host::storage_entries_with_prefix(prefix.as_bytes()).into_iter(),
+        }
+    }
+
+    /// Strips the scanned prefix from the returned keys.
+    pub fn remove_prefix(mut self) -> Self {
+        self.remove_prefix = true;
+        self
+    }
+
+    /// Yields only the keys.
+    pub fn keys(self) -> impl Iterator<Item = ByteString> {
+        self.map(|entry| entry.key)
+    }
+
+    /// Yields only the raw values.
+    pub fn values(self) -> impl Iterator<Item = ByteString> {
+        self.map(|entry| entry.value)
+    }
+
+    /// Yields the values decoded with [`FromByteString`].
+    pub fn values_as<T: FromByteString>(self) -> impl Iterator<Item = T> {
+        self.map(|entry| T::from_byte_string(entry.value))
+    }
+
+    fn next_raw(&mut self) -> Option<(ByteString, ByteString)> {
+        #[cfg(target_family = "wasm")]
+        {
+            if !self.entries.next() {
+                return None;
+            }
+            // Without options the iterator yields `Struct[key, value]`
+            let entry = self.entries.raw_value();
+            let key = ByteString::from_placeholder(unsafe { env::asm::array_get(entry, 0) });
+            let value = ByteString::from_placeholder(unsafe { env::asm::array_get(entry, 1) });
+            Some((key, value))
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        {
+            self.entries
+                .next()
+                .map(|(key, value)| (ByteString::from_bytes(&key), ByteString::from_bytes(&value)))
+        }
+    }
+}
+
+impl Iterator for PrefixIter {
+    type Item = StorageEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.next_raw()?;
+        let key = if self.remove_prefix {
+            key.substr(self.prefix_len, key.len() - self.prefix_len)
+        } else {
+            key
+        };
+        Some(StorageEntry { key, value })
+    }
+}
+
+impl Storage {
+    /// Iterates over the entries whose key starts with `prefix`, in ascending key order.
+    pub fn find_prefix(context: StorageContext, prefix: ByteString) -> PrefixIter {
+        PrefixIter::new(context, prefix)
+    }
+}
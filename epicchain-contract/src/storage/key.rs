@@ -0,0 +1,67 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Storage keys built from one-byte prefix tags.
+//!
+//! A `const` tag costs one byte per key and needs no `ByteString::from_literal` at runtime,
+//! unlike a string prefix stored in the contract struct. The built-in NEP-17 balances use the
+//! same layout ([`PREFIX_BALANCE`](crate::contract::nep17::PREFIX_BALANCE)).
+//!
+//! ```ignore
+//! const PREFIX_POOL: u8 = 0x01;
+//! const PREFIX_STAKE: u8 = 0x02;
+//!
+//! let pools = TypedStorageMap::<Int256, ByteString>::with_tag(PREFIX_POOL);
+//! let stake_key = KeyBuilder::new(PREFIX_STAKE).push(pool_id).push(user).build();
+//! ```
+
+#[allow(unused_imports)]
+use crate::{
+    env,
+    types::{ByteString, IntoByteString},
+};
+
+/// Returns `tag` followed by `key`.
+#[inline(always)]
+pub fn prefixed_key(tag: u8, key: ByteString) -> ByteString {
+    #[cfg(target_family = "wasm")]
+    return unsafe { env::extension::concat_u8_byte_string(tag, key) };
+
+    #[cfg(not(target_family = "wasm"))]
+    return ByteString::with_bytes(&[tag]).concat(&key);
+}
+
+/// Builds a composite storage key starting with a one-byte tag.
+pub struct KeyBuilder {
+    key: ByteString,
+}
+
+impl KeyBuilder {
+    #[inline(always)]
+    pub fn new(tag: u8) -> Self {
+        Self { key: prefixed_key(tag, ByteString::empty()) }
+    }
+
+    /// Appends the byte string encoding of `part`.
+    #[inline(always)]
+    pub fn push<T: IntoByteString>(self, part: T) -> Self {
+        Self { key: self.key.concat(&part.into_byte_string()) }
+    }
+
+    /// Appends `value` as 4 little-endian bytes.
+    #[inline(always)]
+    pub fn push_u32(self, value: u32) -> Self {
+        Self { key: self.key.concat(&ByteString::from_bytes(&value.to_le_bytes())) }
+    }
+
+    /// Appends `value` as 8 little-endian bytes.
+    #[inline(always)]
+    pub fn push_u64(self, value: u64) -> Self {
+        Self { key: self.key.concat(&ByteString::from_bytes(&value.to_le_bytes())) }
+    }
+
+    #[inline(always)]
+    pub fn build(self) -> ByteString {
+        self.key
+    }
+}
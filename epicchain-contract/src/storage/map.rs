@@ -20,10 +20,9 @@ pub struct StorageMap {
     cx: StorageContext,
 }
 
+/// Off-chain, every map shares the contract storage emulated by [`host`](crate::host).
 #[cfg(not(target_family = "wasm"))]
-pub struct StorageMap {
-    items: std::collections::BTreeMap<Vec<u8>, Vec<u8>>,
-}
+pub struct StorageMap;
 
 #[cfg(target_family = "wasm")]
 impl StorageMap {
@@ -57,22 +56,21 @@ impl StorageMap {
 #[cfg(not(target_family = "wasm"))]
 impl StorageMap {
     pub fn new() -> Self {
-        Self { items: std::collections::BTreeMap::new() }
+        Self
     }
 
     pub fn get(&self, key: ByteString) -> Nullable<ByteString> {
-        self.items
-            .get(key.as_bytes())
-            .map(|value| ByteString::with_bytes(value))
+        crate::host::storage_get(key.as_bytes())
+            .map(|value| ByteString::with_bytes(&value))
             .map(Nullable::new)
             .unwrap_or_default()
     }
 
     pub fn put(&mut self, key: ByteString, value: ByteString) {
-        self.items.insert(key.as_bytes().to_vec(), value.as_bytes().to_vec());
+        crate::host::storage_put(key.as_bytes(), value.as_bytes());
     }
 
     pub fn delete(&mut self, key: ByteString) {
-        self.items.remove(key.as_bytes());
+        crate::host::storage_delete(key.as_bytes());
     }
 }
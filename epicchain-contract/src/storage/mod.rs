@@ -1,10 +1,18 @@
 // Copyright @ 2024 - present, R3E Network
 // All Rights Reserved.
 
+pub mod changed;
+pub mod find;
+pub mod key;
 pub(crate) mod map;
+pub mod typed;
 
 // Export specific types from map module instead of using glob imports
+pub use changed::{delete_changed, put_changed};
+pub use find::{PrefixIter, StorageEntry};
+pub use key::{prefixed_key, KeyBuilder};
 pub use map::{StorageMap};
+pub use typed::{StorageMapIter, StorageVec, TypedStorageMap};
 pub use crate::types::storage::StorageItem;
 
 #[allow(unused_imports)]
@@ -107,6 +115,15 @@ impl<T: crate::types::placeholder::FromPlaceholder> Iter<T> {
     }
 }
 
+#[cfg(target_family = "wasm")]
+impl<T> Iter<T> {
+    /// Current value without conversion, for iterators yielding compound items.
+    #[inline(always)]
+    pub(crate) fn raw_value(&self) -> Placeholder {
+        unsafe { env::syscall::system_iterator_value(self.iter) }
+    }
+}
+
 #[cfg(target_family = "wasm")]
 crate::impl_placeholder!(StorageContext);
 
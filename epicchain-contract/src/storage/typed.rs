@@ -0,0 +1,258 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Typed collections over contract storage.
+//!
+//! Each collection owns a key prefix and converts keys and values with
+//! [`IntoByteString`]/[`FromByteString`], so values keep the same encoding as a manual
+//! `Storage::put(context, prefix.concat(&key.into_byte_string()), value.into_byte_string())`
+//! and existing storage layouts can be wrapped without a migration.
+//!
+//! ```ignore
+//! let balances = TypedStorageMap::<H160, Int256>::new("balance_");
+//! balances.add(from, amount.checked_neg());
+//! balances.add(to, amount);
+//! ```
+//!
+//! New layouts can use a one-byte tag instead of a string prefix, see [`key`](super::key).
+
+use core::marker::PhantomData;
+
+use crate::{
+    services::storage::Storage,
+    storage::{prefixed_key, PrefixIter},
+    types::{ByteString, FromByteString, Int256, IntoByteString},
+};
+
+/// A map from `K` to `V` stored under `prefix + key`.
+pub struct TypedStorageMap<K, V> {
+    prefix: ByteString,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> TypedStorageMap<K, V>
+where
+    K: IntoByteString,
+    V: IntoByteString + FromByteString,
+{
+    pub fn new(prefix: &str) -> Self {
+        Self::with_prefix(ByteString::from_literal(prefix))
+    }
+
+    pub fn with_prefix(prefix: ByteString) -> Self {
+        Self { prefix, _marker: PhantomData }
+    }
+
+    /// Uses the one-byte `tag` as prefix.
+    pub fn with_tag(tag: u8) -> Self {
+        Self::with_prefix(prefixed_key(tag, ByteString::empty()))
+    }
+
+    pub fn prefix(&self) -> &ByteString {
+        &self.prefix
+    }
+
+    /// Returns the storage key of `key`.
+    pub fn key(&self, key: K) -> ByteString {
+        self.prefix.concat(&key.into_byte_string())
+    }
+
+    pub fn get(&self, key: K) -> Option<V> {
+        Storage::get(Storage::get_context(), self.key(key)).map(V::from_byte_string)
+    }
+
+    pub fn get_or(&self, key: K, default: V) -> V {
+        self.get(key).unwrap_or(default)
+    }
+
+    pub fn put(&self, key: K, value: V) {
+        Storage::put(Storage::get_context(), self.key(key), value.into_byte_string());
+    }
+
+    pub fn delete(&self, key: K) {
+        Storage::delete(Storage::get_context(), self.key(key));
+    }
+
+    pub fn contains(&self, key: K) -> bool {
+        Storage::get(Storage::get_context(), self.key(key)).is_some()
+    }
+
+    /// Iterates over the entries in ascending key order.
+    pub fn iter(&self) -> StorageMapIter<K, V>
+    where
+        K: FromByteString,
+    {
+        StorageMapIter::new(&self.prefix)
+    }
+}
+
+impl<K: IntoByteString> TypedStorageMap<K, Int256> {
+    /// Returns the stored amount, or zero if `key` is absent.
+    pub fn get_or_zero(&self, key: K) -> Int256 {
+        self.get_or(key, Int256::zero())
+    }
+
+    /// Adds `delta` to the stored amount and returns the new amount.
+    ///
+    /// Zero amounts are deleted rather than stored, as native tokens do for balances.
+    pub fn add(&self, key: K, delta: Int256) -> Int256 {
+        let key = self.key(key);
+        let context = Storage::get_context();
+        let current = Storage::get(context.clone(), key.clone()).map(Int256::from_byte_string).unwrap_or_default();
+        let updated = current.checked_add(&delta);
+        if updated.is_zero() {
+            Storage::delete(context, key);
+        } else {
+            Storage::put(context, key, updated.into_byte_string());
+        }
+        updated
+    }
+}
+
+/// Iterator over the entries of a [`TypedStorageMap`].
+pub struct StorageMapIter<K, V> {
+    entries: PrefixIter,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> StorageMapIter<K, V> {
+    fn new(prefix: &ByteString) -> Self {
+        Self {
+            entries: Storage::find_prefix(Storage::get_context(), prefix.clone()).remove_prefix(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K: FromByteString, V: FromByteString> Iterator for StorageMapIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.entries.next()?;
+        Some((K::from_byte_string(entry.key), V::from_byte_string(entry.value)))
+    }
+}
+
+/// A list of `T` stored as `prefix + index` (u32 little-endian), with the length under `prefix`.
+pub struct StorageVec<T> {
+    prefix: ByteString,
+    _marker: PhantomData<T>,
+}
+
+impl<T> StorageVec<T>
+where
+    T: IntoByteString + FromByteString,
+{
+    pub fn new(prefix: &str) -> Self {
+        Self::with_prefix(ByteString::from_literal(prefix))
+    }
+
+    pub fn with_prefix(prefix: ByteString) -> Self {
+        Self { prefix, _marker: PhantomData }
+    }
+
+    /// Uses the one-byte `tag` as prefix.
+    pub fn with_tag(tag: u8) -> Self {
+        Self::with_prefix(prefixed_key(tag, ByteString::empty()))
+    }
+
+    pub fn len(&self) -> u32 {
+        match Storage::get(Storage::get_context(), self.prefix.clone()) {
+            Some(data) => {
+                let bytes = data.to_bytes();
+                if bytes.len() < 4 {
+                    return 0;
+                }
+                u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+            }
+            None => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: u32) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        Storage::get(Storage::get_context(), self.item_key(index)).map(T::from_byte_string)
+    }
+
+    /// Replaces the item at `index`. Returns false if `index` is out of bounds.
+    pub fn set(&self, index: u32, value: T) -> bool {
+        if index >= self.len() {
+            return false;
+        }
+        Storage::put(Storage::get_context(), self.item_key(index), value.into_byte_string());
+        true
+    }
+
+    pub fn push(&self, value: T) {
+        let len = self.len();
+        Storage::put(Storage::get_context(), self.item_key(len), value.into_byte_string());
+        self.set_len(len + 1);
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        let key = self.item_key(len - 1);
+        let value = Storage::get(Storage::get_context(), key.clone()).map(T::from_byte_string);
+        Storage::delete(Storage::get_context(), key);
+        self.set_len(len - 1);
+        value
+    }
+
+    /// Removes the item at `index`, moving the last item into its slot.
+    pub fn swap_remove(&self, index: u32) -> Option<T> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        let removed = self.get(index);
+        if index != len - 1 {
+            if let Some(last) = Storage::get(Storage::get_context(), self.item_key(len - 1)) {
+                Storage::put(Storage::get_context(), self.item_key(index), last);
+            }
+        }
+        Storage::delete(Storage::get_context(), self.item_key(len - 1));
+        self.set_len(len - 1);
+        removed
+    }
+
+    pub fn clear(&self) {
+        for index in 0..self.len() {
+            Storage::delete(Storage::get_context(), self.item_key(index));
+        }
+        Storage::delete(Storage::get_context(), self.prefix.clone());
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        (0..self.len()).filter_map(move |index| self.get(index))
+    }
+
+    fn item_key(&self, index: u32) -> ByteString {
+        self.prefix.concat(&ByteString::from_bytes(&index.to_le_bytes()))
+    }
+
+    fn set_len(&self, len: u32) {
+        if len == 0 {
+            Storage::delete(Storage::get_context(), self.prefix.clone());
+        } else {
+            Storage::put(Storage::get_context(), self.prefix.clone(), ByteString::from_bytes(&len.to_le_bytes()));
+        }
+    }
+}
+
+impl<T> StorageVec<T>
+where
+    T: IntoByteString + FromByteString + PartialEq,
+{
+    pub fn contains(&self, value: &T) -> bool {
+        self.iter().any(|item| &item == value)
+    }
+}
@@ -3,6 +3,7 @@
 
 #[allow(unused_imports)]
 use crate::types::{
+    consts::StackItemType,
     placeholder::{Placeholder, IntoPlaceholder, FromPlaceholder},
     builtin::{buffer::Buffer, h160::H160, h256::H256, int256::Int256, interop::Interop, string::ByteString, array::Array, map::Map, primitive::Primitive},
 };
@@ -33,15 +34,80 @@ impl Any {
     }
 
     #[inline(always)]
+    #[cfg(target_family = "wasm")]
     pub fn downcast_into<T: 'static + FromPlaceholder>(self) -> T {
+        T::from_placeholder(self.0)
+    }
+
+    // Off-chain values are boxed, so any `'static` type can be recovered (e.g. `Int256`,
+    // which has no placeholder conversion outside the VM)
+    #[inline(always)]
+    #[cfg(not(target_family = "wasm"))]
+    pub fn downcast_into<T: 'static>(self) -> T {
+        *self.0.downcast::<T>().expect("Type downcast failed")
+    }
+
+    /// Returns true for the VM null value (e.g. the result of a void method).
+    #[inline(always)]
+    pub fn is_null(&self) -> bool {
         #[cfg(target_family = "wasm")]
         {
-            T::from_placeholder(self.0)
+            self.0.is_null()
         }
 
         #[cfg(not(target_family = "wasm"))]
         {
-            *self.0.downcast::<T>().expect("Type downcast failed")
+            self.0.is::<()>()
+        }
+    }
+
+    /// Returns true if the value is a VM stack item of `item_type`; false for null.
+    #[inline(always)]
+    pub fn is_type(&self, item_type: StackItemType) -> bool {
+        #[cfg(target_family = "wasm")]
+        {
+            !self.is_null() && unsafe { crate::env::asm::is_type(self.0.clone(), item_type) }
+        }
+
+        // Off-chain values are checked against the Rust types the VM items map to
+        #[cfg(not(target_family = "wasm"))]
+        {
+            match item_type {
+                StackItemType::Boolean => self.0.is::<bool>(),
+                StackItemType::Integer => self.0.is::<Int256>(),
+                StackItemType::ByteString => self.0.is::<ByteString>() || self.0.is::<H160>() || self.0.is::<H256>(),
+                StackItemType::Buffer => self.0.is::<Buffer>(),
+                StackItemType::Array => self.0.is::<Array<Any>>(),
+                StackItemType::InteropInterface => self.0.is::<Interop>(),
+                StackItemType::Pointer | StackItemType::Struct | StackItemType::Map => false,
+            }
+        }
+    }
+
+    /// Interprets a contract call result as a success flag: `false` and zero integers are
+    /// failures. Null (a void method) and results of any other type are success.
+    #[inline(always)]
+    pub fn is_success(&self) -> bool {
+        #[cfg(target_family = "wasm")]
+        {
+            // Only Boolean and Integer items convert to an integer without faulting;
+            // booleans convert to 0/1
+            if self.is_type(StackItemType::Boolean) || self.is_type(StackItemType::Integer) {
+                !Int256::from_placeholder(self.0.clone()).is_zero()
+            } else {
+                true
+            }
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        {
+            if let Some(value) = self.0.downcast_ref::<bool>() {
+                *value
+            } else if let Some(value) = self.0.downcast_ref::<Int256>() {
+                !value.is_zero()
+            } else {
+                true
+            }
         }
     }
 }
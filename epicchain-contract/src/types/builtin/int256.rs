@@ -385,6 +385,30 @@ impl Ord for Int256 {
 #[cfg(target_family = "wasm")]
 crate::impl_placeholder!(Int256);
 
+// VM integers inside arrays and maps, e.g. the elements of an `Array<u32>` argument
+#[cfg(target_family = "wasm")]
+impl IntoPlaceholder for u32 {
+    #[inline(always)]
+    fn into_placeholder(self) -> Placeholder {
+        Int256::new(self as i64).into_placeholder()
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl FromPlaceholder for u32 {
+    /// Reads the low 32 bits from the integer's little-endian two's complement bytes, since
+    /// the VM has no narrowing conversion that the extension layer exposes.
+    fn from_placeholder(placeholder: Placeholder) -> Self {
+        let bytes = Int256::from_placeholder(placeholder).into_byte_string().to_bytes();
+        let fill = if bytes.last().is_some_and(|byte| byte & 0x80 != 0) { 0xFF } else { 0 };
+        let mut low = [fill; 4];
+        for (i, byte) in bytes.iter().take(4).enumerate() {
+            low[i] = *byte;
+        }
+        u32::from_le_bytes(low)
+    }
+}
+
 impl IntoByteString for Int256 {
     #[inline(always)]
     #[cfg(target_family = "wasm")]
@@ -251,5 +251,19 @@ pub trait FromByteString {
     fn from_byte_string(src: ByteString) -> Self;
 }
 
+impl IntoByteString for ByteString {
+    #[inline(always)]
+    fn into_byte_string(self) -> ByteString {
+        self
+    }
+}
+
+impl FromByteString for ByteString {
+    #[inline(always)]
+    fn from_byte_string(src: ByteString) -> Self {
+        src
+    }
+}
+
 // Implement Primitive trait for ByteString
 impl crate::types::builtin::primitive::Primitive for ByteString {}
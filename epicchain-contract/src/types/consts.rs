@@ -164,10 +164,12 @@ pub enum NamedCurveHash {
     /// The secp256r1 curve, which known as prime256v1 or nistP-256, and SHA256 hash algorithm.
     Secp256r1SHA256 = 23,
 
-    /// The secp256k1 curve and Keccak256 hash algorithm.
+    /// The secp256k1 curve and Keccak256 hash algorithm. Requires protocol 3.7.
+    #[cfg(feature = "protocol-3-7")]
     Secp256k1Keccak256 = 122,
 
-    /// The secp256r1 curve, which known as prime256v1 or nistP-256, and Keccak256 hash algorithm.
+    /// The secp256r1 curve, which known as prime256v1 or nistP-256, and Keccak256 hash algorithm. Requires protocol 3.7.
+    #[cfg(feature = "protocol-3-7")]
     Secp256r1Keccak256 = 123,
 }
 
@@ -291,3 +293,26 @@ pub enum ContractParamType {
 
     Void = 0xff,
 }
+
+/// Type of a VM stack item, as checked by `ISTYPE`.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackItemType {
+    Pointer = 0x10,
+
+    Boolean = 0x20,
+
+    Integer = 0x21,
+
+    ByteString = 0x28,
+
+    Buffer = 0x30,
+
+    Array = 0x40,
+
+    Struct = 0x41,
+
+    Map = 0x48,
+
+    InteropInterface = 0x60,
+}
@@ -9,6 +9,7 @@ pub(crate) mod contract;
 pub(crate) mod key;
 pub(crate) mod neo;
 pub(crate) mod notification;
+pub mod page;
 pub(crate) mod signer;
 pub mod storage;
 pub(crate) mod tx;
@@ -17,11 +18,12 @@ pub mod placeholder;
 
 // Export specific types instead of using glob imports
 pub use block::Block;
-pub use consts::{CallFlags, FindOptions, TriggerType, WitnessScope, WitnessRuleAction, WitnessConditionType, ContractParamType, OracleResponseCode, NamedCurveHash};
+pub use consts::{CallFlags, FindOptions, TriggerType, WitnessScope, WitnessRuleAction, WitnessConditionType, ContractParamType, OracleResponseCode, NamedCurveHash, StackItemType};
 pub use contract::Contract;
 pub use key::PublicKey;
 pub use epicchain::{NeoCandidate, NeoAccountState, TxAttrType, Role, VmState};
 pub use notification::Notification;
+pub use page::{Page, PageItem};
 pub use signer::Signer;
 pub use storage::{FindOptions as StorageFindOptions, StorageItem};
 pub use tx::Tx;
@@ -0,0 +1,111 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+#[allow(unused_imports)]
+use crate::types::{builtin::IntoAny, placeholder::*, Any, Array, Int256};
+
+/// One page of an enumeration view.
+///
+/// Crosses the ABI as an `Array` of `[items, next_cursor, total]`, so every paginated method
+/// decodes the same way: `next_cursor` is the cursor to pass for the following page, or -1
+/// once the last page has been returned, and `total` counts all items of the enumeration.
+pub struct Page<T> {
+    pub items: Array<T>,
+    pub next_cursor: Int256,
+    pub total: Int256,
+}
+
+impl<T> Page<T> {
+    #[inline(always)]
+    pub fn new(items: Array<T>, next_cursor: Int256, total: Int256) -> Self {
+        Self { items, next_cursor, total }
+    }
+
+    /// Whether another page follows this one
+    #[inline(always)]
+    pub fn has_more(&self) -> bool {
+        self.next_cursor >= Int256::zero()
+    }
+
+    /// The ABI form of the page: `[items, next_cursor, total]`
+    pub fn into_array(self) -> Array<Any>
+    where
+        T: 'static,
+    {
+        let mut array = Array::new();
+        array.push(self.items.into_any());
+        array.push(self.next_cursor.into_any());
+        array.push(self.total.into_any());
+        array
+    }
+}
+
+/// Item range `[start, end)` of the page at `cursor` and the cursor of the next page
+fn page_bounds(cursor: u32, limit: u32, total: usize) -> (usize, usize, Int256) {
+    let start = (cursor as usize).min(total);
+    let end = start.saturating_add(limit as usize).min(total);
+    let next_cursor = if end < total { Int256::new(end as i64) } else { Int256::minus_one() };
+    (start, end, next_cursor)
+}
+
+/// Item type a page can be built from: the bounds `Array::get` and `Array::push` need on
+/// the current target.
+#[cfg(not(target_family = "wasm"))]
+pub trait PageItem: Clone {}
+
+#[cfg(not(target_family = "wasm"))]
+impl<T: Clone> PageItem for T {}
+
+/// Item type a page can be built from: the bounds `Array::get` and `Array::push` need on
+/// the current target.
+#[cfg(target_family = "wasm")]
+pub trait PageItem: Default + FromPlaceholder + IntoPlaceholder {}
+
+#[cfg(target_family = "wasm")]
+impl<T: Default + FromPlaceholder + IntoPlaceholder> PageItem for T {}
+
+impl<T: PageItem> Page<T> {
+    /// The page of at most `limit` items of `all` starting at index `cursor`
+    pub fn from_array(all: &Array<T>, cursor: u32, limit: u32) -> Self {
+        let (start, end, next_cursor) = page_bounds(cursor, limit, all.size());
+        let mut items = Array::new();
+        for i in start..end {
+            items.push(all.get(i));
+        }
+        Self::new(items, next_cursor, Int256::new(all.size() as i64))
+    }
+
+    /// The page of at most `limit` items of `all` starting at index `cursor`, where `total`
+    /// counts the items of the whole enumeration.
+    ///
+    /// Only reads `all` up to one item past the page, so a storage scan can be paged without
+    /// loading every entry.
+    pub fn from_iterator(all: impl Iterator<Item = T>, cursor: u32, limit: u32, total: Int256) -> Self {
+        let mut rest = all.skip(cursor as usize);
+        let mut items = Array::new();
+        for item in rest.by_ref().take(limit as usize) {
+            items.push(item);
+        }
+        let next_cursor = if rest.next().is_some() {
+            Int256::new(cursor as i64 + items.size() as i64)
+        } else {
+            Int256::minus_one()
+        };
+        Self::new(items, next_cursor, total)
+    }
+}
+
+impl<T: 'static> IntoAny for Page<T> {
+    #[inline(always)]
+    fn into_any(self) -> Any {
+        self.into_array().into_any()
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl<T: 'static> IntoPlaceholder for Page<T> {
+    #[inline(always)]
+    fn into_placeholder(self) -> Placeholder {
+        self.into_array().into_placeholder()
+    }
+}
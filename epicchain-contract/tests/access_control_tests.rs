@@ -0,0 +1,69 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the `AccessControl` component.
+
+#![cfg(test)]
+
+use epicchain_contract::host;
+use epicchain_contract::prelude::*;
+use epicchain_contract::runtime::access;
+
+pub struct RolesContract;
+
+#[contract_impl]
+impl Ownable for RolesContract {}
+
+#[contract_impl]
+impl AccessControl for RolesContract {}
+
+fn owner() -> H160 {
+    H160::from_bytes(&[1u8; 20])
+}
+
+fn minter() -> H160 {
+    H160::from_bytes(&[2u8; 20])
+}
+
+fn deployed() {
+    host::storage_put(b"owner", &[1u8; 20]);
+}
+
+#[test]
+fn test_grant_requires_owner() {
+    deployed();
+    host::set_witnesses(&[minter()]);
+
+    assert!(!RolesContract::grant_role(ByteString::from_literal(MINTER_ROLE), minter()));
+    assert!(!RolesContract::has_role(ByteString::from_literal(MINTER_ROLE), minter()));
+}
+
+#[test]
+fn test_granted_roles_share_access_layout() {
+    deployed();
+    host::set_witnesses(&[owner()]);
+
+    assert!(RolesContract::grant_role(ByteString::from_literal(MINTER_ROLE), minter()));
+    assert!(!RolesContract::grant_role(ByteString::from_literal(MINTER_ROLE), minter()));
+    assert!(access::has_role(MINTER_ROLE, minter()));
+
+    assert!(RolesContract::revoke_role(ByteString::from_literal(MINTER_ROLE), minter()));
+    assert!(!access::has_role(MINTER_ROLE, minter()));
+    assert_eq!(
+        host::event_names(),
+        vec![ByteString::from_literal("RoleGranted"), ByteString::from_literal("RoleRevoked")]
+    );
+}
+
+#[test]
+fn test_renounce_requires_member_witness() {
+    deployed();
+    host::set_witnesses(&[owner()]);
+    assert!(RolesContract::grant_role(ByteString::from_literal(PAUSER_ROLE), minter()));
+
+    assert!(!RolesContract::renounce_role(ByteString::from_literal(PAUSER_ROLE), minter()));
+
+    host::set_witnesses(&[minter()]);
+    assert!(RolesContract::renounce_role(ByteString::from_literal(PAUSER_ROLE), minter()));
+    assert!(!RolesContract::has_role(ByteString::from_literal(PAUSER_ROLE), minter()));
+}
@@ -0,0 +1,90 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the `only_owner`, `only_role` and `only_contract` method attributes.
+
+#![cfg(test)]
+
+use epicchain_contract::prelude::*;
+use epicchain_contract::runtime::access;
+
+pub struct AccessContract;
+
+#[contract_impl]
+impl AccessContract {
+    pub fn init() -> Self {
+        Self
+    }
+
+    #[method]
+    #[only_owner]
+    pub fn owner_action(&self) -> bool {
+        true
+    }
+
+    #[method]
+    #[only_role("MINTER")]
+    pub fn minter_action(&self) -> bool {
+        true
+    }
+
+    #[method]
+    #[only_role("MINTER", account)]
+    pub fn minter_account_action(&self, account: H160) -> bool {
+        account != H160::zero()
+    }
+
+    #[method]
+    #[only_contract(H160::zero())]
+    pub fn trusted_contract_action(&self, value: Int256) -> Int256 {
+        value
+    }
+
+    #[method]
+    #[only_contract(self.trusted_contract())]
+    pub fn other_contract_action(&self) -> bool {
+        true
+    }
+
+    fn trusted_contract(&self) -> H160 {
+        H160::from_bytes(&[7u8; 20])
+    }
+}
+
+#[test]
+fn test_role_key_layout() {
+    let account = H160::from_bytes(&[1u8; 20]);
+    let expected = ByteString::from_literal("role_MINTER_").concat(&ByteString::from_bytes(&[1u8; 20]));
+    assert_eq!(access::role_key("MINTER", account).to_bytes(), expected.to_bytes());
+}
+
+#[test]
+#[should_panic(expected = "E01:Unauthorized: Only owner")]
+fn test_only_owner_rejects_without_owner() {
+    AccessContract::init().owner_action();
+}
+
+#[test]
+#[should_panic(expected = "E01:Unauthorized: Missing role")]
+fn test_only_role_rejects_caller_without_role() {
+    AccessContract::init().minter_action();
+}
+
+#[test]
+#[should_panic(expected = "E01:Unauthorized: Missing role")]
+fn test_only_role_rejects_account_without_role() {
+    AccessContract::init().minter_account_action(H160::from_bytes(&[1u8; 20]));
+}
+
+#[test]
+fn test_only_contract_accepts_expected_caller() {
+    // Off-chain, the calling script hash is the zero hash
+    let value = AccessContract::init().trusted_contract_action(Int256::new(42));
+    assert_eq!(value, Int256::new(42));
+}
+
+#[test]
+#[should_panic(expected = "E01:Unauthorized: Only contract")]
+fn test_only_contract_rejects_other_caller() {
+    AccessContract::init().other_contract_action();
+}
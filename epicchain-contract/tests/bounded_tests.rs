@@ -0,0 +1,27 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the bounded loop guard.
+
+#![cfg(test)]
+
+use epicchain_contract::prelude::*;
+
+#[test]
+fn test_bounded_iter_yields_every_index() {
+    let owners = Array::from_items(&[Int256::new(3), Int256::new(5), Int256::new(7)]);
+
+    let mut sum = Int256::zero();
+    for i in bounded_iter(&owners, 3) {
+        sum = sum.checked_add(&owners.get(i));
+    }
+    assert_eq!(sum, Int256::new(15));
+    assert_eq!(bounded_iter(&Array::<Int256>::new(), 0).count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "E09:LimitExceeded: Array exceeds iteration limit")]
+fn test_bounded_iter_aborts_past_the_limit() {
+    let recipients = Array::from_items(&[Int256::one(), Int256::one()]);
+    for _ in bounded_iter(&recipients, 1) {}
+}
@@ -0,0 +1,49 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for deferred call data encoding and decoding.
+
+#![cfg(test)]
+
+use epicchain_contract::contract::calldata;
+use epicchain_contract::prelude::*;
+
+#[test]
+fn test_round_trip() {
+    let method = ByteString::from_literal("set_fee");
+    let data = calldata::encode(&method, &[ByteString::from_bytes(&[30]), ByteString::empty()]).unwrap();
+
+    let (decoded, mut args) = calldata::decode(&data).unwrap();
+    assert_eq!(decoded, method);
+    assert_eq!(args.size(), 2);
+    let empty: ByteString = args.pop().downcast_into();
+    assert!(empty.is_empty());
+    let fee: ByteString = args.pop().downcast_into();
+    assert_eq!(fee.to_bytes(), vec![30]);
+}
+
+#[test]
+fn test_method_without_arguments() {
+    let data = calldata::encode(&ByteString::from_literal("pause"), &[]).unwrap();
+    let (method, args) = calldata::decode(&data).unwrap();
+    assert_eq!(method, ByteString::from_literal("pause"));
+    assert_eq!(args.size(), 0);
+}
+
+#[test]
+fn test_rejects_malformed_data() {
+    assert!(calldata::decode(&ByteString::empty()).is_none());
+    // Empty method name
+    assert!(calldata::decode(&ByteString::from_bytes(&[0])).is_none());
+    // Method name shorter than its length
+    assert!(calldata::decode(&ByteString::from_bytes(&[5, b'a', b'b'])).is_none());
+    // Truncated argument length
+    assert!(calldata::decode(&ByteString::from_bytes(&[1, b'a', 2])).is_none());
+    // Argument shorter than its length
+    assert!(calldata::decode(&ByteString::from_bytes(&[1, b'a', 3, 0, 1, 2])).is_none());
+}
+
+#[test]
+fn test_encode_rejects_empty_method() {
+    assert!(calldata::encode(&ByteString::empty(), &[]).is_none());
+}
@@ -102,6 +102,36 @@ impl PermissionTestContract {
     }
 }
 
+/// Test contract with scoped and justified wildcard permissions
+#[contract_permission("0xd2a4cff31913016155e38e474a2c06d08be276cf", "transfer", "balanceOf")]
+#[contract_permission("*", "onNEP17Payment, onNEP11Payment")]
+#[contract_permission(
+    "0x02a7bc55fe8684e0119768d104ba30795bdcc86619e864add26156723ed185cd62",
+    "*"
+)]
+#[contract_permission("*", "*", reason = "forwards calls chosen by the owner")]
+pub struct ScopedPermissionContract {
+    target: ByteString,
+}
+
+#[contract_impl]
+impl ScopedPermissionContract {
+    pub fn init() -> Self {
+        Self {
+            target: ByteString::from_literal("target"),
+        }
+    }
+
+    #[method]
+    pub fn forward(&self, contract: H160, method: ByteString) -> Any {
+        Contract::call(contract, method, CallFlags::ReadOnly, Array::new())
+    }
+}
+
+fn has_wildcard_permission<T: epicchain_contract::contract::permission::WildcardPermission>() -> bool {
+    true
+}
+
 /// Test contract with standards compliance
 #[contract_author("Standards Author")]
 #[contract_version("2.1.0")]
@@ -164,6 +194,19 @@ fn test_contract_instantiation() {
     assert!(!standards_contract.token_data.is_empty());
 }
 
+#[test]
+fn test_scoped_permissions() {
+    let scoped_contract = ScopedPermissionContract::init();
+    assert!(!scoped_contract.target.is_empty());
+
+    // Unknown contracts return null off-chain
+    let result = scoped_contract.forward(H160::zero(), ByteString::from_literal("symbol"));
+    assert!(result.is_null());
+
+    // Only an unjustified `*:*` permission carries the wildcard marker
+    assert!(has_wildcard_permission::<TestAttributeContract>());
+}
+
 #[test]
 fn test_safe_methods() {
     let test_contract = TestAttributeContract::init();
@@ -270,3 +313,15 @@ fn test_complex_contract_structure() {
     assert!(!method1_result.is_empty());
     assert!(!method2_result.is_empty());
 }
+
+/// Test contract declaring the author's email
+#[contract_author("Jane Doe", "jane@example.com")]
+pub struct AuthorWithEmailContract;
+
+#[test]
+fn test_generated_metadata_getters() {
+    assert_eq!(TestAttributeContract::contract_author().to_bytes(), b"Test Author");
+    assert_eq!(TestAttributeContract::contract_version().to_bytes(), b"1.0.0");
+    assert_eq!(MinimalContract::contract_author().to_bytes(), b"Minimal Author");
+    assert_eq!(AuthorWithEmailContract::contract_author().to_bytes(), b"Jane Doe <jane@example.com>");
+}
@@ -0,0 +1,55 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for standardized error codes.
+
+#![cfg(test)]
+
+use epicchain_contract::prelude::*;
+
+#[test]
+fn test_error_code_values_are_stable() {
+    assert_eq!(ErrorCode::Unauthorized.code(), 1);
+    assert_eq!(ErrorCode::Paused.code(), 2);
+    assert_eq!(ErrorCode::NotFound.code(), 3);
+    assert_eq!(ErrorCode::InsufficientBalance.code(), 4);
+    assert_eq!(ErrorCode::InvalidArgument.code(), 5);
+    assert_eq!(ErrorCode::Expired.code(), 6);
+    assert_eq!(ErrorCode::CallFailed.code(), 10);
+}
+
+#[test]
+fn test_error_code_round_trip() {
+    for code in 1u8..=10 {
+        let error = ErrorCode::from_code(code).expect("known error code");
+        assert_eq!(error.code(), code);
+    }
+    assert!(ErrorCode::from_code(0).is_none());
+    assert!(ErrorCode::from_code(11).is_none());
+}
+
+#[test]
+fn test_error_code_message_prefix() {
+    assert_eq!(ErrorCode::NotFound.prefix(), "E03:NotFound: ");
+
+    let message = ErrorCode::InsufficientBalance.message("Balance too low");
+    assert_eq!(message.to_bytes(), b"E04:InsufficientBalance: Balance too low");
+}
+
+#[test]
+fn test_assert_with_code_passes() {
+    assert_with_code(true, ErrorCode::InvalidArgument, "never raised");
+    require!(true, ErrorCode::Paused);
+}
+
+#[test]
+#[should_panic(expected = "E01:Unauthorized: Only owner")]
+fn test_require_aborts_with_prefix() {
+    require!(false, ErrorCode::Unauthorized, "Only owner");
+}
+
+#[test]
+#[should_panic(expected = "E06:Expired: Expired")]
+fn test_require_default_message() {
+    require!(false, ErrorCode::Expired);
+}
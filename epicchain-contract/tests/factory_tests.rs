@@ -0,0 +1,45 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the child-contract `Factory`.
+
+#![cfg(test)]
+
+use epicchain_contract::contract::Factory;
+use epicchain_contract::prelude::*;
+
+#[test]
+fn test_child_name_is_salted() {
+    let name = Factory::child_name(&ByteString::from_literal("Pool"), &ByteString::from_literal("42"));
+    assert_eq!(name, ByteString::from_literal("Pool-42"));
+}
+
+#[test]
+fn test_render_manifest_replaces_every_placeholder() {
+    let template = ByteString::from_literal(r#"{"name":"{{name}}","extra":{"Id":"{{name}}"}}"#);
+    let rendered = Factory::render_manifest(&template, &ByteString::from_literal("Dao-1"));
+    assert_eq!(rendered, ByteString::from_literal(r#"{"name":"Dao-1","extra":{"Id":"Dao-1"}}"#));
+}
+
+#[test]
+fn test_render_manifest_without_placeholder() {
+    let template = ByteString::from_literal(r#"{"name":"Static"}"#);
+    let rendered = Factory::render_manifest(&template, &ByteString::from_literal("Dao-1"));
+    assert_eq!(rendered, template);
+}
+
+#[test]
+fn test_deploy_fails_without_deployed_contract() {
+    // Off-chain, ContractManagement::deploy returns a contract with the zero hash
+    let factory = Factory::new("factory_");
+    let deployed = factory.deploy(
+        ByteString::from_literal("nef"),
+        &ByteString::from_literal(r#"{"name":"{{name}}"}"#),
+        &ByteString::from_literal("Pool"),
+        &ByteString::from_literal("1"),
+    );
+
+    assert!(deployed.is_none());
+    assert_eq!(factory.child_count(), Int256::zero());
+    assert!(factory.child_of(&ByteString::from_literal("Pool"), &ByteString::from_literal("1")).is_none());
+}
@@ -0,0 +1,69 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for storage prefix scans.
+
+#![cfg(test)]
+
+use epicchain_contract::prelude::*;
+use epicchain_contract::types::IntoByteString;
+
+fn put(key: &str, value: ByteString) {
+    Storage::put(Storage::get_context(), ByteString::from_literal(key), value);
+}
+
+fn seed() {
+    put("owner_b", ByteString::from_literal("2"));
+    put("owner_a", ByteString::from_literal("1"));
+    put("ownerless", ByteString::from_literal("x"));
+    put("props_a", ByteString::from_literal("y"));
+}
+
+#[test]
+fn test_find_prefix_returns_matching_entries_in_order() {
+    seed();
+
+    let entries: Vec<StorageEntry> = Storage::find_prefix(Storage::get_context(), ByteString::from_literal("owner_")).collect();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].key, ByteString::from_literal("owner_a"));
+    assert_eq!(entries[0].value, ByteString::from_literal("1"));
+    assert_eq!(entries[1].key, ByteString::from_literal("owner_b"));
+}
+
+#[test]
+fn test_find_prefix_keys_only_with_prefix_removed() {
+    seed();
+
+    let keys: Vec<ByteString> = Storage::find_prefix(Storage::get_context(), ByteString::from_literal("owner_"))
+        .remove_prefix()
+        .keys()
+        .collect();
+    assert_eq!(keys, vec![ByteString::from_literal("a"), ByteString::from_literal("b")]);
+}
+
+#[test]
+fn test_find_prefix_values_only() {
+    seed();
+
+    let values: Vec<ByteString> = Storage::find_prefix(Storage::get_context(), ByteString::from_literal("owner"))
+        .values()
+        .collect();
+    assert_eq!(values.len(), 3);
+}
+
+#[test]
+fn test_find_prefix_decoded_values() {
+    Storage::put(Storage::get_context(), ByteString::from_literal("bal_1"), Int256::new(10).into_byte_string());
+    Storage::put(Storage::get_context(), ByteString::from_literal("bal_2"), Int256::new(32).into_byte_string());
+
+    let total = Storage::find_prefix(Storage::get_context(), ByteString::from_literal("bal_"))
+        .values_as::<Int256>()
+        .fold(Int256::zero(), |sum, value| sum.checked_add(&value));
+    assert_eq!(total, Int256::new(42));
+}
+
+#[test]
+fn test_find_prefix_without_matches() {
+    seed();
+    assert_eq!(Storage::find_prefix(Storage::get_context(), ByteString::from_literal("missing_")).count(), 0);
+}
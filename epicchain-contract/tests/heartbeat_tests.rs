@@ -0,0 +1,99 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the dead-man switch.
+
+#![cfg(test)]
+
+use epicchain_contract::host;
+use epicchain_contract::prelude::*;
+
+const CAN_WITHDRAW: u32 = 1;
+const CAN_RECONFIGURE: u32 = 2;
+
+fn owner() -> H160 {
+    H160::from_bytes(&[1u8; 20])
+}
+
+fn heir() -> H160 {
+    H160::from_bytes(&[2u8; 20])
+}
+
+fn armed_switch() -> DeadManSwitch {
+    let switch = DeadManSwitch::new("dms_");
+    switch.configure(owner(), heir(), days(30), CAN_WITHDRAW);
+    switch
+}
+
+#[test]
+fn test_unarmed_switch_never_triggers() {
+    let switch = DeadManSwitch::new("dms_");
+    assert!(!switch.is_armed());
+    assert!(!switch.is_triggered_at(u64::MAX));
+    assert_eq!(switch.owner(), H160::zero());
+}
+
+#[test]
+fn test_configure_records_heartbeat() {
+    let switch = armed_switch();
+    assert!(switch.is_armed());
+    assert_eq!(switch.recovery(), heir());
+    assert_eq!(switch.silence_period(), days(30));
+    assert_eq!(switch.capabilities(), CAN_WITHDRAW);
+    assert_eq!(switch.last_heartbeat(), host::time());
+    assert_eq!(switch.triggers_at(), host::time() + days(30));
+}
+
+#[test]
+fn test_triggers_after_silence_period() {
+    let switch = armed_switch();
+
+    host::advance_time(days(30));
+    assert!(!switch.is_triggered());
+
+    host::advance_time(1);
+    assert!(switch.is_triggered());
+    assert!(switch.grants(CAN_WITHDRAW));
+}
+
+#[test]
+fn test_heartbeat_requires_owner_witness() {
+    let switch = armed_switch();
+    host::advance_time(days(20));
+
+    host::set_witnesses(&[heir()]);
+    assert!(!switch.heartbeat());
+
+    host::set_witnesses(&[owner()]);
+    assert!(switch.heartbeat());
+    assert_eq!(switch.last_heartbeat(), host::time());
+
+    host::advance_time(days(20));
+    assert!(!switch.is_triggered());
+}
+
+#[test]
+fn test_only_granted_capabilities_can_be_exercised() {
+    let switch = armed_switch();
+    host::advance_time(days(31));
+
+    host::set_witnesses(&[heir()]);
+    assert!(switch.can_exercise(CAN_WITHDRAW));
+    assert!(!switch.can_exercise(CAN_RECONFIGURE));
+    assert!(!switch.can_exercise(CAN_WITHDRAW | CAN_RECONFIGURE));
+    assert!(!switch.can_exercise(0));
+
+    host::set_witnesses(&[owner()]);
+    assert!(!switch.can_exercise(CAN_WITHDRAW));
+}
+
+#[test]
+fn test_disarm_clears_state() {
+    let switch = armed_switch();
+    host::advance_time(days(31));
+
+    switch.disarm();
+    assert!(!switch.is_armed());
+    assert!(!switch.grants(CAN_WITHDRAW));
+    assert_eq!(host::storage_entries_with_prefix(b"dms_").len(), 0);
+}
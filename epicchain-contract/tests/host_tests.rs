@@ -0,0 +1,224 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the emulated host backing non-WASM builds.
+
+#![cfg(test)]
+
+use epicchain_contract::contract::native::Ledger;
+use epicchain_contract::host;
+use epicchain_contract::prelude::*;
+use epicchain_contract::runtime::{self, access};
+use epicchain_contract::types::{builtin::IntoAny, IntoByteString, StackItemType};
+
+#[test]
+fn test_storage_persists_between_calls() {
+    let key = ByteString::from_literal("counter");
+    Storage::put(Storage::get_context(), key.clone(), ByteString::from_literal("7"));
+
+    assert_eq!(Storage::get(Storage::get_context(), key.clone()), Some(ByteString::from_literal("7")));
+    assert_eq!(host::storage_len(), 1);
+
+    Storage::delete(Storage::get_context(), key.clone());
+    assert!(Storage::get(Storage::get_context(), key).is_none());
+}
+
+#[test]
+fn test_put_changed_skips_unchanged_value() {
+    let key = ByteString::from_literal("fee");
+
+    assert!(put_changed(Storage::get_context(), key.clone(), "FeeChanged", Int256::new(30)));
+    assert!(!put_changed(Storage::get_context(), key, "FeeChanged", Int256::new(30)));
+    assert_eq!(host::event_names(), vec![ByteString::from_literal("FeeChanged")]);
+}
+
+#[test]
+fn test_clock_and_block_context() {
+    assert_eq!(Runtime::get_time(), host::DEFAULT_TIME);
+    assert_eq!(Ledger::current_block_index(), 0);
+
+    host::advance_blocks(4);
+    assert_eq!(Ledger::current_block_index(), 4);
+    assert_eq!(runtime::get_time(), host::DEFAULT_TIME + 4 * host::BLOCK_TIME_SECS);
+
+    host::set_time(2_000_000_000);
+    host::advance_time(5);
+    assert_eq!(Runtime::get_time(), 2_000_000_005);
+    assert_eq!(Ledger::current_block_index(), 4);
+}
+
+#[test]
+fn test_witnesses_can_be_restricted() {
+    let owner = H160::from_bytes(&[1u8; 20]);
+    let other = H160::from_bytes(&[2u8; 20]);
+    assert!(Runtime::check_witness(other));
+
+    host::set_witnesses(&[owner]);
+    assert!(Runtime::check_witness(owner));
+    assert!(runtime::check_witness_with_account(owner));
+    assert!(!Runtime::check_witness(other));
+
+    host::allow_all_witnesses();
+    assert!(Runtime::check_witness(other));
+}
+
+#[test]
+fn test_owner_check_uses_stored_owner_and_witnesses() {
+    let owner = H160::from_bytes(&[3u8; 20]);
+    Storage::put(Storage::get_context(), ByteString::from_literal(access::OWNER_KEY), owner.into_byte_string());
+
+    host::set_witnesses(&[owner]);
+    assert!(access::is_owner());
+
+    host::set_witnesses(&[]);
+    assert!(!access::is_owner());
+}
+
+#[test]
+fn test_invocation_context() {
+    let caller = H160::from_bytes(&[4u8; 20]);
+    host::set_calling_script_hash(caller);
+
+    assert_eq!(Runtime::get_calling_script_hash(), caller);
+    assert!(access::is_calling_contract(caller));
+}
+
+#[test]
+fn test_random_is_reproducible_from_seed() {
+    host::set_random_seed(7);
+    let first = (Runtime::get_random(), Runtime::get_random());
+    assert_ne!(first.0, first.1);
+
+    host::set_random_seed(7);
+    assert_eq!((Runtime::get_random(), Runtime::get_random()), first);
+}
+
+#[test]
+fn test_logs_and_notifications_are_captured() {
+    Runtime::log(ByteString::from_literal("hello"));
+    let mut payload = EventPayload::new();
+    payload.push_int(Int256::new(1));
+    notify_checked(ByteString::from_literal("Ping"), payload);
+
+    assert_eq!(host::logs(), vec![ByteString::from_literal("hello")]);
+    let notifications = host::take_notifications();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].event_name, ByteString::from_literal("Ping"));
+    assert!(host::event_names().is_empty());
+}
+
+#[test]
+fn test_reset_restores_defaults() {
+    Storage::put(Storage::get_context(), ByteString::from_literal("k"), ByteString::from_literal("v"));
+    host::advance_blocks(1);
+    host::set_witnesses(&[]);
+
+    host::reset();
+    assert_eq!(host::storage_len(), 0);
+    assert_eq!(Ledger::current_block_index(), 0);
+    assert!(Runtime::check_witness(H160::zero()));
+}
+
+#[test]
+fn test_contract_call_dispatches_to_registered_handler() {
+    let token = H160::from_bytes(&[7u8; 20]);
+    let caller = H160::from_bytes(&[1u8; 20]);
+    host::set_executing_script_hash(caller);
+    host::register_contract(token, move |method, _args| {
+        assert_eq!(Runtime::get_executing_script_hash(), token);
+        assert_eq!(Runtime::get_calling_script_hash(), caller);
+        if *method == ByteString::from_literal("balanceOf") {
+            Int256::new(42).into_any()
+        } else {
+            Int256::zero().into_any()
+        }
+    });
+
+    let balance: Int256 = Contract::call_as(token, ByteString::from_literal("balanceOf"), CallFlags::ReadOnly, Array::new());
+    assert_eq!(balance, Int256::new(42));
+    assert!(!Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, Array::new()));
+    assert_eq!(Runtime::get_executing_script_hash(), caller);
+
+    let calls = host::contract_calls();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[1].method, ByteString::from_literal("transfer"));
+    assert_eq!(calls[1].call_flags, CallFlags::All);
+}
+
+#[test]
+fn test_storage_is_scoped_to_the_executing_contract() {
+    let key = ByteString::from_literal("admin");
+    let first = H160::from_bytes(&[3u8; 20]);
+    let second = H160::from_bytes(&[4u8; 20]);
+
+    host::set_executing_script_hash(first);
+    Storage::put(Storage::get_context(), key.clone(), ByteString::from_literal("a"));
+    host::set_executing_script_hash(second);
+    assert!(Storage::get(Storage::get_context(), key.clone()).is_none());
+    Storage::put(Storage::get_context(), key.clone(), ByteString::from_literal("b"));
+
+    host::register_contract(first, move |_, _| Storage::get(Storage::get_context(), ByteString::from_literal("admin")).unwrap().into_any());
+    let seen: ByteString = Contract::call_as(first, ByteString::from_literal("admin"), CallFlags::ReadOnly, Array::new());
+    assert_eq!(seen, ByteString::from_literal("a"));
+    assert_eq!(Storage::get(Storage::get_context(), key), Some(ByteString::from_literal("b")));
+    assert_eq!(host::storage_len(), 2);
+}
+
+#[test]
+fn test_contract_call_to_unknown_contract_returns_null() {
+    let result = Contract::call(H160::from_bytes(&[9u8; 20]), ByteString::from_literal("update"), CallFlags::All, Array::new());
+    assert!(result.is_success());
+}
+
+#[test]
+fn test_call_results_interpreted_by_stack_item_type() {
+    assert!(!Int256::zero().into_any().is_success());
+    assert!(Int256::one().into_any().is_success());
+    // Only Boolean and Integer results are success flags
+    assert!(ByteString::empty().into_any().is_success());
+    assert!(Int256::zero().into_any().is_type(StackItemType::Integer));
+    assert!(H160::zero().into_any().is_type(StackItemType::ByteString));
+    assert!(!Any::default().is_type(StackItemType::Integer));
+}
+
+#[test]
+fn test_storage_usage_counts_writes_and_deletes() {
+    let key = ByteString::from_literal("key");
+
+    let (_, usage) = host::measure_storage(|| {
+        Storage::put(Storage::get_context(), key.clone(), ByteString::from_literal("12345"));
+        Storage::put(Storage::get_context(), key.clone(), ByteString::from_literal("12"));
+    });
+    assert_eq!(usage.puts, 2);
+    assert_eq!(usage.bytes_written, 8 + 5);
+    assert_eq!(usage.net_bytes, 5);
+
+    let (_, usage) = host::measure_storage(|| {
+        Storage::delete(Storage::get_context(), key.clone());
+        Storage::delete(Storage::get_context(), key.clone());
+    });
+    assert_eq!(usage.deletes, 1);
+    assert_eq!(usage.bytes_deleted, 5);
+    assert_eq!(usage.net_bytes, -5);
+}
+
+#[test]
+fn test_storage_usage_is_attributed_to_the_executing_contract() {
+    let callee = H160::from_bytes(&[5u8; 20]);
+    host::register_contract(callee, |_, _| {
+        Storage::put(Storage::get_context(), ByteString::from_literal("k"), ByteString::from_literal("v"));
+        Any::default()
+    });
+
+    let (_, usage) = host::measure_storage(|| {
+        Storage::put(Storage::get_context(), ByteString::from_literal("own"), ByteString::from_literal("1"));
+        Contract::call(callee, ByteString::from_literal("write"), CallFlags::All, Array::new());
+    });
+    assert_eq!(usage.puts, 2);
+    assert_eq!(host::storage_usage(callee).bytes_written, 2);
+    assert_eq!(host::storage_usage(H160::zero()).bytes_written, 4);
+
+    host::reset_storage_usage();
+    assert_eq!(host::total_storage_usage(), host::StorageUsage::default());
+    assert_eq!(host::storage_len(), 2);
+}
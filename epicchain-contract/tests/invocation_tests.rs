@@ -0,0 +1,65 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the per-invocation setup of exported methods.
+
+#![cfg(test)]
+
+use epicchain_contract::host;
+use epicchain_contract::prelude::*;
+use epicchain_contract::types::{FromByteString, IntoByteString};
+
+pub struct Registry;
+
+#[contract_impl]
+impl Registry {
+    pub fn init() -> Self {
+        Self
+    }
+
+    #[method]
+    pub fn register(&self, key: ByteString, value: ByteString) -> bool {
+        if self.contains(key.clone()) {
+            return false;
+        }
+        Storage::put(Storage::get_context(), key, value);
+        self.bump_count();
+        true
+    }
+
+    #[method]
+    #[safe]
+    pub fn contains(&self, key: ByteString) -> bool {
+        Storage::get(Storage::get_context(), key).is_some()
+    }
+
+    #[method]
+    #[safe]
+    pub fn count(&self) -> Int256 {
+        match Storage::get(Storage::get_context(), ByteString::from_literal("count")) {
+            Some(count) => Int256::from_byte_string(count),
+            None => Int256::zero(),
+        }
+    }
+
+    fn bump_count(&self) {
+        let count = self.count().checked_add(&Int256::one());
+        Storage::put(Storage::get_context(), ByteString::from_literal("count"), count.into_byte_string());
+    }
+}
+
+#[test]
+fn test_internal_calls_share_the_storage_context() {
+    // `register` runs `contains`, `count` and `bump_count` in the same invocation
+    assert!(register(ByteString::from_literal("a"), ByteString::from_literal("1")));
+    assert_eq!(host::storage_context_fetches(), 1);
+    assert_eq!(count(), Int256::one());
+}
+
+#[test]
+fn test_each_invocation_fetches_its_own_context() {
+    assert!(register(ByteString::from_literal("a"), ByteString::from_literal("1")));
+    assert!(!register(ByteString::from_literal("a"), ByteString::from_literal("2")));
+    assert!(contains(ByteString::from_literal("a")));
+    assert_eq!(host::storage_context_fetches(), 3);
+}
@@ -0,0 +1,59 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the integer math helpers.
+
+#![cfg(test)]
+
+use epicchain_contract::prelude::*;
+
+#[test]
+fn test_isqrt_rounds_down() {
+    assert_eq!(math::isqrt(Int256::zero()), Int256::zero());
+    assert_eq!(math::isqrt(Int256::new(16)), Int256::new(4));
+    assert_eq!(math::isqrt(Int256::new(17)), Int256::new(4));
+    assert_eq!(math::isqrt(Int256::new(1_000_000).checked_mul(&Int256::new(4_000_000))), Int256::new(2_000_000));
+}
+
+#[test]
+#[should_panic(expected = "E05:InvalidArgument: Square root of a negative value")]
+fn test_isqrt_rejects_negative() {
+    math::isqrt(Int256::minus_one());
+}
+
+#[test]
+fn test_min_max() {
+    assert_eq!(math::min(Int256::new(3), Int256::new(7)), Int256::new(3));
+    assert_eq!(math::max(Int256::new(3), Int256::new(7)), Int256::new(7));
+    assert_eq!(math::min(Int256::new(5), Int256::new(5)), Int256::new(5));
+}
+
+#[test]
+fn test_mul_div_rounds_down() {
+    assert_eq!(math::mul_div(Int256::new(10), Int256::new(10), Int256::new(3)), Int256::new(33));
+    assert_eq!(math::mul_div(Int256::new(7), Int256::zero(), Int256::new(3)), Int256::zero());
+    assert_eq!(math::mul_div(Int256::new(2), Int256::new(5), Int256::new(20)), Int256::zero());
+}
+
+#[test]
+fn test_mul_div_survives_an_overflowing_product() {
+    // 10^40 * 10^40 overflows 256 bits, the result 10^60 does not
+    let big = math::pow10(40);
+    assert_eq!(math::mul_div(big, big, math::pow10(20)), math::pow10(60));
+    assert_eq!(
+        math::mul_div(big.checked_add(&Int256::one()), big, math::pow10(20)),
+        math::pow10(60).checked_add(&math::pow10(20))
+    );
+}
+
+#[test]
+#[should_panic(expected = "E05:InvalidArgument: Invalid mul_div operands")]
+fn test_mul_div_rejects_zero_denominator() {
+    math::mul_div(Int256::one(), Int256::one(), Int256::zero());
+}
+
+#[test]
+fn test_pow10() {
+    assert_eq!(math::pow10(0), Int256::one());
+    assert_eq!(math::pow10(8), Int256::new(100_000_000));
+}
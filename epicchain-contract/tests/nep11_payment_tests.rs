@@ -0,0 +1,50 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the NEP-11 `onNEP11Payment` receiver callback.
+
+#![cfg(test)]
+
+use std::{cell::RefCell, rc::Rc};
+
+use epicchain_contract::contract::invoke_on_nep11_payment;
+use epicchain_contract::host;
+use epicchain_contract::prelude::*;
+use epicchain_contract::types::builtin::IntoAny;
+
+fn account(byte: u8) -> H160 {
+    H160::from_bytes(&[byte; 20])
+}
+
+#[test]
+fn test_callback_skipped_for_plain_accounts() {
+    invoke_on_nep11_payment(account(1), account(2), Int256::one(), ByteString::from_literal("nft-1"), Any::default());
+    assert!(host::contract_calls().is_empty());
+}
+
+#[test]
+fn test_callback_receives_token_id() {
+    let received = Rc::new(RefCell::new(Vec::new()));
+    let log = received.clone();
+    host::register_contract(account(2), move |method, mut args| {
+        assert_eq!(args.size(), 4);
+        let _data = args.pop();
+        let token_id: ByteString = args.pop().downcast_into();
+        log.borrow_mut().push((method.clone(), token_id));
+        Int256::one().into_any()
+    });
+
+    invoke_on_nep11_payment(account(1), account(2), Int256::one(), ByteString::from_literal("nft-1"), Any::default());
+
+    let received = received.borrow();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].0, ByteString::from_literal("onNEP11Payment"));
+    assert_eq!(received[0].1, ByteString::from_literal("nft-1"));
+}
+
+#[test]
+#[should_panic(expected = "onNEP11Payment rejected")]
+fn test_rejected_token_aborts_transfer() {
+    host::register_contract(account(2), |_, _| Int256::zero().into_any());
+    invoke_on_nep11_payment(account(1), account(2), Int256::one(), ByteString::from_literal("nft-1"), Any::default());
+}
@@ -0,0 +1,67 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the NEP-17 `onNEP17Payment` receiver callback.
+
+#![cfg(test)]
+
+use std::{cell::RefCell, rc::Rc};
+
+use epicchain_contract::contract::invoke_on_nep17_payment;
+use epicchain_contract::host;
+use epicchain_contract::prelude::*;
+use epicchain_contract::types::builtin::IntoAny;
+
+fn account(byte: u8) -> H160 {
+    H160::from_bytes(&[byte; 20])
+}
+
+#[test]
+fn test_callback_skipped_for_plain_accounts() {
+    invoke_on_nep17_payment(account(1), account(2), Int256::new(10), Any::default());
+    assert!(host::contract_calls().is_empty());
+}
+
+#[test]
+fn test_callback_invoked_on_contract_recipient() {
+    let received = Rc::new(RefCell::new(Vec::new()));
+    let log = received.clone();
+    host::register_contract(account(2), move |method, mut args| {
+        assert_eq!(args.size(), 3);
+        let _data = args.pop();
+        let amount: Int256 = args.pop().downcast_into();
+        let from: H160 = args.pop().downcast_into();
+        log.borrow_mut().push((method.clone(), from, amount));
+        Any::default()
+    });
+
+    invoke_on_nep17_payment(account(1), account(2), Int256::new(10), Any::default());
+
+    let received = received.borrow();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].0, ByteString::from_literal("onNEP17Payment"));
+    assert_eq!(received[0].1, account(1));
+    assert_eq!(received[0].2, Int256::new(10));
+}
+
+#[test]
+fn test_callback_passes_null_sender_for_mints() {
+    let sender_is_null = Rc::new(RefCell::new(false));
+    let flag = sender_is_null.clone();
+    host::register_contract(account(2), move |_, mut args| {
+        args.pop();
+        args.pop();
+        *flag.borrow_mut() = args.pop().is_null();
+        Any::default()
+    });
+
+    invoke_on_nep17_payment(H160::zero(), account(2), Int256::new(10), Any::default());
+    assert!(*sender_is_null.borrow());
+}
+
+#[test]
+#[should_panic(expected = "onNEP17Payment rejected")]
+fn test_rejected_payment_aborts_transfer() {
+    host::register_contract(account(2), |_, _| Int256::zero().into_any());
+    invoke_on_nep17_payment(account(1), account(2), Int256::new(10), Any::default());
+}
@@ -0,0 +1,93 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for size-checked notifications.
+
+#![cfg(test)]
+
+use epicchain_contract::prelude::*;
+use epicchain_contract::types::IntoByteString;
+use epicchain_contract::runtime::{
+    check_notification, is_valid_event_name, MAX_EVENT_NAME_LENGTH, MAX_NOTIFICATION_SIZE,
+};
+
+fn bytes_of_len(len: usize) -> ByteString {
+    ByteString::from_bytes(&vec![b'x'; len])
+}
+
+#[test]
+fn test_payload_size_accounting() {
+    let mut payload = EventPayload::new();
+    assert!(payload.is_empty());
+    // Empty array: type byte + item count
+    assert_eq!(payload.size(), 2);
+
+    payload.push_hash160(H160::zero());
+    assert_eq!(payload.size(), 2 + 22);
+
+    payload.push_bytes(ByteString::from_literal("memo"));
+    assert_eq!(payload.size(), 2 + 22 + 6);
+
+    let int_len = Int256::new(1).into_byte_string().len();
+    payload.push_int(Int256::new(1));
+    assert_eq!(payload.size(), 2 + 22 + 6 + 2 + int_len);
+    assert_eq!(payload.len(), 3);
+}
+
+#[test]
+fn test_notify_checked_accepts_payload_within_limits() {
+    let mut payload = EventPayload::new();
+    payload.push_hash160(H160::zero());
+    payload.push_int(Int256::new(100));
+    notify_checked(ByteString::from_literal("Transfer"), payload);
+}
+
+#[test]
+#[should_panic(expected = "E09:LimitExceeded: Event name must be 1-32 bytes")]
+fn test_notify_checked_rejects_long_event_name() {
+    notify_checked(bytes_of_len(MAX_EVENT_NAME_LENGTH + 1), EventPayload::new());
+}
+
+#[test]
+#[should_panic(expected = "E09:LimitExceeded: Notification payload too large")]
+fn test_notify_checked_rejects_oversized_payload() {
+    let mut payload = EventPayload::new();
+    payload.push_bytes(bytes_of_len(MAX_NOTIFICATION_SIZE));
+    notify_checked(ByteString::from_literal("Memo"), payload);
+}
+
+#[test]
+fn test_payload_at_size_limit_is_accepted() {
+    let mut payload = EventPayload::new();
+    // Array header (2) + byte string header (3 for lengths >= 0xFD)
+    payload.push_bytes(bytes_of_len(MAX_NOTIFICATION_SIZE - 5));
+    assert_eq!(payload.size(), MAX_NOTIFICATION_SIZE);
+    check_notification(&ByteString::from_literal("Memo"), &payload);
+}
+
+#[test]
+fn test_push_bytes_truncated_fits_payload() {
+    let mut payload = EventPayload::new();
+    payload.push_hash160(H160::zero());
+
+    let truncated = payload.push_bytes_truncated(bytes_of_len(2 * MAX_NOTIFICATION_SIZE));
+    assert!(truncated);
+    assert!(payload.size() <= MAX_NOTIFICATION_SIZE);
+    assert_eq!(payload.remaining(), MAX_NOTIFICATION_SIZE - payload.size());
+    check_notification(&ByteString::from_literal("Memo"), &payload);
+}
+
+#[test]
+fn test_push_bytes_truncated_keeps_short_values() {
+    let mut payload = EventPayload::new();
+    assert!(!payload.push_bytes_truncated(ByteString::from_literal("short")));
+    assert_eq!(payload.size(), 2 + 7);
+}
+
+#[test]
+fn test_event_name_validation_is_const() {
+    const VALID: bool = is_valid_event_name("LiquidityZapped");
+    assert!(VALID);
+    assert!(!is_valid_event_name(""));
+    assert!(!is_valid_event_name("ThisEventNameIsWayTooLongForTheVM"));
+}
@@ -0,0 +1,82 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the two-step `Ownable` handoff.
+
+#![cfg(test)]
+
+use epicchain_contract::host;
+use epicchain_contract::prelude::*;
+
+pub struct OwnedContract;
+
+#[contract_impl]
+impl Ownable for OwnedContract {}
+
+fn owner() -> H160 {
+    H160::from_bytes(&[1u8; 20])
+}
+
+fn successor() -> H160 {
+    H160::from_bytes(&[2u8; 20])
+}
+
+fn deployed() {
+    host::storage_put(b"owner", &[1u8; 20]);
+}
+
+#[test]
+fn test_transfer_requires_owner_witness() {
+    deployed();
+    host::set_witnesses(&[successor()]);
+
+    assert!(!OwnedContract::transfer_ownership(successor()));
+    assert_eq!(OwnedContract::pending_owner(), H160::zero());
+}
+
+#[test]
+fn test_ownership_moves_only_on_accept() {
+    deployed();
+    host::set_witnesses(&[owner()]);
+    assert!(OwnedContract::transfer_ownership(successor()));
+    assert_eq!(OwnedContract::owner(), owner());
+    assert_eq!(OwnedContract::pending_owner(), successor());
+
+    // Only the nominated account can complete the handoff
+    assert!(!OwnedContract::accept_ownership());
+
+    host::set_witnesses(&[successor()]);
+    assert!(OwnedContract::accept_ownership());
+    assert_eq!(OwnedContract::owner(), successor());
+    assert_eq!(OwnedContract::pending_owner(), H160::zero());
+
+    assert_eq!(
+        host::event_names(),
+        vec![ByteString::from_literal("OwnershipTransferStarted"), ByteString::from_literal("OwnershipTransferred")]
+    );
+}
+
+#[test]
+fn test_zero_nomination_cancels_pending_handoff() {
+    deployed();
+    host::set_witnesses(&[owner()]);
+    assert!(OwnedContract::transfer_ownership(successor()));
+    assert!(OwnedContract::transfer_ownership(H160::zero()));
+
+    host::set_witnesses(&[successor()]);
+    assert!(!OwnedContract::accept_ownership());
+    assert_eq!(OwnedContract::owner(), owner());
+}
+
+#[test]
+fn test_renounce_keeps_owner_key() {
+    deployed();
+    host::set_witnesses(&[owner()]);
+    assert!(OwnedContract::transfer_ownership(successor()));
+    assert!(OwnedContract::renounce_ownership());
+
+    assert_eq!(OwnedContract::owner(), H160::zero());
+    assert_eq!(OwnedContract::pending_owner(), H160::zero());
+    assert!(host::storage_get(b"owner").is_some());
+    assert!(!OwnedContract::transfer_ownership(successor()));
+}
@@ -0,0 +1,86 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the pagination return type.
+
+#![cfg(test)]
+
+use epicchain_contract::prelude::*;
+use epicchain_contract::types::builtin::IntoAny;
+
+fn numbers(count: i64) -> Array<Int256> {
+    let mut all = Array::new();
+    for i in 0..count {
+        all.push(Int256::new(i));
+    }
+    all
+}
+
+#[test]
+fn test_page_walks_the_whole_array() {
+    let all = numbers(5);
+
+    let first = Page::from_array(&all, 0, 2);
+    assert_eq!(first.items.size(), 2);
+    assert_eq!(first.items.get(0), Int256::new(0));
+    assert_eq!(first.next_cursor, Int256::new(2));
+    assert_eq!(first.total, Int256::new(5));
+    assert!(first.has_more());
+
+    let last = Page::from_array(&all, 4, 2);
+    assert_eq!(last.items.size(), 1);
+    assert_eq!(last.items.get(0), Int256::new(4));
+    assert_eq!(last.next_cursor, Int256::minus_one());
+    assert!(!last.has_more());
+}
+
+#[test]
+fn test_page_past_the_end_is_empty() {
+    let all = numbers(3);
+
+    let exact = Page::from_array(&all, 0, 3);
+    assert_eq!(exact.items.size(), 3);
+    assert!(!exact.has_more());
+
+    let beyond = Page::from_array(&all, 10, 3);
+    assert_eq!(beyond.items.size(), 0);
+    assert_eq!(beyond.next_cursor, Int256::minus_one());
+    assert_eq!(beyond.total, Int256::new(3));
+
+    let unbounded = Page::from_array(&all, u32::MAX, u32::MAX);
+    assert_eq!(unbounded.items.size(), 0);
+}
+
+#[test]
+fn test_page_abi_layout() {
+    let page = Page::from_array(&numbers(4), 1, 2);
+
+    // [items, next_cursor, total]
+    let mut array: Array<Any> = page.into_any().downcast_into();
+    assert_eq!(array.size(), 3);
+    assert_eq!(array.pop().downcast_into::<Int256>(), Int256::new(4));
+    assert_eq!(array.pop().downcast_into::<Int256>(), Int256::new(3));
+
+    let items: Array<Int256> = array.pop().downcast_into();
+    assert_eq!(items.size(), 2);
+    assert_eq!(items.get(0), Int256::new(1));
+    assert_eq!(items.get(1), Int256::new(2));
+}
+
+#[test]
+fn test_page_from_iterator() {
+    let first = Page::from_iterator((0..5).map(Int256::new), 0, 2, Int256::new(5));
+    assert_eq!(first.items.size(), 2);
+    assert_eq!(first.items.get(1), Int256::new(1));
+    assert_eq!(first.next_cursor, Int256::new(2));
+    assert_eq!(first.total, Int256::new(5));
+
+    let last = Page::from_iterator((0..5).map(Int256::new), 3, 2, Int256::new(5));
+    assert_eq!(last.items.size(), 2);
+    assert_eq!(last.items.get(0), Int256::new(3));
+    assert!(!last.has_more());
+
+    let beyond = Page::from_iterator((0..5).map(Int256::new), 10, 2, Int256::new(5));
+    assert_eq!(beyond.items.size(), 0);
+    assert_eq!(beyond.next_cursor, Int256::minus_one());
+}
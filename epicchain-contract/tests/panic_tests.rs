@@ -0,0 +1,42 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the panic message forwarding.
+
+#![cfg(test)]
+
+use std::panic::Location;
+
+use epicchain_contract::runtime::panic::{install_panic_hook, panic_message};
+
+#[test]
+fn test_panic_message_payloads() {
+    assert_eq!(panic_message(&"balance underflow", None), "balance underflow");
+    assert_eq!(panic_message(&String::from("index 3 out of range"), None), "index 3 out of range");
+    assert_eq!(panic_message(&42u32, None), "panic");
+}
+
+#[test]
+fn test_panic_message_location_in_debug_builds() {
+    let location = Location::caller();
+    let message = panic_message(&"assertion failed", Some(location));
+
+    if cfg!(debug_assertions) {
+        assert_eq!(message, format!("assertion failed at {}:{}:{}", location.file(), location.line(), location.column()));
+    } else {
+        assert_eq!(message, "assertion failed");
+    }
+}
+
+#[test]
+fn test_panic_message_from_caught_panic() {
+    install_panic_hook();
+
+    let payload = std::panic::catch_unwind(|| {
+        let amount: Option<u32> = None;
+        amount.expect("amount required")
+    })
+    .unwrap_err();
+
+    assert_eq!(panic_message(payload.as_ref(), None), "amount required");
+}
@@ -0,0 +1,61 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the `Pausable` component.
+
+#![cfg(test)]
+
+use epicchain_contract::host;
+use epicchain_contract::prelude::*;
+
+pub struct PausableContract;
+
+#[contract_impl]
+impl Ownable for PausableContract {}
+
+#[contract_impl]
+impl Pausable for PausableContract {}
+
+fn owner() -> H160 {
+    H160::from_bytes(&[1u8; 20])
+}
+
+fn deployed() {
+    host::storage_put(b"owner", &[1u8; 20]);
+}
+
+#[test]
+fn test_pause_requires_owner() {
+    deployed();
+    host::set_witnesses(&[H160::from_bytes(&[2u8; 20])]);
+
+    assert!(!PausableContract::pause());
+    assert!(!PausableContract::is_paused());
+}
+
+#[test]
+fn test_pause_and_unpause() {
+    deployed();
+    host::set_witnesses(&[owner()]);
+
+    assert!(PausableContract::pause());
+    assert!(PausableContract::is_paused());
+    assert!(!PausableContract::pause());
+
+    assert!(PausableContract::unpause());
+    assert!(!PausableContract::is_paused());
+    assert_eq!(
+        host::event_names(),
+        vec![ByteString::from_literal("Paused"), ByteString::from_literal("Unpaused")]
+    );
+}
+
+#[test]
+#[should_panic(expected = "E02:Paused: Contract is paused")]
+fn test_when_not_paused_aborts_while_paused() {
+    deployed();
+    host::set_witnesses(&[owner()]);
+    PausableContract::pause();
+
+    PausableContract::when_not_paused();
+}
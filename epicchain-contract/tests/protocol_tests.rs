@@ -0,0 +1,25 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Tests for the protocol version selected through the `protocol-*` features.
+
+#![cfg(test)]
+
+use epicchain_contract::protocol;
+
+#[test]
+fn test_default_targets_latest_protocol() {
+    assert_eq!(protocol::VERSION, (3, 8));
+    assert!(protocol::supports(3, 6));
+    assert!(protocol::supports(3, 8));
+    assert!(!protocol::supports(3, 9));
+    assert!(!protocol::supports(4, 0));
+}
+
+#[test]
+fn test_gated_apis_available_on_latest_protocol() {
+    use epicchain_contract::prelude::*;
+
+    assert_eq!(current_signers().size(), 0);
+    assert!(epicchain_contract::crypto::verify_ed25519(ByteString::empty(), PublicKey::default(), ByteString::empty()));
+}
@@ -0,0 +1,125 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for contract-to-contract event subscriptions.
+
+#![cfg(test)]
+
+use std::{cell::RefCell, rc::Rc};
+
+use epicchain_contract::host;
+use epicchain_contract::runtime::pubsub::MAX_SUBSCRIBERS_PER_TOPIC;
+use epicchain_contract::prelude::*;
+
+fn topic() -> ByteString {
+    ByteString::from_literal("PriceUpdated")
+}
+
+fn consumer(byte: u8) -> H160 {
+    H160::from_bytes(&[byte; 20])
+}
+
+fn callback() -> ByteString {
+    ByteString::from_literal("onPriceUpdated")
+}
+
+/// Registers a consumer that records its calls and burns `burn` gas per call.
+fn register_consumer(hash: H160, burn: i64) -> Rc<RefCell<Vec<ByteString>>> {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let recorded = calls.clone();
+    host::register_contract(hash, move |method, _args| {
+        recorded.borrow_mut().push(method.clone());
+        Runtime::burn_epicpulse(Int256::new(burn));
+        Any::default()
+    });
+    calls
+}
+
+#[test]
+fn test_subscribe_and_unsubscribe() {
+    let subscriptions = Subscriptions::new("subs_");
+    assert!(subscriptions.subscribe(topic(), consumer(1), callback(), Int256::new(100)));
+    assert!(subscriptions.is_subscribed(topic(), consumer(1)));
+    assert_eq!(subscriptions.subscription(topic(), consumer(1)), Some((callback(), Int256::new(100))));
+
+    // Subscribing again replaces the callback and budget
+    assert!(subscriptions.subscribe(topic(), consumer(1), ByteString::from_literal("onTick"), Int256::new(5)));
+    assert_eq!(subscriptions.subscriber_count(topic()), 1);
+    assert_eq!(subscriptions.subscription(topic(), consumer(1)), Some((ByteString::from_literal("onTick"), Int256::new(5))));
+
+    assert!(subscriptions.unsubscribe(topic(), consumer(1)));
+    assert!(!subscriptions.unsubscribe(topic(), consumer(1)));
+    assert_eq!(subscriptions.subscriber_count(topic()), 0);
+}
+
+#[test]
+fn test_subscribe_rejects_invalid_parameters() {
+    let subscriptions = Subscriptions::new("subs_");
+    assert!(!subscriptions.subscribe(ByteString::empty(), consumer(1), callback(), Int256::new(100)));
+    assert!(!subscriptions.subscribe(topic(), consumer(1), ByteString::empty(), Int256::new(100)));
+    assert!(!subscriptions.subscribe(topic(), H160::zero(), callback(), Int256::new(100)));
+    assert!(!subscriptions.subscribe(topic(), consumer(1), callback(), Int256::zero()));
+}
+
+#[test]
+fn test_topics_do_not_overlap() {
+    let subscriptions = Subscriptions::new("subs_");
+    assert!(subscriptions.subscribe(ByteString::from_literal("Price"), consumer(1), callback(), Int256::new(100)));
+    assert!(subscriptions.subscribe(ByteString::from_literal("PriceUpdated"), consumer(2), callback(), Int256::new(100)));
+
+    assert_eq!(subscriptions.subscriber_count(ByteString::from_literal("Price")), 1);
+    assert_eq!(subscriptions.subscribers(ByteString::from_literal("Price")).get(0), consumer(1));
+}
+
+#[test]
+fn test_subscribers_per_topic_are_capped() {
+    let subscriptions = Subscriptions::new("subs_");
+    for byte in 1..=MAX_SUBSCRIBERS_PER_TOPIC as u8 {
+        assert!(subscriptions.subscribe(topic(), consumer(byte), callback(), Int256::new(100)));
+    }
+
+    assert!(!subscriptions.subscribe(topic(), consumer(100), callback(), Int256::new(100)));
+    // Existing subscribers can still update their subscription
+    assert!(subscriptions.subscribe(topic(), consumer(1), callback(), Int256::new(200)));
+}
+
+#[test]
+fn test_publish_calls_every_subscriber() {
+    let subscriptions = Subscriptions::new("subs_");
+    let first = register_consumer(consumer(1), 10);
+    let second = register_consumer(consumer(2), 10);
+    subscriptions.subscribe(topic(), consumer(1), callback(), Int256::new(100));
+    subscriptions.subscribe(topic(), consumer(2), ByteString::from_literal("onTick"), Int256::new(100));
+
+    let report = subscriptions.publish(topic(), Array::new());
+    assert_eq!(report, PublishReport { delivered: 2, evicted: 0, skipped: 0 });
+    assert_eq!(*first.borrow(), vec![callback()]);
+    assert_eq!(*second.borrow(), vec![ByteString::from_literal("onTick")]);
+}
+
+#[test]
+fn test_publish_evicts_subscribers_over_budget() {
+    let subscriptions = Subscriptions::new("subs_");
+    register_consumer(consumer(1), 500);
+    register_consumer(consumer(2), 10);
+    subscriptions.subscribe(topic(), consumer(1), callback(), Int256::new(100));
+    subscriptions.subscribe(topic(), consumer(2), callback(), Int256::new(100));
+
+    let report = subscriptions.publish(topic(), Array::new());
+    assert_eq!(report, PublishReport { delivered: 2, evicted: 1, skipped: 0 });
+    assert!(!subscriptions.is_subscribed(topic(), consumer(1)));
+    assert!(subscriptions.is_subscribed(topic(), consumer(2)));
+}
+
+#[test]
+fn test_publish_skips_subscribers_without_enough_gas() {
+    let subscriptions = Subscriptions::new("subs_");
+    let calls = register_consumer(consumer(1), 10);
+    subscriptions.subscribe(topic(), consumer(1), callback(), Int256::new(1_000));
+    host::set_epicpulse_left(Int256::new(999));
+
+    let report = subscriptions.publish(topic(), Array::new());
+    assert_eq!(report, PublishReport { delivered: 0, evicted: 0, skipped: 1 });
+    assert!(calls.borrow().is_empty());
+    assert!(subscriptions.is_subscribed(topic(), consumer(1)));
+}
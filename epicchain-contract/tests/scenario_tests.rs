@@ -0,0 +1,117 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for scenario scripts on the emulated host.
+
+#![cfg(test)]
+
+use epicchain_contract::host::{self, scenario::{Scenario, Value}};
+use epicchain_contract::prelude::*;
+use epicchain_contract::types::builtin::IntoAny;
+
+fn balances() -> TypedStorageMap<H160, Int256> {
+    TypedStorageMap::with_prefix(ByteString::from_literal("balance_"))
+}
+
+fn emit_transfer(from: H160, to: H160, amount: Int256) {
+    let mut state = Array::new();
+    state.push(from.into_any());
+    state.push(to.into_any());
+    state.push(amount.into_any());
+    Runtime::notify(ByteString::from_literal("Transfer"), state);
+}
+
+/// A minimal token whose transfers require the sender's witness
+fn token_scenario() -> Scenario {
+    Scenario::new()
+        .actor("alice", H160::from_bytes(&[1u8; 20]))
+        .actor("bob", H160::from_bytes(&[2u8; 20]))
+        .action("mints", |actor, args| {
+            balances().put(actor, balances().get_or_zero(actor).checked_add(&args.int(0)));
+            emit_transfer(H160::zero(), actor, args.int(0));
+        })
+        .action("transfers", |actor, args| {
+            let (amount, to) = (args.int(0), args.account(1));
+            let balance = balances().get_or_zero(actor);
+            if !Runtime::check_witness(actor) || balance < amount {
+                Runtime::log(ByteString::from_literal("Insufficient balance"));
+                return;
+            }
+            balances().put(actor, balance.checked_sub(&amount));
+            balances().put(to, balances().get_or_zero(to).checked_add(&amount));
+            emit_transfer(actor, to, amount);
+        })
+        .query("balance", |args| balances().get_or_zero(args.account(0)).into())
+}
+
+#[test]
+fn test_script_drives_actions_and_checks_events() {
+    let mut scenario = token_scenario();
+    scenario.run(r#"
+        # mint, then move part of it
+        alice mints 1_000
+        expect event Transfer(null, alice, 1000)
+        alice transfers 400 to bob
+        expect event Transfer(alice, bob, _)
+        expect no event Transfer
+        expect balance alice 600
+        expect balance of bob 400
+        bob transfers 500 to alice
+        expect no event Transfer
+        expect log "Insufficient balance"
+    "#);
+}
+
+#[test]
+fn test_actions_run_with_only_the_actor_witnessing() {
+    let mut scenario = token_scenario().action("checks", |_, args| {
+        assert!(!Runtime::check_witness(args.account(0)));
+    });
+    scenario.run("alice checks bob");
+    assert!(Runtime::check_witness(scenario.account("bob")));
+}
+
+#[test]
+fn test_advance_moves_clock_and_blocks() {
+    let mut scenario = Scenario::new();
+    scenario.run("
+        advance 1 day
+        advance 2 hours
+        advance 3 blocks
+    ");
+    assert_eq!(host::time(), host::DEFAULT_TIME + 86_400 + 7_200 + 3 * host::BLOCK_TIME_SECS);
+    assert_eq!(host::block_index(), 3);
+}
+
+#[test]
+fn test_values_match_loosely() {
+    assert!(Value::Null.matches(&Value::Account(H160::zero())));
+    assert!(Value::Wildcard.matches(&Value::Other));
+    assert!(!Value::Int(Int256::new(1)).matches(&Value::Int(Int256::new(2))));
+}
+
+#[test]
+#[should_panic(expected = "scenario line 2 `expect balance bob 1`: expected")]
+fn test_failed_expectation_reports_the_step() {
+    let mut scenario = token_scenario();
+    scenario.run("alice mints 5\nexpect balance bob 1");
+}
+
+#[test]
+#[should_panic(expected = "no log of the last action contains")]
+fn test_log_expectation_ignores_earlier_actions() {
+    let mut scenario = token_scenario();
+    scenario.run("
+        bob transfers 5 to alice
+        expect log \"Insufficient balance\"
+        alice mints 5
+        expect log \"Insufficient balance\"
+    ");
+}
+
+#[test]
+#[should_panic(expected = "no matching event")]
+fn test_event_expectation_requires_matching_arguments() {
+    let mut scenario = token_scenario();
+    scenario.run("alice mints 5\nexpect event Transfer(null, bob, 5)");
+}
@@ -5,7 +5,22 @@
 
 #![cfg(test)]
 
+use epicchain_contract::serialize::{deserialize_from_bytestring, serialize_to_bytestring, SerializationError};
 use epicchain_contract::types::*;
+use epicchain_contract::prelude::NeoSerialize;
+
+#[derive(NeoSerialize)]
+struct StoredPosition {
+    owner: H160,
+    amount: Int256,
+    memo: ByteString,
+    index: u32,
+    unlock_time: u64,
+    active: bool,
+}
+
+#[derive(NeoSerialize)]
+struct StoredPair(u32, StoredPosition);
 
 #[test]
 fn test_byte_string_serialization() {
@@ -86,3 +101,48 @@ fn test_binary_data_serialization() {
 
     assert_eq!(bs, deserialized);
 }
+
+fn sample_position() -> StoredPosition {
+    StoredPosition {
+        owner: H160::from_bytes(&[7u8; 20]),
+        amount: Int256::new(-12345),
+        memo: ByteString::from_literal("vesting"),
+        index: 42,
+        unlock_time: 1_700_000_000,
+        active: true,
+    }
+}
+
+#[test]
+fn test_derive_neo_serialize_round_trip() {
+    let data = serialize_to_bytestring(&sample_position()).unwrap();
+    // 20 + 32 + (1 + 7) + 4 + 8 + 1 bytes, written back to back
+    assert_eq!(data.len(), 73);
+
+    let decoded: StoredPosition = deserialize_from_bytestring(&data).unwrap();
+    assert_eq!(decoded.owner, H160::from_bytes(&[7u8; 20]));
+    assert_eq!(decoded.amount, Int256::new(-12345));
+    assert_eq!(decoded.memo, ByteString::from_literal("vesting"));
+    assert_eq!(decoded.index, 42);
+    assert_eq!(decoded.unlock_time, 1_700_000_000);
+    assert!(decoded.active);
+}
+
+#[test]
+fn test_derive_neo_serialize_nested_tuple_struct() {
+    let data = serialize_to_bytestring(&StoredPair(9, sample_position())).unwrap();
+    let decoded: StoredPair = deserialize_from_bytestring(&data).unwrap();
+
+    assert_eq!(decoded.0, 9);
+    assert_eq!(decoded.1.memo, ByteString::from_literal("vesting"));
+    assert_eq!(decoded.1.unlock_time, 1_700_000_000);
+}
+
+#[test]
+fn test_derive_neo_serialize_rejects_truncated_data() {
+    let data = serialize_to_bytestring(&sample_position()).unwrap();
+    let truncated = data.substr(0, data.len() - 2);
+
+    let decoded: Result<StoredPosition, SerializationError> = deserialize_from_bytestring(&truncated);
+    assert!(matches!(decoded, Err(SerializationError::InsufficientData)));
+}
@@ -74,3 +74,12 @@ fn test_native_contracts() {
     let min_fee = ContractManagement::get_min_deployment_fee();
     assert!(min_fee > Int256::zero());
 }
+
+#[test]
+fn test_sha256() {
+    let digest = sha256(ByteString::from_literal("abc"));
+    assert_eq!(
+        digest.to_bytes().to_vec(),
+        hex::decode("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad").unwrap()
+    );
+}
@@ -220,4 +220,37 @@ fn test_storage_map_overwrite() {
     let retrieved = storage.get(key.clone());
     assert!(retrieved.is_some());
     assert_eq!(retrieved.unwrap(), value2);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_put_changed_writes_new_value() {
+    let context = epicchain_contract::prelude::Storage::get_context();
+    let key = ByteString::from_literal("fee");
+
+    // Nothing stored yet, so the write is a change from null
+    assert!(epicchain_contract::storage::put_changed(context.clone(), key.clone(), "FeeChanged", Int256::new(30)));
+    assert_eq!(epicchain_contract::host::event_names(), vec![ByteString::from_literal("FeeChanged")]);
+
+    // Writing the stored value again is not a change
+    assert!(!epicchain_contract::storage::put_changed(context, key, "FeeChanged", Int256::new(30)));
+    assert_eq!(epicchain_contract::host::event_names().len(), 1);
+}
+
+#[test]
+fn test_delete_changed_without_value() {
+    let context = epicchain_contract::prelude::Storage::get_context();
+    let key = ByteString::from_literal("owner");
+
+    assert!(!epicchain_contract::storage::delete_changed::<H160>(context, key, "OwnerChanged"));
+    assert!(epicchain_contract::host::event_names().is_empty());
+}
+
+#[test]
+fn test_delete_changed_notifies_removal() {
+    let context = epicchain_contract::prelude::Storage::get_context();
+    let key = ByteString::from_literal("owner");
+    epicchain_contract::prelude::Storage::put(context.clone(), key.clone(), H160::zero().into_byte_string());
+
+    assert!(epicchain_contract::storage::delete_changed::<H160>(context, key, "OwnerChanged"));
+    assert_eq!(epicchain_contract::host::event_names(), vec![ByteString::from_literal("OwnerChanged")]);
+}
@@ -0,0 +1,73 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the timestamp helpers.
+
+#![cfg(test)]
+
+use epicchain_contract::host;
+use epicchain_contract::prelude::*;
+
+#[test]
+fn test_duration_constants() {
+    assert_eq!(SECONDS_PER_DAY, 86400);
+    assert_eq!(SECONDS_PER_WEEK, 604800);
+    assert_eq!(SECONDS_PER_YEAR, 31536000);
+    assert_eq!(days(2), 172800);
+    assert_eq!(hours(1), 3600);
+    assert_eq!(days(u64::MAX), u64::MAX);
+}
+
+#[test]
+fn test_days_since_epoch() {
+    assert_eq!(days_since_epoch(0), 0);
+    assert_eq!(days_since_epoch(SECONDS_PER_DAY - 1), 0);
+    assert_eq!(days_since_epoch(host::DEFAULT_TIME), 18993);
+}
+
+#[test]
+fn test_elapsed_saturates() {
+    assert_eq!(elapsed(100, 250), 150);
+    assert_eq!(elapsed(250, 100), 0);
+}
+
+#[test]
+fn test_period_index_at() {
+    assert_eq!(period_index_at(1000, 1000, 10), Some(0));
+    assert_eq!(period_index_at(1019, 1000, 10), Some(1));
+    assert_eq!(period_index_at(999, 1000, 10), None);
+    assert_eq!(period_index_at(1000, 1000, 0), None);
+    assert_eq!(period_start(1000, 10, 3), 1030);
+}
+
+#[test]
+fn test_period_index_follows_clock() {
+    let start = host::time();
+    assert_eq!(period_index(start, SECONDS_PER_WEEK), Some(0));
+
+    host::advance_time(SECONDS_PER_WEEK * 2 + 1);
+    assert_eq!(period_index(start, SECONDS_PER_WEEK), Some(2));
+}
+
+#[test]
+fn test_deadline_boundaries() {
+    assert_eq!(deadline(1000, 60), 1060);
+    assert_eq!(deadline(u64::MAX - 1, 60), u64::MAX);
+
+    assert!(!is_expired_at(1060, 1059));
+    assert!(!is_expired_at(1060, 1060));
+    assert!(is_expired_at(1060, 1061));
+    assert!(!is_expired_at(u64::MAX, u64::MAX));
+}
+
+#[test]
+fn test_is_expired_follows_clock() {
+    let expiration = deadline(host::time(), SECONDS_PER_HOUR);
+    assert!(!is_expired(expiration));
+
+    host::advance_time(SECONDS_PER_HOUR);
+    assert!(!is_expired(expiration));
+
+    host::advance_time(1);
+    assert!(is_expired(expiration));
+}
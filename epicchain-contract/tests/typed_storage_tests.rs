@@ -0,0 +1,141 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for the typed storage collections.
+
+#![cfg(test)]
+
+use epicchain_contract::prelude::*;
+use epicchain_contract::types::IntoByteString;
+
+fn account(byte: u8) -> H160 {
+    H160::from_bytes(&[byte; 20])
+}
+
+#[test]
+fn test_map_put_get_delete() {
+    let balances = TypedStorageMap::<H160, Int256>::new("balance_");
+    assert!(balances.get(account(1)).is_none());
+    assert!(!balances.contains(account(1)));
+
+    balances.put(account(1), Int256::new(100));
+    assert_eq!(balances.get(account(1)), Some(Int256::new(100)));
+    assert!(balances.contains(account(1)));
+
+    balances.delete(account(1));
+    assert_eq!(balances.get_or_zero(account(1)), Int256::zero());
+}
+
+#[test]
+fn test_map_uses_plain_key_layout() {
+    let balances = TypedStorageMap::<H160, Int256>::new("balance_");
+    balances.put(account(2), Int256::new(5));
+
+    let key = ByteString::from_literal("balance_").concat(&ByteString::from_bytes(&[2u8; 20]));
+    assert_eq!(balances.key(account(2)), key);
+    assert!(Storage::get(Storage::get_context(), key).is_some());
+}
+
+#[test]
+fn test_map_add_deletes_zero_amounts() {
+    let balances = TypedStorageMap::<H160, Int256>::new("balance_");
+
+    assert_eq!(balances.add(account(1), Int256::new(30)), Int256::new(30));
+    assert_eq!(balances.add(account(1), Int256::new(-10)), Int256::new(20));
+    assert_eq!(balances.add(account(1), Int256::new(-20)), Int256::zero());
+    assert!(!balances.contains(account(1)));
+}
+
+#[test]
+fn test_map_iter_stays_within_prefix() {
+    let balances = TypedStorageMap::<H160, Int256>::new("balance_");
+    let other = TypedStorageMap::<H160, Int256>::new("allowance_");
+    balances.put(account(2), Int256::new(2));
+    balances.put(account(1), Int256::new(1));
+    other.put(account(3), Int256::new(3));
+
+    let entries: Vec<(H160, Int256)> = balances.iter().collect();
+    assert_eq!(entries, vec![(account(1), Int256::new(1)), (account(2), Int256::new(2))]);
+}
+
+#[test]
+fn test_vec_push_pop_and_iter() {
+    let names = StorageVec::<ByteString>::new("names");
+    assert!(names.is_empty());
+
+    names.push(ByteString::from_literal("alice"));
+    names.push(ByteString::from_literal("bob"));
+    assert_eq!(names.len(), 2);
+    assert_eq!(names.get(1), Some(ByteString::from_literal("bob")));
+    assert!(names.get(2).is_none());
+    assert!(names.contains(&ByteString::from_literal("alice")));
+
+    let items: Vec<ByteString> = names.iter().collect();
+    assert_eq!(items, vec![ByteString::from_literal("alice"), ByteString::from_literal("bob")]);
+
+    assert_eq!(names.pop(), Some(ByteString::from_literal("bob")));
+    assert_eq!(names.len(), 1);
+}
+
+#[test]
+fn test_vec_set_and_swap_remove() {
+    let members = StorageVec::<H160>::new("members");
+    members.push(account(1));
+    members.push(account(2));
+    members.push(account(3));
+
+    assert!(members.set(0, account(9)));
+    assert!(!members.set(3, account(4)));
+
+    assert_eq!(members.swap_remove(0), Some(account(9)));
+    assert_eq!(members.len(), 2);
+    assert_eq!(members.get(0), Some(account(3)));
+    assert_eq!(members.get(1), Some(account(2)));
+}
+
+#[test]
+fn test_vec_clear() {
+    let members = StorageVec::<H160>::new("members");
+    members.push(account(1));
+    members.push(account(2));
+
+    members.clear();
+    assert!(members.is_empty());
+    assert_eq!(epicchain_contract::host::storage_len(), 0);
+}
+
+const PREFIX_POOL: u8 = 0x01;
+const PREFIX_STAKE: u8 = 0x02;
+
+#[test]
+fn test_prefixed_key_layout() {
+    let key = prefixed_key(PREFIX_POOL, account(3).into_byte_string());
+    assert_eq!(key.to_bytes()[0], PREFIX_POOL);
+    assert_eq!(key.len(), 21);
+    assert_eq!(prefixed_key(PREFIX_POOL, ByteString::empty()).to_bytes(), vec![PREFIX_POOL]);
+}
+
+#[test]
+fn test_key_builder_composes_parts() {
+    let key = KeyBuilder::new(PREFIX_STAKE).push(account(4)).push_u32(7).push_u64(9).build();
+
+    let mut expected = vec![PREFIX_STAKE];
+    expected.extend_from_slice(&[4u8; 20]);
+    expected.extend_from_slice(&7u32.to_le_bytes());
+    expected.extend_from_slice(&9u64.to_le_bytes());
+    assert_eq!(key.to_bytes(), expected);
+}
+
+#[test]
+fn test_tagged_collections_do_not_collide() {
+    let pools = TypedStorageMap::<H160, Int256>::with_tag(PREFIX_POOL);
+    let stakes = TypedStorageMap::<H160, Int256>::with_tag(PREFIX_STAKE);
+
+    pools.put(account(5), Int256::new(10));
+    assert_eq!(pools.key(account(5)), prefixed_key(PREFIX_POOL, account(5).into_byte_string()));
+    assert!(stakes.get(account(5)).is_none());
+
+    let history = StorageVec::<Int256>::with_tag(0x03);
+    history.push(Int256::new(1));
+    assert_eq!(history.len(), 1);
+}
@@ -0,0 +1,83 @@
+// Copyright @ 2024 - present, R3E Network
+// All Rights Reserved.
+
+//! Unit tests for deprecated entry points routed to their versioned replacements.
+
+#![cfg(test)]
+
+use epicchain_contract::host;
+use epicchain_contract::prelude::*;
+use epicchain_contract::runtime::versioning::DEPRECATED_CALL_EVENT;
+
+pub struct VersionedContract;
+
+#[contract_impl]
+impl VersionedContract {
+    pub fn init() -> Self {
+        Self
+    }
+
+    #[method]
+    pub fn store_v2(&self, key: ByteString, value: ByteString, tag: ByteString) -> bool {
+        Storage::put(Storage::get_context(), key, value.concat(&tag));
+        true
+    }
+
+    #[method]
+    #[deprecated_method(replacement = "store_v2", since = "2.0.0")]
+    pub fn store(&self, key: ByteString, value: ByteString) -> bool {
+        self.store_v2(key, value, ByteString::empty())
+    }
+
+    #[method]
+    #[safe]
+    pub fn read_v2(&self, key: ByteString) -> ByteString {
+        Storage::get(Storage::get_context(), key).unwrap_or_else(ByteString::empty)
+    }
+
+    #[method]
+    #[safe]
+    #[deprecated_method(replacement = "read_v2")]
+    pub fn read(&self, key: ByteString) -> ByteString {
+        self.read_v2(key)
+    }
+}
+
+#[test]
+fn test_deprecated_methods_lists_replacements() {
+    let methods = VersionedContract::deprecated_methods();
+    assert_eq!(methods.size(), 2);
+    assert_eq!(
+        methods.get(&ByteString::from_literal("store")),
+        Some(&ByteString::from_literal("store_v2"))
+    );
+    assert_eq!(
+        methods.get(&ByteString::from_literal("read")),
+        Some(&ByteString::from_literal("read_v2"))
+    );
+    assert!(methods.get(&ByteString::from_literal("store_v2")).is_none());
+}
+
+#[test]
+fn test_deprecated_entry_point_routes_and_reports_caller() {
+    host::set_calling_script_hash(H160::from_bytes(&[3u8; 20]));
+    host::take_notifications();
+
+    assert!(store(ByteString::from_literal("k"), ByteString::from_literal("v")));
+    assert_eq!(read_v2(ByteString::from_literal("k")), ByteString::from_literal("v"));
+
+    let notifications = host::take_notifications();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].event_name, ByteString::from_literal(DEPRECATED_CALL_EVENT));
+    assert_eq!(notifications[0].state.size(), 3);
+}
+
+#[test]
+fn test_current_and_safe_entry_points_do_not_notify() {
+    host::take_notifications();
+
+    assert!(store_v2(ByteString::from_literal("k"), ByteString::from_literal("v"), ByteString::from_literal("2")));
+    assert_eq!(read(ByteString::from_literal("k")), ByteString::from_literal("v2"));
+
+    assert!(host::take_notifications().is_empty());
+}
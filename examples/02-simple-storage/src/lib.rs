@@ -85,15 +85,6 @@ impl SimpleStorage {
         }
     }
 
-    /// Check if caller is the owner
-    fn is_owner(&self) -> bool {
-        let owner = self.get_owner();
-        if owner == H160::zero() {
-            return false;
-        }
-        Runtime::check_witness(owner)
-    }
-
     /// Store a string value
     #[method]
     pub fn put_string(&self, key: ByteString, value: ByteString) -> bool {
@@ -266,12 +257,8 @@ impl SimpleStorage {
 
     /// Delete any stored value by key and type
     #[method]
+    #[only_owner]
     pub fn delete(&self, data_type: ByteString, key: ByteString) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can delete"));
-            return false;
-        }
-
         let storage = Storage::get_context();
         let prefix = self.get_prefix_for_type(&data_type);
 
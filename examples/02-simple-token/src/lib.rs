@@ -19,6 +19,7 @@
 #![no_main]
 
 use epicchain_contract::prelude::*;
+use epicchain_contract::contract::invoke_on_nep17_payment;
 use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
 
 /// Simple token contract implementing NEP-17 standard
@@ -242,7 +243,7 @@ impl SimpleToken {
         self.transfer_tokens(from, to, amount);
 
         // Call onPayment if recipient is a contract
-        self.on_payment_callback(from, amount, data);
+        self.on_payment_callback(from, to, amount, data);
 
         true
     }
@@ -359,12 +360,8 @@ impl SimpleToken {
 
     /// Add authorized minter (only owner)
     #[method]
+    #[only_owner]
     pub fn add_minter(&self, minter: H160) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can add minters"));
-            return false;
-        }
-
         let storage = Storage::get_context();
         let minter_key = self.minters_prefix.concat(&minter.into_byte_string());
         Storage::put(storage, minter_key, ByteString::from_literal("true"));
@@ -377,12 +374,8 @@ impl SimpleToken {
 
     /// Remove authorized minter (only owner)
     #[method]
+    #[only_owner]
     pub fn remove_minter(&self, minter: H160) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can remove minters"));
-            return false;
-        }
-
         let storage = Storage::get_context();
         let minter_key = self.minters_prefix.concat(&minter.into_byte_string());
         Storage::delete(storage, minter_key);
@@ -395,12 +388,8 @@ impl SimpleToken {
 
     /// Pause contract (only owner)
     #[method]
+    #[only_owner]
     pub fn pause(&self) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can pause"));
-            return false;
-        }
-
         let storage = Storage::get_context();
         Storage::put(storage, self.paused_key.clone(), ByteString::from_literal("true"));
 
@@ -410,12 +399,8 @@ impl SimpleToken {
 
     /// Unpause contract (only owner)
     #[method]
+    #[only_owner]
     pub fn unpause(&self) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can unpause"));
-            return false;
-        }
-
         let storage = Storage::get_context();
         Storage::delete(storage, self.paused_key.clone());
 
@@ -534,11 +519,8 @@ impl SimpleToken {
         Runtime::notify(ByteString::from_literal("Transfer"), event_data);
     }
 
-    fn on_payment_callback(&self, from: H160, amount: Int256, data: Any) {
-        let mut event_data = Array::new();
-        event_data.push(from.into_any());
-        event_data.push(amount.into_any());
-        event_data.push(data);
-        Runtime::notify(ByteString::from_literal("PaymentCallback"), event_data);
+    fn on_payment_callback(&self, from: H160, to: H160, amount: Int256, data: Any) {
+        // Calls onNEP17Payment when the recipient is a deployed contract; a rejection aborts the transfer
+        invoke_on_nep17_payment(from, to, amount, data);
     }
 } 
\ No newline at end of file
@@ -108,12 +108,8 @@ impl Counter {
 
     /// Add an operator (owner only)
     #[method]
+    #[only_owner]
     pub fn add_operator(&self, operator: H160) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can add operators"));
-            return false;
-        }
-
         let storage = Storage::get_context();
         let operator_key = self.operators_prefix.concat(&operator.into_byte_string());
         Storage::put(storage, operator_key, ByteString::from_literal("true"));
@@ -124,12 +120,8 @@ impl Counter {
 
     /// Remove an operator (owner only)
     #[method]
+    #[only_owner]
     pub fn remove_operator(&self, operator: H160) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can remove operators"));
-            return false;
-        }
-
         let storage = Storage::get_context();
         let operator_key = self.operators_prefix.concat(&operator.into_byte_string());
         Storage::delete(storage, operator_key);
@@ -292,12 +284,8 @@ impl Counter {
 
     /// Reset the default counter to zero (owner only)
     #[method]
+    #[only_owner]
     pub fn reset(&self) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can reset"));
-            return false;
-        }
-
         let storage = Storage::get_context();
         Storage::put(storage, self.default_counter_key.clone(), Int256::zero().into_byte_string());
 
@@ -309,12 +297,8 @@ impl Counter {
 
     /// Reset a named counter to zero (owner only)
     #[method]
+    #[only_owner]
     pub fn reset_counter(&self, name: ByteString) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can reset"));
-            return false;
-        }
-
         if !self.validate_counter_name(&name) {
             return false;
         }
@@ -365,12 +349,8 @@ impl Counter {
 
     /// Delete a named counter (owner only)
     #[method]
+    #[only_owner]
     pub fn delete_counter(&self, name: ByteString) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can delete"));
-            return false;
-        }
-
         if !self.validate_counter_name(&name) {
             return false;
         }
@@ -4,9 +4,17 @@
 //! This contract demonstrates:
 //! - Full NEP-17 compliance with all required methods
 //! - Secure transfer mechanics with overflow protection
-//! - Allowance system for delegated transfers
-//! - Minting and burning capabilities
-//! - Administrative controls and ownership
+//! - Batch transfers to many recipients under a single witness check
+//! - Allowance system for delegated transfers, including signed off-chain permits
+//! - Optional allowance expiry and per-period spending caps (e.g. 100 tokens a day)
+//! - Minting and burning capabilities with cumulative mint and burn statistics
+//! - A max supply cap the owner can only lower, optionally locked for good
+//! - Circulating supply excluding owner-designated treasury, team and vesting accounts
+//! - Administrative controls and two-step ownership transfer
+//! - Owner-gated code upgrades, checked by the `_deploy` hook of the new code
+//! - Opt-in account freezing by the owner or compliance officers
+//! - Pro-rata dividend distribution of revenue tokens to holders
+//! - Voting power checkpoints, with delegation managed by a governance contract
 //! - Comprehensive event emission
 //! - Gas-optimized operations
 //!
@@ -16,32 +24,109 @@
 #![no_main]
 
 use epicchain_contract::prelude::*;
-use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
+use epicchain_contract::contract::invoke_on_nep17_payment;
+use epicchain_contract::contract::native::ContractManagement;
+use epicchain_contract::crypto::verify_ecdsa;
+use epicchain_contract::runtime::access;
+use epicchain_contract::types::{IntoByteString, FromByteString, NamedCurveHash, builtin::IntoAny};
+
+/// Fixed-point scale for dividend-per-share accounting
+const DIVIDEND_MAGNITUDE: i64 = 1_000_000_000_000_000_000;
+
+/// Maximum number of revenue tokens tracked for dividends
+const MAX_REVENUE_TOKENS: u32 = 8;
+
+/// Maximum number of registered transfer hooks
+const MAX_TRANSFER_HOOKS: u32 = 4;
+
+/// Maximum number of accounts excluded from the circulating supply
+const MAX_EXCLUDED_ACCOUNTS: u32 = 16;
+
+/// Maximum EpicPulse a single transfer hook call may consume (0.02 EpicPulse).
+///
+/// The VM cannot cap the EpicPulse of a contract call, so this budget is enforced after
+/// each hook returns: an overspending hook faults the whole transfer, but the EpicPulse it
+/// burned is still charged to the sender.
+const MAX_HOOK_EPICPULSE: i64 = 2_000_000;
+
+/// Maximum number of recipients of one `multi_transfer`
+const MAX_MULTI_TRANSFER_RECIPIENTS: u32 = 100;
+
+/// Role of the accounts allowed to freeze and unfreeze accounts
+const COMPLIANCE_ROLE: &str = "COMPLIANCE";
+
+/// Version of the permit payload format
+const PERMIT_VERSION: u8 = 1;
+
+/// Domain tag binding permit signatures to this kind of contract
+const PERMIT_DOMAIN: &str = "EpicChain.Nep17Permit";
+
+// Storage key tags. String keys shared with the framework ("owner", "paused", "role_")
+// start with a lowercase letter, so tags stay below 0x40 and never prefix them.
+
+// Token metadata
+const KEY_SYMBOL: u8 = 0x01;
+const KEY_DECIMALS: u8 = 0x02;
+const KEY_TOTAL_SUPPLY: u8 = 0x03;
+
+// Balances and allowances
+const PREFIX_BALANCE: u8 = 0x04;
+const PREFIX_ALLOWANCE: u8 = 0x05;
+const PREFIX_ALLOWANCE_TERMS: u8 = 0x06;     // owner + spender -> allowance terms
+const PREFIX_ALLOWANCE_SPENT: u8 = 0x07;     // owner + spender -> period index and amount spent in it
+const PREFIX_PERMIT_NONCE: u8 = 0x08;        // owner -> number of permits used
+
+// Administrative keys
+const KEY_UPDATE_SNAPSHOT: u8 = 0x09;        // owner and supply recorded by update_contract
+
+// Configuration
+const KEY_MAX_SUPPLY: u8 = 0x0A;
+const KEY_MAX_SUPPLY_LOCKED: u8 = 0x0B;      // set once the max supply can no longer change
+const KEY_FREEZABLE: u8 = 0x0C;              // set if accounts can be frozen (chosen at deploy)
+const PREFIX_FROZEN: u8 = 0x0D;              // account -> frozen flag
+
+// Supply statistics
+const KEY_TOTAL_MINTED: u8 = 0x0E;           // cumulative amount minted, including the initial supply
+const KEY_MINT_COUNT: u8 = 0x0F;             // number of mints
+const KEY_TOTAL_BURNED: u8 = 0x10;           // cumulative amount burned
+const KEY_EXCLUDED_ACCOUNTS: u8 = 0x11;      // list of accounts excluded from the circulating supply
+
+// Dividend distribution
+const KEY_REVENUE_TOKENS: u8 = 0x12;         // list of revenue token hashes
+const PREFIX_DIVIDEND_PER_SHARE: u8 = 0x13;  // token -> magnified dividend per share
+const PREFIX_DIVIDEND_CORRECTION: u8 = 0x14; // token + account -> magnified correction
+const PREFIX_DIVIDEND_WITHDRAWN: u8 = 0x15;  // token + account -> claimed dividends
+const PREFIX_DIVIDEND_RESERVED: u8 = 0x16;   // token -> distributed dividends not yet claimed
+
+// Transfer hooks
+const KEY_TRANSFER_HOOKS: u8 = 0x17;         // list of hook contract hashes
+const KEY_HOOK_LOCK: u8 = 0x18;              // set while hooks are running
+
+// Voting power
+const KEY_VOTES_CONTROLLER: u8 = 0x19;       // contract allowed to change vote delegation
+const PREFIX_VOTE_DELEGATE: u8 = 0x1A;       // account -> delegatee (absent = votes itself)
+const PREFIX_VOTE_CHECKPOINT: u8 = 0x1B;     // account -> checkpoint count, account + index -> (timestamp, votes)
+const KEY_SUPPLY_CHECKPOINT: u8 = 0x1C;      // total supply checkpoints, same layout as accounts
+
+/// Optional limits of an allowance, set with `approve_with_terms`
+#[derive(Clone)]
+pub struct AllowanceTerms {
+    pub expires_at: u64,     // last timestamp the allowance can be used, 0 = never expires
+    pub period: u64,         // length of a spending period in seconds, 0 = no cap
+    pub period_start: u64,   // start of the first period (approval time)
+    pub period_limit: Int256, // maximum spent per period
+}
 
 /// NEP-17 compliant fungible token contract
 #[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
 #[contract_version("1.0.0")]
 #[contract_standards("NEP-17")]
-#[contract_permission("*", "*")]
+#[contract_permission("*", "transfer", "balanceOf", "beforeTransfer", "afterTransfer", "onNEP17Payment")]
 #[contract_meta("description", "Production-ready NEP-17 fungible token")]
 #[contract_meta("website", "https://github.com/R3E-Network/epicchain-contract-rs")]
 pub struct Nep17Token {
-    // Token metadata
-    symbol_key: ByteString,
-    decimals_key: ByteString,
-    total_supply_key: ByteString,
-
-    // Storage prefixes
-    balance_prefix: ByteString,
-    allowance_prefix: ByteString,
-
-    // Administrative keys
+    // String key read by `Ownable` and `#[only_owner]`
     owner_key: ByteString,
-    minters_prefix: ByteString,
-
-    // Configuration
-    paused_key: ByteString,
-    max_supply_key: ByteString,
 }
 
 #[contract_impl]
@@ -49,19 +134,13 @@ impl Nep17Token {
     /// Initialize the token contract
     pub fn init() -> Self {
         Self {
-            symbol_key: ByteString::from_literal("symbol"),
-            decimals_key: ByteString::from_literal("decimals"),
-            total_supply_key: ByteString::from_literal("total_supply"),
-            balance_prefix: ByteString::from_literal("balance_"),
-            allowance_prefix: ByteString::from_literal("allowance_"),
             owner_key: ByteString::from_literal("owner"),
-            minters_prefix: ByteString::from_literal("minter_"),
-            paused_key: ByteString::from_literal("paused"),
-            max_supply_key: ByteString::from_literal("max_supply"),
         }
     }
 
-    /// Deploy the token with initial parameters (one-time setup)
+    /// Deploy the token with initial parameters (one-time setup).
+    ///
+    /// `freezable` enables account freezing for regulated issuers; it cannot be changed later.
     #[method]
     pub fn deploy(
         &self,
@@ -69,7 +148,8 @@ impl Nep17Token {
         symbol: ByteString,
         decimals: u32,
         initial_supply: Int256,
-        max_supply: Int256
+        max_supply: Int256,
+        freezable: bool
     ) -> bool {
         let storage = Storage::get_context();
 
@@ -102,16 +182,20 @@ impl Nep17Token {
         }
 
         // Store token metadata
-        Storage::put(storage.clone(), self.symbol_key.clone(), symbol.clone());
-        Storage::put(storage.clone(), self.decimals_key.clone(), ByteString::from_bytes(&decimals.to_le_bytes()));
-        Storage::put(storage.clone(), self.total_supply_key.clone(), initial_supply.into_byte_string());
-        Storage::put(storage.clone(), self.max_supply_key.clone(), max_supply.into_byte_string());
+        Storage::put(storage.clone(), KeyBuilder::new(KEY_SYMBOL).build(), symbol.clone());
+        Storage::put(storage.clone(), KeyBuilder::new(KEY_DECIMALS).build(), ByteString::from_bytes(&decimals.to_le_bytes()));
+        Storage::put(storage.clone(), KeyBuilder::new(KEY_TOTAL_SUPPLY).build(), initial_supply.into_byte_string());
+        Storage::put(storage.clone(), KeyBuilder::new(KEY_MAX_SUPPLY).build(), max_supply.into_byte_string());
         Storage::put(storage.clone(), self.owner_key.clone(), owner.into_byte_string());
+        if freezable {
+            Storage::put(storage.clone(), KeyBuilder::new(KEY_FREEZABLE).build(), ByteString::from_literal("true"));
+        }
 
         // Mint initial supply to owner
         if initial_supply > Int256::zero() {
-            let balance_key = self.balance_prefix.concat(&owner.into_byte_string());
-            Storage::put(storage, balance_key, initial_supply.into_byte_string());
+            self.balances().put(owner, initial_supply);
+            self.record_mint(initial_supply);
+            self.move_voting_power(H160::zero(), owner, initial_supply);
 
             // Emit Transfer event (from null to owner)
             self.emit_transfer(H160::zero(), owner, initial_supply);
@@ -126,7 +210,7 @@ impl Nep17Token {
     #[safe]
     pub fn symbol(&self) -> ByteString {
         let storage = Storage::get_context();
-        match Storage::get(storage, self.symbol_key.clone()) {
+        match Storage::get(storage, KeyBuilder::new(KEY_SYMBOL).build()) {
             Some(symbol) => symbol,
             None => ByteString::from_literal("UNKNOWN"),
         }
@@ -137,7 +221,7 @@ impl Nep17Token {
     #[safe]
     pub fn decimals(&self) -> u32 {
         let storage = Storage::get_context();
-        match Storage::get(storage, self.decimals_key.clone()) {
+        match Storage::get(storage, KeyBuilder::new(KEY_DECIMALS).build()) {
             Some(decimals_bytes) => {
                 let bytes = decimals_bytes.to_bytes();
                 if bytes.len() >= 4 {
@@ -155,7 +239,7 @@ impl Nep17Token {
     #[safe]
     pub fn total_supply(&self) -> Int256 {
         let storage = Storage::get_context();
-        match Storage::get(storage, self.total_supply_key.clone()) {
+        match Storage::get(storage, KeyBuilder::new(KEY_TOTAL_SUPPLY).build()) {
             Some(supply_bytes) => Int256::from_byte_string(supply_bytes),
             None => Int256::zero(),
         }
@@ -165,13 +249,7 @@ impl Nep17Token {
     #[method]
     #[safe]
     pub fn balance_of(&self, account: H160) -> Int256 {
-        let storage = Storage::get_context();
-        let balance_key = self.balance_prefix.concat(&account.into_byte_string());
-
-        match Storage::get(storage, balance_key) {
-            Some(balance_bytes) => Int256::from_byte_string(balance_bytes),
-            None => Int256::zero(),
-        }
+        self.balances().get_or_zero(account)
     }
 
     /// Transfer tokens (NEP-17 required)
@@ -183,6 +261,12 @@ impl Nep17Token {
             return false;
         }
 
+        // The zero address stands for mint and burn in Transfer events and checkpoints
+        if to == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid recipient"));
+            return false;
+        }
+
         if amount == Int256::zero() {
             return true; // Zero transfers are valid but do nothing
         }
@@ -192,7 +276,7 @@ impl Nep17Token {
         }
 
         // Check if contract is paused
-        if self.is_paused() {
+        if Self::is_paused() {
             Runtime::log(ByteString::from_literal("Contract is paused"));
             return false;
         }
@@ -203,6 +287,11 @@ impl Nep17Token {
             return false;
         }
 
+        if self.is_frozen(from) || self.is_frozen(to) {
+            Runtime::log(ByteString::from_literal("Account is frozen"));
+            return false;
+        }
+
         // Check sender balance
         let from_balance = self.balance_of(from);
         if from_balance < amount {
@@ -210,17 +299,108 @@ impl Nep17Token {
             return false;
         }
 
+        self.run_transfer_hooks("beforeTransfer", from, to, amount);
+
         // Perform transfer
         self.update_balance(from, from_balance.checked_sub(&amount));
 
         let to_balance = self.balance_of(to);
         self.update_balance(to, to_balance.checked_add(&amount));
+        self.move_voting_power(from, to, amount);
 
         // Emit Transfer event
         self.emit_transfer(from, to, amount);
 
+        self.run_transfer_hooks("afterTransfer", from, to, amount);
+
         // Call onNEP17Payment if recipient is a contract
-        self.on_payment_callback(from, amount, data);
+        self.on_payment_callback(from, to, amount, data);
+
+        true
+    }
+
+    /// Transfer tokens from one account to many recipients in one call.
+    ///
+    /// All legs succeed or none do: the sender's witness is checked once and its balance
+    /// is debited once with the total, and one Transfer event is emitted per leg.
+    #[method]
+    pub fn multi_transfer(&self, from: H160, recipients: Array<H160>, amounts: Array<Int256>, data: Any) -> bool {
+        if recipients.size() == 0 || recipients.size() != amounts.size() {
+            Runtime::log(ByteString::from_literal("Invalid recipients: must match amounts"));
+            return false;
+        }
+
+        if recipients.size() as u32 > MAX_MULTI_TRANSFER_RECIPIENTS {
+            Runtime::log(ByteString::from_literal("Too many recipients"));
+            return false;
+        }
+
+        if Self::is_paused() {
+            Runtime::log(ByteString::from_literal("Contract is paused"));
+            return false;
+        }
+
+        if !Runtime::check_witness(from) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        let mut total = Int256::zero();
+        for i in 0..amounts.size() {
+            let amount = amounts.get(i);
+            if amount <= Int256::zero() {
+                Runtime::log(ByteString::from_literal("Invalid amount: must be positive"));
+                return false;
+            }
+            total = total.checked_add(&amount);
+        }
+
+        for i in 0..recipients.size() {
+            if recipients.get(i) == H160::zero() {
+                Runtime::log(ByteString::from_literal("Invalid recipient"));
+                return false;
+            }
+        }
+
+        if self.is_frozen(from) {
+            Runtime::log(ByteString::from_literal("Account is frozen"));
+            return false;
+        }
+        for i in 0..recipients.size() {
+            if self.is_frozen(recipients.get(i)) {
+                Runtime::log(ByteString::from_literal("Account is frozen"));
+                return false;
+            }
+        }
+
+        let from_balance = self.balance_of(from);
+        if from_balance < total {
+            Runtime::log(ByteString::from_literal("Insufficient balance"));
+            return false;
+        }
+
+        for i in 0..recipients.size() {
+            self.run_transfer_hooks("beforeTransfer", from, recipients.get(i), amounts.get(i));
+        }
+
+        // Debit the sender once, then credit each leg; a leg back to the sender nets out
+        self.update_balance(from, from_balance.checked_sub(&total));
+        for i in 0..recipients.size() {
+            let to = recipients.get(i);
+            let amount = amounts.get(i);
+            let to_balance = self.balance_of(to);
+            self.update_balance(to, to_balance.checked_add(&amount));
+            self.move_voting_power(from, to, amount);
+            self.emit_transfer(from, to, amount);
+        }
+
+        for i in 0..recipients.size() {
+            self.run_transfer_hooks("afterTransfer", from, recipients.get(i), amounts.get(i));
+        }
+
+        for i in 0..recipients.size() {
+            self.on_payment_callback(from, recipients.get(i), amounts.get(i), data.clone());
+        }
 
         true
     }
@@ -253,26 +433,160 @@ impl Nep17Token {
             return false;
         }
 
-        // Set allowance
-        let storage = Storage::get_context();
-        let allowance_key = self.get_allowance_key(owner, spender);
+        self.set_allowance(owner, spender, amount);
+        true
+    }
 
-        if amount == Int256::zero() {
-            Storage::delete(storage, allowance_key);
-        } else {
-            Storage::put(storage, allowance_key, amount.into_byte_string());
+    /// Approve spender with an expiry and a per-period spending cap.
+    ///
+    /// The allowance cannot be used after `expires_at` (0 = never), and at most `period_limit`
+    /// of it can be spent in each `period`-second window counted from now (0 and 0 = no cap).
+    /// Any later `approve` or permit replaces the allowance together with its terms.
+    #[method]
+    pub fn approve_with_terms(
+        &self,
+        owner: H160,
+        spender: H160,
+        amount: Int256,
+        expires_at: u64,
+        period: u64,
+        period_limit: Int256
+    ) -> bool {
+        if amount < Int256::zero() || period_limit < Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid amount: cannot be negative"));
+            return false;
         }
 
-        // Emit Approval event
+        if (period == 0) != (period_limit == Int256::zero()) {
+            Runtime::log(ByteString::from_literal("Invalid terms: period and period limit go together"));
+            return false;
+        }
+
+        let now = Runtime::get_time();
+        if expires_at != 0 && is_expired_at(expires_at, now) {
+            Runtime::log(ByteString::from_literal("Invalid terms: already expired"));
+            return false;
+        }
+
+        if !Runtime::check_witness(owner) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        self.set_allowance(owner, spender, amount);
+
+        if amount > Int256::zero() && (expires_at != 0 || period != 0) {
+            let terms = AllowanceTerms { expires_at, period, period_start: now, period_limit };
+            let storage = Storage::get_context();
+            let terms_key = KeyBuilder::new(PREFIX_ALLOWANCE_TERMS).push(owner).push(spender).build();
+            Storage::put(storage, terms_key, self.serialize_allowance_terms(&terms));
+
+            let mut event_data = Array::new();
+            event_data.push(owner.into_any());
+            event_data.push(spender.into_any());
+            event_data.push(Int256::from_u64(expires_at).into_any());
+            event_data.push(Int256::from_u64(period).into_any());
+            event_data.push(period_limit.into_any());
+            Runtime::notify(ByteString::from_literal("AllowanceTermsSet"), event_data);
+        }
+        true
+    }
+
+    /// Terms of an allowance and what `spender` can use right now
+    #[method]
+    #[safe]
+    pub fn allowance_terms(&self, owner: H160, spender: H160) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        let allowance = self.allowance(owner, spender);
+        let available = match self.get_allowance_terms(owner, spender) {
+            Some(terms) => {
+                let spent = self.period_spent(owner, spender, &terms);
+                result.put(ByteString::from_literal("expires_at"), Int256::from_u64(terms.expires_at).into_any());
+                result.put(ByteString::from_literal("period"), Int256::from_u64(terms.period).into_any());
+                result.put(ByteString::from_literal("period_limit"), terms.period_limit.into_any());
+                result.put(ByteString::from_literal("period_spent"), spent.into_any());
+                self.available_allowance(allowance, spent, &terms)
+            }
+            None => allowance,
+        };
+        result.put(ByteString::from_literal("allowance"), allowance.into_any());
+        result.put(ByteString::from_literal("available"), available.into_any());
+        result
+    }
+
+    /// Approve spender with a signature of the owner instead of an owner transaction.
+    ///
+    /// The owner signs `permit_payload(owner, spender, amount, deadline)` off-chain with
+    /// secp256r1/SHA-256; anyone can then submit it before `deadline` (block time in ms).
+    /// Each owner's permits carry an increasing nonce, so a signature is valid only once.
+    #[method]
+    pub fn permit(
+        &self,
+        owner_pubkey: PublicKey,
+        spender: H160,
+        amount: Int256,
+        deadline: u64,
+        signature: ByteString
+    ) -> bool {
+        if amount < Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid amount: cannot be negative"));
+            return false;
+        }
+
+        if spender == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid spender"));
+            return false;
+        }
+
+        if Runtime::get_time() > deadline {
+            Runtime::log(ByteString::from_literal("Permit expired"));
+            return false;
+        }
+
+        let owner = create_standard_account(owner_pubkey.clone());
+        let nonce = self.permit_nonce(owner);
+        let payload = self.encode_permit(owner, spender, amount, nonce, deadline);
+        if !verify_ecdsa(payload, owner_pubkey, signature, NamedCurveHash::Secp256r1SHA256) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid permit signature"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let nonce_key = KeyBuilder::new(PREFIX_PERMIT_NONCE).push(owner).build();
+        Storage::put(storage, nonce_key, nonce.checked_inc().into_byte_string());
+
+        self.set_allowance(owner, spender, amount);
+
         let mut event_data = Array::new();
         event_data.push(owner.into_any());
         event_data.push(spender.into_any());
-        event_data.push(amount.into_any());
-        Runtime::notify(ByteString::from_literal("Approval"), event_data);
-
+        event_data.push(nonce.into_any());
+        Runtime::notify(ByteString::from_literal("PermitUsed"), event_data);
         true
     }
 
+    /// Get the nonce the next permit of `owner` must be signed with
+    #[method]
+    #[safe]
+    pub fn permit_nonce(&self, owner: H160) -> Int256 {
+        let storage = Storage::get_context();
+        let nonce_key = KeyBuilder::new(PREFIX_PERMIT_NONCE).push(owner).build();
+        match Storage::get(storage, nonce_key) {
+            Some(nonce_bytes) => Int256::from_byte_string(nonce_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Bytes the owner signs to permit `spender`, with the owner's current nonce:
+    ///
+    /// sha256(domain | version (u8) | network (u32) | contract hash)
+    /// | owner | spender | amount length (u8) | amount | nonce length (u8) | nonce | deadline (u64)
+    #[method]
+    #[safe]
+    pub fn permit_payload(&self, owner: H160, spender: H160, amount: Int256, deadline: u64) -> ByteString {
+        self.encode_permit(owner, spender, amount, self.permit_nonce(owner), deadline)
+    }
+
     /// Transfer tokens on behalf of another account (requires allowance)
     #[method]
     pub fn transfer_from(&self, spender: H160, from: H160, to: H160, amount: Int256, data: Any) -> bool {
@@ -282,6 +596,11 @@ impl Nep17Token {
             return false;
         }
 
+        if to == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid recipient"));
+            return false;
+        }
+
         if amount == Int256::zero() {
             return true;
         }
@@ -292,6 +611,11 @@ impl Nep17Token {
             return false;
         }
 
+        if self.is_frozen(spender) || self.is_frozen(from) || self.is_frozen(to) {
+            Runtime::log(ByteString::from_literal("Account is frozen"));
+            return false;
+        }
+
         // Check allowance
         let current_allowance = self.allowance(from, spender);
         if current_allowance < amount {
@@ -299,6 +623,20 @@ impl Nep17Token {
             return false;
         }
 
+        let terms = self.get_allowance_terms(from, spender);
+        if let Some(terms) = &terms {
+            if terms.expires_at != 0 && is_expired_at(terms.expires_at, Runtime::get_time()) {
+                Runtime::log(ByteString::from_literal("Allowance expired"));
+                return false;
+            }
+
+            let spent = self.period_spent(from, spender, terms);
+            if self.available_allowance(current_allowance, spent, terms) < amount {
+                Runtime::log(ByteString::from_literal("Allowance period limit exceeded"));
+                return false;
+            }
+        }
+
         // Check sender balance
         let from_balance = self.balance_of(from);
         if from_balance < amount {
@@ -306,6 +644,8 @@ impl Nep17Token {
             return false;
         }
 
+        self.run_transfer_hooks("beforeTransfer", from, to, amount);
+
         // Update allowance
         let new_allowance = current_allowance.checked_sub(&amount);
         let storage = Storage::get_context();
@@ -313,8 +653,12 @@ impl Nep17Token {
 
         if new_allowance == Int256::zero() {
             Storage::delete(storage, allowance_key);
+            self.clear_allowance_terms(from, spender);
         } else {
             Storage::put(storage, allowance_key, new_allowance.into_byte_string());
+            if let Some(terms) = &terms {
+                self.record_period_spend(from, spender, terms, amount);
+            }
         }
 
         // Perform transfer
@@ -322,12 +666,15 @@ impl Nep17Token {
 
         let to_balance = self.balance_of(to);
         self.update_balance(to, to_balance.checked_add(&amount));
+        self.move_voting_power(from, to, amount);
 
         // Emit Transfer event
         self.emit_transfer(from, to, amount);
 
+        self.run_transfer_hooks("afterTransfer", from, to, amount);
+
         // Call onNEP17Payment if recipient is a contract
-        self.on_payment_callback(from, amount, data);
+        self.on_payment_callback(from, to, amount, data);
 
         true
     }
@@ -345,6 +692,11 @@ impl Nep17Token {
             return false;
         }
 
+        if self.is_frozen(to) {
+            Runtime::log(ByteString::from_literal("Account is frozen"));
+            return false;
+        }
+
         // Check max supply limit
         let current_supply = self.total_supply();
         let max_supply = self.get_max_supply();
@@ -355,17 +707,24 @@ impl Nep17Token {
             return false;
         }
 
+        self.run_transfer_hooks("beforeTransfer", H160::zero(), to, amount);
+
         let storage = Storage::get_context();
 
         // Update total supply
-        Storage::put(storage, self.total_supply_key.clone(), new_supply.into_byte_string());
+        Storage::put(storage, KeyBuilder::new(KEY_TOTAL_SUPPLY).build(), new_supply.into_byte_string());
+
+        self.record_mint(amount);
 
         // Update recipient balance
         let to_balance = self.balance_of(to);
         self.update_balance(to, to_balance.checked_add(&amount));
+        self.move_voting_power(H160::zero(), to, amount);
 
         // Emit Transfer event (from null address)
         self.emit_transfer(H160::zero(), to, amount);
+        self.run_transfer_hooks("afterTransfer", H160::zero(), to, amount);
+        self.on_payment_callback(H160::zero(), to, amount, Any::default());
 
         let mut event_data = Array::new(); event_data.push(amount.into_any()); Runtime::notify(ByteString::from_literal("TokensMinted"), event_data);
         true
@@ -385,6 +744,11 @@ impl Nep17Token {
             return false;
         }
 
+        if self.is_frozen(from) {
+            Runtime::log(ByteString::from_literal("Account is frozen"));
+            return false;
+        }
+
         // Check balance
         let from_balance = self.balance_of(from);
         if from_balance < amount {
@@ -392,18 +756,23 @@ impl Nep17Token {
             return false;
         }
 
+        self.run_transfer_hooks("beforeTransfer", from, H160::zero(), amount);
+
         let storage = Storage::get_context();
 
         // Update total supply
         let current_supply = self.total_supply();
         let new_supply = current_supply.checked_sub(&amount);
-        Storage::put(storage, self.total_supply_key.clone(), new_supply.into_byte_string());
+        Storage::put(storage.clone(), KeyBuilder::new(KEY_TOTAL_SUPPLY).build(), new_supply.into_byte_string());
+        Storage::put(storage, KeyBuilder::new(KEY_TOTAL_BURNED).build(), self.total_burned().checked_add(&amount).into_byte_string());
 
         // Update sender balance
         self.update_balance(from, from_balance.checked_sub(&amount));
+        self.move_voting_power(from, H160::zero(), amount);
 
         // Emit Transfer event (to null address)
         self.emit_transfer(from, H160::zero(), amount);
+        self.run_transfer_hooks("afterTransfer", from, H160::zero(), amount);
 
         let mut event_data = Array::new(); event_data.push(amount.into_any()); Runtime::notify(ByteString::from_literal("TokensBurned"), event_data);
         true
@@ -420,74 +789,126 @@ impl Nep17Token {
         }
     }
 
-    /// Add authorized minter (owner only)
+    /// Upgrade the contract code and manifest (owner only).
+    ///
+    /// The owner and total supply are recorded before calling `ContractManagement.update`; the new
+    /// code's `_deploy` runs in the same transaction and aborts it, reverting the update,
+    /// unless both are unchanged.
     #[method]
-    pub fn add_minter(&self, minter: H160) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can add minters"));
+    #[only_owner]
+    pub fn update_contract(&self, nef: ByteString, manifest: ByteString, data: Any) -> bool {
+        if nef.is_empty() || manifest.is_empty() {
+            Runtime::log(ByteString::from_literal("Invalid contract update"));
+            return false;
+        }
+
+        // Hooks run inside transfers; an update from one would swap code mid-transfer
+        if Storage::get(Storage::get_context(), KeyBuilder::new(KEY_HOOK_LOCK).build()).is_some() {
+            Runtime::log(ByteString::from_literal("Transfer hooks are running"));
             return false;
         }
 
         let storage = Storage::get_context();
-        let minter_key = self.minters_prefix.concat(&minter.into_byte_string());
-        Storage::put(storage, minter_key, ByteString::from_literal("true"));
+        Storage::put(storage, KeyBuilder::new(KEY_UPDATE_SNAPSHOT).build(), self.state_snapshot());
 
-        let mut event_data = Array::new(); event_data.push(minter.into_any()); Runtime::notify(ByteString::from_literal("MinterAdded"), event_data);
+        ContractManagement::update(nef, manifest, data);
         true
     }
 
-    /// Remove authorized minter (owner only)
+    /// Called by ContractManagement after deployment and after each update
     #[method]
-    pub fn remove_minter(&self, minter: H160) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can remove minters"));
-            return false;
+    pub fn _deploy(&self, _data: Any, update: bool) {
+        if !update {
+            return;
         }
 
+        // Updates must go through `update_contract`, which snapshots the state first
         let storage = Storage::get_context();
-        let minter_key = self.minters_prefix.concat(&minter.into_byte_string());
-        Storage::delete(storage, minter_key);
+        let snapshot = Storage::get(storage.clone(), KeyBuilder::new(KEY_UPDATE_SNAPSHOT).build());
+        assert_with_code(snapshot.is_some(), ErrorCode::InvalidState, "Update not started by update_contract");
+        assert_with_code(
+            snapshot.unwrap() == self.state_snapshot(),
+            ErrorCode::InvalidState,
+            "Owner or supply changed by update",
+        );
+        Storage::delete(storage, KeyBuilder::new(KEY_UPDATE_SNAPSHOT).build());
 
-        let mut event_data = Array::new(); event_data.push(minter.into_any()); Runtime::notify(ByteString::from_literal("MinterRemoved"), event_data);
-        true
+        let mut event_data = Array::new();
+        event_data.push(self.get_owner().into_any());
+        event_data.push(Runtime::get_executing_script_hash().into_any());
+        Runtime::notify(ByteString::from_literal("ContractUpdated"), event_data);
     }
 
-    /// Pause contract (owner only)
+    /// Freeze an account so it can no longer send, receive, mint or burn tokens
+    /// (owner or compliance officer, tokens deployed as freezable only)
     #[method]
-    pub fn pause(&self) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can pause"));
+    pub fn freeze_account(&self, caller: H160, account: H160) -> bool {
+        if !self.set_frozen(caller, account, true) {
             return false;
         }
 
-        let storage = Storage::get_context();
-        Storage::put(storage, self.paused_key.clone(), ByteString::from_literal("true"));
-
-        Runtime::notify(ByteString::from_literal("ContractPaused"), Array::new());
+        let mut event_data = Array::new();
+        event_data.push(account.into_any());
+        event_data.push(caller.into_any());
+        Runtime::notify(ByteString::from_literal("AccountFrozen"), event_data);
         true
     }
 
-    /// Unpause contract (owner only)
+    /// Unfreeze an account (owner or compliance officer)
     #[method]
-    pub fn unpause(&self) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can unpause"));
+    pub fn unfreeze_account(&self, caller: H160, account: H160) -> bool {
+        if !self.set_frozen(caller, account, false) {
             return false;
         }
 
+        let mut event_data = Array::new();
+        event_data.push(account.into_any());
+        event_data.push(caller.into_any());
+        Runtime::notify(ByteString::from_literal("AccountUnfrozen"), event_data);
+        true
+    }
+
+    /// Check if an account is frozen
+    #[method]
+    #[safe]
+    pub fn is_frozen(&self, account: H160) -> bool {
+        let storage = Storage::get_context();
+        Storage::get(storage, KeyBuilder::new(PREFIX_FROZEN).push(account).build()).is_some()
+    }
+
+    /// Check if the token was deployed with account freezing enabled
+    #[method]
+    #[safe]
+    pub fn is_freezable(&self) -> bool {
         let storage = Storage::get_context();
-        Storage::delete(storage, self.paused_key.clone());
+        Storage::get(storage, KeyBuilder::new(KEY_FREEZABLE).build()).is_some()
+    }
+
+    /// Appoint a compliance officer who may freeze and unfreeze accounts (owner only)
+    #[method]
+    #[only_owner]
+    pub fn add_compliance_officer(&self, officer: H160) -> bool {
+        access::grant_role(COMPLIANCE_ROLE, officer);
+
+        let mut event_data = Array::new(); event_data.push(officer.into_any()); Runtime::notify(ByteString::from_literal("ComplianceOfficerAdded"), event_data);
+        true
+    }
+
+    /// Remove a compliance officer (owner only)
+    #[method]
+    #[only_owner]
+    pub fn remove_compliance_officer(&self, officer: H160) -> bool {
+        access::revoke_role(COMPLIANCE_ROLE, officer);
 
-        Runtime::notify(ByteString::from_literal("ContractUnpaused"), Array::new());
+        let mut event_data = Array::new(); event_data.push(officer.into_any()); Runtime::notify(ByteString::from_literal("ComplianceOfficerRemoved"), event_data);
         true
     }
 
-    /// Check if contract is paused
+    /// Check if an account is a compliance officer
     #[method]
     #[safe]
-    pub fn is_paused(&self) -> bool {
-        let storage = Storage::get_context();
-        Storage::get(storage, self.paused_key.clone()).is_some()
+    pub fn is_compliance_officer(&self, account: H160) -> bool {
+        access::has_role(COMPLIANCE_ROLE, account)
     }
 
     /// Get maximum supply
@@ -495,66 +916,1027 @@ impl Nep17Token {
     #[safe]
     pub fn get_max_supply(&self) -> Int256 {
         let storage = Storage::get_context();
-        match Storage::get(storage, self.max_supply_key.clone()) {
+        match Storage::get(storage, KeyBuilder::new(KEY_MAX_SUPPLY).build()) {
             Some(max_supply_bytes) => Int256::from_byte_string(max_supply_bytes),
             None => Int256::zero(), // No limit if not set
         }
     }
 
-    // Helper functions
+    /// Lower the maximum supply, down to at most the current supply (owner only)
+    ///
+    /// An uncapped token (max supply 0) can be given any cap covering the current supply.
+    #[method]
+    #[only_owner]
+    pub fn set_max_supply(&self, max_supply: Int256) -> bool {
+        if self.is_max_supply_locked() {
+            Runtime::log(ByteString::from_literal("Max supply is locked"));
+            return false;
+        }
 
-    fn is_owner(&self) -> bool {
-        let owner = self.get_owner();
-        if owner == H160::zero() {
+        let previous = self.get_max_supply();
+        if max_supply <= Int256::zero() || (previous > Int256::zero() && max_supply >= previous) {
+            Runtime::log(ByteString::from_literal("Max supply can only decrease"));
             return false;
         }
-        Runtime::check_witness(owner)
+
+        if max_supply < self.total_supply() {
+            Runtime::log(ByteString::from_literal("Max supply below current supply"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        Storage::put(storage, KeyBuilder::new(KEY_MAX_SUPPLY).build(), max_supply.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(previous.into_any());
+        event_data.push(max_supply.into_any());
+        Runtime::notify(ByteString::from_literal("MaxSupplyChanged"), event_data);
+        true
     }
 
-    fn is_authorized_minter(&self) -> bool {
-        if self.is_owner() {
-            return true;
+    /// Make the current max supply a hard cap that can never change again (owner only)
+    #[method]
+    #[only_owner]
+    pub fn lock_max_supply(&self) -> bool {
+        if self.is_max_supply_locked() {
+            Runtime::log(ByteString::from_literal("Max supply is locked"));
+            return false;
+        }
+
+        let max_supply = self.get_max_supply();
+        if max_supply <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No max supply to lock"));
+            return false;
         }
 
-        let caller = Runtime::get_calling_script_hash();
         let storage = Storage::get_context();
-        let minter_key = self.minters_prefix.concat(&caller.into_byte_string());
-        Storage::get(storage, minter_key).is_some()
+        Storage::put(storage, KeyBuilder::new(KEY_MAX_SUPPLY_LOCKED).build(), ByteString::from_literal("true"));
+
+        let mut event_data = Array::new();
+        event_data.push(max_supply.into_any());
+        Runtime::notify(ByteString::from_literal("MaxSupplyLocked"), event_data);
+        true
     }
 
-    fn update_balance(&self, account: H160, new_balance: Int256) {
+    /// Check if the max supply is a locked hard cap
+    #[method]
+    #[safe]
+    pub fn is_max_supply_locked(&self) -> bool {
         let storage = Storage::get_context();
-        let balance_key = self.balance_prefix.concat(&account.into_byte_string());
+        Storage::get(storage, KeyBuilder::new(KEY_MAX_SUPPLY_LOCKED).build()).is_some()
+    }
 
-        if new_balance == Int256::zero() {
-            Storage::delete(storage, balance_key);
+    /// Get the amount that can still be minted, or -1 if the supply is uncapped
+    #[method]
+    #[safe]
+    pub fn remaining_mintable(&self) -> Int256 {
+        let max_supply = self.get_max_supply();
+        if max_supply <= Int256::zero() {
+            return Int256::minus_one();
+        }
+
+        let remaining = max_supply.checked_sub(&self.total_supply());
+        if remaining < Int256::zero() {
+            Int256::zero()
         } else {
-            Storage::put(storage, balance_key, new_balance.into_byte_string());
+            remaining
         }
     }
 
-    fn get_allowance_key(&self, owner: H160, spender: H160) -> ByteString {
-        self.allowance_prefix
-            .concat(&owner.into_byte_string())
-            .concat(&ByteString::from_literal("_"))
-            .concat(&spender.into_byte_string())
+    /// Get the cumulative amount of tokens minted, including the initial supply
+    #[method]
+    #[safe]
+    pub fn total_minted(&self) -> Int256 {
+        self.get_stat(KEY_TOTAL_MINTED)
     }
 
-    fn emit_transfer(&self, from: H160, to: H160, amount: Int256) {
-        let mut event_data = Array::new();
-        event_data.push(from.into_any());
-        event_data.push(to.into_any());
-        event_data.push(amount.into_any());
-        Runtime::notify(ByteString::from_literal("Transfer"), event_data);
+    /// Get the number of mints, counting the initial supply as one
+    #[method]
+    #[safe]
+    pub fn mint_count(&self) -> Int256 {
+        self.get_stat(KEY_MINT_COUNT)
     }
 
-    fn on_payment_callback(&self, from: H160, amount: Int256, data: Any) {
-        // This would call onNEP17Payment on the recipient contract if it's a contract
-        // For now, we'll just emit an event
+    /// Get the cumulative amount of tokens burned
+    #[method]
+    #[safe]
+    pub fn total_burned(&self) -> Int256 {
+        self.get_stat(KEY_TOTAL_BURNED)
+    }
+
+    /// Get the total supply minus the balances of the excluded accounts
+    #[method]
+    #[safe]
+    pub fn circulating_supply(&self) -> Int256 {
+        let excluded = self.get_excluded_accounts();
+        let mut supply = self.total_supply();
+
+        for i in bounded_iter(&excluded, MAX_EXCLUDED_ACCOUNTS as usize) {
+            supply = supply.checked_sub(&self.balance_of(excluded.get(i)));
+        }
+
+        supply
+    }
+
+    /// Exclude a treasury, team or vesting account from the circulating supply (owner only)
+    #[method]
+    #[only_owner]
+    pub fn add_excluded_account(&self, account: H160) -> bool {
+        if account == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid account"));
+            return false;
+        }
+
+        let mut excluded = self.get_excluded_accounts();
+        if self.contains_token(&excluded, account) {
+            Runtime::log(ByteString::from_literal("Account already excluded"));
+            return false;
+        }
+        if excluded.size() as u32 >= MAX_EXCLUDED_ACCOUNTS {
+            Runtime::log(ByteString::from_literal("Too many excluded accounts"));
+            return false;
+        }
+
+        excluded.push(account);
+        let storage = Storage::get_context();
+        Storage::put(storage, KeyBuilder::new(KEY_EXCLUDED_ACCOUNTS).build(), self.serialize_tokens(&excluded));
+
         let mut event_data = Array::new();
-        event_data.push(from.into_any());
-        event_data.push(amount.into_any());
-        event_data.push(data);
-        Runtime::notify(ByteString::from_literal("PaymentCallback"), event_data);
+        event_data.push(account.into_any());
+        Runtime::notify(ByteString::from_literal("SupplyExclusionAdded"), event_data);
+        true
     }
-}
+
+    /// Count an excluded account towards the circulating supply again (owner only)
+    #[method]
+    #[only_owner]
+    pub fn remove_excluded_account(&self, account: H160) -> bool {
+        let excluded = self.get_excluded_accounts();
+        if !self.contains_token(&excluded, account) {
+            Runtime::log(ByteString::from_literal("Account not excluded"));
+            return false;
+        }
+
+        let mut remaining = Array::new();
+        for i in bounded_iter(&excluded, MAX_EXCLUDED_ACCOUNTS as usize) {
+            let existing = excluded.get(i);
+            if existing != account {
+                remaining.push(existing);
+            }
+        }
+
+        let storage = Storage::get_context();
+        if remaining.size() == 0 {
+            Storage::delete(storage, KeyBuilder::new(KEY_EXCLUDED_ACCOUNTS).build());
+        } else {
+            Storage::put(storage, KeyBuilder::new(KEY_EXCLUDED_ACCOUNTS).build(), self.serialize_tokens(&remaining));
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(account.into_any());
+        Runtime::notify(ByteString::from_literal("SupplyExclusionRemoved"), event_data);
+        true
+    }
+
+    /// Get the accounts excluded from the circulating supply
+    #[method]
+    #[safe]
+    pub fn get_excluded_accounts(&self) -> Array<H160> {
+        let storage = Storage::get_context();
+        match Storage::get(storage, KeyBuilder::new(KEY_EXCLUDED_ACCOUNTS).build()) {
+            Some(data) => self.deserialize_tokens(data),
+            None => Array::new(),
+        }
+    }
+
+    /// Distribute revenue tokens received by the contract pro-rata to all holders (owner only).
+    ///
+    /// `amount` must already be held by the contract on top of the dividends distributed
+    /// earlier and not claimed yet.
+    #[method]
+    #[only_owner]
+    pub fn distribute(&self, token: H160, amount: Int256) -> bool {
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid amount: must be positive"));
+            return false;
+        }
+
+        let supply = self.total_supply();
+        if supply <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No holders to distribute to"));
+            return false;
+        }
+
+        let reserved = self.get_dividend_reserved(token);
+        let unallocated = self.token_balance(token, Runtime::get_executing_script_hash()).checked_sub(&reserved);
+        if unallocated < amount {
+            Runtime::log(ByteString::from_literal("Revenue not received"));
+            return false;
+        }
+        self.set_dividend_reserved(token, reserved.checked_add(&amount));
+
+        let mut revenue_tokens = self.get_revenue_tokens();
+        if !self.contains_token(&revenue_tokens, token) {
+            if revenue_tokens.size() as u32 >= MAX_REVENUE_TOKENS {
+                Runtime::log(ByteString::from_literal("Too many revenue tokens"));
+                return false;
+            }
+            revenue_tokens.push(token);
+            let storage = Storage::get_context();
+            Storage::put(storage, KeyBuilder::new(KEY_REVENUE_TOKENS).build(), self.serialize_tokens(&revenue_tokens));
+        }
+
+        // Scale before dividing so small distributions are not lost to rounding
+        let increment = amount
+            .checked_mul(&Int256::new(DIVIDEND_MAGNITUDE))
+            .checked_div(&supply);
+        let per_share = self.get_dividend_per_share(token).checked_add(&increment);
+
+        let storage = Storage::get_context();
+        let per_share_key = KeyBuilder::new(PREFIX_DIVIDEND_PER_SHARE).push(token).build();
+        Storage::put(storage, per_share_key, per_share.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(token.into_any());
+        event_data.push(amount.into_any());
+        event_data.push(per_share.into_any());
+        Runtime::notify(ByteString::from_literal("DividendsDistributed"), event_data);
+        true
+    }
+
+    /// Claim all pending dividends of an account across revenue tokens
+    #[method]
+    pub fn claim_dividends(&self, account: H160) -> bool {
+        if !Runtime::check_witness(account) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        let revenue_tokens = self.get_revenue_tokens();
+        let mut claimed = false;
+
+        for i in bounded_iter(&revenue_tokens, MAX_REVENUE_TOKENS as usize) {
+            let token = revenue_tokens.get(i);
+            let pending = self.dividends_of(account, token);
+            if pending <= Int256::zero() {
+                continue;
+            }
+
+            // Record the claim before paying out
+            let storage = Storage::get_context();
+            let withdrawn_key = self.get_dividend_key(PREFIX_DIVIDEND_WITHDRAWN, token, account);
+            let withdrawn = self.get_dividend_withdrawn(token, account).checked_add(&pending);
+            Storage::put(storage, withdrawn_key, withdrawn.into_byte_string());
+            self.set_dividend_reserved(token, self.get_dividend_reserved(token).checked_sub(&pending));
+
+            // A failed payout aborts, reverting the claim record with it
+            let mut args = Array::new();
+            args.push(Runtime::get_executing_script_hash().into_any());
+            args.push(account.into_any());
+            args.push(pending.into_any());
+            args.push(Any::default());
+            assert_with_code(
+                Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, args),
+                ErrorCode::CallFailed,
+                "Dividend transfer failed",
+            );
+
+            let mut event_data = Array::new();
+            event_data.push(account.into_any());
+            event_data.push(token.into_any());
+            event_data.push(pending.into_any());
+            Runtime::notify(ByteString::from_literal("DividendClaimed"), event_data);
+            claimed = true;
+        }
+
+        if !claimed {
+            Runtime::log(ByteString::from_literal("No dividends to claim"));
+        }
+        claimed
+    }
+
+    /// Get unclaimed dividends of an account in a revenue token
+    #[method]
+    #[safe]
+    pub fn dividends_of(&self, account: H160, token: H160) -> Int256 {
+        let magnified = self.get_dividend_per_share(token)
+            .checked_mul(&self.balance_of(account))
+            .checked_add(&self.get_dividend_correction(token, account));
+        let accumulated = magnified.checked_div(&Int256::new(DIVIDEND_MAGNITUDE));
+        accumulated.checked_sub(&self.get_dividend_withdrawn(token, account))
+    }
+
+    /// Get the list of revenue tokens distributed to holders
+    #[method]
+    #[safe]
+    pub fn get_revenue_tokens(&self) -> Array<H160> {
+        let storage = Storage::get_context();
+        match Storage::get(storage, KeyBuilder::new(KEY_REVENUE_TOKENS).build()) {
+            Some(data) => self.deserialize_tokens(data),
+            None => Array::new(),
+        }
+    }
+
+    /// Register a contract called before and after every transfer (owner only)
+    ///
+    /// Hooks receive `beforeTransfer(from, to, amount)` and `afterTransfer(from, to, amount)`
+    /// with read-only call flags; a hook vetoes a transfer by aborting.
+    /// A hook that consumes more than `MAX_HOOK_EPICPULSE` also aborts the transfer; the
+    /// budget is checked after the call returns, not enforced while the hook runs.
+    #[method]
+    #[only_owner]
+    pub fn register_transfer_hook(&self, hook: H160) -> bool {
+        if hook == H160::zero() || hook == Runtime::get_executing_script_hash() {
+            Runtime::log(ByteString::from_literal("Invalid hook contract"));
+            return false;
+        }
+
+        let mut hooks = self.get_transfer_hooks();
+        if self.contains_token(&hooks, hook) {
+            Runtime::log(ByteString::from_literal("Hook already registered"));
+            return false;
+        }
+        if hooks.size() as u32 >= MAX_TRANSFER_HOOKS {
+            Runtime::log(ByteString::from_literal("Too many transfer hooks"));
+            return false;
+        }
+
+        hooks.push(hook);
+        let storage = Storage::get_context();
+        Storage::put(storage, KeyBuilder::new(KEY_TRANSFER_HOOKS).build(), self.serialize_tokens(&hooks));
+
+        let mut event_data = Array::new();
+        event_data.push(hook.into_any());
+        Runtime::notify(ByteString::from_literal("TransferHookRegistered"), event_data);
+        true
+    }
+
+    /// Remove a registered transfer hook (owner only)
+    #[method]
+    #[only_owner]
+    pub fn remove_transfer_hook(&self, hook: H160) -> bool {
+        let hooks = self.get_transfer_hooks();
+        if !self.contains_token(&hooks, hook) {
+            Runtime::log(ByteString::from_literal("Hook not registered"));
+            return false;
+        }
+
+        let mut remaining = Array::new();
+        for i in bounded_iter(&hooks, MAX_TRANSFER_HOOKS as usize) {
+            let existing = hooks.get(i);
+            if existing != hook {
+                remaining.push(existing);
+            }
+        }
+
+        let storage = Storage::get_context();
+        if remaining.size() == 0 {
+            Storage::delete(storage, KeyBuilder::new(KEY_TRANSFER_HOOKS).build());
+        } else {
+            Storage::put(storage, KeyBuilder::new(KEY_TRANSFER_HOOKS).build(), self.serialize_tokens(&remaining));
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(hook.into_any());
+        Runtime::notify(ByteString::from_literal("TransferHookRemoved"), event_data);
+        true
+    }
+
+    /// Set the contract allowed to change vote delegation, e.g. a governance DAO (owner only)
+    #[method]
+    #[only_owner]
+    pub fn set_votes_controller(&self, controller: H160) -> bool {
+        if controller == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid votes controller"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        Storage::put(storage, KeyBuilder::new(KEY_VOTES_CONTROLLER).build(), controller.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(controller.into_any());
+        Runtime::notify(ByteString::from_literal("VotesControllerChanged"), event_data);
+        true
+    }
+
+    /// Move the voting power of `delegator` to `delegatee` (votes controller only).
+    ///
+    /// The controller enforces its own delegation rules; delegating to yourself removes
+    /// the delegation.
+    #[method]
+    pub fn set_vote_delegate(&self, delegator: H160, delegatee: H160) -> bool {
+        let storage = Storage::get_context();
+        let is_controller = match Storage::get(storage.clone(), KeyBuilder::new(KEY_VOTES_CONTROLLER).build()) {
+            Some(controller) => H160::from_byte_string(controller) == Runtime::get_calling_script_hash(),
+            None => false,
+        };
+        if !is_controller {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only the votes controller"));
+            return false;
+        }
+
+        if delegatee == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid delegatee"));
+            return false;
+        }
+
+        let current = self.vote_delegate(delegator);
+        if current == delegatee {
+            return true;
+        }
+
+        let delegate_key = KeyBuilder::new(PREFIX_VOTE_DELEGATE).push(delegator).build();
+        if delegatee == delegator {
+            Storage::delete(storage, delegate_key);
+        } else {
+            Storage::put(storage, delegate_key, delegatee.into_byte_string());
+        }
+
+        self.move_delegate_votes(current, delegatee, self.balance_of(delegator));
+        true
+    }
+
+    /// Get the account that votes with `account`'s tokens (the account itself if undelegated)
+    #[method]
+    #[safe]
+    pub fn vote_delegate(&self, account: H160) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, KeyBuilder::new(PREFIX_VOTE_DELEGATE).push(account).build()) {
+            Some(delegate_bytes) => H160::from_byte_string(delegate_bytes),
+            None => account,
+        }
+    }
+
+    /// Get the current voting power of an account, including delegated votes
+    #[method]
+    #[safe]
+    pub fn get_votes(&self, account: H160) -> Int256 {
+        self.latest_checkpoint_value(&self.get_vote_checkpoint_base(account))
+    }
+
+    /// Get the voting power of an account as of a timestamp
+    #[method]
+    #[safe]
+    pub fn get_past_votes(&self, account: H160, timestamp: u64) -> Int256 {
+        self.checkpoint_value_at(&self.get_vote_checkpoint_base(account), timestamp)
+    }
+
+    /// Get the total supply as of a timestamp
+    #[method]
+    #[safe]
+    pub fn get_past_total_supply(&self, timestamp: u64) -> Int256 {
+        self.checkpoint_value_at(&self.get_supply_checkpoint_base(), timestamp)
+    }
+
+    /// Get the number of voting power checkpoints of an account
+    #[method]
+    #[safe]
+    pub fn get_vote_checkpoint_count(&self, account: H160) -> u32 {
+        self.get_checkpoint_count_at(&self.get_vote_checkpoint_base(account))
+    }
+
+    /// Get the registered transfer hooks
+    #[method]
+    #[safe]
+    pub fn get_transfer_hooks(&self) -> Array<H160> {
+        let storage = Storage::get_context();
+        match Storage::get(storage, KeyBuilder::new(KEY_TRANSFER_HOOKS).build()) {
+            Some(data) => self.deserialize_tokens(data),
+            None => Array::new(),
+        }
+    }
+
+    // Helper functions
+
+    /// State that must survive a code update: owner | total supply
+    fn state_snapshot(&self) -> ByteString {
+        self.get_owner().into_byte_string().concat(&self.total_supply().into_byte_string())
+    }
+
+    fn is_owner(&self) -> bool {
+        let owner = self.get_owner();
+        if owner == H160::zero() {
+            return false;
+        }
+        Runtime::check_witness(owner)
+    }
+
+    /// Sets or clears the frozen flag of `account` if `caller` may manage freezes
+    fn set_frozen(&self, caller: H160, account: H160, frozen: bool) -> bool {
+        if !self.is_freezable() {
+            Runtime::log(ByteString::from_literal("Account freezing is not enabled"));
+            return false;
+        }
+
+        let authorized = (caller == self.get_owner() && self.is_owner())
+            || access::is_role_witnessed(COMPLIANCE_ROLE, caller);
+        if !authorized {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or compliance officer"));
+            return false;
+        }
+
+        if self.is_frozen(account) == frozen {
+            Runtime::log(ByteString::from_literal(if frozen { "Account already frozen" } else { "Account not frozen" }));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let frozen_key = KeyBuilder::new(PREFIX_FROZEN).push(account).build();
+        if frozen {
+            Storage::put(storage, frozen_key, ByteString::from_literal("true"));
+        } else {
+            Storage::delete(storage, frozen_key);
+        }
+        true
+    }
+
+    fn is_authorized_minter(&self) -> bool {
+        if self.is_owner() {
+            return true;
+        }
+
+        Self::has_role(ByteString::from_literal(MINTER_ROLE), Runtime::get_calling_script_hash())
+    }
+
+    fn update_balance(&self, account: H160, new_balance: Int256) {
+        // Keep already-earned dividends stable when the balance changes
+        let delta = new_balance.checked_sub(&self.balance_of(account));
+        if delta != Int256::zero() {
+            self.correct_dividends(account, delta);
+        }
+
+        if new_balance == Int256::zero() {
+            self.balances().delete(account);
+        } else {
+            self.balances().put(account, new_balance);
+        }
+    }
+
+    fn get_stat(&self, tag: u8) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, KeyBuilder::new(tag).build()) {
+            Some(data) => Int256::from_byte_string(data),
+            None => Int256::zero(),
+        }
+    }
+
+    fn record_mint(&self, amount: Int256) {
+        let storage = Storage::get_context();
+        Storage::put(storage.clone(), KeyBuilder::new(KEY_TOTAL_MINTED).build(), self.total_minted().checked_add(&amount).into_byte_string());
+        Storage::put(storage, KeyBuilder::new(KEY_MINT_COUNT).build(), self.mint_count().checked_inc().into_byte_string());
+    }
+
+    fn balances(&self) -> TypedStorageMap<H160, Int256> {
+        TypedStorageMap::with_tag(PREFIX_BALANCE)
+    }
+
+    fn set_allowance(&self, owner: H160, spender: H160, amount: Int256) {
+        let storage = Storage::get_context();
+        let allowance_key = self.get_allowance_key(owner, spender);
+        self.clear_allowance_terms(owner, spender);
+
+        if amount == Int256::zero() {
+            Storage::delete(storage, allowance_key);
+        } else {
+            Storage::put(storage, allowance_key, amount.into_byte_string());
+        }
+
+        // Emit Approval event
+        let mut event_data = Array::new();
+        event_data.push(owner.into_any());
+        event_data.push(spender.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("Approval"), event_data);
+    }
+
+    fn encode_permit(&self, owner: H160, spender: H160, amount: Int256, nonce: Int256, deadline: u64) -> ByteString {
+        let domain_separator = sha256(
+            ByteString::from_literal(PERMIT_DOMAIN)
+                .concat(&ByteString::from_bytes(&[PERMIT_VERSION]))
+                .concat(&ByteString::from_bytes(&Runtime::get_network().to_le_bytes()))
+                .concat(&Runtime::get_executing_script_hash().into_byte_string())
+        );
+        let amount = amount.into_byte_string();
+        let nonce = nonce.into_byte_string();
+
+        ByteString::from_bytes(&domain_separator.to_bytes())
+            .concat(&owner.into_byte_string())
+            .concat(&spender.into_byte_string())
+            .concat(&ByteString::from_bytes(&[amount.len() as u8]))
+            .concat(&amount)
+            .concat(&ByteString::from_bytes(&[nonce.len() as u8]))
+            .concat(&nonce)
+            .concat(&ByteString::from_bytes(&deadline.to_le_bytes()))
+    }
+
+    fn get_allowance_key(&self, owner: H160, spender: H160) -> ByteString {
+        KeyBuilder::new(PREFIX_ALLOWANCE).push(owner).push(spender).build()
+    }
+
+    fn get_allowance_terms(&self, owner: H160, spender: H160) -> Option<AllowanceTerms> {
+        let storage = Storage::get_context();
+        let terms_key = KeyBuilder::new(PREFIX_ALLOWANCE_TERMS).push(owner).push(spender).build();
+        Storage::get(storage, terms_key).map(|data| self.deserialize_allowance_terms(data))
+    }
+
+    fn clear_allowance_terms(&self, owner: H160, spender: H160) {
+        let storage = Storage::get_context();
+        Storage::delete(storage.clone(), KeyBuilder::new(PREFIX_ALLOWANCE_TERMS).push(owner).push(spender).build());
+        Storage::delete(storage, KeyBuilder::new(PREFIX_ALLOWANCE_SPENT).push(owner).push(spender).build());
+    }
+
+    /// Amount spent in the current period, zero without a period cap
+    fn period_spent(&self, owner: H160, spender: H160, terms: &AllowanceTerms) -> Int256 {
+        let Some(index) = period_index_at(Runtime::get_time(), terms.period_start, terms.period) else {
+            return Int256::zero();
+        };
+
+        let storage = Storage::get_context();
+        let spent_key = KeyBuilder::new(PREFIX_ALLOWANCE_SPENT).push(owner).push(spender).build();
+        match Storage::get(storage, spent_key) {
+            Some(data) => {
+                let bytes = data.to_bytes();
+                let recorded = u64::from_le_bytes([
+                    bytes[0], bytes[1], bytes[2], bytes[3],
+                    bytes[4], bytes[5], bytes[6], bytes[7]
+                ]);
+                if recorded == index {
+                    Int256::from_byte_string(ByteString::from_bytes(&bytes[8..]))
+                } else {
+                    Int256::zero()
+                }
+            }
+            None => Int256::zero(),
+        }
+    }
+
+    /// Spent record layout: period index (u64) | amount spent in that period
+    fn record_period_spend(&self, owner: H160, spender: H160, terms: &AllowanceTerms, amount: Int256) {
+        let Some(index) = period_index_at(Runtime::get_time(), terms.period_start, terms.period) else {
+            return;
+        };
+
+        let spent = self.period_spent(owner, spender, terms).checked_add(&amount);
+        let storage = Storage::get_context();
+        let spent_key = KeyBuilder::new(PREFIX_ALLOWANCE_SPENT).push(owner).push(spender).build();
+        Storage::put(
+            storage,
+            spent_key,
+            ByteString::from_bytes(&index.to_le_bytes()).concat(&spent.into_byte_string())
+        );
+    }
+
+    /// Part of `allowance` usable now, given what was already spent this period
+    fn available_allowance(&self, allowance: Int256, spent: Int256, terms: &AllowanceTerms) -> Int256 {
+        if terms.expires_at != 0 && is_expired_at(terms.expires_at, Runtime::get_time()) {
+            return Int256::zero();
+        }
+        if terms.period == 0 {
+            return allowance;
+        }
+        let remaining = terms.period_limit.checked_sub(&spent);
+        math::max(Int256::zero(), math::min(allowance, remaining))
+    }
+
+    /// Terms record layout: expires_at (u64) | period (u64) | period_start (u64) | period_limit
+    fn serialize_allowance_terms(&self, terms: &AllowanceTerms) -> ByteString {
+        ByteString::from_bytes(&terms.expires_at.to_le_bytes())
+            .concat(&ByteString::from_bytes(&terms.period.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&terms.period_start.to_le_bytes()))
+            .concat(&terms.period_limit.into_byte_string())
+    }
+
+    fn deserialize_allowance_terms(&self, data: ByteString) -> AllowanceTerms {
+        let bytes = data.to_bytes();
+        let u64_at = |offset: usize| {
+            let mut value = [0u8; 8];
+            value.copy_from_slice(&bytes[offset..offset + 8]);
+            u64::from_le_bytes(value)
+        };
+        AllowanceTerms {
+            expires_at: u64_at(0),
+            period: u64_at(8),
+            period_start: u64_at(16),
+            period_limit: Int256::from_byte_string(ByteString::from_bytes(&bytes[24..])),
+        }
+    }
+
+    fn correct_dividends(&self, account: H160, delta: Int256) {
+        let revenue_tokens = self.get_revenue_tokens();
+        let storage = Storage::get_context();
+
+        for i in bounded_iter(&revenue_tokens, MAX_REVENUE_TOKENS as usize) {
+            let token = revenue_tokens.get(i);
+            let adjustment = self.get_dividend_per_share(token).checked_mul(&delta);
+            let correction = self.get_dividend_correction(token, account).checked_sub(&adjustment);
+            let correction_key = self.get_dividend_key(PREFIX_DIVIDEND_CORRECTION, token, account);
+
+            if correction == Int256::zero() {
+                Storage::delete(storage.clone(), correction_key);
+            } else {
+                Storage::put(storage.clone(), correction_key, correction.into_byte_string());
+            }
+        }
+    }
+
+    fn get_dividend_key(&self, tag: u8, token: H160, account: H160) -> ByteString {
+        KeyBuilder::new(tag).push(token).push(account).build()
+    }
+
+    fn get_dividend_per_share(&self, token: H160) -> Int256 {
+        let storage = Storage::get_context();
+        let per_share_key = KeyBuilder::new(PREFIX_DIVIDEND_PER_SHARE).push(token).build();
+        match Storage::get(storage, per_share_key) {
+            Some(data) => Int256::from_byte_string(data),
+            None => Int256::zero(),
+        }
+    }
+
+    fn get_dividend_correction(&self, token: H160, account: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.get_dividend_key(PREFIX_DIVIDEND_CORRECTION, token, account)) {
+            Some(data) => Int256::from_byte_string(data),
+            None => Int256::zero(),
+        }
+    }
+
+    fn get_dividend_withdrawn(&self, token: H160, account: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.get_dividend_key(PREFIX_DIVIDEND_WITHDRAWN, token, account)) {
+            Some(data) => Int256::from_byte_string(data),
+            None => Int256::zero(),
+        }
+    }
+
+    fn get_dividend_reserved(&self, token: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, KeyBuilder::new(PREFIX_DIVIDEND_RESERVED).push(token).build()) {
+            Some(data) => Int256::from_byte_string(data),
+            None => Int256::zero(),
+        }
+    }
+
+    fn set_dividend_reserved(&self, token: H160, reserved: Int256) {
+        let storage = Storage::get_context();
+        Storage::put(storage, KeyBuilder::new(PREFIX_DIVIDEND_RESERVED).push(token).build(), reserved.into_byte_string());
+    }
+
+    fn token_balance(&self, token: H160, account: H160) -> Int256 {
+        let mut args = Array::new();
+        args.push(account.into_any());
+        Contract::call(token, ByteString::from_literal("balanceOf"), CallFlags::ReadOnly, args).downcast_into()
+    }
+
+    fn contains_token(&self, tokens: &Array<H160>, token: H160) -> bool {
+        for i in 0..tokens.size() {
+            if tokens.get(i) == token {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn serialize_tokens(&self, tokens: &Array<H160>) -> ByteString {
+        let count = tokens.size() as u32;
+        let mut result = ByteString::from_bytes(&count.to_le_bytes());
+
+        for i in 0..tokens.size() {
+            result = result.concat(&tokens.get(i).into_byte_string());
+        }
+
+        result
+    }
+
+    fn deserialize_tokens(&self, data: ByteString) -> Array<H160> {
+        let bytes = data.to_bytes();
+        let mut tokens = Array::new();
+
+        if bytes.len() < 4 {
+            return tokens;
+        }
+
+        let count = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+        let mut offset = 4;
+
+        for _ in 0..count {
+            if offset + 20 <= bytes.len() {
+                tokens.push(H160::from_byte_string(ByteString::from_bytes(&bytes[offset..offset + 20])));
+                offset += 20;
+            }
+        }
+
+        tokens
+    }
+
+    fn run_transfer_hooks(&self, method: &str, from: H160, to: H160, amount: Int256) {
+        let hooks = self.get_transfer_hooks();
+        if hooks.size() == 0 {
+            return;
+        }
+
+        // Hooks run read-only, but a hook must never be able to re-enter a transfer
+        let storage = Storage::get_context();
+        assert_with_code(
+            Storage::get(storage.clone(), KeyBuilder::new(KEY_HOOK_LOCK).build()).is_none(),
+            ErrorCode::InvalidState,
+            "Reentrant transfer",
+        );
+        Storage::put(storage.clone(), KeyBuilder::new(KEY_HOOK_LOCK).build(), ByteString::from_literal("1"));
+
+        let limit = Int256::new(MAX_HOOK_EPICPULSE);
+        for i in bounded_iter(&hooks, MAX_TRANSFER_HOOKS as usize) {
+            let before = Runtime::get_epicpulse_left();
+            assert_with_code(before >= limit, ErrorCode::LimitExceeded, "Not enough EpicPulse for transfer hooks");
+
+            let mut args = Array::new();
+            args.push(from.into_any());
+            args.push(to.into_any());
+            args.push(amount.into_any());
+            Contract::call(hooks.get(i), ByteString::from_literal(method), CallFlags::ReadOnly, args);
+
+            // Post-hoc check: the hook has already run, so this only aborts the transfer
+            let used = before.checked_sub(&Runtime::get_epicpulse_left());
+            assert_with_code(used <= limit, ErrorCode::LimitExceeded, "Transfer hook exceeded EpicPulse limit");
+        }
+
+        Storage::delete(storage, KeyBuilder::new(KEY_HOOK_LOCK).build());
+    }
+
+    /// Follow a balance change with the votes of both sides' delegates; the zero address
+    /// stands for minted or burned tokens, which change the total supply checkpoints
+    fn move_voting_power(&self, from: H160, to: H160, amount: Int256) {
+        if from == H160::zero() {
+            let supply = self.latest_checkpoint_value(&self.get_supply_checkpoint_base()).checked_add(&amount);
+            self.write_checkpoint(&self.get_supply_checkpoint_base(), supply);
+        }
+        if to == H160::zero() {
+            let supply = self.latest_checkpoint_value(&self.get_supply_checkpoint_base()).checked_sub(&amount);
+            self.write_checkpoint(&self.get_supply_checkpoint_base(), supply);
+        }
+
+        let from_delegate = if from == H160::zero() { from } else { self.vote_delegate(from) };
+        let to_delegate = if to == H160::zero() { to } else { self.vote_delegate(to) };
+        self.move_delegate_votes(from_delegate, to_delegate, amount);
+    }
+
+    /// Move `amount` votes between two delegates' checkpoints
+    fn move_delegate_votes(&self, from: H160, to: H160, amount: Int256) {
+        if from == to || amount <= Int256::zero() {
+            return;
+        }
+
+        if from != H160::zero() {
+            let base = self.get_vote_checkpoint_base(from);
+            let previous = self.latest_checkpoint_value(&base);
+            let votes = previous.checked_sub(&amount);
+            self.write_checkpoint(&base, votes);
+            self.emit_delegate_votes_changed(from, previous, votes);
+        }
+
+        if to != H160::zero() {
+            let base = self.get_vote_checkpoint_base(to);
+            let previous = self.latest_checkpoint_value(&base);
+            let votes = previous.checked_add(&amount);
+            self.write_checkpoint(&base, votes);
+            self.emit_delegate_votes_changed(to, previous, votes);
+        }
+    }
+
+    fn emit_delegate_votes_changed(&self, delegate: H160, previous: Int256, votes: Int256) {
+        let mut event_data = Array::new();
+        event_data.push(delegate.into_any());
+        event_data.push(previous.into_any());
+        event_data.push(votes.into_any());
+        Runtime::notify(ByteString::from_literal("DelegateVotesChanged"), event_data);
+    }
+
+    fn get_vote_checkpoint_base(&self, account: H160) -> ByteString {
+        KeyBuilder::new(PREFIX_VOTE_CHECKPOINT).push(account).build()
+    }
+
+    fn get_supply_checkpoint_base(&self) -> ByteString {
+        KeyBuilder::new(KEY_SUPPLY_CHECKPOINT).build()
+    }
+
+    fn get_checkpoint_count_at(&self, base: &ByteString) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, base.clone()) {
+            Some(count_bytes) => {
+                let bytes = count_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    /// Read checkpoint `index` as (timestamp, votes); entries are the timestamp (u64 LE)
+    /// followed by the vote count
+    fn get_checkpoint(&self, base: &ByteString, index: u32) -> (u64, Int256) {
+        let storage = Storage::get_context();
+        let checkpoint_key = base.concat(&ByteString::from_bytes(&index.to_le_bytes()));
+        match Storage::get(storage, checkpoint_key) {
+            Some(checkpoint_bytes) => {
+                let bytes = checkpoint_bytes.to_bytes();
+                if bytes.len() < 8 {
+                    return (0, Int256::zero());
+                }
+                let timestamp = u64::from_le_bytes([
+                    bytes[0], bytes[1], bytes[2], bytes[3],
+                    bytes[4], bytes[5], bytes[6], bytes[7]
+                ]);
+                (timestamp, Int256::from_byte_string(ByteString::from_bytes(&bytes[8..])))
+            },
+            None => (0, Int256::zero()),
+        }
+    }
+
+    fn latest_checkpoint_value(&self, base: &ByteString) -> Int256 {
+        match self.get_checkpoint_count_at(base) {
+            0 => Int256::zero(),
+            count => self.get_checkpoint(base, count - 1).1,
+        }
+    }
+
+    /// Append a checkpoint at the current time, or overwrite the latest one if it was
+    /// written in the same block
+    fn write_checkpoint(&self, base: &ByteString, votes: Int256) {
+        let now = Runtime::get_time();
+        let count = self.get_checkpoint_count_at(base);
+        let index = if count > 0 && self.get_checkpoint(base, count - 1).0 == now {
+            count - 1
+        } else {
+            count
+        };
+
+        let storage = Storage::get_context();
+        let entry = ByteString::from_bytes(&now.to_le_bytes()).concat(&votes.into_byte_string());
+        Storage::put(storage.clone(), base.concat(&ByteString::from_bytes(&index.to_le_bytes())), entry);
+        if index == count {
+            Storage::put(storage, base.clone(), ByteString::from_bytes(&(count + 1).to_le_bytes()));
+        }
+    }
+
+    /// Binary search for the value of the latest checkpoint at or before `timestamp`
+    fn checkpoint_value_at(&self, base: &ByteString, timestamp: u64) -> Int256 {
+        let count = self.get_checkpoint_count_at(base);
+        if count == 0 {
+            return Int256::zero();
+        }
+
+        // Most lookups are for recent timestamps
+        let (latest_time, latest_votes) = self.get_checkpoint(base, count - 1);
+        if latest_time <= timestamp {
+            return latest_votes;
+        }
+
+        // Find the first checkpoint written after `timestamp`
+        let mut low = 0;
+        let mut high = count - 1;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.get_checkpoint(base, mid).0 > timestamp {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        if low == 0 {
+            Int256::zero()
+        } else {
+            self.get_checkpoint(base, low - 1).1
+        }
+    }
+
+    fn emit_transfer(&self, from: H160, to: H160, amount: Int256) {
+        let mut event_data = Array::new();
+        event_data.push(from.into_any());
+        event_data.push(to.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("Transfer"), event_data);
+    }
+
+    fn on_payment_callback(&self, from: H160, to: H160, amount: Int256, data: Any) {
+        // Calls onNEP17Payment when the recipient is a deployed contract; a rejection aborts the transfer
+        invoke_on_nep17_payment(from, to, amount, data);
+    }
+}
+
+/// Two-step ownership handoff (`transfer_ownership`, `accept_ownership`, `renounce_ownership`
+/// and `pending_owner`), see [`Ownable`].
+#[contract_impl]
+impl Ownable for Nep17Token {}
+
+/// Owner-controlled emergency stop (`pause`, `unpause` and `is_paused`), see [`Pausable`].
+#[contract_impl]
+impl Pausable for Nep17Token {}
+
+/// Minter management through [`MINTER_ROLE`] (`grant_role`, `revoke_role`, `renounce_role`
+/// and `has_role`), see [`AccessControl`].
+#[contract_impl]
+impl AccessControl for Nep17Token {}
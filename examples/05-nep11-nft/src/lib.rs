@@ -7,7 +7,11 @@
 //! - Token metadata and properties system
 //! - Secure transfer mechanics
 //! - Enumeration capabilities
-//! - Administrative controls
+//! - On-chain attribute index (attribute key/value -> token ids)
+//! - Provenance hash commitment and numbered editions of a base work
+//! - Collection statistics (holders, mints, burns, top holders) kept up to date on-chain
+//! - Administrative controls and two-step ownership transfer
+//! - Owner-gated code upgrades, checked by the `_deploy` hook of the new code
 //! - Event emission for all operations
 //!
 //! This is a production-ready NFT contract suitable for digital collectibles,
@@ -17,7 +21,76 @@
 #![no_main]
 
 use epicchain_contract::prelude::*;
-use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
+use epicchain_contract::contract::invoke_on_nep11_payment;
+use epicchain_contract::contract::native::ContractManagement;
+use epicchain_contract::types::{IntoByteString, FromByteString, StackItemType, builtin::IntoAny};
+
+/// Page size of `tokens_with_attribute`
+const ATTRIBUTE_PAGE_SIZE: u32 = 50;
+
+/// Maximum length of an indexed attribute value
+const MAX_ATTRIBUTE_VALUE_LEN: usize = 64;
+
+/// Page size of `editions_of`
+const EDITION_PAGE_SIZE: u32 = 50;
+
+/// Maximum number of editions of one work
+const MAX_EDITIONS: u32 = 10_000;
+
+/// Maximum number of properties a token can be minted with
+const MAX_TOKEN_PROPERTIES: usize = 64;
+
+/// Maximum page size of `tokens` and `tokens_of`
+const MAX_TOKEN_PAGE_SIZE: u32 = 100;
+
+/// Number of accounts ranked by `top_holders`
+const MAX_TOP_HOLDERS: usize = 10;
+
+// Storage key tags. String keys shared with the framework ("contract_owner", "paused", "role_")
+// start with a lowercase letter, so tags stay below 0x40 and never prefix them.
+
+// Token metadata
+const KEY_SYMBOL: u8 = 0x01;
+const KEY_TOTAL_SUPPLY: u8 = 0x02;
+
+// Ownership and approvals
+const PREFIX_TOKEN_OWNER: u8 = 0x03;          // token_id -> owner
+const PREFIX_BALANCE: u8 = 0x04;              // owner -> balance count
+// 0x05 held per-owner token lists before `tokens_of` was paged; not reused
+const PREFIX_OWNED: u8 = 0x06;                // owner + token_id -> owned flag
+const PREFIX_PROPERTIES: u8 = 0x07;           // token_id -> properties
+const PREFIX_APPROVED: u8 = 0x08;             // token_id -> approved_address
+const PREFIX_OPERATOR: u8 = 0x09;             // owner + operator -> approved for all tokens
+
+// Administrative keys
+const KEY_UPDATE_SNAPSHOT: u8 = 0x0A;         // owner and supply recorded by update_contract
+
+// Configuration
+const KEY_BASE_URI: u8 = 0x0B;
+
+// Token enumeration
+const KEY_ALL_TOKENS: u8 = 0x0C;
+const PREFIX_TOKEN_INDEX: u8 = 0x0D;          // token_id -> index
+
+// Attribute index
+const KEY_INDEXED_ATTRIBUTES: u8 = 0x0E;      // list of indexed attribute keys
+const PREFIX_ATTRIBUTE: u8 = 0x0F;            // token_id + key -> indexed value
+const PREFIX_ATTRIBUTE_INDEX: u8 = 0x10;      // key + value + token_id -> indexed flag
+const PREFIX_ATTRIBUTE_COUNT: u8 = 0x11;      // key + value -> number of indexed tokens
+
+// Provenance and editions
+const KEY_PROVENANCE: u8 = 0x12;              // collection provenance hash
+const PREFIX_WORK: u8 = 0x13;                 // work_id -> max editions | printed editions
+const PREFIX_WORK_TOKENS: u8 = 0x14;          // work_id -> list of edition token_ids
+const PREFIX_EDITION: u8 = 0x15;              // token_id -> edition number | work_id
+
+// Collection statistics
+const KEY_HOLDER_COUNT: u8 = 0x16;            // accounts currently holding at least one token
+const KEY_UNIQUE_OWNER_COUNT: u8 = 0x17;      // accounts that ever held a token
+const PREFIX_SEEN_OWNER: u8 = 0x18;           // owner -> held a token at some point
+const KEY_MINT_COUNT: u8 = 0x19;              // tokens minted
+const KEY_BURN_COUNT: u8 = 0x1A;              // tokens burned
+const KEY_TOP_HOLDERS: u8 = 0x1B;             // largest holders: owner | balance length (u8) | balance, ...
 
 /// NEP-11 compliant non-fungible token contract
 #[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
@@ -27,28 +100,8 @@ use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny
 #[contract_meta("description", "Production-ready NEP-11 non-fungible token")]
 #[contract_meta("website", "https://github.com/R3E-Network/epicchain-contract-rs")]
 pub struct Nep11Token {
-    // Token metadata
-    symbol_key: ByteString,
-    total_supply_key: ByteString,
-
-    // Storage prefixes
-    owner_prefix: ByteString,        // token_id -> owner
-    balance_prefix: ByteString,      // owner -> balance count
-    token_prefix: ByteString,        // owner -> list of token_ids
-    properties_prefix: ByteString,   // token_id -> properties
-    approved_prefix: ByteString,     // token_id -> approved_address
-
-    // Administrative keys
+    // String key read by `Ownable`
     contract_owner_key: ByteString,
-    minters_prefix: ByteString,
-
-    // Configuration
-    paused_key: ByteString,
-    base_uri_key: ByteString,
-
-    // Token enumeration
-    all_tokens_key: ByteString,
-    token_index_prefix: ByteString,  // token_id -> index
 }
 
 #[contract_impl]
@@ -56,19 +109,7 @@ impl Nep11Token {
     /// Initialize the NFT contract
     pub fn init() -> Self {
         Self {
-            symbol_key: ByteString::from_literal("symbol"),
-            total_supply_key: ByteString::from_literal("total_supply"),
-            owner_prefix: ByteString::from_literal("owner_"),
-            balance_prefix: ByteString::from_literal("balance_"),
-            token_prefix: ByteString::from_literal("tokens_"),
-            properties_prefix: ByteString::from_literal("props_"),
-            approved_prefix: ByteString::from_literal("approved_"),
             contract_owner_key: ByteString::from_literal("contract_owner"),
-            minters_prefix: ByteString::from_literal("minter_"),
-            paused_key: ByteString::from_literal("paused"),
-            base_uri_key: ByteString::from_literal("base_uri"),
-            all_tokens_key: ByteString::from_literal("all_tokens"),
-            token_index_prefix: ByteString::from_literal("index_"),
         }
     }
 
@@ -101,17 +142,17 @@ impl Nep11Token {
         }
 
         // Store contract metadata
-        Storage::put(storage.clone(), self.symbol_key.clone(), symbol.clone());
+        Storage::put(storage.clone(), KeyBuilder::new(KEY_SYMBOL).build(), symbol.clone());
         Storage::put(storage.clone(), self.contract_owner_key.clone(), owner.into_byte_string());
-        Storage::put(storage.clone(), self.total_supply_key.clone(), Int256::zero().into_byte_string());
+        Storage::put(storage.clone(), KeyBuilder::new(KEY_TOTAL_SUPPLY).build(), Int256::zero().into_byte_string());
 
         if !base_uri.is_empty() {
-            Storage::put(storage.clone(), self.base_uri_key.clone(), base_uri);
+            Storage::put(storage.clone(), KeyBuilder::new(KEY_BASE_URI).build(), base_uri);
         }
 
         // Initialize empty token list
         let storage_clone = storage.clone();
-        Storage::put(storage_clone, self.all_tokens_key.clone(), ByteString::empty());
+        Storage::put(storage_clone, KeyBuilder::new(KEY_ALL_TOKENS).build(), ByteString::empty());
 
         let mut event_data = Array::new(); event_data.push(symbol.into_any()); Runtime::notify(ByteString::from_literal("ContractDeployed"), event_data);
         true
@@ -122,7 +163,7 @@ impl Nep11Token {
     #[safe]
     pub fn symbol(&self) -> ByteString {
         let storage = Storage::get_context();
-        match Storage::get(storage, self.symbol_key.clone()) {
+        match Storage::get(storage, KeyBuilder::new(KEY_SYMBOL).build()) {
             Some(symbol) => symbol,
             None => ByteString::from_literal("UNKNOWN"),
         }
@@ -140,7 +181,7 @@ impl Nep11Token {
     #[safe]
     pub fn total_supply(&self) -> Int256 {
         let storage = Storage::get_context();
-        match Storage::get(storage, self.total_supply_key.clone()) {
+        match Storage::get(storage, KeyBuilder::new(KEY_TOTAL_SUPPLY).build()) {
             Some(supply_bytes) => Int256::from_byte_string(supply_bytes),
             None => Int256::zero(),
         }
@@ -151,7 +192,7 @@ impl Nep11Token {
     #[safe]
     pub fn balance_of(&self, owner: H160) -> Int256 {
         let storage = Storage::get_context();
-        let balance_key = self.balance_prefix.concat(&owner.into_byte_string());
+        let balance_key = KeyBuilder::new(PREFIX_BALANCE).push(owner).build();
 
         match Storage::get(storage, balance_key) {
             Some(balance_bytes) => Int256::from_byte_string(balance_bytes),
@@ -164,7 +205,7 @@ impl Nep11Token {
     #[safe]
     pub fn owner_of(&self, token_id: ByteString) -> H160 {
         let storage = Storage::get_context();
-        let owner_key = self.owner_prefix.concat(&token_id);
+        let owner_key = KeyBuilder::new(PREFIX_TOKEN_OWNER).push(token_id.clone()).build();
 
         match Storage::get(storage, owner_key) {
             Some(owner_bytes) => H160::from_byte_string(owner_bytes),
@@ -172,17 +213,23 @@ impl Nep11Token {
         }
     }
 
-    /// Get tokens owned by an account (NEP-11 required)
+    /// Get a page of at most `limit` token IDs owned by an account starting at `offset`,
+    /// in ascending key order (NEP-11 required)
     #[method]
     #[safe]
-    pub fn tokens_of(&self, owner: H160) -> Array<ByteString> {
-        let storage = Storage::get_context();
-        let tokens_key = self.token_prefix.concat(&owner.into_byte_string());
+    pub fn tokens_of(&self, owner: H160, offset: u32, limit: u32) -> Page<ByteString> {
+        let prefix = KeyBuilder::new(PREFIX_OWNED).push(owner).build();
+        let token_ids = Storage::find_prefix(Storage::get_context(), prefix).remove_prefix().keys();
+        Page::from_iterator(token_ids, offset, limit.min(MAX_TOKEN_PAGE_SIZE), self.balance_of(owner))
+    }
 
-        match Storage::get(storage, tokens_key) {
-            Some(tokens_data) => self.deserialize_token_list(tokens_data),
-            None => Array::new(),
-        }
+    /// Get a page of at most `limit` minted token IDs starting at `offset`, in ascending key
+    /// order (NEP-11 optional)
+    #[method]
+    #[safe]
+    pub fn tokens(&self, offset: u32, limit: u32) -> Page<ByteString> {
+        let token_ids = Storage::find_prefix(Storage::get_context(), KeyBuilder::new(PREFIX_TOKEN_OWNER).build()).remove_prefix().keys();
+        Page::from_iterator(token_ids, offset, limit.min(MAX_TOKEN_PAGE_SIZE), self.total_supply())
     }
 
     /// Transfer a token (NEP-11 required)
@@ -197,12 +244,12 @@ impl Nep11Token {
 
         // Check authorization (owner or approved)
         if !self.is_authorized_for_token(from, token_id.clone()) {
-            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or approved"));
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner, approved or operator"));
             return false;
         }
 
         // Check if contract is paused
-        if self.is_paused() {
+        if Self::is_paused() {
             Runtime::log(ByteString::from_literal("Contract is paused"));
             return false;
         }
@@ -211,7 +258,43 @@ impl Nep11Token {
         self.transfer_token(from, to, token_id.clone());
 
         // Call onNEP11Payment if recipient is a contract
-        self.on_payment_callback(from, Int256::one(), token_id, data);
+        self.on_payment_callback(from, to, Int256::one(), token_id, data);
+
+        true
+    }
+
+    /// Transfer a token without calling onNEP11Payment (contract owner only).
+    /// Escape hatch for recipients that predate the callback; the holder must still authorize it.
+    #[method]
+    pub fn unsafe_transfer(&self, to: H160, token_id: ByteString) -> bool {
+        if !self.is_owner() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can skip the payment callback"));
+            return false;
+        }
+
+        let from = self.owner_of(token_id.clone());
+        if from == H160::zero() {
+            Runtime::log(ByteString::from_literal("Token does not exist"));
+            return false;
+        }
+
+        if !self.is_authorized_for_token(from, token_id.clone()) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner, approved or operator"));
+            return false;
+        }
+
+        if Self::is_paused() {
+            Runtime::log(ByteString::from_literal("Contract is paused"));
+            return false;
+        }
+
+        self.transfer_token(from, to, token_id.clone());
+
+        let mut event_data = Array::new();
+        event_data.push(from.into_any());
+        event_data.push(to.into_any());
+        event_data.push(token_id.into_any());
+        Runtime::notify(ByteString::from_literal("UnsafeTransfer"), event_data);
 
         true
     }
@@ -221,7 +304,7 @@ impl Nep11Token {
     #[safe]
     pub fn properties(&self, token_id: ByteString) -> Map<ByteString, Any> {
         let storage = Storage::get_context();
-        let props_key = self.properties_prefix.concat(&token_id);
+        let props_key = KeyBuilder::new(PREFIX_PROPERTIES).push(token_id.clone()).build();
 
         match Storage::get(storage, props_key) {
             Some(props_data) => self.deserialize_properties(props_data),
@@ -229,7 +312,8 @@ impl Nep11Token {
         }
     }
 
-    /// Approve another address to transfer a specific token
+    /// Approve another address to transfer a specific token (token owner or operator).
+    /// Approving the zero address clears the approval.
     #[method]
     pub fn approve(&self, to: H160, token_id: ByteString) -> bool {
         let owner = self.owner_of(token_id.clone());
@@ -239,14 +323,14 @@ impl Nep11Token {
         }
 
         // Verify authorization
-        if !Runtime::check_witness(owner) {
-            Runtime::log(ByteString::from_literal("Unauthorized: Not token owner"));
+        if !Runtime::check_witness(owner) && !self.is_calling_operator(owner) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not token owner or operator"));
             return false;
         }
 
         // Set approval
         let storage = Storage::get_context();
-        let approved_key = self.approved_prefix.concat(&token_id);
+        let approved_key = KeyBuilder::new(PREFIX_APPROVED).push(token_id.clone()).build();
 
         if to == H160::zero() {
             let storage_clone = storage.clone(); Storage::delete(storage_clone, approved_key);
@@ -269,7 +353,7 @@ impl Nep11Token {
     #[safe]
     pub fn get_approved(&self, token_id: ByteString) -> H160 {
         let storage = Storage::get_context();
-        let approved_key = self.approved_prefix.concat(&token_id);
+        let approved_key = KeyBuilder::new(PREFIX_APPROVED).push(token_id.clone()).build();
 
         match Storage::get(storage, approved_key) {
             Some(approved_bytes) => H160::from_byte_string(approved_bytes),
@@ -277,6 +361,47 @@ impl Nep11Token {
         }
     }
 
+    /// Approve or revoke an operator allowed to transfer, approve and burn all of the owner's
+    /// tokens, including ones received later. Operators act as the calling contract, e.g. a
+    /// marketplace that transfers listed tokens on sale.
+    #[method]
+    pub fn set_approval_for_all(&self, owner: H160, operator: H160, approved: bool) -> bool {
+        if !Runtime::check_witness(owner) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        if operator == H160::zero() || operator == owner {
+            Runtime::log(ByteString::from_literal("Invalid operator"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let operator_key = self.get_operator_key(owner, operator);
+        if approved {
+            Storage::put(storage, operator_key, ByteString::from_literal("true"));
+        } else {
+            Storage::delete(storage, operator_key);
+        }
+
+        let approved_flag = if approved { Int256::one() } else { Int256::zero() };
+        let mut event_data = Array::new();
+        event_data.push(owner.into_any());
+        event_data.push(operator.into_any());
+        event_data.push(approved_flag.into_any());
+        Runtime::notify(ByteString::from_literal("ApprovalForAll"), event_data);
+
+        true
+    }
+
+    /// Check if an operator is approved for all tokens of an owner
+    #[method]
+    #[safe]
+    pub fn is_approved_for_all(&self, owner: H160, operator: H160) -> bool {
+        let storage = Storage::get_context();
+        Storage::get(storage, self.get_operator_key(owner, operator)).is_some()
+    }
+
     /// Mint a new token (authorized minter only)
     #[method]
     pub fn mint(&self, to: H160, token_id: ByteString, properties: Map<ByteString, Any>) -> bool {
@@ -299,21 +424,24 @@ impl Nep11Token {
         let storage = Storage::get_context();
 
         // Set token owner
-        let owner_key = self.owner_prefix.concat(&token_id);
+        let owner_key = KeyBuilder::new(PREFIX_TOKEN_OWNER).push(token_id.clone()).build();
         Storage::put(storage.clone(), owner_key, to.into_byte_string());
 
         // Update owner's balance
         let current_balance = self.balance_of(to);
         let new_balance = current_balance.checked_add(&Int256::one());
-        let balance_key = self.balance_prefix.concat(&to.into_byte_string());
+        let balance_key = KeyBuilder::new(PREFIX_BALANCE).push(to).build();
         Storage::put(storage.clone(), balance_key, new_balance.into_byte_string());
+        self.record_holding(to, current_balance, new_balance);
 
         // Add token to owner's token list
         self.add_token_to_owner(to, token_id.clone());
 
         // Store properties if provided
         if properties.size() > 0 {
-            let props_key = self.properties_prefix.concat(&token_id);
+            self.index_properties(token_id.clone(), &properties);
+
+            let props_key = KeyBuilder::new(PREFIX_PROPERTIES).push(token_id.clone()).build();
             let serialized_props = self.serialize_properties(properties);
             Storage::put(storage.clone(), props_key, serialized_props);
         }
@@ -321,7 +449,8 @@ impl Nep11Token {
         // Update total supply
         let current_supply = self.total_supply();
         let new_supply = current_supply.checked_add(&Int256::one());
-        let storage_clone = storage.clone(); Storage::put(storage_clone, self.total_supply_key.clone(), new_supply.into_byte_string());
+        let storage_clone = storage.clone(); Storage::put(storage_clone, KeyBuilder::new(KEY_TOTAL_SUPPLY).build(), new_supply.into_byte_string());
+        self.increment_stat(KEY_MINT_COUNT);
 
         // Add to global token list
         self.add_token_to_global_list(token_id.clone());
@@ -333,7 +462,7 @@ impl Nep11Token {
         true
     }
 
-    /// Burn a token (token owner only)
+    /// Burn a token (token owner, approved address or operator)
     #[method]
     pub fn burn(&self, token_id: ByteString) -> bool {
         let owner = self.owner_of(token_id.clone());
@@ -343,35 +472,56 @@ impl Nep11Token {
         }
 
         // Verify authorization
-        if !Runtime::check_witness(owner) {
-            Runtime::log(ByteString::from_literal("Unauthorized: Not token owner"));
+        if !self.is_authorized_for_token(owner, token_id.clone()) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner, approved or operator"));
             return false;
         }
 
         let storage = Storage::get_context();
 
         // Remove token owner
-        let owner_key = self.owner_prefix.concat(&token_id);
+        let owner_key = KeyBuilder::new(PREFIX_TOKEN_OWNER).push(token_id.clone()).build();
         Storage::delete(storage.clone(), owner_key);
 
         // Remove approval if exists
-        let approved_key = self.approved_prefix.concat(&token_id);
+        let approved_key = KeyBuilder::new(PREFIX_APPROVED).push(token_id.clone()).build();
         Storage::delete(storage.clone(), approved_key);
 
         // Remove properties if exist
-        let props_key = self.properties_prefix.concat(&token_id);
+        let props_key = KeyBuilder::new(PREFIX_PROPERTIES).push(token_id.clone()).build();
         Storage::delete(storage.clone(), props_key);
 
+        // Drop the token from its work; edition numbers of the other prints are kept
+        if let Some((_, work_id)) = self.get_edition_record(&token_id) {
+            let mut remaining = Array::new();
+            let editions = self.get_work_tokens(&work_id);
+            for i in bounded_iter(&editions, MAX_EDITIONS as usize) {
+                let edition_token = editions.get(i);
+                if edition_token != token_id {
+                    remaining.push(edition_token);
+                }
+            }
+            Storage::put(storage.clone(), KeyBuilder::new(PREFIX_WORK_TOKENS).push(work_id.clone()).build(), self.serialize_token_list(remaining));
+            Storage::delete(storage.clone(), KeyBuilder::new(PREFIX_EDITION).push(token_id.clone()).build());
+        }
+
+        // Drop the token from every attribute index
+        let indexed_keys = self.get_indexed_attributes();
+        for i in 0..indexed_keys.size() {
+            self.set_indexed_value(token_id.clone(), indexed_keys.get(i), ByteString::empty());
+        }
+
         // Update owner's balance
         let current_balance = self.balance_of(owner);
         let new_balance = current_balance.checked_sub(&Int256::one());
-        let balance_key = self.balance_prefix.concat(&owner.into_byte_string());
+        let balance_key = KeyBuilder::new(PREFIX_BALANCE).push(owner).build();
 
         if new_balance == Int256::zero() {
             Storage::delete(storage.clone(), balance_key);
         } else {
             Storage::put(storage.clone(), balance_key, new_balance.into_byte_string());
         }
+        self.record_holding(owner, current_balance, new_balance);
 
         // Remove token from owner's token list
         self.remove_token_from_owner(owner, token_id.clone());
@@ -379,7 +529,8 @@ impl Nep11Token {
         // Update total supply
         let current_supply = self.total_supply();
         let new_supply = current_supply.checked_sub(&Int256::one());
-        let storage_clone = storage.clone(); Storage::put(storage_clone, self.total_supply_key.clone(), new_supply.into_byte_string());
+        let storage_clone = storage.clone(); Storage::put(storage_clone, KeyBuilder::new(KEY_TOTAL_SUPPLY).build(), new_supply.into_byte_string());
+        self.increment_stat(KEY_BURN_COUNT);
 
         // Remove from global token list
         self.remove_token_from_global_list(token_id.clone());
@@ -402,22 +553,54 @@ impl Nep11Token {
         }
     }
 
-    /// Add authorized minter (owner only)
+    /// Upgrade the contract code and manifest (owner only).
+    ///
+    /// The owner and token supply are recorded before calling `ContractManagement.update`; the new
+    /// code's `_deploy` runs in the same transaction and aborts it, reverting the update,
+    /// unless both are unchanged.
     #[method]
-    pub fn add_minter(&self, minter: H160) -> bool {
+    pub fn update_contract(&self, nef: ByteString, manifest: ByteString, data: Any) -> bool {
         if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can add minters"));
+            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can update the contract"));
+            return false;
+        }
+
+        if nef.is_empty() || manifest.is_empty() {
+            Runtime::log(ByteString::from_literal("Invalid contract update"));
             return false;
         }
 
         let storage = Storage::get_context();
-        let minter_key = self.minters_prefix.concat(&minter.into_byte_string());
-        let storage_clone = storage.clone(); Storage::put(storage_clone, minter_key, ByteString::from_literal("true"));
+        Storage::put(storage, KeyBuilder::new(KEY_UPDATE_SNAPSHOT).build(), self.state_snapshot());
 
-        let mut event_data = Array::new(); event_data.push(minter.into_any()); Runtime::notify(ByteString::from_literal("MinterAdded"), event_data);
+        ContractManagement::update(nef, manifest, data);
         true
     }
 
+    /// Called by ContractManagement after deployment and after each update
+    #[method]
+    pub fn _deploy(&self, _data: Any, update: bool) {
+        if !update {
+            return;
+        }
+
+        // Updates must go through `update_contract`, which snapshots the state first
+        let storage = Storage::get_context();
+        let snapshot = Storage::get(storage.clone(), KeyBuilder::new(KEY_UPDATE_SNAPSHOT).build());
+        assert_with_code(snapshot.is_some(), ErrorCode::InvalidState, "Update not started by update_contract");
+        assert_with_code(
+            snapshot.unwrap() == self.state_snapshot(),
+            ErrorCode::InvalidState,
+            "Owner or supply changed by update",
+        );
+        Storage::delete(storage, KeyBuilder::new(KEY_UPDATE_SNAPSHOT).build());
+
+        let mut event_data = Array::new();
+        event_data.push(self.get_owner().into_any());
+        event_data.push(Runtime::get_executing_script_hash().into_any());
+        Runtime::notify(ByteString::from_literal("ContractUpdated"), event_data);
+    }
+
     /// Set base URI for token metadata (owner only)
     #[method]
     pub fn set_base_uri(&self, base_uri: ByteString) -> bool {
@@ -428,10 +611,10 @@ impl Nep11Token {
 
         let storage = Storage::get_context();
         if base_uri.is_empty() {
-            let storage_clone = storage.clone(); Storage::delete(storage_clone, self.base_uri_key.clone());
+            let storage_clone = storage.clone(); Storage::delete(storage_clone, KeyBuilder::new(KEY_BASE_URI).build());
         } else {
             let storage_clone = storage.clone();
-            Storage::put(storage_clone, self.base_uri_key.clone(), base_uri.clone());
+            Storage::put(storage_clone, KeyBuilder::new(KEY_BASE_URI).build(), base_uri.clone());
         }
 
         let mut event_data = Array::new(); event_data.push(base_uri.into_any()); Runtime::notify(ByteString::from_literal("BaseURISet"), event_data);
@@ -443,7 +626,7 @@ impl Nep11Token {
     #[safe]
     pub fn get_base_uri(&self) -> ByteString {
         let storage = Storage::get_context();
-        match Storage::get(storage, self.base_uri_key.clone()) {
+        match Storage::get(storage, KeyBuilder::new(KEY_BASE_URI).build()) {
             Some(uri) => uri,
             None => ByteString::empty(),
         }
@@ -465,31 +648,324 @@ impl Nep11Token {
         base_uri.concat(&token_id)
     }
 
-    /// Pause contract (owner only)
+    /// Register an attribute key to be indexed on mint and update (owner only)
     #[method]
-    pub fn pause(&self) -> bool {
+    pub fn add_indexed_attribute(&self, key: ByteString) -> bool {
         if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can pause"));
+            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can index attributes"));
             return false;
         }
 
+        if key.is_empty() || key.len() > 32 {
+            Runtime::log(ByteString::from_literal("Invalid attribute key"));
+            return false;
+        }
+
+        let mut keys = self.get_indexed_attributes();
+        for i in 0..keys.size() {
+            if keys.get(i) == key {
+                Runtime::log(ByteString::from_literal("Attribute already indexed"));
+                return false;
+            }
+        }
+
+        keys.push(key.clone());
         let storage = Storage::get_context();
-        let storage_clone = storage.clone(); Storage::put(storage_clone, self.paused_key.clone(), ByteString::from_literal("true"));
+        Storage::put(storage, KeyBuilder::new(KEY_INDEXED_ATTRIBUTES).build(), self.serialize_token_list(keys));
+
+        let mut event_data = Array::new();
+        event_data.push(key.into_any());
+        Runtime::notify(ByteString::from_literal("AttributeIndexed"), event_data);
+        true
+    }
+
+    /// Update an indexed attribute of a token (authorized minter only)
+    #[method]
+    pub fn set_attribute(&self, token_id: ByteString, key: ByteString, value: ByteString) -> bool {
+        if !self.is_authorized_minter() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not authorized to update attributes"));
+            return false;
+        }
+
+        if self.owner_of(token_id.clone()) == H160::zero() {
+            Runtime::log(ByteString::from_literal("Token does not exist"));
+            return false;
+        }
+
+        if !self.is_indexed_attribute(&key) {
+            Runtime::log(ByteString::from_literal("Attribute is not indexed"));
+            return false;
+        }
 
-        Runtime::notify(ByteString::from_literal("ContractPaused"), Array::new());
+        if value.len() > MAX_ATTRIBUTE_VALUE_LEN {
+            Runtime::log(ByteString::from_literal("Attribute value too long"));
+            return false;
+        }
+
+        self.set_indexed_value(token_id.clone(), key.clone(), value.clone());
+
+        let mut event_data = Array::new();
+        event_data.push(token_id.into_any());
+        event_data.push(key.into_any());
+        event_data.push(value.into_any());
+        Runtime::notify(ByteString::from_literal("AttributeUpdated"), event_data);
         true
     }
 
-    /// Check if contract is paused
+    /// Get the indexed value of a token attribute (empty if unset)
+    #[method]
+    #[safe]
+    pub fn get_attribute(&self, token_id: ByteString, key: ByteString) -> ByteString {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.get_attribute_key(&token_id, &key)) {
+            Some(value) => value,
+            None => ByteString::empty(),
+        }
+    }
+
+    /// Get a page of tokens whose indexed attribute `key` equals `value`
+    #[method]
+    #[safe]
+    pub fn tokens_with_attribute(&self, key: ByteString, value: ByteString, cursor: u32) -> Page<ByteString> {
+        let prefix = self.get_attribute_index_prefix(&key, &value);
+        let token_ids = Storage::find_prefix(Storage::get_context(), prefix).remove_prefix().keys();
+        Page::from_iterator(token_ids, cursor, ATTRIBUTE_PAGE_SIZE, self.get_attribute_count(&key, &value))
+    }
+
+    /// Get the list of indexed attribute keys
     #[method]
     #[safe]
-    pub fn is_paused(&self) -> bool {
+    pub fn get_indexed_attributes(&self) -> Array<ByteString> {
+        let storage = Storage::get_context();
+        match Storage::get(storage, KeyBuilder::new(KEY_INDEXED_ATTRIBUTES).build()) {
+            Some(keys_data) => self.deserialize_token_list(keys_data),
+            None => Array::new(),
+        }
+    }
+
+    /// Commit to the provenance hash of the collection (owner only, once, before the first mint).
+    ///
+    /// The hash is typically the SHA-256 of the concatenated artwork hashes in minting order,
+    /// so collectors can verify the artworks were fixed before the sale.
+    #[method]
+    pub fn set_provenance_hash(&self, provenance_hash: ByteString) -> bool {
+        if !self.is_owner() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can set provenance"));
+            return false;
+        }
+
+        if provenance_hash.len() != 32 {
+            Runtime::log(ByteString::from_literal("Invalid provenance hash: must be 32 bytes"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        if Storage::get(storage.clone(), KeyBuilder::new(KEY_PROVENANCE).build()).is_some() {
+            Runtime::log(ByteString::from_literal("Provenance hash already set"));
+            return false;
+        }
+
+        if self.total_supply() > Int256::zero() {
+            Runtime::log(ByteString::from_literal("Provenance must be set before minting"));
+            return false;
+        }
+
+        Storage::put(storage, KeyBuilder::new(KEY_PROVENANCE).build(), provenance_hash.clone());
+
+        let mut event_data = Array::new();
+        event_data.push(provenance_hash.into_any());
+        Runtime::notify(ByteString::from_literal("ProvenanceSet"), event_data);
+        true
+    }
+
+    /// Get the provenance hash of the collection (empty if none was committed)
+    #[method]
+    #[safe]
+    pub fn get_provenance_hash(&self) -> ByteString {
+        let storage = Storage::get_context();
+        match Storage::get(storage, KeyBuilder::new(KEY_PROVENANCE).build()) {
+            Some(provenance_hash) => provenance_hash,
+            None => ByteString::empty(),
+        }
+    }
+
+    /// Register a base work that can be printed in up to `max_editions` numbered editions (owner only)
+    #[method]
+    pub fn create_work(&self, work_id: ByteString, max_editions: u32) -> bool {
+        if !self.is_owner() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can create works"));
+            return false;
+        }
+
+        if work_id.is_empty() || work_id.len() > 32 {
+            Runtime::log(ByteString::from_literal("Invalid work ID: must be 1-32 characters"));
+            return false;
+        }
+
+        if max_editions == 0 || max_editions > MAX_EDITIONS {
+            Runtime::log(ByteString::from_literal("Invalid max editions (1-10000)"));
+            return false;
+        }
+
+        if self.get_work_record(&work_id).is_some() {
+            Runtime::log(ByteString::from_literal("Work already exists"));
+            return false;
+        }
+
+        self.put_work_record(&work_id, max_editions, 0);
+
+        let mut event_data = Array::new();
+        event_data.push(work_id.into_any());
+        event_data.push(Int256::new(max_editions as i64).into_any());
+        Runtime::notify(ByteString::from_literal("WorkCreated"), event_data);
+        true
+    }
+
+    /// Mint the next numbered edition of a work (authorized minter only).
+    ///
+    /// Returns the edition number, or 0 on failure.
+    #[method]
+    pub fn mint_edition(&self, to: H160, token_id: ByteString, work_id: ByteString, properties: Map<ByteString, Any>) -> u32 {
+        let (max_editions, printed) = match self.get_work_record(&work_id) {
+            Some(record) => record,
+            None => {
+                Runtime::log(ByteString::from_literal("Work does not exist"));
+                return 0;
+            }
+        };
+
+        if printed >= max_editions {
+            Runtime::log(ByteString::from_literal("All editions printed"));
+            return 0;
+        }
+
+        if !self.mint(to, token_id.clone(), properties) {
+            return 0;
+        }
+
+        let edition = printed + 1;
+        self.put_work_record(&work_id, max_editions, edition);
+
+        let mut editions = self.get_work_tokens(&work_id);
+        editions.push(token_id.clone());
         let storage = Storage::get_context();
-        Storage::get(storage, self.paused_key.clone()).is_some()
+        Storage::put(storage.clone(), KeyBuilder::new(PREFIX_WORK_TOKENS).push(work_id.clone()).build(), self.serialize_token_list(editions));
+        Storage::put(
+            storage,
+            KeyBuilder::new(PREFIX_EDITION).push(token_id.clone()).build(),
+            ByteString::from_bytes(&edition.to_le_bytes()).concat(&work_id),
+        );
+
+        let mut event_data = Array::new();
+        event_data.push(token_id.into_any());
+        event_data.push(work_id.into_any());
+        event_data.push(Int256::new(edition as i64).into_any());
+        event_data.push(Int256::new(max_editions as i64).into_any());
+        Runtime::notify(ByteString::from_literal("EditionMinted"), event_data);
+        edition
+    }
+
+    /// Get the edition of a token, e.g. 12 of 100 (empty map for tokens outside any work)
+    #[method]
+    #[safe]
+    pub fn get_edition(&self, token_id: ByteString) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        if let Some((edition, work_id)) = self.get_edition_record(&token_id) {
+            let (max_editions, _) = self.get_work_record(&work_id).unwrap_or((0, 0));
+            result.put(ByteString::from_literal("work_id"), work_id.into_any());
+            result.put(ByteString::from_literal("edition"), Int256::new(edition as i64).into_any());
+            result.put(ByteString::from_literal("max_editions"), Int256::new(max_editions as i64).into_any());
+        }
+        result
+    }
+
+    /// Get the edition cap and print count of a work (empty map for unknown works)
+    #[method]
+    #[safe]
+    pub fn get_work(&self, work_id: ByteString) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        if let Some((max_editions, printed)) = self.get_work_record(&work_id) {
+            result.put(ByteString::from_literal("max_editions"), Int256::new(max_editions as i64).into_any());
+            result.put(ByteString::from_literal("printed"), Int256::new(printed as i64).into_any());
+            result.put(ByteString::from_literal("existing"), Int256::new(self.get_work_tokens(&work_id).size() as i64).into_any());
+        }
+        result
+    }
+
+    /// Get a page of the existing editions of a work in minting order
+    #[method]
+    #[safe]
+    pub fn editions_of(&self, work_id: ByteString, cursor: u32) -> Page<ByteString> {
+        Page::from_array(&self.get_work_tokens(&work_id), cursor, EDITION_PAGE_SIZE)
+    }
+
+    /// Get the number of accounts currently holding at least one token
+    #[method]
+    #[safe]
+    pub fn holder_count(&self) -> Int256 {
+        self.get_stat(KEY_HOLDER_COUNT)
+    }
+
+    /// Get the number of distinct accounts that have ever held a token
+    #[method]
+    #[safe]
+    pub fn unique_owner_count(&self) -> Int256 {
+        self.get_stat(KEY_UNIQUE_OWNER_COUNT)
+    }
+
+    /// Get the number of tokens minted, including burned ones
+    #[method]
+    #[safe]
+    pub fn mint_count(&self) -> Int256 {
+        self.get_stat(KEY_MINT_COUNT)
+    }
+
+    /// Get the number of tokens burned
+    #[method]
+    #[safe]
+    pub fn burn_count(&self) -> Int256 {
+        self.get_stat(KEY_BURN_COUNT)
+    }
+
+    /// Get the largest holders, most tokens first (at most MAX_TOP_HOLDERS).
+    ///
+    /// The ranking is updated whenever a balance changes, so an account that drops out
+    /// only comes back once its own balance changes again, even if a ranked holder later
+    /// falls below it.
+    #[method]
+    #[safe]
+    pub fn top_holders(&self) -> Array<Map<ByteString, Any>> {
+        let (holders, balances) = self.get_top_holders();
+        let mut result = Array::new();
+        for i in 0..holders.size() {
+            let mut entry = Map::new();
+            entry.put(ByteString::from_literal("owner"), holders.get(i).into_any());
+            entry.put(ByteString::from_literal("balance"), balances.get(i).into_any());
+            result.push(entry);
+        }
+        result
+    }
+
+    /// Get the collection statistics in one call
+    #[method]
+    #[safe]
+    pub fn collection_stats(&self) -> Map<ByteString, Any> {
+        let mut stats = Map::new();
+        stats.put(ByteString::from_literal("total_supply"), self.total_supply().into_any());
+        stats.put(ByteString::from_literal("holders"), self.holder_count().into_any());
+        stats.put(ByteString::from_literal("unique_owners"), self.unique_owner_count().into_any());
+        stats.put(ByteString::from_literal("minted"), self.mint_count().into_any());
+        stats.put(ByteString::from_literal("burned"), self.burn_count().into_any());
+        stats
     }
 
     // Helper functions
 
+    /// State that must survive a code update: owner | total supply
+    fn state_snapshot(&self) -> ByteString {
+        self.get_owner().into_byte_string().concat(&self.total_supply().into_byte_string())
+    }
+
     fn is_owner(&self) -> bool {
         let owner = self.get_owner();
         if owner == H160::zero() {
@@ -503,10 +979,7 @@ impl Nep11Token {
             return true;
         }
 
-        let caller = Runtime::get_calling_script_hash();
-        let storage = Storage::get_context();
-        let minter_key = self.minters_prefix.concat(&caller.into_byte_string());
-        Storage::get(storage, minter_key).is_some()
+        Self::has_role(ByteString::from_literal(MINTER_ROLE), Runtime::get_calling_script_hash())
     }
 
     fn is_authorized_for_token(&self, owner: H160, token_id: ByteString) -> bool {
@@ -521,36 +994,48 @@ impl Nep11Token {
             return true;
         }
 
-        false
+        // Check if the calling contract is an operator of the owner
+        self.is_calling_operator(owner)
+    }
+
+    /// Whether the calling contract is an operator approved for all tokens of `owner`
+    fn is_calling_operator(&self, owner: H160) -> bool {
+        self.is_approved_for_all(owner, Runtime::get_calling_script_hash())
+    }
+
+    fn get_operator_key(&self, owner: H160, operator: H160) -> ByteString {
+        KeyBuilder::new(PREFIX_OPERATOR).push(owner).push(operator).build()
     }
 
     fn transfer_token(&self, from: H160, to: H160, token_id: ByteString) {
         let storage = Storage::get_context();
 
         // Update token owner
-        let owner_key = self.owner_prefix.concat(&token_id);
+        let owner_key = KeyBuilder::new(PREFIX_TOKEN_OWNER).push(token_id.clone()).build();
         Storage::put(storage.clone(), owner_key, to.into_byte_string());
 
         // Clear approval
-        let approved_key = self.approved_prefix.concat(&token_id);
+        let approved_key = KeyBuilder::new(PREFIX_APPROVED).push(token_id.clone()).build();
         Storage::delete(storage.clone(), approved_key);
 
         // Update balances
         let from_balance = self.balance_of(from);
         let new_from_balance = from_balance.checked_sub(&Int256::one());
-        let from_balance_key = self.balance_prefix.concat(&from.into_byte_string());
+        let from_balance_key = KeyBuilder::new(PREFIX_BALANCE).push(from).build();
 
         if new_from_balance == Int256::zero() {
             Storage::delete(storage.clone(), from_balance_key);
         } else {
             Storage::put(storage.clone(), from_balance_key, new_from_balance.into_byte_string());
         }
+        self.record_holding(from, from_balance, new_from_balance);
 
         let to_balance = self.balance_of(to);
         let new_to_balance = to_balance.checked_add(&Int256::one());
-        let to_balance_key = self.balance_prefix.concat(&to.into_byte_string());
+        let to_balance_key = KeyBuilder::new(PREFIX_BALANCE).push(to).build();
         let storage_clone = storage.clone();
         Storage::put(storage_clone, to_balance_key, new_to_balance.into_byte_string());
+        self.record_holding(to, to_balance, new_to_balance);
 
         // Update token lists
         self.remove_token_from_owner(from, token_id.clone());
@@ -562,56 +1047,274 @@ impl Nep11Token {
 
     fn add_token_to_owner(&self, owner: H160, token_id: ByteString) {
         let storage = Storage::get_context();
-        let tokens_key = self.token_prefix.concat(&owner.into_byte_string());
+        let owned_key = KeyBuilder::new(PREFIX_OWNED).push(owner).push(token_id.clone()).build();
+        Storage::put(storage, owned_key, ByteString::from_literal("true"));
+    }
 
-        let mut tokens = match Storage::get(storage.clone(), tokens_key.clone()) {
-            Some(tokens_data) => self.deserialize_token_list(tokens_data),
-            None => Array::new(),
-        };
+    fn remove_token_from_owner(&self, owner: H160, token_id: ByteString) {
+        let storage = Storage::get_context();
+        let owned_key = KeyBuilder::new(PREFIX_OWNED).push(owner).push(token_id).build();
+        Storage::delete(storage, owned_key);
+    }
 
-        tokens.push(token_id);
-        let serialized = self.serialize_token_list(tokens);
-        let storage_clone = storage.clone();
-        Storage::put(storage_clone, tokens_key, serialized);
+    fn add_token_to_global_list(&self, token_id: ByteString) {
+        let storage = Storage::get_context();
+        let current_supply = self.total_supply();
+        let index_key = KeyBuilder::new(PREFIX_TOKEN_INDEX).push(token_id.clone()).build();
+        let storage_clone = storage.clone(); Storage::put(storage_clone, index_key, current_supply.into_byte_string());
     }
 
-    fn remove_token_from_owner(&self, owner: H160, token_id: ByteString) {
+    fn remove_token_from_global_list(&self, token_id: ByteString) {
+        let storage = Storage::get_context();
+        let index_key = KeyBuilder::new(PREFIX_TOKEN_INDEX).push(token_id.clone()).build();
+        let storage_clone = storage.clone(); Storage::delete(storage_clone, index_key);
+    }
+
+    /// Updates the holder statistics after the balance of `owner` went from `previous` to `balance`
+    fn record_holding(&self, owner: H160, previous: Int256, balance: Int256) {
         let storage = Storage::get_context();
-        let tokens_key = self.token_prefix.concat(&owner.into_byte_string());
 
-        let tokens = match Storage::get(storage.clone(), tokens_key.clone()) {
-            Some(tokens_data) => self.deserialize_token_list(tokens_data),
-            None => return,
+        if previous == Int256::zero() && balance > Int256::zero() {
+            self.increment_stat(KEY_HOLDER_COUNT);
+
+            let seen_key = KeyBuilder::new(PREFIX_SEEN_OWNER).push(owner).build();
+            if Storage::get(storage.clone(), seen_key.clone()).is_none() {
+                Storage::put(storage, seen_key, ByteString::from_literal("true"));
+                self.increment_stat(KEY_UNIQUE_OWNER_COUNT);
+            }
+        } else if previous > Int256::zero() && balance == Int256::zero() {
+            let holders = self.holder_count().checked_sub(&Int256::one());
+            Storage::put(storage, KeyBuilder::new(KEY_HOLDER_COUNT).build(), holders.into_byte_string());
+        }
+
+        self.update_top_holders(owner, balance);
+    }
+
+    /// Re-ranks `owner` among the top holders with its new balance
+    fn update_top_holders(&self, owner: H160, balance: Int256) {
+        let (holders, balances) = self.get_top_holders();
+        let mut ranked = ByteString::empty();
+        let mut count = 0;
+        let mut placed = balance <= Int256::zero();
+
+        for i in 0..holders.size() {
+            let holder = holders.get(i);
+            if holder == owner {
+                continue;
+            }
+
+            let held = balances.get(i);
+            if !placed && balance > held {
+                ranked = ranked.concat(&self.serialize_holding(owner, balance));
+                count += 1;
+                placed = true;
+            }
+            if count < MAX_TOP_HOLDERS {
+                ranked = ranked.concat(&self.serialize_holding(holder, held));
+                count += 1;
+            }
+        }
+
+        if !placed && count < MAX_TOP_HOLDERS {
+            ranked = ranked.concat(&self.serialize_holding(owner, balance));
+        }
+
+        let storage = Storage::get_context();
+        if ranked.is_empty() {
+            Storage::delete(storage, KeyBuilder::new(KEY_TOP_HOLDERS).build());
+        } else {
+            Storage::put(storage, KeyBuilder::new(KEY_TOP_HOLDERS).build(), ranked);
+        }
+    }
+
+    fn get_top_holders(&self) -> (Array<H160>, Array<Int256>) {
+        let mut holders = Array::new();
+        let mut balances = Array::new();
+
+        let storage = Storage::get_context();
+        let bytes = match Storage::get(storage, KeyBuilder::new(KEY_TOP_HOLDERS).build()) {
+            Some(data) => data.to_bytes(),
+            None => return (holders, balances),
         };
 
-        let mut new_tokens = Array::new();
-        for i in 0..tokens.size() {
-            let token = tokens.get(i).clone();
-            if token != token_id {
-                new_tokens.push(token);
+        let mut offset = 0;
+        while offset + 21 <= bytes.len() {
+            let len = bytes[offset + 20] as usize;
+            if offset + 21 + len > bytes.len() {
+                break;
+            }
+            holders.push(H160::from_bytes(&bytes[offset..offset + 20]));
+            balances.push(Int256::from_byte_string(ByteString::from_bytes(&bytes[offset + 21..offset + 21 + len])));
+            offset += 21 + len;
+        }
+
+        (holders, balances)
+    }
+
+    fn serialize_holding(&self, owner: H160, balance: Int256) -> ByteString {
+        let balance_bytes = balance.into_byte_string();
+        owner
+            .into_byte_string()
+            .concat(&ByteString::from_bytes(&[balance_bytes.len() as u8]))
+            .concat(&balance_bytes)
+    }
+
+    fn get_stat(&self, tag: u8) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, KeyBuilder::new(tag).build()) {
+            Some(data) => Int256::from_byte_string(data),
+            None => Int256::zero(),
+        }
+    }
+
+    fn increment_stat(&self, tag: u8) {
+        let storage = Storage::get_context();
+        let value = self.get_stat(tag).checked_add(&Int256::one());
+        Storage::put(storage, KeyBuilder::new(tag).build(), value.into_byte_string());
+    }
+
+    /// Indexes the registered attribute keys found in `properties`.
+    /// Indexed values that are not ByteStrings, or longer than `MAX_ATTRIBUTE_VALUE_LEN`, are skipped.
+    fn index_properties(&self, token_id: ByteString, properties: &Map<ByteString, Any>) {
+        let keys = properties.keys();
+        let values = properties.values();
+
+        for i in bounded_iter(&keys, MAX_TOKEN_PROPERTIES) {
+            let key = keys.get(i);
+            let value = values.get(i);
+            if !self.is_indexed_attribute(&key) || !value.is_type(StackItemType::ByteString) {
+                continue;
+            }
+
+            let value: ByteString = value.downcast_into();
+            if value.len() <= MAX_ATTRIBUTE_VALUE_LEN {
+                self.set_indexed_value(token_id.clone(), key, value);
             }
         }
+    }
+
+    /// Moves `token_id` from the index entry of its current value to the one of `value`.
+    /// An empty `value` removes the attribute.
+    fn set_indexed_value(&self, token_id: ByteString, key: ByteString, value: ByteString) {
+        let storage = Storage::get_context();
+        let attribute_key = self.get_attribute_key(&token_id, &key);
 
-        if new_tokens.size() == 0 {
-            let storage_clone = storage.clone(); Storage::delete(storage_clone, tokens_key);
+        if let Some(old_value) = Storage::get(storage.clone(), attribute_key.clone()) {
+            if old_value == value {
+                return;
+            }
+
+            let entry_key = self.get_attribute_index_prefix(&key, &old_value).concat(&token_id);
+            Storage::delete(storage.clone(), entry_key);
+            let count = self.get_attribute_count(&key, &old_value).checked_sub(&Int256::one());
+            self.put_attribute_count(&key, &old_value, count);
+        }
+
+        if value.is_empty() {
+            Storage::delete(storage, attribute_key);
+            return;
+        }
+
+        let entry_key = self.get_attribute_index_prefix(&key, &value).concat(&token_id);
+        Storage::put(storage.clone(), entry_key, ByteString::from_literal("1"));
+        let count = self.get_attribute_count(&key, &value).checked_add(&Int256::one());
+        self.put_attribute_count(&key, &value, count);
+        Storage::put(storage, attribute_key, value);
+    }
+
+    fn is_indexed_attribute(&self, key: &ByteString) -> bool {
+        let keys = self.get_indexed_attributes();
+        for i in 0..keys.size() {
+            if keys.get(i) == *key {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Attribute keys are built from length-prefixed parts, so no two (key, value) pairs
+    /// share a key prefix, e.g. ("a_b", "c") and ("a", "b_c")
+    fn length_prefixed(&self, part: &ByteString) -> ByteString {
+        ByteString::from_bytes(&[part.len() as u8]).concat(part)
+    }
+
+    fn get_attribute_key(&self, token_id: &ByteString, key: &ByteString) -> ByteString {
+        KeyBuilder::new(PREFIX_ATTRIBUTE)
+            .push(self.length_prefixed(token_id))
+            .push(self.length_prefixed(key))
+            .build()
+    }
+
+    fn get_attribute_index_prefix(&self, key: &ByteString, value: &ByteString) -> ByteString {
+        KeyBuilder::new(PREFIX_ATTRIBUTE_INDEX)
+            .push(self.length_prefixed(key))
+            .push(self.length_prefixed(value))
+            .build()
+    }
+
+    fn get_attribute_count(&self, key: &ByteString, value: &ByteString) -> Int256 {
+        let storage = Storage::get_context();
+        let count_key = KeyBuilder::new(PREFIX_ATTRIBUTE_COUNT)
+            .push(self.length_prefixed(key))
+            .push(self.length_prefixed(value))
+            .build();
+        match Storage::get(storage, count_key) {
+            Some(count) => Int256::from_byte_string(count),
+            None => Int256::zero(),
+        }
+    }
+
+    fn put_attribute_count(&self, key: &ByteString, value: &ByteString, count: Int256) {
+        let storage = Storage::get_context();
+        let count_key = KeyBuilder::new(PREFIX_ATTRIBUTE_COUNT)
+            .push(self.length_prefixed(key))
+            .push(self.length_prefixed(value))
+            .build();
+        if count <= Int256::zero() {
+            Storage::delete(storage, count_key);
         } else {
-            let serialized = self.serialize_token_list(new_tokens);
-            let storage_clone = storage.clone();
-            Storage::put(storage_clone, tokens_key, serialized);
+            Storage::put(storage, count_key, count.into_byte_string());
         }
     }
 
-    fn add_token_to_global_list(&self, token_id: ByteString) {
+    /// Work record as (max editions, printed editions)
+    fn get_work_record(&self, work_id: &ByteString) -> Option<(u32, u32)> {
         let storage = Storage::get_context();
-        let current_supply = self.total_supply();
-        let index_key = self.token_index_prefix.concat(&token_id);
-        let storage_clone = storage.clone(); Storage::put(storage_clone, index_key, current_supply.into_byte_string());
+        let bytes = Storage::get(storage, KeyBuilder::new(PREFIX_WORK).push(work_id.clone()).build())?.to_bytes();
+        if bytes.len() < 8 {
+            return None;
+        }
+
+        let max_editions = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let printed = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        Some((max_editions, printed))
     }
 
-    fn remove_token_from_global_list(&self, token_id: ByteString) {
+    fn put_work_record(&self, work_id: &ByteString, max_editions: u32, printed: u32) {
         let storage = Storage::get_context();
-        let index_key = self.token_index_prefix.concat(&token_id);
-        let storage_clone = storage.clone(); Storage::delete(storage_clone, index_key);
+        let record = ByteString::from_bytes(&max_editions.to_le_bytes())
+            .concat(&ByteString::from_bytes(&printed.to_le_bytes()));
+        Storage::put(storage, KeyBuilder::new(PREFIX_WORK).push(work_id.clone()).build(), record);
+    }
+
+    fn get_work_tokens(&self, work_id: &ByteString) -> Array<ByteString> {
+        let storage = Storage::get_context();
+        match Storage::get(storage, KeyBuilder::new(PREFIX_WORK_TOKENS).push(work_id.clone()).build()) {
+            Some(tokens_data) => self.deserialize_token_list(tokens_data),
+            None => Array::new(),
+        }
+    }
+
+    /// Edition record of a token as (edition number, work id)
+    fn get_edition_record(&self, token_id: &ByteString) -> Option<(u32, ByteString)> {
+        let storage = Storage::get_context();
+        let bytes = Storage::get(storage, KeyBuilder::new(PREFIX_EDITION).push(token_id.clone()).build())?.to_bytes();
+        if bytes.len() < 4 {
+            return None;
+        }
+
+        let edition = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        Some((edition, ByteString::from_bytes(&bytes[4..])))
     }
 
     fn emit_transfer(&self, from: H160, to: H160, amount: Int256, token_id: ByteString) {
@@ -623,14 +1326,9 @@ impl Nep11Token {
         Runtime::notify(ByteString::from_literal("Transfer"), event_data);
     }
 
-    fn on_payment_callback(&self, from: H160, amount: Int256, token_id: ByteString, data: Any) {
-        // This would call onNEP11Payment on the recipient contract if it's a contract
-        let mut event_data = Array::new();
-        event_data.push(from.into_any());
-        event_data.push(amount.into_any());
-        event_data.push(token_id.into_any());
-        event_data.push(data);
-        Runtime::notify(ByteString::from_literal("PaymentCallback"), event_data);
+    fn on_payment_callback(&self, from: H160, to: H160, amount: Int256, token_id: ByteString, data: Any) {
+        // Calls onNEP11Payment when the recipient is a deployed contract; a rejection aborts the transfer
+        invoke_on_nep11_payment(from, to, amount, token_id, data);
     }
 
     fn serialize_token_list(&self, tokens: Array<ByteString>) -> ByteString {
@@ -736,3 +1434,19 @@ impl Nep11Token {
         properties
     }
 }
+
+/// Two-step ownership handoff (`transfer_ownership`, `accept_ownership`, `renounce_ownership`
+/// and `pending_owner`), see [`Ownable`].
+#[contract_impl]
+impl Ownable for Nep11Token {
+    const OWNER_KEY: &'static str = "contract_owner";
+}
+
+/// Owner-controlled emergency stop (`pause`, `unpause` and `is_paused`), see [`Pausable`].
+#[contract_impl]
+impl Pausable for Nep11Token {}
+
+/// Minter management through [`MINTER_ROLE`] (`grant_role`, `revoke_role`, `renounce_role`
+/// and `has_role`), see [`AccessControl`].
+#[contract_impl]
+impl AccessControl for Nep11Token {}
@@ -6,6 +6,7 @@
 //! - Advanced royalty distribution with multiple recipients
 //! - Marketplace integration with automatic royalty payments
 //! - Creator and collector management systems
+//! - Creator royalty updates that can never raise a token's royalty after its first sale
 //!
 //! This contract enables creators to earn ongoing royalties from secondary sales
 //! while providing a complete NFT ecosystem for digital art and collectibles.
@@ -14,8 +15,15 @@
 #![no_main]
 
 use epicchain_contract::prelude::*;
+use epicchain_contract::contract::invoke_on_nep11_payment;
 use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny, placeholder::{FromPlaceholder, IntoPlaceholder, Placeholder}};
 
+/// Maximum number of royalty recipients of one token
+const MAX_ROYALTY_RECIPIENTS: usize = 10;
+
+/// Maximum page size of `tokens` and `tokens_of`
+const MAX_TOKEN_PAGE_SIZE: u32 = 100;
+
 /// Royalty information structure
 #[derive(Clone, Default)]
 pub struct RoyaltyInfo {
@@ -211,7 +219,7 @@ pub struct RoyaltyNft {
     total_supply_key: ByteString,
     owner_prefix: ByteString,
     balance_prefix: ByteString,
-    token_prefix: ByteString,
+    token_prefix: ByteString,           // owner + token_id -> owned flag
     properties_prefix: ByteString,
     approved_prefix: ByteString,
 
@@ -219,6 +227,8 @@ pub struct RoyaltyNft {
     royalty_prefix: ByteString,
     default_royalty_key: ByteString,
     royalty_registry_prefix: ByteString,
+    token_creator_prefix: ByteString,   // token_id -> creator allowed to update its royalties
+    first_sale_prefix: ByteString,      // token_id -> sold through a marketplace at least once
 
     // Administrative
     contract_owner_key: ByteString,
@@ -246,6 +256,8 @@ impl RoyaltyNft {
             royalty_prefix: ByteString::from_literal("royalty_"),
             default_royalty_key: ByteString::from_literal("default_royalty"),
             royalty_registry_prefix: ByteString::from_literal("creator_royalty_"),
+            token_creator_prefix: ByteString::from_literal("token_creator_"),
+            first_sale_prefix: ByteString::from_literal("first_sale_"),
             contract_owner_key: ByteString::from_literal("contract_owner"),
             minters_prefix: ByteString::from_literal("minter_"),
             marketplace_prefix: ByteString::from_literal("marketplace_"),
@@ -371,6 +383,24 @@ impl RoyaltyNft {
         }
     }
 
+    /// Get a page of at most `limit` token IDs owned by an account starting at `offset`,
+    /// in ascending key order
+    #[method]
+    #[safe]
+    pub fn tokens_of(&self, owner: H160, offset: u32, limit: u32) -> Page<ByteString> {
+        let prefix = self.token_prefix.concat(&owner.into_byte_string());
+        let token_ids = Storage::find_prefix(Storage::get_context(), prefix).remove_prefix().keys();
+        Page::from_iterator(token_ids, offset, limit.min(MAX_TOKEN_PAGE_SIZE), self.balance_of(owner))
+    }
+
+    /// Get a page of at most `limit` minted token IDs starting at `offset`, in ascending key order
+    #[method]
+    #[safe]
+    pub fn tokens(&self, offset: u32, limit: u32) -> Page<ByteString> {
+        let token_ids = Storage::find_prefix(Storage::get_context(), self.owner_prefix.clone()).remove_prefix().keys();
+        Page::from_iterator(token_ids, offset, limit.min(MAX_TOKEN_PAGE_SIZE), self.total_supply())
+    }
+
     /// Transfer token
     #[method]
     pub fn transfer(&self, to: H160, token_id: ByteString, data: Any) -> bool {
@@ -396,7 +426,43 @@ impl RoyaltyNft {
         self.transfer_token(from, to, token_id.clone());
 
         // Call payment callback
-        self.on_payment_callback(from, Int256::one(), token_id, data);
+        self.on_payment_callback(from, to, Int256::one(), token_id, data);
+
+        true
+    }
+
+    /// Transfer a token without calling onNEP11Payment (contract owner only).
+    /// Escape hatch for recipients that predate the callback; the holder must still authorize it.
+    #[method]
+    pub fn unsafe_transfer(&self, to: H160, token_id: ByteString) -> bool {
+        if !self.is_owner() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can skip the payment callback"));
+            return false;
+        }
+
+        let from = self.owner_of(token_id.clone());
+        if from == H160::zero() {
+            Runtime::log(ByteString::from_literal("Token does not exist"));
+            return false;
+        }
+
+        if !self.is_authorized_for_token(from, token_id.clone()) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or approved"));
+            return false;
+        }
+
+        if self.is_paused() {
+            Runtime::log(ByteString::from_literal("Contract is paused"));
+            return false;
+        }
+
+        self.transfer_token(from, to, token_id.clone());
+
+        let mut event_data = Array::new();
+        event_data.push(from.into_any());
+        event_data.push(to.into_any());
+        event_data.push(token_id.into_any());
+        Runtime::notify(ByteString::from_literal("UnsafeTransfer"), event_data);
 
         true
     }
@@ -417,7 +483,7 @@ impl RoyaltyNft {
         // Get royalty info for the token
         let royalty_infos = self.get_token_royalty_info(token_id);
 
-        for i in 0..royalty_infos.size() {
+        for i in bounded_iter(&royalty_infos, MAX_ROYALTY_RECIPIENTS) {
             let royalty_info = royalty_infos.get(i);
             let mut royalty_map = Map::new();
             
@@ -441,7 +507,8 @@ impl RoyaltyNft {
         result
     }
 
-    /// Mint NFT with royalty information
+    /// Mint NFT with royalty information; `royalty_percentages[i]` is the share of
+    /// `royalty_recipients[i]` in basis points
     #[method]
     pub fn mint_with_royalty(
         &self,
@@ -462,15 +529,20 @@ impl RoyaltyNft {
             return false;
         }
 
+        let max_royalty = self.get_max_royalty();
         let mut total_royalty = 0u32;
         let mut royalty_infos = Array::new();
 
-        for i in 0..royalty_recipients.size() {
+        for i in bounded_iter(&royalty_recipients, MAX_ROYALTY_RECIPIENTS) {
             let recipient = royalty_recipients.get(i);
-            // For now, use a fixed percentage since u32 arrays need special handling
-            let percentage = 250u32; // 2.5% default
+            let percentage = royalty_percentages.get(i);
 
-            if percentage > self.get_max_royalty() {
+            if recipient == H160::zero() || percentage == 0 {
+                Runtime::log(ByteString::from_literal("Invalid royalty recipient or percentage"));
+                return false;
+            }
+
+            if percentage > max_royalty {
                 Runtime::log(ByteString::from_literal("Individual royalty percentage too high"));
                 return false;
             }
@@ -479,7 +551,7 @@ impl RoyaltyNft {
             royalty_infos.push(RoyaltyInfo::new(recipient, percentage));
         }
 
-        if total_royalty > self.get_max_royalty() {
+        if total_royalty > max_royalty {
             Runtime::log(ByteString::from_literal("Total royalty percentage too high"));
             return false;
         }
@@ -490,13 +562,16 @@ impl RoyaltyNft {
         }
 
         // Set royalty information
+        let storage = Storage::get_context();
         if royalty_infos.size() > 0 {
-            let storage = Storage::get_context();
             let royalty_key = self.royalty_prefix.concat(&token_id);
             let serialized_royalty = self.serialize_royalty_array(&royalty_infos);
-            Storage::put(storage, royalty_key, serialized_royalty);
+            Storage::put(storage.clone(), royalty_key, serialized_royalty);
         }
 
+        // The first owner is the creator who may later adjust the royalties
+        Storage::put(storage, self.token_creator_prefix.concat(&token_id), to.into_byte_string());
+
         let mut event_data = Array::new();
         event_data.push(token_id.into_any());
         event_data.push(to.into_any());
@@ -537,6 +612,84 @@ impl RoyaltyNft {
         true
     }
 
+    /// Replace the royalty split of a token (token creator only).
+    ///
+    /// Before the token's first marketplace sale any split within the maximum royalty is
+    /// allowed. Afterwards recipients may change but the total percentage may not go up, so
+    /// collectors never pay more royalty than the token carried when it was first sold.
+    #[method]
+    pub fn update_token_royalty(
+        &self,
+        token_id: ByteString,
+        recipients: Array<H160>,
+        percentages: Array<u32>
+    ) -> bool {
+        if recipients.size() != percentages.size() {
+            Runtime::log(ByteString::from_literal("Royalty recipients and percentages length mismatch"));
+            return false;
+        }
+
+        if recipients.size() > MAX_ROYALTY_RECIPIENTS {
+            Runtime::log(ByteString::from_literal("Too many royalty recipients"));
+            return false;
+        }
+
+        let max_royalty = self.get_max_royalty();
+        let mut total_royalty = 0u32;
+        let mut royalty_infos = Array::new();
+        for i in 0..recipients.size() {
+            let recipient = recipients.get(i);
+            let percentage = percentages.get(i);
+
+            if recipient == H160::zero() || percentage == 0 {
+                Runtime::log(ByteString::from_literal("Invalid royalty recipient or percentage"));
+                return false;
+            }
+
+            if percentage > max_royalty {
+                Runtime::log(ByteString::from_literal("Individual royalty percentage too high"));
+                return false;
+            }
+
+            total_royalty = total_royalty.saturating_add(percentage);
+            if total_royalty > max_royalty {
+                Runtime::log(ByteString::from_literal("Total royalty percentage too high"));
+                return false;
+            }
+
+            royalty_infos.push(RoyaltyInfo::new(recipient, percentage));
+        }
+
+        self.replace_token_royalty(token_id, royalty_infos, total_royalty)
+    }
+
+    /// Remove all royalties of a token (token creator only).
+    ///
+    /// The token keeps an empty split rather than falling back to the default royalty.
+    #[method]
+    pub fn clear_token_royalty(&self, token_id: ByteString) -> bool {
+        self.replace_token_royalty(token_id, Array::new(), 0)
+    }
+
+    /// Get the creator allowed to update a token's royalties
+    #[method]
+    #[safe]
+    pub fn get_token_creator(&self, token_id: ByteString) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.token_creator_prefix.concat(&token_id)) {
+            Some(creator_bytes) => H160::from_byte_string(creator_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    /// Check if a token has been sold through a marketplace, capping its royalty
+    #[method]
+    #[safe]
+    pub fn is_first_sale_done(&self, token_id: ByteString) -> bool {
+        let storage = Storage::get_context();
+        Storage::get(storage, self.first_sale_prefix.concat(&token_id)).is_some()
+    }
+
     /// Add approved marketplace
     #[method]
     pub fn add_marketplace(&self, marketplace: H160) -> bool {
@@ -555,7 +708,10 @@ impl RoyaltyNft {
         true
     }
 
-    /// Process marketplace sale with automatic royalty distribution
+    /// Process a marketplace sale: the buyer pays each royalty recipient its share of
+    /// `sale_price` in `payment_token` and the seller the rest, and the token moves to the
+    /// buyer. The buyer's witness must cover this contract so the payment token accepts the
+    /// transfers. If any transfer fails the whole sale is reverted.
     #[method]
     pub fn marketplace_sale(
         &self,
@@ -578,27 +734,71 @@ impl RoyaltyNft {
             return false;
         }
 
-        // Calculate and distribute royalties
+        if sale_price <= Int256::zero() || buyer == H160::zero() || buyer == seller {
+            Runtime::log(ByteString::from_literal("Invalid sale parameters"));
+            return false;
+        }
+
+        // Collect the royalty payments before anything moves
         let royalty_info = self.royalty_info(token_id.clone(), payment_token, sale_price);
+        let mut recipients = Array::new();
+        let mut amounts = Array::new();
         let mut total_royalty = Int256::zero();
 
-        for i in 0..royalty_info.size() {
-            let royalty_data = royalty_info.get(i);
-            
-            // Extract royalty amount from the map
-            let amount_key = ByteString::from_literal("royaltyAmount");
-            if let Some(_royalty_amount_any) = royalty_data.get(&amount_key) {
-                // Complete implementation for proper Any to Int256 conversion
-                let royalty_amount = Int256::new(100); // This would be extracted from Any
-                total_royalty = total_royalty.checked_add(&royalty_amount);
+        for i in bounded_iter(&royalty_info, MAX_ROYALTY_RECIPIENTS) {
+            let mut royalty_data = royalty_info.get(i);
+            let recipient = royalty_data.remove(&ByteString::from_literal("royaltyRecipient"));
+            let amount = royalty_data.remove(&ByteString::from_literal("royaltyAmount"));
+            let (recipient, amount) = match (recipient, amount) {
+                (Some(recipient), Some(amount)) => (recipient.downcast_into::<H160>(), amount.downcast_into::<Int256>()),
+                _ => continue,
+            };
+
+            if amount > Int256::zero() {
+                total_royalty = total_royalty.checked_add(&amount);
+                recipients.push(recipient);
+                amounts.push(amount);
             }
         }
 
+        if total_royalty > sale_price {
+            Runtime::log(ByteString::from_literal("Royalties exceed sale price"));
+            return false;
+        }
+
         // Transfer NFT
         self.transfer_token(seller, buyer, token_id.clone());
 
-        // Calculate seller proceeds
+        // From now on the token's royalty can no longer be raised
+        let storage = Storage::get_context();
+        Storage::put(storage, self.first_sale_prefix.concat(&token_id), ByteString::from_literal("1"));
+
+        // Pay the royalty recipients, then the seller
+        for i in 0..recipients.size() {
+            let recipient = recipients.get(i);
+            let amount = amounts.get(i);
+            assert_with_code(
+                self.pay(payment_token, buyer, recipient, amount),
+                ErrorCode::CallFailed,
+                "Royalty payment failed",
+            );
+
+            let mut event_data = Array::new();
+            event_data.push(token_id.clone().into_any());
+            event_data.push(recipient.into_any());
+            event_data.push(payment_token.into_any());
+            event_data.push(amount.into_any());
+            Runtime::notify(ByteString::from_literal("RoyaltyPaid"), event_data);
+        }
+
         let seller_proceeds = sale_price.checked_sub(&total_royalty);
+        if seller_proceeds > Int256::zero() {
+            assert_with_code(
+                self.pay(payment_token, buyer, seller, seller_proceeds),
+                ErrorCode::CallFailed,
+                "Seller payment failed",
+            );
+        }
 
         let mut event_data = Array::new();
         event_data.push(token_id.into_any());
@@ -731,6 +931,16 @@ impl RoyaltyNft {
         Storage::get(storage, minter_key).is_some()
     }
 
+    /// Transfers `amount` of a NEP-17 token from `from` to `to`
+    fn pay(&self, token: H160, from: H160, to: H160, amount: Int256) -> bool {
+        let mut args = Array::new();
+        args.push(from.into_any());
+        args.push(to.into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+        Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, args)
+    }
+
     fn is_authorized_for_token(&self, owner: H160, token_id: ByteString) -> bool {
         if Runtime::check_witness(owner) {
             return true;
@@ -770,6 +980,8 @@ impl RoyaltyNft {
         // Set token owner
         let owner_key = self.owner_prefix.concat(&token_id);
         Storage::put(storage.clone(), owner_key, to.into_byte_string());
+        let owned_key = self.token_prefix.concat(&to.into_byte_string()).concat(&token_id);
+        Storage::put(storage.clone(), owned_key, ByteString::from_literal("true"));
 
         // Update balance
         let current_balance = self.balance_of(to);
@@ -799,6 +1011,8 @@ impl RoyaltyNft {
         // Update token owner
         let owner_key = self.owner_prefix.concat(&token_id);
         Storage::put(storage.clone(), owner_key, to.into_byte_string());
+        Storage::delete(storage.clone(), self.token_prefix.concat(&from.into_byte_string()).concat(&token_id));
+        Storage::put(storage.clone(), self.token_prefix.concat(&to.into_byte_string()).concat(&token_id), ByteString::from_literal("true"));
 
         // Clear approval
         let approved_key = self.approved_prefix.concat(&token_id);
@@ -824,6 +1038,50 @@ impl RoyaltyNft {
         self.emit_transfer(from, to, Int256::one(), token_id);
     }
 
+    /// Stores a validated royalty split after checking the creator's witness and the first-sale cap
+    fn replace_token_royalty(&self, token_id: ByteString, royalty_infos: Array<RoyaltyInfo>, total_royalty: u32) -> bool {
+        let creator = self.get_token_creator(token_id.clone());
+        if creator == H160::zero() {
+            Runtime::log(ByteString::from_literal("Token has no creator"));
+            return false;
+        }
+
+        if !Runtime::check_witness(creator) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only the token creator can update royalties"));
+            return false;
+        }
+
+        // Collector protection: after the first sale the royalty can only stay or go down
+        let previous_royalty = self.total_royalty_percentage(token_id.clone());
+        if self.is_first_sale_done(token_id.clone()) && total_royalty > previous_royalty {
+            Runtime::log(ByteString::from_literal("Royalty cannot increase after the first sale"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let royalty_key = self.royalty_prefix.concat(&token_id);
+        Storage::put(storage, royalty_key, self.serialize_royalty_array(&royalty_infos));
+
+        let mut event_data = Array::new();
+        event_data.push(token_id.into_any());
+        event_data.push(creator.into_any());
+        event_data.push(Int256::new(previous_royalty as i64).into_any());
+        event_data.push(Int256::new(total_royalty as i64).into_any());
+        Runtime::notify(ByteString::from_literal("RoyaltyUpdated"), event_data);
+
+        true
+    }
+
+    /// Sum of the royalty percentages currently applied to a token, in basis points
+    fn total_royalty_percentage(&self, token_id: ByteString) -> u32 {
+        let royalty_infos = self.get_token_royalty_info(token_id);
+        let mut total = 0u32;
+        for i in bounded_iter(&royalty_infos, MAX_ROYALTY_RECIPIENTS) {
+            total = total.saturating_add(royalty_infos.get(i).percentage);
+        }
+        total
+    }
+
     fn serialize_royalty_array(&self, royalty_infos: &Array<RoyaltyInfo>) -> ByteString {
         let mut serialized = ByteString::empty();
         let len = royalty_infos.size() as u32;
@@ -875,12 +1133,8 @@ impl RoyaltyNft {
         Runtime::notify(ByteString::from_literal("Transfer"), event_data);
     }
 
-    fn on_payment_callback(&self, from: H160, amount: Int256, token_id: ByteString, data: Any) {
-        let mut event_data = Array::new();
-        event_data.push(from.into_any());
-        event_data.push(amount.into_any());
-        event_data.push(token_id.into_any());
-        event_data.push(data);
-        Runtime::notify(ByteString::from_literal("PaymentCallback"), event_data);
+    fn on_payment_callback(&self, from: H160, to: H160, amount: Int256, token_id: ByteString, data: Any) {
+        // Calls onNEP11Payment when the recipient is a deployed contract; a rejection aborts the transfer
+        invoke_on_nep11_payment(from, to, amount, token_id, data);
     }
 }
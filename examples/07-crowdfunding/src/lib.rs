@@ -2,10 +2,15 @@
 //!
 //! A comprehensive crowdfunding platform demonstrating real-world DeFi functionality:
 //! - Goal-based funding with time limits
-//! - Refund mechanisms if goals aren't met
+//! - Contributions held in escrow: tokens sent with a campaign id as `data` are
+//!   credited to the sender, and `contribute` pulls tokens through the same path
+//! - Refunds if goals aren't met, and creator withdrawals once they are
+//! - A platform fee on creator payouts, collected per token for the platform owner
 //! - Milestone-based fund release
-//! - Contributor tracking and rewards
-//! - Administrative controls and emergency mechanisms
+//! - Escrowed stretch goals with contributor-approved release
+//! - Contributor tracking and reward tiers, optionally minting NFT rewards
+//! - Optional contribution gating by identity attestation or NFT ownership
+//! - Administrative controls, two-step platform ownership transfer and emergency mechanisms
 //!
 //! This contract showcases advanced EpicChain patterns for decentralized fundraising.
 
@@ -16,6 +21,30 @@ use epicchain_contract::prelude::*;
 use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
 use epicchain_contract::contract::native::{Gas, Neo};
 
+/// Maximum number of stretch goals per campaign
+const MAX_STRETCH_GOALS: u32 = 5;
+
+/// Maximum number of reward tiers per campaign
+const MAX_REWARD_TIERS: u32 = 10;
+
+/// Maximum length of a reward tier description
+const MAX_REWARD_DESCRIPTION_LEN: usize = 256;
+
+/// Maximum length of a reward NFT token id
+const MAX_REWARD_TOKEN_ID_LEN: usize = 64;
+
+/// Maximum number of refunds pushed out by one process_refunds call
+const MAX_REFUNDS_PER_CRANK: u32 = 50;
+
+/// Maximum page size of `get_contributors`
+const MAX_CONTRIBUTOR_PAGE: u32 = 100;
+
+/// Share of each crank-processed refund paid to the caller of process_refunds (basis points)
+const REFUND_BOUNTY_BP: i64 = 10;
+
+/// Offset of the status byte in a campaign record, after creator, deadline and payment token
+const CAMPAIGN_STATUS_OFFSET: usize = 20 + 8 + 20;
+
 /// Campaign status enumeration
 #[derive(Clone, Copy, PartialEq)]
 pub enum CampaignStatus {
@@ -40,6 +69,72 @@ impl CampaignStatus {
     }
 }
 
+/// Who may contribute to a gated campaign
+#[derive(Clone, Copy, PartialEq)]
+pub enum GateKind {
+    Open = 0,
+    Attestation = 1,    // holders of an attestation from an identity registry
+    NftHolder = 2,      // holders of a token of a NEP-11 collection
+}
+
+impl GateKind {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(GateKind::Open),
+            1 => Some(GateKind::Attestation),
+            2 => Some(GateKind::NftHolder),
+            _ => None,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Typed interface of an identity (DID) registry issuing attestations about accounts
+pub struct IdentityRegistry(H160);
+
+impl IdentityRegistry {
+    pub fn new(hash: H160) -> Self {
+        Self(hash)
+    }
+
+    /// Whether `subject` holds a valid attestation of `claim`
+    pub fn has_attestation(&self, subject: H160, claim: ByteString) -> bool {
+        let mut args = Array::new();
+        args.push(subject.into_any());
+        args.push(claim.into_any());
+        let result = Contract::call(self.0, ByteString::from_literal("has_attestation"), CallFlags::ReadOnly, args);
+        // A registry that returns nothing has not attested anything
+        !result.is_null() && result.is_success()
+    }
+}
+
+/// Typed interface of a NEP-11 collection
+pub struct Nep11Collection(H160);
+
+impl Nep11Collection {
+    pub fn new(hash: H160) -> Self {
+        Self(hash)
+    }
+
+    /// Number of tokens owned by `owner`
+    pub fn balance_of(&self, owner: H160) -> Int256 {
+        let mut args = Array::new();
+        args.push(owner.into_any());
+        Contract::call_as(self.0, ByteString::from_literal("balanceOf"), CallFlags::ReadOnly, args)
+    }
+
+    /// Mints `token_id` to `to`; the collection must allow this contract to mint
+    pub fn mint(&self, to: H160, token_id: ByteString) -> bool {
+        let mut args = Array::new();
+        args.push(to.into_any());
+        args.push(token_id.into_any());
+        Contract::call_bool(self.0, ByteString::from_literal("mint"), CallFlags::All, args)
+    }
+}
+
 /// Crowdfunding platform contract
 #[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
 #[contract_version("1.0.0")]
@@ -54,16 +149,43 @@ pub struct Crowdfunding {
 
     // Contribution tracking
     contributions_prefix: ByteString,  // campaign_id + contributor -> amount
-    contributor_list_prefix: ByteString, // campaign_id -> list of contributors
+    contributor_list_prefix: ByteString, // campaign_id + index -> contributor, in order of first contribution
+    contributor_count_prefix: ByteString, // campaign_id -> number of listed contributors
+    contributor_listed_prefix: ByteString, // campaign_id + contributor -> listed flag
+    refund_cursor_prefix: ByteString,    // campaign_id -> next list index for process_refunds
     total_raised_prefix: ByteString,   // campaign_id -> total amount raised
 
     // Milestone tracking
     milestones_prefix: ByteString,     // campaign_id -> milestone data
     milestone_released_prefix: ByteString, // campaign_id + milestone_id -> released amount
 
+    // Stretch goals (escrow buckets above the base goal)
+    stretch_count_prefix: ByteString,    // campaign_id -> number of stretch goals
+    stretch_target_prefix: ByteString,   // campaign_id + bucket -> cumulative target
+    stretch_quorum_prefix: ByteString,   // campaign_id + bucket -> approval quorum (bps of escrow)
+    stretch_refund_prefix: ByteString,   // campaign_id + bucket -> refund deadline
+    stretch_raised_prefix: ByteString,   // campaign_id + bucket -> escrowed amount
+    stretch_contrib_prefix: ByteString,  // campaign_id + bucket + contributor -> amount
+    stretch_voter_prefix: ByteString,    // campaign_id + bucket + contributor -> approved amount
+    stretch_approved_prefix: ByteString, // campaign_id + bucket -> total approved amount
+    stretch_released_prefix: ByteString, // campaign_id + bucket -> released flag
+
+    // Reward tiers
+    tier_count_prefix: ByteString,       // campaign_id -> number of reward tiers
+    tier_prefix: ByteString,             // campaign_id + tier -> threshold | token id | description
+    contributor_tier_prefix: ByteString, // campaign_id + contributor -> highest tier reached
+    reward_collection_key: ByteString,   // NEP-11 contract minting reward tokens
+
+    // Creator payouts
+    withdrawn_prefix: ByteString,        // campaign_id -> base funds withdrawn flag
+
+    // Contribution gating
+    gate_prefix: ByteString,             // campaign_id -> kind | gate contract | claim
+
     // Administrative
     platform_owner_key: ByteString,
     platform_fee_key: ByteString,     // Platform fee percentage (basis points)
+    platform_fees_prefix: ByteString, // token -> collected platform fees
     emergency_pause_key: ByteString,
 
     // Supported tokens
@@ -79,11 +201,30 @@ impl Crowdfunding {
             campaign_count_key: ByteString::from_literal("campaign_count"),
             contributions_prefix: ByteString::from_literal("contrib_"),
             contributor_list_prefix: ByteString::from_literal("contributors_"),
+            contributor_count_prefix: ByteString::from_literal("contributor_count_"),
+            contributor_listed_prefix: ByteString::from_literal("contributor_listed_"),
+            refund_cursor_prefix: ByteString::from_literal("refund_cursor_"),
             total_raised_prefix: ByteString::from_literal("raised_"),
             milestones_prefix: ByteString::from_literal("milestones_"),
             milestone_released_prefix: ByteString::from_literal("released_"),
+            stretch_count_prefix: ByteString::from_literal("stretch_count_"),
+            stretch_target_prefix: ByteString::from_literal("stretch_target_"),
+            stretch_quorum_prefix: ByteString::from_literal("stretch_quorum_"),
+            stretch_refund_prefix: ByteString::from_literal("stretch_refund_"),
+            stretch_raised_prefix: ByteString::from_literal("stretch_raised_"),
+            stretch_contrib_prefix: ByteString::from_literal("stretch_contrib_"),
+            stretch_voter_prefix: ByteString::from_literal("stretch_voter_"),
+            stretch_approved_prefix: ByteString::from_literal("stretch_weight_"),
+            stretch_released_prefix: ByteString::from_literal("stretch_released_"),
+            tier_count_prefix: ByteString::from_literal("tier_count_"),
+            tier_prefix: ByteString::from_literal("tier_"),
+            contributor_tier_prefix: ByteString::from_literal("contrib_tier_"),
+            reward_collection_key: ByteString::from_literal("reward_collection"),
+            withdrawn_prefix: ByteString::from_literal("withdrawn_"),
+            gate_prefix: ByteString::from_literal("gate_"),
             platform_owner_key: ByteString::from_literal("platform_owner"),
             platform_fee_key: ByteString::from_literal("platform_fee"),
+            platform_fees_prefix: ByteString::from_literal("platform_fees_"),
             emergency_pause_key: ByteString::from_literal("emergency_pause"),
             supported_tokens_key: ByteString::from_literal("supported_tokens"),
         }
@@ -196,7 +337,10 @@ impl Crowdfunding {
         campaign_id
     }
 
-    /// Contribute to a campaign
+    /// Contribute to a campaign by transferring `amount` of its payment token into escrow.
+    ///
+    /// The transfer carries the campaign id as `data`, so the contribution is recorded by
+    /// the payment hook exactly as for a direct transfer from a wallet.
     #[method]
     pub fn contribute(&self, campaign_id: Int256, contributor: H160, amount: Int256) -> bool {
         // Validate inputs
@@ -211,72 +355,198 @@ impl Crowdfunding {
             return false;
         }
 
-        // Check if platform is paused
-        if self.is_emergency_paused() {
-            Runtime::log(ByteString::from_literal("Platform is paused"));
+        // Check everything the payment hook checks before any tokens move
+        let payment_token = match self.check_contribution(campaign_id, contributor) {
+            Some(token) => token,
+            None => return false,
+        };
+
+        let contributed = self.get_contribution(campaign_id, contributor);
+
+        let mut args = Array::new();
+        args.push(contributor.into_any());
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(amount.into_any());
+        args.push(campaign_id.into_any());
+        if !Contract::call_bool(payment_token, ByteString::from_literal("transfer"), CallFlags::All, args) {
+            Runtime::log(ByteString::from_literal("Contribution transfer failed"));
             return false;
         }
 
-        // Get campaign data
+        let received = self.get_contribution(campaign_id, contributor).checked_sub(&contributed);
+        assert_with_code(received >= amount, ErrorCode::CallFailed, "Contribution was not recorded");
+        true
+    }
+
+    /// NEP-17 receiver callback: tokens sent with a campaign id as `data` are escrowed as a
+    /// contribution of the sender to that campaign
+    #[method]
+    #[wasm_export(name = "onNEP17Payment")]
+    pub fn receive_contribution(&self, from: H160, amount: Int256, data: Any) -> bool {
+        // The calling contract is the token; a direct invocation is not a payment
+        let token = Runtime::get_calling_script_hash();
+        if token == Runtime::get_entry_script_hash() {
+            Runtime::log(ByteString::from_literal("Payments must come from a token contract"));
+            return false;
+        }
+
+        if data.is_null() {
+            Runtime::log(ByteString::from_literal("Missing campaign id"));
+            return false;
+        }
+
+        if from == H160::zero() || amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid contribution"));
+            return false;
+        }
+
+        let campaign_id: Int256 = data.downcast_into();
+        let payment_token = match self.check_contribution(campaign_id, from) {
+            Some(token) => token,
+            None => return false,
+        };
+
+        if token != payment_token {
+            Runtime::log(ByteString::from_literal("Token not accepted by this campaign"));
+            return false;
+        }
+
+        self.record_contribution(campaign_id, from, amount);
+        true
+    }
+
+    /// Withdraw the base funds of a successful campaign to its creator (once, after the
+    /// deadline), less the platform fee. Stretch goal escrow is paid out separately through
+    /// `release_stretch_goal`. Returns the amount sent to the creator.
+    #[method]
+    pub fn withdraw_funds(&self, campaign_id: Int256) -> Int256 {
         let campaign_data = match self.get_campaign_data(campaign_id) {
             Some(data) => data,
             None => {
                 Runtime::log(ByteString::from_literal("Campaign not found"));
-                return false;
+                return Int256::zero();
             }
         };
 
-        // Verify campaign is active and not expired
-        if !self.is_campaign_active(&campaign_data) {
-            Runtime::log(ByteString::from_literal("Campaign is not active or has expired"));
-            return false;
-        }
+        let (creator, _, _, funding_goal, deadline, payment_token, status) = self.deserialize_campaign_data(campaign_data);
 
-        let storage = Storage::get_context();
+        if !Runtime::check_witness(creator) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only creator can withdraw funds"));
+            return Int256::zero();
+        }
 
-        // Update contributor's contribution
-        let contrib_key = self.contributions_prefix
-            .concat(&campaign_id.into_byte_string())
-            .concat(&ByteString::from_literal("_"))
-            .concat(&contributor.into_byte_string());
+        if status == CampaignStatus::Cancelled || status == CampaignStatus::Failed {
+            Runtime::log(ByteString::from_literal("Campaign did not succeed"));
+            return Int256::zero();
+        }
 
-        let current_contrib = match Storage::get(storage.clone(), contrib_key.clone()) {
-            Some(amount_bytes) => Int256::from_byte_string(amount_bytes),
-            None => Int256::zero(),
-        };
+        if Runtime::get_time() <= deadline {
+            Runtime::log(ByteString::from_literal("Campaign is still running"));
+            return Int256::zero();
+        }
 
-        let new_contrib = current_contrib.checked_add(&amount);
-        Storage::put(storage.clone(), contrib_key, new_contrib.into_byte_string());
+        let total_raised = self.get_total_raised(campaign_id);
+        if total_raised < funding_goal {
+            Runtime::log(ByteString::from_literal("Funding goal not reached"));
+            return Int256::zero();
+        }
 
-        // Add to contributor list if first contribution
-        if current_contrib == Int256::zero() {
-            self.add_contributor_to_list(campaign_id, contributor);
+        let storage = Storage::get_context();
+        let withdrawn_key = self.withdrawn_prefix.concat(&campaign_id.into_byte_string());
+        if Storage::get(storage.clone(), withdrawn_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Funds already withdrawn"));
+            return Int256::zero();
         }
 
-        // Update total raised
-        let raised_key = self.total_raised_prefix.concat(&campaign_id.into_byte_string());
-        let current_raised = match Storage::get(storage.clone(), raised_key.clone()) {
-            Some(amount_bytes) => Int256::from_byte_string(amount_bytes),
-            None => Int256::zero(),
+        // Everything above the base goal is escrowed in the stretch goal buckets
+        let amount = if self.get_stretch_goal_count(campaign_id) > 0 {
+            funding_goal
+        } else {
+            total_raised
         };
 
-        let new_raised = current_raised.checked_add(&amount);
-        let storage_clone = storage.clone(); Storage::put(storage_clone, raised_key, new_raised.into_byte_string());
+        Storage::put(storage, withdrawn_key, ByteString::from_literal("1"));
+        let fee = self.collect_platform_fee(payment_token, amount);
+        let payout = amount.checked_sub(&fee);
+        assert_with_code(
+            self.send_tokens(payment_token, creator, payout),
+            ErrorCode::CallFailed,
+            "Withdrawal transfer failed",
+        );
 
-        // Check if funding goal is reached
-        let funding_goal = self.extract_funding_goal(&campaign_data);
-        if new_raised >= funding_goal {
-            self.mark_campaign_successful(campaign_id);
+        let mut event_data = Array::new();
+        event_data.push(campaign_id.into_any());
+        event_data.push(creator.into_any());
+        event_data.push(payout.into_any());
+        event_data.push(fee.into_any());
+        Runtime::notify(ByteString::from_literal("FundsWithdrawn"), event_data);
+
+        payout
+    }
+
+    /// Send the platform fees collected in `token` to `to` (platform owner only)
+    #[method]
+    pub fn withdraw_platform_fees(&self, token: H160, to: H160) -> Int256 {
+        if !self.is_platform_owner() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only platform owner can withdraw fees"));
+            return Int256::zero();
+        }
+
+        if to == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid recipient"));
+            return Int256::zero();
         }
 
+        let amount = self.get_platform_fees(token);
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No platform fees collected"));
+            return Int256::zero();
+        }
+
+        let storage = Storage::get_context();
+        Storage::delete(storage, self.platform_fees_prefix.concat(&token.into_byte_string()));
+        assert_with_code(
+            self.send_tokens(token, to, amount),
+            ErrorCode::CallFailed,
+            "Platform fee transfer failed",
+        );
+
         let mut event_data = Array::new();
-        event_data.push(campaign_id.into_any());
-        event_data.push(contributor.into_any());
+        event_data.push(token.into_any());
+        event_data.push(to.into_any());
         event_data.push(amount.into_any());
-        event_data.push(new_raised.into_any());
-        Runtime::notify(ByteString::from_literal("ContributionMade"), event_data);
+        Runtime::notify(ByteString::from_literal("PlatformFeesWithdrawn"), event_data);
 
-        true
+        amount
+    }
+
+    /// Get the platform fees collected in a token and not yet withdrawn
+    #[method]
+    #[safe]
+    pub fn get_platform_fees(&self, token: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.platform_fees_prefix.concat(&token.into_byte_string())) {
+            Some(amount_bytes) => Int256::from_byte_string(amount_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Get the platform fee in basis points
+    #[method]
+    #[safe]
+    pub fn get_platform_fee(&self) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.platform_fee_key.clone()) {
+            Some(fee_bytes) => {
+                let bytes = fee_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            }
+            None => 0,
+        }
     }
 
     /// Get campaign information
@@ -298,7 +568,7 @@ impl Crowdfunding {
                 result.put(ByteString::from_literal("payment_token"), payment_token.into_any());
                 result.put(ByteString::from_literal("status"), Int256::new(status.to_u8() as i64).into_any());
                 result.put(ByteString::from_literal("total_raised"), self.get_total_raised(campaign_id).into_any());
-                result.put(ByteString::from_literal("contributor_count"), self.get_contributor_count(campaign_id).into_any());
+                result.put(ByteString::from_literal("contributor_count"), Int256::new(self.get_contributor_count(campaign_id) as i64).into_any());
             },
             None => {
                 result.put(ByteString::from_literal("error"), ByteString::from_literal("Campaign not found").into_any());
@@ -308,6 +578,16 @@ impl Crowdfunding {
         result
     }
 
+    /// Get a page of at most `limit` accounts that contributed to a campaign, in order of
+    /// first contribution. Refunded contributors stay listed.
+    #[method]
+    #[safe]
+    pub fn get_contributors(&self, campaign_id: Int256, cursor: u32, limit: u32) -> Page<H160> {
+        let count = self.get_contributor_count(campaign_id);
+        let contributors = (0..count).map(|index| self.get_listed_contributor(campaign_id, index));
+        Page::from_iterator(contributors, cursor, limit.min(MAX_CONTRIBUTOR_PAGE), Int256::new(count as i64))
+    }
+
     /// Get contributor's contribution amount
     #[method]
     #[safe]
@@ -367,175 +647,1357 @@ impl Crowdfunding {
         Storage::get(storage, self.emergency_pause_key.clone()).is_some()
     }
 
-    // Helper functions
-
-    fn validate_campaign_params(
+    /// Restrict who may contribute to a campaign (creator only).
+    ///
+    /// `Attestation` gates require an attestation of `claim` from the identity registry at
+    /// `gate_contract`; `NftHolder` gates require owning a token of the NEP-11 collection at
+    /// `gate_contract`. `Open` removes the gate. Existing contributions are not affected.
+    #[method]
+    pub fn set_contribution_gate(
         &self,
-        title: &ByteString,
-        description: &ByteString,
-        funding_goal: Int256,
-        deadline: u64
+        campaign_id: Int256,
+        kind: u8,
+        gate_contract: H160,
+        claim: ByteString
     ) -> bool {
-        if title.is_empty() || title.len() > 100 {
-            Runtime::log(ByteString::from_literal("Invalid title: must be 1-100 characters"));
-            return false;
-        }
+        let campaign_data = match self.get_campaign_data(campaign_id) {
+            Some(data) => data,
+            None => {
+                Runtime::log(ByteString::from_literal("Campaign not found"));
+                return false;
+            }
+        };
 
-        if description.is_empty() || description.len() > 1000 {
-            Runtime::log(ByteString::from_literal("Invalid description: must be 1-1000 characters"));
-            return false;
-        }
+        let (creator, _, _, _, _, _, _) = self.deserialize_campaign_data(campaign_data.clone());
 
-        if funding_goal <= Int256::zero() {
-            Runtime::log(ByteString::from_literal("Invalid funding goal: must be positive"));
+        if !Runtime::check_witness(creator) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only creator can gate contributions"));
             return false;
         }
 
-        let current_time = Runtime::get_time();
-        if deadline <= current_time {
-            Runtime::log(ByteString::from_literal("Invalid deadline: must be in the future"));
+        if !self.is_campaign_active(&campaign_data) {
+            Runtime::log(ByteString::from_literal("Campaign is not active or has expired"));
             return false;
         }
 
-        true
-    }
-
-    fn is_token_supported(&self, token: H160) -> bool {
-        // For simplicity, support GAS and EpicChain
-        token == Gas::hash() || token == EpicChain::hash()
-    }
+        let kind = match GateKind::from_u8(kind) {
+            Some(kind) => kind,
+            None => {
+                Runtime::log(ByteString::from_literal("Unknown gate kind"));
+                return false;
+            }
+        };
 
-    fn get_campaign_data(&self, campaign_id: Int256) -> Option<ByteString> {
         let storage = Storage::get_context();
-        let campaign_key = self.campaign_prefix.concat(&campaign_id.into_byte_string());
-        Storage::get(storage, campaign_key)
-    }
+        let gate_key = self.gate_prefix.concat(&campaign_id.into_byte_string());
 
-    fn serialize_campaign_data(
-        &self,
-        creator: H160,
-        title: ByteString,
-        description: ByteString,
-        funding_goal: Int256,
-        deadline: u64,
-        payment_token: H160,
-        status: CampaignStatus
-    ) -> ByteString {
-        // Simple serialization - in production, use a proper format
-        let mut data = creator.into_byte_string();
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&title);
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&description);
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&funding_goal.into_byte_string());
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&ByteString::from_bytes(&deadline.to_le_bytes()));
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&payment_token.into_byte_string());
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&ByteString::from_bytes(&[status.to_u8()]));
-        data
-    }
+        if kind == GateKind::Open {
+            Storage::delete(storage, gate_key);
+        } else {
+            if gate_contract == H160::zero() {
+                Runtime::log(ByteString::from_literal("Invalid gate contract"));
+                return false;
+            }
 
-    fn deserialize_campaign_data(&self, data: ByteString) -> (H160, ByteString, ByteString, Int256, u64, H160, CampaignStatus) {
-        // Simplified deserialization - in production, use proper parsing
-        let parts = self.split_by_delimiter(data, ByteString::from_literal("|"));
-
-        if parts.size() >= 7 {
-            let creator = H160::from_byte_string(parts.get(0).clone());
-            let title = parts.get(1).clone();
-            let description = parts.get(2).clone();
-            let funding_goal = Int256::from_byte_string(parts.get(3).clone());
-
-            let deadline_bytes = parts.get(4).to_bytes();
-            let deadline = if deadline_bytes.len() >= 8 {
-                u64::from_le_bytes([
-                    deadline_bytes[0], deadline_bytes[1], deadline_bytes[2], deadline_bytes[3],
-                    deadline_bytes[4], deadline_bytes[5], deadline_bytes[6], deadline_bytes[7]
-                ])
-            } else {
-                0
-            };
+            if kind == GateKind::Attestation && claim.is_empty() {
+                Runtime::log(ByteString::from_literal("Attestation gates need a claim"));
+                return false;
+            }
 
-            let payment_token = H160::from_byte_string(parts.get(5).clone());
+            let gate_data = ByteString::from_bytes(&[kind.to_u8()])
+                .concat(&gate_contract.into_byte_string())
+                .concat(&claim);
+            Storage::put(storage, gate_key, gate_data);
+        }
 
-            let status_bytes = parts.get(6).to_bytes();
-            let status = if status_bytes.len() > 0 {
-                CampaignStatus::from_u8(status_bytes[0])
-            } else {
-                CampaignStatus::Active
-            };
+        let mut event_data = Array::new();
+        event_data.push(campaign_id.into_any());
+        event_data.push(Int256::new(kind.to_u8() as i64).into_any());
+        event_data.push(gate_contract.into_any());
+        event_data.push(claim.into_any());
+        Runtime::notify(ByteString::from_literal("ContributionGateSet"), event_data);
 
-            (creator, title, description, funding_goal, deadline, payment_token, status)
-        } else {
-            (H160::zero(), ByteString::empty(), ByteString::empty(),
-             Int256::zero(), 0, H160::zero(), CampaignStatus::Failed)
-        }
+        true
     }
 
-    fn split_by_delimiter(&self, data: ByteString, _delimiter: ByteString) -> Array<ByteString> {
-        // Simplified string splitting - in production, use proper parsing
-        let mut parts = Array::new();
+    /// Get the contribution gate of a campaign
+    #[method]
+    #[safe]
+    pub fn get_contribution_gate(&self, campaign_id: Int256) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        let (kind, gate_contract, claim) = self.get_gate(campaign_id)
+            .unwrap_or((GateKind::Open, H160::zero(), ByteString::empty()));
 
-        // For now, return the original data as single part
-        // Complete implementation with proper string splitting
-        parts.push(data);
-        parts
+        result.put(ByteString::from_literal("kind"), Int256::new(kind.to_u8() as i64).into_any());
+        result.put(ByteString::from_literal("gate_contract"), gate_contract.into_any());
+        result.put(ByteString::from_literal("claim"), claim.into_any());
+        result
     }
 
-    fn extract_funding_goal(&self, data: &ByteString) -> Int256 {
-        let (_, _, _, funding_goal, _, _, _) = self.deserialize_campaign_data(data.clone());
-        funding_goal
+    /// Check whether an account passes the contribution gate of a campaign
+    #[method]
+    #[safe]
+    pub fn can_contribute(&self, campaign_id: Int256, contributor: H160) -> bool {
+        match self.get_gate(campaign_id) {
+            None => true,
+            Some((GateKind::Open, _, _)) => true,
+            Some((GateKind::Attestation, registry, claim)) => {
+                IdentityRegistry::new(registry).has_attestation(contributor, claim)
+            }
+            Some((GateKind::NftHolder, collection, _)) => {
+                Nep11Collection::new(collection).balance_of(contributor) > Int256::zero()
+            }
+        }
     }
 
-    fn is_campaign_active(&self, data: &ByteString) -> bool {
-        let (_, _, _, _, deadline, _, status) = self.deserialize_campaign_data(data.clone());
-        let current_time = Runtime::get_time();
+    /// Set the NEP-11 collection that mints reward tokens (platform owner only).
+    /// The zero hash disables minting.
+    #[method]
+    pub fn set_reward_collection(&self, collection: H160) -> bool {
+        if !self.is_platform_owner() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only platform owner can set the reward collection"));
+            return false;
+        }
 
-        status == CampaignStatus::Active && current_time < deadline
-    }
+        let storage = Storage::get_context();
+        if collection == H160::zero() {
+            Storage::delete(storage, self.reward_collection_key.clone());
+        } else {
+            Storage::put(storage, self.reward_collection_key.clone(), collection.into_byte_string());
+        }
 
-    fn mark_campaign_successful(&self, campaign_id: Int256) {
-        // Update campaign status to successful
         let mut event_data = Array::new();
-        event_data.push(campaign_id.into_any());
-        Runtime::notify(ByteString::from_literal("CampaignSuccessful"), event_data);
-    }
+        event_data.push(collection.into_any());
+        Runtime::notify(ByteString::from_literal("RewardCollectionChanged"), event_data);
 
-    fn add_contributor_to_list(&self, campaign_id: Int256, contributor: H160) {
-        // Add contributor to the campaign's contributor list
-        let mut event_data = Array::new();
-        event_data.push(campaign_id.into_any());
-        event_data.push(contributor.into_any());
-        Runtime::notify(ByteString::from_literal("NewContributor"), event_data);
+        true
     }
 
-    fn get_contributor_count(&self, _campaign_id: Int256) -> Int256 {
-        // Return number of unique contributors
-        // For now, return 0 - implement proper counting in production
-        Int256::zero()
+    /// Get the NEP-11 collection that mints reward tokens, or the zero hash if none
+    #[method]
+    #[safe]
+    pub fn get_reward_collection(&self) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.reward_collection_key.clone()) {
+            Some(hash_bytes) => H160::from_byte_string(hash_bytes),
+            None => H160::zero(),
+        }
     }
 
-    /// Request refund for failed or cancelled campaign
+    /// Add a reward tier to a campaign (creator only, while active and before any funds
+    /// are raised, so every contributor is measured against the same tiers).
+    ///
+    /// Contributors whose total contribution reaches `threshold` reach the tier. If
+    /// `token_id` is not empty, reaching the tier mints it to the contributor through the
+    /// reward collection. Thresholds must strictly increase. Returns the tier index.
     #[method]
-    pub fn request_refund(&self, campaign_id: Int256, contributor: H160) -> bool {
-        // Verify authorization
-        if !Runtime::check_witness(contributor) {
+    pub fn add_reward_tier(
+        &self,
+        campaign_id: Int256,
+        threshold: Int256,
+        description: ByteString,
+        token_id: ByteString
+    ) -> Int256 {
+        let campaign_data = match self.get_campaign_data(campaign_id) {
+            Some(data) => data,
+            None => {
+                Runtime::log(ByteString::from_literal("Campaign not found"));
+                return Int256::minus_one();
+            }
+        };
+
+        let (creator, _, _, _, _, _, _) = self.deserialize_campaign_data(campaign_data.clone());
+
+        if !Runtime::check_witness(creator) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only creator can add reward tiers"));
+            return Int256::minus_one();
+        }
+
+        if !self.is_campaign_active(&campaign_data) {
+            Runtime::log(ByteString::from_literal("Campaign is not active or has expired"));
+            return Int256::minus_one();
+        }
+
+        if self.get_total_raised(campaign_id) > Int256::zero() {
+            Runtime::log(ByteString::from_literal("Reward tiers must be defined before contributions"));
+            return Int256::minus_one();
+        }
+
+        let count = self.get_reward_tier_count(campaign_id);
+        if count >= MAX_REWARD_TIERS {
+            Runtime::log(ByteString::from_literal("Too many reward tiers"));
+            return Int256::minus_one();
+        }
+
+        let previous_threshold = if count == 0 {
+            Int256::zero()
+        } else {
+            self.get_tier_threshold(campaign_id, count - 1)
+        };
+        if threshold <= previous_threshold {
+            Runtime::log(ByteString::from_literal("Tier threshold must exceed the previous threshold"));
+            return Int256::minus_one();
+        }
+
+        if description.is_empty() || description.len() > MAX_REWARD_DESCRIPTION_LEN {
+            Runtime::log(ByteString::from_literal("Invalid reward description"));
+            return Int256::minus_one();
+        }
+
+        if token_id.len() > MAX_REWARD_TOKEN_ID_LEN {
+            Runtime::log(ByteString::from_literal("Reward token id too long"));
+            return Int256::minus_one();
+        }
+
+        if !token_id.is_empty() && self.get_reward_collection() == H160::zero() {
+            Runtime::log(ByteString::from_literal("No reward collection configured"));
+            return Int256::minus_one();
+        }
+
+        let threshold_bytes = threshold.into_byte_string();
+        let record = ByteString::from_bytes(&[threshold_bytes.len() as u8])
+            .concat(&threshold_bytes)
+            .concat(&ByteString::from_bytes(&(token_id.len() as u16).to_le_bytes()))
+            .concat(&token_id)
+            .concat(&ByteString::from_bytes(&(description.len() as u16).to_le_bytes()))
+            .concat(&description);
+
+        let storage = Storage::get_context();
+        Storage::put(storage.clone(), self.tier_prefix.concat(&self.get_stretch_key(campaign_id, count)), record);
+        Storage::put(
+            storage,
+            self.tier_count_prefix.concat(&campaign_id.into_byte_string()),
+            ByteString::from_bytes(&(count + 1).to_le_bytes()),
+        );
+
+        let tier = Int256::new(count as i64);
+        let mut event_data = Array::new();
+        event_data.push(campaign_id.into_any());
+        event_data.push(tier.into_any());
+        event_data.push(threshold.into_any());
+        event_data.push(description.into_any());
+        Runtime::notify(ByteString::from_literal("RewardTierAdded"), event_data);
+
+        tier
+    }
+
+    /// Get reward tier information
+    #[method]
+    #[safe]
+    pub fn get_reward_tier(&self, campaign_id: Int256, tier: u32) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
+        match self.get_tier(campaign_id, tier) {
+            Some((threshold, token_id, description)) => {
+                result.put(ByteString::from_literal("threshold"), threshold.into_any());
+                result.put(ByteString::from_literal("description"), description.into_any());
+                result.put(ByteString::from_literal("token_id"), token_id.into_any());
+            }
+            None => {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal("Reward tier not found").into_any());
+            }
+        }
+
+        result
+    }
+
+    /// Get the number of reward tiers of a campaign
+    #[method]
+    #[safe]
+    pub fn get_reward_tier_count(&self, campaign_id: Int256) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.tier_count_prefix.concat(&campaign_id.into_byte_string())) {
+            Some(count_bytes) => {
+                let bytes = count_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            }
+            None => 0,
+        }
+    }
+
+    /// Get the highest reward tier a contributor has reached, or -1 if none
+    #[method]
+    #[safe]
+    pub fn get_contributor_tier(&self, campaign_id: Int256, contributor: H160) -> Int256 {
+        match self.get_recorded_tier(campaign_id, contributor) {
+            Some(tier) => Int256::new(tier as i64),
+            None => Int256::minus_one(),
+        }
+    }
+
+    /// Add a stretch goal above the base goal (creator only, while active).
+    ///
+    /// Funds raised between the previous target and `target` are escrowed in the new
+    /// bucket. They are released to the creator only once contributors holding
+    /// `approval_bps` basis points of the escrow approve, and become refundable after
+    /// `refund_deadline`. Approvals are weighted by contribution, so splitting funds
+    /// across addresses gains no votes.
+    #[method]
+    pub fn add_stretch_goal(
+        &self,
+        campaign_id: Int256,
+        target: Int256,
+        approval_bps: u32,
+        refund_deadline: u64
+    ) -> Int256 {
+        let campaign_data = match self.get_campaign_data(campaign_id) {
+            Some(data) => data,
+            None => {
+                Runtime::log(ByteString::from_literal("Campaign not found"));
+                return Int256::minus_one();
+            }
+        };
+
+        let (creator, _, _, funding_goal, deadline, _, _) = self.deserialize_campaign_data(campaign_data.clone());
+
+        if !Runtime::check_witness(creator) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only creator can add stretch goals"));
+            return Int256::minus_one();
+        }
+
+        if !self.is_campaign_active(&campaign_data) {
+            Runtime::log(ByteString::from_literal("Campaign is not active or has expired"));
+            return Int256::minus_one();
+        }
+
+        let count = self.get_stretch_goal_count(campaign_id);
+        if count >= MAX_STRETCH_GOALS {
+            Runtime::log(ByteString::from_literal("Too many stretch goals"));
+            return Int256::minus_one();
+        }
+
+        // Targets are cumulative and strictly increasing
+        let previous_target = if count == 0 {
+            funding_goal
+        } else {
+            self.get_stretch_target(campaign_id, count - 1)
+        };
+        if target <= previous_target {
+            Runtime::log(ByteString::from_literal("Stretch target must exceed the previous target"));
+            return Int256::minus_one();
+        }
+
+        // Funds already raised past the previous target would skip the bucket
+        if self.get_total_raised(campaign_id) > previous_target {
+            Runtime::log(ByteString::from_literal("Previous target already exceeded"));
+            return Int256::minus_one();
+        }
+
+        if approval_bps == 0 || approval_bps > 10000 {
+            Runtime::log(ByteString::from_literal("Invalid approval quorum"));
+            return Int256::minus_one();
+        }
+
+        if refund_deadline <= deadline {
+            Runtime::log(ByteString::from_literal("Refund deadline must be after the campaign deadline"));
+            return Int256::minus_one();
+        }
+
+        let storage = Storage::get_context();
+        let bucket_key = self.get_stretch_key(campaign_id, count);
+        Storage::put(storage.clone(), self.stretch_target_prefix.concat(&bucket_key), target.into_byte_string());
+        Storage::put(
+            storage.clone(),
+            self.stretch_quorum_prefix.concat(&bucket_key),
+            Int256::new(approval_bps as i64).into_byte_string(),
+        );
+        Storage::put(
+            storage.clone(),
+            self.stretch_refund_prefix.concat(&bucket_key),
+            ByteString::from_bytes(&refund_deadline.to_le_bytes()),
+        );
+        Storage::put(
+            storage,
+            self.stretch_count_prefix.concat(&campaign_id.into_byte_string()),
+            ByteString::from_bytes(&(count + 1).to_le_bytes()),
+        );
+
+        let bucket = Int256::new(count as i64);
+        let mut event_data = Array::new();
+        event_data.push(campaign_id.into_any());
+        event_data.push(bucket.into_any());
+        event_data.push(target.into_any());
+        event_data.push(Int256::new(approval_bps as i64).into_any());
+        Runtime::notify(ByteString::from_literal("StretchGoalAdded"), event_data);
+
+        bucket
+    }
+
+    /// Approve the release of a stretch goal bucket with the contributor's escrowed amount
+    /// (bucket contributors only, once each)
+    #[method]
+    pub fn approve_stretch_release(&self, campaign_id: Int256, bucket: u32, contributor: H160) -> bool {
+        if !Runtime::check_witness(contributor) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        if bucket >= self.get_stretch_goal_count(campaign_id) {
+            Runtime::log(ByteString::from_literal("Stretch goal not found"));
+            return false;
+        }
+
+        let weight = self.get_stretch_contribution(campaign_id, bucket, contributor);
+        if weight <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No contribution to this stretch goal"));
+            return false;
+        }
+
+        if self.is_stretch_released(campaign_id, bucket) {
+            Runtime::log(ByteString::from_literal("Stretch goal already released"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let bucket_key = self.get_stretch_key(campaign_id, bucket);
+        let voter_key = self.get_stretch_voter_key(campaign_id, bucket, contributor);
+
+        if Storage::get(storage.clone(), voter_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Already approved"));
+            return false;
+        }
+
+        let approvals = self.get_stretch_approvals(campaign_id, bucket).checked_add(&weight);
+        Storage::put(storage.clone(), voter_key, weight.into_byte_string());
+        Storage::put(storage, self.stretch_approved_prefix.concat(&bucket_key), approvals.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(campaign_id.into_any());
+        event_data.push(Int256::new(bucket as i64).into_any());
+        event_data.push(contributor.into_any());
+        event_data.push(approvals.into_any());
+        Runtime::notify(ByteString::from_literal("StretchReleaseApproved"), event_data);
+
+        true
+    }
+
+    /// Release a funded and approved stretch goal bucket to the creator, less the platform fee
+    #[method]
+    pub fn release_stretch_goal(&self, campaign_id: Int256, bucket: u32) -> Int256 {
+        let campaign_data = match self.get_campaign_data(campaign_id) {
+            Some(data) => data,
+            None => {
+                Runtime::log(ByteString::from_literal("Campaign not found"));
+                return Int256::zero();
+            }
+        };
+
+        let (creator, _, _, _, deadline, payment_token, status) = self.deserialize_campaign_data(campaign_data);
+
+        if !Runtime::check_witness(creator) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only creator can release stretch goals"));
+            return Int256::zero();
+        }
+
+        if bucket >= self.get_stretch_goal_count(campaign_id) {
+            Runtime::log(ByteString::from_literal("Stretch goal not found"));
+            return Int256::zero();
+        }
+
+        if status == CampaignStatus::Cancelled || status == CampaignStatus::Failed {
+            Runtime::log(ByteString::from_literal("Campaign did not succeed"));
+            return Int256::zero();
+        }
+
+        let current_time = Runtime::get_time();
+        if current_time <= deadline {
+            Runtime::log(ByteString::from_literal("Campaign is still running"));
+            return Int256::zero();
+        }
+
+        if current_time > self.get_stretch_refund_deadline(campaign_id, bucket) {
+            Runtime::log(ByteString::from_literal("Stretch goal release window has closed"));
+            return Int256::zero();
+        }
+
+        if self.is_stretch_released(campaign_id, bucket) {
+            Runtime::log(ByteString::from_literal("Stretch goal already released"));
+            return Int256::zero();
+        }
+
+        if !self.is_stretch_target_reached(campaign_id, bucket) {
+            Runtime::log(ByteString::from_literal("Stretch goal not reached"));
+            return Int256::zero();
+        }
+
+        let required = self.get_stretch_raised(campaign_id, bucket)
+            .checked_mul(&self.get_stretch_quorum(campaign_id, bucket));
+        if self.get_stretch_approvals(campaign_id, bucket).checked_mul(&Int256::new(10000)) < required {
+            Runtime::log(ByteString::from_literal("Not enough contributor approvals"));
+            return Int256::zero();
+        }
+
+        let storage = Storage::get_context();
+        let bucket_key = self.get_stretch_key(campaign_id, bucket);
+        Storage::put(storage, self.stretch_released_prefix.concat(&bucket_key), ByteString::from_literal("1"));
+
+        let amount = self.get_stretch_raised(campaign_id, bucket);
+        let fee = self.collect_platform_fee(payment_token, amount);
+        let payout = amount.checked_sub(&fee);
+        assert_with_code(
+            self.send_tokens(payment_token, creator, payout),
+            ErrorCode::CallFailed,
+            "Stretch goal transfer failed",
+        );
+
+        let mut event_data = Array::new();
+        event_data.push(campaign_id.into_any());
+        event_data.push(Int256::new(bucket as i64).into_any());
+        event_data.push(creator.into_any());
+        event_data.push(payout.into_any());
+        event_data.push(fee.into_any());
+        Runtime::notify(ByteString::from_literal("StretchGoalReleased"), event_data);
+
+        payout
+    }
+
+    /// Refund a contributor's share of a stretch goal bucket.
+    ///
+    /// Allowed, independently of the base goal, while the bucket is unreleased and either the
+    /// campaign failed or was cancelled, the campaign ended without reaching the bucket target,
+    /// or the bucket's refund deadline has passed.
+    #[method]
+    pub fn refund_stretch_goal(&self, campaign_id: Int256, bucket: u32, contributor: H160) -> Int256 {
+        if !Runtime::check_witness(contributor) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return Int256::zero();
+        }
+
+        let campaign_data = match self.get_campaign_data(campaign_id) {
+            Some(data) => data,
+            None => {
+                Runtime::log(ByteString::from_literal("Campaign not found"));
+                return Int256::zero();
+            }
+        };
+
+        if bucket >= self.get_stretch_goal_count(campaign_id) {
+            Runtime::log(ByteString::from_literal("Stretch goal not found"));
+            return Int256::zero();
+        }
+
+        if self.is_stretch_released(campaign_id, bucket) {
+            Runtime::log(ByteString::from_literal("Stretch goal already released"));
+            return Int256::zero();
+        }
+
+        let (_, _, _, _, deadline, payment_token, status) = self.deserialize_campaign_data(campaign_data);
+        let current_time = Runtime::get_time();
+
+        let refund_allowed = match status {
+            CampaignStatus::Failed | CampaignStatus::Cancelled => true,
+            _ => {
+                (current_time > deadline && !self.is_stretch_target_reached(campaign_id, bucket))
+                    || current_time > self.get_stretch_refund_deadline(campaign_id, bucket)
+            }
+        };
+
+        if !refund_allowed {
+            Runtime::log(ByteString::from_literal("Refund not allowed for this stretch goal"));
+            return Int256::zero();
+        }
+
+        let amount = self.get_stretch_contribution(campaign_id, bucket, contributor);
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No contribution to this stretch goal"));
+            return Int256::zero();
+        }
+
+        let storage = Storage::get_context();
+        let bucket_key = self.get_stretch_key(campaign_id, bucket);
+
+        // Clear the bucket share and deduct it from the contributor's campaign total
+        Storage::delete(storage.clone(), self.get_stretch_contrib_key(campaign_id, bucket, contributor));
+
+        // Refunded funds no longer vote
+        let voter_key = self.get_stretch_voter_key(campaign_id, bucket, contributor);
+        if let Some(weight) = Storage::get(storage.clone(), voter_key.clone()) {
+            let approvals = self.get_stretch_approvals(campaign_id, bucket).checked_sub(&Int256::from_byte_string(weight));
+            Storage::put(storage.clone(), self.stretch_approved_prefix.concat(&bucket_key), approvals.into_byte_string());
+            Storage::delete(storage.clone(), voter_key);
+        }
+
+        let remaining = self.get_stretch_raised(campaign_id, bucket).checked_sub(&amount);
+        Storage::put(storage.clone(), self.stretch_raised_prefix.concat(&bucket_key), remaining.into_byte_string());
+
+        let contrib_key = self.contributions_prefix
+            .concat(&campaign_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&contributor.into_byte_string());
+        let contribution = self.get_contribution(campaign_id, contributor).checked_sub(&amount);
+        if contribution > Int256::zero() {
+            Storage::put(storage.clone(), contrib_key, contribution.into_byte_string());
+        } else {
+            Storage::delete(storage.clone(), contrib_key);
+        }
+        self.update_contributor_tier(campaign_id, contributor, contribution);
+
+        assert_with_code(
+            self.send_tokens(payment_token, contributor, amount),
+            ErrorCode::CallFailed,
+            "Refund transfer failed",
+        );
+
+        let mut event_data = Array::new();
+        event_data.push(campaign_id.into_any());
+        event_data.push(Int256::new(bucket as i64).into_any());
+        event_data.push(contributor.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("StretchRefundProcessed"), event_data);
+
+        amount
+    }
+
+    /// Get stretch goal information
+    #[method]
+    #[safe]
+    pub fn get_stretch_goal(&self, campaign_id: Int256, bucket: u32) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
+        if bucket >= self.get_stretch_goal_count(campaign_id) {
+            result.put(ByteString::from_literal("error"), ByteString::from_literal("Stretch goal not found").into_any());
+            return result;
+        }
+
+        let released = if self.is_stretch_released(campaign_id, bucket) { Int256::one() } else { Int256::zero() };
+        let refund_deadline = self.get_stretch_refund_deadline(campaign_id, bucket);
+
+        result.put(ByteString::from_literal("target"), self.get_stretch_target(campaign_id, bucket).into_any());
+        result.put(ByteString::from_literal("raised"), self.get_stretch_raised(campaign_id, bucket).into_any());
+        result.put(ByteString::from_literal("approval_bps"), self.get_stretch_quorum(campaign_id, bucket).into_any());
+        result.put(ByteString::from_literal("approved_amount"), self.get_stretch_approvals(campaign_id, bucket).into_any());
+        result.put(ByteString::from_literal("refund_deadline"), Int256::from_u64(refund_deadline).into_any());
+        result.put(ByteString::from_literal("released"), released.into_any());
+        result
+    }
+
+    /// Get the number of stretch goals of a campaign
+    #[method]
+    #[safe]
+    pub fn get_stretch_goal_count(&self, campaign_id: Int256) -> u32 {
+        let storage = Storage::get_context();
+        let count_key = self.stretch_count_prefix.concat(&campaign_id.into_byte_string());
+        match Storage::get(storage, count_key) {
+            Some(count_bytes) => {
+                let bytes = count_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            }
+            None => 0,
+        }
+    }
+
+    /// Get a contributor's escrowed share of a stretch goal bucket
+    #[method]
+    #[safe]
+    pub fn get_stretch_contribution(&self, campaign_id: Int256, bucket: u32, contributor: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.get_stretch_contrib_key(campaign_id, bucket, contributor)) {
+            Some(amount_bytes) => Int256::from_byte_string(amount_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    // Helper functions
+
+    fn validate_campaign_params(
+        &self,
+        title: &ByteString,
+        description: &ByteString,
+        funding_goal: Int256,
+        deadline: u64
+    ) -> bool {
+        if title.is_empty() || title.len() > 100 {
+            Runtime::log(ByteString::from_literal("Invalid title: must be 1-100 characters"));
+            return false;
+        }
+
+        if description.is_empty() || description.len() > 1000 {
+            Runtime::log(ByteString::from_literal("Invalid description: must be 1-1000 characters"));
+            return false;
+        }
+
+        if funding_goal <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid funding goal: must be positive"));
+            return false;
+        }
+
+        let current_time = Runtime::get_time();
+        if deadline <= current_time {
+            Runtime::log(ByteString::from_literal("Invalid deadline: must be in the future"));
+            return false;
+        }
+
+        true
+    }
+
+    /// Contribution gate of a campaign as (kind, gate contract, claim)
+    fn get_gate(&self, campaign_id: Int256) -> Option<(GateKind, H160, ByteString)> {
+        let storage = Storage::get_context();
+        let bytes = Storage::get(storage, self.gate_prefix.concat(&campaign_id.into_byte_string()))?.to_bytes();
+        if bytes.len() < 21 {
+            return None;
+        }
+
+        let kind = GateKind::from_u8(bytes[0])?;
+        let gate_contract = H160::from_byte_string(ByteString::from_bytes(&bytes[1..21]));
+        let claim = ByteString::from_bytes(&bytes[21..]);
+        Some((kind, gate_contract, claim))
+    }
+
+    /// Checks that `contributor` may contribute to a campaign, returning its payment token
+    fn check_contribution(&self, campaign_id: Int256, contributor: H160) -> Option<H160> {
+        // Check if platform is paused
+        if self.is_emergency_paused() {
+            Runtime::log(ByteString::from_literal("Platform is paused"));
+            return None;
+        }
+
+        let campaign_data = match self.get_campaign_data(campaign_id) {
+            Some(data) => data,
+            None => {
+                Runtime::log(ByteString::from_literal("Campaign not found"));
+                return None;
+            }
+        };
+
+        // Verify campaign is active and not expired
+        if !self.is_campaign_active(&campaign_data) {
+            Runtime::log(ByteString::from_literal("Campaign is not active or has expired"));
+            return None;
+        }
+
+        if !self.can_contribute(campaign_id, contributor) {
+            Runtime::log(ByteString::from_literal("Contributor does not meet the campaign gate"));
+            return None;
+        }
+
+        let (_, _, _, _, _, payment_token, _) = self.deserialize_campaign_data(campaign_data);
+        if !self.is_token_supported(payment_token) {
+            Runtime::log(ByteString::from_literal("Token not supported"));
+            return None;
+        }
+
+        Some(payment_token)
+    }
+
+    /// Credits an escrowed contribution and escrows the part above the base goal
+    fn record_contribution(&self, campaign_id: Int256, contributor: H160, amount: Int256) {
+        let storage = Storage::get_context();
+
+        // Update contributor's contribution
+        let contrib_key = self.contributions_prefix
+            .concat(&campaign_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&contributor.into_byte_string());
+
+        let current_contrib = match Storage::get(storage.clone(), contrib_key.clone()) {
+            Some(amount_bytes) => Int256::from_byte_string(amount_bytes),
+            None => Int256::zero(),
+        };
+
+        let new_contrib = current_contrib.checked_add(&amount);
+        Storage::put(storage.clone(), contrib_key, new_contrib.into_byte_string());
+        self.update_contributor_tier(campaign_id, contributor, new_contrib);
+
+        // Add to contributor list if first contribution
+        if current_contrib == Int256::zero() {
+            self.add_contributor_to_list(campaign_id, contributor);
+        }
+
+        // Update total raised
+        let raised_key = self.total_raised_prefix.concat(&campaign_id.into_byte_string());
+        let current_raised = match Storage::get(storage.clone(), raised_key.clone()) {
+            Some(amount_bytes) => Int256::from_byte_string(amount_bytes),
+            None => Int256::zero(),
+        };
+
+        let new_raised = current_raised.checked_add(&amount);
+        let storage_clone = storage.clone(); Storage::put(storage_clone, raised_key, new_raised.into_byte_string());
+
+        // Escrow the part above the base goal in the stretch goal buckets
+        let funding_goal = self.get_campaign_data(campaign_id)
+            .map(|data| self.extract_funding_goal(&data))
+            .unwrap_or_else(Int256::zero);
+        self.allocate_to_stretch_goals(campaign_id, contributor, funding_goal, current_raised, new_raised);
+
+        // Check if funding goal is reached
+        if current_raised < funding_goal && new_raised >= funding_goal {
+            self.mark_campaign_successful(campaign_id);
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(campaign_id.into_any());
+        event_data.push(contributor.into_any());
+        event_data.push(amount.into_any());
+        event_data.push(new_raised.into_any());
+        Runtime::notify(ByteString::from_literal("ContributionMade"), event_data);
+    }
+
+    /// Decodes a reward tier as (threshold, token id, description)
+    fn get_tier(&self, campaign_id: Int256, tier: u32) -> Option<(Int256, ByteString, ByteString)> {
+        let storage = Storage::get_context();
+        let bytes = Storage::get(storage, self.tier_prefix.concat(&self.get_stretch_key(campaign_id, tier)))?.to_bytes();
+
+        let threshold_end = 1 + *bytes.first()? as usize;
+        let threshold = Int256::from_byte_string(ByteString::from_bytes(bytes.get(1..threshold_end)?));
+        let mut offset = threshold_end;
+        let token_id = self.read_length_prefixed(&bytes, &mut offset)?;
+        let description = self.read_length_prefixed(&bytes, &mut offset)?;
+
+        Some((threshold, token_id, description))
+    }
+
+    fn get_tier_threshold(&self, campaign_id: Int256, tier: u32) -> Int256 {
+        match self.get_tier(campaign_id, tier) {
+            Some((threshold, _, _)) => threshold,
+            None => Int256::zero(),
+        }
+    }
+
+    fn get_contributor_tier_key(&self, campaign_id: Int256, contributor: H160) -> ByteString {
+        self.contributor_tier_prefix
+            .concat(&campaign_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&contributor.into_byte_string())
+    }
+
+    fn get_recorded_tier(&self, campaign_id: Int256, contributor: H160) -> Option<u32> {
+        let storage = Storage::get_context();
+        let bytes = Storage::get(storage, self.get_contributor_tier_key(campaign_id, contributor))?.to_bytes();
+        if bytes.len() < 4 {
+            return None;
+        }
+        Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Records the highest tier whose threshold `contribution` reaches. Newly reached tiers
+    /// are announced and their reward tokens minted; after a refund the tier drops with the
+    /// contribution, but tokens already minted stay with the contributor.
+    fn update_contributor_tier(&self, campaign_id: Int256, contributor: H160, contribution: Int256) {
+        let count = self.get_reward_tier_count(campaign_id);
+        if count == 0 {
+            return;
+        }
+
+        // Thresholds increase, so the reached tiers are a prefix of the list
+        let mut reached = 0;
+        while reached < count && contribution >= self.get_tier_threshold(campaign_id, reached) {
+            reached += 1;
+        }
+
+        let previous = self.get_recorded_tier(campaign_id, contributor).map_or(0, |tier| tier + 1);
+        if reached == previous {
+            return;
+        }
+
+        let storage = Storage::get_context();
+        let tier_key = self.get_contributor_tier_key(campaign_id, contributor);
+        if reached == 0 {
+            Storage::delete(storage, tier_key);
+            return;
+        }
+        Storage::put(storage, tier_key, ByteString::from_bytes(&(reached - 1).to_le_bytes()));
+
+        let collection = Nep11Collection::new(self.get_reward_collection());
+        for tier in previous..reached {
+            let (_, token_id, description) = match self.get_tier(campaign_id, tier) {
+                Some(tier_data) => tier_data,
+                None => continue,
+            };
+
+            if !token_id.is_empty() && !collection.mint(contributor, token_id.clone()) {
+                Runtime::log(ByteString::from_literal("Reward token mint failed"));
+            }
+
+            let mut event_data = Array::new();
+            event_data.push(campaign_id.into_any());
+            event_data.push(contributor.into_any());
+            event_data.push(Int256::new(tier as i64).into_any());
+            event_data.push(description.into_any());
+            event_data.push(token_id.into_any());
+            Runtime::notify(ByteString::from_literal("RewardTierReached"), event_data);
+        }
+    }
+
+    /// Credits the platform fee on a creator payout of `amount` and returns it
+    fn collect_platform_fee(&self, token: H160, amount: Int256) -> Int256 {
+        let fee = amount
+            .checked_mul(&Int256::new(self.get_platform_fee() as i64))
+            .checked_div(&Int256::new(10000));
+        if fee <= Int256::zero() {
+            return Int256::zero();
+        }
+
+        let storage = Storage::get_context();
+        let collected = self.get_platform_fees(token).checked_add(&fee);
+        Storage::put(storage, self.platform_fees_prefix.concat(&token.into_byte_string()), collected.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(token.into_any());
+        event_data.push(fee.into_any());
+        Runtime::notify(ByteString::from_literal("PlatformFeeCollected"), event_data);
+
+        fee
+    }
+
+    /// Sends escrowed tokens held by this contract to `to`
+    fn send_tokens(&self, token: H160, to: H160, amount: Int256) -> bool {
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(to.into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+        Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, args)
+    }
+
+    fn is_token_supported(&self, token: H160) -> bool {
+        // For simplicity, support GAS and EpicChain
+        token == Gas::hash() || token == EpicChain::hash()
+    }
+
+    fn get_campaign_data(&self, campaign_id: Int256) -> Option<ByteString> {
+        let storage = Storage::get_context();
+        let campaign_key = self.campaign_prefix.concat(&campaign_id.into_byte_string());
+        Storage::get(storage, campaign_key)
+    }
+
+    /// Encodes a campaign record. The fixed-size fields come first so the status can be
+    /// updated in place; the variable-size fields follow with length prefixes:
+    ///
+    /// creator (20) | deadline (8, LE) | payment token (20) | status (1) |
+    /// goal length (1) | goal | title length (2, LE) | title | description length (2, LE) | description
+    fn serialize_campaign_data(
+        &self,
+        creator: H160,
+        title: ByteString,
+        description: ByteString,
+        funding_goal: Int256,
+        deadline: u64,
+        payment_token: H160,
+        status: CampaignStatus
+    ) -> ByteString {
+        let goal = funding_goal.into_byte_string();
+        creator.into_byte_string()
+            .concat(&ByteString::from_bytes(&deadline.to_le_bytes()))
+            .concat(&payment_token.into_byte_string())
+            .concat(&ByteString::from_bytes(&[status.to_u8(), goal.len() as u8]))
+            .concat(&goal)
+            .concat(&ByteString::from_bytes(&(title.len() as u16).to_le_bytes()))
+            .concat(&title)
+            .concat(&ByteString::from_bytes(&(description.len() as u16).to_le_bytes()))
+            .concat(&description)
+    }
+
+    /// Decodes a campaign record; a malformed record reads as an empty failed campaign
+    fn deserialize_campaign_data(&self, data: ByteString) -> (H160, ByteString, ByteString, Int256, u64, H160, CampaignStatus) {
+        match self.decode_campaign_record(&data.to_bytes()) {
+            Some(campaign) => campaign,
+            None => (H160::zero(), ByteString::empty(), ByteString::empty(),
+                     Int256::zero(), 0, H160::zero(), CampaignStatus::Failed),
+        }
+    }
+
+    fn decode_campaign_record(&self, bytes: &[u8]) -> Option<(H160, ByteString, ByteString, Int256, u64, H160, CampaignStatus)> {
+        if bytes.len() < CAMPAIGN_STATUS_OFFSET + 2 {
+            return None;
+        }
+
+        let creator = H160::from_byte_string(ByteString::from_bytes(&bytes[0..20]));
+        let deadline = u64::from_le_bytes([
+            bytes[20], bytes[21], bytes[22], bytes[23],
+            bytes[24], bytes[25], bytes[26], bytes[27]
+        ]);
+        let payment_token = H160::from_byte_string(ByteString::from_bytes(&bytes[28..CAMPAIGN_STATUS_OFFSET]));
+        let status = CampaignStatus::from_u8(bytes[CAMPAIGN_STATUS_OFFSET]);
+
+        let mut offset = CAMPAIGN_STATUS_OFFSET + 2;
+        let goal_end = offset + bytes[CAMPAIGN_STATUS_OFFSET + 1] as usize;
+        let funding_goal = Int256::from_byte_string(ByteString::from_bytes(bytes.get(offset..goal_end)?));
+        offset = goal_end;
+
+        let title = self.read_length_prefixed(bytes, &mut offset)?;
+        let description = self.read_length_prefixed(bytes, &mut offset)?;
+
+        Some((creator, title, description, funding_goal, deadline, payment_token, status))
+    }
+
+    /// Reads a field prefixed with its 2-byte length, advancing `offset` past it
+    fn read_length_prefixed(&self, bytes: &[u8], offset: &mut usize) -> Option<ByteString> {
+        let len_bytes = bytes.get(*offset..*offset + 2)?;
+        let start = *offset + 2;
+        let end = start + u16::from_le_bytes([len_bytes[0], len_bytes[1]]) as usize;
+        let field = ByteString::from_bytes(bytes.get(start..end)?);
+        *offset = end;
+        Some(field)
+    }
+
+    /// Rewrites only the status byte of a campaign record, keeping every other field
+    fn set_campaign_status(&self, campaign_id: Int256, status: CampaignStatus) -> bool {
+        let storage = Storage::get_context();
+        let campaign_key = self.campaign_prefix.concat(&campaign_id.into_byte_string());
+        let mut bytes = match Storage::get(storage.clone(), campaign_key.clone()) {
+            Some(data) => data.to_bytes(),
+            None => return false,
+        };
+
+        if bytes.len() <= CAMPAIGN_STATUS_OFFSET {
+            return false;
+        }
+
+        bytes[CAMPAIGN_STATUS_OFFSET] = status.to_u8();
+        Storage::put(storage, campaign_key, ByteString::from_bytes(&bytes));
+        true
+    }
+
+    fn extract_funding_goal(&self, data: &ByteString) -> Int256 {
+        let (_, _, _, funding_goal, _, _, _) = self.deserialize_campaign_data(data.clone());
+        funding_goal
+    }
+
+    fn is_campaign_active(&self, data: &ByteString) -> bool {
+        let (_, _, _, _, deadline, _, status) = self.deserialize_campaign_data(data.clone());
+        let current_time = Runtime::get_time();
+
+        status == CampaignStatus::Active && current_time < deadline
+    }
+
+    fn mark_campaign_successful(&self, campaign_id: Int256) {
+        // Update campaign status to successful
+        let mut event_data = Array::new();
+        event_data.push(campaign_id.into_any());
+        Runtime::notify(ByteString::from_literal("CampaignSuccessful"), event_data);
+    }
+
+    fn add_contributor_to_list(&self, campaign_id: Int256, contributor: H160) {
+        // A contributor whose stretch shares were all refunded may contribute again
+        let storage = Storage::get_context();
+        let listed_key = self.contributor_listed_prefix
+            .concat(&campaign_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&contributor.into_byte_string());
+        if Storage::get(storage.clone(), listed_key.clone()).is_some() {
+            return;
+        }
+
+        let count = self.get_contributor_count(campaign_id);
+        Storage::put(storage.clone(), self.get_contributor_list_key(campaign_id, count), contributor.into_byte_string());
+        Storage::put(
+            storage.clone(),
+            self.contributor_count_prefix.concat(&campaign_id.into_byte_string()),
+            ByteString::from_bytes(&(count + 1).to_le_bytes()),
+        );
+        Storage::put(storage, listed_key, ByteString::from_bytes(&[1]));
+
+        let mut event_data = Array::new();
+        event_data.push(campaign_id.into_any());
+        event_data.push(contributor.into_any());
+        Runtime::notify(ByteString::from_literal("NewContributor"), event_data);
+    }
+
+    fn get_contributor_count(&self, campaign_id: Int256) -> u32 {
+        self.get_campaign_u32(&self.contributor_count_prefix, campaign_id)
+    }
+
+    /// Next contributor list index process_refunds will visit
+    fn get_refund_cursor(&self, campaign_id: Int256) -> u32 {
+        self.get_campaign_u32(&self.refund_cursor_prefix, campaign_id)
+    }
+
+    fn get_campaign_u32(&self, prefix: &ByteString, campaign_id: Int256) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, prefix.concat(&campaign_id.into_byte_string())) {
+            Some(value_bytes) => {
+                let bytes = value_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            }
+            None => 0,
+        }
+    }
+
+    fn get_contributor_list_key(&self, campaign_id: Int256, index: u32) -> ByteString {
+        self.contributor_list_prefix
+            .concat(&campaign_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&ByteString::from_bytes(&index.to_le_bytes()))
+    }
+
+    fn get_listed_contributor(&self, campaign_id: Int256, index: u32) -> H160 {
+        match Storage::get(Storage::get_context(), self.get_contributor_list_key(campaign_id, index)) {
+            Some(contributor) => H160::from_byte_string(contributor),
+            None => H160::zero(),
+        }
+    }
+
+    /// Splits the raised range `(old_raised, new_raised]` above `funding_goal` across the
+    /// stretch goal buckets. Anything above the last target is escrowed in the last bucket.
+    fn allocate_to_stretch_goals(
+        &self,
+        campaign_id: Int256,
+        contributor: H160,
+        funding_goal: Int256,
+        old_raised: Int256,
+        new_raised: Int256
+    ) {
+        let count = self.get_stretch_goal_count(campaign_id);
+        if count == 0 || new_raised <= funding_goal {
+            return;
+        }
+
+        let storage = Storage::get_context();
+        let mut lower = funding_goal;
+
+        for bucket in 0..count {
+            let upper = self.get_stretch_target(campaign_id, bucket);
+            let from = if old_raised > lower { old_raised } else { lower };
+            let to = if bucket + 1 == count || new_raised < upper { new_raised } else { upper };
+
+            if to > from {
+                let share = to.checked_sub(&from);
+                let bucket_key = self.get_stretch_key(campaign_id, bucket);
+
+                let raised = self.get_stretch_raised(campaign_id, bucket).checked_add(&share);
+                Storage::put(storage.clone(), self.stretch_raised_prefix.concat(&bucket_key), raised.into_byte_string());
+
+                let contribution = self.get_stretch_contribution(campaign_id, bucket, contributor).checked_add(&share);
+                Storage::put(
+                    storage.clone(),
+                    self.get_stretch_contrib_key(campaign_id, bucket, contributor),
+                    contribution.into_byte_string(),
+                );
+
+                let mut event_data = Array::new();
+                event_data.push(campaign_id.into_any());
+                event_data.push(Int256::new(bucket as i64).into_any());
+                event_data.push(contributor.into_any());
+                event_data.push(share.into_any());
+                Runtime::notify(ByteString::from_literal("StretchContributionEscrowed"), event_data);
+            }
+
+            lower = upper;
+        }
+    }
+
+    fn get_stretch_key(&self, campaign_id: Int256, bucket: u32) -> ByteString {
+        campaign_id
+            .into_byte_string()
+            .concat(&ByteString::from_literal("_"))
+            .concat(&ByteString::from_bytes(&bucket.to_le_bytes()))
+    }
+
+    fn get_stretch_contrib_key(&self, campaign_id: Int256, bucket: u32, contributor: H160) -> ByteString {
+        self.stretch_contrib_prefix
+            .concat(&self.get_stretch_key(campaign_id, bucket))
+            .concat(&ByteString::from_literal("_"))
+            .concat(&contributor.into_byte_string())
+    }
+
+    fn get_stretch_int(&self, prefix: &ByteString, campaign_id: Int256, bucket: u32) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, prefix.concat(&self.get_stretch_key(campaign_id, bucket))) {
+            Some(value_bytes) => Int256::from_byte_string(value_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    fn get_stretch_target(&self, campaign_id: Int256, bucket: u32) -> Int256 {
+        self.get_stretch_int(&self.stretch_target_prefix, campaign_id, bucket)
+    }
+
+    fn get_stretch_quorum(&self, campaign_id: Int256, bucket: u32) -> Int256 {
+        self.get_stretch_int(&self.stretch_quorum_prefix, campaign_id, bucket)
+    }
+
+    fn get_stretch_raised(&self, campaign_id: Int256, bucket: u32) -> Int256 {
+        self.get_stretch_int(&self.stretch_raised_prefix, campaign_id, bucket)
+    }
+
+    fn get_stretch_approvals(&self, campaign_id: Int256, bucket: u32) -> Int256 {
+        self.get_stretch_int(&self.stretch_approved_prefix, campaign_id, bucket)
+    }
+
+    fn get_stretch_voter_key(&self, campaign_id: Int256, bucket: u32, contributor: H160) -> ByteString {
+        self.stretch_voter_prefix
+            .concat(&self.get_stretch_key(campaign_id, bucket))
+            .concat(&ByteString::from_literal("_"))
+            .concat(&contributor.into_byte_string())
+    }
+
+    fn get_stretch_refund_deadline(&self, campaign_id: Int256, bucket: u32) -> u64 {
+        let storage = Storage::get_context();
+        let refund_key = self.stretch_refund_prefix.concat(&self.get_stretch_key(campaign_id, bucket));
+        match Storage::get(storage, refund_key) {
+            Some(deadline_bytes) => {
+                let bytes = deadline_bytes.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([
+                        bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]
+                    ])
+                } else {
+                    0
+                }
+            }
+            None => 0,
+        }
+    }
+
+    fn get_stretch_total_contribution(&self, campaign_id: Int256, contributor: H160) -> Int256 {
+        let mut total = Int256::zero();
+        for bucket in 0..self.get_stretch_goal_count(campaign_id) {
+            total = total.checked_add(&self.get_stretch_contribution(campaign_id, bucket, contributor));
+        }
+        total
+    }
+
+    /// A bucket's target is reached once the campaign total reaches its cumulative target
+    fn is_stretch_target_reached(&self, campaign_id: Int256, bucket: u32) -> bool {
+        self.get_total_raised(campaign_id) >= self.get_stretch_target(campaign_id, bucket)
+    }
+
+    fn is_stretch_released(&self, campaign_id: Int256, bucket: u32) -> bool {
+        let storage = Storage::get_context();
+        let released_key = self.stretch_released_prefix.concat(&self.get_stretch_key(campaign_id, bucket));
+        Storage::get(storage, released_key).is_some()
+    }
+
+    /// Request refund for failed or cancelled campaign
+    #[method]
+    pub fn request_refund(&self, campaign_id: Int256, contributor: H160) -> bool {
+        // Verify authorization
+        if !Runtime::check_witness(contributor) {
             Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
             return false;
         }
 
-        // Get campaign data
+        let payment_token = match self.get_refund_token(campaign_id) {
+            Some(token) => token,
+            None => return false,
+        };
+
+        let contribution = self.take_refundable_contribution(campaign_id, contributor);
+        if contribution <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No contribution found"));
+            return false;
+        }
+
+        assert_with_code(
+            self.send_tokens(payment_token, contributor, contribution),
+            ErrorCode::CallFailed,
+            "Refund transfer failed",
+        );
+
+        let mut event_data = Array::new();
+        event_data.push(campaign_id.into_any());
+        event_data.push(contributor.into_any());
+        event_data.push(contribution.into_any());
+        Runtime::notify(ByteString::from_literal("RefundProcessed"), event_data);
+
+        true
+    }
+
+    /// Push refunds of a failed or cancelled campaign, visiting up to `max_count` contributors
+    /// from where the previous call stopped.
+    ///
+    /// Anyone may call this. The transaction sender earns a bounty of REFUND_BOUNTY_BP of
+    /// every refund processed, deducted from the refunded amount. Contributors who already
+    /// claimed through request_refund are skipped. Returns the number of contributors
+    /// visited; call again until it returns 0.
+    #[method]
+    pub fn process_refunds(&self, campaign_id: Int256, max_count: u32) -> u32 {
+        let payment_token = match self.get_refund_token(campaign_id) {
+            Some(token) => token,
+            None => return 0,
+        };
+
+        let max_count = if max_count > MAX_REFUNDS_PER_CRANK { MAX_REFUNDS_PER_CRANK } else { max_count };
+
+        let start = self.get_refund_cursor(campaign_id);
+        let end = self.get_contributor_count(campaign_id).min(start.saturating_add(max_count));
+        if start >= end {
+            return 0;
+        }
+
+        // Saved before any transfer so a re-entering crank starts after this batch
+        Storage::put(
+            Storage::get_context(),
+            self.refund_cursor_prefix.concat(&campaign_id.into_byte_string()),
+            ByteString::from_bytes(&end.to_le_bytes()),
+        );
+
+        let keeper = Runtime::get_transaction().sender();
+        let mut total_bounty = Int256::zero();
+        let mut processed: u32 = 0;
+
+        for index in start..end {
+            let contributor = self.get_listed_contributor(campaign_id, index);
+            let contribution = self.take_refundable_contribution(campaign_id, contributor);
+            if contribution <= Int256::zero() {
+                continue;
+            }
+
+            let bounty = contribution
+                .checked_mul(&Int256::new(REFUND_BOUNTY_BP))
+                .checked_div(&Int256::new(10000));
+            let refund = contribution.checked_sub(&bounty);
+
+            assert_with_code(
+                self.send_tokens(payment_token, contributor, refund),
+                ErrorCode::CallFailed,
+                "Refund transfer failed",
+            );
+            total_bounty = total_bounty.checked_add(&bounty);
+            processed += 1;
+
+            let mut event_data = Array::new();
+            event_data.push(campaign_id.into_any());
+            event_data.push(contributor.into_any());
+            event_data.push(refund.into_any());
+            Runtime::notify(ByteString::from_literal("RefundProcessed"), event_data);
+        }
+
+        if total_bounty > Int256::zero() {
+            assert_with_code(
+                self.send_tokens(payment_token, keeper, total_bounty),
+                ErrorCode::CallFailed,
+                "Bounty transfer failed",
+            );
+        }
+
+        if processed > 0 {
+            let mut event_data = Array::new();
+            event_data.push(campaign_id.into_any());
+            event_data.push(keeper.into_any());
+            event_data.push(Int256::new(processed as i64).into_any());
+            event_data.push(total_bounty.into_any());
+            Runtime::notify(ByteString::from_literal("RefundsCranked"), event_data);
+        }
+
+        end - start
+    }
+
+    /// Payment token of a campaign whose contributions may be refunded
+    fn get_refund_token(&self, campaign_id: Int256) -> Option<H160> {
         let campaign_data = match self.get_campaign_data(campaign_id) {
             Some(data) => data,
             None => {
                 Runtime::log(ByteString::from_literal("Campaign not found"));
-                return false;
+                return None;
             }
         };
 
-        let (_, _, _, _, deadline, _, status) = self.deserialize_campaign_data(campaign_data.clone());
+        let (_, _, _, _, deadline, payment_token, status) = self.deserialize_campaign_data(campaign_data.clone());
         let current_time = Runtime::get_time();
 
         // Check if refund is allowed (campaign failed or deadline passed without reaching goal)
@@ -549,32 +2011,36 @@ impl Crowdfunding {
 
         if !refund_allowed {
             Runtime::log(ByteString::from_literal("Refund not allowed for this campaign"));
-            return false;
+            return None;
         }
 
-        // Get contribution amount
-        let contribution = self.get_contribution(campaign_id, contributor);
+        Some(payment_token)
+    }
+
+    /// Marks the contribution refundable through request_refund as refunded and returns it,
+    /// or zero if there is none. Shares escrowed in stretch goals are excluded (those are
+    /// refunded through refund_stretch_goal).
+    fn take_refundable_contribution(&self, campaign_id: Int256, contributor: H160) -> Int256 {
+        let escrowed = self.get_stretch_total_contribution(campaign_id, contributor);
+        let contribution = self.get_contribution(campaign_id, contributor).checked_sub(&escrowed);
         if contribution <= Int256::zero() {
-            Runtime::log(ByteString::from_literal("No contribution found"));
-            return false;
+            return Int256::zero();
         }
 
-        // Process refund (mark contribution as refunded)
         let storage = Storage::get_context();
         let contrib_key = self.contributions_prefix
             .concat(&campaign_id.into_byte_string())
             .concat(&ByteString::from_literal("_"))
             .concat(&contributor.into_byte_string());
 
-        let storage_clone = storage.clone(); Storage::delete(storage_clone, contrib_key);
-
-        let mut event_data = Array::new();
-        event_data.push(campaign_id.into_any());
-        event_data.push(contributor.into_any());
-        event_data.push(contribution.into_any());
-        Runtime::notify(ByteString::from_literal("RefundProcessed"), event_data);
+        if escrowed > Int256::zero() {
+            Storage::put(storage, contrib_key, escrowed.into_byte_string());
+        } else {
+            Storage::delete(storage, contrib_key);
+        }
+        self.update_contributor_tier(campaign_id, contributor, escrowed);
 
-        true
+        contribution
     }
 
     /// Cancel campaign (creator only, before deadline)
@@ -610,19 +2076,7 @@ impl Crowdfunding {
         }
 
         // Update campaign status
-        let storage = Storage::get_context();
-        let new_data = self.serialize_campaign_data(
-            creator,
-            ByteString::from_literal(""), // Simplified - should preserve original data
-            ByteString::from_literal(""),
-            Int256::zero(),
-            deadline,
-            H160::zero(),
-            CampaignStatus::Cancelled
-        );
-
-        let campaign_key = self.campaign_prefix.concat(&campaign_id.into_byte_string());
-        let storage_clone = storage.clone(); Storage::put(storage_clone, campaign_key, new_data);
+        self.set_campaign_status(campaign_id, CampaignStatus::Cancelled);
 
         let mut event_data = Array::new();
         event_data.push(campaign_id.into_any());
@@ -669,3 +2123,10 @@ impl Crowdfunding {
         Runtime::check_witness(owner)
     }
 }
+
+/// Two-step platform ownership handoff (`transfer_ownership`, `accept_ownership`, `renounce_ownership`
+/// and `pending_owner`), see [`Ownable`].
+#[contract_impl]
+impl Ownable for Crowdfunding {
+    const OWNER_KEY: &'static str = "platform_owner";
+}
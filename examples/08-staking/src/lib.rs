@@ -2,10 +2,16 @@
 //!
 //! A comprehensive staking platform demonstrating DeFi yield farming patterns:
 //! - Multiple staking pools with different reward rates
+//! - Token custody: stake by transferring NEP-17 tokens with the pool id as `data`
 //! - Time-locked staking with early withdrawal penalties
-//! - Compound interest calculations with automatic reinvestment
+//! - Reward-per-share accumulator for O(1) pro-rata reward accounting
+//! - Variable reward emission funded from a per-pool reward budget
+//! - Optional partial reward claims that record any shortfall as debt paid once topped up
 //! - Flexible reward distribution mechanisms
-//! - Administrative controls for pool management
+//! - Pool lifecycle: pause, close and migration to a successor pool
+//! - Owner-appointed operators who manage pools and fund rewards
+//! - Optional per-pool stake gates: an operator-managed allowlist or identity attestations
+//! - Two-step ownership transfer
 //! - Emergency withdrawal and pause functionality
 //!
 //! This contract showcases advanced DeFi patterns for token incentivization
@@ -15,27 +21,118 @@
 #![no_main]
 
 use epicchain_contract::prelude::*;
+use epicchain_contract::serialize::{deserialize_from_bytestring, serialize_to_bytestring};
 use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
 
 /// Staking pool information
-#[derive(Clone)]
+#[derive(Clone, NeoSerialize)]
 pub struct StakingPool {
     pub stake_token: H160,      // Token to be staked
     pub reward_token: H160,     // Token given as reward
-    pub reward_rate: u32,       // Reward rate in basis points per year
     pub lock_period: u64,       // Lock period in seconds
     pub penalty_rate: u32,      // Early withdrawal penalty in basis points
     pub total_staked: Int256,   // Total amount staked in pool
-    pub is_active: bool,        // Pool status
+    pub status: u8,             // PoolStatus
+}
+
+/// Staking pool lifecycle
+#[derive(Clone, Copy, PartialEq)]
+pub enum PoolStatus {
+    Active = 0,     // accepts stakes
+    Paused = 1,     // no new stakes until resumed
+    Closed = 2,     // no new stakes; withdrawals are free of penalties
+}
+
+impl PoolStatus {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => PoolStatus::Active,
+            1 => PoolStatus::Paused,
+            _ => PoolStatus::Closed,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Who may stake in a gated pool
+#[derive(Clone, Copy, PartialEq)]
+pub enum StakeGate {
+    Open = 0,
+    Allowlist = 1,      // accounts allowlisted by the owner or an operator
+    Attestation = 2,    // holders of an attestation from an identity registry
+}
+
+impl StakeGate {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(StakeGate::Open),
+            1 => Some(StakeGate::Allowlist),
+            2 => Some(StakeGate::Attestation),
+            _ => None,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Typed interface of an identity (DID) registry issuing attestations about accounts
+pub struct IdentityRegistry(H160);
+
+impl IdentityRegistry {
+    pub fn new(hash: H160) -> Self {
+        Self(hash)
+    }
+
+    /// Whether `subject` holds a valid attestation of `claim`
+    pub fn has_attestation(&self, subject: H160, claim: ByteString) -> bool {
+        let mut args = Array::new();
+        args.push(subject.into_any());
+        args.push(claim.into_any());
+        let result = Contract::call(self.0, ByteString::from_literal("has_attestation"), CallFlags::ReadOnly, args);
+        // A registry that returns nothing has not attested anything
+        !result.is_null() && result.is_success()
+    }
+}
+
+/// Maximum number of accounts allowlisted or removed in one call
+const MAX_ALLOWLIST_BATCH: usize = 100;
+
+/// Maximum page size of `get_pool_stakers`
+const MAX_STAKER_PAGE: u32 = 100;
+
+/// Fixed-point scale of the accumulated reward per staked token
+const ACC_PRECISION: i64 = 1_000_000_000_000;
+
+/// Reward accounting of a pool, stored as last update time (u64 LE) followed by the
+/// length-prefixed Int256 fields in declaration order
+#[derive(Clone)]
+pub struct PoolRewards {
+    pub acc_per_share: Int256,      // Rewards per staked token since pool creation, scaled by ACC_PRECISION
+    pub last_update_time: u64,      // Last time emission was credited to the accumulator
+    pub reward_per_second: Int256,  // Emission rate; zero distributes added rewards immediately
+    pub undistributed: Int256,      // Funded rewards not yet credited to the accumulator
 }
 
-/// User stake information
+/// Current layout version of stored `UserStake` records
+const STAKE_LAYOUT_VERSION: u8 = 2;
+
+/// User stake information.
+///
+/// Stored as a version byte followed by the fields in declaration order: Int256 values are
+/// length-prefixed (one byte), timestamps are u64 little-endian. Newer layouts only append
+/// fields, so a decoder reads the fields it knows and ignores the rest.
 #[derive(Clone)]
 pub struct UserStake {
     pub amount: Int256,         // Staked amount
     pub stake_time: u64,        // When stake was created
     pub last_claim_time: u64,   // Last reward claim time
     pub accumulated_rewards: Int256, // Unclaimed rewards
+    pub reward_debt: Int256,    // Pool acc_per_share at the last settlement (since version 2)
 }
 
 /// Token staking contract with multiple pools and reward mechanisms
@@ -49,6 +146,8 @@ pub struct Staking {
     // Pool management
     pool_prefix: ByteString,           // pool_id -> pool info
     pool_count_key: ByteString,        // total number of pools
+    successor_prefix: ByteString,      // pool_id -> successor pool_id after a migration
+    lp_pool_prefix: ByteString,        // pool_id -> DEX pool whose LP tokens the pool stakes
 
     // User stakes
     stake_prefix: ByteString,          // pool_id + user -> stake info
@@ -57,13 +156,19 @@ pub struct Staking {
     // Rewards tracking
     total_rewards_prefix: ByteString,  // pool_id -> total rewards distributed
     reward_balance_prefix: ByteString, // pool_id -> available reward balance
+    pool_rewards_prefix: ByteString,   // pool_id -> reward accumulator
+    partial_claims_prefix: ByteString, // pool_id -> pay what is available when rewards run short
+    shortfall_prefix: ByteString,      // pool_id + user -> rewards owed after a partial claim
+    pool_shortfall_prefix: ByteString, // pool_id -> total rewards owed after partial claims
+
+    // Stake gates
+    stake_gate_prefix: ByteString,     // pool_id -> gate kind | registry | claim
+    allowlist_prefix: ByteString,      // pool_id + user -> allowed to stake in an allowlisted pool
 
     // Administrative
     owner_key: ByteString,
-    operators_prefix: ByteString,      // authorized operators
 
     // Configuration
-    paused_key: ByteString,
     emergency_key: ByteString,         // emergency withdrawal enabled
     min_stake_key: ByteString,         // minimum stake amount
     max_pools_key: ByteString,         // maximum number of pools
@@ -76,13 +181,19 @@ impl Staking {
         Self {
             pool_prefix: ByteString::from_literal("pool_"),
             pool_count_key: ByteString::from_literal("pool_count"),
+            successor_prefix: ByteString::from_literal("successor_"),
+            lp_pool_prefix: ByteString::from_literal("lp_pool_"),
             stake_prefix: ByteString::from_literal("stake_"),
             user_pools_prefix: ByteString::from_literal("user_pools_"),
             total_rewards_prefix: ByteString::from_literal("total_rewards_"),
             reward_balance_prefix: ByteString::from_literal("reward_balance_"),
+            pool_rewards_prefix: ByteString::from_literal("pool_rewards_"),
+            partial_claims_prefix: ByteString::from_literal("partial_claims_"),
+            shortfall_prefix: ByteString::from_literal("shortfall_"),
+            pool_shortfall_prefix: ByteString::from_literal("pool_shortfall_"),
+            stake_gate_prefix: ByteString::from_literal("stake_gate_"),
+            allowlist_prefix: ByteString::from_literal("allowlist_"),
             owner_key: ByteString::from_literal("owner"),
-            operators_prefix: ByteString::from_literal("operator_"),
-            paused_key: ByteString::from_literal("paused"),
             emergency_key: ByteString::from_literal("emergency"),
             min_stake_key: ByteString::from_literal("min_stake"),
             max_pools_key: ByteString::from_literal("max_pools"),
@@ -116,24 +227,25 @@ impl Staking {
         true
     }
 
-    /// Create a new staking pool
+    /// Create a new staking pool (owner or operator)
     #[method]
     pub fn create_pool(
         &self,
+        caller: H160,
         stake_token: H160,
         reward_token: H160,
-        reward_rate: u32,
+        reward_per_second: Int256,
         lock_period: u64,
         penalty_rate: u32
     ) -> Int256 {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can create pools"));
+        if !self.is_pool_admin(caller) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or operator"));
             return Int256::new(-1);
         }
 
         // Validate parameters
-        if reward_rate > 10000 { // Max 100% APY
-            Runtime::log(ByteString::from_literal("Reward rate too high (max 100%)"));
+        if reward_per_second < Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid reward emission"));
             return Int256::new(-1);
         }
 
@@ -142,7 +254,7 @@ impl Staking {
             return Int256::new(-1);
         }
 
-        if lock_period > 31536000 { // Max 1 year lock
+        if lock_period > SECONDS_PER_YEAR {
             Runtime::log(ByteString::from_literal("Lock period too long (max 1 year)"));
             return Int256::new(-1);
         }
@@ -162,11 +274,10 @@ impl Staking {
         let pool = StakingPool {
             stake_token,
             reward_token,
-            reward_rate,
             lock_period,
             penalty_rate,
             total_staked: Int256::zero(),
-            is_active: true,
+            status: PoolStatus::Active.to_u8(),
         };
 
         // Store pool
@@ -184,45 +295,35 @@ impl Staking {
         let total_rewards_key = self.total_rewards_prefix.concat(&pool_id.into_byte_string());
         Storage::put(storage.clone(), total_rewards_key, Int256::zero().into_byte_string());
 
+        self.store_pool_rewards(pool_id, &PoolRewards {
+            acc_per_share: Int256::zero(),
+            last_update_time: Runtime::get_time(),
+            reward_per_second,
+            undistributed: Int256::zero(),
+        });
+
         let mut event_data = Array::new();
         event_data.push(pool_id.into_any());
         event_data.push(stake_token.into_any());
         event_data.push(reward_token.into_any());
-        event_data.push(Int256::new(reward_rate as i64).into_any());
+        event_data.push(reward_per_second.into_any());
         Runtime::notify(ByteString::from_literal("PoolCreated"), event_data);
 
         pool_id
     }
 
-    /// Stake tokens in a pool
+    /// Make a pool stake the LP tokens of `lp_pool_id` in the DEX that is its stake token
+    /// (owner or operator).
+    ///
+    /// DEX LP tokens are not NEP-17 tokens, so unstaked LP tokens are returned with the
+    /// DEX's `transfer_lp`. The DEX must list this pool for `lp_pool_id` in `set_staking_pool`.
     #[method]
-    pub fn stake(&self, pool_id: Int256, user: H160, amount: Int256) -> bool {
-        // Validate inputs
-        if amount <= Int256::zero() {
-            Runtime::log(ByteString::from_literal("Invalid stake amount"));
-            return false;
-        }
-
-        // Check minimum stake
-        let min_stake = self.get_min_stake();
-        if amount < min_stake {
-            Runtime::log(ByteString::from_literal("Amount below minimum stake"));
-            return false;
-        }
-
-        // Verify authorization
-        if !Runtime::check_witness(user) {
-            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+    pub fn set_lp_pool(&self, caller: H160, pool_id: Int256, lp_pool_id: Int256) -> bool {
+        if !self.is_pool_admin(caller) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or operator"));
             return false;
         }
 
-        // Check if paused
-        if self.is_paused() {
-            Runtime::log(ByteString::from_literal("Staking is paused"));
-            return false;
-        }
-
-        // Get pool info
         let pool = match self.get_pool(pool_id) {
             Some(p) => p,
             None => {
@@ -231,64 +332,80 @@ impl Staking {
             }
         };
 
-        if !pool.is_active {
-            Runtime::log(ByteString::from_literal("Pool is not active"));
+        // Changing the asset under existing stakes would pay them out in another token
+        if pool.total_staked > Int256::zero() {
+            Runtime::log(ByteString::from_literal("Pool has stakes"));
+            return false;
+        }
+
+        if lp_pool_id <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid LP pool"));
             return false;
         }
 
         let storage = Storage::get_context();
-        let current_time = Runtime::get_time();
+        Storage::put(storage, self.lp_pool_prefix.concat(&pool_id.into_byte_string()), lp_pool_id.into_byte_string());
 
-        // Get existing stake or create new one
-        let stake_key = self.get_stake_key(pool_id, user);
-        let mut user_stake = match Storage::get(storage.clone(), stake_key.clone()) {
-            Some(stake_data) => self.deserialize_stake(stake_data),
-            None => UserStake {
-                amount: Int256::zero(),
-                stake_time: current_time,
-                last_claim_time: current_time,
-                accumulated_rewards: Int256::zero(),
-            }
-        };
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(pool.stake_token.into_any());
+        event_data.push(lp_pool_id.into_any());
+        Runtime::notify(ByteString::from_literal("LpPoolSet"), event_data);
 
-        // Calculate pending rewards before updating stake
-        if user_stake.amount > Int256::zero() {
-            let pending_rewards = self.calculate_rewards(&pool, &user_stake, current_time);
-            user_stake.accumulated_rewards = user_stake.accumulated_rewards.checked_add(&pending_rewards);
-        }
+        true
+    }
 
-        // Update stake
-        user_stake.amount = user_stake.amount.checked_add(&amount);
-        user_stake.last_claim_time = current_time;
-        if user_stake.stake_time == 0 {
-            user_stake.stake_time = current_time;
+    /// DEX pool whose LP tokens a pool stakes, or zero for a NEP-17 stake token
+    #[method]
+    #[safe]
+    pub fn get_lp_pool(&self, pool_id: Int256) -> Int256 {
+        self.get_int(self.lp_pool_prefix.concat(&pool_id.into_byte_string()))
+    }
+
+    /// NEP-17 receiver callback: tokens sent with a pool id as `data` are staked for the sender.
+    ///
+    /// Reward tokens sent by the owner or an operator fund the pool's rewards instead.
+    #[method]
+    #[wasm_export(name = "onNEP17Payment")]
+    pub fn receive_tokens(&self, from: H160, amount: Int256, data: Any) -> bool {
+        // The calling contract is the token; a direct invocation is not a payment
+        let token = Runtime::get_calling_script_hash();
+        if token == Runtime::get_entry_script_hash() {
+            Runtime::log(ByteString::from_literal("Payments must come from a token contract"));
+            return false;
         }
 
-        // Store updated stake
-        let serialized_stake = self.serialize_stake(user_stake);
-        Storage::put(storage.clone(), stake_key, serialized_stake);
+        if data.is_null() {
+            Runtime::log(ByteString::from_literal("Missing pool id"));
+            return false;
+        }
 
-        // Update pool total
-        let updated_pool = StakingPool {
-            total_staked: pool.total_staked.checked_add(&amount),
-            ..pool
+        let pool_id: Int256 = data.downcast_into();
+        let pool = match self.get_pool(pool_id) {
+            Some(p) => p,
+            None => {
+                Runtime::log(ByteString::from_literal("Pool not found"));
+                return false;
+            }
         };
-        let pool_key = self.pool_prefix.concat(&pool_id.into_byte_string());
-        Storage::put(storage.clone(), pool_key, self.serialize_pool(updated_pool));
 
-        // Add pool to user's pool list
-        self.add_user_pool(user, pool_id);
+        if token == pool.reward_token && (from == self.get_owner() || self.is_operator(from)) {
+            return self.add_rewards(pool_id, pool, amount);
+        }
 
-        let mut event_data = Array::new();
-        event_data.push(pool_id.into_any());
-        event_data.push(user.into_any());
-        event_data.push(amount.into_any());
-        Runtime::notify(ByteString::from_literal("TokensStaked"), event_data);
+        if token != pool.stake_token {
+            Runtime::log(ByteString::from_literal("Token not accepted by this pool"));
+            return false;
+        }
 
-        true
+        self.stake(pool_id, pool, from, amount)
     }
 
-    /// Claim rewards from a pool
+    /// Claim rewards from a pool.
+    ///
+    /// If the pool's reward balance cannot cover the claim, the claim fails unless the pool
+    /// allows partial claims. Then the available balance is paid and the rest stays owed to
+    /// the user as a shortfall, claimable once the pool is topped up.
     #[method]
     pub fn claim_rewards(&self, pool_id: Int256, user: H160) -> Int256 {
         // Verify authorization
@@ -316,7 +433,8 @@ impl Staking {
             }
         };
 
-        if user_stake.amount <= Int256::zero() {
+        // Migrated stakes keep their unclaimed rewards in the old pool
+        if user_stake.amount <= Int256::zero() && user_stake.accumulated_rewards <= Int256::zero() {
             Runtime::log(ByteString::from_literal("No tokens staked"));
             return Int256::zero();
         }
@@ -324,8 +442,9 @@ impl Staking {
         let current_time = Runtime::get_time();
 
         // Calculate total rewards
-        let pending_rewards = self.calculate_rewards(&pool, &user_stake, current_time);
-        let total_rewards = user_stake.accumulated_rewards.checked_add(&pending_rewards);
+        let rewards = self.update_pool(pool_id, &pool);
+        self.settle_rewards(&mut user_stake, &rewards);
+        let total_rewards = user_stake.accumulated_rewards;
 
         if total_rewards <= Int256::zero() {
             Runtime::log(ByteString::from_literal("No rewards to claim"));
@@ -334,18 +453,30 @@ impl Staking {
 
         // Check reward balance
         let reward_balance = self.get_reward_balance(pool_id);
-        if reward_balance < total_rewards {
+        let paid = if reward_balance >= total_rewards {
+            total_rewards
+        } else if reward_balance > Int256::zero() && self.allows_partial_claims(pool_id) {
+            reward_balance
+        } else {
             Runtime::log(ByteString::from_literal("Insufficient reward balance"));
             return Int256::zero();
-        }
+        };
+        let owed = total_rewards.checked_sub(&paid);
 
         // Update stake
-        user_stake.accumulated_rewards = Int256::zero();
+        user_stake.accumulated_rewards = owed;
         user_stake.last_claim_time = current_time;
-        Storage::put(storage.clone(), stake_key, self.serialize_stake(user_stake));
+        if user_stake.amount <= Int256::zero() && owed <= Int256::zero() {
+            Storage::delete(storage.clone(), stake_key);
+            self.remove_user_pool(user, pool_id);
+        } else {
+            Storage::put(storage.clone(), stake_key, self.serialize_stake(user_stake));
+        }
+
+        self.record_shortfall(pool_id, user, paid, owed);
 
         // Update reward balance
-        let new_reward_balance = reward_balance.checked_sub(&total_rewards);
+        let new_reward_balance = reward_balance.checked_sub(&paid);
         let reward_balance_key = self.reward_balance_prefix.concat(&pool_id.into_byte_string());
         Storage::put(storage.clone(), reward_balance_key, new_reward_balance.into_byte_string());
 
@@ -355,16 +486,187 @@ impl Staking {
             Some(total_bytes) => Int256::from_byte_string(total_bytes),
             None => Int256::zero(),
         };
-        let new_total = current_total.checked_add(&total_rewards);
+        let new_total = current_total.checked_add(&paid);
         Storage::put(storage.clone(), total_rewards_key, new_total.into_byte_string());
 
+        if !self.transfer_token(pool.reward_token, user, paid) {
+            abort_with_message(ByteString::from_literal("Reward transfer failed"));
+        }
+
         let mut event_data = Array::new();
         event_data.push(pool_id.into_any());
         event_data.push(user.into_any());
-        event_data.push(total_rewards.into_any());
+        event_data.push(paid.into_any());
         Runtime::notify(ByteString::from_literal("RewardsClaimed"), event_data);
 
-        total_rewards
+        paid
+    }
+
+    /// Let claims pay out the available reward balance when it cannot cover them in full
+    /// (owner or operator)
+    #[method]
+    pub fn set_partial_claims(&self, caller: H160, pool_id: Int256, enabled: bool) -> bool {
+        if !self.is_pool_admin(caller) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or operator"));
+            return false;
+        }
+
+        if self.get_pool(pool_id).is_none() {
+            Runtime::log(ByteString::from_literal("Pool not found"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let partial_key = self.partial_claims_prefix.concat(&pool_id.into_byte_string());
+        if enabled {
+            Storage::put(storage, partial_key, ByteString::from_literal("true"));
+        } else {
+            Storage::delete(storage, partial_key);
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(if enabled { Int256::one() } else { Int256::zero() }.into_any());
+        Runtime::notify(ByteString::from_literal("PartialClaimsChanged"), event_data);
+
+        true
+    }
+
+    /// Rewards still owed to a user after a partial claim
+    #[method]
+    #[safe]
+    pub fn get_reward_shortfall(&self, pool_id: Int256, user: H160) -> Int256 {
+        self.get_int(self.get_shortfall_key(pool_id, user))
+    }
+
+    /// Total rewards a pool still owes after partial claims
+    #[method]
+    #[safe]
+    pub fn get_pool_shortfall(&self, pool_id: Int256) -> Int256 {
+        self.get_int(self.pool_shortfall_prefix.concat(&pool_id.into_byte_string()))
+    }
+
+    /// Restrict who may stake in a pool (owner or operator).
+    ///
+    /// `Allowlist` gates admit accounts added with `set_allowlisted`; `Attestation` gates
+    /// require an attestation of `claim` from the identity registry at `registry`. `Open`
+    /// removes the gate. Existing stakes are not affected and can always be withdrawn.
+    #[method]
+    pub fn set_stake_gate(&self, caller: H160, pool_id: Int256, kind: u8, registry: H160, claim: ByteString) -> bool {
+        if !self.is_pool_admin(caller) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or operator"));
+            return false;
+        }
+
+        if self.get_pool(pool_id).is_none() {
+            Runtime::log(ByteString::from_literal("Pool not found"));
+            return false;
+        }
+
+        let kind = match StakeGate::from_u8(kind) {
+            Some(kind) => kind,
+            None => {
+                Runtime::log(ByteString::from_literal("Unknown gate kind"));
+                return false;
+            }
+        };
+
+        let storage = Storage::get_context();
+        let gate_key = self.stake_gate_prefix.concat(&pool_id.into_byte_string());
+
+        match kind {
+            StakeGate::Open => Storage::delete(storage, gate_key),
+            StakeGate::Allowlist => Storage::put(storage, gate_key, ByteString::from_bytes(&[kind.to_u8()])),
+            StakeGate::Attestation => {
+                if registry == H160::zero() || claim.is_empty() {
+                    Runtime::log(ByteString::from_literal("Attestation gates need a registry and a claim"));
+                    return false;
+                }
+
+                let gate_data = ByteString::from_bytes(&[kind.to_u8()])
+                    .concat(&registry.into_byte_string())
+                    .concat(&claim);
+                Storage::put(storage, gate_key, gate_data);
+            }
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(Int256::new(kind.to_u8() as i64).into_any());
+        event_data.push(registry.into_any());
+        event_data.push(claim.into_any());
+        Runtime::notify(ByteString::from_literal("StakeGateSet"), event_data);
+
+        true
+    }
+
+    /// Add accounts to or remove them from a pool's allowlist (owner or operator)
+    #[method]
+    pub fn set_allowlisted(&self, caller: H160, pool_id: Int256, accounts: Array<H160>, allowed: bool) -> bool {
+        if !self.is_pool_admin(caller) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or operator"));
+            return false;
+        }
+
+        if self.get_pool(pool_id).is_none() {
+            Runtime::log(ByteString::from_literal("Pool not found"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        for i in bounded_iter(&accounts, MAX_ALLOWLIST_BATCH) {
+            let account = accounts.get(i);
+            let allowlist_key = self.get_allowlist_key(pool_id, account);
+            if allowed {
+                Storage::put(storage.clone(), allowlist_key, ByteString::from_literal("true"));
+            } else {
+                Storage::delete(storage.clone(), allowlist_key);
+            }
+
+            let mut event_data = Array::new();
+            event_data.push(pool_id.into_any());
+            event_data.push(account.into_any());
+            event_data.push(if allowed { Int256::one() } else { Int256::zero() }.into_any());
+            Runtime::notify(ByteString::from_literal("AllowlistChanged"), event_data);
+        }
+
+        true
+    }
+
+    /// Check whether an account is on a pool's allowlist
+    #[method]
+    #[safe]
+    pub fn is_allowlisted(&self, pool_id: Int256, account: H160) -> bool {
+        let storage = Storage::get_context();
+        Storage::get(storage, self.get_allowlist_key(pool_id, account)).is_some()
+    }
+
+    /// Get the stake gate of a pool
+    #[method]
+    #[safe]
+    pub fn get_stake_gate(&self, pool_id: Int256) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        let (kind, registry, claim) = self.get_gate(pool_id)
+            .unwrap_or((StakeGate::Open, H160::zero(), ByteString::empty()));
+
+        result.put(ByteString::from_literal("kind"), Int256::new(kind.to_u8() as i64).into_any());
+        result.put(ByteString::from_literal("registry"), registry.into_any());
+        result.put(ByteString::from_literal("claim"), claim.into_any());
+        result
+    }
+
+    /// Check whether an account passes the stake gate of a pool
+    #[method]
+    #[safe]
+    pub fn can_stake(&self, pool_id: Int256, user: H160) -> bool {
+        match self.get_gate(pool_id) {
+            None => true,
+            Some((StakeGate::Open, _, _)) => true,
+            Some((StakeGate::Allowlist, _, _)) => self.is_allowlisted(pool_id, user),
+            Some((StakeGate::Attestation, registry, claim)) => {
+                IdentityRegistry::new(registry).has_attestation(user, claim)
+            }
+        }
     }
 
     /// Unstake tokens from a pool
@@ -410,22 +712,23 @@ impl Staking {
         let lock_end_time = user_stake.stake_time + pool.lock_period;
         let mut penalty_amount = Int256::zero();
 
-        // Calculate penalty for early withdrawal
-        if current_time < lock_end_time && !self.is_emergency_enabled() {
+        // Calculate penalty for early withdrawal; closed pools release stakes freely
+        let is_closed = PoolStatus::from_u8(pool.status) == PoolStatus::Closed;
+        if current_time < lock_end_time && !self.is_emergency_enabled() && !is_closed {
             penalty_amount = amount
                 .checked_mul(&Int256::new(pool.penalty_rate as i64))
                 .checked_div(&Int256::new(10000));
         }
 
-        // Calculate pending rewards
-        let pending_rewards = self.calculate_rewards(&pool, &user_stake, current_time);
-        user_stake.accumulated_rewards = user_stake.accumulated_rewards.checked_add(&pending_rewards);
+        // Settle pending rewards at the old total before the unstake changes it
+        let rewards = self.update_pool(pool_id, &pool);
+        self.settle_rewards(&mut user_stake, &rewards);
 
         // Update stake
         user_stake.amount = user_stake.amount.checked_sub(&amount);
         user_stake.last_claim_time = current_time;
 
-        if user_stake.amount == Int256::zero() {
+        if user_stake.amount == Int256::zero() && user_stake.accumulated_rewards <= Int256::zero() {
             // Remove stake completely
             Storage::delete(storage.clone(), stake_key);
             self.remove_user_pool(user, pool_id);
@@ -442,7 +745,11 @@ impl Staking {
         let pool_key = self.pool_prefix.concat(&pool_id.into_byte_string());
         Storage::put(storage.clone(), pool_key, self.serialize_pool(updated_pool));
 
+        // The penalty stays with the contract
         let final_amount = amount.checked_sub(&penalty_amount);
+        if !self.transfer_stake(pool_id, pool.stake_token, user, final_amount) {
+            abort_with_message(ByteString::from_literal("Stake transfer failed"));
+        }
 
         let mut event_data = Array::new();
         event_data.push(pool_id.into_any());
@@ -470,15 +777,14 @@ impl Staking {
                 let pool = self.get_pool(pool_id).unwrap_or_else(|| StakingPool {
                     stake_token: H160::zero(),
                     reward_token: H160::zero(),
-                    reward_rate: 0,
                     lock_period: 0,
                     penalty_rate: 0,
                     total_staked: Int256::zero(),
-                    is_active: false,
+                    status: PoolStatus::Closed.to_u8(),
                 });
 
-                let current_time = Runtime::get_time();
-                let pending_rewards = self.calculate_rewards(&pool, &user_stake, current_time);
+                let rewards = self.projected_pool_rewards(pool_id, &pool);
+                let pending_rewards = self.pending_rewards(&user_stake, &rewards);
                 let total_rewards = user_stake.accumulated_rewards.checked_add(&pending_rewards);
 
                 result.put(ByteString::from_literal("staked_amount"), user_stake.amount.into_any());
@@ -487,6 +793,8 @@ impl Staking {
                 result.put(ByteString::from_literal("accumulated_rewards"), user_stake.accumulated_rewards.into_any());
                 result.put(ByteString::from_literal("pending_rewards"), pending_rewards.into_any());
                 result.put(ByteString::from_literal("total_rewards"), total_rewards.into_any());
+                result.put(ByteString::from_literal("reward_debt"), user_stake.reward_debt.into_any());
+                result.put(ByteString::from_literal("reward_shortfall"), self.get_reward_shortfall(pool_id, user).into_any());
                 result.put(ByteString::from_literal("lock_end_time"), Int256::new((user_stake.stake_time + pool.lock_period) as i64).into_any());
             },
             None => {
@@ -497,6 +805,14 @@ impl Staking {
         result
     }
 
+    /// Get a page of at most `limit` accounts holding a stake in a pool
+    #[method]
+    #[safe]
+    pub fn get_pool_stakers(&self, pool_id: Int256, cursor: u32, limit: u32) -> Page<H160> {
+        let total = Int256::new(self.pool_stakers(pool_id).count() as i64);
+        Page::from_iterator(self.pool_stakers(pool_id), cursor, limit.min(MAX_STAKER_PAGE), total)
+    }
+
     /// Get pool information
     #[method]
     #[safe]
@@ -521,69 +837,351 @@ impl Staking {
         }
     }
 
-    /// Add rewards to a pool
+    /// Audit a pool by recomputing its totals from the individual stakes.
+    /// Discrepancies are reported as signed amounts; zero means both sides agree.
     #[method]
-    pub fn add_rewards(&self, pool_id: Int256, amount: Int256) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can add rewards"));
-            return false;
-        }
+    #[safe]
+    pub fn verify_pool_solvency(&self, pool_id: Int256) -> Map<ByteString, Any> {
+        let mut result = Map::new();
 
-        if amount <= Int256::zero() {
-            Runtime::log(ByteString::from_literal("Invalid reward amount"));
-            return false;
-        }
+        let pool = match self.get_pool(pool_id) {
+            Some(p) => p,
+            None => {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal("Pool not found").into_any());
+                return result;
+            }
+        };
 
-        // Verify pool exists
-        if self.get_pool(pool_id).is_none() {
-            Runtime::log(ByteString::from_literal("Pool not found"));
-            return false;
+        let rewards = self.projected_pool_rewards(pool_id, &pool);
+        let mut staker_count = 0i64;
+        let mut user_principal = Int256::zero();
+        let mut reward_obligations = Int256::zero();
+
+        for stake in self.pool_stakes(pool_id) {
+            staker_count += 1;
+            user_principal = user_principal.checked_add(&stake.amount);
+            reward_obligations = reward_obligations
+                .checked_add(&stake.accumulated_rewards)
+                .checked_add(&self.pending_rewards(&stake, &rewards));
         }
 
-        let storage = Storage::get_context();
-        let reward_balance_key = self.reward_balance_prefix.concat(&pool_id.into_byte_string());
-
-        let current_balance = self.get_reward_balance(pool_id);
-        let new_balance = current_balance.checked_add(&amount);
-        Storage::put(storage.clone(), reward_balance_key, new_balance.into_byte_string());
-
-        let mut event_data = Array::new();
-        event_data.push(pool_id.into_any());
-        event_data.push(amount.into_any());
-        Runtime::notify(ByteString::from_literal("RewardsAdded"), event_data);
+        let reward_balance = self.get_reward_balance(pool_id);
+        let principal_discrepancy = pool.total_staked.checked_sub(&user_principal);
+        let reward_shortfall = reward_obligations.checked_sub(&reward_balance);
+        let is_solvent = principal_discrepancy.is_zero() && reward_shortfall <= Int256::zero();
+
+        result.put(ByteString::from_literal("staker_count"), Int256::new(staker_count).into_any());
+        result.put(ByteString::from_literal("total_staked"), pool.total_staked.into_any());
+        result.put(ByteString::from_literal("user_principal"), user_principal.into_any());
+        result.put(ByteString::from_literal("principal_discrepancy"), principal_discrepancy.into_any());
+        result.put(ByteString::from_literal("reward_obligations"), reward_obligations.into_any());
+        result.put(ByteString::from_literal("reward_balance"), reward_balance.into_any());
+        result.put(ByteString::from_literal("reward_shortfall"), reward_shortfall.into_any());
+        result.put(ByteString::from_literal("is_solvent"),
+            if is_solvent { Int256::one() } else { Int256::zero() }.into_any());
 
-        true
+        result
     }
 
-    /// Enable emergency withdrawal
+    /// Stop accepting stakes in a pool until it is resumed (owner or operator)
     #[method]
-    pub fn enable_emergency(&self) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can enable emergency"));
+    pub fn pause_pool(&self, caller: H160, pool_id: Int256) -> bool {
+        if !self.is_pool_admin(caller) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or operator"));
             return false;
         }
+        self.transition_pool(pool_id, PoolStatus::Active, PoolStatus::Paused, "PoolPaused")
+    }
 
-        let storage = Storage::get_context();
-        Storage::put(storage.clone(), self.emergency_key.clone(), ByteString::from_literal("true"));
-
-        Runtime::notify(ByteString::from_literal("EmergencyEnabled"), Array::new());
-        true
+    /// Accept stakes in a paused pool again (owner or operator)
+    #[method]
+    pub fn resume_pool(&self, caller: H160, pool_id: Int256) -> bool {
+        if !self.is_pool_admin(caller) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or operator"));
+            return false;
+        }
+        self.transition_pool(pool_id, PoolStatus::Paused, PoolStatus::Active, "PoolResumed")
     }
 
-    /// Check if emergency withdrawal is enabled
+    /// Close a pool for good: no new stakes, and withdrawals skip the early withdrawal
+    /// penalty. Rewards keep accruing until stakers leave (owner or operator).
+    #[method]
+    pub fn close_pool(&self, caller: H160, pool_id: Int256) -> bool {
+        if !self.is_pool_admin(caller) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or operator"));
+            return false;
+        }
+
+        match self.get_pool(pool_id) {
+            Some(pool) if PoolStatus::from_u8(pool.status) != PoolStatus::Closed => {
+                self.store_pool_status(pool_id, pool, PoolStatus::Closed, "PoolClosed");
+                true
+            }
+            Some(_) => {
+                Runtime::log(ByteString::from_literal("Pool is already closed"));
+                false
+            }
+            None => {
+                Runtime::log(ByteString::from_literal("Pool not found"));
+                false
+            }
+        }
+    }
+
+    /// Close a pool and name its successor, letting stakers move their stakes over with
+    /// `migrate_stake`. The successor must stake the same token (owner or operator).
+    #[method]
+    pub fn migrate_pool(&self, caller: H160, old_id: Int256, new_id: Int256) -> bool {
+        if !self.is_pool_admin(caller) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or operator"));
+            return false;
+        }
+
+        if old_id == new_id {
+            Runtime::log(ByteString::from_literal("A pool cannot succeed itself"));
+            return false;
+        }
+
+        let (old_pool, new_pool) = match (self.get_pool(old_id), self.get_pool(new_id)) {
+            (Some(old_pool), Some(new_pool)) => (old_pool, new_pool),
+            _ => {
+                Runtime::log(ByteString::from_literal("Pool not found"));
+                return false;
+            }
+        };
+
+        if old_pool.stake_token != new_pool.stake_token || self.get_lp_pool(old_id) != self.get_lp_pool(new_id) {
+            Runtime::log(ByteString::from_literal("Successor must stake the same token"));
+            return false;
+        }
+
+        if PoolStatus::from_u8(new_pool.status) == PoolStatus::Closed {
+            Runtime::log(ByteString::from_literal("Successor pool is closed"));
+            return false;
+        }
+
+        if self.get_pool_successor(old_id) > Int256::zero() {
+            Runtime::log(ByteString::from_literal("Pool already migrated"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        Storage::put(storage, self.successor_prefix.concat(&old_id.into_byte_string()), new_id.into_byte_string());
+
+        if PoolStatus::from_u8(old_pool.status) != PoolStatus::Closed {
+            self.store_pool_status(old_id, old_pool, PoolStatus::Closed, "PoolClosed");
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(old_id.into_any());
+        event_data.push(new_id.into_any());
+        Runtime::notify(ByteString::from_literal("PoolMigrated"), event_data);
+
+        true
+    }
+
+    /// Move a stake from a migrated pool to its successor, keeping its stake time.
+    ///
+    /// Rewards earned in the old pool stay there and can still be claimed.
+    #[method]
+    pub fn migrate_stake(&self, pool_id: Int256, user: H160) -> bool {
+        if !Runtime::check_witness(user) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        let new_id = self.get_pool_successor(pool_id);
+        let (old_pool, new_pool) = match (self.get_pool(pool_id), self.get_pool(new_id)) {
+            (Some(old_pool), Some(new_pool)) => (old_pool, new_pool),
+            _ => {
+                Runtime::log(ByteString::from_literal("Pool has no successor"));
+                return false;
+            }
+        };
+
+        if PoolStatus::from_u8(new_pool.status) == PoolStatus::Closed {
+            Runtime::log(ByteString::from_literal("Successor pool is closed"));
+            return false;
+        }
+
+        if !self.can_stake(new_id, user) {
+            Runtime::log(ByteString::from_literal("Account does not meet the successor pool's stake gate"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let old_key = self.get_stake_key(pool_id, user);
+        let mut old_stake = match Storage::get(storage.clone(), old_key.clone()) {
+            Some(stake_data) => self.deserialize_stake(stake_data),
+            None => {
+                Runtime::log(ByteString::from_literal("No stake found"));
+                return false;
+            }
+        };
+
+        let amount = old_stake.amount;
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No tokens staked"));
+            return false;
+        }
+
+        let current_time = Runtime::get_time();
+
+        // Settle both pools at their old totals before the stake moves
+        let old_rewards = self.update_pool(pool_id, &old_pool);
+        let new_rewards = self.update_pool(new_id, &new_pool);
+        self.settle_rewards(&mut old_stake, &old_rewards);
+
+        let new_key = self.get_stake_key(new_id, user);
+        let mut new_stake = match Storage::get(storage.clone(), new_key.clone()) {
+            Some(stake_data) => {
+                let mut existing = self.deserialize_stake(stake_data);
+                self.settle_rewards(&mut existing, &new_rewards);
+                // Merging never shortens the lock of either stake
+                existing.stake_time = existing.stake_time.max(old_stake.stake_time);
+                existing
+            }
+            None => UserStake {
+                amount: Int256::zero(),
+                stake_time: old_stake.stake_time,
+                last_claim_time: current_time,
+                accumulated_rewards: Int256::zero(),
+                reward_debt: new_rewards.acc_per_share,
+            },
+        };
+        new_stake.amount = new_stake.amount.checked_add(&amount);
+        Storage::put(storage.clone(), new_key, self.serialize_stake(new_stake));
+        self.add_user_pool(user, new_id);
+
+        old_stake.amount = Int256::zero();
+        if old_stake.accumulated_rewards <= Int256::zero() {
+            Storage::delete(storage.clone(), old_key);
+            self.remove_user_pool(user, pool_id);
+        } else {
+            Storage::put(storage.clone(), old_key, self.serialize_stake(old_stake));
+        }
+
+        let old_pool = StakingPool {
+            total_staked: old_pool.total_staked.checked_sub(&amount),
+            ..old_pool
+        };
+        Storage::put(storage.clone(), self.pool_prefix.concat(&pool_id.into_byte_string()), self.serialize_pool(old_pool));
+
+        let new_pool = StakingPool {
+            total_staked: new_pool.total_staked.checked_add(&amount),
+            ..new_pool
+        };
+        Storage::put(storage, self.pool_prefix.concat(&new_id.into_byte_string()), self.serialize_pool(new_pool));
+
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(new_id.into_any());
+        event_data.push(user.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("StakeMigrated"), event_data);
+
+        true
+    }
+
+    /// Get the successor of a migrated pool (0 if the pool was not migrated)
     #[method]
     #[safe]
-    pub fn is_emergency_enabled(&self) -> bool {
+    pub fn get_pool_successor(&self, pool_id: Int256) -> Int256 {
         let storage = Storage::get_context();
-        Storage::get(storage.clone(), self.emergency_key.clone()).is_some()
+        match Storage::get(storage, self.successor_prefix.concat(&pool_id.into_byte_string())) {
+            Some(id_bytes) => Int256::from_byte_string(id_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Change the reward emission of a pool; emission up to now accrues at the old rate
+    /// (owner or operator)
+    #[method]
+    pub fn set_reward_emission(&self, caller: H160, pool_id: Int256, reward_per_second: Int256) -> bool {
+        if !self.is_pool_admin(caller) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Not owner or operator"));
+            return false;
+        }
+
+        if reward_per_second < Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid reward emission"));
+            return false;
+        }
+
+        let pool = match self.get_pool(pool_id) {
+            Some(p) => p,
+            None => {
+                Runtime::log(ByteString::from_literal("Pool not found"));
+                return false;
+            }
+        };
+
+        let mut rewards = self.update_pool(pool_id, &pool);
+        rewards.reward_per_second = reward_per_second;
+        let rewards = self.accrue_rewards(&rewards, pool.total_staked, rewards.last_update_time);
+        self.store_pool_rewards(pool_id, &rewards);
+
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(reward_per_second.into_any());
+        Runtime::notify(ByteString::from_literal("RewardEmissionChanged"), event_data);
+
+        true
+    }
+
+    /// Accumulated rewards per staked token of a pool as of now, scaled by 1e12
+    #[method]
+    #[safe]
+    pub fn pool_acc_per_share(&self, pool_id: Int256) -> Int256 {
+        match self.get_pool(pool_id) {
+            Some(pool) => self.projected_pool_rewards(pool_id, &pool).acc_per_share,
+            None => Int256::zero(),
+        }
+    }
+
+    /// Get the reward accounting of a pool as of now
+    #[method]
+    #[safe]
+    pub fn get_pool_rewards(&self, pool_id: Int256) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
+        match self.get_pool(pool_id) {
+            Some(pool) => {
+                let rewards = self.projected_pool_rewards(pool_id, &pool);
+                result.put(ByteString::from_literal("acc_per_share"), rewards.acc_per_share.into_any());
+                result.put(ByteString::from_literal("acc_precision"), Int256::new(ACC_PRECISION).into_any());
+                result.put(ByteString::from_literal("last_update_time"), Int256::from_u64(rewards.last_update_time).into_any());
+                result.put(ByteString::from_literal("reward_per_second"), rewards.reward_per_second.into_any());
+                result.put(ByteString::from_literal("undistributed"), rewards.undistributed.into_any());
+                result.put(ByteString::from_literal("partial_claims"),
+                    if self.allows_partial_claims(pool_id) { Int256::one() } else { Int256::zero() }.into_any());
+                result.put(ByteString::from_literal("shortfall"), self.get_pool_shortfall(pool_id).into_any());
+            },
+            None => {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal("Pool not found").into_any());
+            }
+        }
+
+        result
+    }
+
+    /// Enable emergency withdrawal
+    #[method]
+    #[only_owner]
+    pub fn enable_emergency(&self) -> bool {
+        let storage = Storage::get_context();
+        Storage::put(storage.clone(), self.emergency_key.clone(), ByteString::from_literal("true"));
+
+        Runtime::notify(ByteString::from_literal("EmergencyEnabled"), Array::new());
+        true
     }
 
-    /// Check if contract is paused
+    /// Check if emergency withdrawal is enabled
     #[method]
     #[safe]
-    pub fn is_paused(&self) -> bool {
+    pub fn is_emergency_enabled(&self) -> bool {
         let storage = Storage::get_context();
-        Storage::get(storage.clone(), self.paused_key.clone()).is_some()
+        Storage::get(storage.clone(), self.emergency_key.clone()).is_some()
     }
 
     /// Get contract owner
@@ -599,12 +1197,136 @@ impl Staking {
 
     // Helper functions
 
-    fn is_owner(&self) -> bool {
-        let owner = self.get_owner();
-        if owner == H160::zero() {
+    /// Whether `account` holds the operator role
+    fn is_operator(&self, account: H160) -> bool {
+        Self::has_role(ByteString::from_literal(OPERATOR_ROLE), account)
+    }
+
+    /// Whether `caller` is the owner or an operator and witnessed the transaction.
+    /// Operators manage pools and fund rewards but can never move staked or reward funds out.
+    fn is_pool_admin(&self, caller: H160) -> bool {
+        (caller == self.get_owner() || self.is_operator(caller)) && Runtime::check_witness(caller)
+    }
+
+    /// Stake tokens the contract has just received from `user`
+    fn stake(&self, pool_id: Int256, pool: StakingPool, user: H160, amount: Int256) -> bool {
+        // Validate inputs
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid stake amount"));
+            return false;
+        }
+
+        // Check minimum stake
+        let min_stake = self.get_min_stake();
+        if amount < min_stake {
+            Runtime::log(ByteString::from_literal("Amount below minimum stake"));
+            return false;
+        }
+
+        // Check if paused
+        if Self::is_paused() {
+            Runtime::log(ByteString::from_literal("Staking is paused"));
+            return false;
+        }
+
+        if PoolStatus::from_u8(pool.status) != PoolStatus::Active {
+            Runtime::log(ByteString::from_literal("Pool is not accepting stakes"));
+            return false;
+        }
+
+        if !self.can_stake(pool_id, user) {
+            Runtime::log(ByteString::from_literal("Account does not meet the pool's stake gate"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let current_time = Runtime::get_time();
+
+        // Credit emission up to now at the old total before the stake changes it
+        let rewards = self.update_pool(pool_id, &pool);
+
+        // Get existing stake or create new one
+        let stake_key = self.get_stake_key(pool_id, user);
+        let mut user_stake = match Storage::get(storage.clone(), stake_key.clone()) {
+            Some(stake_data) => self.deserialize_stake(stake_data),
+            None => UserStake {
+                amount: Int256::zero(),
+                stake_time: current_time,
+                last_claim_time: current_time,
+                accumulated_rewards: Int256::zero(),
+                reward_debt: rewards.acc_per_share,
+            }
+        };
+
+        // Settle pending rewards before updating stake
+        self.settle_rewards(&mut user_stake, &rewards);
+
+        // Update stake
+        user_stake.amount = user_stake.amount.checked_add(&amount);
+        user_stake.last_claim_time = current_time;
+        if user_stake.stake_time == 0 {
+            user_stake.stake_time = current_time;
+        }
+
+        // Store updated stake
+        let serialized_stake = self.serialize_stake(user_stake);
+        Storage::put(storage.clone(), stake_key, serialized_stake);
+
+        // Update pool total
+        let updated_pool = StakingPool {
+            total_staked: pool.total_staked.checked_add(&amount),
+            ..pool
+        };
+        let pool_key = self.pool_prefix.concat(&pool_id.into_byte_string());
+        Storage::put(storage.clone(), pool_key, self.serialize_pool(updated_pool));
+
+        // Add pool to user's pool list
+        self.add_user_pool(user, pool_id);
+
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(user.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("TokensStaked"), event_data);
+
+        true
+    }
+
+    /// Fund a pool's rewards with reward tokens the contract has just received
+    fn add_rewards(&self, pool_id: Int256, pool: StakingPool, amount: Int256) -> bool {
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid reward amount"));
             return false;
         }
-        Runtime::check_witness(owner)
+
+        let storage = Storage::get_context();
+        let reward_balance_key = self.reward_balance_prefix.concat(&pool_id.into_byte_string());
+
+        let current_balance = self.get_reward_balance(pool_id);
+        let new_balance = current_balance.checked_add(&amount);
+        Storage::put(storage.clone(), reward_balance_key, new_balance.into_byte_string());
+
+        // Fund the budget after crediting past emission; without emission it is shared out now
+        let mut rewards = self.update_pool(pool_id, &pool);
+        rewards.undistributed = rewards.undistributed.checked_add(&amount);
+        let rewards = self.accrue_rewards(&rewards, pool.total_staked, rewards.last_update_time);
+        self.store_pool_rewards(pool_id, &rewards);
+
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("RewardsAdded"), event_data);
+
+        // Tell monitors when the balance can pay everything owed from partial claims
+        let shortfall = self.get_pool_shortfall(pool_id);
+        if shortfall > Int256::zero() && current_balance < shortfall && new_balance >= shortfall {
+            let mut event_data = Array::new();
+            event_data.push(pool_id.into_any());
+            event_data.push(shortfall.into_any());
+            Runtime::notify(ByteString::from_literal("RewardShortfallCovered"), event_data);
+        }
+
+        true
     }
 
     fn get_min_stake(&self) -> Int256 {
@@ -633,6 +1355,145 @@ impl Staking {
         }
     }
 
+    fn allows_partial_claims(&self, pool_id: Int256) -> bool {
+        let storage = Storage::get_context();
+        Storage::get(storage, self.partial_claims_prefix.concat(&pool_id.into_byte_string())).is_some()
+    }
+
+    /// Stake gate of a pool as (kind, registry, claim)
+    fn get_gate(&self, pool_id: Int256) -> Option<(StakeGate, H160, ByteString)> {
+        let storage = Storage::get_context();
+        let bytes = Storage::get(storage, self.stake_gate_prefix.concat(&pool_id.into_byte_string()))?.to_bytes();
+        let kind = StakeGate::from_u8(*bytes.first()?)?;
+        if bytes.len() < 21 {
+            return Some((kind, H160::zero(), ByteString::empty()));
+        }
+
+        let registry = H160::from_byte_string(ByteString::from_bytes(&bytes[1..21]));
+        let claim = ByteString::from_bytes(&bytes[21..]);
+        Some((kind, registry, claim))
+    }
+
+    fn get_allowlist_key(&self, pool_id: Int256, user: H160) -> ByteString {
+        self.allowlist_prefix
+            .concat(&pool_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&user.into_byte_string())
+    }
+
+    fn get_shortfall_key(&self, pool_id: Int256, user: H160) -> ByteString {
+        self.shortfall_prefix
+            .concat(&pool_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&user.into_byte_string())
+    }
+
+    fn get_int(&self, key: ByteString) -> Int256 {
+        match Storage::get(Storage::get_context(), key) {
+            Some(value_bytes) => Int256::from_byte_string(value_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Replaces the shortfall owed to `user` with `owed` and keeps the pool total in step
+    fn record_shortfall(&self, pool_id: Int256, user: H160, paid: Int256, owed: Int256) {
+        let previous = self.get_reward_shortfall(pool_id, user);
+        if previous.is_zero() && owed.is_zero() {
+            return;
+        }
+
+        let storage = Storage::get_context();
+        let shortfall_key = self.get_shortfall_key(pool_id, user);
+        if owed > Int256::zero() {
+            Storage::put(storage.clone(), shortfall_key, owed.into_byte_string());
+        } else {
+            Storage::delete(storage.clone(), shortfall_key);
+        }
+
+        let pool_total = self.get_pool_shortfall(pool_id).checked_sub(&previous).checked_add(&owed);
+        let pool_shortfall_key = self.pool_shortfall_prefix.concat(&pool_id.into_byte_string());
+        if pool_total > Int256::zero() {
+            Storage::put(storage, pool_shortfall_key, pool_total.into_byte_string());
+        } else {
+            Storage::delete(storage, pool_shortfall_key);
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(user.into_any());
+        if owed > Int256::zero() {
+            event_data.push(paid.into_any());
+            event_data.push(owed.into_any());
+            Runtime::notify(ByteString::from_literal("RewardShortfall"), event_data);
+        } else {
+            event_data.push(previous.into_any());
+            Runtime::notify(ByteString::from_literal("RewardShortfallSettled"), event_data);
+        }
+    }
+
+    fn transition_pool(&self, pool_id: Int256, from: PoolStatus, to: PoolStatus, event: &str) -> bool {
+        let pool = match self.get_pool(pool_id) {
+            Some(p) => p,
+            None => {
+                Runtime::log(ByteString::from_literal("Pool not found"));
+                return false;
+            }
+        };
+
+        if PoolStatus::from_u8(pool.status) != from {
+            Runtime::log(ByteString::from_literal("Invalid pool status for this transition"));
+            return false;
+        }
+
+        self.store_pool_status(pool_id, pool, to, event);
+        true
+    }
+
+    fn store_pool_status(&self, pool_id: Int256, pool: StakingPool, status: PoolStatus, event: &str) {
+        let updated_pool = StakingPool {
+            status: status.to_u8(),
+            ..pool
+        };
+        let storage = Storage::get_context();
+        Storage::put(storage, self.pool_prefix.concat(&pool_id.into_byte_string()), self.serialize_pool(updated_pool));
+
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        Runtime::notify(ByteString::from_literal(event), event_data);
+    }
+
+    fn transfer_token(&self, token: H160, to: H160, amount: Int256) -> bool {
+        if amount <= Int256::zero() {
+            return true;
+        }
+
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(to.into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+        Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, args)
+    }
+
+    /// Returns staked tokens of a pool, through `transfer_lp` when it stakes DEX LP tokens
+    fn transfer_stake(&self, pool_id: Int256, stake_token: H160, to: H160, amount: Int256) -> bool {
+        let lp_pool_id = self.get_lp_pool(pool_id);
+        if lp_pool_id == Int256::zero() {
+            return self.transfer_token(stake_token, to, amount);
+        }
+
+        if amount <= Int256::zero() {
+            return true;
+        }
+
+        let mut args = Array::new();
+        args.push(lp_pool_id.into_any());
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(to.into_any());
+        args.push(amount.into_any());
+        Contract::call_bool(stake_token, ByteString::from_literal("transfer_lp"), CallFlags::All, args)
+    }
+
     fn get_stake_key(&self, pool_id: Int256, user: H160) -> ByteString {
         self.stake_prefix
             .concat(&pool_id.into_byte_string())
@@ -640,24 +1501,141 @@ impl Staking {
             .concat(&user.into_byte_string())
     }
 
-    fn calculate_rewards(&self, pool: &StakingPool, stake: &UserStake, current_time: u64) -> Int256 {
-        if stake.amount <= Int256::zero() || current_time <= stake.last_claim_time {
+    /// Stakes of every user in a pool, read with a prefix scan of `stake_<pool_id>_`
+    fn pool_stakes(&self, pool_id: Int256) -> impl Iterator<Item = UserStake> + '_ {
+        let prefix = self.stake_prefix
+            .concat(&pool_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"));
+
+        // Longer pool ids sharing this prefix leave more than a 20-byte address after it
+        Storage::find_prefix(Storage::get_context(), prefix)
+            .remove_prefix()
+            .filter(|entry| entry.key.len() == 20)
+            .map(move |entry| self.deserialize_stake(entry.value))
+    }
+
+    /// Accounts holding a stake in a pool, read with a key scan of `stake_<pool_id>_`
+    fn pool_stakers(&self, pool_id: Int256) -> impl Iterator<Item = H160> {
+        let prefix = self.stake_prefix
+            .concat(&pool_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"));
+
+        Storage::find_prefix(Storage::get_context(), prefix)
+            .remove_prefix()
+            .keys()
+            .filter(|key| key.len() == 20)
+            .map(H160::from_byte_string)
+    }
+
+    /// Rewards earned by a stake since its last settlement: amount * (acc_per_share - reward_debt)
+    fn pending_rewards(&self, stake: &UserStake, rewards: &PoolRewards) -> Int256 {
+        if stake.amount <= Int256::zero() || rewards.acc_per_share <= stake.reward_debt {
             return Int256::zero();
         }
 
-        let time_diff = current_time - stake.last_claim_time;
-        let seconds_per_year = 31536000u64; // 365 * 24 * 60 * 60
+        stake.amount
+            .checked_mul(&rewards.acc_per_share.checked_sub(&stake.reward_debt))
+            .checked_div(&Int256::new(ACC_PRECISION))
+    }
+
+    /// Move pending rewards into the stake's unclaimed balance and reset its debt
+    fn settle_rewards(&self, stake: &mut UserStake, rewards: &PoolRewards) {
+        let pending = self.pending_rewards(stake, rewards);
+        stake.accumulated_rewards = stake.accumulated_rewards.checked_add(&pending);
+        stake.reward_debt = rewards.acc_per_share;
+    }
 
-        // Calculate rewards: (staked_amount * reward_rate * time_diff) / (10000 * seconds_per_year)
-        let rewards = stake.amount
-            .checked_mul(&Int256::new(pool.reward_rate as i64))
-            .checked_mul(&Int256::new(time_diff as i64))
-            .checked_div(&Int256::new(10000))
-            .checked_div(&Int256::new(seconds_per_year as i64));
+    /// Credit emission since the last update to the accumulator, bounded by the funded budget.
+    /// Nothing is emitted while the pool is empty, so the budget waits for the next staker.
+    fn accrue_rewards(&self, rewards: &PoolRewards, total_staked: Int256, now: u64) -> PoolRewards {
+        let mut updated = rewards.clone();
+        updated.last_update_time = now.max(rewards.last_update_time);
 
+        if total_staked <= Int256::zero() || rewards.undistributed <= Int256::zero() {
+            return updated;
+        }
+
+        let emitted = if rewards.reward_per_second.is_zero() {
+            rewards.undistributed
+        } else {
+            let due = rewards.reward_per_second
+                .checked_mul(&Int256::from_u64(elapsed(rewards.last_update_time, now)));
+            if due > rewards.undistributed { rewards.undistributed } else { due }
+        };
+
+        let increment = emitted
+            .checked_mul(&Int256::new(ACC_PRECISION))
+            .checked_div(&total_staked);
+        if increment.is_zero() {
+            // Too small to credit; keep it in the budget rather than losing it to rounding
+            return updated;
+        }
+
+        // Only the part the accumulator can represent leaves the budget
+        let credited = increment.checked_mul(&total_staked).checked_div(&Int256::new(ACC_PRECISION));
+        updated.acc_per_share = rewards.acc_per_share.checked_add(&increment);
+        updated.undistributed = rewards.undistributed.checked_sub(&credited);
+        updated
+    }
+
+    /// Accrue a pool's rewards up to now and store the result
+    fn update_pool(&self, pool_id: Int256, pool: &StakingPool) -> PoolRewards {
+        let rewards = self.projected_pool_rewards(pool_id, pool);
+        self.store_pool_rewards(pool_id, &rewards);
         rewards
     }
 
+    /// A pool's rewards accrued up to now, without storing them
+    fn projected_pool_rewards(&self, pool_id: Int256, pool: &StakingPool) -> PoolRewards {
+        let rewards = self.get_pool_rewards_record(pool_id);
+        self.accrue_rewards(&rewards, pool.total_staked, Runtime::get_time())
+    }
+
+    fn get_pool_rewards_record(&self, pool_id: Int256) -> PoolRewards {
+        let empty = PoolRewards {
+            acc_per_share: Int256::zero(),
+            last_update_time: Runtime::get_time(),
+            reward_per_second: Int256::zero(),
+            undistributed: Int256::zero(),
+        };
+
+        let storage = Storage::get_context();
+        let key = self.pool_rewards_prefix.concat(&pool_id.into_byte_string());
+        let bytes = match Storage::get(storage, key) {
+            Some(data) => data.to_bytes(),
+            None => return empty,
+        };
+
+        let mut offset = 0;
+        let last_update_time = match self.decode_u64(&bytes, &mut offset) {
+            Some(value) => value,
+            None => return empty,
+        };
+        match (
+            self.decode_int(&bytes, &mut offset),
+            self.decode_int(&bytes, &mut offset),
+            self.decode_int(&bytes, &mut offset),
+        ) {
+            (Some(acc_per_share), Some(reward_per_second), Some(undistributed)) => PoolRewards {
+                acc_per_share,
+                last_update_time,
+                reward_per_second,
+                undistributed,
+            },
+            _ => empty,
+        }
+    }
+
+    fn store_pool_rewards(&self, pool_id: Int256, rewards: &PoolRewards) {
+        let data = ByteString::from_bytes(&rewards.last_update_time.to_le_bytes())
+            .concat(&self.encode_int(rewards.acc_per_share))
+            .concat(&self.encode_int(rewards.reward_per_second))
+            .concat(&self.encode_int(rewards.undistributed));
+
+        let storage = Storage::get_context();
+        Storage::put(storage, self.pool_rewards_prefix.concat(&pool_id.into_byte_string()), data);
+    }
+
     fn add_user_pool(&self, user: H160, pool_id: Int256) {
         let storage = Storage::get_context();
         let user_pools_key = self.user_pools_prefix.concat(&user.into_byte_string());
@@ -724,100 +1702,96 @@ impl Staking {
     }
 
     fn serialize_pool(&self, pool: StakingPool) -> ByteString {
-        // Simplified serialization
-        let mut data = pool.stake_token.into_byte_string();
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&pool.reward_token.into_byte_string());
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&ByteString::from_bytes(&pool.reward_rate.to_le_bytes()));
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&ByteString::from_bytes(&pool.lock_period.to_le_bytes()));
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&ByteString::from_bytes(&pool.penalty_rate.to_le_bytes()));
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&pool.total_staked.into_byte_string());
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&ByteString::from_bytes(&[if pool.is_active { 1u8 } else { 0u8 }]));
-        data
+        serialize_to_bytestring(&pool).unwrap_or_else(|_| ByteString::empty())
     }
 
     fn deserialize_pool(&self, data: ByteString) -> StakingPool {
-        let bytes = data.to_bytes();
-        
-        if bytes.len() < 100 { // Minimum size check
-            return StakingPool {
-                stake_token: H160::zero(),
-                reward_token: H160::zero(),
-                reward_rate: 0,
-                lock_period: 0,
-                penalty_rate: 0,
-                total_staked: Int256::zero(),
-                is_active: false,
-            };
-        }
-        
-        let mut offset = 0;
-        
-        // Deserialize stake_token (20 bytes)
-        let stake_token = H160::from_byte_string(ByteString::from_bytes(&bytes[offset..offset + 20]));
-        offset += 20;
-
-        // Deserialize reward_token (20 bytes)
-        let reward_token = H160::from_byte_string(ByteString::from_bytes(&bytes[offset..offset + 20]));
-        offset += 20;
-
-        // Deserialize total_staked (32 bytes)
-        let total_staked = Int256::from_byte_string(ByteString::from_bytes(&bytes[offset..offset + 32]));
-        offset += 32;
-        
-        // Deserialize reward_rate (32 bytes)
-        let reward_rate = u32::from_le_bytes([bytes[offset], bytes[offset+1], bytes[offset+2], bytes[offset+3]]);
-        offset += 4;
-        
-        // Deserialize lock_period (8 bytes)
-        let lock_period = u64::from_le_bytes([bytes[offset], bytes[offset+1], bytes[offset+2], bytes[offset+3],
-            bytes[offset+4], bytes[offset+5], bytes[offset+6], bytes[offset+7]]);
-        offset += 8;
-        
-        // Deserialize penalty_rate (32 bytes)
-        let penalty_rate = u32::from_le_bytes([bytes[offset], bytes[offset+1], bytes[offset+2], bytes[offset+3]]);
-        offset += 4;
-        
-        // Deserialize is_active (1 byte)
-        let is_active = bytes[offset] != 0;
-        offset += 1;
-        
-        StakingPool {
-            stake_token,
-            reward_token,
-            reward_rate,
-            lock_period,
-            penalty_rate,
-            total_staked,
-            is_active,
-        }
+        deserialize_from_bytestring(&data).unwrap_or(StakingPool {
+            stake_token: H160::zero(),
+            reward_token: H160::zero(),
+            lock_period: 0,
+            penalty_rate: 0,
+            total_staked: Int256::zero(),
+            status: PoolStatus::Closed.to_u8(),
+        })
     }
 
     fn serialize_stake(&self, stake: UserStake) -> ByteString {
-        // Simplified serialization
-        let mut data = stake.amount.into_byte_string();
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&ByteString::from_bytes(&stake.stake_time.to_le_bytes()));
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&ByteString::from_bytes(&stake.last_claim_time.to_le_bytes()));
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&stake.accumulated_rewards.into_byte_string());
-        data
-    }
-
-    fn deserialize_stake(&self, __data: ByteString) -> UserStake {
-        // Simplified deserialization - in production, use proper parsing
-        UserStake {
+        ByteString::from_bytes(&[STAKE_LAYOUT_VERSION])
+            .concat(&self.encode_int(stake.amount))
+            .concat(&ByteString::from_bytes(&stake.stake_time.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&stake.last_claim_time.to_le_bytes()))
+            .concat(&self.encode_int(stake.accumulated_rewards))
+            .concat(&self.encode_int(stake.reward_debt))
+    }
+
+    fn deserialize_stake(&self, data: ByteString) -> UserStake {
+        let empty = UserStake {
             amount: Int256::zero(),
             stake_time: 0,
             last_claim_time: 0,
             accumulated_rewards: Int256::zero(),
+            reward_debt: Int256::zero(),
+        };
+
+        let bytes = data.to_bytes();
+        // Version 0 is unused; any later version starts with the version 1 fields
+        if bytes.is_empty() || bytes[0] == 0 {
+            return empty;
         }
+
+        let mut offset = 1;
+        let amount = match self.decode_int(&bytes, &mut offset) {
+            Some(value) => value,
+            None => return empty,
+        };
+        let stake_time = match self.decode_u64(&bytes, &mut offset) {
+            Some(value) => value,
+            None => return empty,
+        };
+        let last_claim_time = match self.decode_u64(&bytes, &mut offset) {
+            Some(value) => value,
+            None => return empty,
+        };
+        let accumulated_rewards = match self.decode_int(&bytes, &mut offset) {
+            Some(value) => value,
+            None => return empty,
+        };
+
+        // Version 1 stakes predate the accumulator, which starts at zero
+        let reward_debt = self.decode_int(&bytes, &mut offset).unwrap_or_else(Int256::zero);
+
+        UserStake { amount, stake_time, last_claim_time, accumulated_rewards, reward_debt }
+    }
+
+    /// One length byte followed by the byte string encoding of `value`
+    fn encode_int(&self, value: Int256) -> ByteString {
+        let encoded = value.into_byte_string();
+        ByteString::from_bytes(&[encoded.len() as u8]).concat(&encoded)
+    }
+
+    fn decode_int(&self, bytes: &[u8], offset: &mut usize) -> Option<Int256> {
+        let len = *bytes.get(*offset)? as usize;
+        let start = *offset + 1;
+        if len == 0 || len > 32 || start + len > bytes.len() {
+            return None;
+        }
+
+        *offset = start + len;
+        Some(Int256::from_byte_string(ByteString::from_bytes(&bytes[start..start + len])))
+    }
+
+    fn decode_u64(&self, bytes: &[u8], offset: &mut usize) -> Option<u64> {
+        let start = *offset;
+        if start + 8 > bytes.len() {
+            return None;
+        }
+
+        *offset = start + 8;
+        Some(u64::from_le_bytes([
+            bytes[start], bytes[start + 1], bytes[start + 2], bytes[start + 3],
+            bytes[start + 4], bytes[start + 5], bytes[start + 6], bytes[start + 7]
+        ]))
     }
 
     fn deserialize_user_pools(&self, data: ByteString) -> Array<Int256> {
@@ -856,3 +1830,17 @@ impl Staking {
         result
     }
 }
+
+/// Two-step ownership handoff (`transfer_ownership`, `accept_ownership`, `renounce_ownership`
+/// and `pending_owner`), see [`Ownable`].
+#[contract_impl]
+impl Ownable for Staking {}
+
+/// Owner-controlled emergency stop (`pause`, `unpause` and `is_paused`), see [`Pausable`].
+#[contract_impl]
+impl Pausable for Staking {}
+
+/// Operator management through [`OPERATOR_ROLE`] (`grant_role`, `revoke_role`, `renounce_role`
+/// and `has_role`), see [`AccessControl`].
+#[contract_impl]
+impl AccessControl for Staking {}
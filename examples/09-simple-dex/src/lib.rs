@@ -4,9 +4,18 @@
 //! - Liquidity pools with constant product formula (x * y = k)
 //! - Token swapping with slippage protection
 //! - Liquidity provision and removal with LP tokens
+//! - One pool per fee tier of a pair, with one-call liquidity migration between them
 //! - Fee collection and distribution to liquidity providers
+//! - Protocol fee share of each swap, withdrawable by the owner
+//! - Time-weighted average prices for other contracts (`consult`)
+//! - Reserve maintenance for tokens sent directly to the DEX (`sync`, `skim`)
+//! - Flash swaps verified against the constant product (see the flash borrower example)
 //! - Price impact calculation and MEV protection
-//! - Emergency pause and administrative controls
+//! - Best-route quotes across direct pools and 2-hop routes through intermediate tokens
+//! - Emergency pause, administrative controls and two-step ownership transfer
+//! - One-call liquidity mining via the Staking example (`zap_and_stake`)
+//! - Single-transaction deposits with NEP-17 permits (`add_liquidity_with_permit`)
+//! - Transferable LP positions valued in the pool's first token (used by the POL bonding example)
 //!
 //! This contract showcases fundamental DeFi mechanics for token exchange
 //! and liquidity management on EpicChain.
@@ -15,8 +24,33 @@
 #![no_main]
 
 use epicchain_contract::prelude::*;
+use epicchain_contract::serialize::{deserialize_from_bytestring, serialize_to_bytestring};
 use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
 
+/// Number of recent swaps kept per pool
+const SWAP_HISTORY_SIZE: u64 = 32;
+
+/// Fixed-point scale of recorded swap prices (8 decimals)
+const PRICE_PRECISION: i64 = 100_000_000;
+
+/// Minimum time between two stored price observations (30 minutes)
+const OBSERVATION_PERIOD: u64 = 1800;
+
+/// Number of price observations kept per pool, covering at least a day
+const OBSERVATION_HISTORY_SIZE: u64 = 48;
+
+/// Averaging window of the price used to value LP tokens in `get_lp_value` (1 hour)
+const LP_VALUE_WINDOW: u64 = 3600;
+
+/// Price accumulators wrap around at 2^ACCUMULATOR_BITS; only their differences are meaningful
+const ACCUMULATOR_BITS: u32 = 200;
+
+/// Maximum number of intermediate tokens considered for 2-hop quotes
+const MAX_ROUTE_TOKENS: u32 = 8;
+
+/// Size of a stored pool record (see `serialize_pool`)
+const POOL_RECORD_SIZE: usize = 20 + 20 + 32 * 3 + 4 + 1 + 32 * 2 + 8;
+
 /// Liquidity pool information
 #[derive(Clone)]
 pub struct LiquidityPool {
@@ -27,6 +61,9 @@ pub struct LiquidityPool {
     pub total_liquidity: Int256,
     pub fee_rate: u32, // Fee in basis points (e.g., 30 = 0.3%)
     pub is_active: bool,
+    pub price_a_cumulative: Int256, // sum of price_a_to_b * seconds, wrapping
+    pub price_b_cumulative: Int256, // sum of price_b_to_a * seconds, wrapping
+    pub last_price_update: u64,     // timestamp the accumulators were last advanced to
 }
 
 /// Liquidity provider position
@@ -38,11 +75,29 @@ pub struct LpPosition {
     pub timestamp: u64,
 }
 
+/// A swap kept in the per-pool history ring buffer
+#[derive(Clone, NeoSerialize)]
+pub struct SwapRecord {
+    pub token_in: H160,
+    pub amount_in: Int256,
+    pub amount_out: Int256,
+    pub price: Int256,          // amount_out per amount_in, scaled by PRICE_PRECISION
+    pub timestamp: u64,
+}
+
+/// Snapshot of a pool's price accumulators, kept in a per-pool ring buffer
+#[derive(Clone, NeoSerialize)]
+pub struct PriceObservation {
+    pub timestamp: u64,
+    pub price_a_cumulative: Int256,
+    pub price_b_cumulative: Int256,
+}
+
 /// Simple DEX contract with AMM functionality
 #[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
 #[contract_version("1.0.0")]
 #[contract_standards("")]
-#[contract_permission("*", "*")]
+#[contract_permission("*", "onNEP17Payment", "permit", "transfer", "transfer_from", "balanceOf", "onFlashSwap")]
 #[contract_meta("description", "Simple AMM-based decentralized exchange")]
 #[contract_meta("category", "DeFi")]
 pub struct SimpleDex {
@@ -54,16 +109,35 @@ pub struct SimpleDex {
     // Liquidity positions
     lp_position_prefix: ByteString,    // pool_id + provider -> position
     provider_pools_prefix: ByteString, // provider -> list of pool_ids
+    lp_balance_prefix: ByteString,     // pool_id + holder -> LP token balance (kept outside "lp_")
+
+    // Liquidity mining
+    staking_contract_key: ByteString,  // Staking contract receiving LP tokens
+    staking_pool_prefix: ByteString,   // pool_id -> staking pool_id
+
+    // Routing
+    route_tokens_key: ByteString,      // intermediate tokens for 2-hop quotes
+
+    // Swap history
+    swap_history_prefix: ByteString,   // pool_id + slot -> swap record
+    swap_count_prefix: ByteString,     // pool_id -> total number of swaps
+
+    // Price oracle
+    observation_prefix: ByteString,       // pool_id + slot -> price observation
+    observation_count_prefix: ByteString, // pool_id -> total number of observations
 
     // Administrative
     owner_key: ByteString,
-    paused_key: ByteString,
     min_liquidity_key: ByteString,     // minimum liquidity for new pools
     max_slippage_key: ByteString,      // maximum allowed slippage
 
     // Fee collection
-    protocol_fee_key: ByteString,      // protocol fee rate
+    protocol_fee_key: ByteString,      // protocol share of swap input, in basis points
     collected_fees_prefix: ByteString, // token -> collected fees
+
+    // Custody
+    reserved_prefix: ByteString,       // token -> reserves held for it across all pools
+    flash_lock_key: ByteString,        // present while a flash swap callback runs
 }
 
 #[contract_impl]
@@ -76,12 +150,21 @@ impl SimpleDex {
             token_pair_prefix: ByteString::from_literal("pair_"),
             lp_position_prefix: ByteString::from_literal("lp_"),
             provider_pools_prefix: ByteString::from_literal("provider_"),
+            lp_balance_prefix: ByteString::from_literal("lpbal_"),
+            staking_contract_key: ByteString::from_literal("staking_contract"),
+            staking_pool_prefix: ByteString::from_literal("staking_pool_"),
+            route_tokens_key: ByteString::from_literal("route_tokens"),
+            swap_history_prefix: ByteString::from_literal("swap_history_"),
+            swap_count_prefix: ByteString::from_literal("swap_count_"),
+            observation_prefix: ByteString::from_literal("price_observation_"),
+            observation_count_prefix: ByteString::from_literal("observation_count_"),
             owner_key: ByteString::from_literal("owner"),
-            paused_key: ByteString::from_literal("paused"),
             min_liquidity_key: ByteString::from_literal("min_liquidity"),
             max_slippage_key: ByteString::from_literal("max_slippage"),
             protocol_fee_key: ByteString::from_literal("protocol_fee"),
-            collected_fees_prefix: ByteString::from_literal("fees_"),
+            collected_fees_prefix: ByteString::from_literal("collected_fees_"),
+            reserved_prefix: ByteString::from_literal("reserved_"),
+            flash_lock_key: ByteString::from_literal("flash_lock"),
         }
     }
 
@@ -141,7 +224,7 @@ impl SimpleDex {
         fee_rate: u32
     ) -> Int256 {
         // Check if DEX is paused
-        if self.is_paused() {
+        if Self::is_paused() {
             Runtime::log(ByteString::from_literal("DEX is paused"));
             return Int256::new(-1);
         }
@@ -183,14 +266,19 @@ impl SimpleDex {
             (token_b, token_a, initial_b, initial_a)
         };
 
-        // Check if pool already exists
+        // Check if a pool already exists for this fee tier
         let pair_key = self.get_pair_key(token_a, token_b);
+        let fee_tier_key = self.get_fee_tier_key(token_a, token_b, fee_rate);
         let storage = Storage::get_context();
-        if Storage::get(storage.clone(), pair_key.clone()).is_some() {
+        if self.get_fee_tier_pool(token_a, token_b, fee_rate) >= Int256::zero() {
             Runtime::log(ByteString::from_literal("Pool already exists"));
             return Int256::new(-1);
         }
 
+        // Take custody of the initial deposit before crediting it as reserves
+        self.pull_tokens(token_a, creator, reserve_a);
+        self.pull_tokens(token_b, creator, reserve_b);
+
         // Generate pool ID
         let pool_count = self.get_pool_count();
         let pool_id = pool_count.checked_add(&Int256::one());
@@ -204,17 +292,26 @@ impl SimpleDex {
             total_liquidity: initial_liquidity,
             fee_rate,
             is_active: true,
+            price_a_cumulative: Int256::zero(),
+            price_b_cumulative: Int256::zero(),
+            last_price_update: Runtime::get_time(),
         };
 
         // Store pool
+        self.add_reserved(token_a, reserve_a);
+        self.add_reserved(token_b, reserve_b);
+        self.record_observation(pool_id, &pool);
         let pool_key = self.pool_prefix.concat(&pool_id.into_byte_string());
         Storage::put(storage.clone(), pool_key, self.serialize_pool(pool));
 
         // Update pool count
         Storage::put(storage.clone(), self.pool_count_key.clone(), pool_id.into_byte_string());
 
-        // Store pair mapping
-        Storage::put(storage.clone(), pair_key, pool_id.into_byte_string());
+        // Store pair mappings; the first pool of a pair serves token-addressed swaps and deposits
+        if Storage::get(storage.clone(), pair_key.clone()).is_none() {
+            Storage::put(storage.clone(), pair_key, pool_id.into_byte_string());
+        }
+        Storage::put(storage.clone(), fee_tier_key, pool_id.into_byte_string());
 
         // Create initial LP position for creator
         let lp_position = LpPosition {
@@ -226,6 +323,7 @@ impl SimpleDex {
 
         let lp_key = self.get_lp_position_key(pool_id, creator);
         Storage::put(storage.clone(), lp_key, self.serialize_lp_position(lp_position));
+        self.set_lp_balance(pool_id, creator, initial_liquidity);
 
         // Add to provider's pool list
         self.add_provider_pool(creator, pool_id);
@@ -254,7 +352,7 @@ impl SimpleDex {
         min_amount_out: Int256
     ) -> Int256 {
         // Check if DEX is paused
-        if self.is_paused() {
+        if Self::is_paused() {
             Runtime::log(ByteString::from_literal("DEX is paused"));
             return Int256::zero();
         }
@@ -276,6 +374,11 @@ impl SimpleDex {
             return Int256::zero();
         }
 
+        if self.is_locked() {
+            Runtime::log(ByteString::from_literal("Flash swap in progress"));
+            return Int256::zero();
+        }
+
         // Get pool
         let pool_id = match self.get_pool_for_pair(token_in, token_out) {
             Some(id) => id,
@@ -299,7 +402,7 @@ impl SimpleDex {
         }
 
         // Calculate swap amounts
-        let (amount_out, new_reserve_in, new_reserve_out) =
+        let (amount_out, new_reserve_in, new_reserve_out, protocol_fee) =
             self.calculate_swap_amounts(&pool, token_in, amount_in);
 
         // Check slippage protection
@@ -308,7 +411,11 @@ impl SimpleDex {
             return Int256::zero();
         }
 
+        self.pull_tokens(token_in, trader, amount_in);
+
         // Update pool reserves
+        self.update_price_accumulators(pool_id, &mut pool);
+        let (reserve_a, reserve_b) = (pool.reserve_a, pool.reserve_b);
         if token_in == pool.token_a {
             pool.reserve_a = new_reserve_in;
             pool.reserve_b = new_reserve_out;
@@ -316,15 +423,22 @@ impl SimpleDex {
             pool.reserve_b = new_reserve_in;
             pool.reserve_a = new_reserve_out;
         }
+        self.assert_k_invariant(reserve_a, reserve_b, &pool);
 
         // Store updated pool
         let storage = Storage::get_context();
         let pool_key = self.pool_prefix.concat(&pool_id.into_byte_string());
         Storage::put(storage.clone(), pool_key, self.serialize_pool(pool));
 
-        // Complete implementation for token transfers
-        // transfer token_in from trader to pool
-        // transfer token_out from pool to trader
+        self.add_reserved(token_in, amount_in.checked_sub(&protocol_fee));
+        self.add_reserved(token_out, amount_out.checked_neg());
+        if protocol_fee > Int256::zero() {
+            self.accrue_protocol_fee(token_in, protocol_fee);
+        }
+
+        assert_with_code(self.send_tokens(token_out, trader, amount_out), ErrorCode::CallFailed, "Swap transfer failed");
+
+        self.record_swap(pool_id, token_in, amount_in, amount_out);
 
         // Emit event
         let mut event_data = Array::new();
@@ -343,212 +457,1573 @@ impl SimpleDex {
     #[method]
     pub fn add_liquidity(
         &self,
-        _provider: H160,
-        _token_a: H160,
-        _token_b: H160,
-        _amount_a: Int256,
-        _amount_b: Int256,
-        _min_liquidity: Int256
+        provider: H160,
+        token_a: H160,
+        token_b: H160,
+        amount_a: Int256,
+        amount_b: Int256,
+        min_liquidity: Int256
     ) -> Int256 {
-        // Implementation similar to create_pool but for existing pools
-        Runtime::log(ByteString::from_literal("Add liquidity not fully implemented"));
-        Int256::zero()
-    }
+        if Self::is_paused() {
+            Runtime::log(ByteString::from_literal("DEX is paused"));
+            return Int256::zero();
+        }
 
-    /// Remove liquidity from a pool
-    #[method]
-    pub fn remove_liquidity(
-        &self,
-        _provider: H160,
-        _pool_id: Int256,
-        _liquidity_amount: Int256,
-        _min_amount_a: Int256,
-        _min_amount_b: Int256
-    ) -> bool {
-        // Implementation for liquidity removal
-        Runtime::log(ByteString::from_literal("Remove liquidity not fully implemented"));
-        false
+        if !Runtime::check_witness(provider) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return Int256::zero();
+        }
+
+        let deposit = |token: H160, amount: Int256| self.pull_tokens(token, provider, amount);
+        match self.mint_liquidity(provider, token_a, token_b, amount_a, amount_b, min_liquidity, &deposit) {
+            Some((_, liquidity)) => liquidity,
+            None => Int256::zero(),
+        }
     }
 
-    /// Get pool information
+    /// Add liquidity in a single transaction using signed NEP-17 permits for both tokens.
+    ///
+    /// Each permit lets the DEX pull the deposit with `transfer_from`, so the provider
+    /// does not need prior `approve` calls. The provider is the standard account of
+    /// `provider_pubkey`, the key the permits are signed with. Any failed pull aborts
+    /// the whole transaction.
     #[method]
-    #[safe]
-    pub fn get_pool(&self, pool_id: Int256) -> Map<ByteString, Any> {
-        let mut result = Map::new();
-
-        match self.get_pool_data(pool_id) {
-            Some(pool) => {
-                result.put(ByteString::from_literal("token_a"), pool.token_a.into_any());
-                result.put(ByteString::from_literal("token_b"), pool.token_b.into_any());
-                result.put(ByteString::from_literal("reserve_a"), pool.reserve_a.into_any());
-                result.put(ByteString::from_literal("reserve_b"), pool.reserve_b.into_any());
-                result.put(ByteString::from_literal("total_liquidity"), pool.total_liquidity.into_any());
-                result.put(ByteString::from_literal("fee_rate"), Int256::new(pool.fee_rate as i64).into_any());
-                result.put(ByteString::from_literal("is_active"),
-                    if pool.is_active { Int256::one() } else { Int256::zero() }.into_any());
+    pub fn add_liquidity_with_permit(
+        &self,
+        provider_pubkey: PublicKey,
+        token_a: H160,
+        token_b: H160,
+        amount_a: Int256,
+        amount_b: Int256,
+        min_liquidity: Int256,
+        deadline: u64,
+        signature_a: ByteString,
+        signature_b: ByteString
+    ) -> Int256 {
+        if Self::is_paused() {
+            Runtime::log(ByteString::from_literal("DEX is paused"));
+            return Int256::zero();
+        }
 
-                // Calculate current price
-                let price_a_to_b = if pool.reserve_a > Int256::zero() {
-                    pool.reserve_b.checked_div(&pool.reserve_a)
-                } else {
-                    Int256::zero()
-                };
-                result.put(ByteString::from_literal("price_a_to_b"), price_a_to_b.into_any());
-            },
-            None => {
-                result.put(ByteString::from_literal("error"), ByteString::from_literal("Pool not found").into_any());
-            }
+        if Runtime::get_time() > deadline {
+            Runtime::log(ByteString::from_literal("Permit expired"));
+            return Int256::zero();
         }
 
-        result
+        // The signatures authorize the provider's deposit, so the transaction may be relayed
+        let provider = create_standard_account(provider_pubkey.clone());
+        let deposit = |token: H160, amount: Int256| {
+            let signature = if token == token_a { signature_a.clone() } else { signature_b.clone() };
+            self.pull_with_permit(token, &provider_pubkey, amount, deadline, signature);
+        };
+        match self.mint_liquidity(provider, token_a, token_b, amount_a, amount_b, min_liquidity, &deposit) {
+            Some((_, liquidity)) => liquidity,
+            None => Int256::zero(),
+        }
     }
 
-    /// Get quote for a swap
+    /// Add liquidity and stake the minted LP tokens in the configured Staking contract
     #[method]
-    #[safe]
-    pub fn get_swap_quote(
+    pub fn zap_and_stake(
         &self,
-        token_in: H160,
-        token_out: H160,
-        amount_in: Int256
-    ) -> Map<ByteString, Any> {
-        let mut result = Map::new();
+        provider: H160,
+        token_a: H160,
+        token_b: H160,
+        amount_a: Int256,
+        amount_b: Int256,
+        min_liquidity: Int256
+    ) -> Int256 {
+        if Self::is_paused() {
+            Runtime::log(ByteString::from_literal("DEX is paused"));
+            return Int256::zero();
+        }
 
-        if let Some(pool_id) = self.get_pool_for_pair(token_in, token_out) {
-            if let Some(pool) = self.get_pool_data(pool_id) {
-                let (amount_out, _, _) = self.calculate_swap_amounts(&pool, token_in, amount_in);
-                let price_impact = self.calculate_price_impact(&pool, token_in, amount_in);
+        if !Runtime::check_witness(provider) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return Int256::zero();
+        }
 
-                result.put(ByteString::from_literal("amount_out"), amount_out.into_any());
-                result.put(ByteString::from_literal("price_impact"), price_impact.into_any());
-                result.put(ByteString::from_literal("pool_id"), pool_id.into_any());
-            } else {
-                result.put(ByteString::from_literal("error"), ByteString::from_literal("Pool data not found").into_any());
+        let staking_contract = self.get_staking_contract();
+        if staking_contract == H160::zero() {
+            Runtime::log(ByteString::from_literal("Staking contract not configured"));
+            return Int256::zero();
+        }
+
+        let pool_id = match self.get_pool_for_pair(token_a, token_b) {
+            Some(id) => id,
+            None => {
+                Runtime::log(ByteString::from_literal("Pool not found"));
+                return Int256::zero();
             }
-        } else {
-            result.put(ByteString::from_literal("error"), ByteString::from_literal("Pool not found").into_any());
+        };
+
+        let staking_pool_id = match self.get_staking_pool(pool_id) {
+            Some(id) => id,
+            None => {
+                Runtime::log(ByteString::from_literal("No staking pool for LP token"));
+                return Int256::zero();
+            }
+        };
+
+        let deposit = |token: H160, amount: Int256| self.pull_tokens(token, provider, amount);
+        let liquidity = match self.mint_liquidity(provider, token_a, token_b, amount_a, amount_b, min_liquidity, &deposit) {
+            Some((_, liquidity)) => liquidity,
+            None => return Int256::zero(),
+        };
+
+        // Move the freshly minted LP tokens into the Staking contract's custody
+        let provider_balance = self.get_lp_balance(pool_id, provider);
+        self.set_lp_balance(pool_id, provider, provider_balance.checked_sub(&liquidity));
+        let staking_balance = self.get_lp_balance(pool_id, staking_contract);
+        self.set_lp_balance(pool_id, staking_contract, staking_balance.checked_add(&liquidity));
+
+        let mut transfer_data = Array::new();
+        transfer_data.push(pool_id.into_any());
+        transfer_data.push(provider.into_any());
+        transfer_data.push(staking_contract.into_any());
+        transfer_data.push(liquidity.into_any());
+        Runtime::notify(ByteString::from_literal("LpTransfer"), transfer_data);
+
+        // Report the payment like a NEP-17 token would, with the DEX as the staking pool's stake
+        // token, so the stake is credited to the provider, who keeps ownership of it. The staking
+        // pool returns the LP tokens through `transfer_lp` once `set_lp_pool` names this pool
+        let mut args = Array::new();
+        args.push(provider.into_any());
+        args.push(liquidity.into_any());
+        args.push(staking_pool_id.into_any());
+        if !Contract::call_bool(staking_contract, ByteString::from_literal("onNEP17Payment"), CallFlags::All, args) {
+            abort_with_message(ByteString::from_literal("Staking rejected the LP tokens"));
         }
 
-        result
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(staking_pool_id.into_any());
+        event_data.push(provider.into_any());
+        event_data.push(liquidity.into_any());
+        Runtime::notify(ByteString::from_literal("LiquidityZapped"), event_data);
+
+        liquidity
     }
 
-    /// Check if DEX is paused
+    /// Set the Staking contract used by `zap_and_stake` (owner only)
     #[method]
-    #[safe]
-    pub fn is_paused(&self) -> bool {
+    #[only_owner]
+    pub fn set_staking_contract(&self, staking_contract: H160) -> bool {
         let storage = Storage::get_context();
-        Storage::get(storage.clone(), self.paused_key.clone()).is_some()
+        put_changed(storage, self.staking_contract_key.clone(), "StakingContractChanged", staking_contract);
+        true
     }
 
-    /// Get DEX owner
+    /// Map a DEX pool to the staking pool that accepts its LP tokens (owner only)
     #[method]
-    #[safe]
-    pub fn get_owner(&self) -> H160 {
-        let storage = Storage::get_context();
-        match Storage::get(storage.clone(), self.owner_key.clone()) {
-            Some(owner_bytes) => H160::from_byte_string(owner_bytes),
-            None => H160::zero(),
+    #[only_owner]
+    pub fn set_staking_pool(&self, pool_id: Int256, staking_pool_id: Int256) -> bool {
+        if self.get_pool_data(pool_id).is_none() {
+            Runtime::log(ByteString::from_literal("Pool not found"));
+            return false;
         }
+
+        let storage = Storage::get_context();
+        let staking_pool_key = self.staking_pool_prefix.concat(&pool_id.into_byte_string());
+        Storage::put(storage, staking_pool_key, staking_pool_id.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(staking_pool_id.into_any());
+        Runtime::notify(ByteString::from_literal("StakingPoolSet"), event_data);
+        true
     }
 
-    /// Get pool count
+    /// Send the protocol fees collected in `token` to `to` (owner only).
+    ///
+    /// Returns the amount withdrawn, or zero when nothing was collected, the DEX holds less
+    /// than it owes in `token`, or the transfer failed.
     #[method]
-    #[safe]
-    pub fn get_pool_count(&self) -> Int256 {
-        let storage = Storage::get_context();
-        match Storage::get(storage.clone(), self.pool_count_key.clone()) {
-            Some(count_bytes) => Int256::from_byte_string(count_bytes),
-            None => Int256::zero(),
+    #[only_owner]
+    pub fn withdraw_protocol_fees(&self, token: H160, to: H160) -> Int256 {
+        if to == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid recipient"));
+            return Int256::zero();
         }
-    }
 
-    // Helper functions
+        if self.is_locked() {
+            Runtime::log(ByteString::from_literal("Flash swap in progress"));
+            return Int256::zero();
+        }
 
-    fn is_owner(&self) -> bool {
-        let owner = self.get_owner();
-        if owner == H160::zero() {
-            return false;
+        let amount = self.get_collected_fees(token);
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No protocol fees collected"));
+            return Int256::zero();
         }
-        Runtime::check_witness(owner)
-    }
 
-    fn get_min_liquidity(&self) -> Int256 {
+        // Fees are only paid out of a balance that also covers every pool's reserves
+        if !self.is_backed(token) {
+            Runtime::log(ByteString::from_literal("Reserves not backed by DEX balance"));
+            return Int256::zero();
+        }
+
+        // Clear the balance before the external call so a re-entrant withdrawal finds nothing
         let storage = Storage::get_context();
-        match Storage::get(storage.clone(), self.min_liquidity_key.clone()) {
-            Some(min_bytes) => Int256::from_byte_string(min_bytes),
-            None => Int256::new(1000), // Default minimum
+        let fees_key = self.collected_fees_prefix.concat(&token.into_byte_string());
+        Storage::delete(storage.clone(), fees_key.clone());
+
+        if !self.send_tokens(token, to, amount) {
+            Storage::put(storage, fees_key, amount.into_byte_string());
+            Runtime::log(ByteString::from_literal("Protocol fee transfer failed"));
+            return Int256::zero();
         }
-    }
 
-    fn calculate_initial_liquidity(&self, amount_a: Int256, amount_b: Int256) -> Int256 {
-        // Simple geometric mean for initial liquidity
-        // Complete implementation using proper square root calculation
-        amount_a.checked_add(&amount_b).checked_div(&Int256::new(2))
-    }
+        let mut event_data = Array::new();
+        event_data.push(token.into_any());
+        event_data.push(to.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("ProtocolFeesWithdrawn"), event_data);
 
-    fn get_pair_key(&self, token_a: H160, token_b: H160) -> ByteString {
-        self.token_pair_prefix
-            .concat(&token_a.into_byte_string())
-            .concat(&ByteString::from_literal("_"))
-            .concat(&token_b.into_byte_string())
+        amount
     }
 
-    fn get_lp_position_key(&self, pool_id: Int256, provider: H160) -> ByteString {
-        self.lp_position_prefix
-            .concat(&pool_id.into_byte_string())
-            .concat(&ByteString::from_literal("_"))
-            .concat(&provider.into_byte_string())
-    }
+    /// Realign a pool's reserves with the tokens the DEX actually holds (owner only).
+    ///
+    /// The DEX keeps every pool's tokens in one account, so a surplus or shortfall of a token
+    /// (direct transfers, rebasing or fee-on-transfer tokens) cannot be traced to a pool and
+    /// is assigned to the one being synced. Reserves never drop below zero.
+    #[method]
+    #[only_owner]
+    pub fn sync(&self, pool_id: Int256) -> bool {
+        if self.is_locked() {
+            Runtime::log(ByteString::from_literal("Flash swap in progress"));
+            return false;
+        }
+
+        let mut pool = match self.get_pool_data(pool_id) {
+            Some(pool) => pool,
+            None => {
+                Runtime::log(ByteString::from_literal("Pool not found"));
+                return false;
+            }
+        };
+
+        self.update_price_accumulators(pool_id, &mut pool);
+        let new_reserve_a = math::max(pool.reserve_a.checked_add(&self.unreserved_balance(pool.token_a)), Int256::zero());
+        let new_reserve_b = math::max(pool.reserve_b.checked_add(&self.unreserved_balance(pool.token_b)), Int256::zero());
+        self.add_reserved(pool.token_a, new_reserve_a.checked_sub(&pool.reserve_a));
+        self.add_reserved(pool.token_b, new_reserve_b.checked_sub(&pool.reserve_b));
+        pool.reserve_a = new_reserve_a;
+        pool.reserve_b = new_reserve_b;
 
-    fn get_pool_data(&self, pool_id: Int256) -> Option<LiquidityPool> {
         let storage = Storage::get_context();
         let pool_key = self.pool_prefix.concat(&pool_id.into_byte_string());
+        Storage::put(storage, pool_key, self.serialize_pool(pool));
 
-        match Storage::get(storage.clone(), pool_key) {
-            Some(pool_data) => Some(self.deserialize_pool(pool_data)),
-            None => None,
-        }
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(new_reserve_a.into_any());
+        event_data.push(new_reserve_b.into_any());
+        Runtime::notify(ByteString::from_literal("Sync"), event_data);
+        true
     }
 
-    fn get_pool_for_pair(&self, token_a: H160, token_b: H160) -> Option<Int256> {
-        let storage = Storage::get_context();
+    /// Send the DEX's holdings of a pool's tokens beyond all reserves and fees to `to`.
+    ///
+    /// Returns false if a transfer failed; tokens without a surplus are skipped.
+    #[method]
+    pub fn skim(&self, pool_id: Int256, to: H160) -> bool {
+        if self.is_locked() {
+            Runtime::log(ByteString::from_literal("Flash swap in progress"));
+            return false;
+        }
 
-        // Try both orderings
-        let pair_key1 = self.get_pair_key(token_a, token_b);
-        if let Some(pool_id_bytes) = Storage::get(storage.clone(), pair_key1) {
-            return Some(Int256::from_byte_string(pool_id_bytes));
+        let pool = match self.get_pool_data(pool_id) {
+            Some(pool) => pool,
+            None => {
+                Runtime::log(ByteString::from_literal("Pool not found"));
+                return false;
+            }
+        };
+
+        if to == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid recipient"));
+            return false;
         }
 
-        let pair_key2 = self.get_pair_key(token_b, token_a);
-        if let Some(pool_id_bytes) = Storage::get(storage.clone(), pair_key2) {
-            return Some(Int256::from_byte_string(pool_id_bytes));
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(to.into_any());
+
+        // Each surplus is measured right before it is sent, so a re-entrant skim finds nothing left
+        for token in [pool.token_a, pool.token_b] {
+            let excess = math::max(self.unreserved_balance(token), Int256::zero());
+            if excess > Int256::zero() && !self.send_tokens(token, to, excess) {
+                Runtime::log(ByteString::from_literal("Skim transfer failed"));
+                return false;
+            }
+            event_data.push(excess.into_any());
         }
 
-        None
+        Runtime::notify(ByteString::from_literal("Skim"), event_data);
+        true
     }
 
-    fn calculate_swap_amounts(&self, pool: &LiquidityPool, token_in: H160, amount_in: Int256) -> (Int256, Int256, Int256) {
-        let (reserve_in, reserve_out) = if token_in == pool.token_a {
-            (pool.reserve_a, pool.reserve_b)
-        } else {
-            (pool.reserve_b, pool.reserve_a)
-        };
-
-        // Apply fee
-        let fee_amount = amount_in.checked_mul(&Int256::new(pool.fee_rate as i64)).checked_div(&Int256::new(10000));
-        let amount_in_after_fee = amount_in.checked_sub(&fee_amount);
+    /// Lend a pool's tokens to `receiver` for the duration of an `onFlashSwap` callback.
+    ///
+    /// The callback receives (initiator, pool_id, token_a, token_b, amount_a, amount_b, fee_rate,
+    /// data) and must return true. By the time it returns, the tokens sent back must restore the
+    /// constant product after charging the pool fee on them, as for a swap; otherwise the whole
+    /// transaction aborts and the loan never happened. Repaying in the other token makes it a swap.
+    #[method]
+    pub fn flash_swap(
+        &self,
+        pool_id: Int256,
+        amount_a: Int256,
+        amount_b: Int256,
+        receiver: H160,
+        data: Any
+    ) -> bool {
+        if Self::is_paused() {
+            Runtime::log(ByteString::from_literal("DEX is paused"));
+            return false;
+        }
 
-        // Constant product formula: (x + dx) * (y - dy) = x * y
-        // dy = y * dx / (x + dx)
-        let numerator = reserve_out.checked_mul(&amount_in_after_fee);
-        let denominator = reserve_in.checked_add(&amount_in_after_fee);
-        let amount_out = numerator.checked_div(&denominator);
+        if self.is_locked() {
+            Runtime::log(ByteString::from_literal("Flash swap in progress"));
+            return false;
+        }
 
-        let new_reserve_in = reserve_in.checked_add(&amount_in);
-        let new_reserve_out = reserve_out.checked_sub(&amount_out);
+        let mut pool = match self.get_pool_data(pool_id) {
+            Some(pool) => pool,
+            None => {
+                Runtime::log(ByteString::from_literal("Pool not found"));
+                return false;
+            }
+        };
 
-        (amount_out, new_reserve_in, new_reserve_out)
-    }
+        if !pool.is_active {
+            Runtime::log(ByteString::from_literal("Pool is not active"));
+            return false;
+        }
+
+        if amount_a < Int256::zero() || amount_b < Int256::zero() || (amount_a.is_zero() && amount_b.is_zero())
+            || amount_a >= pool.reserve_a || amount_b >= pool.reserve_b {
+            Runtime::log(ByteString::from_literal("Invalid flash swap amounts"));
+            return false;
+        }
+
+        let dex = Runtime::get_executing_script_hash();
+        let balance_a = self.token_balance(pool.token_a, dex);
+        let balance_b = self.token_balance(pool.token_b, dex);
+
+        let storage = Storage::get_context();
+        Storage::put(storage.clone(), self.flash_lock_key.clone(), ByteString::from_literal("true"));
+
+        // Optimistically send the tokens, then hand control to the receiver
+        for (token, amount) in [(pool.token_a, amount_a), (pool.token_b, amount_b)] {
+            if amount > Int256::zero() {
+                assert_with_code(self.send_tokens(token, receiver, amount), ErrorCode::CallFailed, "Flash swap transfer failed");
+            }
+        }
+
+        let mut callback_args = Array::new();
+        callback_args.push(Runtime::get_calling_script_hash().into_any());
+        callback_args.push(pool_id.into_any());
+        callback_args.push(pool.token_a.into_any());
+        callback_args.push(pool.token_b.into_any());
+        callback_args.push(amount_a.into_any());
+        callback_args.push(amount_b.into_any());
+        callback_args.push(Int256::new(pool.fee_rate as i64).into_any());
+        callback_args.push(data);
+        assert_with_code(
+            Contract::call_bool(receiver, ByteString::from_literal("onFlashSwap"), CallFlags::All, callback_args),
+            ErrorCode::CallFailed,
+            "Flash swap callback failed"
+        );
+
+        Storage::delete(storage.clone(), self.flash_lock_key.clone());
+
+        // Whatever came back on top of the remaining balance is the input of the swap
+        let returned_a = math::max(self.token_balance(pool.token_a, dex).checked_sub(&balance_a.checked_sub(&amount_a)), Int256::zero());
+        let returned_b = math::max(self.token_balance(pool.token_b, dex).checked_sub(&balance_b.checked_sub(&amount_b)), Int256::zero());
+        let new_reserve_a = pool.reserve_a.checked_sub(&amount_a).checked_add(&returned_a);
+        let new_reserve_b = pool.reserve_b.checked_sub(&amount_b).checked_add(&returned_b);
+
+        // (x' * 10000 - in_x * fee) * (y' * 10000 - in_y * fee) >= x * y * 10000^2
+        let basis_points = Int256::new(10000);
+        let fee_rate = Int256::new(pool.fee_rate as i64);
+        let adjusted_a = new_reserve_a.checked_mul(&basis_points).checked_sub(&returned_a.checked_mul(&fee_rate));
+        let adjusted_b = new_reserve_b.checked_mul(&basis_points).checked_sub(&returned_b.checked_mul(&fee_rate));
+        let k_before = pool.reserve_a.checked_mul(&pool.reserve_b).checked_mul(&basis_points).checked_mul(&basis_points);
+        assert_with_code(
+            adjusted_a.checked_mul(&adjusted_b) >= k_before,
+            ErrorCode::InvalidState,
+            "Flash swap not repaid"
+        );
+
+        self.update_price_accumulators(pool_id, &mut pool);
+        let (reserve_a, reserve_b) = (pool.reserve_a, pool.reserve_b);
+        self.add_reserved(pool.token_a, new_reserve_a.checked_sub(&reserve_a));
+        self.add_reserved(pool.token_b, new_reserve_b.checked_sub(&reserve_b));
+        pool.reserve_a = new_reserve_a;
+        pool.reserve_b = new_reserve_b;
+        self.assert_k_invariant(reserve_a, reserve_b, &pool);
+
+        let pool_key = self.pool_prefix.concat(&pool_id.into_byte_string());
+        Storage::put(storage, pool_key, self.serialize_pool(pool));
+
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(receiver.into_any());
+        event_data.push(amount_a.into_any());
+        event_data.push(amount_b.into_any());
+        event_data.push(returned_a.into_any());
+        event_data.push(returned_b.into_any());
+        Runtime::notify(ByteString::from_literal("FlashSwap"), event_data);
+        true
+    }
+
+    /// NEP-17 receiver callback.
+    ///
+    /// Tokens arrive through the deposits the DEX pulls, flash swap repayments and plain
+    /// transfers. Reserves only change in the methods that account for them, so anything else
+    /// is surplus for `sync` or `skim`.
+    #[method]
+    #[wasm_export(name = "onNEP17Payment")]
+    pub fn on_nep17_payment(&self, _from: H160, _amount: Int256, _data: Any) -> bool {
+        true
+    }
+
+    /// Get the protocol fees collected in a token and not yet withdrawn
+    #[method]
+    #[safe]
+    pub fn get_collected_fees(&self, token: H160) -> Int256 {
+        let storage = Storage::get_context();
+        let fees_key = self.collected_fees_prefix.concat(&token.into_byte_string());
+        match Storage::get(storage, fees_key) {
+            Some(fees_bytes) => Int256::from_byte_string(fees_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Get the LP token balance of a holder in a pool
+    #[method]
+    #[safe]
+    pub fn get_lp_balance(&self, pool_id: Int256, holder: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.get_lp_balance_key(pool_id, holder)) {
+            Some(balance_bytes) => Int256::from_byte_string(balance_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Transfer LP tokens of a pool to another holder
+    #[method]
+    pub fn transfer_lp(&self, pool_id: Int256, from: H160, to: H160, amount: Int256) -> bool {
+        if !Runtime::check_witness(from) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        if to == H160::zero() || amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid LP transfer"));
+            return false;
+        }
+
+        let from_balance = self.get_lp_balance(pool_id, from);
+        if from_balance < amount {
+            Runtime::log(ByteString::from_literal("Insufficient LP balance"));
+            return false;
+        }
+
+        if from != to {
+            self.set_lp_balance(pool_id, from, from_balance.checked_sub(&amount));
+            let to_balance = self.get_lp_balance(pool_id, to);
+            self.set_lp_balance(pool_id, to, to_balance.checked_add(&amount));
+        }
+
+        let mut transfer_data = Array::new();
+        transfer_data.push(pool_id.into_any());
+        transfer_data.push(from.into_any());
+        transfer_data.push(to.into_any());
+        transfer_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("LpTransfer"), transfer_data);
+
+        true
+    }
+
+    /// Value of `amount` LP tokens in units of the pool's `token_a`.
+    ///
+    /// The pool is worth `2 * sqrt(k * p)`, with `k` the product of the reserves and `p` the
+    /// price of `token_b` averaged over LP_VALUE_WINDOW. A swap moves the reserves along `k`
+    /// without changing that value, so unlike the reserves it cannot be skewed within a
+    /// transaction. Zero while the pool has less than LP_VALUE_WINDOW of price history.
+    #[method]
+    #[safe]
+    pub fn get_lp_value(&self, pool_id: Int256, amount: Int256) -> Int256 {
+        let pool = match self.get_pool_data(pool_id) {
+            Some(pool) if pool.total_liquidity > Int256::zero() && amount > Int256::zero() => pool,
+            _ => return Int256::zero(),
+        };
+
+        let price_b_to_a = match self.average_prices(pool_id, &pool, LP_VALUE_WINDOW) {
+            Some((_, price_b_to_a, _)) => price_b_to_a,
+            None => return Int256::zero(),
+        };
+
+        let k = pool.reserve_a.checked_mul(&pool.reserve_b);
+        let pool_value = math::isqrt(math::mul_div(k, price_b_to_a, Int256::new(PRICE_PRECISION))).checked_mul(&Int256::new(2));
+        math::mul_div(amount, pool_value, pool.total_liquidity)
+    }
+
+    /// Get the Staking contract used for liquidity mining
+    #[method]
+    #[safe]
+    pub fn get_staking_contract(&self) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.staking_contract_key.clone()) {
+            Some(contract_bytes) => H160::from_byte_string(contract_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    /// Remove liquidity from a pool
+    #[method]
+    pub fn remove_liquidity(
+        &self,
+        _provider: H160,
+        _pool_id: Int256,
+        _liquidity_amount: Int256,
+        _min_amount_a: Int256,
+        _min_amount_b: Int256
+    ) -> bool {
+        // Implementation for liquidity removal
+        Runtime::log(ByteString::from_literal("Remove liquidity not fully implemented"));
+        false
+    }
+
+    /// Move liquidity between two pools of the same pair, e.g. to another fee tier.
+    ///
+    /// Burns `liquidity` LP tokens of `from_pool` and deposits the withdrawn tokens into
+    /// `to_pool` at its current price; whatever does not fit that price is returned to the
+    /// provider. `min_amount_a` and `min_amount_b` bound the withdrawn amounts, in the pools'
+    /// token order. Fails while the DEX holds less of either token than the pools and
+    /// collected fees account for. Returns the LP tokens minted in `to_pool`, or zero on failure.
+    #[method]
+    pub fn migrate_liquidity(
+        &self,
+        provider: H160,
+        from_pool: Int256,
+        to_pool: Int256,
+        liquidity: Int256,
+        min_amount_a: Int256,
+        min_amount_b: Int256
+    ) -> Int256 {
+        if Self::is_paused() {
+            Runtime::log(ByteString::from_literal("DEX is paused"));
+            return Int256::zero();
+        }
+
+        if !Runtime::check_witness(provider) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return Int256::zero();
+        }
+
+        if self.is_locked() {
+            Runtime::log(ByteString::from_literal("Flash swap in progress"));
+            return Int256::zero();
+        }
+
+        if from_pool == to_pool || liquidity <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid migration"));
+            return Int256::zero();
+        }
+
+        let (mut source, mut target) = match (self.get_pool_data(from_pool), self.get_pool_data(to_pool)) {
+            (Some(source), Some(target)) => (source, target),
+            _ => {
+                Runtime::log(ByteString::from_literal("Pool not found"));
+                return Int256::zero();
+            }
+        };
+
+        if source.token_a != target.token_a || source.token_b != target.token_b {
+            Runtime::log(ByteString::from_literal("Pools do not share a token pair"));
+            return Int256::zero();
+        }
+
+        if !self.is_backed(source.token_a) || !self.is_backed(source.token_b) {
+            Runtime::log(ByteString::from_literal("Reserves not backed by DEX balance"));
+            return Int256::zero();
+        }
+
+        // Leaving an inactive pool is allowed, entering one is not
+        if !target.is_active {
+            Runtime::log(ByteString::from_literal("Pool is not active"));
+            return Int256::zero();
+        }
+
+        if target.reserve_a <= Int256::zero() || target.reserve_b <= Int256::zero() || target.total_liquidity <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Pool has no reserves"));
+            return Int256::zero();
+        }
+
+        let balance = self.get_lp_balance(from_pool, provider);
+        if balance < liquidity || source.total_liquidity < liquidity {
+            Runtime::log(ByteString::from_literal("Insufficient LP balance"));
+            return Int256::zero();
+        }
+
+        // Pro-rata share of the source pool
+        let amount_a = math::mul_div(liquidity, source.reserve_a, source.total_liquidity);
+        let amount_b = math::mul_div(liquidity, source.reserve_b, source.total_liquidity);
+        if amount_a < min_amount_a || amount_b < min_amount_b {
+            Runtime::log(ByteString::from_literal("Slippage too high"));
+            return Int256::zero();
+        }
+
+        // Mint for the smaller share at the target price, charging the deposit rounded up
+        let minted = math::min(
+            math::mul_div(amount_a, target.total_liquidity, target.reserve_a),
+            math::mul_div(amount_b, target.total_liquidity, target.reserve_b),
+        );
+        if minted <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Insufficient liquidity minted"));
+            return Int256::zero();
+        }
+        let deposit_a = self.mul_div_up(minted, target.reserve_a, target.total_liquidity);
+        let deposit_b = self.mul_div_up(minted, target.reserve_b, target.total_liquidity);
+
+        let storage = Storage::get_context();
+
+        self.update_price_accumulators(from_pool, &mut source);
+        source.reserve_a = source.reserve_a.checked_sub(&amount_a);
+        source.reserve_b = source.reserve_b.checked_sub(&amount_b);
+        source.total_liquidity = source.total_liquidity.checked_sub(&liquidity);
+        let source_key = self.pool_prefix.concat(&from_pool.into_byte_string());
+        Storage::put(storage.clone(), source_key, self.serialize_pool(source));
+        self.set_lp_balance(from_pool, provider, balance.checked_sub(&liquidity));
+
+        self.update_price_accumulators(to_pool, &mut target);
+        let (reserve_a, reserve_b) = (target.reserve_a, target.reserve_b);
+        target.reserve_a = target.reserve_a.checked_add(&deposit_a);
+        target.reserve_b = target.reserve_b.checked_add(&deposit_b);
+        target.total_liquidity = target.total_liquidity.checked_add(&minted);
+        self.assert_k_invariant(reserve_a, reserve_b, &target);
+        let (token_a, token_b) = (target.token_a, target.token_b);
+        let target_key = self.pool_prefix.concat(&to_pool.into_byte_string());
+        Storage::put(storage.clone(), target_key, self.serialize_pool(target));
+
+        let new_balance = self.get_lp_balance(to_pool, provider).checked_add(&minted);
+        self.set_lp_balance(to_pool, provider, new_balance);
+        let lp_position = LpPosition {
+            pool_id: to_pool,
+            provider,
+            liquidity_tokens: new_balance,
+            timestamp: Runtime::get_time(),
+        };
+        let lp_key = self.get_lp_position_key(to_pool, provider);
+        Storage::put(storage, lp_key, self.serialize_lp_position(lp_position));
+        self.add_provider_pool(provider, to_pool);
+
+        // Only the remainder leaves the DEX; the rest moves between reserves
+        let refund_a = amount_a.checked_sub(&deposit_a);
+        let refund_b = amount_b.checked_sub(&deposit_b);
+        self.add_reserved(token_a, refund_a.checked_neg());
+        self.add_reserved(token_b, refund_b.checked_neg());
+        if refund_a > Int256::zero() {
+            assert_with_code(self.send_tokens(token_a, provider, refund_a), ErrorCode::CallFailed, "Refund transfer failed");
+        }
+        if refund_b > Int256::zero() {
+            assert_with_code(self.send_tokens(token_b, provider, refund_b), ErrorCode::CallFailed, "Refund transfer failed");
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(from_pool.into_any());
+        event_data.push(to_pool.into_any());
+        event_data.push(provider.into_any());
+        event_data.push(liquidity.into_any());
+        event_data.push(minted.into_any());
+        event_data.push(refund_a.into_any());
+        event_data.push(refund_b.into_any());
+        Runtime::notify(ByteString::from_literal("LiquidityMigrated"), event_data);
+
+        minted
+    }
+
+    /// Pool of a pair for one fee tier, or -1 if there is none
+    #[method]
+    #[safe]
+    pub fn get_fee_tier_pool(&self, token_a: H160, token_b: H160, fee_rate: u32) -> Int256 {
+        let storage = Storage::get_context();
+        if let Some(pool_id_bytes) = Storage::get(storage.clone(), self.get_fee_tier_key(token_a, token_b, fee_rate)) {
+            return Int256::from_byte_string(pool_id_bytes);
+        }
+        if let Some(pool_id_bytes) = Storage::get(storage, self.get_fee_tier_key(token_b, token_a, fee_rate)) {
+            return Int256::from_byte_string(pool_id_bytes);
+        }
+
+        // Pools created before fee tiers are only recorded under their pair
+        match self.get_pool_for_pair(token_a, token_b) {
+            Some(pool_id) if self.get_pool_data(pool_id).map(|pool| pool.fee_rate == fee_rate).unwrap_or(false) => pool_id,
+            _ => Int256::new(-1),
+        }
+    }
+
+    /// Get pool information
+    #[method]
+    #[safe]
+    pub fn get_pool(&self, pool_id: Int256) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
+        match self.get_pool_data(pool_id) {
+            Some(pool) => {
+                result.put(ByteString::from_literal("token_a"), pool.token_a.into_any());
+                result.put(ByteString::from_literal("token_b"), pool.token_b.into_any());
+                result.put(ByteString::from_literal("reserve_a"), pool.reserve_a.into_any());
+                result.put(ByteString::from_literal("reserve_b"), pool.reserve_b.into_any());
+                result.put(ByteString::from_literal("total_liquidity"), pool.total_liquidity.into_any());
+                result.put(ByteString::from_literal("fee_rate"), Int256::new(pool.fee_rate as i64).into_any());
+                result.put(ByteString::from_literal("is_active"),
+                    if pool.is_active { Int256::one() } else { Int256::zero() }.into_any());
+
+                // Current price of token_a in token_b, as a whole-unit ratio and scaled by PRICE_PRECISION
+                let (price_a_to_b, price_a_to_b_scaled) = if pool.reserve_a > Int256::zero() {
+                    (
+                        pool.reserve_b.checked_div(&pool.reserve_a),
+                        math::mul_div(pool.reserve_b, Int256::new(PRICE_PRECISION), pool.reserve_a),
+                    )
+                } else {
+                    (Int256::zero(), Int256::zero())
+                };
+                result.put(ByteString::from_literal("price_a_to_b"), price_a_to_b.into_any());
+                result.put(ByteString::from_literal("price_a_to_b_scaled"), price_a_to_b_scaled.into_any());
+                result.put(ByteString::from_literal("price_a_cumulative"), pool.price_a_cumulative.into_any());
+                result.put(ByteString::from_literal("price_b_cumulative"), pool.price_b_cumulative.into_any());
+                result.put(ByteString::from_literal("last_price_update"), Int256::from_u64(pool.last_price_update).into_any());
+            },
+            None => {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal("Pool not found").into_any());
+            }
+        }
+
+        result
+    }
+
+    /// Get quote for a swap
+    #[method]
+    #[safe]
+    pub fn get_swap_quote(
+        &self,
+        token_in: H160,
+        token_out: H160,
+        amount_in: Int256
+    ) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
+        if let Some(pool_id) = self.get_pool_for_pair(token_in, token_out) {
+            if let Some(pool) = self.get_pool_data(pool_id) {
+                let (amount_out, _, _, protocol_fee) = self.calculate_swap_amounts(&pool, token_in, amount_in);
+                let price_impact = self.calculate_price_impact(&pool, token_in, amount_in);
+
+                result.put(ByteString::from_literal("amount_out"), amount_out.into_any());
+                result.put(ByteString::from_literal("price_impact"), price_impact.into_any());
+                result.put(ByteString::from_literal("protocol_fee"), protocol_fee.into_any());
+                result.put(ByteString::from_literal("pool_id"), pool_id.into_any());
+            } else {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal("Pool data not found").into_any());
+            }
+        } else {
+            result.put(ByteString::from_literal("error"), ByteString::from_literal("Pool not found").into_any());
+        }
+
+        result
+    }
+
+    /// Get the best quote for swapping `amount_in` of `token_in` into `token_out`.
+    ///
+    /// Compares the direct pool with every 2-hop route through a configured intermediate
+    /// token and returns the route with the largest output: `amount_out`, `path` (tokens
+    /// from `token_in` to `token_out`) and `pools` (pool ids, one per hop). Inactive pools
+    /// are skipped. Each hop is quoted like `get_swap_quote`, so executing the route hop by
+    /// hop in one transaction yields the quoted output.
+    #[method]
+    #[safe]
+    pub fn get_best_quote(
+        &self,
+        token_in: H160,
+        token_out: H160,
+        amount_in: Int256
+    ) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
+        if token_in == token_out || amount_in <= Int256::zero() {
+            result.put(ByteString::from_literal("error"), ByteString::from_literal("Invalid quote request").into_any());
+            return result;
+        }
+
+        let mut best_out = Int256::zero();
+        let mut best_via = None;
+        let mut best_pools = (Int256::zero(), Int256::zero());
+
+        if let Some((pool_id, amount_out)) = self.quote_hop(token_in, token_out, amount_in) {
+            best_out = amount_out;
+            best_pools = (pool_id, Int256::zero());
+        }
+
+        let route_tokens = self.get_route_tokens();
+        for i in bounded_iter(&route_tokens, MAX_ROUTE_TOKENS as usize) {
+            let via = route_tokens.get(i);
+            if via == token_in || via == token_out {
+                continue;
+            }
+
+            let (first_pool, amount_mid) = match self.quote_hop(token_in, via, amount_in) {
+                Some(hop) => hop,
+                None => continue,
+            };
+            let (second_pool, amount_out) = match self.quote_hop(via, token_out, amount_mid) {
+                Some(hop) => hop,
+                None => continue,
+            };
+
+            if amount_out > best_out {
+                best_out = amount_out;
+                best_via = Some(via);
+                best_pools = (first_pool, second_pool);
+            }
+        }
+
+        if best_out <= Int256::zero() {
+            result.put(ByteString::from_literal("error"), ByteString::from_literal("No route found").into_any());
+            return result;
+        }
+
+        let mut path = Array::new();
+        let mut pools = Array::new();
+        path.push(token_in);
+        pools.push(best_pools.0);
+        if let Some(via) = best_via {
+            path.push(via);
+            pools.push(best_pools.1);
+        }
+        path.push(token_out);
+
+        result.put(ByteString::from_literal("amount_out"), best_out.into_any());
+        result.put(ByteString::from_literal("hops"), Int256::new(pools.size() as i64).into_any());
+        result.put(ByteString::from_literal("path"), path.into_any());
+        result.put(ByteString::from_literal("pools"), pools.into_any());
+        result
+    }
+
+    /// Add an intermediate token considered by `get_best_quote` (owner only)
+    #[method]
+    #[only_owner]
+    pub fn add_route_token(&self, token: H160) -> bool {
+        if token == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid route token"));
+            return false;
+        }
+
+        let mut route_tokens = self.get_route_tokens();
+        for i in 0..route_tokens.size() {
+            if route_tokens.get(i) == token {
+                Runtime::log(ByteString::from_literal("Route token already added"));
+                return false;
+            }
+        }
+        if route_tokens.size() as u32 >= MAX_ROUTE_TOKENS {
+            Runtime::log(ByteString::from_literal("Too many route tokens"));
+            return false;
+        }
+
+        route_tokens.push(token);
+        let storage = Storage::get_context();
+        Storage::put(storage, self.route_tokens_key.clone(), self.serialize_route_tokens(&route_tokens));
+
+        let mut event_data = Array::new();
+        event_data.push(token.into_any());
+        Runtime::notify(ByteString::from_literal("RouteTokenAdded"), event_data);
+        true
+    }
+
+    /// Remove an intermediate token from `get_best_quote` (owner only)
+    #[method]
+    #[only_owner]
+    pub fn remove_route_token(&self, token: H160) -> bool {
+        let route_tokens = self.get_route_tokens();
+        let mut remaining = Array::new();
+        for i in bounded_iter(&route_tokens, MAX_ROUTE_TOKENS as usize) {
+            let existing = route_tokens.get(i);
+            if existing != token {
+                remaining.push(existing);
+            }
+        }
+
+        if remaining.size() == route_tokens.size() {
+            Runtime::log(ByteString::from_literal("Route token not found"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        if remaining.size() == 0 {
+            Storage::delete(storage, self.route_tokens_key.clone());
+        } else {
+            Storage::put(storage, self.route_tokens_key.clone(), self.serialize_route_tokens(&remaining));
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(token.into_any());
+        Runtime::notify(ByteString::from_literal("RouteTokenRemoved"), event_data);
+        true
+    }
+
+    /// Get the intermediate tokens considered by `get_best_quote`
+    #[method]
+    #[safe]
+    pub fn get_route_tokens(&self) -> Array<H160> {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.route_tokens_key.clone()) {
+            Some(data) => self.deserialize_route_tokens(data),
+            None => Array::new(),
+        }
+    }
+
+    /// Get DEX owner
+    #[method]
+    #[safe]
+    pub fn get_owner(&self) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage.clone(), self.owner_key.clone()) {
+            Some(owner_bytes) => H160::from_byte_string(owner_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    /// Get pool count
+    #[method]
+    #[safe]
+    pub fn get_pool_count(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage.clone(), self.pool_count_key.clone()) {
+            Some(count_bytes) => Int256::from_byte_string(count_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Get the most recent swaps of a pool, newest first (at most SWAP_HISTORY_SIZE)
+    #[method]
+    #[safe]
+    pub fn get_recent_swaps(&self, pool_id: Int256) -> Array<Map<ByteString, Any>> {
+        let mut swaps = Array::new();
+        let storage = Storage::get_context();
+        let count = self.get_swap_count(pool_id);
+        let kept = if count < SWAP_HISTORY_SIZE { count } else { SWAP_HISTORY_SIZE };
+
+        for i in 0..kept {
+            let sequence = count - 1 - i;
+            let record = match Storage::get(storage.clone(), self.get_swap_history_key(pool_id, sequence)) {
+                Some(data) => match deserialize_from_bytestring::<SwapRecord>(&data) {
+                    Ok(record) => record,
+                    Err(_) => continue,
+                },
+                None => continue,
+            };
+
+            let mut entry = Map::new();
+            entry.put(ByteString::from_literal("sequence"), Int256::new(sequence as i64).into_any());
+            entry.put(ByteString::from_literal("token_in"), record.token_in.into_any());
+            entry.put(ByteString::from_literal("amount_in"), record.amount_in.into_any());
+            entry.put(ByteString::from_literal("amount_out"), record.amount_out.into_any());
+            entry.put(ByteString::from_literal("price"), record.price.into_any());
+            entry.put(ByteString::from_literal("timestamp"), Int256::new(record.timestamp as i64).into_any());
+            swaps.push(entry);
+        }
+
+        swaps
+    }
+
+    /// Time-weighted average prices of a pool over at least the last `window` seconds.
+    ///
+    /// The average starts at the newest stored observation that is `window` or more seconds
+    /// old, so the reported window can be up to OBSERVATION_PERIOD longer than requested.
+    /// Moving it requires holding a skewed price for the whole window, unlike the spot price.
+    #[method]
+    #[safe]
+    pub fn consult(&self, pool_id: Int256, window: u64) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
+        let pool = match self.get_pool_data(pool_id) {
+            Some(pool) => pool,
+            None => {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal("Pool not found").into_any());
+                return result;
+            }
+        };
+
+        if window == 0 || window > Runtime::get_time() {
+            result.put(ByteString::from_literal("error"), ByteString::from_literal("Invalid window").into_any());
+            return result;
+        }
+
+        let (price_a_to_b, price_b_to_a, elapsed) = match self.average_prices(pool_id, &pool, window) {
+            Some(prices) => prices,
+            None => {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal("Insufficient price history").into_any());
+                return result;
+            }
+        };
+
+        result.put(ByteString::from_literal("price_a_to_b"), price_a_to_b.into_any());
+        result.put(ByteString::from_literal("price_b_to_a"), price_b_to_a.into_any());
+        result.put(ByteString::from_literal("window"), elapsed.into_any());
+        result
+    }
+
+    // Helper functions
+
+    fn get_min_liquidity(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage.clone(), self.min_liquidity_key.clone()) {
+            Some(min_bytes) => Int256::from_byte_string(min_bytes),
+            None => Int256::new(1000), // Default minimum
+        }
+    }
+
+    /// Geometric mean of the deposits, so the LP supply does not depend on either token's units
+    fn calculate_initial_liquidity(&self, amount_a: Int256, amount_b: Int256) -> Int256 {
+        math::isqrt(amount_a.checked_mul(&amount_b))
+    }
+
+    fn get_swap_count(&self, pool_id: Int256) -> u64 {
+        let storage = Storage::get_context();
+        let count_key = self.swap_count_prefix.concat(&pool_id.into_byte_string());
+        match Storage::get(storage, count_key) {
+            Some(count_bytes) => {
+                let bytes = count_bytes.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([
+                        bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]
+                    ])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    /// Swap number `sequence` lives in slot `sequence % SWAP_HISTORY_SIZE`, overwriting the oldest entry
+    fn get_swap_history_key(&self, pool_id: Int256, sequence: u64) -> ByteString {
+        let slot = (sequence % SWAP_HISTORY_SIZE) as u32;
+        self.swap_history_prefix
+            .concat(&pool_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&ByteString::from_bytes(&slot.to_le_bytes()))
+    }
+
+    fn record_swap(&self, pool_id: Int256, token_in: H160, amount_in: Int256, amount_out: Int256) {
+        let record = SwapRecord {
+            token_in,
+            amount_in,
+            amount_out,
+            price: math::mul_div(amount_out, Int256::new(PRICE_PRECISION), amount_in),
+            timestamp: Runtime::get_time(),
+        };
+
+        let data = match serialize_to_bytestring(&record) {
+            Ok(data) => data,
+            Err(_) => return,
+        };
+
+        let storage = Storage::get_context();
+        let sequence = self.get_swap_count(pool_id);
+        Storage::put(storage.clone(), self.get_swap_history_key(pool_id, sequence), data);
+
+        let count_key = self.swap_count_prefix.concat(&pool_id.into_byte_string());
+        Storage::put(storage, count_key, ByteString::from_bytes(&(sequence + 1).to_le_bytes()));
+    }
+
+    /// Spot prices of a pool (token_b per token_a, token_a per token_b), scaled by PRICE_PRECISION
+    fn spot_prices(&self, pool: &LiquidityPool) -> (Int256, Int256) {
+        if pool.reserve_a <= Int256::zero() || pool.reserve_b <= Int256::zero() {
+            return (Int256::zero(), Int256::zero());
+        }
+
+        let precision = Int256::new(PRICE_PRECISION);
+        (
+            math::mul_div(pool.reserve_b, precision, pool.reserve_a),
+            math::mul_div(pool.reserve_a, precision, pool.reserve_b),
+        )
+    }
+
+    /// Adds the current spot prices, weighted by the time since the last update, to the accumulators
+    fn advance_accumulators(&self, pool: &mut LiquidityPool, now: u64) {
+        if now <= pool.last_price_update {
+            return;
+        }
+
+        let elapsed = Int256::from_u64(now - pool.last_price_update);
+        let (price_a, price_b) = self.spot_prices(pool);
+        let modulus = Int256::one().checked_shl(ACCUMULATOR_BITS);
+        pool.price_a_cumulative = pool.price_a_cumulative.checked_add(&price_a.checked_mul(&elapsed)).checked_mod(&modulus);
+        pool.price_b_cumulative = pool.price_b_cumulative.checked_add(&price_b.checked_mul(&elapsed)).checked_mod(&modulus);
+        pool.last_price_update = now;
+    }
+
+    /// Time-weighted average prices (a to b, b to a) and the seconds they cover, over at least
+    /// the last `window` seconds; `None` without an observation that old (see `consult`)
+    fn average_prices(&self, pool_id: Int256, pool: &LiquidityPool, window: u64) -> Option<(Int256, Int256, Int256)> {
+        let now = Runtime::get_time();
+        let observation = self.find_observation_before(pool_id, now.checked_sub(window)?)?;
+
+        let mut current = pool.clone();
+        self.advance_accumulators(&mut current, now);
+        let elapsed = Int256::from_u64(now - observation.timestamp);
+        if elapsed <= Int256::zero() {
+            return None;
+        }
+
+        Some((
+            self.accumulator_delta(current.price_a_cumulative, observation.price_a_cumulative).checked_div(&elapsed),
+            self.accumulator_delta(current.price_b_cumulative, observation.price_b_cumulative).checked_div(&elapsed),
+            elapsed,
+        ))
+    }
+
+    /// `newer - older` of two wrapping accumulator values
+    fn accumulator_delta(&self, newer: Int256, older: Int256) -> Int256 {
+        let delta = newer.checked_sub(&older);
+        if delta < Int256::zero() {
+            delta.checked_add(&Int256::one().checked_shl(ACCUMULATOR_BITS))
+        } else {
+            delta
+        }
+    }
+
+    /// Must run before every reserve change so the old price is credited for the time it held
+    fn update_price_accumulators(&self, pool_id: Int256, pool: &mut LiquidityPool) {
+        self.advance_accumulators(pool, Runtime::get_time());
+
+        let count = self.get_observation_count(pool_id);
+        let due = match count.checked_sub(1).and_then(|latest| self.get_observation(pool_id, latest)) {
+            Some(latest) => pool.last_price_update >= latest.timestamp + OBSERVATION_PERIOD,
+            None => true,
+        };
+        if due {
+            self.record_observation(pool_id, pool);
+        }
+    }
+
+    fn get_observation_count(&self, pool_id: Int256) -> u64 {
+        let storage = Storage::get_context();
+        let count_key = self.observation_count_prefix.concat(&pool_id.into_byte_string());
+        match Storage::get(storage, count_key) {
+            Some(count_bytes) => {
+                let bytes = count_bytes.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([
+                        bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]
+                    ])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    /// Observation number `sequence` lives in slot `sequence % OBSERVATION_HISTORY_SIZE`
+    fn get_observation_key(&self, pool_id: Int256, sequence: u64) -> ByteString {
+        let slot = (sequence % OBSERVATION_HISTORY_SIZE) as u32;
+        self.observation_prefix
+            .concat(&pool_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&ByteString::from_bytes(&slot.to_le_bytes()))
+    }
+
+    fn get_observation(&self, pool_id: Int256, sequence: u64) -> Option<PriceObservation> {
+        let storage = Storage::get_context();
+        Storage::get(storage, self.get_observation_key(pool_id, sequence))
+            .and_then(|data| deserialize_from_bytestring::<PriceObservation>(&data).ok())
+    }
+
+    fn record_observation(&self, pool_id: Int256, pool: &LiquidityPool) {
+        let observation = PriceObservation {
+            timestamp: pool.last_price_update,
+            price_a_cumulative: pool.price_a_cumulative,
+            price_b_cumulative: pool.price_b_cumulative,
+        };
+
+        let data = match serialize_to_bytestring(&observation) {
+            Ok(data) => data,
+            Err(_) => return,
+        };
+
+        let storage = Storage::get_context();
+        let sequence = self.get_observation_count(pool_id);
+        Storage::put(storage.clone(), self.get_observation_key(pool_id, sequence), data);
+
+        let count_key = self.observation_count_prefix.concat(&pool_id.into_byte_string());
+        Storage::put(storage, count_key, ByteString::from_bytes(&(sequence + 1).to_le_bytes()));
+    }
+
+    /// Newest kept observation taken at or before `timestamp`
+    fn find_observation_before(&self, pool_id: Int256, timestamp: u64) -> Option<PriceObservation> {
+        let count = self.get_observation_count(pool_id);
+        let kept = if count < OBSERVATION_HISTORY_SIZE { count } else { OBSERVATION_HISTORY_SIZE };
+
+        for i in 0..kept {
+            if let Some(observation) = self.get_observation(pool_id, count - 1 - i) {
+                if observation.timestamp <= timestamp {
+                    return Some(observation);
+                }
+            }
+        }
+        None
+    }
+
+    fn get_pair_key(&self, token_a: H160, token_b: H160) -> ByteString {
+        self.token_pair_prefix
+            .concat(&token_a.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&token_b.into_byte_string())
+    }
+
+    fn get_fee_tier_key(&self, token_a: H160, token_b: H160, fee_rate: u32) -> ByteString {
+        self.get_pair_key(token_a, token_b)
+            .concat(&ByteString::from_literal("_"))
+            .concat(&ByteString::from_bytes(&fee_rate.to_le_bytes()))
+    }
+
+    fn get_lp_position_key(&self, pool_id: Int256, provider: H160) -> ByteString {
+        self.lp_position_prefix
+            .concat(&pool_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&provider.into_byte_string())
+    }
+
+    /// Consumes the permit `owner_pubkey` signed on `token` and pulls `amount` from its
+    /// standard account into the DEX.
+    /// Aborts if the token rejects the permit or the DEX balance grew by less than `amount`.
+    fn pull_with_permit(&self, token: H160, owner_pubkey: &PublicKey, amount: Int256, deadline: u64, signature: ByteString) {
+        let dex = Runtime::get_executing_script_hash();
+        let owner = create_standard_account(owner_pubkey.clone());
+        let balance_before = self.token_balance(token, dex);
+
+        let mut permit_args = Array::new();
+        permit_args.push(ByteString::from_bytes(&owner_pubkey.to_bytes()).into_any());
+        permit_args.push(dex.into_any());
+        permit_args.push(amount.into_any());
+        permit_args.push(Int256::from_u64(deadline).into_any());
+        permit_args.push(signature.into_any());
+        assert_with_code(
+            Contract::call_bool(token, ByteString::from_literal("permit"), CallFlags::All, permit_args),
+            ErrorCode::CallFailed,
+            "Permit rejected"
+        );
+
+        let mut transfer_args = Array::new();
+        transfer_args.push(dex.into_any());
+        transfer_args.push(owner.into_any());
+        transfer_args.push(dex.into_any());
+        transfer_args.push(amount.into_any());
+        transfer_args.push(Any::default());
+        Contract::call(token, ByteString::from_literal("transfer_from"), CallFlags::All, transfer_args);
+
+        let received = self.token_balance(token, dex).checked_sub(&balance_before);
+        assert_with_code(received >= amount, ErrorCode::CallFailed, "Permit transfer failed");
+    }
+
+    /// Moves `amount` of `token` from `from` into the DEX. `from` must have signed with a
+    /// witness scope that covers the token's `transfer` call.
+    /// Aborts if the transfer fails or the DEX balance grew by less than `amount`.
+    fn pull_tokens(&self, token: H160, from: H160, amount: Int256) {
+        let dex = Runtime::get_executing_script_hash();
+        let balance_before = self.token_balance(token, dex);
+
+        let mut args = Array::new();
+        args.push(from.into_any());
+        args.push(dex.into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+        assert_with_code(
+            Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, args),
+            ErrorCode::CallFailed,
+            "Deposit transfer failed"
+        );
+
+        let received = self.token_balance(token, dex).checked_sub(&balance_before);
+        assert_with_code(received >= amount, ErrorCode::CallFailed, "Deposit transfer failed");
+    }
+
+    /// Transfers `amount` of `token` held by the DEX to `to`
+    fn send_tokens(&self, token: H160, to: H160, amount: Int256) -> bool {
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(to.into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+        Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, args)
+    }
+
+    fn is_locked(&self) -> bool {
+        let storage = Storage::get_context();
+        Storage::get(storage, self.flash_lock_key.clone()).is_some()
+    }
+
+    fn get_reserved(&self, token: H160) -> Int256 {
+        let storage = Storage::get_context();
+        let reserved_key = self.reserved_prefix.concat(&token.into_byte_string());
+        match Storage::get(storage, reserved_key) {
+            Some(reserved_bytes) => Int256::from_byte_string(reserved_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    fn add_reserved(&self, token: H160, delta: Int256) {
+        let storage = Storage::get_context();
+        let reserved_key = self.reserved_prefix.concat(&token.into_byte_string());
+        Storage::put(storage, reserved_key, self.get_reserved(token).checked_add(&delta).into_byte_string());
+    }
+
+    /// DEX balance of `token` minus all pool reserves and uncollected protocol fees; negative on a shortfall
+    fn unreserved_balance(&self, token: H160) -> Int256 {
+        let dex = Runtime::get_executing_script_hash();
+        self.token_balance(token, dex)
+            .checked_sub(&self.get_reserved(token))
+            .checked_sub(&self.get_collected_fees(token))
+    }
+
+    /// Whether the DEX holds at least the pool reserves and collected fees of `token`
+    fn is_backed(&self, token: H160) -> bool {
+        self.unreserved_balance(token) >= Int256::zero()
+    }
+
+    fn token_balance(&self, token: H160, account: H160) -> Int256 {
+        let mut args = Array::new();
+        args.push(account.into_any());
+        Contract::call(token, ByteString::from_literal("balanceOf"), CallFlags::ReadOnly, args).downcast_into()
+    }
+
+    /// Adds liquidity to an existing pool and credits the LP tokens to `provider`.
+    /// `deposit` moves each token amount into the DEX, aborting on failure, before the
+    /// reserves are credited. Returns the pool id and minted liquidity, or `None` after
+    /// logging the failure.
+    fn mint_liquidity(
+        &self,
+        provider: H160,
+        token_a: H160,
+        token_b: H160,
+        amount_a: Int256,
+        amount_b: Int256,
+        min_liquidity: Int256,
+        deposit: &dyn Fn(H160, Int256)
+    ) -> Option<(Int256, Int256)> {
+        if amount_a <= Int256::zero() || amount_b <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid liquidity amounts"));
+            return None;
+        }
+
+        if self.is_locked() {
+            Runtime::log(ByteString::from_literal("Flash swap in progress"));
+            return None;
+        }
+
+        let pool_id = match self.get_pool_for_pair(token_a, token_b) {
+            Some(id) => id,
+            None => {
+                Runtime::log(ByteString::from_literal("Pool not found"));
+                return None;
+            }
+        };
+
+        let mut pool = match self.get_pool_data(pool_id) {
+            Some(p) => p,
+            None => {
+                Runtime::log(ByteString::from_literal("Pool data not found"));
+                return None;
+            }
+        };
+
+        if !pool.is_active {
+            Runtime::log(ByteString::from_literal("Pool is not active"));
+            return None;
+        }
+
+        if pool.reserve_a <= Int256::zero() || pool.reserve_b <= Int256::zero() || pool.total_liquidity <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Pool has no reserves"));
+            return None;
+        }
+
+        // Align amounts with the pool's token ordering
+        let (amount_a, amount_b) = if token_a == pool.token_a {
+            (amount_a, amount_b)
+        } else {
+            (amount_b, amount_a)
+        };
+
+        // Mint proportionally to the smaller share so the pool price is unchanged
+        let liquidity_a = math::mul_div(amount_a, pool.total_liquidity, pool.reserve_a);
+        let liquidity_b = math::mul_div(amount_b, pool.total_liquidity, pool.reserve_b);
+        let liquidity = math::min(liquidity_a, liquidity_b);
+
+        if liquidity <= Int256::zero() || liquidity < min_liquidity {
+            Runtime::log(ByteString::from_literal("Insufficient liquidity minted"));
+            return None;
+        }
+
+        deposit(pool.token_a, amount_a);
+        deposit(pool.token_b, amount_b);
+
+        self.update_price_accumulators(pool_id, &mut pool);
+        let (reserve_a, reserve_b) = (pool.reserve_a, pool.reserve_b);
+        pool.reserve_a = pool.reserve_a.checked_add(&amount_a);
+        pool.reserve_b = pool.reserve_b.checked_add(&amount_b);
+        self.add_reserved(pool.token_a, amount_a);
+        self.add_reserved(pool.token_b, amount_b);
+        pool.total_liquidity = pool.total_liquidity.checked_add(&liquidity);
+        self.assert_k_invariant(reserve_a, reserve_b, &pool);
+
+        let storage = Storage::get_context();
+        let pool_key = self.pool_prefix.concat(&pool_id.into_byte_string());
+        Storage::put(storage.clone(), pool_key, self.serialize_pool(pool));
+
+        let new_balance = self.get_lp_balance(pool_id, provider).checked_add(&liquidity);
+        self.set_lp_balance(pool_id, provider, new_balance);
+
+        let lp_position = LpPosition {
+            pool_id,
+            provider,
+            liquidity_tokens: new_balance,
+            timestamp: Runtime::get_time(),
+        };
+        let lp_key = self.get_lp_position_key(pool_id, provider);
+        Storage::put(storage, lp_key, self.serialize_lp_position(lp_position));
+
+        self.add_provider_pool(provider, pool_id);
+
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(provider.into_any());
+        event_data.push(amount_a.into_any());
+        event_data.push(amount_b.into_any());
+        event_data.push(liquidity.into_any());
+        Runtime::notify(ByteString::from_literal("LiquidityAdded"), event_data);
+
+        Some((pool_id, liquidity))
+    }
+
+    fn get_lp_balance_key(&self, pool_id: Int256, holder: H160) -> ByteString {
+        self.lp_balance_prefix
+            .concat(&pool_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&holder.into_byte_string())
+    }
+
+    fn set_lp_balance(&self, pool_id: Int256, holder: H160, balance: Int256) {
+        let storage = Storage::get_context();
+        let balance_key = self.get_lp_balance_key(pool_id, holder);
+        if balance == Int256::zero() {
+            Storage::delete(storage, balance_key);
+        } else {
+            Storage::put(storage, balance_key, balance.into_byte_string());
+        }
+    }
+
+    fn get_staking_pool(&self, pool_id: Int256) -> Option<Int256> {
+        let storage = Storage::get_context();
+        let staking_pool_key = self.staking_pool_prefix.concat(&pool_id.into_byte_string());
+        Storage::get(storage, staking_pool_key).map(Int256::from_byte_string)
+    }
+
+    fn get_pool_data(&self, pool_id: Int256) -> Option<LiquidityPool> {
+        let storage = Storage::get_context();
+        let pool_key = self.pool_prefix.concat(&pool_id.into_byte_string());
+
+        Storage::get(storage, pool_key).and_then(|pool_data| self.deserialize_pool(pool_data))
+    }
+
+    fn get_pool_for_pair(&self, token_a: H160, token_b: H160) -> Option<Int256> {
+        let storage = Storage::get_context();
+
+        // Try both orderings
+        let pair_key1 = self.get_pair_key(token_a, token_b);
+        if let Some(pool_id_bytes) = Storage::get(storage.clone(), pair_key1) {
+            return Some(Int256::from_byte_string(pool_id_bytes));
+        }
+
+        let pair_key2 = self.get_pair_key(token_b, token_a);
+        if let Some(pool_id_bytes) = Storage::get(storage.clone(), pair_key2) {
+            return Some(Int256::from_byte_string(pool_id_bytes));
+        }
+
+        None
+    }
+
+    /// Quotes one hop through the pool of a pair, returning the pool id and the output
+    /// amount, or `None` if there is no active pool that yields a positive output.
+    fn quote_hop(&self, token_in: H160, token_out: H160, amount_in: Int256) -> Option<(Int256, Int256)> {
+        let pool_id = self.get_pool_for_pair(token_in, token_out)?;
+        let pool = self.get_pool_data(pool_id)?;
+        if !pool.is_active || pool.reserve_a <= Int256::zero() || pool.reserve_b <= Int256::zero() {
+            return None;
+        }
+
+        let (amount_out, _, _, _) = self.calculate_swap_amounts(&pool, token_in, amount_in);
+        if amount_out <= Int256::zero() {
+            return None;
+        }
+        Some((pool_id, amount_out))
+    }
+
+    /// Returns the output amount, the new reserves and the protocol's share of the input.
+    /// The protocol share is carved out of the pool fee and never enters the reserves.
+    fn calculate_swap_amounts(&self, pool: &LiquidityPool, token_in: H160, amount_in: Int256) -> (Int256, Int256, Int256, Int256) {
+        let (reserve_in, reserve_out) = if token_in == pool.token_a {
+            (pool.reserve_a, pool.reserve_b)
+        } else {
+            (pool.reserve_b, pool.reserve_a)
+        };
+
+        // Apply fee
+        let fee_amount = math::mul_div(amount_in, Int256::new(pool.fee_rate as i64), Int256::new(10000));
+        let amount_in_after_fee = amount_in.checked_sub(&fee_amount);
+        let protocol_fee = math::min(
+            math::mul_div(amount_in, Int256::new(self.get_protocol_fee_rate() as i64), Int256::new(10000)),
+            fee_amount
+        );
+
+        // Constant product formula: (x + dx) * (y - dy) = x * y
+        // dy = y * dx / (x + dx)
+        let amount_out = math::mul_div(reserve_out, amount_in_after_fee, reserve_in.checked_add(&amount_in_after_fee));
+
+        let new_reserve_in = reserve_in.checked_add(&amount_in).checked_sub(&protocol_fee);
+        let new_reserve_out = reserve_out.checked_sub(&amount_out);
+
+        (amount_out, new_reserve_in, new_reserve_out, protocol_fee)
+    }
+
+    fn get_protocol_fee_rate(&self) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.protocol_fee_key.clone()) {
+            Some(rate_bytes) => {
+                let bytes = rate_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    fn accrue_protocol_fee(&self, token: H160, amount: Int256) {
+        let storage = Storage::get_context();
+        let fees_key = self.collected_fees_prefix.concat(&token.into_byte_string());
+        let collected = self.get_collected_fees(token).checked_add(&amount);
+        Storage::put(storage, fees_key, collected.into_byte_string());
+    }
 
     fn calculate_price_impact(&self, pool: &LiquidityPool, token_in: H160, amount_in: Int256) -> Int256 {
         // Calculate proper price impact: (amount_in / reserve_in) * 10000
@@ -558,13 +2033,8 @@ impl SimpleDex {
             (pool.reserve_b, pool.reserve_a)
         };
         
-        let price_impact_numerator = amount_in
-            .checked_mul(&Int256::new(10000)); // Basis points
-        let price_impact = price_impact_numerator
-            .checked_div(&reserve_in);
-        
         // Return price impact in basis points (e.g., 250 = 2.5%)
-        price_impact
+        math::mul_div(amount_in, Int256::new(10000), reserve_in)
     }
 
     fn add_provider_pool(&self, provider: H160, pool_id: Int256) {
@@ -599,49 +2069,77 @@ impl SimpleDex {
         Runtime::notify(ByteString::from_literal("ProviderPoolAdded"), event_data);
     }
 
+    /// Pool record layout (POOL_RECORD_SIZE bytes, integers little-endian):
+    /// token_a (20) | token_b (20) | reserve_a (32) | reserve_b (32) | total_liquidity (32) |
+    /// fee_rate (u32) | is_active (u8) | price_a_cumulative (32) | price_b_cumulative (32) |
+    /// last_price_update (u64)
     fn serialize_pool(&self, pool: LiquidityPool) -> ByteString {
-        let mut result = ByteString::empty();
-        
-        // Serialize token_a (20 bytes)
-        result = result.concat(&pool.token_a.into_byte_string());
-        
-        // Serialize token_b (20 bytes)
-        result = result.concat(&pool.token_b.into_byte_string());
-        
-        // Serialize reserve_a (32 bytes)
-        let reserve_a_bytes = pool.reserve_a.into_byte_string();
-        result = result.concat(&reserve_a_bytes);
+        pool.token_a.into_byte_string()
+            .concat(&pool.token_b.into_byte_string())
+            .concat(&self.encode_amount(pool.reserve_a))
+            .concat(&self.encode_amount(pool.reserve_b))
+            .concat(&self.encode_amount(pool.total_liquidity))
+            .concat(&ByteString::from_bytes(&pool.fee_rate.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&[if pool.is_active { 1u8 } else { 0u8 }]))
+            .concat(&self.encode_amount(pool.price_a_cumulative))
+            .concat(&self.encode_amount(pool.price_b_cumulative))
+            .concat(&ByteString::from_bytes(&pool.last_price_update.to_le_bytes()))
+    }
+
+    fn deserialize_pool(&self, data: ByteString) -> Option<LiquidityPool> {
+        let bytes = data.to_bytes();
+        if bytes.len() != POOL_RECORD_SIZE {
+            return None;
+        }
 
-        // Serialize reserve_b (32 bytes)
-        let reserve_b_bytes = pool.reserve_b.into_byte_string();
-        result = result.concat(&reserve_b_bytes);
+        let amount_at = |offset: usize| Int256::from_byte_string(ByteString::from_bytes(&bytes[offset..offset + 32]));
+        Some(LiquidityPool {
+            token_a: H160::from_byte_string(ByteString::from_bytes(&bytes[0..20])),
+            token_b: H160::from_byte_string(ByteString::from_bytes(&bytes[20..40])),
+            reserve_a: amount_at(40),
+            reserve_b: amount_at(72),
+            total_liquidity: amount_at(104),
+            fee_rate: u32::from_le_bytes([bytes[136], bytes[137], bytes[138], bytes[139]]),
+            is_active: bytes[140] != 0,
+            price_a_cumulative: amount_at(141),
+            price_b_cumulative: amount_at(173),
+            last_price_update: u64::from_le_bytes([
+                bytes[205], bytes[206], bytes[207], bytes[208],
+                bytes[209], bytes[210], bytes[211], bytes[212]
+            ]),
+        })
+    }
 
-        // Serialize total_liquidity (32 bytes)
-        let liquidity_bytes = pool.total_liquidity.into_byte_string();
-        result = result.concat(&liquidity_bytes);
-        
-        // Serialize fee_rate (4 bytes)
-        result = result.concat(&ByteString::from_bytes(&pool.fee_rate.to_le_bytes()));
-        
-        // Serialize is_active (1 byte)
-        result = result.concat(&ByteString::from_bytes(&[if pool.is_active { 1u8 } else { 0u8 }]));
-        
-        result
+    /// Two's complement little-endian, sign-extended to 32 bytes
+    fn encode_amount(&self, value: Int256) -> ByteString {
+        let bytes = value.into_byte_string().to_bytes();
+        let fill = if value < Int256::zero() { 0xFFu8 } else { 0u8 };
+        let mut padded = [fill; 32];
+        padded[..bytes.len()].copy_from_slice(&bytes);
+        ByteString::from_bytes(&padded)
     }
 
-    fn deserialize_pool(&self, _data: ByteString) -> LiquidityPool {
-        // Simplified deserialization - in production, use proper parsing
-        LiquidityPool {
-            token_a: H160::zero(),
-            token_b: H160::zero(),
-            reserve_a: Int256::zero(),
-            reserve_b: Int256::zero(),
-            total_liquidity: Int256::zero(),
-            fee_rate: 30, // 0.3% default
-            is_active: true,
+    /// `a * b / denominator`, rounded up
+    fn mul_div_up(&self, a: Int256, b: Int256, denominator: Int256) -> Int256 {
+        let quotient = math::mul_div(a, b, denominator);
+        if quotient.checked_mul(&denominator) < a.checked_mul(&b) {
+            quotient.checked_add(&Int256::one())
+        } else {
+            quotient
         }
     }
 
+    /// Aborts unless reserves stay non-negative and the product of reserves did not shrink.
+    /// `sync` is exempt: it deliberately follows the DEX's real balances.
+    fn assert_k_invariant(&self, reserve_a_before: Int256, reserve_b_before: Int256, pool: &LiquidityPool) {
+        assert_with_code(
+            pool.reserve_a >= Int256::zero() && pool.reserve_b >= Int256::zero()
+                && pool.reserve_a.checked_mul(&pool.reserve_b) >= reserve_a_before.checked_mul(&reserve_b_before),
+            ErrorCode::InvalidState,
+            "K invariant violated"
+        );
+    }
+
     fn serialize_lp_position(&self, position: LpPosition) -> ByteString {
         // Simplified serialization
         let mut data = position.pool_id.into_byte_string();
@@ -689,4 +2187,314 @@ impl SimpleDex {
         
         result
     }
+
+    fn serialize_route_tokens(&self, tokens: &Array<H160>) -> ByteString {
+        let mut result = ByteString::empty();
+        for i in 0..tokens.size() {
+            result = result.concat(&tokens.get(i).into_byte_string());
+        }
+        result
+    }
+
+    fn deserialize_route_tokens(&self, data: ByteString) -> Array<H160> {
+        let bytes = data.to_bytes();
+        let mut tokens = Array::new();
+        for chunk in bytes.chunks_exact(20) {
+            tokens.push(H160::from_byte_string(ByteString::from_bytes(chunk)));
+        }
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use epicchain_contract::contract::invoke_on_nep17_payment;
+    use epicchain_contract::host;
+
+    fn account(byte: u8) -> H160 {
+        H160::from_bytes(&[byte; 20])
+    }
+
+    fn dex_hash() -> H160 {
+        account(0xDE)
+    }
+
+    /// NEP-17 token mock keeping balances in its own storage and notifying contract recipients
+    fn register_token(token: H160) {
+        host::register_contract(token, |method, mut args| {
+            let storage = Storage::get_context();
+            let balance = |account: H160| match Storage::get(Storage::get_context(), account.into_byte_string()) {
+                Some(balance) => Int256::from_byte_string(balance),
+                None => Int256::zero(),
+            };
+
+            if *method == ByteString::from_literal("balanceOf") {
+                let account: H160 = args.pop().downcast_into();
+                return balance(account).into_any();
+            }
+
+            if *method == ByteString::from_literal("mint") {
+                let amount: Int256 = args.pop().downcast_into();
+                let account: H160 = args.pop().downcast_into();
+                Storage::put(storage, account.into_byte_string(), balance(account).checked_add(&amount).into_byte_string());
+                return Any::default();
+            }
+
+            assert_eq!(*method, ByteString::from_literal("transfer"));
+            let data = args.pop();
+            let amount: Int256 = args.pop().downcast_into();
+            let to: H160 = args.pop().downcast_into();
+            let from: H160 = args.pop().downcast_into();
+            if balance(from) < amount {
+                return Int256::zero().into_any();
+            }
+            Storage::put(storage.clone(), from.into_byte_string(), balance(from).checked_sub(&amount).into_byte_string());
+            Storage::put(storage, to.into_byte_string(), balance(to).checked_add(&amount).into_byte_string());
+            invoke_on_nep17_payment(from, to, amount, data);
+            Int256::one().into_any()
+        });
+    }
+
+    fn mint(token: H160, account: H160, amount: i64) {
+        let mut args = Array::new();
+        args.push(account.into_any());
+        args.push(Int256::new(amount).into_any());
+        Contract::call(token, ByteString::from_literal("mint"), CallFlags::All, args);
+    }
+
+    fn balance_of(token: H160, account: H160) -> Int256 {
+        let mut args = Array::new();
+        args.push(account.into_any());
+        Contract::call(token, ByteString::from_literal("balanceOf"), CallFlags::ReadOnly, args).downcast_into()
+    }
+
+    fn transfer(token: H160, from: H160, to: H160, amount: i64) -> bool {
+        let mut args = Array::new();
+        args.push(from.into_any());
+        args.push(to.into_any());
+        args.push(Int256::new(amount).into_any());
+        args.push(Any::default());
+        Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, args)
+    }
+
+    /// The DEX deployed at `dex_hash`, answering NEP-17 callbacks through its receiver
+    fn deploy() -> SimpleDex {
+        deploy_with_protocol_fee(0)
+    }
+
+    fn deploy_with_protocol_fee(protocol_fee_rate: u32) -> SimpleDex {
+        host::set_executing_script_hash(dex_hash());
+        let dex = SimpleDex::init();
+        assert!(dex.initialize(account(1), Int256::new(1_000), protocol_fee_rate));
+        host::register_contract(dex_hash(), |method, mut args| {
+            assert_eq!(*method, ByteString::from_literal("onNEP17Payment"));
+            let data = args.pop();
+            let amount: Int256 = args.pop().downcast_into();
+            let from = args.pop();
+            let from = if from.is_null() { H160::zero() } else { from.downcast_into() };
+            let accepted = SimpleDex::init().on_nep17_payment(from, amount, data);
+            let result = if accepted { Int256::one() } else { Int256::zero() };
+            result.into_any()
+        });
+        dex
+    }
+
+    /// A 100k/100k pool of tokens 0x0A and 0x0B, created by 0x11
+    fn deploy_pool(dex: &SimpleDex) -> (H160, H160, Int256) {
+        let (token_a, token_b) = (account(0x0A), account(0x0B));
+        register_token(token_a);
+        register_token(token_b);
+        mint(token_a, account(0x11), 1_000_000);
+        mint(token_b, account(0x11), 1_000_000);
+        let pool_id = dex.create_pool(account(0x11), token_a, token_b, Int256::new(100_000), Int256::new(100_000), 30);
+        assert_eq!(pool_id, Int256::one());
+        (token_a, token_b, pool_id)
+    }
+
+    /// Flash borrower mock that repays each loan plus the pool fee from its own balance, the
+    /// way the flash borrower example does, or leaves it unpaid
+    fn register_borrower(borrower: H160, repaying: bool) {
+        host::register_contract(borrower, move |method, mut args| {
+            if *method == ByteString::from_literal("onNEP17Payment") {
+                return Int256::one().into_any();
+            }
+
+            assert_eq!(*method, ByteString::from_literal("onFlashSwap"));
+            args.pop();
+            let fee_rate: Int256 = args.pop().downcast_into();
+            let amount_b: Int256 = args.pop().downcast_into();
+            let amount_a: Int256 = args.pop().downcast_into();
+            let token_b: H160 = args.pop().downcast_into();
+            let token_a: H160 = args.pop().downcast_into();
+            if !repaying {
+                return Int256::one().into_any();
+            }
+
+            let basis_points = Int256::new(10000);
+            for (token, amount) in [(token_a, amount_a), (token_b, amount_b)] {
+                if amount > Int256::zero() {
+                    let repayment = math::mul_div(amount, basis_points, basis_points.checked_sub(&fee_rate)).checked_add(&Int256::one());
+                    let mut transfer_args = Array::new();
+                    transfer_args.push(borrower.into_any());
+                    transfer_args.push(dex_hash().into_any());
+                    transfer_args.push(repayment.into_any());
+                    transfer_args.push(Any::default());
+                    assert!(Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, transfer_args));
+                }
+            }
+            Int256::one().into_any()
+        });
+    }
+
+    #[test]
+    fn test_token_transfers_to_the_dex_are_accepted() {
+        let dex = deploy();
+        let token = account(0x70);
+        register_token(token);
+        mint(token, account(0x11), 500);
+
+        assert!(transfer(token, account(0x11), dex_hash(), 200));
+        assert_eq!(balance_of(token, dex_hash()), Int256::new(200));
+        assert_eq!(dex.unreserved_balance(token), Int256::new(200));
+    }
+
+    #[test]
+    fn test_reserves_are_backed_by_pulled_tokens() {
+        let dex = deploy();
+        let (token_a, token_b, pool_id) = deploy_pool(&dex);
+        assert_eq!(balance_of(token_a, dex_hash()), Int256::new(100_000));
+        assert_eq!(balance_of(token_b, dex_hash()), Int256::new(100_000));
+
+        let trader = account(0x22);
+        mint(token_a, trader, 10_000);
+        let amount_out = dex.swap(trader, token_a, token_b, Int256::new(1_000), Int256::one());
+        assert!(amount_out > Int256::zero());
+        assert_eq!(balance_of(token_a, trader), Int256::new(9_000));
+        assert_eq!(balance_of(token_b, trader), amount_out);
+
+        let liquidity = dex.add_liquidity(account(0x11), token_a, token_b, Int256::new(10_100), Int256::new(10_000), Int256::one());
+        assert!(liquidity > Int256::zero());
+        assert_eq!(balance_of(token_a, account(0x11)), Int256::new(889_900));
+
+        // Every token the DEX holds is accounted for, so there is nothing to skim
+        assert_eq!(dex.unreserved_balance(token_a), Int256::zero());
+        assert_eq!(dex.unreserved_balance(token_b), Int256::zero());
+        assert!(dex.skim(pool_id, account(0x33)));
+        assert_eq!(balance_of(token_a, account(0x33)), Int256::zero());
+        assert_eq!(balance_of(token_b, account(0x33)), Int256::zero());
+    }
+
+    #[test]
+    fn test_flash_loan_is_repaid_through_the_receiver() {
+        let dex = deploy();
+        let (token_a, token_b, pool_id) = deploy_pool(&dex);
+        let borrower = account(0xB0);
+        register_borrower(borrower, true);
+        mint(token_a, borrower, 1_000);
+
+        assert!(dex.flash_swap(pool_id, Int256::new(10_000), Int256::zero(), borrower, Any::default()));
+        assert!(!dex.is_locked());
+
+        // The repayment arrived while the loan was open and the fee stays in the pool
+        let pool = dex.get_pool_data(pool_id).unwrap();
+        let fee = Int256::new(1_000).checked_sub(&balance_of(token_a, borrower));
+        assert!(fee > Int256::zero());
+        assert_eq!(pool.reserve_a, Int256::new(100_000).checked_add(&fee));
+        assert_eq!(pool.reserve_b, Int256::new(100_000));
+        assert_eq!(balance_of(token_a, dex_hash()), pool.reserve_a);
+        assert_eq!(dex.unreserved_balance(token_a), Int256::zero());
+        assert_eq!(dex.unreserved_balance(token_b), Int256::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "Flash swap not repaid")]
+    fn test_unpaid_flash_loan_aborts() {
+        let dex = deploy();
+        let (_, _, pool_id) = deploy_pool(&dex);
+        let borrower = account(0xB0);
+        register_borrower(borrower, false);
+        dex.flash_swap(pool_id, Int256::new(10_000), Int256::zero(), borrower, Any::default());
+    }
+
+    #[test]
+    fn test_protocol_fees_are_paid_from_a_backed_balance() {
+        let dex = deploy_with_protocol_fee(10);
+        let (token_a, token_b, _) = deploy_pool(&dex);
+        let trader = account(0x22);
+        mint(token_a, trader, 10_000);
+        assert!(dex.swap(trader, token_a, token_b, Int256::new(10_000), Int256::one()) > Int256::zero());
+        assert_eq!(dex.get_collected_fees(token_a), Int256::new(10));
+
+        // A shortfall in the DEX balance blocks the payout and keeps the fees on record
+        assert!(transfer(token_a, dex_hash(), account(0x99), 1));
+        assert_eq!(dex.withdraw_protocol_fees(token_a, account(0x33)), Int256::zero());
+        assert_eq!(dex.get_collected_fees(token_a), Int256::new(10));
+
+        assert!(transfer(token_a, account(0x99), dex_hash(), 1));
+        assert_eq!(dex.withdraw_protocol_fees(token_a, account(0x33)), Int256::new(10));
+        assert_eq!(balance_of(token_a, account(0x33)), Int256::new(10));
+        assert_eq!(dex.get_collected_fees(token_a), Int256::zero());
+        assert_eq!(dex.unreserved_balance(token_a), Int256::zero());
+    }
+
+    #[test]
+    fn test_migration_refunds_come_from_backed_reserves() {
+        let dex = deploy();
+        let (token_a, token_b, from_pool) = deploy_pool(&dex);
+        let provider = account(0x11);
+        let to_pool = dex.create_pool(provider, token_a, token_b, Int256::new(50_000), Int256::new(100_000), 5);
+        assert!(to_pool > Int256::zero());
+        let liquidity = dex.get_lp_balance(from_pool, provider).checked_div(&Int256::new(2));
+
+        assert!(transfer(token_b, dex_hash(), account(0x99), 1));
+        assert_eq!(dex.migrate_liquidity(provider, from_pool, to_pool, liquidity, Int256::zero(), Int256::zero()), Int256::zero());
+        assert!(transfer(token_b, account(0x99), dex_hash(), 1));
+
+        let before = balance_of(token_a, provider);
+        assert!(dex.migrate_liquidity(provider, from_pool, to_pool, liquidity, Int256::zero(), Int256::zero()) > Int256::zero());
+
+        // The token A that does not fit the 1:2 target price is refunded out of the DEX
+        assert!(balance_of(token_a, provider) > before);
+        assert_eq!(dex.unreserved_balance(token_a), Int256::zero());
+        assert_eq!(dex.unreserved_balance(token_b), Int256::zero());
+    }
+
+    #[test]
+    fn test_lp_value_ignores_price_moves_within_the_window() {
+        let dex = deploy();
+        let (token_a, token_b, pool_id) = deploy_pool(&dex);
+        let total = dex.get_pool_data(pool_id).unwrap().total_liquidity;
+
+        // No value until the pool has a full window of price history
+        assert_eq!(dex.get_lp_value(pool_id, total), Int256::zero());
+        host::advance_time(LP_VALUE_WINDOW);
+        assert_eq!(dex.get_lp_value(pool_id, total), Int256::new(200_000));
+
+        // Dumping token A moves the spot price but leaves the value of the pool in token A alone
+        let trader = account(0x22);
+        mint(token_a, trader, 100_000);
+        assert!(dex.swap(trader, token_a, token_b, Int256::new(100_000), Int256::one()) > Int256::zero());
+        let pool = dex.get_pool_data(pool_id).unwrap();
+        assert!(pool.reserve_a > Int256::new(199_000));
+        let value = dex.get_lp_value(pool_id, total);
+        assert!(value >= Int256::new(200_000) && value < Int256::new(201_000));
+    }
+
+    #[test]
+    #[should_panic(expected = "Deposit transfer failed")]
+    fn test_swap_without_funds_aborts() {
+        let dex = deploy();
+        let (token_a, token_b, _) = deploy_pool(&dex);
+        dex.swap(account(0x22), token_a, token_b, Int256::new(1_000), Int256::one());
+    }
 }
+
+/// Two-step ownership handoff (`transfer_ownership`, `accept_ownership`, `renounce_ownership`
+/// and `pending_owner`), see [`Ownable`].
+#[contract_impl]
+impl Ownable for SimpleDex {}
+
+/// Owner-controlled emergency stop (`pause`, `unpause` and `is_paused`), see [`Pausable`].
+#[contract_impl]
+impl Pausable for SimpleDex {}
@@ -5,8 +5,11 @@
 //! - Proposal-based transaction system with voting
 //! - Owner management with add/remove capabilities
 //! - Time-locked transactions with expiration
-//! - Emergency recovery mechanisms
+//! - Emergency recovery of the whole owner set through a time-locked recovery address,
+//!   optionally only once every owner has been inactive for a set period (dead-man switch)
+//! - Social recovery of lost owner keys by a guardian supermajority
 //! - Support for multiple asset types (NEP-17 tokens, GAS, NEO)
+//! - Versioned proposal digests that signing devices can decode and display
 //!
 //! This contract showcases enterprise-grade security patterns for managing
 //! shared funds and implementing governance mechanisms.
@@ -15,6 +18,7 @@
 #![no_main]
 
 use epicchain_contract::prelude::*;
+use epicchain_contract::contract::{calldata, native::Gas};
 use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
 
 /// Transaction proposal status
@@ -24,6 +28,7 @@ pub enum ProposalStatus {
     Executed = 1,
     Cancelled = 2,
     Expired = 3,
+    Failed = 4,
 }
 
 impl ProposalStatus {
@@ -32,6 +37,7 @@ impl ProposalStatus {
             1 => ProposalStatus::Executed,
             2 => ProposalStatus::Cancelled,
             3 => ProposalStatus::Expired,
+            4 => ProposalStatus::Failed,
             _ => ProposalStatus::Pending,
         }
     }
@@ -41,6 +47,65 @@ impl ProposalStatus {
     }
 }
 
+/// Wallet configuration changes executed by the wallet itself once confirmed
+#[derive(Clone, Copy, PartialEq)]
+pub enum OwnerAction {
+    AddOwner = 1,
+    RemoveOwner = 2,
+    ReplaceOwner = 3,
+    ChangeThreshold = 4,
+}
+
+impl OwnerAction {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(OwnerAction::AddOwner),
+            2 => Some(OwnerAction::RemoveOwner),
+            3 => Some(OwnerAction::ReplaceOwner),
+            4 => Some(OwnerAction::ChangeThreshold),
+            _ => None,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Default challenge period of a guardian recovery
+const DEFAULT_RECOVERY_DELAY: u64 = 2 * SECONDS_PER_DAY;
+
+/// Default delay before an emergency recovery can be executed
+const DEFAULT_EMERGENCY_DELAY: u64 = 2 * SECONDS_PER_DAY;
+
+/// Capability the inactivity switch grants the recovery address
+const CAN_REPLACE_OWNERS: u32 = 1;
+
+/// Bounds of the owner inactivity period
+const MIN_INACTIVITY_PERIOD: u64 = 7 * SECONDS_PER_DAY;
+const MAX_INACTIVITY_PERIOD: u64 = 365 * SECONDS_PER_DAY;
+
+/// Maximum number of guardians
+const MAX_GUARDIANS: usize = 20;
+
+/// Version of the proposal digest format
+const DIGEST_VERSION: u8 = 1;
+
+/// Domain tag binding proposal digests to this kind of contract
+const DIGEST_DOMAIN: &str = "EpicChain.Multisig";
+
+/// Length of a proposal document hash (SHA-256)
+const DOCUMENT_HASH_LENGTH: usize = 32;
+
+/// Maximum length of a proposal memo
+const MAX_MEMO_LENGTH: usize = 128;
+
+/// Action kinds in the proposal digest
+const ACTION_NATIVE_TRANSFER: u8 = 1;
+const ACTION_TOKEN_TRANSFER: u8 = 2;
+const ACTION_CONTRACT_CALL: u8 = 3;
+const ACTION_OWNER_ACTION: u8 = 4;
+
 /// Transaction proposal information
 #[derive(Clone)]
 pub struct TransactionProposal {
@@ -49,7 +114,7 @@ pub struct TransactionProposal {
     pub token: H160,        // H160::zero() for native transfers
     pub amount: Int256,
     pub data: ByteString,   // Additional call data
-    pub expiration: i64,
+    pub expiration: u64,    // Block time after which the proposal can no longer be confirmed
     pub status: ProposalStatus,
     pub confirmations: u32,
     pub required_confirmations: u32,
@@ -59,7 +124,7 @@ pub struct TransactionProposal {
 #[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
 #[contract_version("1.0.0")]
 #[contract_standards("")]
-#[contract_permission("*", "*")]
+#[contract_permission("*", "*", reason = "executes arbitrary confirmed transactions")]
 #[contract_meta("description", "Multi-signature wallet with proposal-based governance")]
 #[contract_meta("category", "Security")]
 pub struct MultisigWallet {
@@ -83,10 +148,28 @@ pub struct MultisigWallet {
 
     // Emergency
     emergency_recovery_key: ByteString, // Emergency recovery address
-    recovery_delay_key: ByteString,     // Delay before recovery can be executed
+    recovery_delay_key: ByteString,     // Challenge period of a guardian recovery
+    emergency_delay_key: ByteString,    // Delay before an emergency recovery can be executed
+    emergency_pending_key: ByteString,  // start time | threshold | proposed owner list
+    proposal_floor_key: ByteString,     // proposals up to this id were voided by an emergency recovery
+    inactivity_switch: DeadManSwitch,   // last owner activity; arms emergency recovery after silence
 
     // Transaction execution
     executed_prefix: ByteString,        // transaction_id -> executed status
+    expiration_prefix: ByteString,      // proposal_id -> expiration time
+    owner_action_prefix: ByteString,    // proposal_id -> action | owner | new owner | threshold
+    proposal_call_prefix: ByteString,   // proposal_id -> target | token | amount length | amount | data
+    proposal_memo_prefix: ByteString,   // proposal_id -> document hash length | document hash | memo
+
+    // Social recovery
+    guardians_key: ByteString,          // List of guardians
+    guardian_epoch_key: ByteString,     // Incremented whenever the guardian set changes
+    is_guardian_prefix: ByteString,     // guardian -> true
+    recovery_count_key: ByteString,     // Total number of recoveries
+    active_recovery_prefix: ByteString, // lost owner -> active recovery id
+    recovery_prefix: ByteString,        // recovery id -> new owner | start time | epoch
+    recovery_approval_prefix: ByteString, // recovery id + guardian -> approved
+    recovery_approvals_prefix: ByteString, // recovery id -> approval count
 }
 
 #[contract_impl]
@@ -106,7 +189,23 @@ impl MultisigWallet {
             proposal_lifetime_key: ByteString::from_literal("proposal_lifetime"),
             emergency_recovery_key: ByteString::from_literal("emergency_recovery"),
             recovery_delay_key: ByteString::from_literal("recovery_delay"),
+            emergency_delay_key: ByteString::from_literal("emergency_delay"),
+            emergency_pending_key: ByteString::from_literal("emergency_pending"),
+            proposal_floor_key: ByteString::from_literal("proposal_floor"),
+            inactivity_switch: DeadManSwitch::new("inactivity_"),
             executed_prefix: ByteString::from_literal("executed_"),
+            expiration_prefix: ByteString::from_literal("expiration_"),
+            owner_action_prefix: ByteString::from_literal("owner_action_"),
+            proposal_call_prefix: ByteString::from_literal("proposal_call_"),
+            proposal_memo_prefix: ByteString::from_literal("proposal_memo_"),
+            guardians_key: ByteString::from_literal("guardians"),
+            guardian_epoch_key: ByteString::from_literal("guardian_epoch"),
+            is_guardian_prefix: ByteString::from_literal("is_guardian_"),
+            recovery_count_key: ByteString::from_literal("recovery_count"),
+            active_recovery_prefix: ByteString::from_literal("active_recovery_"),
+            recovery_prefix: ByteString::from_literal("recovery_"),
+            recovery_approval_prefix: ByteString::from_literal("recovery_approval_"),
+            recovery_approvals_prefix: ByteString::from_literal("recovery_approvals_"),
         }
     }
 
@@ -145,7 +244,7 @@ impl MultisigWallet {
             return false;
         }
 
-        if proposal_lifetime < 3600 || proposal_lifetime > 2592000 { // 1 hour to 30 days
+        if proposal_lifetime < SECONDS_PER_HOUR as i64 || proposal_lifetime > days(30) as i64 {
             Runtime::log(ByteString::from_literal("Invalid proposal lifetime (1 hour to 30 days)"));
             return false;
         }
@@ -166,12 +265,12 @@ impl MultisigWallet {
             return false;
         }
 
-        // Store configuration using Int256 to avoid WASM issues
-        Storage::put(storage.clone(), self.owner_count_key.clone(), Int256::new(owner_count as i64).into_byte_string());
-        Storage::put(storage.clone(), self.required_confirmations_key.clone(), Int256::new(required_confirmations as i64).into_byte_string());
+        // Store configuration
+        Storage::put(storage.clone(), self.owner_count_key.clone(), ByteString::from_bytes(&owner_count.to_le_bytes()));
+        Storage::put(storage.clone(), self.required_confirmations_key.clone(), ByteString::from_bytes(&required_confirmations.to_le_bytes()));
         Storage::put(storage.clone(), self.proposal_count_key.clone(), Int256::zero().into_byte_string());
         Storage::put(storage.clone(), self.max_owners_key.clone(), Int256::new(20).into_byte_string());
-        Storage::put(storage.clone(), self.proposal_lifetime_key.clone(), Int256::new(proposal_lifetime).into_byte_string());
+        Storage::put(storage.clone(), self.proposal_lifetime_key.clone(), ByteString::from_bytes(&(proposal_lifetime as u64).to_le_bytes()));
 
         // Store owners
         let serialized_owners = self.serialize_owners_list(&initial_owners);
@@ -217,6 +316,7 @@ impl MultisigWallet {
             Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
             return Int256::new(-1);
         }
+        self.note_owner_activity();
 
         // Validate parameters
         if amount < Int256::zero() {
@@ -225,14 +325,19 @@ impl MultisigWallet {
         }
 
         let storage = Storage::get_context();
-        // Avoid u64 operations by using a simplified time approach
-        let proposal_lifetime = self.get_proposal_lifetime();
-        let expiration = proposal_lifetime; // Simplified: just use lifetime as expiration
+        let expiration = deadline(Runtime::get_time(), self.get_proposal_lifetime());
 
         // Get next proposal ID
         let proposal_count = self.get_proposal_count();
         let proposal_id = proposal_count.checked_add(&Int256::one());
 
+        let amount_bytes = amount.into_byte_string();
+        let call_record = target.into_byte_string()
+            .concat(&token.into_byte_string())
+            .concat(&ByteString::from_bytes(&[amount_bytes.len() as u8]))
+            .concat(&amount_bytes)
+            .concat(&data);
+
         // Create proposal
         let proposal = TransactionProposal {
             proposer,
@@ -254,6 +359,13 @@ impl MultisigWallet {
         // Update proposal count
         Storage::put(storage.clone(), self.proposal_count_key.clone(), proposal_id.into_byte_string());
 
+        // Expiration also gets its own record, read by `get_proposal_expiration`
+        let expiration_key = self.expiration_prefix.concat(&proposal_id.into_byte_string());
+        Storage::put(storage.clone(), expiration_key, ByteString::from_bytes(&expiration.to_le_bytes()));
+
+        // So is the call, in the fixed layout the proposal digest is built from
+        Storage::put(storage.clone(), self.proposal_call_prefix.concat(&proposal_id.into_byte_string()), call_record);
+
         // Record proposer's confirmation
         let confirmation_key = self.get_confirmation_key(proposal_id, proposer);
         Storage::put(storage.clone(), confirmation_key, ByteString::from_literal("true"));
@@ -263,11 +375,58 @@ impl MultisigWallet {
         event_data.push(proposer.into_any());
         event_data.push(target.into_any());
         event_data.push(amount.into_any());
+        event_data.push(Int256::new(expiration as i64).into_any());
         Runtime::notify(ByteString::from_literal("TransactionProposed"), event_data);
 
         proposal_id
     }
 
+    /// Propose a transaction with an off-chain document hash and a short memo attached.
+    ///
+    /// The document hash (empty or 32 bytes, e.g. the SHA-256 of an invoice or audit report)
+    /// and memo are fixed at proposal time, so every confirmation approves the action together
+    /// with the context it was proposed with.
+    #[method]
+    pub fn propose_transaction_with_memo(
+        &self,
+        proposer: H160,
+        target: H160,
+        token: H160,
+        amount: Int256,
+        data: ByteString,
+        document_hash: ByteString,
+        memo: ByteString
+    ) -> Int256 {
+        if !document_hash.is_empty() && document_hash.len() != DOCUMENT_HASH_LENGTH {
+            Runtime::log(ByteString::from_literal("Invalid document hash: must be 32 bytes"));
+            return Int256::new(-1);
+        }
+
+        if memo.len() > MAX_MEMO_LENGTH {
+            Runtime::log(ByteString::from_literal("Memo too long"));
+            return Int256::new(-1);
+        }
+
+        let proposal_id = self.propose_transaction(proposer, target, token, amount, data);
+        if proposal_id < Int256::zero() || (document_hash.is_empty() && memo.is_empty()) {
+            return proposal_id;
+        }
+
+        let storage = Storage::get_context();
+        let memo_record = ByteString::from_bytes(&[document_hash.len() as u8])
+            .concat(&document_hash)
+            .concat(&memo);
+        Storage::put(storage, self.proposal_memo_prefix.concat(&proposal_id.into_byte_string()), memo_record);
+
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        event_data.push(document_hash.into_any());
+        event_data.push(memo.into_any());
+        Runtime::notify(ByteString::from_literal("ProposalMemoAttached"), event_data);
+
+        proposal_id
+    }
+
     /// Confirm a transaction proposal
     #[method]
     pub fn confirm_transaction(&self, proposal_id: Int256, confirmer: H160) -> bool {
@@ -282,6 +441,7 @@ impl MultisigWallet {
             Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
             return false;
         }
+        self.note_owner_activity();
 
         // Get proposal
         let mut proposal = match self.get_proposal(proposal_id) {
@@ -298,9 +458,16 @@ impl MultisigWallet {
             return false;
         }
 
-        // Simplified expiration check - skip time validation for now
-        // In production, implement proper time handling without u64 operations
-        // if proposal.expiration < some_threshold { return false; }
+        if proposal_id <= self.get_proposal_floor() {
+            Runtime::log(ByteString::from_literal("Proposal voided by emergency recovery"));
+            return false;
+        }
+
+        // Expired proposals can neither be confirmed nor executed
+        if is_expired(self.get_proposal_expiration(proposal_id)) {
+            Runtime::log(ByteString::from_literal("Proposal has expired"));
+            return false;
+        }
 
         let storage = Storage::get_context();
         let confirmation_key = self.get_confirmation_key(proposal_id, confirmer);
@@ -316,10 +483,14 @@ impl MultisigWallet {
         proposal.confirmations += 1;
         let confirmations_count = proposal.confirmations;
 
-        // Check if enough confirmations
-        if proposal.confirmations >= proposal.required_confirmations {
+        // A threshold raised after the proposal was made applies to it as well
+        let required = proposal.required_confirmations.max(self.get_required_confirmations());
+        if proposal.confirmations >= required {
             // Execute the transaction based on proposal data
-            let success = if proposal.token == H160::zero() {
+            let success = if let Some(owner_action) = self.get_owner_action(proposal_id) {
+                // Wallet configuration change
+                self.execute_owner_action(owner_action)
+            } else if proposal.token == H160::zero() {
                 // Native transfer (GAS/NEO)
                 self.execute_native_transfer(proposal.target, proposal.amount)
             } else if proposal.data.is_empty() {
@@ -330,17 +501,24 @@ impl MultisigWallet {
                 self.execute_contract_call(proposal.target, proposal.data.clone())
             };
 
+            // Record the outcome so a failed transaction cannot be executed again
+            proposal.status = if success { ProposalStatus::Executed } else { ProposalStatus::Failed };
+            let proposal_key = self.proposal_prefix.concat(&proposal_id.into_byte_string());
+            Storage::put(storage.clone(), proposal_key, self.serialize_proposal(proposal.clone()));
+
+            let mut event_data = Array::new();
+            event_data.push(proposal_id.into_any());
+            event_data.push(proposal.target.into_any());
+            event_data.push(proposal.amount.into_any());
+
             if success {
                 // Mark transaction as executed
                 let executed_key = self.executed_prefix.concat(&proposal_id.into_byte_string());
                 Storage::put(storage.clone(), executed_key, ByteString::from_literal("true"));
 
-                let mut event_data = Array::new();
-                event_data.push(proposal_id.into_any());
-                event_data.push(proposal.target.into_any());
-                event_data.push(proposal.amount.into_any());
                 Runtime::notify(ByteString::from_literal("TransactionExecuted"), event_data);
             } else {
+                Runtime::notify(ByteString::from_literal("TransactionFailed"), event_data);
                 Runtime::log(ByteString::from_literal("Transaction execution failed"));
                 return false;
             }
@@ -458,33 +636,107 @@ impl MultisigWallet {
         true
     }
 
-    /// Add a new owner (requires multisig approval)
+    /// Mark a pending proposal whose expiration has passed as expired.
+    ///
+    /// Anyone can call it; it only records what the block time already enforces.
+    #[method]
+    pub fn expire_proposal(&self, proposal_id: Int256) -> bool {
+        let mut proposal = match self.get_proposal(proposal_id) {
+            Some(p) => p,
+            None => {
+                Runtime::log(ByteString::from_literal("Proposal not found"));
+                return false;
+            }
+        };
+
+        if proposal.status != ProposalStatus::Pending {
+            Runtime::log(ByteString::from_literal("Proposal is not pending"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let executed_key = self.executed_prefix.concat(&proposal_id.into_byte_string());
+        if Storage::get(storage.clone(), executed_key).is_some() {
+            Runtime::log(ByteString::from_literal("Proposal already executed"));
+            return false;
+        }
+
+        let expiration = proposal.expiration;
+        if !is_expired(expiration) {
+            Runtime::log(ByteString::from_literal("Proposal has not expired yet"));
+            return false;
+        }
+
+        proposal.status = ProposalStatus::Expired;
+        let proposal_key = self.proposal_prefix.concat(&proposal_id.into_byte_string());
+        Storage::put(storage, proposal_key, self.serialize_proposal(proposal));
+
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        event_data.push(Int256::new(expiration as i64).into_any());
+        Runtime::notify(ByteString::from_literal("ProposalExpired"), event_data);
+
+        true
+    }
+
+    /// Propose adding an owner; executes once the proposal is confirmed
+    #[method]
+    pub fn add_owner(&self, proposer: H160, new_owner: H160) -> Int256 {
+        if new_owner == H160::zero() || self.is_owner(new_owner) {
+            Runtime::log(ByteString::from_literal("Address is already an owner"));
+            return Int256::new(-1);
+        }
+
+        if self.get_owner_count() >= self.get_max_owners() {
+            Runtime::log(ByteString::from_literal("Maximum number of owners reached"));
+            return Int256::new(-1);
+        }
+
+        self.propose_owner_action(proposer, OwnerAction::AddOwner, new_owner, H160::zero(), 0)
+    }
+
+    /// Propose removing an owner and setting the threshold for the smaller owner set
+    #[method]
+    pub fn remove_owner(&self, proposer: H160, owner: H160, new_threshold: u32) -> Int256 {
+        if !self.is_owner(owner) {
+            Runtime::log(ByteString::from_literal("Address is not an owner"));
+            return Int256::new(-1);
+        }
+
+        let remaining = self.get_owner_count().saturating_sub(1);
+        if new_threshold == 0 || new_threshold > remaining {
+            Runtime::log(ByteString::from_literal("Invalid required confirmations"));
+            return Int256::new(-1);
+        }
+
+        self.propose_owner_action(proposer, OwnerAction::RemoveOwner, owner, H160::zero(), new_threshold)
+    }
+
+    /// Propose replacing an owner with a new address, keeping the threshold
     #[method]
-    pub fn add_owner(&self, new_owner: H160) -> Int256 {
-        // This should be called through a proposal, but for simplicity, we'll allow direct calls
-        // Complete implementation: This would create a proposal that calls an internal add_owner function
+    pub fn replace_owner(&self, proposer: H160, old_owner: H160, new_owner: H160) -> Int256 {
+        if !self.is_owner(old_owner) {
+            Runtime::log(ByteString::from_literal("Address is not an owner"));
+            return Int256::new(-1);
+        }
 
-        if self.is_owner(new_owner) {
+        if new_owner == H160::zero() || self.is_owner(new_owner) {
             Runtime::log(ByteString::from_literal("Address is already an owner"));
             return Int256::new(-1);
         }
 
-        let owner_count = self.get_owner_count();
-        let max_owners = self.get_max_owners();
+        self.propose_owner_action(proposer, OwnerAction::ReplaceOwner, old_owner, new_owner, 0)
+    }
 
-        if owner_count >= max_owners {
-            Runtime::log(ByteString::from_literal("Maximum number of owners reached"));
+    /// Propose changing the number of required confirmations
+    #[method]
+    pub fn change_threshold(&self, proposer: H160, new_threshold: u32) -> Int256 {
+        if new_threshold == 0 || new_threshold > self.get_owner_count() {
+            Runtime::log(ByteString::from_literal("Invalid required confirmations"));
             return Int256::new(-1);
         }
 
-        // Create a proposal to add the owner
-        self.propose_transaction(
-            Runtime::get_calling_script_hash(), // Use contract as proposer for internal operations
-            Runtime::get_executing_script_hash(),
-            H160::zero(),
-            Int256::zero(),
-            ByteString::from_literal("add_owner")
-        )
+        self.propose_owner_action(proposer, OwnerAction::ChangeThreshold, H160::zero(), H160::zero(), new_threshold)
     }
 
     /// Get proposal information
@@ -494,10 +746,154 @@ impl MultisigWallet {
         let storage = Storage::get_context();
         let proposal_key = self.proposal_prefix.concat(&proposal_id.into_byte_string());
 
-        match Storage::get(storage.clone(), proposal_key) {
-            Some(proposal_data) => Some(self.deserialize_proposal(proposal_data)),
-            None => None,
+        Storage::get(storage, proposal_key).and_then(|proposal_data| self.deserialize_proposal(proposal_data))
+    }
+
+    /// Get the block time after which a proposal expires (0 if unknown)
+    #[method]
+    #[safe]
+    pub fn get_proposal_expiration(&self, proposal_id: Int256) -> u64 {
+        let storage = Storage::get_context();
+        let expiration_key = self.expiration_prefix.concat(&proposal_id.into_byte_string());
+        match Storage::get(storage, expiration_key) {
+            Some(time_bytes) => {
+                let bytes = time_bytes.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([
+                        bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]
+                    ])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    /// Get the document hash and memo attached to a proposal (both empty if none)
+    #[method]
+    #[safe]
+    pub fn get_proposal_memo(&self, proposal_id: Int256) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        let (document_hash, memo) = self.get_memo(proposal_id);
+        result.put(ByteString::from_literal("document_hash"), document_hash.into_any());
+        result.put(ByteString::from_literal("memo"), memo.into_any());
+        result
+    }
+
+    /// Dry-run a proposal as if it were executed now, so owners can inspect it before confirming.
+    ///
+    /// Owner actions are checked against the current owner set and transfers against the wallet's
+    /// balance. Contract calls are performed with `ReadOnly` flags and their return value is
+    /// reported; a target method that writes state cannot run that way and faults the invocation,
+    /// so its effects can only be previewed by test-invoking `confirm_transaction`.
+    #[method]
+    #[safe]
+    pub fn simulate_proposal(&self, proposal_id: Int256) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
+        let proposal = match self.get_proposal(proposal_id) {
+            Some(p) => p,
+            None => {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal("Proposal not found").into_any());
+                return result;
+            }
+        };
+
+        // Whether a confirmation could execute the proposal at all
+        let blocked = if proposal.status != ProposalStatus::Pending {
+            Some("Proposal is not pending")
+        } else if proposal_id <= self.get_proposal_floor() {
+            Some("Proposal voided by emergency recovery")
+        } else if is_expired(self.get_proposal_expiration(proposal_id)) {
+            Some("Proposal has expired")
+        } else {
+            None
+        };
+
+        let (kind, outcome) = if let Some(owner_action) = self.get_owner_action(proposal_id) {
+            ("owner_action", self.check_owner_action(owner_action))
+        } else if proposal.token == H160::zero() {
+            ("native_transfer", self.check_transfer(Gas::hash(), proposal.amount))
+        } else if proposal.data.is_empty() {
+            ("token_transfer", self.check_transfer(proposal.token, proposal.amount))
+        } else {
+            match calldata::decode(&proposal.data) {
+                Some((method, args)) => {
+                    let value = Contract::call(proposal.target, method, CallFlags::ReadOnly, args);
+                    let success = value.is_success();
+                    result.put(ByteString::from_literal("return_value"), value);
+                    ("contract_call", if success { Ok(()) } else { Err("Call returned failure") })
+                }
+                None => ("contract_call", Err("Invalid call data")),
+            }
+        };
+
+        let remaining = proposal.required_confirmations.saturating_sub(proposal.confirmations);
+        let success = blocked.is_none() && outcome.is_ok();
+
+        result.put(ByteString::from_literal("kind"), ByteString::from_literal(kind).into_any());
+        result.put(ByteString::from_literal("executable"),
+            if blocked.is_none() { Int256::one() } else { Int256::zero() }.into_any());
+        result.put(ByteString::from_literal("expected_success"),
+            if success { Int256::one() } else { Int256::zero() }.into_any());
+        result.put(ByteString::from_literal("confirmations_needed"), Int256::new(remaining as i64).into_any());
+        if let Some(reason) = blocked.or(outcome.err()) {
+            result.put(ByteString::from_literal("reason"), ByteString::from_literal(reason).into_any());
         }
+
+        result
+    }
+
+    /// Canonical digest of a proposal, for signing devices to display and sign.
+    ///
+    /// Layout (version 1, integers little-endian):
+    /// - domain separator: sha256(DIGEST_DOMAIN | version | network (u32) | wallet hash)
+    /// - payload: domain separator | nonce length (u8) | nonce (the proposal id) |
+    ///   expiration (u64) | action count (u8) | actions
+    /// - actions: kind (u8) followed by
+    ///   1 native transfer: target | amount length (u8) | amount
+    ///   2 token transfer: token | target | amount length (u8) | amount
+    ///   3 contract call: target | call data length (u16) | call data
+    ///   4 owner action: action (u8) | owner | new owner | threshold (u32)
+    /// - digest: sha256(payload)
+    ///
+    /// The payload is returned alongside the digest so a device can decode every field it
+    /// shows and recompute the digest itself rather than trusting this view.
+    #[method]
+    #[safe]
+    pub fn proposal_digest(&self, proposal_id: Int256) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
+        let actions = match self.encode_proposal_actions(proposal_id) {
+            Some(actions) => actions,
+            None => {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal("Proposal not found").into_any());
+                return result;
+            }
+        };
+
+        let domain_separator = sha256(
+            ByteString::from_literal(DIGEST_DOMAIN)
+                .concat(&ByteString::from_bytes(&[DIGEST_VERSION]))
+                .concat(&ByteString::from_bytes(&Runtime::get_network().to_le_bytes()))
+                .concat(&Runtime::get_executing_script_hash().into_byte_string())
+        );
+        let nonce = proposal_id.into_byte_string();
+        let payload = domain_separator.clone()
+            .concat(&ByteString::from_bytes(&[nonce.len() as u8]))
+            .concat(&nonce)
+            .concat(&ByteString::from_bytes(&self.get_proposal_expiration(proposal_id).to_le_bytes()))
+            .concat(&ByteString::from_bytes(&[1u8]))
+            .concat(&actions);
+
+        result.put(ByteString::from_literal("version"), Int256::new(DIGEST_VERSION as i64).into_any());
+        result.put(ByteString::from_literal("domain_separator"), domain_separator.into_any());
+        result.put(ByteString::from_literal("nonce"), proposal_id.into_any());
+        result.put(ByteString::from_literal("digest"), sha256(payload.clone()).into_any());
+        result.put(ByteString::from_literal("payload"), payload.into_any());
+        result
     }
 
     /// Check if address is an owner
@@ -527,15 +923,11 @@ impl MultisigWallet {
         let storage = Storage::get_context();
         match Storage::get(storage.clone(), self.required_confirmations_key.clone()) {
             Some(req_bytes) => {
-                // Use Int256 parsing to avoid WASM issues
-                let int_val = Int256::from_byte_string(req_bytes);
-                // Simple conversion - assume small values fit in u32
-                if int_val.is_zero() {
-                    1
+                let bytes = req_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
                 } else {
-                    // For simplicity, just return a reasonable default for now
-                    // In production, you'd implement proper Int256 to u32 conversion
-                    2
+                    1
                 }
             },
             None => 1,
@@ -549,13 +941,11 @@ impl MultisigWallet {
         let storage = Storage::get_context();
         match Storage::get(storage.clone(), self.owner_count_key.clone()) {
             Some(count_bytes) => {
-                // Use Int256 parsing to avoid WASM issues
-                let int_val = Int256::from_byte_string(count_bytes);
-                if int_val.is_zero() {
-                    0
+                let bytes = count_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
                 } else {
-                    // For simplicity, return a reasonable default
-                    3
+                    0
                 }
             },
             None => 0,
@@ -573,160 +963,1282 @@ impl MultisigWallet {
         }
     }
 
-    // Helper functions
+    /// Replace the guardian set (requires the witnesses of M owners).
+    ///
+    /// Guardians cannot act as owners; they can only rotate the key of a lost owner.
+    /// Changing the set invalidates pending recoveries.
+    #[method]
+    pub fn set_guardians(&self, signers: Array<H160>, guardians: Array<H160>, recovery_delay: u64) -> bool {
+        if !self.has_owner_quorum(&signers) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Owner quorum required"));
+            return false;
+        }
 
-    fn get_max_owners(&self) -> u32 {
-        let storage = Storage::get_context();
-        match Storage::get(storage.clone(), self.max_owners_key.clone()) {
-            Some(max_bytes) => {
-                // Use Int256 parsing to avoid WASM issues
-                let int_val = Int256::from_byte_string(max_bytes);
-                if int_val.is_zero() {
-                    20
-                } else {
-                    // For simplicity, return the default
-                    20
+        if guardians.size() > MAX_GUARDIANS {
+            Runtime::log(ByteString::from_literal("Too many guardians"));
+            return false;
+        }
+
+        if recovery_delay < SECONDS_PER_DAY || recovery_delay > days(30) {
+            Runtime::log(ByteString::from_literal("Invalid recovery delay (1 to 30 days)"));
+            return false;
+        }
+
+        for i in bounded_iter(&guardians, MAX_GUARDIANS) {
+            let guardian = guardians.get(i);
+            if guardian == H160::zero() || self.is_owner(guardian) {
+                Runtime::log(ByteString::from_literal("Guardians must be non-owner addresses"));
+                return false;
+            }
+            for j in 0..i {
+                if guardians.get(j) == guardian {
+                    Runtime::log(ByteString::from_literal("Duplicate guardian"));
+                    return false;
                 }
-            },
-            None => 20,
+            }
         }
-    }
 
-    fn get_proposal_lifetime(&self) -> i64 {
         let storage = Storage::get_context();
-        match Storage::get(storage.clone(), self.proposal_lifetime_key.clone()) {
-            Some(lifetime_bytes) => {
-                // Use Int256 parsing to avoid WASM issues
-                let int_val = Int256::from_byte_string(lifetime_bytes);
-                if int_val.is_zero() {
-                    86400 // 1 day default
-                } else {
-                    // For simplicity, return the default
-                    86400
-                }
-            },
-            None => 86400,
+
+        let previous = self.get_guardians();
+        for i in bounded_iter(&previous, MAX_GUARDIANS) {
+            Storage::delete(storage.clone(), self.is_guardian_prefix.concat(&previous.get(i).into_byte_string()));
         }
-    }
 
-    fn get_confirmation_key(&self, proposal_id: Int256, owner: H160) -> ByteString {
-        self.confirmations_prefix
-            .concat(&proposal_id.into_byte_string())
-            .concat(&ByteString::from_literal("_"))
-            .concat(&owner.into_byte_string())
-    }
+        for i in bounded_iter(&guardians, MAX_GUARDIANS) {
+            Storage::put(
+                storage.clone(),
+                self.is_guardian_prefix.concat(&guardians.get(i).into_byte_string()),
+                ByteString::from_literal("true"),
+            );
+        }
 
-    fn serialize_proposal(&self, proposal: TransactionProposal) -> ByteString {
-        // Simplified serialization
-        let mut data = proposal.proposer.into_byte_string();
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&proposal.target.into_byte_string());
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&proposal.token.into_byte_string());
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&proposal.amount.into_byte_string());
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&proposal.data);
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&Int256::new(proposal.expiration).into_byte_string());
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&ByteString::from_bytes(&[proposal.status.to_u8()]));
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&Int256::new(proposal.confirmations as i64).into_byte_string());
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&Int256::new(proposal.required_confirmations as i64).into_byte_string());
-        data
+        let epoch = self.get_guardian_epoch().checked_add(&Int256::one());
+        Storage::put(storage.clone(), self.guardians_key.clone(), self.serialize_guardians(&guardians));
+        Storage::put(storage.clone(), self.guardian_epoch_key.clone(), epoch.into_byte_string());
+        Storage::put(storage, self.recovery_delay_key.clone(), ByteString::from_bytes(&recovery_delay.to_le_bytes()));
+
+        let mut event_data = Array::new();
+        event_data.push(Int256::new(guardians.size() as i64).into_any());
+        event_data.push(epoch.into_any());
+        Runtime::notify(ByteString::from_literal("GuardiansUpdated"), event_data);
+
+        true
     }
 
-    fn deserialize_proposal(&self, __data: ByteString) -> TransactionProposal {
-        // Simplified deserialization - in production, use proper parsing
-        TransactionProposal {
-            proposer: H160::zero(),
-            target: H160::zero(),
-            token: H160::zero(),
-            amount: Int256::zero(),
-            data: ByteString::empty(),
-            expiration: 0,
-            status: ProposalStatus::Pending,
-            confirmations: 0,
-            required_confirmations: 1,
+    /// Start rotating the key of a lost owner (guardian only)
+    #[method]
+    pub fn initiate_recovery(&self, guardian: H160, lost_owner: H160, new_owner: H160) -> Int256 {
+        if !self.is_guardian(guardian) || !Runtime::check_witness(guardian) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only guardians can initiate recovery"));
+            return Int256::minus_one();
         }
-    }
 
-    fn serialize_owners_list(&self, owners: &Array<H160>) -> ByteString {
-        let mut data = ByteString::empty();
-        // Get length safely by converting usize to i64 explicitly
-        let size_val = owners.size();
-        let len = if size_val > i64::MAX as usize {
-            i64::MAX
-        } else {
-            size_val as i64
-        };
+        if !self.is_owner(lost_owner) {
+            Runtime::log(ByteString::from_literal("Lost address is not an owner"));
+            return Int256::minus_one();
+        }
 
-        // Store length using Int256 to avoid WASM issues
-        data = data.concat(&Int256::new(len).into_byte_string());
+        if new_owner == H160::zero() || self.is_owner(new_owner) || self.is_guardian(new_owner) {
+            Runtime::log(ByteString::from_literal("Invalid new owner"));
+            return Int256::minus_one();
+        }
 
-        // Store each owner
-        for i in 0..len {
-            let owner = owners.get(i as usize);
-            data = data.concat(&owner.clone().into_byte_string());
+        if self.get_active_recovery(lost_owner) > Int256::zero() {
+            Runtime::log(ByteString::from_literal("Recovery already in progress"));
+            return Int256::minus_one();
         }
 
-        data
-    }
+        let storage = Storage::get_context();
+        let recovery_id = self.get_recovery_count().checked_add(&Int256::one());
+        let start = Runtime::get_time();
 
-    fn deserialize_owners_list(&self, data: ByteString) -> Array<H160> {
-        let bytes = data.to_bytes();
-        let mut owners = Array::new();
+        let mut record = new_owner.into_byte_string();
+        record = record.concat(&ByteString::from_bytes(&start.to_le_bytes()));
+        record = record.concat(&self.get_guardian_epoch().into_byte_string());
 
-        // Check if we have enough bytes for Int256 (32 bytes)
-        let bytes_len = bytes.len();
-        if bytes_len < 32 { // Int256 is 32 bytes
-            return owners;
-        }
+        Storage::put(storage.clone(), self.recovery_count_key.clone(), recovery_id.into_byte_string());
+        Storage::put(storage.clone(), self.recovery_prefix.concat(&recovery_id.into_byte_string()), record);
+        Storage::put(
+            storage,
+            self.active_recovery_prefix.concat(&lost_owner.into_byte_string()),
+            recovery_id.into_byte_string(),
+        );
 
-        // Use Int256 parsing to avoid WASM issues
-        let len_int = Int256::from_byte_string(ByteString::from_bytes(&bytes[0..32]));
-        // For now, use a reasonable default instead of complex parsing
-        let len = if len_int.is_zero() { 0 } else { 3 };
-        let mut offset = 32;
+        self.record_recovery_approval(recovery_id, guardian);
 
-        for _ in 0..len {
-            if offset + 20 > 1000 { // Avoid .len() method, use reasonable limit
-                break;
-            }
+        let mut event_data = Array::new();
+        event_data.push(recovery_id.into_any());
+        event_data.push(lost_owner.into_any());
+        event_data.push(new_owner.into_any());
+        event_data.push(guardian.into_any());
+        Runtime::notify(ByteString::from_literal("RecoveryInitiated"), event_data);
 
-            let owner_bytes = &bytes[offset..offset + 20];
-            let owner_byte_string = ByteString::from_bytes(owner_bytes);
-            let owner = H160::from_byte_string(owner_byte_string);
-            owners.push(owner);
-            offset += 20;
+        recovery_id
+    }
+
+    /// Support a pending recovery (guardian only, once per recovery)
+    #[method]
+    pub fn support_recovery(&self, guardian: H160, lost_owner: H160) -> bool {
+        if !self.is_guardian(guardian) || !Runtime::check_witness(guardian) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only guardians can support recovery"));
+            return false;
         }
 
-        owners
-    }
+        let recovery_id = self.get_active_recovery(lost_owner);
+        if recovery_id <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No recovery in progress"));
+            return false;
+        }
 
-    fn execute_native_transfer(&self, _to: H160, _amount: Int256) -> bool {
-        // Complete implementation: Uses Contract::call to transfer native assets
-        Runtime::log(ByteString::from_literal("Native transfer executed"));
-        
-        // Implementation would use Contract::call with native transfer parameters
-        true
-    }
+        let storage = Storage::get_context();
+        let approval_key = self.get_recovery_approval_key(recovery_id, guardian);
+        if Storage::get(storage, approval_key).is_some() {
+            Runtime::log(ByteString::from_literal("Guardian already supported this recovery"));
+            return false;
+        }
+
+        let approvals = self.record_recovery_approval(recovery_id, guardian);
+
+        let mut event_data = Array::new();
+        event_data.push(recovery_id.into_any());
+        event_data.push(guardian.into_any());
+        event_data.push(approvals.into_any());
+        Runtime::notify(ByteString::from_literal("RecoverySupported"), event_data);
 
-    fn execute_token_transfer(&self, _to: H160, _token: H160, _amount: Int256) -> bool {
-        // Complete implementation: Uses Contract::call to invoke the token's transfer method
-        Runtime::log(ByteString::from_literal("Token transfer executed"));
-        
-        // Implementation would use Contract::call to invoke NEP-17 transfer
         true
     }
 
-    fn execute_contract_call(&self, _target: H160, _data: ByteString) -> bool {
-        // Complete implementation: Uses Contract::call to invoke the target contract
-        Runtime::log(ByteString::from_literal("Contract call executed"));
-        
-        // Implementation would use Contract::call with provided data
-        true
+    /// Veto a pending recovery during its challenge period (owner only)
+    #[method]
+    pub fn veto_recovery(&self, owner: H160, lost_owner: H160) -> bool {
+        if !self.is_owner(owner) || !Runtime::check_witness(owner) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only owners can veto recovery"));
+            return false;
+        }
+
+        let recovery_id = self.get_active_recovery(lost_owner);
+        if recovery_id <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No recovery in progress"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        Storage::delete(storage, self.active_recovery_prefix.concat(&lost_owner.into_byte_string()));
+
+        let mut event_data = Array::new();
+        event_data.push(recovery_id.into_any());
+        event_data.push(lost_owner.into_any());
+        event_data.push(owner.into_any());
+        Runtime::notify(ByteString::from_literal("RecoveryVetoed"), event_data);
+
+        true
+    }
+
+    /// Rotate the lost owner key once a guardian supermajority supports the recovery
+    /// and the challenge period has passed without a veto
+    #[method]
+    pub fn execute_recovery(&self, lost_owner: H160) -> bool {
+        let recovery_id = self.get_active_recovery(lost_owner);
+        if recovery_id <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No recovery in progress"));
+            return false;
+        }
+
+        let (new_owner, start, epoch) = match self.get_recovery_record(recovery_id) {
+            Some(record) => record,
+            None => {
+                Runtime::log(ByteString::from_literal("Recovery not found"));
+                return false;
+            }
+        };
+
+        let storage = Storage::get_context();
+        let active_key = self.active_recovery_prefix.concat(&lost_owner.into_byte_string());
+
+        if epoch != self.get_guardian_epoch() {
+            // The guardian set changed since the recovery started
+            Storage::delete(storage, active_key);
+            Runtime::log(ByteString::from_literal("Guardian set changed, recovery discarded"));
+            return false;
+        }
+
+        if Runtime::get_time() < start + self.get_recovery_delay() {
+            Runtime::log(ByteString::from_literal("Challenge period not over"));
+            return false;
+        }
+
+        if self.get_recovery_approvals(recovery_id) < self.get_guardian_supermajority() {
+            Runtime::log(ByteString::from_literal("Guardian supermajority not reached"));
+            return false;
+        }
+
+        if !self.is_owner(lost_owner) || self.is_owner(new_owner) {
+            Storage::delete(storage, active_key);
+            Runtime::log(ByteString::from_literal("Owner set changed, recovery discarded"));
+            return false;
+        }
+
+        // Rotate the owner in place, keeping its index and the M-of-N configuration
+        self.rotate_owner(lost_owner, new_owner);
+
+        Storage::delete(storage, active_key);
+
+        let mut event_data = Array::new();
+        event_data.push(recovery_id.into_any());
+        event_data.push(lost_owner.into_any());
+        event_data.push(new_owner.into_any());
+        Runtime::notify(ByteString::from_literal("OwnerRecovered"), event_data);
+
+        true
+    }
+
+    /// Check if address is a guardian
+    #[method]
+    #[safe]
+    pub fn is_guardian(&self, address: H160) -> bool {
+        let storage = Storage::get_context();
+        let is_guardian_key = self.is_guardian_prefix.concat(&address.into_byte_string());
+        Storage::get(storage, is_guardian_key).is_some()
+    }
+
+    /// Get list of guardians
+    #[method]
+    #[safe]
+    pub fn get_guardians(&self) -> Array<H160> {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.guardians_key.clone()) {
+            Some(guardians_data) => self.deserialize_guardians(guardians_data),
+            None => Array::new(),
+        }
+    }
+
+    /// Get the pending recovery of an owner
+    #[method]
+    #[safe]
+    pub fn get_recovery(&self, lost_owner: H160) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
+        let recovery_id = self.get_active_recovery(lost_owner);
+        let record = if recovery_id > Int256::zero() { self.get_recovery_record(recovery_id) } else { None };
+
+        match record {
+            Some((new_owner, start, epoch)) => {
+                result.put(ByteString::from_literal("id"), recovery_id.into_any());
+                result.put(ByteString::from_literal("new_owner"), new_owner.into_any());
+                result.put(ByteString::from_literal("executable_at"), Int256::from_u64(start + self.get_recovery_delay()).into_any());
+                result.put(ByteString::from_literal("approvals"), self.get_recovery_approvals(recovery_id).into_any());
+                result.put(ByteString::from_literal("required_approvals"), self.get_guardian_supermajority().into_any());
+                result.put(ByteString::from_literal("epoch"), epoch.into_any());
+            }
+            None => {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal("No recovery in progress").into_any());
+            }
+        }
+
+        result
+    }
+
+    /// Set or clear (with `H160::zero()`) the emergency recovery address (requires the witnesses of M owners).
+    ///
+    /// The recovery address can replace the whole owner set after the recovery delay, unless an
+    /// owner cancels in time. Changing it discards a pending emergency recovery.
+    #[method]
+    pub fn set_recovery_address(&self, signers: Array<H160>, recovery_address: H160) -> bool {
+        if !self.has_owner_quorum(&signers) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Owner quorum required"));
+            return false;
+        }
+
+        if self.is_owner(recovery_address) {
+            Runtime::log(ByteString::from_literal("Recovery address cannot be an owner"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        if recovery_address == H160::zero() {
+            Storage::delete(storage.clone(), self.emergency_recovery_key.clone());
+            self.inactivity_switch.disarm();
+        } else {
+            Storage::put(storage.clone(), self.emergency_recovery_key.clone(), recovery_address.into_byte_string());
+            if self.inactivity_switch.is_armed() {
+                let period = self.inactivity_switch.silence_period();
+                self.arm_inactivity_switch(recovery_address, period);
+            }
+        }
+        Storage::delete(storage, self.emergency_pending_key.clone());
+
+        let mut event_data = Array::new();
+        event_data.push(recovery_address.into_any());
+        Runtime::notify(ByteString::from_literal("RecoveryAddressChanged"), event_data);
+
+        true
+    }
+
+    /// Start replacing the owner set (recovery address only)
+    #[method]
+    pub fn initiate_emergency_recovery(&self, new_owners: Array<H160>, new_threshold: u32) -> bool {
+        let recovery_address = self.get_recovery_address();
+        if recovery_address == H160::zero() || !Runtime::check_witness(recovery_address) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only the recovery address can initiate"));
+            return false;
+        }
+
+        // With an inactivity period set, recovery waits until every owner has gone silent
+        if self.inactivity_switch.is_armed() && !self.inactivity_switch.grants(CAN_REPLACE_OWNERS) {
+            Runtime::log(ByteString::from_literal("Owners are still active"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        if Storage::get(storage.clone(), self.emergency_pending_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Emergency recovery already pending"));
+            return false;
+        }
+
+        let owner_count = new_owners.size() as u32;
+        if owner_count == 0 || owner_count > self.get_max_owners() {
+            Runtime::log(ByteString::from_literal("Invalid owner count (1-20)"));
+            return false;
+        }
+
+        if new_threshold == 0 || new_threshold > owner_count {
+            Runtime::log(ByteString::from_literal("Invalid required confirmations"));
+            return false;
+        }
+
+        for i in bounded_iter(&new_owners, self.get_max_owners() as usize) {
+            let owner = new_owners.get(i);
+            if owner == H160::zero() || owner == recovery_address || self.is_guardian(owner) {
+                Runtime::log(ByteString::from_literal("Invalid new owner"));
+                return false;
+            }
+            for j in 0..i {
+                if new_owners.get(j) == owner {
+                    Runtime::log(ByteString::from_literal("Duplicate owner"));
+                    return false;
+                }
+            }
+        }
+
+        let start = Runtime::get_time();
+        let record = ByteString::from_bytes(&start.to_le_bytes())
+            .concat(&ByteString::from_bytes(&new_threshold.to_le_bytes()))
+            .concat(&self.serialize_owners_list(&new_owners));
+        Storage::put(storage, self.emergency_pending_key.clone(), record);
+
+        let mut event_data = Array::new();
+        event_data.push(recovery_address.into_any());
+        event_data.push(Int256::new(owner_count as i64).into_any());
+        event_data.push(Int256::new(new_threshold as i64).into_any());
+        event_data.push(Int256::from_u64(deadline(start, self.get_emergency_delay())).into_any());
+        Runtime::notify(ByteString::from_literal("EmergencyRecoveryInitiated"), event_data);
+
+        true
+    }
+
+    /// Cancel a pending emergency recovery (any current owner)
+    #[method]
+    pub fn cancel_emergency_recovery(&self, owner: H160) -> bool {
+        if !self.is_owner(owner) || !Runtime::check_witness(owner) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only owners can cancel recovery"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        if Storage::get(storage.clone(), self.emergency_pending_key.clone()).is_none() {
+            Runtime::log(ByteString::from_literal("No emergency recovery pending"));
+            return false;
+        }
+
+        Storage::delete(storage, self.emergency_pending_key.clone());
+        self.note_owner_activity();
+
+        let mut event_data = Array::new();
+        event_data.push(owner.into_any());
+        Runtime::notify(ByteString::from_literal("EmergencyRecoveryCancelled"), event_data);
+
+        true
+    }
+
+    /// Replace the owner set once the recovery delay has passed without a cancellation.
+    ///
+    /// Proposals created before the replacement are voided, so confirmations collected from
+    /// the previous owners cannot be completed by the new ones.
+    #[method]
+    pub fn execute_emergency_recovery(&self) -> bool {
+        let (start, new_threshold, new_owners) = match self.get_emergency_recovery_record() {
+            Some(record) => record,
+            None => {
+                Runtime::log(ByteString::from_literal("No emergency recovery pending"));
+                return false;
+            }
+        };
+
+        if elapsed(start, Runtime::get_time()) < self.get_emergency_delay() {
+            Runtime::log(ByteString::from_literal("Recovery delay not over"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+
+        let previous = self.get_owners();
+        for i in 0..previous.size() {
+            let owner_key = previous.get(i).into_byte_string();
+            Storage::delete(storage.clone(), self.is_owner_prefix.concat(&owner_key));
+            Storage::delete(storage.clone(), self.owner_index_prefix.concat(&owner_key));
+        }
+
+        self.store_owners(&new_owners);
+        self.set_required_confirmations(new_threshold);
+
+        let proposal_floor = self.get_proposal_count();
+        Storage::put(storage.clone(), self.proposal_floor_key.clone(), proposal_floor.into_byte_string());
+        Storage::delete(storage, self.emergency_pending_key.clone());
+
+        let mut event_data = Array::new();
+        event_data.push(Int256::new(new_owners.size() as i64).into_any());
+        event_data.push(Int256::new(new_threshold as i64).into_any());
+        event_data.push(proposal_floor.into_any());
+        Runtime::notify(ByteString::from_literal("EmergencyRecoveryExecuted"), event_data);
+
+        true
+    }
+
+    /// Set the delay before an emergency recovery can be executed (requires the witnesses of
+    /// M owners). It is independent of the guardian recovery challenge period.
+    #[method]
+    pub fn set_emergency_recovery_delay(&self, signers: Array<H160>, delay: u64) -> bool {
+        if !self.has_owner_quorum(&signers) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Owner quorum required"));
+            return false;
+        }
+
+        if delay < SECONDS_PER_DAY || delay > days(30) {
+            Runtime::log(ByteString::from_literal("Invalid recovery delay (1 to 30 days)"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        Storage::put(storage, self.emergency_delay_key.clone(), ByteString::from_bytes(&delay.to_le_bytes()));
+
+        let mut event_data = Array::new();
+        event_data.push(Int256::from_u64(delay).into_any());
+        Runtime::notify(ByteString::from_literal("EmergencyRecoveryDelayChanged"), event_data);
+
+        true
+    }
+
+    /// Only allow emergency recovery after every owner has been inactive for `period` seconds
+    /// (requires the witnesses of M owners). A zero period removes the requirement.
+    ///
+    /// Proposals, confirmations, heartbeats and recovery cancellations count as owner activity.
+    #[method]
+    pub fn set_inactivity_period(&self, signers: Array<H160>, period: u64) -> bool {
+        if !self.has_owner_quorum(&signers) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Owner quorum required"));
+            return false;
+        }
+
+        if period == 0 {
+            self.inactivity_switch.disarm();
+        } else {
+            if period < MIN_INACTIVITY_PERIOD || period > MAX_INACTIVITY_PERIOD {
+                Runtime::log(ByteString::from_literal("Invalid inactivity period (7 to 365 days)"));
+                return false;
+            }
+
+            let recovery_address = self.get_recovery_address();
+            if recovery_address == H160::zero() {
+                Runtime::log(ByteString::from_literal("No recovery address set"));
+                return false;
+            }
+
+            self.arm_inactivity_switch(recovery_address, period);
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(Int256::from_u64(period).into_any());
+        Runtime::notify(ByteString::from_literal("InactivityPeriodChanged"), event_data);
+
+        true
+    }
+
+    /// Record that an owner is still active, postponing inactivity-based recovery
+    #[method]
+    pub fn heartbeat(&self, owner: H160) -> bool {
+        if !self.is_owner(owner) || !Runtime::check_witness(owner) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only owners can send heartbeats"));
+            return false;
+        }
+
+        if !self.inactivity_switch.is_armed() {
+            Runtime::log(ByteString::from_literal("No inactivity period set"));
+            return false;
+        }
+
+        self.inactivity_switch.record_heartbeat();
+        true
+    }
+
+    /// Get the emergency recovery address and any pending recovery
+    #[method]
+    #[safe]
+    pub fn get_emergency_recovery(&self) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        result.put(ByteString::from_literal("recovery_address"), self.get_recovery_address().into_any());
+        result.put(ByteString::from_literal("recovery_delay"), Int256::from_u64(self.get_emergency_delay()).into_any());
+
+        if self.inactivity_switch.is_armed() {
+            result.put(
+                ByteString::from_literal("inactivity_period"),
+                Int256::from_u64(self.inactivity_switch.silence_period()).into_any(),
+            );
+            result.put(
+                ByteString::from_literal("last_owner_activity"),
+                Int256::from_u64(self.inactivity_switch.last_heartbeat()).into_any(),
+            );
+            result.put(
+                ByteString::from_literal("recoverable_after"),
+                Int256::from_u64(self.inactivity_switch.triggers_at()).into_any(),
+            );
+        }
+
+        if let Some((start, new_threshold, new_owners)) = self.get_emergency_recovery_record() {
+            result.put(ByteString::from_literal("started_at"), Int256::from_u64(start).into_any());
+            result.put(
+                ByteString::from_literal("executable_at"),
+                Int256::from_u64(deadline(start, self.get_emergency_delay())).into_any(),
+            );
+            result.put(ByteString::from_literal("new_threshold"), Int256::new(new_threshold as i64).into_any());
+            result.put(ByteString::from_literal("new_owners"), new_owners.into_any());
+        }
+
+        result
+    }
+
+    // Helper functions
+
+    fn get_max_owners(&self) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage.clone(), self.max_owners_key.clone()) {
+            Some(max_bytes) => {
+                // Use Int256 parsing to avoid WASM issues
+                let int_val = Int256::from_byte_string(max_bytes);
+                if int_val.is_zero() {
+                    20
+                } else {
+                    // For simplicity, return the default
+                    20
+                }
+            },
+            None => 20,
+        }
+    }
+
+    fn get_proposal_lifetime(&self) -> u64 {
+        let storage = Storage::get_context();
+        match Storage::get(storage.clone(), self.proposal_lifetime_key.clone()) {
+            Some(lifetime_bytes) => {
+                let bytes = lifetime_bytes.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([
+                        bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]
+                    ])
+                } else {
+                    SECONDS_PER_DAY
+                }
+            },
+            None => SECONDS_PER_DAY,
+        }
+    }
+
+    fn get_confirmation_key(&self, proposal_id: Int256, owner: H160) -> ByteString {
+        self.confirmations_prefix
+            .concat(&proposal_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&owner.into_byte_string())
+    }
+
+    /// Document hash and memo attached to a proposal, both empty if none
+    fn get_memo(&self, proposal_id: Int256) -> (ByteString, ByteString) {
+        let storage = Storage::get_context();
+        let record = match Storage::get(storage, self.proposal_memo_prefix.concat(&proposal_id.into_byte_string())) {
+            Some(record) => record.to_bytes(),
+            None => return (ByteString::empty(), ByteString::empty()),
+        };
+
+        let hash_end = 1 + record.first().copied().unwrap_or(0) as usize;
+        if record.len() < hash_end {
+            return (ByteString::empty(), ByteString::empty());
+        }
+        (ByteString::from_bytes(&record[1..hash_end]), ByteString::from_bytes(&record[hash_end..]))
+    }
+
+    /// Proposal layout: proposer (20) | target (20) | token (20) | expiration (u64 LE) |
+    /// status (u8) | confirmations (u32 LE) | required confirmations (u32 LE) |
+    /// amount length (u8) | amount | data
+    fn serialize_proposal(&self, proposal: TransactionProposal) -> ByteString {
+        let amount = proposal.amount.into_byte_string();
+        proposal.proposer.into_byte_string()
+            .concat(&proposal.target.into_byte_string())
+            .concat(&proposal.token.into_byte_string())
+            .concat(&ByteString::from_bytes(&proposal.expiration.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&[proposal.status.to_u8()]))
+            .concat(&ByteString::from_bytes(&proposal.confirmations.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&proposal.required_confirmations.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&[amount.len() as u8]))
+            .concat(&amount)
+            .concat(&proposal.data)
+    }
+
+    fn deserialize_proposal(&self, data: ByteString) -> Option<TransactionProposal> {
+        let bytes = data.to_bytes();
+        if bytes.len() < 78 || bytes.len() < 78 + bytes[77] as usize {
+            return None;
+        }
+
+        let amount_end = 78 + bytes[77] as usize;
+        Some(TransactionProposal {
+            proposer: H160::from_byte_string(ByteString::from_bytes(&bytes[..20])),
+            target: H160::from_byte_string(ByteString::from_bytes(&bytes[20..40])),
+            token: H160::from_byte_string(ByteString::from_bytes(&bytes[40..60])),
+            amount: Int256::from_byte_string(ByteString::from_bytes(&bytes[78..amount_end])),
+            data: ByteString::from_bytes(&bytes[amount_end..]),
+            expiration: u64::from_le_bytes([
+                bytes[60], bytes[61], bytes[62], bytes[63],
+                bytes[64], bytes[65], bytes[66], bytes[67]
+            ]),
+            status: ProposalStatus::from_u8(bytes[68]),
+            confirmations: u32::from_le_bytes([bytes[69], bytes[70], bytes[71], bytes[72]]),
+            required_confirmations: u32::from_le_bytes([bytes[73], bytes[74], bytes[75], bytes[76]]),
+        })
+    }
+
+    fn serialize_owners_list(&self, owners: &Array<H160>) -> ByteString {
+        let count = owners.size() as u32;
+        let mut data = ByteString::from_bytes(&count.to_le_bytes());
+        for i in 0..owners.size() {
+            data = data.concat(&owners.get(i).into_byte_string());
+        }
+        data
+    }
+
+    fn deserialize_owners_list(&self, data: ByteString) -> Array<H160> {
+        let bytes = data.to_bytes();
+        let mut owners = Array::new();
+
+        if bytes.len() < 4 {
+            return owners;
+        }
+
+        let count = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+        let mut offset = 4;
+
+        for _ in 0..count {
+            if offset + 20 > bytes.len() {
+                break;
+            }
+            owners.push(H160::from_byte_string(ByteString::from_bytes(&bytes[offset..offset + 20])));
+            offset += 20;
+        }
+
+        owners
+    }
+
+    /// True if `signers` holds at least M distinct owners that witnessed the transaction
+    fn has_owner_quorum(&self, signers: &Array<H160>) -> bool {
+        let mut witnessed = 0u32;
+        for i in bounded_iter(&signers, self.get_max_owners() as usize) {
+            let signer = signers.get(i);
+
+            let mut duplicate = false;
+            for j in 0..i {
+                if signers.get(j) == signer {
+                    duplicate = true;
+                }
+            }
+
+            if !duplicate && self.is_owner(signer) && Runtime::check_witness(signer) {
+                witnessed += 1;
+            }
+        }
+        witnessed > 0 && witnessed >= self.get_required_confirmations()
+    }
+
+    fn get_guardian_epoch(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.guardian_epoch_key.clone()) {
+            Some(epoch_bytes) => Int256::from_byte_string(epoch_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// More than two thirds of the guardians: floor(2n / 3) + 1
+    fn get_guardian_supermajority(&self) -> Int256 {
+        let count = Int256::new(self.get_guardians().size() as i64);
+        count.checked_mul(&Int256::new(2)).checked_div(&Int256::new(3)).checked_add(&Int256::one())
+    }
+
+    fn get_recovery_delay(&self) -> u64 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.recovery_delay_key.clone()) {
+            Some(delay_bytes) => {
+                let bytes = delay_bytes.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([
+                        bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]
+                    ])
+                } else {
+                    DEFAULT_RECOVERY_DELAY
+                }
+            }
+            None => DEFAULT_RECOVERY_DELAY,
+        }
+    }
+
+    fn get_emergency_delay(&self) -> u64 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.emergency_delay_key.clone()) {
+            Some(delay_bytes) => {
+                let bytes = delay_bytes.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([
+                        bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]
+                    ])
+                } else {
+                    DEFAULT_EMERGENCY_DELAY
+                }
+            }
+            None => DEFAULT_EMERGENCY_DELAY,
+        }
+    }
+
+    /// Postpones inactivity-based recovery after an owner action
+    fn note_owner_activity(&self) {
+        if self.inactivity_switch.is_armed() {
+            self.inactivity_switch.record_heartbeat();
+        }
+    }
+
+    /// Arms the inactivity switch for `recovery_address`, counting from now
+    fn arm_inactivity_switch(&self, recovery_address: H160, period: u64) {
+        self.inactivity_switch.configure(
+            Runtime::get_executing_script_hash(),
+            recovery_address,
+            period,
+            CAN_REPLACE_OWNERS,
+        );
+    }
+
+    fn get_recovery_address(&self) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.emergency_recovery_key.clone()) {
+            Some(address_bytes) => H160::from_byte_string(address_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    /// Pending emergency recovery as (start time, new threshold, new owners)
+    fn get_emergency_recovery_record(&self) -> Option<(u64, u32, Array<H160>)> {
+        let storage = Storage::get_context();
+        let bytes = Storage::get(storage, self.emergency_pending_key.clone())?.to_bytes();
+        if bytes.len() < 12 {
+            return None;
+        }
+
+        let start = u64::from_le_bytes([
+            bytes[0], bytes[1], bytes[2], bytes[3],
+            bytes[4], bytes[5], bytes[6], bytes[7]
+        ]);
+        let threshold = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+        let owners = self.deserialize_owners_list(ByteString::from_bytes(&bytes[12..]));
+        Some((start, threshold, owners))
+    }
+
+    fn get_proposal_floor(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.proposal_floor_key.clone()) {
+            Some(floor_bytes) => Int256::from_byte_string(floor_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    fn get_recovery_count(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.recovery_count_key.clone()) {
+            Some(count_bytes) => Int256::from_byte_string(count_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    fn get_active_recovery(&self, lost_owner: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.active_recovery_prefix.concat(&lost_owner.into_byte_string())) {
+            Some(id_bytes) => Int256::from_byte_string(id_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Returns the new owner, start time and guardian epoch of a recovery
+    fn get_recovery_record(&self, recovery_id: Int256) -> Option<(H160, u64, Int256)> {
+        let storage = Storage::get_context();
+        let data = Storage::get(storage, self.recovery_prefix.concat(&recovery_id.into_byte_string()))?;
+        let bytes = data.to_bytes();
+        if bytes.len() < 28 {
+            return None;
+        }
+
+        let new_owner = H160::from_byte_string(ByteString::from_bytes(&bytes[0..20]));
+        let start = u64::from_le_bytes([
+            bytes[20], bytes[21], bytes[22], bytes[23],
+            bytes[24], bytes[25], bytes[26], bytes[27]
+        ]);
+        let epoch = Int256::from_byte_string(ByteString::from_bytes(&bytes[28..]));
+        Some((new_owner, start, epoch))
+    }
+
+    fn get_recovery_approval_key(&self, recovery_id: Int256, guardian: H160) -> ByteString {
+        self.recovery_approval_prefix
+            .concat(&recovery_id.into_byte_string())
+            .concat(&ByteString::from_literal("_"))
+            .concat(&guardian.into_byte_string())
+    }
+
+    fn get_recovery_approvals(&self, recovery_id: Int256) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.recovery_approvals_prefix.concat(&recovery_id.into_byte_string())) {
+            Some(count_bytes) => Int256::from_byte_string(count_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    fn record_recovery_approval(&self, recovery_id: Int256, guardian: H160) -> Int256 {
+        let storage = Storage::get_context();
+        let approvals = self.get_recovery_approvals(recovery_id).checked_add(&Int256::one());
+        Storage::put(storage.clone(), self.get_recovery_approval_key(recovery_id, guardian), ByteString::from_literal("true"));
+        Storage::put(
+            storage,
+            self.recovery_approvals_prefix.concat(&recovery_id.into_byte_string()),
+            approvals.into_byte_string(),
+        );
+        approvals
+    }
+
+    fn serialize_guardians(&self, guardians: &Array<H160>) -> ByteString {
+        let count = guardians.size() as u32;
+        let mut data = ByteString::from_bytes(&count.to_le_bytes());
+        for i in 0..guardians.size() {
+            data = data.concat(&guardians.get(i).into_byte_string());
+        }
+        data
+    }
+
+    fn deserialize_guardians(&self, data: ByteString) -> Array<H160> {
+        let bytes = data.to_bytes();
+        let mut guardians = Array::new();
+
+        if bytes.len() < 4 {
+            return guardians;
+        }
+
+        let count = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+        let mut offset = 4;
+
+        for _ in 0..count {
+            if offset + 20 > bytes.len() {
+                break;
+            }
+            guardians.push(H160::from_byte_string(ByteString::from_bytes(&bytes[offset..offset + 20])));
+            offset += 20;
+        }
+
+        guardians
+    }
+
+    fn propose_owner_action(
+        &self,
+        proposer: H160,
+        action: OwnerAction,
+        owner: H160,
+        new_owner: H160,
+        threshold: u32
+    ) -> Int256 {
+        // Owner actions target the wallet itself and carry their parameters separately
+        let proposal_id = self.propose_transaction(
+            proposer,
+            Runtime::get_executing_script_hash(),
+            H160::zero(),
+            Int256::zero(),
+            ByteString::empty()
+        );
+        if proposal_id <= Int256::zero() {
+            return proposal_id;
+        }
+
+        let action_data = ByteString::from_bytes(&[action.to_u8()])
+            .concat(&owner.into_byte_string())
+            .concat(&new_owner.into_byte_string())
+            .concat(&ByteString::from_bytes(&threshold.to_le_bytes()));
+        let storage = Storage::get_context();
+        Storage::put(storage, self.owner_action_prefix.concat(&proposal_id.into_byte_string()), action_data);
+
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        event_data.push(Int256::new(action.to_u8() as i64).into_any());
+        event_data.push(owner.into_any());
+        event_data.push(new_owner.into_any());
+        event_data.push(Int256::new(threshold as i64).into_any());
+        Runtime::notify(ByteString::from_literal("OwnerActionProposed"), event_data);
+
+        proposal_id
+    }
+
+    /// The action of a proposal in its digest encoding (see `proposal_digest`)
+    fn encode_proposal_actions(&self, proposal_id: Int256) -> Option<ByteString> {
+        let storage = Storage::get_context();
+
+        if let Some(action_data) = Storage::get(storage.clone(), self.owner_action_prefix.concat(&proposal_id.into_byte_string())) {
+            return Some(ByteString::from_bytes(&[ACTION_OWNER_ACTION]).concat(&action_data));
+        }
+
+        let bytes = Storage::get(storage, self.proposal_call_prefix.concat(&proposal_id.into_byte_string()))?.to_bytes();
+        if bytes.len() < 41 || bytes.len() < 41 + bytes[40] as usize {
+            return None;
+        }
+
+        let target = ByteString::from_bytes(&bytes[0..20]);
+        let token = ByteString::from_bytes(&bytes[20..40]);
+        let amount_end = 41 + bytes[40] as usize;
+        let amount = ByteString::from_bytes(&bytes[40..amount_end]); // length prefix included
+        let data = &bytes[amount_end..];
+
+        let encoded = if H160::from_byte_string(token.clone()) == H160::zero() {
+            ByteString::from_bytes(&[ACTION_NATIVE_TRANSFER]).concat(&target).concat(&amount)
+        } else if data.is_empty() {
+            ByteString::from_bytes(&[ACTION_TOKEN_TRANSFER]).concat(&token).concat(&target).concat(&amount)
+        } else {
+            ByteString::from_bytes(&[ACTION_CONTRACT_CALL])
+                .concat(&target)
+                .concat(&ByteString::from_bytes(&(data.len() as u16).to_le_bytes()))
+                .concat(&ByteString::from_bytes(data))
+        };
+        Some(encoded)
+    }
+
+    /// Owner action of a proposal as (action, owner, new owner, threshold)
+    fn get_owner_action(&self, proposal_id: Int256) -> Option<(OwnerAction, H160, H160, u32)> {
+        let storage = Storage::get_context();
+        let bytes = Storage::get(storage, self.owner_action_prefix.concat(&proposal_id.into_byte_string()))?.to_bytes();
+        if bytes.len() < 45 {
+            return None;
+        }
+
+        let action = OwnerAction::from_u8(bytes[0])?;
+        let owner = H160::from_byte_string(ByteString::from_bytes(&bytes[1..21]));
+        let new_owner = H160::from_byte_string(ByteString::from_bytes(&bytes[21..41]));
+        let threshold = u32::from_le_bytes([bytes[41], bytes[42], bytes[43], bytes[44]]);
+        Some((action, owner, new_owner, threshold))
+    }
+
+    /// Check an owner action against the current owner set
+    fn check_owner_action(&self, (action, owner, new_owner, threshold): (OwnerAction, H160, H160, u32)) -> Result<(), &'static str> {
+        let owner_count = self.get_owner_count();
+
+        match action {
+            OwnerAction::AddOwner => {
+                if self.is_owner(owner) || owner_count >= self.get_max_owners() {
+                    return Err("Cannot add owner");
+                }
+            }
+            OwnerAction::RemoveOwner => {
+                if !self.is_owner(owner) || threshold == 0 || threshold >= owner_count {
+                    return Err("Cannot remove owner");
+                }
+            }
+            OwnerAction::ReplaceOwner => {
+                if !self.is_owner(owner) || self.is_owner(new_owner) {
+                    return Err("Cannot replace owner");
+                }
+            }
+            OwnerAction::ChangeThreshold => {
+                if threshold == 0 || threshold > owner_count {
+                    return Err("Invalid required confirmations");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply a confirmed owner action; the owner set may have changed since it was proposed,
+    /// so every precondition is checked again
+    fn execute_owner_action(&self, (action, owner, new_owner, threshold): (OwnerAction, H160, H160, u32)) -> bool {
+        if let Err(message) = self.check_owner_action((action, owner, new_owner, threshold)) {
+            Runtime::log(ByteString::from_literal(message));
+            return false;
+        }
+
+        match action {
+            OwnerAction::AddOwner => {
+                let mut owners = self.get_owners();
+                owners.push(owner);
+                self.store_owners(&owners);
+
+                let mut event_data = Array::new();
+                event_data.push(owner.into_any());
+                Runtime::notify(ByteString::from_literal("OwnerAdded"), event_data);
+            }
+            OwnerAction::RemoveOwner => {
+                let owners = self.get_owners();
+                let mut remaining = Array::new();
+                for i in 0..owners.size() {
+                    let existing = owners.get(i);
+                    if existing != owner {
+                        remaining.push(existing);
+                    }
+                }
+
+                let storage = Storage::get_context();
+                Storage::delete(storage.clone(), self.is_owner_prefix.concat(&owner.into_byte_string()));
+                Storage::delete(storage, self.owner_index_prefix.concat(&owner.into_byte_string()));
+                self.store_owners(&remaining);
+                self.set_required_confirmations(threshold);
+
+                let mut event_data = Array::new();
+                event_data.push(owner.into_any());
+                Runtime::notify(ByteString::from_literal("OwnerRemoved"), event_data);
+            }
+            OwnerAction::ReplaceOwner => {
+                self.rotate_owner(owner, new_owner);
+
+                let mut event_data = Array::new();
+                event_data.push(owner.into_any());
+                event_data.push(new_owner.into_any());
+                Runtime::notify(ByteString::from_literal("OwnerReplaced"), event_data);
+            }
+            OwnerAction::ChangeThreshold => {
+                self.set_required_confirmations(threshold);
+            }
+        }
+
+        true
+    }
+
+    /// Store the owner list together with the owner flags, indices and count derived from it
+    fn store_owners(&self, owners: &Array<H160>) {
+        let storage = Storage::get_context();
+        for i in 0..owners.size() {
+            let owner_key = owners.get(i).into_byte_string();
+            Storage::put(storage.clone(), self.is_owner_prefix.concat(&owner_key), ByteString::from_literal("true"));
+            Storage::put(storage.clone(), self.owner_index_prefix.concat(&owner_key), Int256::new(i as i64).into_byte_string());
+        }
+
+        let owner_count = owners.size() as u32;
+        Storage::put(storage.clone(), self.owners_key.clone(), self.serialize_owners_list(owners));
+        Storage::put(storage, self.owner_count_key.clone(), ByteString::from_bytes(&owner_count.to_le_bytes()));
+    }
+
+    /// Swap `old_owner` for `new_owner` at the same index
+    fn rotate_owner(&self, old_owner: H160, new_owner: H160) {
+        let storage = Storage::get_context();
+        let old_key = old_owner.into_byte_string();
+        Storage::delete(storage.clone(), self.owner_index_prefix.concat(&old_key));
+        Storage::delete(storage, self.is_owner_prefix.concat(&old_key));
+
+        let owners = self.get_owners();
+        let mut rotated = Array::new();
+        for i in 0..owners.size() {
+            let owner = owners.get(i);
+            rotated.push(if owner == old_owner { new_owner } else { owner });
+        }
+        self.store_owners(&rotated);
+    }
+
+    fn set_required_confirmations(&self, threshold: u32) {
+        let storage = Storage::get_context();
+        Storage::put(storage, self.required_confirmations_key.clone(), ByteString::from_bytes(&threshold.to_le_bytes()));
+
+        let mut event_data = Array::new();
+        event_data.push(Int256::new(threshold as i64).into_any());
+        Runtime::notify(ByteString::from_literal("ThresholdChanged"), event_data);
+    }
+
+    fn execute_native_transfer(&self, to: H160, amount: Int256) -> bool {
+        self.execute_token_transfer(to, Gas::hash(), amount)
+    }
+
+    fn execute_token_transfer(&self, to: H160, token: H160, amount: Int256) -> bool {
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(to.into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+
+        // NEP-17 transfer returns false instead of faulting on insufficient balance
+        Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, args)
+    }
+
+    /// Check that the wallet holds enough of `token` for a transfer of `amount`
+    fn check_transfer(&self, token: H160, amount: Int256) -> Result<(), &'static str> {
+        if amount <= Int256::zero() {
+            return Err("Invalid transfer amount");
+        }
+
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        let balance: Int256 = Contract::call_as(token, ByteString::from_literal("balanceOf"), CallFlags::ReadOnly, args);
+        if balance < amount {
+            return Err("Insufficient wallet balance");
+        }
+
+        Ok(())
+    }
+
+    fn execute_contract_call(&self, target: H160, data: ByteString) -> bool {
+        match calldata::decode(&data) {
+            Some((method, args)) => Contract::call_bool(target, method, CallFlags::All, args),
+            None => {
+                Runtime::log(ByteString::from_literal("Invalid call data"));
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use epicchain_contract::host;
+
+    fn account(byte: u8) -> H160 {
+        H160::from_bytes(&[byte; 20])
+    }
+
+    /// A 2-of-3 wallet owned by 0x01, 0x02 and 0x03
+    fn deploy() -> MultisigWallet {
+        host::set_executing_script_hash(account(0xAA));
+        let wallet = MultisigWallet::init();
+        let mut owners = Array::new();
+        owners.push(account(1));
+        owners.push(account(2));
+        owners.push(account(3));
+        assert!(wallet.initialize(owners, 2, SECONDS_PER_DAY as i64));
+        wallet
+    }
+
+    #[test]
+    fn test_proposal_round_trips_through_storage() {
+        let wallet = deploy();
+        let (token, recipient) = (account(0x70), account(0x44));
+        let data = ByteString::from_literal("call data");
+
+        let proposal_id = wallet.propose_transaction(account(1), recipient, token, Int256::new(500), data.clone());
+        let proposal = wallet.get_proposal(proposal_id).unwrap();
+        assert!(proposal.proposer == account(1));
+        assert!(proposal.target == recipient);
+        assert!(proposal.token == token);
+        assert_eq!(proposal.amount, Int256::new(500));
+        assert_eq!(proposal.data, data);
+        assert_eq!(proposal.expiration, wallet.get_proposal_expiration(proposal_id));
+        assert!(proposal.status == ProposalStatus::Pending);
+        assert_eq!(proposal.confirmations, 1);
+        assert_eq!(proposal.required_confirmations, 2);
+    }
+
+    #[test]
+    fn test_owner_actions_need_m_of_n_confirmations() {
+        let wallet = deploy();
+
+        let proposal_id = wallet.add_owner(account(1), account(4));
+        assert!(!wallet.is_owner(account(4)));
+
+        // A revoked confirmation no longer counts
+        assert!(wallet.revoke_confirmation(proposal_id, account(1)));
+        assert!(wallet.confirm_transaction(proposal_id, account(2)));
+        assert!(!wallet.is_owner(account(4)));
+        assert_eq!(wallet.get_proposal(proposal_id).unwrap().confirmations, 1);
+
+        assert!(wallet.confirm_transaction(proposal_id, account(3)));
+        assert!(wallet.is_owner(account(4)));
+        assert_eq!(wallet.get_owner_count(), 4);
+
+        // Raise the threshold to 3-of-4
+        let raise = wallet.change_threshold(account(1), 3);
+        assert!(wallet.confirm_transaction(raise, account(4)));
+        assert_eq!(wallet.get_required_confirmations(), 3);
+
+        let removal = wallet.remove_owner(account(1), account(2), 2);
+        assert!(wallet.confirm_transaction(removal, account(3)));
+        assert!(wallet.is_owner(account(2)));
+        assert!(wallet.confirm_transaction(removal, account(4)));
+        assert!(!wallet.is_owner(account(2)));
+        assert_eq!(wallet.get_required_confirmations(), 2);
+    }
+
+    #[test]
+    fn test_raised_threshold_applies_to_open_proposals() {
+        let wallet = deploy();
+        let stale = wallet.add_owner(account(1), account(4));
+
+        let raise = wallet.change_threshold(account(2), 3);
+        assert!(wallet.confirm_transaction(raise, account(3)));
+
+        assert!(wallet.confirm_transaction(stale, account(2)));
+        assert!(!wallet.is_owner(account(4)));
+        assert!(wallet.confirm_transaction(stale, account(3)));
+        assert!(wallet.is_owner(account(4)));
+    }
+
+    #[test]
+    fn test_expiry_is_inclusive_and_final() {
+        let wallet = deploy();
+        let proposal_id = wallet.add_owner(account(1), account(4));
+        let expiration = wallet.get_proposal_expiration(proposal_id);
+
+        // Still open at exactly the expiration time
+        host::set_time(expiration);
+        assert!(!wallet.expire_proposal(proposal_id));
+
+        host::set_time(expiration + 1);
+        assert!(!wallet.confirm_transaction(proposal_id, account(2)));
+        assert!(wallet.expire_proposal(proposal_id));
+        assert!(wallet.get_proposal(proposal_id).unwrap().status == ProposalStatus::Expired);
+        assert!(!wallet.expire_proposal(proposal_id));
+        assert!(!wallet.is_owner(account(4)));
+
+        // Executed proposals cannot be marked expired afterwards
+        let executed = wallet.add_owner(account(1), account(5));
+        assert!(wallet.confirm_transaction(executed, account(2)));
+        host::advance_time(2 * SECONDS_PER_DAY);
+        assert!(!wallet.expire_proposal(executed));
+        assert!(wallet.get_proposal(executed).unwrap().status == ProposalStatus::Executed);
+    }
+
+    #[test]
+    fn test_executed_and_failed_status_is_final() {
+        let wallet = deploy();
+        let token = account(0x70);
+        host::register_contract(token, |method, _| {
+            assert_eq!(*method, ByteString::from_literal("transfer"));
+            Int256::one().into_any()
+        });
+
+        let paid = wallet.propose_transaction(account(1), account(0x44), token, Int256::new(5), ByteString::empty());
+        assert!(wallet.confirm_transaction(paid, account(2)));
+        assert!(wallet.get_proposal(paid).unwrap().status == ProposalStatus::Executed);
+        assert!(!wallet.confirm_transaction(paid, account(3)));
+        assert!(!wallet.cancel_proposal(paid, account(1)));
+
+        // This token refuses the transfer
+        let empty_token = account(0x71);
+        host::register_contract(empty_token, |_, _| Int256::zero().into_any());
+        let failed = wallet.propose_transaction(account(1), account(0x44), empty_token, Int256::new(5), ByteString::empty());
+        assert!(!wallet.confirm_transaction(failed, account(2)));
+        assert!(wallet.get_proposal(failed).unwrap().status == ProposalStatus::Failed);
+        assert!(!wallet.confirm_transaction(failed, account(3)));
     }
 }
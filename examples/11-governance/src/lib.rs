@@ -2,11 +2,16 @@
 //!
 //! A comprehensive governance system demonstrating DAO patterns:
 //! - Proposal creation and voting mechanisms
-//! - Token-weighted voting with delegation support
+//! - Typed proposals (parameter changes, treasury spends, code upgrades) validated on creation
+//! - Token-weighted voting with single-hop delegation
 //! - Time-locked execution with veto periods
 //! - Quorum requirements and participation tracking
 //! - Multi-signature emergency controls
-//! - Treasury management and fund allocation
+//! - Treasury funded by NEP-17 deposits and spent only through proposals
+//! - Opt-in voter rewards paid from the treasury, capped per proposal
+//! - Off-chain voting power snapshots committed as Merkle roots
+//! - On-chain voting power checkpoints read from the governance token
+//! - Cross-DAO bridge: a parent DAO's proposals enqueue actions in a sub-DAO's timelock
 //!
 //! This contract showcases decentralized governance patterns
 //! for community-driven decision making and protocol upgrades.
@@ -15,6 +20,7 @@
 #![no_main]
 
 use epicchain_contract::prelude::*;
+use epicchain_contract::contract::{calldata, native::ContractManagement};
 use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
 
 /// Proposal status enumeration
@@ -28,6 +34,8 @@ pub enum ProposalStatus {
     Executed = 5,
     Cancelled = 6,
     Expired = 7,
+    Failed = 8,
+    Vetoed = 9,
 }
 
 impl ProposalStatus {
@@ -40,6 +48,8 @@ impl ProposalStatus {
             5 => ProposalStatus::Executed,
             6 => ProposalStatus::Cancelled,
             7 => ProposalStatus::Expired,
+            8 => ProposalStatus::Failed,
+            9 => ProposalStatus::Vetoed,
             _ => ProposalStatus::Pending,
         }
     }
@@ -80,6 +90,7 @@ pub struct Proposal {
     pub description: ByteString,
     pub target_contract: H160,
     pub call_data: ByteString,
+    pub snapshot_time: u64,            // voting power is read as of this time
     pub start_time: u64,
     pub end_time: u64,
     pub execution_time: u64,
@@ -90,6 +101,27 @@ pub struct Proposal {
     pub quorum_required: Int256,
 }
 
+/// Structured intent of a proposal, validated when the proposal is created
+#[derive(Clone)]
+pub enum ProposalAction {
+    /// Set a governance parameter, see `propose_parameter_change`
+    ParameterChange { key: ByteString, value: Int256 },
+    /// Release treasury funds
+    TreasurySpend { token: H160, to: H160, amount: Int256 },
+    /// Approve new contract code, identified by the SHA-256 hashes of its NEF file and manifest
+    Upgrade { nef_hash: ByteString, manifest_hash: ByteString },
+}
+
+impl ProposalAction {
+    fn kind(&self) -> u8 {
+        match self {
+            ProposalAction::ParameterChange { .. } => 1,
+            ProposalAction::TreasurySpend { .. } => 2,
+            ProposalAction::Upgrade { .. } => 3,
+        }
+    }
+}
+
 /// Vote record
 #[derive(Clone)]
 pub struct Vote {
@@ -100,11 +132,41 @@ pub struct Vote {
     pub timestamp: u64,
 }
 
+/// An action a parent DAO enqueued in this DAO's timelock
+#[derive(Clone)]
+pub struct BridgedAction {
+    pub parent: H160,
+    pub source_proposal_id: Int256,
+    pub target_contract: H160,
+    pub call_data: ByteString,
+    pub execution_time: u64,
+    pub status: ProposalStatus,
+}
+
+/// Maximum Merkle proof length accepted for snapshot votes
+const MAX_PROOF_DEPTH: usize = 32;
+
+/// Emergency pauses of the execution pipeline lift automatically after this long
+const EXECUTION_PAUSE_DURATION: u64 = 7 * SECONDS_PER_DAY;
+
+/// Minimum time between the end of one emergency pause and the next, so the
+/// guardian cannot chain pauses to hold the DAO indefinitely
+const EXECUTION_PAUSE_COOLDOWN: u64 = 2 * SECONDS_PER_DAY;
+
+/// Maximum number of delegates returned by one registry page
+const MAX_DELEGATE_PAGE: u32 = 50;
+
+/// Queued proposals that are not executed within this window after their timelock expire
+const EXECUTION_GRACE_PERIOD: u64 = 14 * SECONDS_PER_DAY;
+
+/// Upper bound on the number of rewarded votes per proposal
+const MAX_REWARDED_VOTES: u32 = 10_000;
+
 /// Governance contract with DAO functionality
 #[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
 #[contract_version("1.0.0")]
 #[contract_standards("")]
-#[contract_permission("*", "*")]
+#[contract_permission("*", "*", reason = "executes arbitrary approved proposals")]
 #[contract_meta("description", "Decentralized governance with token-weighted voting")]
 #[contract_meta("category", "Governance")]
 pub struct Governance {
@@ -128,13 +190,39 @@ pub struct Governance {
     admin_key: ByteString,
     guardian_key: ByteString,          // emergency guardian
     timelock_key: ByteString,          // timelock contract
+    execution_paused_until_key: ByteString, // end of the current emergency pause
 
     // Treasury
     treasury_prefix: ByteString,       // token -> treasury balance
+    treasury_spend_prefix: ByteString, // proposal_id -> recipient + amount of a treasury spend
+
+    // Typed proposals
+    proposal_action_prefix: ByteString, // proposal_id -> kind (u8) | action payload
+    approved_upgrade_key: ByteString,  // NEF hash approved by an executed upgrade proposal
+
+    // Voter rewards
+    voter_reward_config_key: ByteString, // token | cap per proposal (u32) | reward length (u8) | reward | min power
+    rewarded_votes_prefix: ByteString, // proposal_id -> number of rewarded votes
+    voter_reward_prefix: ByteString,   // voter + token -> claimable reward
+
+    // Cross-DAO bridge
+    bridge_proposal_prefix: ByteString, // proposal_id -> target + call data relayed to a sub-DAO
+    parent_dao_prefix: ByteString,     // parent DAO -> allowed to enqueue actions here
+    bridged_action_prefix: ByteString, // action_id -> bridged action
+    bridged_action_count_key: ByteString, // number of bridged actions received
 
     // Delegation
     delegate_prefix: ByteString,       // delegator -> delegate
-    delegated_votes_prefix: ByteString, // delegate -> total delegated votes
+    delegator_count_prefix: ByteString, // delegate -> number of accounts delegating to it
+
+    // Delegate registry
+    registry_count_key: ByteString,    // number of registered delegates
+    registry_index_prefix: ByteString, // index -> delegate
+    registry_entry_prefix: ByteString, // delegate -> index + registration time + statement hash
+
+    // Off-chain snapshots
+    snapshot_root_prefix: ByteString,  // proposal_id -> Merkle root of (voter, balance) leaves
+    snapshot_total_prefix: ByteString, // proposal_id -> total voting power in the snapshot
 }
 
 #[contract_impl]
@@ -155,13 +243,32 @@ impl Governance {
             admin_key: ByteString::from_literal("admin"),
             guardian_key: ByteString::from_literal("guardian"),
             timelock_key: ByteString::from_literal("timelock"),
+            execution_paused_until_key: ByteString::from_literal("execution_paused_until"),
             treasury_prefix: ByteString::from_literal("treasury_"),
+            treasury_spend_prefix: ByteString::from_literal("treasury_spend_"),
+            proposal_action_prefix: ByteString::from_literal("proposal_action_"),
+            approved_upgrade_key: ByteString::from_literal("approved_upgrade"),
+            voter_reward_config_key: ByteString::from_literal("voter_reward_config"),
+            rewarded_votes_prefix: ByteString::from_literal("rewarded_votes_"),
+            voter_reward_prefix: ByteString::from_literal("voter_reward_"),
+            bridge_proposal_prefix: ByteString::from_literal("bridge_proposal_"),
+            parent_dao_prefix: ByteString::from_literal("parent_dao_"),
+            bridged_action_prefix: ByteString::from_literal("bridged_action_"),
+            bridged_action_count_key: ByteString::from_literal("bridged_action_count"),
             delegate_prefix: ByteString::from_literal("delegate_"),
-            delegated_votes_prefix: ByteString::from_literal("delegated_"),
+            delegator_count_prefix: ByteString::from_literal("delegator_count_"),
+            registry_count_key: ByteString::from_literal("registry_count"),
+            registry_index_prefix: ByteString::from_literal("registry_index_"),
+            registry_entry_prefix: ByteString::from_literal("registry_entry_"),
+            snapshot_root_prefix: ByteString::from_literal("snapshot_root_"),
+            snapshot_total_prefix: ByteString::from_literal("snapshot_total_"),
         }
     }
 
-    /// Initialize governance with parameters
+    /// Initialize governance with parameters.
+    ///
+    /// Voting power is read from `governance_token`'s checkpoints; make this contract the
+    /// token's votes controller (`set_votes_controller`) so delegation can move votes.
     #[method]
     pub fn initialize(
         &self,
@@ -183,17 +290,17 @@ impl Governance {
         }
 
         // Validate parameters
-        if voting_delay < 3600 || voting_delay > 604800 { // 1 hour to 7 days
+        if voting_delay < SECONDS_PER_HOUR || voting_delay > SECONDS_PER_WEEK {
             Runtime::log(ByteString::from_literal("Invalid voting delay"));
             return false;
         }
 
-        if voting_period < 86400 || voting_period > 2592000 { // 1 day to 30 days
+        if voting_period < SECONDS_PER_DAY || voting_period > days(30) {
             Runtime::log(ByteString::from_literal("Invalid voting period"));
             return false;
         }
 
-        if execution_delay < 86400 || execution_delay > 604800 { // 1 day to 7 days
+        if execution_delay < SECONDS_PER_DAY || execution_delay > SECONDS_PER_WEEK {
             Runtime::log(ByteString::from_literal("Invalid execution delay"));
             return false;
         }
@@ -240,8 +347,13 @@ impl Governance {
             return Int256::minus_one();
         }
 
+        // Voting power is fixed just before the proposal's creation, so tokens moved
+        // in the same block cannot be counted
+        let current_time = Runtime::get_time();
+        let snapshot_time = current_time.saturating_sub(1);
+
         // Check proposal threshold
-        let voting_power = self.get_voting_power(proposer);
+        let voting_power = self.get_voting_power_at(proposer, snapshot_time);
         let proposal_threshold = self.get_proposal_threshold();
         if voting_power < proposal_threshold {
             Runtime::log(ByteString::from_literal("Insufficient voting power to propose"));
@@ -259,7 +371,6 @@ impl Governance {
             return Int256::minus_one();
         }
 
-        let current_time = Runtime::get_time();
         let voting_delay = self.get_voting_delay();
         let voting_period = self.get_voting_period();
         let execution_delay = self.get_execution_delay();
@@ -269,7 +380,7 @@ impl Governance {
         let execution_time = end_time + execution_delay;
 
         // Calculate proper quorum based on total voting power
-        let total_voting_power = self.get_total_voting_supply();
+        let total_voting_power = self.get_total_voting_power_at(snapshot_time);
         let required_quorum = total_voting_power
             .checked_mul(&Int256::from_u64(self.get_quorum_percentage() as u64))
             .checked_div(&Int256::from_u64(10000)); // Basis points (10000 = 100%)
@@ -286,6 +397,7 @@ impl Governance {
             description,
             target_contract,
             call_data,
+            snapshot_time,
             start_time,
             end_time,
             execution_time,
@@ -317,211 +429,934 @@ impl Governance {
         proposal_id
     }
 
-    /// Cast a vote on a proposal
+    /// Propose releasing `amount` of `token` from the treasury to `recipient`.
+    ///
+    /// The spend goes through the normal vote, queue and timelock; funds only move when the
+    /// proposal is executed.
     #[method]
-    pub fn vote(
+    pub fn propose_treasury_spend(
         &self,
-        voter: H160,
-        proposal_id: Int256,
-        choice: u8,
-        reason: ByteString
-    ) -> bool {
-        // Verify authorization
-        if !Runtime::check_witness(voter) {
-            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
-            return false;
+        proposer: H160,
+        title: ByteString,
+        description: ByteString,
+        token: H160,
+        recipient: H160,
+        amount: Int256
+    ) -> Int256 {
+        if recipient == H160::zero() || amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid treasury spend"));
+            return Int256::minus_one();
         }
 
-        // Get proposal
-        let mut proposal = match self.get_proposal_data(proposal_id) {
-            Some(p) => p,
-            None => {
-                Runtime::log(ByteString::from_literal("Proposal not found"));
-                return false;
-            }
-        };
+        if amount > self.get_treasury_balance(token) {
+            Runtime::log(ByteString::from_literal("Insufficient treasury balance"));
+            return Int256::minus_one();
+        }
 
-        let current_time = Runtime::get_time();
+        let proposal_id = self.propose(proposer, title, description, token, ByteString::empty());
+        if proposal_id <= Int256::zero() {
+            return proposal_id;
+        }
 
-        // Check if voting is active
-        if current_time < proposal.start_time {
-            Runtime::log(ByteString::from_literal("Voting has not started"));
-            return false;
+        let storage = Storage::get_context();
+        let spend_key = self.treasury_spend_prefix.concat(&proposal_id.into_byte_string());
+        Storage::put(storage, spend_key, recipient.into_byte_string().concat(&amount.into_byte_string()));
+        self.store_proposal_action(proposal_id, &ProposalAction::TreasurySpend { token, to: recipient, amount });
+
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        event_data.push(token.into_any());
+        event_data.push(recipient.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("TreasurySpendProposed"), event_data);
+
+        proposal_id
+    }
+
+    /// Propose setting a governance parameter to `value`.
+    ///
+    /// `key` is one of `voting_delay`, `voting_period`, `execution_delay` (seconds),
+    /// `proposal_threshold` or `quorum_percentage` (basis points). The value must be within
+    /// the bounds `initialize` enforces, so a passed proposal cannot misconfigure the DAO.
+    #[method]
+    pub fn propose_parameter_change(
+        &self,
+        proposer: H160,
+        title: ByteString,
+        description: ByteString,
+        key: ByteString,
+        value: Int256
+    ) -> Int256 {
+        if self.encode_parameter(&key, value).is_none() {
+            Runtime::log(ByteString::from_literal("Invalid parameter change"));
+            return Int256::minus_one();
         }
 
-        if current_time > proposal.end_time {
-            Runtime::log(ByteString::from_literal("Voting has ended"));
-            return false;
+        let proposal_id = self.propose(proposer, title, description, Runtime::get_executing_script_hash(), ByteString::empty());
+        if proposal_id <= Int256::zero() {
+            return proposal_id;
         }
 
-        if proposal.status != ProposalStatus::Pending && proposal.status != ProposalStatus::Active {
-            Runtime::log(ByteString::from_literal("Proposal is not active"));
-            return false;
+        self.store_proposal_action(proposal_id, &ProposalAction::ParameterChange { key: key.clone(), value });
+
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        event_data.push(key.into_any());
+        event_data.push(value.into_any());
+        Runtime::notify(ByteString::from_literal("ParameterChangeProposed"), event_data);
+
+        proposal_id
+    }
+
+    /// Propose upgrading this contract to the NEF file and manifest hashing (SHA-256) to
+    /// `nef_hash` and `manifest_hash`.
+    ///
+    /// Executing the proposal only approves the code; the admin then applies it with `upgrade`.
+    #[method]
+    pub fn propose_upgrade(
+        &self,
+        proposer: H160,
+        title: ByteString,
+        description: ByteString,
+        nef_hash: ByteString,
+        manifest_hash: ByteString
+    ) -> Int256 {
+        if nef_hash.len() != 32 {
+            Runtime::log(ByteString::from_literal("Invalid NEF hash"));
+            return Int256::minus_one();
         }
 
-        // Check if already voted
-        let vote_key = self.get_vote_key(proposal_id, voter);
-        let storage = Storage::get_context();
-        if Storage::get(storage.clone(), vote_key.clone()).is_some() {
-            Runtime::log(ByteString::from_literal("Already voted"));
-            return false;
+        if manifest_hash.len() != 32 {
+            Runtime::log(ByteString::from_literal("Invalid manifest hash"));
+            return Int256::minus_one();
         }
 
-        // Get voting power
-        let voting_power = self.get_voting_power(voter);
-        if voting_power <= Int256::zero() {
-            Runtime::log(ByteString::from_literal("No voting power"));
-            return false;
+        let proposal_id = self.propose(proposer, title, description, Runtime::get_executing_script_hash(), ByteString::empty());
+        if proposal_id <= Int256::zero() {
+            return proposal_id;
         }
 
-        let vote_choice = VoteChoice::from_u8(choice);
+        self.store_proposal_action(proposal_id, &ProposalAction::Upgrade {
+            nef_hash: nef_hash.clone(),
+            manifest_hash: manifest_hash.clone(),
+        });
 
-        // Update proposal vote counts
-        match vote_choice {
-            VoteChoice::For => proposal.for_votes = proposal.for_votes.checked_add(&voting_power),
-            VoteChoice::Against => proposal.against_votes = proposal.against_votes.checked_add(&voting_power),
-            VoteChoice::Abstain => proposal.abstain_votes = proposal.abstain_votes.checked_add(&voting_power),
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        event_data.push(nef_hash.into_any());
+        event_data.push(manifest_hash.into_any());
+        Runtime::notify(ByteString::from_literal("UpgradeProposed"), event_data);
+
+        proposal_id
+    }
+
+    /// Apply the code approved by an executed upgrade proposal (admin only).
+    ///
+    /// `nef` and `manifest` must hash to the approved hashes, so the admin cannot attach other
+    /// permissions or ABI than the vote approved; the approval is used up by the update.
+    #[method]
+    pub fn upgrade(&self, nef: ByteString, manifest: ByteString) -> bool {
+        if !self.is_admin() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only admin can apply upgrades"));
+            return false;
         }
 
-        // Update proposal status to active if first vote
-        if proposal.status == ProposalStatus::Pending {
-            proposal.status = ProposalStatus::Active;
+        let storage = Storage::get_context();
+        let hashes = sha256(nef.clone()).into_byte_string().concat(&sha256(manifest.clone()).into_byte_string());
+        match Storage::get(storage.clone(), self.approved_upgrade_key.clone()) {
+            Some(approved) if approved == hashes => {}
+            _ => {
+                Runtime::log(ByteString::from_literal("Upgrade not approved"));
+                return false;
+            }
         }
 
-        // Store updated proposal
-        let proposal_key = self.proposal_prefix.concat(&proposal_id.into_byte_string());
-        Storage::put(storage.clone(), proposal_key, self.serialize_proposal(proposal));
+        Storage::delete(storage, self.approved_upgrade_key.clone());
+        ContractManagement::update(nef, manifest, Any::default());
+        true
+    }
 
-        // Record vote
-        let vote = Vote {
-            proposal_id,
-            voter,
-            choice: vote_choice,
-            voting_power,
-            timestamp: current_time,
-        };
+    /// Propose that `child_dao` enqueue a call to `target` in its own timelock.
+    ///
+    /// Once this proposal passes and is executed, the call is handed to the child's
+    /// `enqueue_bridged_action`; the child only accepts it if it lists this DAO as a parent,
+    /// and it still runs only after the child's own execution delay.
+    #[method]
+    pub fn propose_bridge_action(
+        &self,
+        proposer: H160,
+        title: ByteString,
+        description: ByteString,
+        child_dao: H160,
+        target: H160,
+        call_data: ByteString
+    ) -> Int256 {
+        if child_dao == H160::zero() || child_dao == Runtime::get_executing_script_hash() {
+            Runtime::log(ByteString::from_literal("Invalid child DAO"));
+            return Int256::minus_one();
+        }
 
-        let storage_clone = storage.clone(); Storage::put(storage_clone, vote_key, self.serialize_vote(vote));
+        if calldata::decode(&call_data).is_none() {
+            Runtime::log(ByteString::from_literal("Invalid call data"));
+            return Int256::minus_one();
+        }
 
-        // Add to voter's proposal list
-        self.add_voter_proposal(voter, proposal_id);
+        let proposal_id = self.propose(proposer, title, description, child_dao, ByteString::empty());
+        if proposal_id <= Int256::zero() {
+            return proposal_id;
+        }
+
+        let storage = Storage::get_context();
+        let bridge_key = self.bridge_proposal_prefix.concat(&proposal_id.into_byte_string());
+        Storage::put(storage, bridge_key, target.into_byte_string().concat(&call_data));
 
-        // Emit event
         let mut event_data = Array::new();
         event_data.push(proposal_id.into_any());
-        event_data.push(voter.into_any());
-        event_data.push(Int256::new(choice as i64).into_any());
-        event_data.push(voting_power.into_any());
-        event_data.push(reason.into_any());
-        Runtime::notify(ByteString::from_literal("VoteCast"), event_data);
+        event_data.push(child_dao.into_any());
+        event_data.push(target.into_any());
+        Runtime::notify(ByteString::from_literal("BridgeActionProposed"), event_data);
 
-        true
+        proposal_id
     }
 
-    /// Queue a successful proposal for execution
+    /// Allow or revoke a parent DAO's right to enqueue actions in this DAO's timelock.
+    ///
+    /// Callable by the admin or by one of this DAO's own proposals.
     #[method]
-    pub fn queue_proposal(&self, proposal_id: Int256) -> bool {
-        let mut proposal = match self.get_proposal_data(proposal_id) {
-            Some(p) => p,
-            None => {
-                Runtime::log(ByteString::from_literal("Proposal not found"));
-                return false;
-            }
-        };
-
-        let current_time = Runtime::get_time();
+    pub fn set_parent_dao(&self, parent: H160, allowed: bool) -> bool {
+        if !self.is_admin() && !self.is_self_call() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only admin or governance can set parent DAOs"));
+            return false;
+        }
 
-        // Check if voting has ended
-        if current_time <= proposal.end_time {
-            Runtime::log(ByteString::from_literal("Voting still active"));
+        if parent == H160::zero() || parent == Runtime::get_executing_script_hash() {
+            Runtime::log(ByteString::from_literal("Invalid parent DAO"));
             return false;
         }
 
-        // Check if proposal succeeded
-        if !self.is_proposal_successful(&proposal) {
-            proposal.status = ProposalStatus::Defeated;
+        let storage = Storage::get_context();
+        let parent_key = self.parent_dao_prefix.concat(&parent.into_byte_string());
+        if allowed {
+            Storage::put(storage, parent_key, ByteString::from_bytes(&[1u8]));
         } else {
-            proposal.status = ProposalStatus::Queued;
+            Storage::delete(storage, parent_key);
         }
 
-        // Store updated proposal
+        let mut event_data = Array::new();
+        event_data.push(parent.into_any());
+        event_data.push(if allowed { Int256::one() } else { Int256::zero() }.into_any());
+        Runtime::notify(ByteString::from_literal("ParentDaoSet"), event_data);
+        true
+    }
+
+    /// Whether `parent` may enqueue actions in this DAO's timelock
+    #[method]
+    #[safe]
+    pub fn is_parent_dao(&self, parent: H160) -> bool {
         let storage = Storage::get_context();
-        let proposal_key = self.proposal_prefix.concat(&proposal_id.into_byte_string());
-        let storage_clone = storage.clone(); Storage::put(storage_clone, proposal_key, self.serialize_proposal(proposal.clone()));
+        Storage::get(storage, self.parent_dao_prefix.concat(&parent.into_byte_string())).is_some()
+    }
 
-        if proposal.status == ProposalStatus::Queued {
-            let mut event_data = Array::new();
-            event_data.push(proposal_id.into_any());
-            event_data.push(Int256::new(proposal.execution_time as i64).into_any());
-            Runtime::notify(ByteString::from_literal("ProposalQueued"), event_data);
-        } else {
-            let mut event_data = Array::new();
-            event_data.push(proposal_id.into_any());
-            Runtime::notify(ByteString::from_literal("ProposalDefeated"), event_data);
+    /// Inter-DAO adapter: queue an action relayed by a parent DAO's executed proposal.
+    ///
+    /// The calling contract must be a registered parent. The action gets this DAO's execution
+    /// delay, during which the guardian can veto it. Returns the action id, or -1.
+    #[method]
+    pub fn enqueue_bridged_action(&self, source_proposal_id: Int256, target: H160, call_data: ByteString) -> Int256 {
+        let parent = Runtime::get_calling_script_hash();
+        if parent == Runtime::get_entry_script_hash() || !self.is_parent_dao(parent) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Caller is not a parent DAO"));
+            return Int256::minus_one();
         }
 
-        true
+        if self.is_execution_paused() {
+            Runtime::log(ByteString::from_literal("Execution paused"));
+            return Int256::minus_one();
+        }
+
+        if calldata::decode(&call_data).is_none() {
+            Runtime::log(ByteString::from_literal("Invalid call data"));
+            return Int256::minus_one();
+        }
+
+        let action_id = self.get_bridged_action_count().checked_add(&Int256::one());
+        let action = BridgedAction {
+            parent,
+            source_proposal_id,
+            target_contract: target,
+            call_data,
+            execution_time: Runtime::get_time() + self.get_execution_delay(),
+            status: ProposalStatus::Queued,
+        };
+        let execution_time = action.execution_time;
+        self.store_bridged_action(action_id, action);
+
+        let storage = Storage::get_context();
+        Storage::put(storage, self.bridged_action_count_key.clone(), action_id.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(action_id.into_any());
+        event_data.push(parent.into_any());
+        event_data.push(source_proposal_id.into_any());
+        event_data.push(target.into_any());
+        event_data.push(Int256::new(execution_time as i64).into_any());
+        Runtime::notify(ByteString::from_literal("BridgedActionQueued"), event_data);
+
+        action_id
     }
 
-    /// Execute a queued proposal
+    /// Execute a bridged action once this DAO's timelock has passed
     #[method]
-    pub fn execute_proposal(&self, proposal_id: Int256) -> bool {
-        let mut proposal = match self.get_proposal_data(proposal_id) {
-            Some(p) => p,
+    pub fn execute_bridged_action(&self, action_id: Int256) -> bool {
+        if self.is_execution_paused() {
+            Runtime::log(ByteString::from_literal("Execution paused"));
+            return false;
+        }
+
+        let mut action = match self.get_bridged_action_data(action_id) {
+            Some(action) => action,
             None => {
-                Runtime::log(ByteString::from_literal("Proposal not found"));
+                Runtime::log(ByteString::from_literal("Bridged action not found"));
                 return false;
             }
         };
 
-        let current_time = Runtime::get_time();
-
-        // Check if proposal is queued and ready for execution
-        if proposal.status != ProposalStatus::Queued {
-            Runtime::log(ByteString::from_literal("Proposal not queued"));
+        if action.status != ProposalStatus::Queued {
+            Runtime::log(ByteString::from_literal("Bridged action not queued"));
             return false;
         }
 
-        if current_time < proposal.execution_time {
+        let current_time = Runtime::get_time();
+        if current_time < action.execution_time {
             Runtime::log(ByteString::from_literal("Execution time not reached"));
             return false;
         }
 
-        // Execute the proposal with proper implementation
-        let success = if proposal.call_data.is_empty() {
-            // Simple parameter change or administrative action
-            self.execute_administrative_action(proposal.target_contract, proposal.description.clone())
-        } else {
-            // Contract call with specific data
-            self.execute_contract_call(proposal.target_contract, proposal.call_data.clone())
-        };
+        let mut event_data = Array::new();
+        event_data.push(action_id.into_any());
+        event_data.push(action.target_contract.into_any());
+
+        if current_time > action.execution_time.saturating_add(EXECUTION_GRACE_PERIOD) {
+            action.status = ProposalStatus::Expired;
+            self.store_bridged_action(action_id, action);
+            Runtime::notify(ByteString::from_literal("BridgedActionExpired"), event_data);
+            Runtime::log(ByteString::from_literal("Bridged action expired"));
+            return false;
+        }
+
+        // Like proposals, a failed call is final and has to be relayed again
+        let success = self.execute_contract_call(action.target_contract, action.call_data.clone());
+        action.status = if success { ProposalStatus::Executed } else { ProposalStatus::Failed };
+        self.store_bridged_action(action_id, action);
 
         if success {
-            proposal.status = ProposalStatus::Executed;
-            
-            // Store updated proposal
-            let storage = Storage::get_context();
-            let proposal_key = self.proposal_prefix.concat(&proposal_id.into_byte_string());
-            Storage::put(storage, proposal_key, self.serialize_proposal(proposal.clone()));
-            
-            let mut event_data = Array::new();
-            event_data.push(proposal_id.into_any());
-            event_data.push(proposal.target_contract.into_any());
-            Runtime::notify(ByteString::from_literal("ProposalExecuted"), event_data);
-            
+            Runtime::notify(ByteString::from_literal("BridgedActionExecuted"), event_data);
             true
         } else {
-            Runtime::log(ByteString::from_literal("Proposal execution failed"));
+            Runtime::notify(ByteString::from_literal("BridgedActionFailed"), event_data);
+            Runtime::log(ByteString::from_literal("Bridged action execution failed"));
             false
         }
     }
 
-    /// Get proposal information
+    /// Veto a bridged action during its timelock (guardian only)
     #[method]
-    #[safe]
-    pub fn get_proposal(&self, proposal_id: Int256) -> Map<ByteString, Any> {
-        let mut result = Map::new();
-
+    pub fn veto_bridged_action(&self, action_id: Int256) -> bool {
+        if !self.is_guardian() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only guardian can veto"));
+            return false;
+        }
+
+        let mut action = match self.get_bridged_action_data(action_id) {
+            Some(action) => action,
+            None => {
+                Runtime::log(ByteString::from_literal("Bridged action not found"));
+                return false;
+            }
+        };
+
+        if action.status != ProposalStatus::Queued {
+            Runtime::log(ByteString::from_literal("Bridged action not queued"));
+            return false;
+        }
+
+        if Runtime::get_time() >= action.execution_time {
+            Runtime::log(ByteString::from_literal("Timelock has ended"));
+            return false;
+        }
+
+        action.status = ProposalStatus::Vetoed;
+        self.store_bridged_action(action_id, action);
+
+        let mut event_data = Array::new();
+        event_data.push(action_id.into_any());
+        Runtime::notify(ByteString::from_literal("BridgedActionVetoed"), event_data);
+        true
+    }
+
+    /// Get a bridged action
+    #[method]
+    #[safe]
+    pub fn get_bridged_action(&self, action_id: Int256) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
+        match self.get_bridged_action_data(action_id) {
+            Some(action) => {
+                let status = if action.status == ProposalStatus::Queued
+                    && Runtime::get_time() > action.execution_time.saturating_add(EXECUTION_GRACE_PERIOD)
+                {
+                    ProposalStatus::Expired
+                } else {
+                    action.status
+                };
+                result.put(ByteString::from_literal("id"), action_id.into_any());
+                result.put(ByteString::from_literal("parent"), action.parent.into_any());
+                result.put(ByteString::from_literal("source_proposal_id"), action.source_proposal_id.into_any());
+                result.put(ByteString::from_literal("target_contract"), action.target_contract.into_any());
+                result.put(ByteString::from_literal("execution_time"), Int256::new(action.execution_time as i64).into_any());
+                result.put(ByteString::from_literal("status"), Int256::new(status.to_u8() as i64).into_any());
+            },
+            None => {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal("Bridged action not found").into_any());
+            }
+        }
+
+        result
+    }
+
+    /// Number of bridged actions received from parent DAOs
+    #[method]
+    #[safe]
+    pub fn get_bridged_action_count(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.bridged_action_count_key.clone()) {
+            Some(count_bytes) => Int256::from_byte_string(count_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Cast a vote on a proposal
+    #[method]
+    pub fn vote(
+        &self,
+        voter: H160,
+        proposal_id: Int256,
+        choice: u8,
+        reason: ByteString
+    ) -> bool {
+        // Verify authorization
+        if !Runtime::check_witness(voter) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        if self.get_snapshot_root(proposal_id).is_some() {
+            Runtime::log(ByteString::from_literal("Snapshot proposal: use vote_with_proof"));
+            return false;
+        }
+
+        let snapshot_time = match self.get_proposal_data(proposal_id) {
+            Some(proposal) => proposal.snapshot_time,
+            None => {
+                Runtime::log(ByteString::from_literal("Proposal not found"));
+                return false;
+            }
+        };
+
+        let voting_power = self.get_voting_power_at(voter, snapshot_time);
+        self.cast_vote(voter, proposal_id, choice, voting_power, reason)
+    }
+
+    /// Cast a vote using voting power proven against the proposal's snapshot root.
+    ///
+    /// Leaves are `sha256(voter || balance)` with the balance in `Int256` byte encoding;
+    /// inner nodes hash the sorted pair of their children.
+    #[method]
+    pub fn vote_with_proof(
+        &self,
+        voter: H160,
+        proposal_id: Int256,
+        choice: u8,
+        balance: Int256,
+        proof: Array<ByteString>,
+        reason: ByteString
+    ) -> bool {
+        if !Runtime::check_witness(voter) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        let root = match self.get_snapshot_root(proposal_id) {
+            Some(root) => root,
+            None => {
+                Runtime::log(ByteString::from_literal("Proposal has no snapshot"));
+                return false;
+            }
+        };
+
+        if proof.size() > MAX_PROOF_DEPTH {
+            Runtime::log(ByteString::from_literal("Proof too long"));
+            return false;
+        }
+
+        let leaf = voter.into_byte_string().concat(&balance.into_byte_string());
+        if !self.verify_merkle_proof(sha256(leaf).into_byte_string(), &proof, root) {
+            Runtime::log(ByteString::from_literal("Invalid snapshot proof"));
+            return false;
+        }
+
+        self.cast_vote(voter, proposal_id, choice, balance, reason)
+    }
+
+    /// Commit the off-chain voting power snapshot of a proposal (admin only, before voting starts)
+    #[method]
+    pub fn set_snapshot_root(&self, proposal_id: Int256, root: ByteString, total_power: Int256) -> bool {
+        if !self.is_admin() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only admin can set snapshots"));
+            return false;
+        }
+
+        if root.len() != 32 {
+            Runtime::log(ByteString::from_literal("Invalid snapshot root"));
+            return false;
+        }
+
+        if total_power <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid snapshot total"));
+            return false;
+        }
+
+        let mut proposal = match self.get_proposal_data(proposal_id) {
+            Some(p) => p,
+            None => {
+                Runtime::log(ByteString::from_literal("Proposal not found"));
+                return false;
+            }
+        };
+
+        // The snapshot is fixed once voting opens
+        if Runtime::get_time() >= proposal.start_time || proposal.status != ProposalStatus::Pending {
+            Runtime::log(ByteString::from_literal("Voting already started"));
+            return false;
+        }
+
+        // Quorum follows the snapshot supply instead of the live supply
+        proposal.quorum_required = total_power
+            .checked_mul(&Int256::from_u64(self.get_quorum_percentage() as u64))
+            .checked_div(&Int256::from_u64(10000));
+
+        let storage = Storage::get_context();
+        let proposal_key = self.proposal_prefix.concat(&proposal_id.into_byte_string());
+        Storage::put(storage.clone(), proposal_key, self.serialize_proposal(proposal));
+        Storage::put(storage.clone(), self.snapshot_root_prefix.concat(&proposal_id.into_byte_string()), root.clone());
+        Storage::put(storage, self.snapshot_total_prefix.concat(&proposal_id.into_byte_string()), total_power.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        event_data.push(root.into_any());
+        event_data.push(total_power.into_any());
+        Runtime::notify(ByteString::from_literal("SnapshotRootSet"), event_data);
+        true
+    }
+
+    /// Get the snapshot root of a proposal (empty if the proposal uses live voting power)
+    #[method]
+    #[safe]
+    pub fn get_snapshot(&self, proposal_id: Int256) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        let storage = Storage::get_context();
+
+        let root = self.get_snapshot_root(proposal_id).unwrap_or(ByteString::empty());
+        let total = match Storage::get(storage, self.snapshot_total_prefix.concat(&proposal_id.into_byte_string())) {
+            Some(total_bytes) => Int256::from_byte_string(total_bytes),
+            None => Int256::zero(),
+        };
+
+        result.put(ByteString::from_literal("root"), root.into_any());
+        result.put(ByteString::from_literal("total_power"), total.into_any());
+        result
+    }
+
+    fn cast_vote(
+        &self,
+        voter: H160,
+        proposal_id: Int256,
+        choice: u8,
+        voting_power: Int256,
+        reason: ByteString
+    ) -> bool {
+        // Get proposal
+        let mut proposal = match self.get_proposal_data(proposal_id) {
+            Some(p) => p,
+            None => {
+                Runtime::log(ByteString::from_literal("Proposal not found"));
+                return false;
+            }
+        };
+
+        let current_time = Runtime::get_time();
+
+        // Check if voting is active
+        if current_time < proposal.start_time {
+            Runtime::log(ByteString::from_literal("Voting has not started"));
+            return false;
+        }
+
+        if current_time > proposal.end_time {
+            Runtime::log(ByteString::from_literal("Voting has ended"));
+            return false;
+        }
+
+        if proposal.status != ProposalStatus::Pending && proposal.status != ProposalStatus::Active {
+            Runtime::log(ByteString::from_literal("Proposal is not active"));
+            return false;
+        }
+
+        // Check if already voted
+        let vote_key = self.get_vote_key(proposal_id, voter);
+        let storage = Storage::get_context();
+        if Storage::get(storage.clone(), vote_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Already voted"));
+            return false;
+        }
+
+        if voting_power <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No voting power"));
+            return false;
+        }
+
+        let vote_choice = VoteChoice::from_u8(choice);
+
+        // Update proposal vote counts
+        match vote_choice {
+            VoteChoice::For => proposal.for_votes = proposal.for_votes.checked_add(&voting_power),
+            VoteChoice::Against => proposal.against_votes = proposal.against_votes.checked_add(&voting_power),
+            VoteChoice::Abstain => proposal.abstain_votes = proposal.abstain_votes.checked_add(&voting_power),
+        }
+
+        // Update proposal status to active if first vote
+        if proposal.status == ProposalStatus::Pending {
+            proposal.status = ProposalStatus::Active;
+        }
+
+        // Store updated proposal
+        let proposal_key = self.proposal_prefix.concat(&proposal_id.into_byte_string());
+        Storage::put(storage.clone(), proposal_key, self.serialize_proposal(proposal));
+
+        // Record vote
+        let vote = Vote {
+            proposal_id,
+            voter,
+            choice: vote_choice,
+            voting_power,
+            timestamp: current_time,
+        };
+
+        let storage_clone = storage.clone(); Storage::put(storage_clone, vote_key, self.serialize_vote(vote));
+
+        // Add to voter's proposal list
+        self.add_voter_proposal(voter, proposal_id);
+        self.accrue_voter_reward(proposal_id, voter, voting_power);
+
+        // Emit event
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        event_data.push(voter.into_any());
+        event_data.push(Int256::new(choice as i64).into_any());
+        event_data.push(voting_power.into_any());
+        event_data.push(reason.into_any());
+        Runtime::notify(ByteString::from_literal("VoteCast"), event_data);
+
+        true
+    }
+
+    /// Freeze proposal queueing and execution (guardian only). Voting stays open and the
+    /// pause lifts automatically after `EXECUTION_PAUSE_DURATION`.
+    #[method]
+    pub fn pause_execution(&self) -> bool {
+        if !self.is_guardian() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only guardian can pause execution"));
+            return false;
+        }
+
+        let current_time = Runtime::get_time();
+        let paused_until = self.get_execution_paused_until();
+
+        if current_time < paused_until {
+            Runtime::log(ByteString::from_literal("Execution already paused"));
+            return false;
+        }
+
+        if paused_until > 0 && current_time < paused_until + EXECUTION_PAUSE_COOLDOWN {
+            Runtime::log(ByteString::from_literal("Pause cooldown active"));
+            return false;
+        }
+
+        let until = current_time + EXECUTION_PAUSE_DURATION;
+        let storage = Storage::get_context();
+        Storage::put(storage, self.execution_paused_until_key.clone(), ByteString::from_bytes(&until.to_le_bytes()));
+
+        let mut event_data = Array::new();
+        event_data.push(Int256::new(until as i64).into_any());
+        Runtime::notify(ByteString::from_literal("ExecutionPaused"), event_data);
+        true
+    }
+
+    /// Lift an emergency pause before it expires (guardian or admin)
+    #[method]
+    pub fn unpause_execution(&self) -> bool {
+        if !self.is_guardian() && !self.is_admin() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only guardian or admin can unpause execution"));
+            return false;
+        }
+
+        let current_time = Runtime::get_time();
+        if current_time >= self.get_execution_paused_until() {
+            Runtime::log(ByteString::from_literal("Execution not paused"));
+            return false;
+        }
+
+        // Keep the pause end as the cooldown anchor
+        let storage = Storage::get_context();
+        Storage::put(storage, self.execution_paused_until_key.clone(), ByteString::from_bytes(&current_time.to_le_bytes()));
+
+        let mut event_data = Array::new();
+        event_data.push(Int256::new(current_time as i64).into_any());
+        Runtime::notify(ByteString::from_literal("ExecutionUnpaused"), event_data);
+        true
+    }
+
+    /// Whether proposal queueing and execution are currently paused
+    #[method]
+    #[safe]
+    pub fn is_execution_paused(&self) -> bool {
+        Runtime::get_time() < self.get_execution_paused_until()
+    }
+
+    /// Get the time the current (or last) emergency pause ends, 0 if never paused
+    #[method]
+    #[safe]
+    pub fn get_execution_paused_until(&self) -> u64 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.execution_paused_until_key.clone()) {
+            Some(until_bytes) => {
+                let bytes = until_bytes.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([
+                        bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]
+                    ])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    /// Queue a successful proposal for execution
+    #[method]
+    pub fn queue_proposal(&self, proposal_id: Int256) -> bool {
+        if self.is_execution_paused() {
+            Runtime::log(ByteString::from_literal("Execution paused"));
+            return false;
+        }
+
+        let mut proposal = match self.get_proposal_data(proposal_id) {
+            Some(p) => p,
+            None => {
+                Runtime::log(ByteString::from_literal("Proposal not found"));
+                return false;
+            }
+        };
+
+        if proposal.status != ProposalStatus::Pending && proposal.status != ProposalStatus::Active {
+            Runtime::log(ByteString::from_literal("Proposal is not active"));
+            return false;
+        }
+
+        let current_time = Runtime::get_time();
+
+        // Check if voting has ended
+        if current_time <= proposal.end_time {
+            Runtime::log(ByteString::from_literal("Voting still active"));
+            return false;
+        }
+
+        // Check if proposal succeeded
+        if !self.is_proposal_successful(&proposal) {
+            proposal.status = ProposalStatus::Defeated;
+        } else {
+            proposal.status = ProposalStatus::Queued;
+        }
+
+        // Store updated proposal
+        let storage = Storage::get_context();
+        let proposal_key = self.proposal_prefix.concat(&proposal_id.into_byte_string());
+        let storage_clone = storage.clone(); Storage::put(storage_clone, proposal_key, self.serialize_proposal(proposal.clone()));
+
+        if proposal.status == ProposalStatus::Queued {
+            let mut event_data = Array::new();
+            event_data.push(proposal_id.into_any());
+            event_data.push(Int256::new(proposal.execution_time as i64).into_any());
+            Runtime::notify(ByteString::from_literal("ProposalQueued"), event_data);
+        } else {
+            let mut event_data = Array::new();
+            event_data.push(proposal_id.into_any());
+            Runtime::notify(ByteString::from_literal("ProposalDefeated"), event_data);
+        }
+
+        true
+    }
+
+    /// Cancel a proposal before its voting starts (proposer only)
+    #[method]
+    pub fn cancel_proposal(&self, proposal_id: Int256) -> bool {
+        let mut proposal = match self.get_proposal_data(proposal_id) {
+            Some(p) => p,
+            None => {
+                Runtime::log(ByteString::from_literal("Proposal not found"));
+                return false;
+            }
+        };
+
+        if !Runtime::check_witness(proposal.proposer) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only the proposer can cancel"));
+            return false;
+        }
+
+        if proposal.status != ProposalStatus::Pending || Runtime::get_time() >= proposal.start_time {
+            Runtime::log(ByteString::from_literal("Proposal is no longer pending"));
+            return false;
+        }
+
+        proposal.status = ProposalStatus::Cancelled;
+        self.store_proposal(proposal_id, proposal);
+
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        Runtime::notify(ByteString::from_literal("ProposalCancelled"), event_data);
+        true
+    }
+
+    /// Veto a queued proposal during its timelock (guardian only)
+    #[method]
+    pub fn veto_proposal(&self, proposal_id: Int256) -> bool {
+        if !self.is_guardian() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only guardian can veto"));
+            return false;
+        }
+
+        let mut proposal = match self.get_proposal_data(proposal_id) {
+            Some(p) => p,
+            None => {
+                Runtime::log(ByteString::from_literal("Proposal not found"));
+                return false;
+            }
+        };
+
+        if proposal.status != ProposalStatus::Queued {
+            Runtime::log(ByteString::from_literal("Proposal not queued"));
+            return false;
+        }
+
+        if Runtime::get_time() >= proposal.execution_time {
+            Runtime::log(ByteString::from_literal("Timelock has ended"));
+            return false;
+        }
+
+        proposal.status = ProposalStatus::Vetoed;
+        self.store_proposal(proposal_id, proposal);
+
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        Runtime::notify(ByteString::from_literal("ProposalVetoed"), event_data);
+        true
+    }
+
+    /// Execute a queued proposal
+    #[method]
+    pub fn execute_proposal(&self, proposal_id: Int256) -> bool {
+        if self.is_execution_paused() {
+            Runtime::log(ByteString::from_literal("Execution paused"));
+            return false;
+        }
+
+        let mut proposal = match self.get_proposal_data(proposal_id) {
+            Some(p) => p,
+            None => {
+                Runtime::log(ByteString::from_literal("Proposal not found"));
+                return false;
+            }
+        };
+
+        let current_time = Runtime::get_time();
+
+        // Check if proposal is queued and ready for execution
+        if proposal.status != ProposalStatus::Queued {
+            Runtime::log(ByteString::from_literal("Proposal not queued"));
+            return false;
+        }
+
+        if current_time < proposal.execution_time {
+            Runtime::log(ByteString::from_literal("Execution time not reached"));
+            return false;
+        }
+
+        if self.is_past_grace_period(&proposal, current_time) {
+            proposal.status = ProposalStatus::Expired;
+            self.store_proposal(proposal_id, proposal);
+
+            let mut event_data = Array::new();
+            event_data.push(proposal_id.into_any());
+            Runtime::notify(ByteString::from_literal("ProposalExpired"), event_data);
+            Runtime::log(ByteString::from_literal("Proposal expired"));
+            return false;
+        }
+
+        // Execute the proposal with proper implementation
+        let success = if let Some(action) = self.get_proposal_action(proposal_id) {
+            self.execute_proposal_action(proposal_id, action)
+        } else if let Some((recipient, amount)) = self.get_treasury_spend(proposal_id) {
+            self.execute_treasury_spend(proposal_id, proposal.target_contract, recipient, amount)
+        } else if let Some((target, call_data)) = self.get_bridge_proposal(proposal_id) {
+            self.relay_bridge_action(proposal_id, proposal.target_contract, target, call_data)
+        } else if proposal.call_data.is_empty() {
+            // Simple parameter change or administrative action
+            self.execute_administrative_action(proposal.target_contract, proposal.description.clone())
+        } else {
+            // Contract call with specific data
+            self.execute_contract_call(proposal.target_contract, proposal.call_data.clone())
+        };
+
+        // A failed call is final: the proposal cannot be retried and has to be re-proposed
+        proposal.status = if success { ProposalStatus::Executed } else { ProposalStatus::Failed };
+
+        // Store updated proposal
+        let storage = Storage::get_context();
+        let proposal_key = self.proposal_prefix.concat(&proposal_id.into_byte_string());
+        Storage::put(storage, proposal_key, self.serialize_proposal(proposal.clone()));
+
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        event_data.push(proposal.target_contract.into_any());
+
+        if success {
+            Runtime::notify(ByteString::from_literal("ProposalExecuted"), event_data);
+            true
+        } else {
+            Runtime::notify(ByteString::from_literal("ProposalExecutionFailed"), event_data);
+            Runtime::log(ByteString::from_literal("Proposal execution failed"));
+            false
+        }
+    }
+
+    /// Get proposal information
+    #[method]
+    #[safe]
+    pub fn get_proposal(&self, proposal_id: Int256) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
         match self.get_proposal_data(proposal_id) {
             Some(proposal) => {
                 result.put(ByteString::from_literal("id"), proposal.id.into_any());
@@ -529,15 +1364,59 @@ impl Governance {
                 result.put(ByteString::from_literal("title"), proposal.title.clone().into_any());
                 result.put(ByteString::from_literal("description"), proposal.description.clone().into_any());
                 result.put(ByteString::from_literal("target_contract"), proposal.target_contract.into_any());
+                result.put(ByteString::from_literal("snapshot_time"), Int256::new(proposal.snapshot_time as i64).into_any());
                 result.put(ByteString::from_literal("start_time"), Int256::new(proposal.start_time as i64).into_any());
                 result.put(ByteString::from_literal("end_time"), Int256::new(proposal.end_time as i64).into_any());
                 result.put(ByteString::from_literal("execution_time"), Int256::new(proposal.execution_time as i64).into_any());
                 result.put(ByteString::from_literal("for_votes"), proposal.for_votes.into_any());
                 result.put(ByteString::from_literal("against_votes"), proposal.against_votes.into_any());
                 result.put(ByteString::from_literal("abstain_votes"), proposal.abstain_votes.into_any());
-                result.put(ByteString::from_literal("status"), Int256::new(proposal.status.to_u8() as i64).into_any());
+                // Queued proposals past their grace period read as expired before anyone touches them
+                let status = if self.is_past_grace_period(&proposal, Runtime::get_time()) {
+                    ProposalStatus::Expired
+                } else {
+                    proposal.status
+                };
+                result.put(ByteString::from_literal("status"), Int256::new(status.to_u8() as i64).into_any());
                 result.put(ByteString::from_literal("quorum_required"), proposal.quorum_required.into_any());
 
+                // Decoded intent, so voters need not parse call data themselves
+                let kind = match self.get_proposal_action(proposal_id) {
+                    Some(ProposalAction::ParameterChange { key, value }) => {
+                        result.put(ByteString::from_literal("parameter_key"), key.into_any());
+                        result.put(ByteString::from_literal("parameter_value"), value.into_any());
+                        "ParameterChange"
+                    }
+                    Some(ProposalAction::TreasurySpend { token, to, amount }) => {
+                        result.put(ByteString::from_literal("treasury_token"), token.into_any());
+                        result.put(ByteString::from_literal("treasury_recipient"), to.into_any());
+                        result.put(ByteString::from_literal("treasury_amount"), amount.into_any());
+                        "TreasurySpend"
+                    }
+                    Some(ProposalAction::Upgrade { nef_hash, manifest_hash }) => {
+                        result.put(ByteString::from_literal("nef_hash"), nef_hash.into_any());
+                        result.put(ByteString::from_literal("manifest_hash"), manifest_hash.into_any());
+                        "Upgrade"
+                    }
+                    None => {
+                        if let Some((recipient, amount)) = self.get_treasury_spend(proposal_id) {
+                            result.put(ByteString::from_literal("treasury_recipient"), recipient.into_any());
+                            result.put(ByteString::from_literal("treasury_amount"), amount.into_any());
+                            "TreasurySpend"
+                        } else if let Some((target, _)) = self.get_bridge_proposal(proposal_id) {
+                            result.put(ByteString::from_literal("bridge_target"), target.into_any());
+                            "BridgeAction"
+                        } else if let Some((method, args)) = calldata::decode(&proposal.call_data) {
+                            result.put(ByteString::from_literal("call_method"), method.into_any());
+                            result.put(ByteString::from_literal("call_args"), args.into_any());
+                            "ContractCall"
+                        } else {
+                            "Administrative"
+                        }
+                    }
+                };
+                result.put(ByteString::from_literal("kind"), ByteString::from_literal(kind).into_any());
+
                 let _current_time = Runtime::get_time();
                 let total_votes = proposal.for_votes.checked_add(&proposal.against_votes).checked_add(&proposal.abstain_votes);
                 result.put(ByteString::from_literal("total_votes"), total_votes.into_any());
@@ -554,12 +1433,454 @@ impl Governance {
         result
     }
 
-    /// Get voting power for an address
+    /// Delegate the voting power of `delegator` to `delegatee`.
+    ///
+    /// Delegation is single-hop: an account that has delegated cannot receive delegations,
+    /// and an account holding delegations cannot delegate onwards. Delegating to yourself
+    /// is the same as `undelegate`.
+    #[method]
+    pub fn delegate(&self, delegator: H160, delegatee: H160) -> bool {
+        if !Runtime::check_witness(delegator) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        if delegatee == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid delegatee"));
+            return false;
+        }
+
+        let current = self.get_delegate(delegator);
+        if current == delegatee {
+            Runtime::log(ByteString::from_literal("Already delegated to this account"));
+            return false;
+        }
+
+        if delegatee != delegator {
+            if self.get_delegate(delegatee) != delegatee {
+                Runtime::log(ByteString::from_literal("Delegatee has delegated its votes"));
+                return false;
+            }
+
+            if self.get_delegator_count(delegator) > 0 {
+                Runtime::log(ByteString::from_literal("Delegator holds delegated votes"));
+                return false;
+            }
+
+            if self.get_registry_entry(delegator).is_some() {
+                Runtime::log(ByteString::from_literal("Registered delegates cannot delegate"));
+                return false;
+            }
+        }
+
+        self.set_delegate(delegator, current, delegatee);
+        true
+    }
+
+    /// Return the voting power of `delegator` to itself
+    #[method]
+    pub fn undelegate(&self, delegator: H160) -> bool {
+        if !Runtime::check_witness(delegator) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        let current = self.get_delegate(delegator);
+        if current == delegator {
+            Runtime::log(ByteString::from_literal("Not delegated"));
+            return false;
+        }
+
+        self.set_delegate(delegator, current, delegator);
+        true
+    }
+
+    /// Get the account that votes with `account`'s tokens (the account itself if undelegated)
+    #[method]
+    #[safe]
+    pub fn delegates(&self, account: H160) -> H160 {
+        self.get_delegate(account)
+    }
+
+    /// Get the voting power delegated to `delegate` by other accounts
+    #[method]
+    #[safe]
+    pub fn get_delegated_votes(&self, delegate: H160) -> Int256 {
+        // Delegation is single-hop, so an account that has delegated holds no delegations
+        if self.get_delegate(delegate) != delegate {
+            return Int256::zero();
+        }
+
+        let mut args = Array::new();
+        args.push(delegate.into_any());
+        let balance = self.call_governance_token("balance_of", args);
+        self.get_voting_power(delegate).checked_sub(&balance)
+    }
+
+    /// Register as a delegate, or update the statement of an existing registration.
+    ///
+    /// `statement_hash` is the SHA-256 of the delegate's off-chain platform statement.
+    #[method]
+    pub fn register_delegate(&self, delegate: H160, statement_hash: ByteString) -> bool {
+        if !Runtime::check_witness(delegate) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        if statement_hash.len() != 32 {
+            Runtime::log(ByteString::from_literal("Invalid statement hash"));
+            return false;
+        }
+
+        if self.get_delegate(delegate) != delegate {
+            Runtime::log(ByteString::from_literal("Delegates cannot delegate their own votes"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let entry_key = self.registry_entry_prefix.concat(&delegate.into_byte_string());
+        let (index, registered_at, is_update) = match self.get_registry_entry(delegate) {
+            Some((index, registered_at, _)) => (index, registered_at, true),
+            None => {
+                let count = self.get_registered_delegate_count();
+                let index_key = self.registry_index_prefix.concat(&ByteString::from_bytes(&count.to_le_bytes()));
+                Storage::put(storage.clone(), index_key, delegate.into_byte_string());
+                Storage::put(storage.clone(), self.registry_count_key.clone(), ByteString::from_bytes(&(count + 1).to_le_bytes()));
+                (count, Runtime::get_time(), false)
+            }
+        };
+
+        let entry = ByteString::from_bytes(&index.to_le_bytes())
+            .concat(&ByteString::from_bytes(&registered_at.to_le_bytes()))
+            .concat(&statement_hash);
+        Storage::put(storage, entry_key, entry);
+
+        let mut event_data = Array::new();
+        event_data.push(delegate.into_any());
+        event_data.push(statement_hash.into_any());
+        let event_name = if is_update { "DelegateStatementUpdated" } else { "DelegateRegistered" };
+        Runtime::notify(ByteString::from_literal(event_name), event_data);
+        true
+    }
+
+    /// Remove a delegate from the registry; existing delegations are kept
+    #[method]
+    pub fn unregister_delegate(&self, delegate: H160) -> bool {
+        if !Runtime::check_witness(delegate) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        let index = match self.get_registry_entry(delegate) {
+            Some((index, _, _)) => index,
+            None => {
+                Runtime::log(ByteString::from_literal("Delegate not registered"));
+                return false;
+            }
+        };
+
+        // Move the last delegate into the freed slot to keep the index dense
+        let storage = Storage::get_context();
+        let last = self.get_registered_delegate_count() - 1;
+        let last_key = self.registry_index_prefix.concat(&ByteString::from_bytes(&last.to_le_bytes()));
+        if index != last {
+            if let Some(moved_bytes) = Storage::get(storage.clone(), last_key.clone()) {
+                let moved = H160::from_byte_string(moved_bytes.clone());
+                let index_key = self.registry_index_prefix.concat(&ByteString::from_bytes(&index.to_le_bytes()));
+                Storage::put(storage.clone(), index_key, moved_bytes);
+
+                if let Some((_, registered_at, statement_hash)) = self.get_registry_entry(moved) {
+                    let entry = ByteString::from_bytes(&index.to_le_bytes())
+                        .concat(&ByteString::from_bytes(&registered_at.to_le_bytes()))
+                        .concat(&statement_hash);
+                    Storage::put(storage.clone(), self.registry_entry_prefix.concat(&moved.into_byte_string()), entry);
+                }
+            }
+        }
+
+        Storage::delete(storage.clone(), last_key);
+        Storage::delete(storage.clone(), self.registry_entry_prefix.concat(&delegate.into_byte_string()));
+        if last == 0 {
+            Storage::delete(storage, self.registry_count_key.clone());
+        } else {
+            Storage::put(storage, self.registry_count_key.clone(), ByteString::from_bytes(&last.to_le_bytes()));
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(delegate.into_any());
+        Runtime::notify(ByteString::from_literal("DelegateUnregistered"), event_data);
+        true
+    }
+
+    /// Get the number of registered delegates
+    #[method]
+    #[safe]
+    pub fn get_registered_delegate_count(&self) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.registry_count_key.clone()) {
+            Some(count_bytes) => {
+                let bytes = count_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    /// Get a page of registered delegates with their statement and current voting power,
+    /// starting at registry index `cursor`
+    #[method]
+    #[safe]
+    pub fn get_registered_delegates(&self, cursor: u32, limit: u32) -> Page<Map<ByteString, Any>> {
+        let mut items = Array::new();
+        let count = self.get_registered_delegate_count();
+        let start = cursor.min(count);
+        let end = start.saturating_add(limit.min(MAX_DELEGATE_PAGE)).min(count);
+
+        let storage = Storage::get_context();
+        for index in start..end {
+            let index_key = self.registry_index_prefix.concat(&ByteString::from_bytes(&index.to_le_bytes()));
+            if let Some(delegate_bytes) = Storage::get(storage.clone(), index_key) {
+                items.push(self.get_delegate_info(H160::from_byte_string(delegate_bytes)));
+            }
+        }
+
+        let next_cursor = if end < count { Int256::new(end as i64) } else { Int256::minus_one() };
+        Page::new(items, next_cursor, Int256::new(count as i64))
+    }
+
+    /// Get the registry entry of a delegate (with `registered` = 0 if not registered)
+    #[method]
+    #[safe]
+    pub fn get_delegate_info(&self, delegate: H160) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        result.put(ByteString::from_literal("delegate"), delegate.into_any());
+
+        match self.get_registry_entry(delegate) {
+            Some((_, registered_at, statement_hash)) => {
+                result.put(ByteString::from_literal("registered"), Int256::one().into_any());
+                result.put(ByteString::from_literal("registered_at"), Int256::new(registered_at as i64).into_any());
+                result.put(ByteString::from_literal("statement_hash"), statement_hash.into_any());
+            },
+            None => {
+                result.put(ByteString::from_literal("registered"), Int256::zero().into_any());
+            }
+        }
+
+        result.put(ByteString::from_literal("voting_power"), self.get_voting_power(delegate).into_any());
+        result.put(ByteString::from_literal("delegated_votes"), self.get_delegated_votes(delegate).into_any());
+        result.put(ByteString::from_literal("delegator_count"), Int256::new(self.get_delegator_count(delegate) as i64).into_any());
+        result
+    }
+
+    /// Get the current voting power of an address
+    #[method]
+    #[safe]
+    pub fn get_voting_power(&self, account: H160) -> Int256 {
+        let mut args = Array::new();
+        args.push(account.into_any());
+        self.call_governance_token("get_votes", args)
+    }
+
+    /// Get the voting power of an address as of a timestamp
+    #[method]
+    #[safe]
+    pub fn get_voting_power_at(&self, account: H160, timestamp: u64) -> Int256 {
+        let mut args = Array::new();
+        args.push(account.into_any());
+        args.push(Int256::from_u64(timestamp).into_any());
+        self.call_governance_token("get_past_votes", args)
+    }
+
+    /// Get the total voting power (governance token supply) as of a timestamp
+    #[method]
+    #[safe]
+    pub fn get_total_voting_power_at(&self, timestamp: u64) -> Int256 {
+        let mut args = Array::new();
+        args.push(Int256::from_u64(timestamp).into_any());
+        self.call_governance_token("get_past_total_supply", args)
+    }
+
+    /// NEP-17 receiver callback: tokens sent to the governance contract are credited to the treasury
+    #[method]
+    #[wasm_export(name = "onNEP17Payment")]
+    pub fn deposit_treasury(&self, from: H160, amount: Int256, _data: Any) -> bool {
+        // The calling contract is the token; a direct invocation is not a deposit
+        let token = Runtime::get_calling_script_hash();
+        if token == Runtime::get_entry_script_hash() {
+            Runtime::log(ByteString::from_literal("Deposits must come from a token contract"));
+            return false;
+        }
+
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid deposit amount"));
+            return false;
+        }
+
+        let balance = self.get_treasury_balance(token).checked_add(&amount);
+        self.set_treasury_balance(token, balance);
+
+        let mut event_data = Array::new();
+        event_data.push(token.into_any());
+        event_data.push(from.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("TreasuryDeposit"), event_data);
+
+        true
+    }
+
+    /// Get the treasury balance of a token
+    #[method]
+    #[safe]
+    pub fn get_treasury_balance(&self, token: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.treasury_prefix.concat(&token.into_byte_string())) {
+            Some(balance_bytes) => Int256::from_byte_string(balance_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Configure the voter reward pool (executed proposals only).
+    ///
+    /// Each vote cast with at least `min_voting_power` accrues `reward_per_vote` of `token`,
+    /// set aside from the treasury, for the first `max_rewarded_votes` votes of a proposal.
+    /// The minimum voting power keeps splitting holdings across many accounts from farming
+    /// rewards. A zero reward disables the pool.
+    #[method]
+    pub fn set_voter_rewards(
+        &self,
+        token: H160,
+        reward_per_vote: Int256,
+        max_rewarded_votes: u32,
+        min_voting_power: Int256
+    ) -> bool {
+        if !self.is_self_call() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Voter rewards are set by proposal"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        if reward_per_vote.is_zero() {
+            Storage::delete(storage, self.voter_reward_config_key.clone());
+            Runtime::notify(ByteString::from_literal("VoterRewardsDisabled"), Array::new());
+            return true;
+        }
+
+        if token == H160::zero() || reward_per_vote < Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid voter reward"));
+            return false;
+        }
+
+        if max_rewarded_votes == 0 || max_rewarded_votes > MAX_REWARDED_VOTES {
+            Runtime::log(ByteString::from_literal("Invalid rewarded vote cap"));
+            return false;
+        }
+
+        if min_voting_power <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Minimum voting power must be positive"));
+            return false;
+        }
+
+        let reward_bytes = reward_per_vote.into_byte_string();
+        let config = token.into_byte_string()
+            .concat(&ByteString::from_bytes(&max_rewarded_votes.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&[reward_bytes.len() as u8]))
+            .concat(&reward_bytes)
+            .concat(&min_voting_power.into_byte_string());
+        Storage::put(storage, self.voter_reward_config_key.clone(), config);
+
+        let mut event_data = Array::new();
+        event_data.push(token.into_any());
+        event_data.push(reward_per_vote.into_any());
+        event_data.push(Int256::new(max_rewarded_votes as i64).into_any());
+        event_data.push(min_voting_power.into_any());
+        Runtime::notify(ByteString::from_literal("VoterRewardsConfigured"), event_data);
+
+        true
+    }
+
+    /// Get the voter reward configuration (empty if the pool is disabled)
+    #[method]
+    #[safe]
+    pub fn get_voter_rewards(&self) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        if let Some((token, reward_per_vote, max_rewarded_votes, min_voting_power)) = self.get_voter_reward_config() {
+            result.put(ByteString::from_literal("token"), token.into_any());
+            result.put(ByteString::from_literal("reward_per_vote"), reward_per_vote.into_any());
+            result.put(ByteString::from_literal("max_rewarded_votes"), Int256::new(max_rewarded_votes as i64).into_any());
+            result.put(ByteString::from_literal("min_voting_power"), min_voting_power.into_any());
+        }
+        result
+    }
+
+    /// Get the number of votes on a proposal that earned a reward
+    #[method]
+    #[safe]
+    pub fn get_rewarded_vote_count(&self, proposal_id: Int256) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.rewarded_votes_prefix.concat(&proposal_id.into_byte_string())) {
+            Some(count_bytes) => {
+                let bytes = count_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            }
+            None => 0,
+        }
+    }
+
+    /// Get the voter rewards of `token` an account can claim
     #[method]
     #[safe]
-    pub fn get_voting_power(&self, _account: H160) -> Int256 {
-        // Complete implementation querying the governance token contract
-        Int256::new(1000)
+    pub fn get_claimable_voter_reward(&self, voter: H160, token: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.get_voter_reward_key(voter, token)) {
+            Some(reward_bytes) => Int256::from_byte_string(reward_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Claim the accrued voter rewards of `token`
+    #[method]
+    pub fn claim_voter_rewards(&self, voter: H160, token: H160) -> Int256 {
+        if !Runtime::check_witness(voter) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return Int256::zero();
+        }
+
+        let amount = self.get_claimable_voter_reward(voter, token);
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No voter rewards to claim"));
+            return Int256::zero();
+        }
+
+        // Cleared before the transfer so a re-entrant call cannot claim twice
+        let storage = Storage::get_context();
+        Storage::delete(storage, self.get_voter_reward_key(voter, token));
+
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(voter.into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+        assert_with_code(
+            Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, args),
+            ErrorCode::CallFailed,
+            "Voter reward transfer failed",
+        );
+
+        let mut event_data = Array::new();
+        event_data.push(voter.into_any());
+        event_data.push(token.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("VoterRewardClaimed"), event_data);
+
+        amount
     }
 
     /// Get proposal count
@@ -579,10 +1900,62 @@ impl Governance {
         let storage = Storage::get_context();
         let proposal_key = self.proposal_prefix.concat(&proposal_id.into_byte_string());
 
-        match Storage::get(storage, proposal_key) {
-            Some(proposal_data) => Some(self.deserialize_proposal(proposal_data)),
-            None => None,
+        Storage::get(storage, proposal_key).and_then(|proposal_data| self.deserialize_proposal(proposal_data))
+    }
+
+    fn store_proposal(&self, proposal_id: Int256, proposal: Proposal) {
+        let storage = Storage::get_context();
+        let proposal_key = self.proposal_prefix.concat(&proposal_id.into_byte_string());
+        Storage::put(storage, proposal_key, self.serialize_proposal(proposal));
+    }
+
+    fn is_past_grace_period(&self, proposal: &Proposal, current_time: u64) -> bool {
+        proposal.status == ProposalStatus::Queued
+            && current_time > proposal.execution_time.saturating_add(EXECUTION_GRACE_PERIOD)
+    }
+
+    fn is_admin(&self) -> bool {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.admin_key.clone()) {
+            Some(admin_bytes) => Runtime::check_witness(H160::from_byte_string(admin_bytes)),
+            None => false,
+        }
+    }
+
+    /// Whether the call comes from this contract itself, i.e. from one of its executed proposals
+    fn is_self_call(&self) -> bool {
+        Runtime::get_calling_script_hash() == Runtime::get_executing_script_hash()
+    }
+
+    fn is_guardian(&self) -> bool {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.guardian_key.clone()) {
+            Some(guardian_bytes) => Runtime::check_witness(H160::from_byte_string(guardian_bytes)),
+            None => false,
+        }
+    }
+
+    fn get_snapshot_root(&self, proposal_id: Int256) -> Option<ByteString> {
+        let storage = Storage::get_context();
+        Storage::get(storage, self.snapshot_root_prefix.concat(&proposal_id.into_byte_string()))
+    }
+
+    fn verify_merkle_proof(&self, leaf: ByteString, proof: &Array<ByteString>, root: ByteString) -> bool {
+        let mut node = leaf;
+        for i in bounded_iter(&proof, MAX_PROOF_DEPTH) {
+            let sibling = proof.get(i);
+            if sibling.len() != 32 {
+                return false;
+            }
+
+            // Sorted pairs make the proof independent of the leaf position
+            node = if node < sibling {
+                sha256(node.concat(&sibling)).into_byte_string()
+            } else {
+                sha256(sibling.concat(&node)).into_byte_string()
+            };
         }
+        node == root
     }
 
     fn get_vote_key(&self, proposal_id: Int256, voter: H160) -> ByteString {
@@ -603,10 +1976,10 @@ impl Governance {
                         bytes[4], bytes[5], bytes[6], bytes[7]
                     ])
                 } else {
-                    86400 // 1 day default
+                    SECONDS_PER_DAY
                 }
             },
-            None => 86400,
+            None => SECONDS_PER_DAY,
         }
     }
 
@@ -621,10 +1994,10 @@ impl Governance {
                         bytes[4], bytes[5], bytes[6], bytes[7]
                     ])
                 } else {
-                    604800 // 7 days default
+                    SECONDS_PER_WEEK
                 }
             },
-            None => 604800,
+            None => SECONDS_PER_WEEK,
         }
     }
 
@@ -639,10 +2012,10 @@ impl Governance {
                         bytes[4], bytes[5], bytes[6], bytes[7]
                     ])
                 } else {
-                    172800 // 2 days default
+                    days(2)
                 }
             },
-            None => 172800,
+            None => days(2),
         }
     }
 
@@ -669,9 +2042,109 @@ impl Governance {
         }
     }
 
-    fn get_total_voting_supply(&self) -> Int256 {
-        // Complete implementation querying the governance token contract
-        Int256::new(1000000) // Placeholder for demonstration
+    /// Read-only call to the governance token returning an amount; a token that is not
+    /// deployed yet counts as zero
+    fn call_governance_token(&self, method: &str, args: Array<Any>) -> Int256 {
+        let token = match self.get_governance_token() {
+            Some(token) => token,
+            None => return Int256::zero(),
+        };
+
+        let result = Contract::call(token, ByteString::from_literal(method), CallFlags::ReadOnly, args);
+        if result.is_null() {
+            Int256::zero()
+        } else {
+            result.downcast_into()
+        }
+    }
+
+    fn get_governance_token(&self) -> Option<H160> {
+        let storage = Storage::get_context();
+        Storage::get(storage, self.governance_token_key.clone()).map(H160::from_byte_string)
+    }
+
+    /// Registry entry of a delegate as (index, registration time, statement hash)
+    fn get_registry_entry(&self, delegate: H160) -> Option<(u32, u64, ByteString)> {
+        let storage = Storage::get_context();
+        let bytes = Storage::get(storage, self.registry_entry_prefix.concat(&delegate.into_byte_string()))?.to_bytes();
+        if bytes.len() < 12 {
+            return None;
+        }
+
+        let index = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let registered_at = u64::from_le_bytes([
+            bytes[4], bytes[5], bytes[6], bytes[7],
+            bytes[8], bytes[9], bytes[10], bytes[11]
+        ]);
+        Some((index, registered_at, ByteString::from_bytes(&bytes[12..])))
+    }
+
+    fn get_delegate(&self, account: H160) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.delegate_prefix.concat(&account.into_byte_string())) {
+            Some(delegate_bytes) => H160::from_byte_string(delegate_bytes),
+            None => account,
+        }
+    }
+
+    fn set_delegate(&self, delegator: H160, current: H160, delegatee: H160) {
+        let storage = Storage::get_context();
+        let delegate_key = self.delegate_prefix.concat(&delegator.into_byte_string());
+        if delegatee == delegator {
+            Storage::delete(storage, delegate_key);
+        } else {
+            Storage::put(storage, delegate_key, delegatee.into_byte_string());
+        }
+
+        if current != delegator {
+            self.add_delegator_count(current, -1);
+        }
+        if delegatee != delegator {
+            self.add_delegator_count(delegatee, 1);
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(delegator.into_any());
+        event_data.push(current.into_any());
+        event_data.push(delegatee.into_any());
+        Runtime::notify(ByteString::from_literal("DelegateChanged"), event_data);
+
+        // The token moves the votes and keeps the checkpoints
+        let token = self.get_governance_token().unwrap_or(H160::zero());
+        let mut args = Array::new();
+        args.push(delegator.into_any());
+        args.push(delegatee.into_any());
+        assert_with_code(
+            Contract::call_bool(token, ByteString::from_literal("set_vote_delegate"), CallFlags::All, args),
+            ErrorCode::CallFailed,
+            "Governance token rejected the delegation",
+        );
+    }
+
+    fn get_delegator_count(&self, delegate: H160) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.delegator_count_prefix.concat(&delegate.into_byte_string())) {
+            Some(count_bytes) => {
+                let bytes = count_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    fn add_delegator_count(&self, delegate: H160, delta: i32) {
+        let storage = Storage::get_context();
+        let count_key = self.delegator_count_prefix.concat(&delegate.into_byte_string());
+        let count = self.get_delegator_count(delegate).saturating_add_signed(delta);
+        if count == 0 {
+            Storage::delete(storage, count_key);
+        } else {
+            Storage::put(storage, count_key, ByteString::from_bytes(&count.to_le_bytes()));
+        }
     }
 
     fn is_proposal_successful(&self, proposal: &Proposal) -> bool {
@@ -694,29 +2167,85 @@ impl Governance {
         Runtime::notify(ByteString::from_literal("VoterProposalAdded"), event_data);
     }
 
-    fn serialize_proposal(&self, _proposal: Proposal) -> ByteString {
-        // Simplified serialization - in production, use proper serialization
-        ByteString::from_literal("proposal_data")
+    /// Proposal layout: proposer (20) | target (20) | snapshot, start, end and execution
+    /// times (u64 LE each) | status (u8) | title length (u8) | title | description length
+    /// (u16 LE) | description | id, for, against, abstain and quorum, each as length (u8) |
+    /// value | call data
+    fn serialize_proposal(&self, proposal: Proposal) -> ByteString {
+        let mut data = proposal.proposer.into_byte_string()
+            .concat(&proposal.target_contract.into_byte_string())
+            .concat(&ByteString::from_bytes(&proposal.snapshot_time.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&proposal.start_time.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&proposal.end_time.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&proposal.execution_time.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&[proposal.status.to_u8()]))
+            .concat(&ByteString::from_bytes(&[proposal.title.len() as u8]))
+            .concat(&proposal.title)
+            .concat(&ByteString::from_bytes(&(proposal.description.len() as u16).to_le_bytes()))
+            .concat(&proposal.description);
+
+        for value in [
+            proposal.id,
+            proposal.for_votes,
+            proposal.against_votes,
+            proposal.abstain_votes,
+            proposal.quorum_required,
+        ] {
+            let value_bytes = value.into_byte_string();
+            data = data
+                .concat(&ByteString::from_bytes(&[value_bytes.len() as u8]))
+                .concat(&value_bytes);
+        }
+
+        data.concat(&proposal.call_data)
     }
 
-    fn deserialize_proposal(&self, _data: ByteString) -> Proposal {
-        // Simplified deserialization - in production, use proper parsing
-        Proposal {
-            id: Int256::zero(),
-            proposer: H160::zero(),
-            title: ByteString::empty(),
-            description: ByteString::empty(),
-            target_contract: H160::zero(),
-            call_data: ByteString::empty(),
-            start_time: 0,
-            end_time: 0,
-            execution_time: 0,
-            for_votes: Int256::zero(),
-            against_votes: Int256::zero(),
-            abstain_votes: Int256::zero(),
-            status: ProposalStatus::Pending,
-            quorum_required: Int256::zero(),
+    fn deserialize_proposal(&self, data: ByteString) -> Option<Proposal> {
+        let bytes = data.to_bytes();
+        if bytes.len() < 74 {
+            return None;
+        }
+
+        let read_u64 = |at: usize| u64::from_le_bytes([
+            bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3],
+            bytes[at + 4], bytes[at + 5], bytes[at + 6], bytes[at + 7]
+        ]);
+
+        let title_len = bytes[73] as usize;
+        let title = bytes.get(74..74 + title_len)?;
+        let mut offset = 74 + title_len;
+
+        let description_len = u16::from_le_bytes([*bytes.get(offset)?, *bytes.get(offset + 1)?]) as usize;
+        offset += 2;
+        let description = bytes.get(offset..offset + description_len)?;
+        offset += description_len;
+
+        let mut values = [Int256::zero(); 5];
+        for value in values.iter_mut() {
+            let value_len = *bytes.get(offset)? as usize;
+            offset += 1;
+            *value = Int256::from_byte_string(ByteString::from_bytes(bytes.get(offset..offset + value_len)?));
+            offset += value_len;
         }
+        let [id, for_votes, against_votes, abstain_votes, quorum_required] = values;
+
+        Some(Proposal {
+            id,
+            proposer: H160::from_byte_string(ByteString::from_bytes(&bytes[..20])),
+            title: ByteString::from_bytes(title),
+            description: ByteString::from_bytes(description),
+            target_contract: H160::from_byte_string(ByteString::from_bytes(&bytes[20..40])),
+            call_data: ByteString::from_bytes(&bytes[offset..]),
+            snapshot_time: read_u64(40),
+            start_time: read_u64(48),
+            end_time: read_u64(56),
+            execution_time: read_u64(64),
+            for_votes,
+            against_votes,
+            abstain_votes,
+            status: ProposalStatus::from_u8(bytes[72]),
+            quorum_required,
+        })
     }
 
     fn serialize_vote(&self, _vote: Vote) -> ByteString {
@@ -724,6 +2253,315 @@ impl Governance {
         ByteString::from_literal("vote_data")
     }
 
+    fn set_treasury_balance(&self, token: H160, balance: Int256) {
+        let storage = Storage::get_context();
+        let treasury_key = self.treasury_prefix.concat(&token.into_byte_string());
+        if balance <= Int256::zero() {
+            Storage::delete(storage, treasury_key);
+        } else {
+            Storage::put(storage, treasury_key, balance.into_byte_string());
+        }
+    }
+
+    /// Token, reward per vote, rewarded votes per proposal and minimum voting power
+    fn get_voter_reward_config(&self) -> Option<(H160, Int256, u32, Int256)> {
+        let storage = Storage::get_context();
+        let config = Storage::get(storage, self.voter_reward_config_key.clone())?.to_bytes();
+        if config.len() < 25 {
+            return None;
+        }
+
+        let token = H160::from_byte_string(ByteString::from_bytes(&config[..20]));
+        let max_rewarded_votes = u32::from_le_bytes([config[20], config[21], config[22], config[23]]);
+        let reward_end = 25 + config[24] as usize;
+        let reward_per_vote = Int256::from_byte_string(ByteString::from_bytes(config.get(25..reward_end)?));
+        let min_voting_power = Int256::from_byte_string(ByteString::from_bytes(&config[reward_end..]));
+        Some((token, reward_per_vote, max_rewarded_votes, min_voting_power))
+    }
+
+    fn get_voter_reward_key(&self, voter: H160, token: H160) -> ByteString {
+        self.voter_reward_prefix
+            .concat(&voter.into_byte_string())
+            .concat(&token.into_byte_string())
+    }
+
+    /// Sets aside the vote reward from the treasury if the pool is enabled, the vote carries
+    /// enough voting power and the proposal's cap is not reached. Votes that cannot be
+    /// rewarded, including when the treasury runs dry, are still counted.
+    fn accrue_voter_reward(&self, proposal_id: Int256, voter: H160, voting_power: Int256) {
+        let (token, reward_per_vote, max_rewarded_votes, min_voting_power) = match self.get_voter_reward_config() {
+            Some(config) => config,
+            None => return,
+        };
+
+        if voting_power < min_voting_power {
+            return;
+        }
+
+        let rewarded_votes = self.get_rewarded_vote_count(proposal_id);
+        if rewarded_votes >= max_rewarded_votes {
+            return;
+        }
+
+        let treasury_balance = self.get_treasury_balance(token);
+        if treasury_balance < reward_per_vote {
+            return;
+        }
+
+        let storage = Storage::get_context();
+        self.set_treasury_balance(token, treasury_balance.checked_sub(&reward_per_vote));
+        Storage::put(
+            storage.clone(),
+            self.rewarded_votes_prefix.concat(&proposal_id.into_byte_string()),
+            ByteString::from_bytes(&(rewarded_votes + 1).to_le_bytes()),
+        );
+        let claimable = self.get_claimable_voter_reward(voter, token).checked_add(&reward_per_vote);
+        Storage::put(storage, self.get_voter_reward_key(voter, token), claimable.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        event_data.push(voter.into_any());
+        event_data.push(token.into_any());
+        event_data.push(reward_per_vote.into_any());
+        Runtime::notify(ByteString::from_literal("VoterRewardAccrued"), event_data);
+    }
+
+    fn get_treasury_spend(&self, proposal_id: Int256) -> Option<(H160, Int256)> {
+        let storage = Storage::get_context();
+        let spend_bytes = Storage::get(storage, self.treasury_spend_prefix.concat(&proposal_id.into_byte_string()))?.to_bytes();
+        if spend_bytes.len() < 20 {
+            return None;
+        }
+
+        let recipient = H160::from_byte_string(ByteString::from_bytes(&spend_bytes[..20]));
+        let amount = Int256::from_byte_string(ByteString::from_bytes(&spend_bytes[20..]));
+        Some((recipient, amount))
+    }
+
+    fn execute_treasury_spend(&self, proposal_id: Int256, token: H160, recipient: H160, amount: Int256) -> bool {
+        let balance = self.get_treasury_balance(token);
+        if amount > balance {
+            Runtime::log(ByteString::from_literal("Insufficient treasury balance"));
+            return false;
+        }
+
+        // Debit before the transfer so a re-entrant call cannot spend the same funds twice
+        self.set_treasury_balance(token, balance.checked_sub(&amount));
+
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(recipient.into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+        if !Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, args) {
+            self.set_treasury_balance(token, balance);
+            Runtime::log(ByteString::from_literal("Treasury transfer failed"));
+            return false;
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        event_data.push(token.into_any());
+        event_data.push(recipient.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("TreasurySpend"), event_data);
+
+        true
+    }
+
+    fn get_bridge_proposal(&self, proposal_id: Int256) -> Option<(H160, ByteString)> {
+        let storage = Storage::get_context();
+        let bridge_bytes = Storage::get(storage, self.bridge_proposal_prefix.concat(&proposal_id.into_byte_string()))?.to_bytes();
+        if bridge_bytes.len() < 20 {
+            return None;
+        }
+
+        let target = H160::from_byte_string(ByteString::from_bytes(&bridge_bytes[..20]));
+        Some((target, ByteString::from_bytes(&bridge_bytes[20..])))
+    }
+
+    /// Hand an executed bridge proposal to the child DAO's adapter
+    fn relay_bridge_action(&self, proposal_id: Int256, child_dao: H160, target: H160, call_data: ByteString) -> bool {
+        let mut args = Array::new();
+        args.push(proposal_id.into_any());
+        args.push(target.into_any());
+        args.push(call_data.into_any());
+        let result = Contract::call(child_dao, ByteString::from_literal("enqueue_bridged_action"), CallFlags::All, args);
+        if result.is_null() {
+            Runtime::log(ByteString::from_literal("Child DAO did not accept the action"));
+            return false;
+        }
+
+        let action_id: Int256 = result.downcast_into();
+        if action_id <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Child DAO did not accept the action"));
+            return false;
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(proposal_id.into_any());
+        event_data.push(child_dao.into_any());
+        event_data.push(action_id.into_any());
+        Runtime::notify(ByteString::from_literal("BridgeActionRelayed"), event_data);
+        true
+    }
+
+    /// Bridged action layout: parent (20) | execution time (u64 LE) | status (u8) | target (20) |
+    /// source proposal id length (u8) | source proposal id | call data
+    fn get_bridged_action_data(&self, action_id: Int256) -> Option<BridgedAction> {
+        let storage = Storage::get_context();
+        let bytes = Storage::get(storage, self.bridged_action_prefix.concat(&action_id.into_byte_string()))?.to_bytes();
+        if bytes.len() < 50 {
+            return None;
+        }
+
+        let id_len = bytes[49] as usize;
+        if bytes.len() < 50 + id_len {
+            return None;
+        }
+
+        Some(BridgedAction {
+            parent: H160::from_byte_string(ByteString::from_bytes(&bytes[..20])),
+            execution_time: u64::from_le_bytes([
+                bytes[20], bytes[21], bytes[22], bytes[23],
+                bytes[24], bytes[25], bytes[26], bytes[27]
+            ]),
+            status: ProposalStatus::from_u8(bytes[28]),
+            target_contract: H160::from_byte_string(ByteString::from_bytes(&bytes[29..49])),
+            source_proposal_id: Int256::from_byte_string(ByteString::from_bytes(&bytes[50..50 + id_len])),
+            call_data: ByteString::from_bytes(&bytes[50 + id_len..]),
+        })
+    }
+
+    fn store_bridged_action(&self, action_id: Int256, action: BridgedAction) {
+        let source_id = action.source_proposal_id.into_byte_string();
+        let data = action.parent.into_byte_string()
+            .concat(&ByteString::from_bytes(&action.execution_time.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&[action.status.to_u8()]))
+            .concat(&action.target_contract.into_byte_string())
+            .concat(&ByteString::from_bytes(&[source_id.len() as u8]))
+            .concat(&source_id)
+            .concat(&action.call_data);
+
+        let storage = Storage::get_context();
+        Storage::put(storage, self.bridged_action_prefix.concat(&action_id.into_byte_string()), data);
+    }
+
+    fn get_proposal_action(&self, proposal_id: Int256) -> Option<ProposalAction> {
+        let storage = Storage::get_context();
+        let action_key = self.proposal_action_prefix.concat(&proposal_id.into_byte_string());
+        let bytes = Storage::get(storage, action_key)?.to_bytes();
+        if bytes.is_empty() {
+            return None;
+        }
+
+        let payload = &bytes[1..];
+        match bytes[0] {
+            1 => {
+                let key_len = *payload.first()? as usize;
+                if payload.len() < 1 + key_len {
+                    return None;
+                }
+                Some(ProposalAction::ParameterChange {
+                    key: ByteString::from_bytes(&payload[1..1 + key_len]),
+                    value: Int256::from_byte_string(ByteString::from_bytes(&payload[1 + key_len..])),
+                })
+            }
+            2 if payload.len() >= 40 => Some(ProposalAction::TreasurySpend {
+                token: H160::from_byte_string(ByteString::from_bytes(&payload[..20])),
+                to: H160::from_byte_string(ByteString::from_bytes(&payload[20..40])),
+                amount: Int256::from_byte_string(ByteString::from_bytes(&payload[40..])),
+            }),
+            3 if payload.len() == 64 => Some(ProposalAction::Upgrade {
+                nef_hash: ByteString::from_bytes(&payload[..32]),
+                manifest_hash: ByteString::from_bytes(&payload[32..]),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Action record layout: kind (u8) | payload, where the payload is
+    /// key length (u8) | key | value for parameter changes, token | recipient | amount for
+    /// treasury spends and NEF hash | manifest hash for upgrades
+    fn store_proposal_action(&self, proposal_id: Int256, action: &ProposalAction) {
+        let payload = match action {
+            ProposalAction::ParameterChange { key, value } => ByteString::from_bytes(&[key.len() as u8])
+                .concat(key)
+                .concat(&value.into_byte_string()),
+            ProposalAction::TreasurySpend { token, to, amount } => token.into_byte_string()
+                .concat(&to.into_byte_string())
+                .concat(&amount.into_byte_string()),
+            ProposalAction::Upgrade { nef_hash, manifest_hash } => nef_hash.concat(manifest_hash),
+        };
+
+        let storage = Storage::get_context();
+        let action_key = self.proposal_action_prefix.concat(&proposal_id.into_byte_string());
+        Storage::put(storage, action_key, ByteString::from_bytes(&[action.kind()]).concat(&payload));
+    }
+
+    fn execute_proposal_action(&self, proposal_id: Int256, action: ProposalAction) -> bool {
+        match action {
+            ProposalAction::ParameterChange { key, value } => {
+                // Bounds are checked again in case they changed since the proposal was made
+                let (storage_key, encoded) = match self.encode_parameter(&key, value) {
+                    Some(parameter) => parameter,
+                    None => {
+                        Runtime::log(ByteString::from_literal("Invalid parameter change"));
+                        return false;
+                    }
+                };
+                Storage::put(Storage::get_context(), storage_key, encoded);
+
+                let mut event_data = Array::new();
+                event_data.push(key.into_any());
+                event_data.push(value.into_any());
+                Runtime::notify(ByteString::from_literal("ParameterChanged"), event_data);
+                true
+            }
+            ProposalAction::TreasurySpend { token, to, amount } => {
+                self.execute_treasury_spend(proposal_id, token, to, amount)
+            }
+            ProposalAction::Upgrade { nef_hash, manifest_hash } => {
+                let approved = nef_hash.concat(&manifest_hash);
+                Storage::put(Storage::get_context(), self.approved_upgrade_key.clone(), approved);
+
+                let mut event_data = Array::new();
+                event_data.push(proposal_id.into_any());
+                event_data.push(nef_hash.into_any());
+                event_data.push(manifest_hash.into_any());
+                Runtime::notify(ByteString::from_literal("UpgradeApproved"), event_data);
+                true
+            }
+        }
+    }
+
+    /// Storage key and encoding of a governance parameter, or `None` if `key` is unknown
+    /// or `value` is outside the bounds `initialize` enforces
+    fn encode_parameter(&self, key: &ByteString, value: Int256) -> Option<(ByteString, ByteString)> {
+        let in_range = |min: u64, max: u64| value >= Int256::from_u64(min) && value <= Int256::from_u64(max);
+        let as_u64 = || {
+            let bytes = value.into_byte_string().to_bytes();
+            let mut le = [0u8; 8];
+            let len = if bytes.len() < 8 { bytes.len() } else { 8 };
+            le[..len].copy_from_slice(&bytes[..len]);
+            u64::from_le_bytes(le)
+        };
+
+        if *key == ByteString::from_literal("voting_delay") && in_range(SECONDS_PER_HOUR, SECONDS_PER_WEEK) {
+            Some((self.voting_delay_key.clone(), ByteString::from_bytes(&as_u64().to_le_bytes())))
+        } else if *key == ByteString::from_literal("voting_period") && in_range(SECONDS_PER_DAY, days(30)) {
+            Some((self.voting_period_key.clone(), ByteString::from_bytes(&as_u64().to_le_bytes())))
+        } else if *key == ByteString::from_literal("execution_delay") && in_range(SECONDS_PER_DAY, SECONDS_PER_WEEK) {
+            Some((self.execution_delay_key.clone(), ByteString::from_bytes(&as_u64().to_le_bytes())))
+        } else if *key == ByteString::from_literal("quorum_percentage") && in_range(1, 10000) {
+            Some((self.quorum_percentage_key.clone(), ByteString::from_bytes(&(as_u64() as u32).to_le_bytes())))
+        } else if *key == ByteString::from_literal("proposal_threshold") && value >= Int256::zero() {
+            Some((self.proposal_threshold_key.clone(), value.into_byte_string()))
+        } else {
+            None
+        }
+    }
+
     fn execute_administrative_action(&self, target: H160, description: ByteString) -> bool {
         // Complete implementation for executing administrative actions like parameter changes
         Runtime::log(ByteString::from_literal("Administrative action executed"));
@@ -737,14 +2575,310 @@ impl Governance {
     }
 
     fn execute_contract_call(&self, target: H160, call_data: ByteString) -> bool {
-        // Complete implementation using Contract::call to invoke the target contract
-        Runtime::log(ByteString::from_literal("Contract call executed"));
-        
+        let (method, args) = match calldata::decode(&call_data) {
+            Some(call) => call,
+            None => {
+                Runtime::log(ByteString::from_literal("Invalid call data"));
+                return false;
+            }
+        };
+
+        if !Contract::call_bool(target, method.clone(), CallFlags::All, args) {
+            Runtime::log(ByteString::from_literal("Contract call failed"));
+            return false;
+        }
+
         let mut event_data = Array::new();
         event_data.push(target.into_any());
-        event_data.push(call_data.into_any());
+        event_data.push(method.into_any());
         Runtime::notify(ByteString::from_literal("ContractCallExecuted"), event_data);
-        
+
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use epicchain_contract::host;
+    use epicchain_contract::host::scenario::Scenario;
+
+    fn account(byte: u8) -> H160 {
+        H160::from_bytes(&[byte; 20])
+    }
+
+    fn deploy(hash: H160) -> Governance {
+        host::set_executing_script_hash(hash);
+        let dao = Governance::init();
+        assert!(dao.initialize(account(1), account(2), account(3), SECONDS_PER_HOUR, SECONDS_PER_DAY, SECONDS_PER_DAY, Int256::zero(), 400));
+        dao
+    }
+
+    /// Parent and child DAOs deployed side by side; the child answers calls through its adapter
+    fn deploy_pair() -> (H160, H160, Governance, Governance) {
+        let (parent_hash, child_hash) = (account(0xA0), account(0xC0));
+        let parent = deploy(parent_hash);
+        let child = deploy(child_hash);
+        host::register_contract(child_hash, |method, mut args| {
+            assert_eq!(*method, ByteString::from_literal("enqueue_bridged_action"));
+            let call_data: ByteString = args.pop().downcast_into();
+            let target: H160 = args.pop().downcast_into();
+            let source_proposal_id: Int256 = args.pop().downcast_into();
+            Governance::init().enqueue_bridged_action(source_proposal_id, target, call_data).into_any()
+        });
+        (parent_hash, child_hash, parent, child)
+    }
+
+    /// Governance token mock: 0x11 holds 600 votes and 0x12 holds 300 out of a 1,000 supply
+    fn register_governance_token() {
+        host::register_contract(account(3), |method, mut args| {
+            if *method == ByteString::from_literal("get_past_total_supply") {
+                return Int256::new(1_000).into_any();
+            }
+            assert_eq!(*method, ByteString::from_literal("get_past_votes"));
+            args.pop();
+            let voter: H160 = args.pop().downcast_into();
+            let votes = if voter == account(0x11) {
+                600
+            } else if voter == account(0x12) {
+                300
+            } else {
+                0
+            };
+            Int256::new(votes).into_any()
+        });
+    }
+
+    fn set_fee_call() -> ByteString {
+        calldata::encode(&ByteString::from_literal("set_fee"), &[ByteString::from_bytes(&[30])]).unwrap()
+    }
+
+    #[test]
+    fn test_proposal_lifecycle_round_trips_through_storage() {
+        let dao = deploy(account(0xD0));
+        register_governance_token();
+        let target = account(0x7A);
+        host::register_contract(target, |method, _| {
+            assert_eq!(*method, ByteString::from_literal("set_fee"));
+            Int256::one().into_any()
+        });
+
+        let proposal_id = dao.propose(
+            account(0x11),
+            ByteString::from_literal("Lower fee"),
+            ByteString::from_literal("Set the fee to 30"),
+            target,
+            set_fee_call(),
+        );
+        assert_eq!(proposal_id, Int256::one());
+
+        let proposal = dao.get_proposal_data(proposal_id).unwrap();
+        assert!(proposal.proposer == account(0x11));
+        assert!(proposal.target_contract == target);
+        assert_eq!(proposal.title, ByteString::from_literal("Lower fee"));
+        assert_eq!(proposal.description, ByteString::from_literal("Set the fee to 30"));
+        assert_eq!(proposal.call_data, set_fee_call());
+        assert_eq!(proposal.quorum_required, Int256::new(40));
+        assert!(proposal.status == ProposalStatus::Pending);
+
+        assert!(!dao.vote(account(0x11), proposal_id, VoteChoice::For.to_u8(), ByteString::empty()));
+        host::advance_time(SECONDS_PER_HOUR);
+        assert!(dao.vote(account(0x11), proposal_id, VoteChoice::For.to_u8(), ByteString::empty()));
+        assert!(dao.vote(account(0x12), proposal_id, VoteChoice::Against.to_u8(), ByteString::empty()));
+        assert!(!dao.vote(account(0x12), proposal_id, VoteChoice::For.to_u8(), ByteString::empty()));
+
+        let proposal = dao.get_proposal_data(proposal_id).unwrap();
+        assert_eq!(proposal.for_votes, Int256::new(600));
+        assert_eq!(proposal.against_votes, Int256::new(300));
+        assert!(proposal.status == ProposalStatus::Active);
+
+        assert!(!dao.queue_proposal(proposal_id));
+        host::advance_time(SECONDS_PER_DAY + 1);
+        assert!(dao.queue_proposal(proposal_id));
+        assert!(dao.get_proposal_data(proposal_id).unwrap().status == ProposalStatus::Queued);
+
+        assert!(!dao.execute_proposal(proposal_id));
+        host::advance_time(SECONDS_PER_DAY);
+        assert!(dao.execute_proposal(proposal_id));
+        assert!(dao.get_proposal_data(proposal_id).unwrap().status == ProposalStatus::Executed);
+        assert!(host::contract_calls().iter().any(|call| call.contract == target));
+
+        // An executed proposal stays executed
+        assert!(!dao.execute_proposal(proposal_id));
+    }
+
+    #[test]
+    fn test_parent_proposal_enqueues_in_child_timelock() {
+        let (parent_hash, child_hash, parent, child) = deploy_pair();
+        let target = account(0x7A);
+        host::register_contract(target, |method, _| {
+            assert_eq!(*method, ByteString::from_literal("set_fee"));
+            Int256::one().into_any()
+        });
+
+        host::set_executing_script_hash(child_hash);
+        assert!(child.set_parent_dao(parent_hash, true));
+
+        // The parent executes its passed bridge proposal
+        host::set_executing_script_hash(parent_hash);
+        assert!(parent.relay_bridge_action(Int256::new(5), child_hash, target, set_fee_call()));
+
+        host::set_executing_script_hash(child_hash);
+        assert_eq!(child.get_bridged_action_count(), Int256::one());
+        let action = child.get_bridged_action_data(Int256::one()).unwrap();
+        assert!(action.parent == parent_hash);
+        assert_eq!(action.source_proposal_id, Int256::new(5));
+        assert!(action.status == ProposalStatus::Queued);
+
+        // The child's own timelock still applies
+        assert!(!child.execute_bridged_action(Int256::one()));
+        host::advance_time(SECONDS_PER_DAY);
+        assert!(child.execute_bridged_action(Int256::one()));
+        assert!(child.get_bridged_action_data(Int256::one()).unwrap().status == ProposalStatus::Executed);
+        assert!(host::contract_calls().iter().any(|call| call.contract == target));
+
+        // The parent's own storage never saw the action
+        host::set_executing_script_hash(parent_hash);
+        assert_eq!(parent.get_bridged_action_count(), Int256::zero());
+    }
+
+    #[test]
+    fn test_child_rejects_unregistered_parent() {
+        let (parent_hash, child_hash, parent, child) = deploy_pair();
+
+        host::set_executing_script_hash(parent_hash);
+        assert!(!parent.relay_bridge_action(Int256::one(), child_hash, account(0x7A), set_fee_call()));
+
+        host::set_executing_script_hash(child_hash);
+        assert_eq!(child.get_bridged_action_count(), Int256::zero());
+    }
+
+    #[test]
+    fn test_voter_rewards_respect_cap_and_minimum_power() {
+        let (dao_hash, token) = (account(0xD0), account(0x70));
+        let dao = deploy(dao_hash);
+
+        // Only an executed proposal can configure the pool
+        assert!(!dao.set_voter_rewards(token, Int256::new(10), 2, Int256::new(100)));
+        host::set_calling_script_hash(dao_hash);
+        assert!(dao.set_voter_rewards(token, Int256::new(10), 2, Int256::new(100)));
+        dao.set_treasury_balance(token, Int256::new(25));
+
+        let proposal_id = Int256::one();
+        dao.accrue_voter_reward(proposal_id, account(0x11), Int256::new(99));
+        dao.accrue_voter_reward(proposal_id, account(0x12), Int256::new(100));
+        dao.accrue_voter_reward(proposal_id, account(0x13), Int256::new(500));
+        dao.accrue_voter_reward(proposal_id, account(0x14), Int256::new(500));
+
+        assert_eq!(dao.get_claimable_voter_reward(account(0x11), token), Int256::zero());
+        assert_eq!(dao.get_claimable_voter_reward(account(0x12), token), Int256::new(10));
+        assert_eq!(dao.get_claimable_voter_reward(account(0x13), token), Int256::new(10));
+        assert_eq!(dao.get_claimable_voter_reward(account(0x14), token), Int256::zero());
+        assert_eq!(dao.get_rewarded_vote_count(proposal_id), 2);
+        assert_eq!(dao.get_treasury_balance(token), Int256::new(5));
+
+        // The treasury cannot cover another reward
+        dao.accrue_voter_reward(Int256::new(2), account(0x12), Int256::new(100));
+        assert_eq!(dao.get_claimable_voter_reward(account(0x12), token), Int256::new(10));
+        assert_eq!(dao.get_rewarded_vote_count(Int256::new(2)), 0);
+    }
+
+    #[test]
+    fn test_parameter_change_is_validated_and_applied() {
+        deploy(account(0xD0));
+        register_governance_token();
+        let mut scenario = Scenario::new()
+            .actor("proposer", account(0x11))
+            .actor("opponent", account(0x12))
+            .action("proposes", |actor, args| {
+                Governance::init().propose_parameter_change(
+                    actor,
+                    ByteString::from_literal("Raise quorum"),
+                    ByteString::from_literal("Quorum to 25%"),
+                    args.bytes(0),
+                    args.int(1),
+                );
+            })
+            .action("supports", |actor, args| {
+                Governance::init().vote(actor, args.int(0), VoteChoice::For.to_u8(), ByteString::empty());
+            })
+            .action("opposes", |actor, args| {
+                Governance::init().vote(actor, args.int(0), VoteChoice::Against.to_u8(), ByteString::empty());
+            })
+            .action("queues", |_, args| {
+                Governance::init().queue_proposal(args.int(0));
+            })
+            .action("executes", |_, args| {
+                Governance::init().execute_proposal(args.int(0));
+            })
+            .query("quorum", |_| Int256::new(Governance::init().get_quorum_percentage() as i64).into());
+
+        scenario.run(r#"
+            proposer proposes "quorum_percentage" 20_000
+            expect log "Invalid parameter change"
+            expect no event ProposalCreated
+            proposer proposes "admin" 1
+            expect log "Invalid parameter change"
+
+            proposer proposes "quorum_percentage" 2_500
+            expect event ProposalCreated(1, proposer, "Raise quorum", _, _, _)
+            expect event ParameterChangeProposed(1, "quorum_percentage", 2_500)
+
+            advance 1 hour
+            proposer supports 1
+            expect event VoteCast(1, proposer, 1, 600, _)
+            opponent opposes 1
+            expect event VoteCast(1, opponent, 0, 300, _)
+
+            advance 86401 seconds
+            proposer queues 1
+            expect event ProposalQueued(1, _)
+            proposer executes 1
+            expect log "Execution time not reached"
+            expect quorum 400
+
+            advance 1 day
+            proposer executes 1
+            expect event ParameterChanged("quorum_percentage", 2_500)
+            expect event ProposalExecuted(1, _)
+            expect quorum 2_500
+        "#);
+    }
+
+    #[test]
+    fn test_upgrade_requires_approved_nef() {
+        let dao = deploy(account(0xD0));
+        let nef = ByteString::from_literal("new code");
+        let manifest = ByteString::from_literal("{}");
+        host::set_witnesses(&[account(1)]);
+
+        assert!(!dao.upgrade(nef.clone(), manifest.clone()));
+
+        let approval = ProposalAction::Upgrade {
+            nef_hash: sha256(nef.clone()).into_byte_string(),
+            manifest_hash: sha256(manifest.clone()).into_byte_string(),
+        };
+        assert!(dao.execute_proposal_action(Int256::one(), approval));
+        assert!(!dao.upgrade(ByteString::from_literal("other code"), manifest.clone()));
+        assert!(!dao.upgrade(nef.clone(), ByteString::from_literal("{\"permissions\":[]}")));
+        assert!(dao.upgrade(nef.clone(), manifest.clone()));
+
+        // The approval is used up
+        assert!(!dao.upgrade(nef, manifest));
+    }
+
+    #[test]
+    fn test_guardian_vetoes_bridged_action() {
+        let (parent_hash, child_hash, parent, child) = deploy_pair();
+
+        host::set_executing_script_hash(child_hash);
+        assert!(child.set_parent_dao(parent_hash, true));
+        host::set_executing_script_hash(parent_hash);
+        assert!(parent.relay_bridge_action(Int256::one(), child_hash, account(0x7A), set_fee_call()));
+
+        host::set_executing_script_hash(child_hash);
+        assert!(child.veto_bridged_action(Int256::one()));
+        host::advance_time(SECONDS_PER_DAY);
+        assert!(!child.execute_bridged_action(Int256::one()));
+    }
+}
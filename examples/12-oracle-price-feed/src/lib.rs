@@ -4,9 +4,15 @@
 //! - Real-time price feeds from multiple sources
 //! - Data aggregation and validation mechanisms
 //! - Historical price tracking and analytics
-//! - Subscription-based access control
+//! - Subscription tiers with per-symbol access and query quotas
+//! - Delegated access letting a subscriber's consumer contracts read feeds on its subscription
 //! - Emergency circuit breakers for data quality
 //! - Multi-oracle consensus for reliability
+//! - Push-feed mode with stake-weighted aggregation and reporter slashing; reporters bond
+//!   NEP-17 tokens through `onNEP17Payment`, and rounds close after a deadline or a quorum
+//! - Batch requests updating several symbols from one oracle response
+//! - Price attestations with per-symbol round ids for cross-chain consumers
+//! - Latest prices packed into one fixed-width storage value per update
 //!
 //! This contract showcases how to integrate external data sources
 //! into EpicChain smart contracts using the Oracle service.
@@ -28,6 +34,80 @@ pub struct PriceData {
     pub confidence: u32, // Confidence level in basis points
 }
 
+/// Size of a packed price record
+const PACKED_PRICE_SIZE: usize = 32;
+
+/// Bit widths of the fields sharing the upper 128 bits of a packed price record
+const TIMESTAMP_BITS: u32 = 40;
+const CONFIDENCE_BITS: u32 = 14;
+const ROUND_SHIFT: u32 = 64;
+
+/// Price update packed into one 256-bit little-endian word:
+///
+/// | bits    | field                                 |
+/// |---------|---------------------------------------|
+/// | 0-127   | price (two's complement)              |
+/// | 128-167 | timestamp (seconds)                   |
+/// | 168-181 | confidence (basis points)             |
+/// | 182-191 | reserved, zero                        |
+/// | 192-255 | round id                              |
+#[derive(Clone, Copy)]
+pub struct PackedPrice {
+    pub price: Int256,
+    pub timestamp: u64,
+    pub confidence: u32,
+    pub round_id: u64,
+}
+
+impl PackedPrice {
+    /// Encodes the record, or `None` if a field does not fit its width
+    pub fn encode(&self) -> Option<ByteString> {
+        if self.timestamp >> TIMESTAMP_BITS != 0 || self.confidence >> CONFIDENCE_BITS != 0 {
+            return None;
+        }
+
+        let price = Self::price_to_i128(self.price)?;
+        let upper = self.timestamp as u128
+            | (self.confidence as u128) << TIMESTAMP_BITS
+            | (self.round_id as u128) << ROUND_SHIFT;
+
+        let mut bytes = [0u8; PACKED_PRICE_SIZE];
+        bytes[..16].copy_from_slice(&price.to_le_bytes());
+        bytes[16..].copy_from_slice(&upper.to_le_bytes());
+        Some(ByteString::from_bytes(&bytes))
+    }
+
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != PACKED_PRICE_SIZE {
+            return None;
+        }
+
+        let mut upper_bytes = [0u8; 16];
+        upper_bytes.copy_from_slice(&bytes[16..]);
+        let upper = u128::from_le_bytes(upper_bytes);
+
+        Some(Self {
+            price: Int256::from_byte_string(ByteString::from_bytes(&bytes[..16])),
+            timestamp: (upper & ((1u128 << TIMESTAMP_BITS) - 1)) as u64,
+            confidence: ((upper >> TIMESTAMP_BITS) & ((1u128 << CONFIDENCE_BITS) - 1)) as u32,
+            round_id: (upper >> ROUND_SHIFT) as u64,
+        })
+    }
+
+    /// Sign-extends the VM encoding of `price` to 128 bits
+    fn price_to_i128(price: Int256) -> Option<i128> {
+        let encoded = price.into_byte_string().to_bytes();
+        if encoded.len() > 16 {
+            return None;
+        }
+
+        let fill = if price.is_negative() { 0xff } else { 0x00 };
+        let mut bytes = [fill; 16];
+        bytes[..encoded.len()].copy_from_slice(&encoded);
+        Some(i128::from_le_bytes(bytes))
+    }
+}
+
 /// Oracle request information
 #[derive(Clone)]
 pub struct OracleRequest {
@@ -41,6 +121,33 @@ pub struct OracleRequest {
     pub status: u8, // 0=pending, 1=completed, 2=failed
 }
 
+/// Delay before a reporter can withdraw an unbonded stake
+const UNBOND_DELAY: u64 = SECONDS_PER_WEEK;
+
+/// Time a push-feed round stays open after its first submission before anyone may finalize it
+const ROUND_DURATION: u64 = 5 * SECONDS_PER_MINUTE;
+
+/// Share of bonded reporters (basis points) whose submissions close a round early
+const ROUND_QUORUM_BPS: u64 = 6667;
+
+/// Maximum number of submissions to one push-feed round, bounding the work of `finalize_round`
+const MAX_ROUND_REPORTERS: usize = 32;
+
+/// Maximum number of symbols in one batch request
+const MAX_BATCH_SYMBOLS: usize = 16;
+
+/// Maximum number of symbols a subscription tier can be restricted to
+const MAX_TIER_SYMBOLS: usize = 32;
+
+/// Maximum number of consumer contracts one subscriber can delegate access to
+const MAX_DELEGATES: usize = 16;
+
+/// Built-in tier with unlimited queries on every symbol
+const UNLIMITED_TIER: u32 = 0;
+
+/// Layout version of the attestation payload, bumped whenever the layout changes
+const ATTESTATION_VERSION: u8 = 1;
+
 /// Oracle price feed contract
 #[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
 #[contract_version("1.0.0")]
@@ -50,14 +157,17 @@ pub struct OracleRequest {
 #[contract_meta("category", "Oracle")]
 pub struct OraclePriceFeed {
     // Price storage
-    price_prefix: ByteString,           // symbol -> latest price data
-    historical_prefix: ByteString,      // symbol + timestamp -> price data
+    packed_price_prefix: ByteString,    // symbol -> latest packed price
+    price_source_prefix: ByteString,    // symbol -> source of the latest price
+    price_prefix: ByteString,           // symbol -> legacy pipe-delimited price data
+    historical_prefix: ByteString,      // symbol + timestamp -> packed price
     price_sources_prefix: ByteString,   // symbol -> list of sources
 
     // Oracle requests
     request_prefix: ByteString,         // request_id -> request data
     request_count_key: ByteString,      // total number of requests
     pending_requests_key: ByteString,   // list of pending request IDs
+    batch_symbols_prefix: ByteString,   // request_id -> symbols of a batch request
 
     // Configuration
     owner_key: ByteString,
@@ -67,12 +177,39 @@ pub struct OraclePriceFeed {
     price_deviation_key: ByteString,    // maximum allowed price deviation
 
     // Subscriptions
-    subscribers_prefix: ByteString,     // user -> subscription data
+    subscribers_prefix: ByteString,     // user -> start | expiration | tier
     subscription_fee_key: ByteString,   // fee for price feed access
+    tier_prefix: ByteString,            // tier -> query quota | period | symbols
+    tier_symbol_prefix: ByteString,     // tier + symbol -> allowed
+    usage_prefix: ByteString,           // user -> period index | queries used
+    delegation_prefix: ByteString,      // consumer -> subscriber | symbols
+    delegates_prefix: ByteString,       // subscriber + consumer -> delegated
 
     // Emergency controls
     circuit_breaker_key: ByteString,    // emergency stop
     emergency_price_prefix: ByteString, // emergency fallback prices
+
+    // Attestations
+    attestation_prefix: ByteString,     // symbol -> legacy round id | timestamp | price record
+    attestation_digest_prefix: ByteString, // symbol -> digest of the latest attestation payload
+
+    // Push-feed reporters
+    reporter_bond_prefix: ByteString,   // reporter -> bonded amount
+    bond_token_key: ByteString,         // NEP-17 token reporters bond
+    bonded_reporters_key: ByteString,   // number of reporters with a non-zero bond
+    unbond_amount_prefix: ByteString,   // reporter -> amount pending withdrawal
+    unbond_time_prefix: ByteString,     // reporter -> unbond unlock time
+    min_reporter_bond_key: ByteString,  // minimum bond to submit prices
+    slashed_total_key: ByteString,      // total slashed bonds
+
+    // Push-feed rounds
+    round_prefix: ByteString,           // symbol -> current open round
+    round_reporters_prefix: ByteString, // symbol + round -> list of reporters
+    round_start_prefix: ByteString,     // symbol + round -> time of the first submission
+    submission_prefix: ByteString,      // symbol + round + reporter -> price
+    submission_weight_prefix: ByteString, // symbol + round + reporter -> bond weight
+    round_result_prefix: ByteString,    // symbol + round -> weighted median price
+    slashed_prefix: ByteString,         // symbol + round + reporter -> slashed flag
 }
 
 #[contract_impl]
@@ -80,12 +217,15 @@ impl OraclePriceFeed {
     /// Initialize the oracle price feed
     pub fn init() -> Self {
         Self {
+            packed_price_prefix: ByteString::from_literal("px_"),
+            price_source_prefix: ByteString::from_literal("src_"),
             price_prefix: ByteString::from_literal("price_"),
             historical_prefix: ByteString::from_literal("hist_"),
             price_sources_prefix: ByteString::from_literal("sources_"),
             request_prefix: ByteString::from_literal("req_"),
             request_count_key: ByteString::from_literal("req_count"),
             pending_requests_key: ByteString::from_literal("pending_reqs"),
+            batch_symbols_prefix: ByteString::from_literal("batch_"),
             owner_key: ByteString::from_literal("owner"),
             authorized_oracles_prefix: ByteString::from_literal("oracle_"),
             min_sources_key: ByteString::from_literal("min_sources"),
@@ -93,8 +233,29 @@ impl OraclePriceFeed {
             price_deviation_key: ByteString::from_literal("max_deviation"),
             subscribers_prefix: ByteString::from_literal("sub_"),
             subscription_fee_key: ByteString::from_literal("sub_fee"),
+            tier_prefix: ByteString::from_literal("tier_"),
+            tier_symbol_prefix: ByteString::from_literal("tier_sym_"),
+            usage_prefix: ByteString::from_literal("usage_"),
+            delegation_prefix: ByteString::from_literal("delegation_"),
+            delegates_prefix: ByteString::from_literal("delegates_"),
             circuit_breaker_key: ByteString::from_literal("circuit_breaker"),
             emergency_price_prefix: ByteString::from_literal("emergency_"),
+            attestation_prefix: ByteString::from_literal("att_rec_"),
+            attestation_digest_prefix: ByteString::from_literal("att_dig_"),
+            reporter_bond_prefix: ByteString::from_literal("rbond_"),
+            bond_token_key: ByteString::from_literal("bond_token"),
+            bonded_reporters_key: ByteString::from_literal("bonded_count"),
+            unbond_amount_prefix: ByteString::from_literal("unbond_amt_"),
+            unbond_time_prefix: ByteString::from_literal("unbond_time_"),
+            min_reporter_bond_key: ByteString::from_literal("min_rbond"),
+            slashed_total_key: ByteString::from_literal("slashed_total"),
+            round_prefix: ByteString::from_literal("round_"),
+            round_reporters_prefix: ByteString::from_literal("rreps_"),
+            round_start_prefix: ByteString::from_literal("rstart_"),
+            submission_prefix: ByteString::from_literal("subm_"),
+            submission_weight_prefix: ByteString::from_literal("subw_"),
+            round_result_prefix: ByteString::from_literal("rres_"),
+            slashed_prefix: ByteString::from_literal("slashed_"),
         }
     }
 
@@ -127,7 +288,7 @@ impl OraclePriceFeed {
             return false;
         }
 
-        if max_price_age < 60 || max_price_age > 3600 { // 1 minute to 1 hour
+        if max_price_age < SECONDS_PER_MINUTE || max_price_age > SECONDS_PER_HOUR {
             Runtime::log(ByteString::from_literal("Invalid max price age (1 min to 1 hour)"));
             return false;
         }
@@ -146,6 +307,7 @@ impl OraclePriceFeed {
 
     /// Request price data from oracle
     #[method]
+    #[only_owner]
     pub fn request_price_data(
         &self,
         symbol: ByteString,
@@ -153,11 +315,6 @@ impl OraclePriceFeed {
         filter: ByteString,
         epicpulse_for_response: Int256
     ) -> Int256 {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can request data"));
-            return Int256::minus_one();
-        }
-
         // Validate inputs
         if symbol.is_empty() || symbol.len() > 20 {
             Runtime::log(ByteString::from_literal("Invalid symbol"));
@@ -295,34 +452,195 @@ impl OraclePriceFeed {
         true
     }
 
-    /// Get latest price for a symbol
+    /// Request prices of several symbols from a single oracle response.
+    ///
+    /// `filter` must select one integer price per symbol, in the order of `symbols`
+    /// (e.g. `[6512345,301234]`); the result is handled by `oracle_batch_callback`.
+    #[method]
+    #[only_owner]
+    pub fn request_price_batch(
+        &self,
+        symbols: Array<ByteString>,
+        source_url: ByteString,
+        filter: ByteString,
+        epicpulse_for_response: Int256
+    ) -> Int256 {
+        if symbols.size() == 0 || symbols.size() > MAX_BATCH_SYMBOLS {
+            Runtime::log(ByteString::from_literal("Invalid number of symbols"));
+            return Int256::minus_one();
+        }
+
+        for i in bounded_iter(&symbols, MAX_BATCH_SYMBOLS) {
+            let symbol = symbols.get(i);
+            if symbol.is_empty() || symbol.len() > 20 {
+                Runtime::log(ByteString::from_literal("Invalid symbol"));
+                return Int256::minus_one();
+            }
+        }
+
+        if source_url.is_empty() {
+            Runtime::log(ByteString::from_literal("Invalid source URL"));
+            return Int256::minus_one();
+        }
+
+        // One response covers the whole batch, so the minimum stays 1 GAS
+        if epicpulse_for_response < Int256::new(100_000_000) {
+            Runtime::log(ByteString::from_literal("Insufficient gas for response"));
+            return Int256::minus_one();
+        }
+
+        let storage = Storage::get_context();
+        let current_time = Runtime::get_time();
+
+        let request_id = self.get_request_count().checked_add(&Int256::one());
+        let callback = ByteString::from_literal("oracle_batch_callback");
+        let user_data = request_id.into_byte_string();
+
+        if !Oracle::request(
+            source_url.clone(),
+            filter.clone(),
+            callback.clone(),
+            user_data.clone().into_any(),
+            epicpulse_for_response,
+        ) {
+            Runtime::log(ByteString::from_literal("Oracle request failed"));
+            return Int256::minus_one();
+        }
+
+        let request = OracleRequest {
+            id: request_id,
+            url: source_url.clone(),
+            filter,
+            callback,
+            user_data: user_data.clone(),
+            epicpulse_for_response,
+            timestamp: current_time,
+            status: 0, // Pending
+        };
+
+        let request_key = self.request_prefix.concat(&user_data);
+        Storage::put(storage.clone(), request_key, self.serialize_request(request));
+        Storage::put(storage.clone(), self.batch_symbols_prefix.concat(&user_data), self.serialize_symbols(&symbols));
+        Storage::put(storage, self.request_count_key.clone(), user_data);
+
+        let mut event_data = Array::new();
+        event_data.push(request_id.into_any());
+        event_data.push(symbols.into_any());
+        event_data.push(source_url.into_any());
+        Runtime::notify(ByteString::from_literal("BatchPriceDataRequested"), event_data);
+
+        request_id
+    }
+
+    /// Oracle callback for batch requests (called by Oracle service)
+    #[method]
+    pub fn oracle_batch_callback(
+        &self,
+        url: ByteString,
+        user_data: ByteString,
+        code: u32,
+        result: ByteString
+    ) -> bool {
+        if Runtime::get_calling_script_hash() != Oracle::hash() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only Oracle can call this"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let batch_key = self.batch_symbols_prefix.concat(&user_data);
+        let symbols = match Storage::get(storage.clone(), batch_key.clone()) {
+            Some(data) => self.deserialize_symbols(data),
+            None => {
+                Runtime::log(ByteString::from_literal("Unknown batch request"));
+                return false;
+            }
+        };
+
+        // The batch is answered once, whatever the outcome
+        Storage::delete(storage, batch_key);
+
+        if self.is_circuit_breaker_active() {
+            Runtime::log(ByteString::from_literal("Circuit breaker active, ignoring oracle data"));
+            return false;
+        }
+
+        if code != 0 {
+            Runtime::log(ByteString::from_literal("Oracle request failed"));
+            return true;
+        }
+
+        let prices = self.parse_price_list(result);
+        if prices.size() != symbols.size() {
+            Runtime::log(ByteString::from_literal("Oracle result does not match batch symbols"));
+            return false;
+        }
+
+        let current_time = Runtime::get_time();
+        let mut updated = 0i64;
+
+        for i in bounded_iter(&symbols, MAX_BATCH_SYMBOLS) {
+            let symbol = symbols.get(i);
+            let price = prices.get(i);
+
+            let price_data = PriceData {
+                symbol: symbol.clone(),
+                price,
+                timestamp: current_time,
+                source: url.clone(),
+                confidence: 9500, // 95% confidence
+            };
+
+            if !self.validate_price_data(&price_data) {
+                Runtime::log(ByteString::from_literal("Price data failed validation"));
+                continue;
+            }
+
+            self.store_price_data(price_data);
+            updated += 1;
+
+            let mut event_data = Array::new();
+            event_data.push(symbol.into_any());
+            event_data.push(price.into_any());
+            event_data.push(Int256::from_u64(current_time).into_any());
+            Runtime::notify(ByteString::from_literal("PriceDataReceived"), event_data);
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(Int256::from_byte_string(user_data).into_any());
+        event_data.push(Int256::new(updated).into_any());
+        Runtime::notify(ByteString::from_literal("BatchPriceDataReceived"), event_data);
+
+        true
+    }
+
+    /// Get latest price for a symbol.
+    ///
+    /// Not `#[safe]`: every query by a subscriber counts against the quota of its tier.
     #[method]
-    #[safe]
     pub fn get_price(&self, symbol: ByteString) -> Map<ByteString, Any> {
         let mut result = Map::new();
 
-        // Check subscription (simplified - in production, implement proper access control)
         let caller = Runtime::get_calling_script_hash();
-        if !self.is_subscribed(caller) && !self.is_owner() {
-            result.put(ByteString::from_literal("error"), ByteString::from_literal("Subscription required").into_any());
-            return result;
+        if !self.is_owner() {
+            let checked = self.resolve_subscriber(caller, &symbol)
+                .and_then(|subscriber| self.consume_query(subscriber, &symbol));
+            if let Err(reason) = checked {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal(reason).into_any());
+                return result;
+            }
         }
 
-        let storage = Storage::get_context();
-        let price_key = self.price_prefix.concat(&symbol);
-
-        match Storage::get(storage, price_key) {
-            Some(price_data) => {
-                let price_info = self.deserialize_price_data(price_data);
+        match self.get_latest_price(&symbol) {
+            Some((price_info, source)) => {
                 let current_time = Runtime::get_time();
                 let max_age = self.get_max_price_age();
 
                 // Check if price is still valid
                 if current_time - price_info.timestamp <= max_age {
-                    result.put(ByteString::from_literal("symbol"), price_info.symbol.into_any());
+                    result.put(ByteString::from_literal("symbol"), symbol.into_any());
                     result.put(ByteString::from_literal("price"), price_info.price.into_any());
                     result.put(ByteString::from_literal("timestamp"), Int256::from_u64(price_info.timestamp).into_any());
-                    result.put(ByteString::from_literal("source"), price_info.source.into_any());
+                    result.put(ByteString::from_literal("source"), source.into_any());
                     result.put(ByteString::from_literal("confidence"), Int256::from_u64(price_info.confidence as u64).into_any());
                     result.put(ByteString::from_literal("age"), Int256::from_u64(current_time - price_info.timestamp).into_any());
                 } else {
@@ -337,9 +655,77 @@ impl OraclePriceFeed {
         result
     }
 
-    /// Subscribe to price feed
+    /// Latest price of a symbol with an attestation payload for other chains and contracts.
+    ///
+    /// The payload is `version (u8) | network (u32 LE) | contract hash (20) | symbol length (u8) |
+    /// symbol | round id (u64 LE) | timestamp (u64 LE) | price length (u8) | price`, with the
+    /// price in VM integer encoding. `digest` is its SHA-256 and is also kept in contract
+    /// storage, so a verifier holding a state proof of this contract can check provenance.
+    /// Round ids increase by one per update of the symbol; a gap means missed updates.
+    #[method]
+    #[safe]
+    pub fn get_price_attestation(&self, symbol: ByteString) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+
+        match self.get_attestation_record(&symbol) {
+            Some((round_id, timestamp, price)) => {
+                let payload = self.attestation_payload(&symbol, round_id, timestamp, price);
+                result.put(ByteString::from_literal("symbol"), symbol.into_any());
+                result.put(ByteString::from_literal("price"), price.into_any());
+                result.put(ByteString::from_literal("timestamp"), Int256::from_u64(timestamp).into_any());
+                result.put(ByteString::from_literal("round_id"), Int256::from_u64(round_id).into_any());
+                result.put(ByteString::from_literal("digest"), sha256(payload.clone()).into_any());
+                result.put(ByteString::from_literal("payload"), payload.into_any());
+            },
+            None => {
+                result.put(ByteString::from_literal("error"), ByteString::from_literal("Price not found").into_any());
+            }
+        }
+
+        result
+    }
+
+    /// Round id of the latest update of a symbol, 0 if it was never updated
+    #[method]
+    #[safe]
+    pub fn get_latest_round_id(&self, symbol: ByteString) -> Int256 {
+        match self.get_attestation_record(&symbol) {
+            Some((round_id, _, _)) => Int256::from_u64(round_id),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Move the latest price of a symbol from the legacy pipe-delimited and attestation
+    /// records into a packed record, deleting the legacy keys. Anyone may migrate a symbol;
+    /// until then reads fall back to the legacy records, and the next update migrates it too.
     #[method]
-    pub fn subscribe(&self, subscriber: H160, duration: u64) -> bool {
+    pub fn migrate_price_record(&self, symbol: ByteString) -> bool {
+        if self.get_packed_price(&symbol).is_some() {
+            Runtime::log(ByteString::from_literal("Price record already packed"));
+            return false;
+        }
+
+        let (packed, source) = match self.get_legacy_price(&symbol) {
+            Some(legacy) => legacy,
+            None => {
+                Runtime::log(ByteString::from_literal("No legacy price record"));
+                return false;
+            }
+        };
+
+        self.put_packed_price(&symbol, packed, source);
+
+        let mut event_data = Array::new();
+        event_data.push(symbol.into_any());
+        event_data.push(Int256::from_u64(packed.round_id).into_any());
+        Runtime::notify(ByteString::from_literal("PriceRecordMigrated"), event_data);
+
+        true
+    }
+
+    /// Subscribe to price feed on a tier; replaces any existing subscription and resets its quota
+    #[method]
+    pub fn subscribe(&self, subscriber: H160, tier: u32, duration: u64) -> bool {
         // Verify authorization
         if !Runtime::check_witness(subscriber) {
             Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
@@ -347,142 +733,905 @@ impl OraclePriceFeed {
         }
 
         // Validate duration
-        if duration < 86400 || duration > 31536000 { // 1 day to 1 year
+        if duration < SECONDS_PER_DAY || duration > SECONDS_PER_YEAR {
             Runtime::log(ByteString::from_literal("Invalid subscription duration"));
             return false;
         }
 
+        if tier != UNLIMITED_TIER && self.get_tier(tier).is_none() {
+            Runtime::log(ByteString::from_literal("Unknown subscription tier"));
+            return false;
+        }
+
         let storage = Storage::get_context();
         let current_time = Runtime::get_time();
-        let expiration = current_time + duration;
+        let expiration = deadline(current_time, duration);
 
         // Store subscription
         let sub_key = self.subscribers_prefix.concat(&subscriber.into_byte_string());
-        let storage_clone = storage.clone(); Storage::put(storage_clone, sub_key, ByteString::from_bytes(&expiration.to_le_bytes()));
+        let sub_data = ByteString::from_bytes(&current_time.to_le_bytes())
+            .concat(&ByteString::from_bytes(&expiration.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&tier.to_le_bytes()));
+        Storage::put(storage.clone(), sub_key, sub_data);
+        Storage::delete(storage, self.usage_prefix.concat(&subscriber.into_byte_string()));
 
         let mut event_data = Array::new();
         event_data.push(subscriber.into_any());
         event_data.push(Int256::new(duration as i64).into_any());
         event_data.push(Int256::new(expiration as i64).into_any());
+        event_data.push(Int256::new(tier as i64).into_any());
         Runtime::notify(ByteString::from_literal("Subscribed"), event_data);
 
         true
     }
 
-    /// Set emergency price (owner only)
+    /// Define or replace a subscription tier (owner only).
+    ///
+    /// `query_quota` queries are allowed per `period` seconds (0 for unlimited); an empty
+    /// `symbols` list grants access to every symbol.
     #[method]
-    pub fn set_emergency_price(&self, symbol: ByteString, price: Int256) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can set emergency prices"));
+    #[only_owner]
+    pub fn set_tier(&self, tier: u32, query_quota: u32, period: u64, symbols: Array<ByteString>) -> bool {
+        if tier == UNLIMITED_TIER {
+            Runtime::log(ByteString::from_literal("Tier 0 is reserved"));
             return false;
         }
 
-        if price <= Int256::zero() {
-            Runtime::log(ByteString::from_literal("Invalid emergency price"));
+        if query_quota > 0 && (period < SECONDS_PER_MINUTE || period > SECONDS_PER_YEAR) {
+            Runtime::log(ByteString::from_literal("Invalid quota period (1 minute to 1 year)"));
+            return false;
+        }
+
+        if symbols.size() > MAX_TIER_SYMBOLS {
+            Runtime::log(ByteString::from_literal("Too many tier symbols"));
             return false;
         }
 
+        for i in bounded_iter(&symbols, MAX_TIER_SYMBOLS) {
+            let symbol = symbols.get(i);
+            if symbol.is_empty() || symbol.len() > 20 {
+                Runtime::log(ByteString::from_literal("Invalid symbol"));
+                return false;
+            }
+        }
+
         let storage = Storage::get_context();
-        let emergency_key = self.emergency_price_prefix.concat(&symbol);
-        let storage_clone = storage.clone(); Storage::put(storage_clone, emergency_key, price.into_byte_string());
+
+        // Drop the symbol grants of the previous definition
+        if let Some((_, _, old_symbols)) = self.get_tier(tier) {
+            for i in bounded_iter(&old_symbols, MAX_TIER_SYMBOLS) {
+                Storage::delete(storage.clone(), self.get_tier_symbol_key(tier, old_symbols.get(i)));
+            }
+        }
+
+        for i in bounded_iter(&symbols, MAX_TIER_SYMBOLS) {
+            Storage::put(storage.clone(), self.get_tier_symbol_key(tier, symbols.get(i)), ByteString::from_literal("true"));
+        }
+
+        let tier_data = ByteString::from_bytes(&query_quota.to_le_bytes())
+            .concat(&ByteString::from_bytes(&period.to_le_bytes()))
+            .concat(&self.serialize_symbols(&symbols));
+        Storage::put(storage, self.tier_prefix.concat(&ByteString::from_bytes(&tier.to_le_bytes())), tier_data);
 
         let mut event_data = Array::new();
-        event_data.push(symbol.into_any());
-        event_data.push(price.into_any());
-        Runtime::notify(ByteString::from_literal("EmergencyPriceSet"), event_data);
+        event_data.push(Int256::new(tier as i64).into_any());
+        event_data.push(Int256::new(query_quota as i64).into_any());
+        event_data.push(Int256::new(period as i64).into_any());
+        event_data.push(Int256::new(symbols.size() as i64).into_any());
+        Runtime::notify(ByteString::from_literal("TierUpdated"), event_data);
 
         true
     }
 
-    /// Activate circuit breaker (owner only)
+    /// Let a consumer contract read feeds on the subscriber's subscription.
+    ///
+    /// Its queries count against the subscriber's quota and stay limited to the tier's
+    /// symbols; a non-empty `symbols` list restricts the consumer further. Delegating again
+    /// replaces the symbol list. A consumer can act for only one subscriber at a time.
     #[method]
-    pub fn activate_circuit_breaker(&self) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can activate circuit breaker"));
+    pub fn add_delegate(&self, subscriber: H160, consumer: H160, symbols: Array<ByteString>) -> bool {
+        if !Runtime::check_witness(subscriber) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        if consumer == H160::zero() || consumer == subscriber {
+            Runtime::log(ByteString::from_literal("Invalid consumer"));
             return false;
         }
 
+        if self.get_subscription_record(subscriber).is_none() {
+            Runtime::log(ByteString::from_literal("Subscription required"));
+            return false;
+        }
+
+        if symbols.size() > MAX_TIER_SYMBOLS {
+            Runtime::log(ByteString::from_literal("Too many delegated symbols"));
+            return false;
+        }
+
+        for i in bounded_iter(&symbols, MAX_TIER_SYMBOLS) {
+            let symbol = symbols.get(i);
+            if symbol.is_empty() || symbol.len() > 20 {
+                Runtime::log(ByteString::from_literal("Invalid symbol"));
+                return false;
+            }
+        }
+
+        match self.get_delegation(consumer) {
+            Some((owner, _)) if owner != subscriber => {
+                Runtime::log(ByteString::from_literal("Consumer is delegated by another subscriber"));
+                return false;
+            }
+            Some(_) => {}
+            None => {
+                if self.get_delegates(subscriber).size() >= MAX_DELEGATES {
+                    Runtime::log(ByteString::from_literal("Too many delegates"));
+                    return false;
+                }
+            }
+        }
+
         let storage = Storage::get_context();
-        let storage_clone = storage.clone(); Storage::put(storage_clone, self.circuit_breaker_key.clone(), ByteString::from_literal("true"));
+        let delegation_data = subscriber.into_byte_string().concat(&self.serialize_symbols(&symbols));
+        Storage::put(storage.clone(), self.delegation_prefix.concat(&consumer.into_byte_string()), delegation_data);
+        Storage::put(storage, self.get_delegates_key(subscriber, consumer), ByteString::from_literal("true"));
+
+        let mut event_data = Array::new();
+        event_data.push(subscriber.into_any());
+        event_data.push(consumer.into_any());
+        event_data.push(symbols.into_any());
+        Runtime::notify(ByteString::from_literal("DelegateAdded"), event_data);
 
-        Runtime::notify(ByteString::from_literal("CircuitBreakerActivated"), Array::new());
         true
     }
 
-    /// Deactivate circuit breaker (owner only)
+    /// Revoke a consumer contract's access to the subscriber's subscription
     #[method]
-    pub fn deactivate_circuit_breaker(&self) -> bool {
-        if !self.is_owner() {
-            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can deactivate circuit breaker"));
+    pub fn remove_delegate(&self, subscriber: H160, consumer: H160) -> bool {
+        if !Runtime::check_witness(subscriber) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
             return false;
         }
 
+        match self.get_delegation(consumer) {
+            Some((owner, _)) if owner == subscriber => {}
+            _ => {
+                Runtime::log(ByteString::from_literal("Consumer is not a delegate of this subscriber"));
+                return false;
+            }
+        }
+
         let storage = Storage::get_context();
-        let storage_clone = storage.clone(); Storage::delete(storage_clone, self.circuit_breaker_key.clone());
+        Storage::delete(storage.clone(), self.delegation_prefix.concat(&consumer.into_byte_string()));
+        Storage::delete(storage, self.get_delegates_key(subscriber, consumer));
+
+        let mut event_data = Array::new();
+        event_data.push(subscriber.into_any());
+        event_data.push(consumer.into_any());
+        Runtime::notify(ByteString::from_literal("DelegateRemoved"), event_data);
 
-        Runtime::notify(ByteString::from_literal("CircuitBreakerDeactivated"), Array::new());
         true
     }
 
-    /// Check if circuit breaker is active
+    /// Get the consumer contracts a subscriber has delegated access to
     #[method]
     #[safe]
-    pub fn is_circuit_breaker_active(&self) -> bool {
-        let storage = Storage::get_context();
-        Storage::get(storage, self.circuit_breaker_key.clone()).is_some()
+    pub fn get_delegates(&self, subscriber: H160) -> Array<H160> {
+        let mut delegates = Array::new();
+        let prefix = self.delegates_prefix.concat(&subscriber.into_byte_string());
+        for consumer in Storage::find_prefix(Storage::get_context(), prefix).remove_prefix().keys() {
+            delegates.push(H160::from_byte_string(consumer));
+        }
+        delegates
     }
 
-    /// Get contract owner
+    /// Get the subscriber a consumer contract reads feeds for and the symbols it may read
+    /// (an empty list means every symbol of the subscriber's tier)
     #[method]
     #[safe]
-    pub fn get_owner(&self) -> H160 {
-        let storage = Storage::get_context();
-        match Storage::get(storage, self.owner_key.clone()) {
-            Some(owner_bytes) => H160::from_byte_string(owner_bytes),
-            None => H160::zero(),
+    pub fn get_delegated_access(&self, consumer: H160) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        if let Some((subscriber, symbols)) = self.get_delegation(consumer) {
+            result.put(ByteString::from_literal("subscriber"), subscriber.into_any());
+            result.put(ByteString::from_literal("symbols"), symbols.into_any());
         }
+        result
     }
 
-    /// Get request count
+    /// Get the subscription and remaining entitlements of a subscriber
     #[method]
     #[safe]
-    pub fn get_request_count(&self) -> Int256 {
-        let storage = Storage::get_context();
-        match Storage::get(storage, self.request_count_key.clone()) {
-            Some(count_bytes) => Int256::from_byte_string(count_bytes),
-            None => Int256::zero(),
-        }
-    }
+    pub fn get_subscription(&self, subscriber: H160) -> Map<ByteString, Any> {
+        let mut result = Map::new();
 
-    // Helper functions
+        let (start, expiration, tier) = match self.get_subscription_record(subscriber) {
+            Some(record) => record,
+            None => {
+                result.put(ByteString::from_literal("active"), Int256::zero().into_any());
+                return result;
+            }
+        };
 
-    fn is_owner(&self) -> bool {
-        let owner = self.get_owner();
-        if owner == H160::zero() {
-            return false;
+        let now = Runtime::get_time();
+        let (query_quota, period, symbols) = self.get_tier(tier).unwrap_or((0, 0, Array::new()));
+        let used = self.get_queries_used(subscriber, start, period, now);
+
+        let active = if now < expiration { Int256::one() } else { Int256::zero() };
+        result.put(ByteString::from_literal("active"), active.into_any());
+        result.put(ByteString::from_literal("tier"), Int256::new(tier as i64).into_any());
+        result.put(ByteString::from_literal("start"), Int256::from_u64(start).into_any());
+        result.put(ByteString::from_literal("expiration"), Int256::from_u64(expiration).into_any());
+        result.put(ByteString::from_literal("query_quota"), Int256::new(query_quota as i64).into_any());
+        result.put(ByteString::from_literal("period"), Int256::from_u64(period).into_any());
+        result.put(ByteString::from_literal("queries_used"), Int256::new(used as i64).into_any());
+        if query_quota > 0 {
+            let remaining = query_quota.saturating_sub(used);
+            result.put(ByteString::from_literal("queries_remaining"), Int256::new(remaining as i64).into_any());
+            let period_end = period_index_at(now, start, period)
+                .map(|index| period_start(start, period, index + 1))
+                .unwrap_or(start);
+            result.put(ByteString::from_literal("quota_resets_at"), Int256::from_u64(period_end).into_any());
         }
-        Runtime::check_witness(owner)
+        // An empty list means every symbol is available
+        result.put(ByteString::from_literal("symbols"), symbols.into_any());
+
+        result
+    }
+
+    /// Set emergency price (owner only)
+    #[method]
+    #[only_owner]
+    pub fn set_emergency_price(&self, symbol: ByteString, price: Int256) -> bool {
+        if price <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid emergency price"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let emergency_key = self.emergency_price_prefix.concat(&symbol);
+        let storage_clone = storage.clone(); Storage::put(storage_clone, emergency_key, price.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(symbol.into_any());
+        event_data.push(price.into_any());
+        Runtime::notify(ByteString::from_literal("EmergencyPriceSet"), event_data);
+
+        true
+    }
+
+    /// Activate circuit breaker (owner only)
+    #[method]
+    #[only_owner]
+    pub fn activate_circuit_breaker(&self) -> bool {
+        let storage = Storage::get_context();
+        let storage_clone = storage.clone(); Storage::put(storage_clone, self.circuit_breaker_key.clone(), ByteString::from_literal("true"));
+
+        Runtime::notify(ByteString::from_literal("CircuitBreakerActivated"), Array::new());
+        true
+    }
+
+    /// Deactivate circuit breaker (owner only)
+    #[method]
+    #[only_owner]
+    pub fn deactivate_circuit_breaker(&self) -> bool {
+        let storage = Storage::get_context();
+        let storage_clone = storage.clone(); Storage::delete(storage_clone, self.circuit_breaker_key.clone());
+
+        Runtime::notify(ByteString::from_literal("CircuitBreakerDeactivated"), Array::new());
+        true
+    }
+
+    /// Check if circuit breaker is active
+    #[method]
+    #[safe]
+    pub fn is_circuit_breaker_active(&self) -> bool {
+        let storage = Storage::get_context();
+        Storage::get(storage, self.circuit_breaker_key.clone()).is_some()
+    }
+
+    /// Get contract owner
+    #[method]
+    #[safe]
+    pub fn get_owner(&self) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.owner_key.clone()) {
+            Some(owner_bytes) => H160::from_byte_string(owner_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    /// Get request count
+    #[method]
+    #[safe]
+    pub fn get_request_count(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.request_count_key.clone()) {
+            Some(count_bytes) => Int256::from_byte_string(count_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Set the minimum bond required to submit push-feed prices (owner only).
+    ///
+    /// The push feed stays closed until a non-zero minimum is set.
+    #[method]
+    #[only_owner]
+    pub fn set_min_reporter_bond(&self, amount: Int256) -> bool {
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid bond amount"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        put_changed(storage, self.min_reporter_bond_key.clone(), "MinReporterBondChanged", amount);
+        true
+    }
+
+    /// Set the NEP-17 token reporters bond (owner only, once)
+    #[method]
+    #[only_owner]
+    pub fn set_bond_token(&self, token: H160) -> bool {
+        if token == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid bond token"));
+            return false;
+        }
+
+        // Existing bonds are held in the current token
+        let storage = Storage::get_context();
+        if Storage::get(storage.clone(), self.bond_token_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Bond token already set"));
+            return false;
+        }
+
+        Storage::put(storage, self.bond_token_key.clone(), token.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(token.into_any());
+        Runtime::notify(ByteString::from_literal("BondTokenSet"), event_data);
+        true
+    }
+
+    /// NEP-17 receiver callback: bond token payments bond the sender as a push-feed reporter
+    #[method]
+    #[wasm_export(name = "onNEP17Payment")]
+    pub fn bond_reporter(&self, reporter: H160, amount: Int256, _data: Any) -> bool {
+        let bond_token = self.get_bond_token();
+        if bond_token == H160::zero() || Runtime::get_calling_script_hash() != bond_token {
+            Runtime::log(ByteString::from_literal("Only the bond token can be bonded"));
+            return false;
+        }
+
+        if reporter == H160::zero() || amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid bond amount"));
+            return false;
+        }
+
+        let new_bond = self.get_reporter_bond(reporter).checked_add(&amount);
+        self.set_reporter_bond(reporter, new_bond);
+
+        let mut event_data = Array::new();
+        event_data.push(reporter.into_any());
+        event_data.push(amount.into_any());
+        event_data.push(new_bond.into_any());
+        Runtime::notify(ByteString::from_literal("ReporterBonded"), event_data);
+        true
+    }
+
+    /// Start unbonding part of a reporter bond; it stays slashable until withdrawn
+    #[method]
+    pub fn request_unbond(&self, reporter: H160, amount: Int256) -> bool {
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid unbond amount"));
+            return false;
+        }
+
+        if !Runtime::check_witness(reporter) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        let bond = self.get_reporter_bond(reporter);
+        let pending = self.get_pending_unbond(reporter);
+        if bond.checked_sub(&pending) < amount {
+            Runtime::log(ByteString::from_literal("Insufficient bond"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let unlock_time = Runtime::get_time() + UNBOND_DELAY;
+        let new_pending = pending.checked_add(&amount);
+        let amount_key = self.unbond_amount_prefix.concat(&reporter.into_byte_string());
+        let time_key = self.unbond_time_prefix.concat(&reporter.into_byte_string());
+        Storage::put(storage.clone(), amount_key, new_pending.into_byte_string());
+        Storage::put(storage, time_key, ByteString::from_bytes(&unlock_time.to_le_bytes()));
+
+        let mut event_data = Array::new();
+        event_data.push(reporter.into_any());
+        event_data.push(new_pending.into_any());
+        event_data.push(Int256::from_u64(unlock_time).into_any());
+        Runtime::notify(ByteString::from_literal("ReporterUnbondRequested"), event_data);
+        true
+    }
+
+    /// Withdraw an unbonded stake once the unbonding delay has elapsed
+    #[method]
+    pub fn withdraw_bond(&self, reporter: H160) -> Int256 {
+        if !Runtime::check_witness(reporter) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return Int256::zero();
+        }
+
+        let pending = self.get_pending_unbond(reporter);
+        if pending <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Nothing to withdraw"));
+            return Int256::zero();
+        }
+
+        if Runtime::get_time() < self.get_unbond_unlock_time(reporter) {
+            Runtime::log(ByteString::from_literal("Unbonding period not elapsed"));
+            return Int256::zero();
+        }
+
+        // Slashing may have reduced the bond below the pending amount
+        let bond = self.get_reporter_bond(reporter);
+        let amount = if pending > bond { bond } else { pending };
+        self.set_reporter_bond(reporter, bond.checked_sub(&amount));
+
+        let storage = Storage::get_context();
+        Storage::delete(storage.clone(), self.unbond_amount_prefix.concat(&reporter.into_byte_string()));
+        Storage::delete(storage, self.unbond_time_prefix.concat(&reporter.into_byte_string()));
+
+        assert_with_code(
+            self.transfer_bond_token(reporter, amount),
+            ErrorCode::CallFailed,
+            "Bond transfer failed",
+        );
+
+        let mut event_data = Array::new();
+        event_data.push(reporter.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("ReporterBondWithdrawn"), event_data);
+        amount
+    }
+
+    /// Submit a price for the open round of a symbol (bonded reporters only).
+    ///
+    /// A round takes at most MAX_ROUND_REPORTERS submissions; later ones wait for the next round.
+    #[method]
+    pub fn submit_price(&self, reporter: H160, symbol: ByteString, price: Int256) -> bool {
+        if !Runtime::check_witness(reporter) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        if self.is_circuit_breaker_active() {
+            Runtime::log(ByteString::from_literal("Circuit breaker active"));
+            return false;
+        }
+
+        if symbol.is_empty() || symbol.len() > 20 {
+            Runtime::log(ByteString::from_literal("Invalid symbol"));
+            return false;
+        }
+
+        if price <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid price"));
+            return false;
+        }
+
+        // Without a minimum bond any account could report, and flood rounds, for free
+        let min_bond = self.get_min_reporter_bond();
+        if min_bond <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Minimum reporter bond not set"));
+            return false;
+        }
+
+        // Only the portion of the bond that is not unbonding carries weight
+        let weight = self.get_reporter_bond(reporter).checked_sub(&self.get_pending_unbond(reporter));
+        if weight < min_bond {
+            Runtime::log(ByteString::from_literal("Reporter bond below minimum"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let round = self.get_current_round(symbol.clone());
+        let submission_key = self.get_submission_key(&self.submission_prefix, symbol.clone(), round, reporter);
+        if Storage::get(storage.clone(), submission_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Already submitted for this round"));
+            return false;
+        }
+
+        let reporters_key = self.get_round_key(&self.round_reporters_prefix, symbol.clone(), round);
+        let mut reporters = match Storage::get(storage.clone(), reporters_key.clone()) {
+            Some(data) => self.deserialize_reporters(data),
+            None => Array::new(),
+        };
+        if reporters.size() >= MAX_ROUND_REPORTERS {
+            Runtime::log(ByteString::from_literal("Round is full"));
+            return false;
+        }
+
+        let weight_key = self.get_submission_key(&self.submission_weight_prefix, symbol.clone(), round, reporter);
+        Storage::put(storage.clone(), submission_key, price.into_byte_string());
+        Storage::put(storage.clone(), weight_key, weight.into_byte_string());
+
+        if reporters.size() == 0 {
+            let start_key = self.get_round_key(&self.round_start_prefix, symbol.clone(), round);
+            Storage::put(storage.clone(), start_key, ByteString::from_bytes(&Runtime::get_time().to_le_bytes()));
+        }
+        reporters.push(reporter);
+        Storage::put(storage, reporters_key, self.serialize_reporters(&reporters));
+
+        let mut event_data = Array::new();
+        event_data.push(symbol.into_any());
+        event_data.push(round.into_any());
+        event_data.push(reporter.into_any());
+        event_data.push(price.into_any());
+        event_data.push(weight.into_any());
+        Runtime::notify(ByteString::from_literal("PriceSubmitted"), event_data);
+        true
+    }
+
+    /// Close the open round of a symbol, publishing the stake-weighted median price.
+    ///
+    /// Anyone may finalize once the round has been open for `ROUND_DURATION`, or earlier once
+    /// a quorum of the bonded reporters has submitted, so no single reporter sets the price
+    /// by finalizing right after its own submission.
+    #[method]
+    pub fn finalize_round(&self, symbol: ByteString) -> Int256 {
+        if self.is_circuit_breaker_active() {
+            Runtime::log(ByteString::from_literal("Circuit breaker active"));
+            return Int256::zero();
+        }
+
+        let storage = Storage::get_context();
+        let round = self.get_current_round(symbol.clone());
+        let reporters_key = self.get_round_key(&self.round_reporters_prefix, symbol.clone(), round);
+        let reporters = match Storage::get(storage.clone(), reporters_key) {
+            Some(data) => self.deserialize_reporters(data),
+            None => Array::new(),
+        };
+
+        if (reporters.size() as u32) < self.get_min_sources() {
+            Runtime::log(ByteString::from_literal("Not enough submissions"));
+            return Int256::zero();
+        }
+
+        let start_key = self.get_round_key(&self.round_start_prefix, symbol.clone(), round);
+        let deadline = self.read_u64(storage.clone(), start_key.clone()) + ROUND_DURATION;
+        // A full round counts as a quorum even when more reporters are bonded than it can take
+        let quorum_base = self.get_bonded_reporters().min(MAX_ROUND_REPORTERS as u64);
+        let quorum_reached = reporters.size() as u64 * 10000 >= quorum_base * ROUND_QUORUM_BPS;
+        if Runtime::get_time() < deadline && !quorum_reached {
+            Runtime::log(ByteString::from_literal("Round still open"));
+            return Int256::zero();
+        }
+        Storage::delete(storage.clone(), start_key);
+
+        let mut prices = Array::new();
+        let mut weights = Array::new();
+        for i in bounded_iter(&reporters, MAX_ROUND_REPORTERS) {
+            let reporter = reporters.get(i);
+            prices.push(self.get_submission(&self.submission_prefix, symbol.clone(), round, reporter));
+            weights.push(self.get_submission(&self.submission_weight_prefix, symbol.clone(), round, reporter));
+        }
+
+        let median = self.weighted_median(&mut prices, &mut weights);
+
+        let result_key = self.get_round_key(&self.round_result_prefix, symbol.clone(), round);
+        Storage::put(storage.clone(), result_key, median.into_byte_string());
+
+        let round_key = self.round_prefix.concat(&symbol);
+        Storage::put(storage, round_key, round.checked_add(&Int256::one()).into_byte_string());
+
+        self.store_price_data(PriceData {
+            symbol: symbol.clone(),
+            price: median,
+            timestamp: Runtime::get_time(),
+            source: ByteString::from_literal("push"),
+            confidence: 10000,
+        });
+
+        let mut event_data = Array::new();
+        event_data.push(symbol.into_any());
+        event_data.push(round.into_any());
+        event_data.push(median.into_any());
+        event_data.push(Int256::new(reporters.size() as i64).into_any());
+        Runtime::notify(ByteString::from_literal("RoundFinalized"), event_data);
+
+        median
+    }
+
+    /// Slash a reporter whose submission deviated from the final median beyond the
+    /// configured bound (owner / governance only)
+    #[method]
+    #[only_owner]
+    pub fn slash_reporter(
+        &self,
+        symbol: ByteString,
+        round: Int256,
+        reporter: H160,
+        slash_bps: u32
+    ) -> Int256 {
+        if slash_bps == 0 || slash_bps > 10000 {
+            Runtime::log(ByteString::from_literal("Invalid slash rate"));
+            return Int256::zero();
+        }
+
+        let storage = Storage::get_context();
+        let result_key = self.get_round_key(&self.round_result_prefix, symbol.clone(), round);
+        let median = match Storage::get(storage.clone(), result_key) {
+            Some(data) => Int256::from_byte_string(data),
+            None => {
+                Runtime::log(ByteString::from_literal("Round not finalized"));
+                return Int256::zero();
+            }
+        };
+
+        let submission_key = self.get_submission_key(&self.submission_prefix, symbol.clone(), round, reporter);
+        let submitted = match Storage::get(storage.clone(), submission_key) {
+            Some(data) => Int256::from_byte_string(data),
+            None => {
+                Runtime::log(ByteString::from_literal("No submission found"));
+                return Int256::zero();
+            }
+        };
+
+        let slashed_key = self.get_submission_key(&self.slashed_prefix, symbol.clone(), round, reporter);
+        if Storage::get(storage.clone(), slashed_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Submission already slashed"));
+            return Int256::zero();
+        }
+
+        // Only provably bad submissions can be slashed
+        let difference = if submitted > median {
+            submitted.checked_sub(&median)
+        } else {
+            median.checked_sub(&submitted)
+        };
+        let deviation_bps = difference.checked_mul(&Int256::new(10000)).checked_div(&median);
+        if deviation_bps <= Int256::new(self.get_price_deviation() as i64) {
+            Runtime::log(ByteString::from_literal("Submission within deviation bounds"));
+            return Int256::zero();
+        }
+
+        let bond = self.get_reporter_bond(reporter);
+        let penalty = bond.checked_mul(&Int256::new(slash_bps as i64)).checked_div(&Int256::new(10000));
+        self.set_reporter_bond(reporter, bond.checked_sub(&penalty));
+        Storage::put(storage.clone(), slashed_key, ByteString::from_literal("true"));
+
+        let slashed_total = match Storage::get(storage.clone(), self.slashed_total_key.clone()) {
+            Some(data) => Int256::from_byte_string(data),
+            None => Int256::zero(),
+        };
+        Storage::put(storage, self.slashed_total_key.clone(), slashed_total.checked_add(&penalty).into_byte_string());
+
+        // Slashed bonds go to the owner's treasury
+        assert_with_code(
+            self.transfer_bond_token(self.get_owner(), penalty),
+            ErrorCode::CallFailed,
+            "Slash transfer failed",
+        );
+
+        let mut event_data = Array::new();
+        event_data.push(reporter.into_any());
+        event_data.push(symbol.into_any());
+        event_data.push(round.into_any());
+        event_data.push(submitted.into_any());
+        event_data.push(median.into_any());
+        event_data.push(penalty.into_any());
+        Runtime::notify(ByteString::from_literal("ReporterSlashed"), event_data);
+
+        penalty
+    }
+
+    /// Get the bonded amount of a reporter
+    #[method]
+    #[safe]
+    pub fn get_reporter_bond(&self, reporter: H160) -> Int256 {
+        let storage = Storage::get_context();
+        let bond_key = self.reporter_bond_prefix.concat(&reporter.into_byte_string());
+        match Storage::get(storage, bond_key) {
+            Some(bond_bytes) => Int256::from_byte_string(bond_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Get the open round for a symbol
+    #[method]
+    #[safe]
+    pub fn get_current_round(&self, symbol: ByteString) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.round_prefix.concat(&symbol)) {
+            Some(round_bytes) => Int256::from_byte_string(round_bytes),
+            None => Int256::one(),
+        }
+    }
+
+    /// Get the stake-weighted median of a finalized round (zero if not finalized)
+    #[method]
+    #[safe]
+    pub fn get_round_result(&self, symbol: ByteString, round: Int256) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.get_round_key(&self.round_result_prefix, symbol, round)) {
+            Some(result_bytes) => Int256::from_byte_string(result_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    // Helper functions
+
+    fn is_owner(&self) -> bool {
+        let owner = self.get_owner();
+        if owner == H160::zero() {
+            return false;
+        }
+        Runtime::check_witness(owner)
+    }
+
+    /// Subscription that pays for `caller` reading `symbol`: its own, or the one of the
+    /// subscriber that delegated access to it
+    fn resolve_subscriber(&self, caller: H160, symbol: &ByteString) -> Result<H160, &'static str> {
+        if self.get_subscription_record(caller).is_some() {
+            return Ok(caller);
+        }
+
+        let (subscriber, symbols) = match self.get_delegation(caller) {
+            Some(delegation) => delegation,
+            None => return Err("Subscription required"),
+        };
+
+        if symbols.size() > 0 {
+            let mut allowed = false;
+            for i in bounded_iter(&symbols, MAX_TIER_SYMBOLS) {
+                if symbols.get(i) == *symbol {
+                    allowed = true;
+                    break;
+                }
+            }
+            if !allowed {
+                return Err("Symbol not delegated to caller");
+            }
+        }
+
+        Ok(subscriber)
     }
 
-    fn is_subscribed(&self, subscriber: H160) -> bool {
+    /// Delegation of a consumer contract as (subscriber, symbols)
+    fn get_delegation(&self, consumer: H160) -> Option<(H160, Array<ByteString>)> {
         let storage = Storage::get_context();
-        let sub_key = self.subscribers_prefix.concat(&subscriber.into_byte_string());
+        let bytes = Storage::get(storage, self.delegation_prefix.concat(&consumer.into_byte_string()))?.to_bytes();
+        if bytes.len() < 20 {
+            return None;
+        }
 
-        match Storage::get(storage, sub_key) {
-            Some(expiration_bytes) => {
-                let bytes = expiration_bytes.to_bytes();
-                if bytes.len() >= 4 {
-                    // Use u32 instead of u64 to avoid I32WrapI64 WASM operation
-                    let expiration = u32::from_le_bytes([
-                        bytes[0], bytes[1], bytes[2], bytes[3]
-                    ]) as u64;
-                    let current_time = Runtime::get_time();
-                    current_time < expiration
+        let subscriber = H160::from_byte_string(ByteString::from_bytes(&bytes[..20]));
+        let symbols = self.deserialize_symbols(ByteString::from_bytes(&bytes[20..]));
+        Some((subscriber, symbols))
+    }
+
+    fn get_delegates_key(&self, subscriber: H160, consumer: H160) -> ByteString {
+        self.delegates_prefix
+            .concat(&subscriber.into_byte_string())
+            .concat(&consumer.into_byte_string())
+    }
+
+    /// Check the caller's subscription for `symbol` and count the query against its quota
+    fn consume_query(&self, subscriber: H160, symbol: &ByteString) -> Result<(), &'static str> {
+        let (start, expiration, tier) = match self.get_subscription_record(subscriber) {
+            Some(record) => record,
+            None => return Err("Subscription required"),
+        };
+
+        let now = Runtime::get_time();
+        if now >= expiration {
+            return Err("Subscription expired");
+        }
+
+        if tier == UNLIMITED_TIER {
+            return Ok(());
+        }
+
+        let (query_quota, period, symbols) = match self.get_tier(tier) {
+            Some(definition) => definition,
+            None => return Err("Subscription tier no longer exists"),
+        };
+
+        if symbols.size() > 0 {
+            let storage = Storage::get_context();
+            if Storage::get(storage, self.get_tier_symbol_key(tier, symbol.clone())).is_none() {
+                return Err("Symbol not included in subscription tier");
+            }
+        }
+
+        if query_quota == 0 {
+            return Ok(());
+        }
+
+        let used = self.get_queries_used(subscriber, start, period, now);
+        if used >= query_quota {
+            return Err("Query quota exceeded");
+        }
+
+        let index = period_index_at(now, start, period).unwrap_or(0);
+        let usage_data = ByteString::from_bytes(&index.to_le_bytes())
+            .concat(&ByteString::from_bytes(&(used + 1).to_le_bytes()));
+        let storage = Storage::get_context();
+        Storage::put(storage, self.usage_prefix.concat(&subscriber.into_byte_string()), usage_data);
+
+        Ok(())
+    }
+
+    /// Subscription of a subscriber as (start, expiration, tier)
+    fn get_subscription_record(&self, subscriber: H160) -> Option<(u64, u64, u32)> {
+        let storage = Storage::get_context();
+        let bytes = Storage::get(storage, self.subscribers_prefix.concat(&subscriber.into_byte_string()))?.to_bytes();
+        if bytes.len() < 20 {
+            return None;
+        }
+
+        let start = u64::from_le_bytes([
+            bytes[0], bytes[1], bytes[2], bytes[3],
+            bytes[4], bytes[5], bytes[6], bytes[7]
+        ]);
+        let expiration = u64::from_le_bytes([
+            bytes[8], bytes[9], bytes[10], bytes[11],
+            bytes[12], bytes[13], bytes[14], bytes[15]
+        ]);
+        let tier = u32::from_le_bytes([bytes[16], bytes[17], bytes[18], bytes[19]]);
+        Some((start, expiration, tier))
+    }
+
+    /// Tier definition as (query quota, period, symbols)
+    fn get_tier(&self, tier: u32) -> Option<(u32, u64, Array<ByteString>)> {
+        let storage = Storage::get_context();
+        let data = Storage::get(storage, self.tier_prefix.concat(&ByteString::from_bytes(&tier.to_le_bytes())))?;
+        let bytes = data.to_bytes();
+        if bytes.len() < 12 {
+            return None;
+        }
+
+        let query_quota = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let period = u64::from_le_bytes([
+            bytes[4], bytes[5], bytes[6], bytes[7],
+            bytes[8], bytes[9], bytes[10], bytes[11]
+        ]);
+        let symbols = self.deserialize_symbols(ByteString::from_bytes(&bytes[12..]));
+        Some((query_quota, period, symbols))
+    }
+
+    fn get_tier_symbol_key(&self, tier: u32, symbol: ByteString) -> ByteString {
+        self.tier_symbol_prefix
+            .concat(&ByteString::from_bytes(&tier.to_le_bytes()))
+            .concat(&symbol)
+    }
+
+    /// Queries used in the quota period containing `now`; counters of earlier periods read as zero
+    fn get_queries_used(&self, subscriber: H160, start: u64, period: u64, now: u64) -> u32 {
+        let index = match period_index_at(now, start, period) {
+            Some(index) => index,
+            None => return 0,
+        };
+
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.usage_prefix.concat(&subscriber.into_byte_string())) {
+            Some(usage_bytes) => {
+                let bytes = usage_bytes.to_bytes();
+                if bytes.len() < 12 {
+                    return 0;
+                }
+                let recorded = u64::from_le_bytes([
+                    bytes[0], bytes[1], bytes[2], bytes[3],
+                    bytes[4], bytes[5], bytes[6], bytes[7]
+                ]);
+                if recorded == index {
+                    u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]])
                 } else {
-                    false
+                    0
                 }
             },
-            None => false,
+            None => 0,
         }
     }
 
@@ -518,16 +1667,154 @@ impl OraclePriceFeed {
     fn store_price_data(&self, price_data: PriceData) {
         let storage = Storage::get_context();
 
+        // Every update gets the next round id of its symbol
+        let round_id = match self.get_latest_price(&price_data.symbol) {
+            Some((latest, _)) => latest.round_id + 1,
+            None => 1,
+        };
+        let packed = PackedPrice {
+            price: price_data.price,
+            timestamp: price_data.timestamp,
+            confidence: price_data.confidence,
+            round_id,
+        };
+
         // Store latest price
-        let price_key = self.price_prefix.concat(&price_data.symbol);
-        Storage::put(storage.clone(), price_key, self.serialize_price_data(price_data.clone()));
+        self.put_packed_price(&price_data.symbol, packed, price_data.source.clone());
 
         // Store historical price
         let hist_key = self.historical_prefix
             .concat(&price_data.symbol)
             .concat(&ByteString::from_literal("_"))
             .concat(&ByteString::from_bytes(&(price_data.timestamp as u32).to_le_bytes()));
-        let storage_clone = storage.clone(); Storage::put(storage_clone, hist_key, self.serialize_price_data(price_data));
+        if let Some(encoded) = packed.encode() {
+            Storage::put(storage.clone(), hist_key, encoded);
+        }
+
+        let digest = sha256(self.attestation_payload(&price_data.symbol, round_id, price_data.timestamp, price_data.price));
+        Storage::put(storage, self.attestation_digest_prefix.concat(&price_data.symbol), digest.into_byte_string());
+    }
+
+    /// Writes the packed latest price of `symbol`, storing the source only when it changes,
+    /// and removes any legacy records of the symbol
+    fn put_packed_price(&self, symbol: &ByteString, packed: PackedPrice, source: ByteString) {
+        let Some(encoded) = packed.encode() else {
+            abort_with_code(ErrorCode::InvalidArgument, "Price does not fit the packed record");
+            return;
+        };
+
+        let storage = Storage::get_context();
+        let packed_key = self.packed_price_prefix.concat(symbol);
+        let migrating = Storage::get(storage.clone(), packed_key.clone()).is_none();
+        Storage::put(storage.clone(), packed_key, encoded);
+
+        let source_key = self.price_source_prefix.concat(symbol);
+        if Storage::get(storage.clone(), source_key.clone()) != Some(source.clone()) {
+            Storage::put(storage.clone(), source_key, source);
+        }
+
+        if migrating {
+            Storage::delete(storage.clone(), self.price_prefix.concat(symbol));
+            Storage::delete(storage, self.attestation_prefix.concat(symbol));
+        }
+    }
+
+    fn get_packed_price(&self, symbol: &ByteString) -> Option<PackedPrice> {
+        let storage = Storage::get_context();
+        PackedPrice::decode(&Storage::get(storage, self.packed_price_prefix.concat(symbol))?.to_bytes())
+    }
+
+    /// Latest price of a symbol and its source, from the packed record or the legacy records
+    fn get_latest_price(&self, symbol: &ByteString) -> Option<(PackedPrice, ByteString)> {
+        match self.get_packed_price(symbol) {
+            Some(packed) => {
+                let storage = Storage::get_context();
+                let source = Storage::get(storage, self.price_source_prefix.concat(symbol))
+                    .unwrap_or_else(ByteString::empty);
+                Some((packed, source))
+            }
+            None => self.get_legacy_price(symbol),
+        }
+    }
+
+    /// Rebuilds a packed price from the legacy price record
+    /// (`symbol | price | timestamp (u64 LE) | source | confidence (u32 LE)`).
+    ///
+    /// The record carries no round id. Prices stored since attestations were introduced take
+    /// it from the attestation record (`round id (u64 LE) | timestamp (u64 LE) | price`);
+    /// older ones have none and start from round zero.
+    fn get_legacy_price(&self, symbol: &ByteString) -> Option<(PackedPrice, ByteString)> {
+        let storage = Storage::get_context();
+        let record = Storage::get(storage.clone(), self.price_prefix.concat(symbol))?.to_bytes();
+        let (price, timestamp, source, confidence) = self.parse_legacy_price_record(symbol, &record)?;
+
+        let round_id = match Storage::get(storage, self.attestation_prefix.concat(symbol)) {
+            Some(attestation) if attestation.len() >= 8 => {
+                let bytes = attestation.to_bytes();
+                u64::from_le_bytes([
+                    bytes[0], bytes[1], bytes[2], bytes[3],
+                    bytes[4], bytes[5], bytes[6], bytes[7]
+                ])
+            }
+            _ => 0,
+        };
+
+        Some((PackedPrice { price, timestamp, confidence, round_id }, source))
+    }
+
+    /// Splits a legacy price record into (price, timestamp, source, confidence).
+    ///
+    /// The price has a variable length and it and the timestamp may contain the `|` delimiter,
+    /// so each price length is tried in turn. Sources never contain the delimiter, which rules
+    /// out every length but the real one: a shorter one leaves a delimiter in the source.
+    fn parse_legacy_price_record(&self, symbol: &ByteString, record: &[u8]) -> Option<(Int256, u64, ByteString, u32)> {
+        let delimiter = b'|';
+        let n = record.len();
+        let price_start = symbol.len() + 1;
+        if n < price_start + 1 + 1 + 8 + 1 + 5 || record[price_start - 1] != delimiter || record[n - 5] != delimiter {
+            return None;
+        }
+
+        let confidence = u32::from_le_bytes([record[n - 4], record[n - 3], record[n - 2], record[n - 1]]);
+        for price_len in 1..=32 {
+            let timestamp_start = price_start + price_len + 1;
+            let source_start = timestamp_start + 8 + 1;
+            if source_start > n - 5 {
+                break;
+            }
+
+            let source = &record[source_start..n - 5];
+            if record[timestamp_start - 1] != delimiter || record[source_start - 1] != delimiter || source.contains(&delimiter) {
+                continue;
+            }
+
+            let price = Int256::from_byte_string(ByteString::from_bytes(&record[price_start..timestamp_start - 1]));
+            let t = &record[timestamp_start..timestamp_start + 8];
+            let timestamp = u64::from_le_bytes([t[0], t[1], t[2], t[3], t[4], t[5], t[6], t[7]]);
+            return Some((price, timestamp, ByteString::from_bytes(source), confidence));
+        }
+
+        None
+    }
+
+    /// Latest update of a symbol as (round id, timestamp, price)
+    fn get_attestation_record(&self, symbol: &ByteString) -> Option<(u64, u64, Int256)> {
+        let (latest, _) = self.get_latest_price(symbol)?;
+        Some((latest.round_id, latest.timestamp, latest.price))
+    }
+
+    /// Canonical attestation payload, bound to this network and contract
+    fn attestation_payload(&self, symbol: &ByteString, round_id: u64, timestamp: u64, price: Int256) -> ByteString {
+        let price_bytes = price.into_byte_string();
+        ByteString::from_bytes(&[ATTESTATION_VERSION])
+            .concat(&ByteString::from_bytes(&Runtime::get_network().to_le_bytes()))
+            .concat(&Runtime::get_executing_script_hash().into_byte_string())
+            .concat(&ByteString::from_bytes(&[symbol.len() as u8]))
+            .concat(symbol)
+            .concat(&ByteString::from_bytes(&round_id.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&timestamp.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&[price_bytes.len() as u8]))
+            .concat(&price_bytes)
     }
 
     fn validate_price_data(&self, price_data: &PriceData) -> bool {
@@ -546,31 +1833,6 @@ impl OraclePriceFeed {
         true
     }
 
-    fn serialize_price_data(&self, price_data: PriceData) -> ByteString {
-        // Simplified serialization
-        let mut data = price_data.symbol;
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&price_data.price.into_byte_string());
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&ByteString::from_bytes(&price_data.timestamp.to_le_bytes()));
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&price_data.source);
-        data = data.concat(&ByteString::from_literal("|"));
-        data = data.concat(&ByteString::from_bytes(&price_data.confidence.to_le_bytes()));
-        data
-    }
-
-    fn deserialize_price_data(&self, _data: ByteString) -> PriceData {
-        // Simplified deserialization - in production, use proper parsing
-        PriceData {
-            symbol: ByteString::from_literal("BTC"),
-            price: Int256::new(50000),
-            timestamp: Runtime::get_time(),
-            source: ByteString::from_literal("coinapi"),
-            confidence: 9500,
-        }
-    }
-
     fn serialize_request(&self, request: OracleRequest) -> ByteString {
         // Simplified serialization
         let mut data = request.id.into_byte_string();
@@ -596,4 +1858,411 @@ impl OraclePriceFeed {
         // Check against authorized oracles, subscription status, etc.
         true
     }
+
+    fn get_min_sources(&self) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.min_sources_key.clone()) {
+            Some(sources_bytes) => {
+                let bytes = sources_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    1
+                }
+            },
+            None => 1,
+        }
+    }
+
+    fn get_price_deviation(&self) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.price_deviation_key.clone()) {
+            Some(deviation_bytes) => {
+                let bytes = deviation_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    1000
+                }
+            },
+            None => 1000, // 10% default
+        }
+    }
+
+    fn get_min_reporter_bond(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.min_reporter_bond_key.clone()) {
+            Some(bond_bytes) => Int256::from_byte_string(bond_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    fn set_reporter_bond(&self, reporter: H160, bond: Int256) {
+        let storage = Storage::get_context();
+        let bond_key = self.reporter_bond_prefix.concat(&reporter.into_byte_string());
+        let was_bonded = Storage::get(storage.clone(), bond_key.clone()).is_some();
+        if bond == Int256::zero() {
+            Storage::delete(storage.clone(), bond_key);
+        } else {
+            Storage::put(storage.clone(), bond_key, bond.into_byte_string());
+        }
+
+        // Bonded reporter count used for the round quorum
+        let bonded = self.get_bonded_reporters();
+        if was_bonded && bond == Int256::zero() {
+            Storage::put(storage, self.bonded_reporters_key.clone(), ByteString::from_bytes(&bonded.saturating_sub(1).to_le_bytes()));
+        } else if !was_bonded && bond != Int256::zero() {
+            Storage::put(storage, self.bonded_reporters_key.clone(), ByteString::from_bytes(&(bonded + 1).to_le_bytes()));
+        }
+    }
+
+    fn get_bonded_reporters(&self) -> u64 {
+        self.read_u64(Storage::get_context(), self.bonded_reporters_key.clone())
+    }
+
+    fn get_bond_token(&self) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.bond_token_key.clone()) {
+            Some(token_bytes) => H160::from_byte_string(token_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    fn transfer_bond_token(&self, to: H160, amount: Int256) -> bool {
+        if amount <= Int256::zero() {
+            return true;
+        }
+
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(to.into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+        Contract::call_bool(self.get_bond_token(), ByteString::from_literal("transfer"), CallFlags::All, args)
+    }
+
+    fn read_u64(&self, storage: StorageContext, key: ByteString) -> u64 {
+        match Storage::get(storage, key) {
+            Some(value_bytes) => {
+                let bytes = value_bytes.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    fn get_pending_unbond(&self, reporter: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.unbond_amount_prefix.concat(&reporter.into_byte_string())) {
+            Some(amount_bytes) => Int256::from_byte_string(amount_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    fn get_unbond_unlock_time(&self, reporter: H160) -> u64 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.unbond_time_prefix.concat(&reporter.into_byte_string())) {
+            Some(time_bytes) => {
+                let bytes = time_bytes.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    fn get_round_key(&self, prefix: &ByteString, symbol: ByteString, round: Int256) -> ByteString {
+        prefix
+            .concat(&symbol)
+            .concat(&ByteString::from_literal("_"))
+            .concat(&round.into_byte_string())
+    }
+
+    fn get_submission_key(&self, prefix: &ByteString, symbol: ByteString, round: Int256, reporter: H160) -> ByteString {
+        self.get_round_key(prefix, symbol, round)
+            .concat(&ByteString::from_literal("_"))
+            .concat(&reporter.into_byte_string())
+    }
+
+    fn get_submission(&self, prefix: &ByteString, symbol: ByteString, round: Int256, reporter: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.get_submission_key(prefix, symbol, round, reporter)) {
+            Some(value_bytes) => Int256::from_byte_string(value_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Sorts submissions by price and returns the first price at which the
+    /// cumulative bond weight reaches half of the total weight.
+    fn weighted_median(&self, prices: &mut Array<Int256>, weights: &mut Array<Int256>) -> Int256 {
+        let count = prices.size();
+        if count == 0 {
+            return Int256::zero();
+        }
+
+        // Insertion sort keeps prices and weights paired
+        for i in 1..count {
+            let price = prices.get(i);
+            let weight = weights.get(i);
+            let mut j = i;
+            while j > 0 && prices.get(j - 1) > price {
+                prices.set(j, prices.get(j - 1));
+                weights.set(j, weights.get(j - 1));
+                j -= 1;
+            }
+            prices.set(j, price);
+            weights.set(j, weight);
+        }
+
+        let mut total_weight = Int256::zero();
+        for i in 0..count {
+            total_weight = total_weight.checked_add(&weights.get(i));
+        }
+
+        let mut cumulative = Int256::zero();
+        for i in 0..count {
+            cumulative = cumulative.checked_add(&weights.get(i));
+            if cumulative.checked_mul(&Int256::new(2)) >= total_weight {
+                return prices.get(i);
+            }
+        }
+
+        prices.get(count - 1)
+    }
+
+    fn serialize_reporters(&self, reporters: &Array<H160>) -> ByteString {
+        let count = reporters.size() as u32;
+        let mut result = ByteString::from_bytes(&count.to_le_bytes());
+
+        for i in 0..reporters.size() {
+            result = result.concat(&reporters.get(i).into_byte_string());
+        }
+
+        result
+    }
+
+    fn deserialize_reporters(&self, data: ByteString) -> Array<H160> {
+        let bytes = data.to_bytes();
+        let mut reporters = Array::new();
+
+        if bytes.len() < 4 {
+            return reporters;
+        }
+
+        let count = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+        let mut offset = 4;
+
+        for _ in 0..count {
+            if offset + 20 <= bytes.len() {
+                reporters.push(H160::from_byte_string(ByteString::from_bytes(&bytes[offset..offset + 20])));
+                offset += 20;
+            }
+        }
+
+        reporters
+    }
+
+    /// Parses a list of non-negative integers such as `[6512345,301234]`.
+    /// Brackets, quotes and whitespace are ignored; an entry containing any other
+    /// character (a sign, a decimal point, ...) parses as zero and fails validation.
+    fn parse_price_list(&self, result: ByteString) -> Array<Int256> {
+        let bytes = result.to_bytes();
+        let ten = Int256::new(10);
+        let mut prices = Array::new();
+        let mut current = Int256::zero();
+        let mut has_entry = false;
+        let mut invalid = false;
+
+        for &byte in bytes.iter() {
+            match byte {
+                b'0'..=b'9' => {
+                    if !invalid {
+                        current = current.checked_mul(&ten).checked_add(&Int256::new((byte - b'0') as i64));
+                    }
+                    has_entry = true;
+                }
+                b',' => {
+                    prices.push(if invalid { Int256::zero() } else { current });
+                    current = Int256::zero();
+                    has_entry = false;
+                    invalid = false;
+                }
+                b'[' | b']' | b'"' | b' ' | b'\n' | b'\r' | b'\t' => {}
+                _ => {
+                    // Fractions and signs are not supported: the entry stays invalid
+                    // until the next separator
+                    invalid = true;
+                    has_entry = true;
+                }
+            }
+        }
+
+        if has_entry || prices.size() > 0 {
+            prices.push(if invalid { Int256::zero() } else { current });
+        }
+
+        prices
+    }
+
+    fn serialize_symbols(&self, symbols: &Array<ByteString>) -> ByteString {
+        let count = symbols.size() as u32;
+        let mut result = ByteString::from_bytes(&count.to_le_bytes());
+
+        for i in 0..symbols.size() {
+            let symbol = symbols.get(i);
+            result = result.concat(&ByteString::from_bytes(&[symbol.len() as u8]));
+            result = result.concat(&symbol);
+        }
+
+        result
+    }
+
+    fn deserialize_symbols(&self, data: ByteString) -> Array<ByteString> {
+        let bytes = data.to_bytes();
+        let mut symbols = Array::new();
+
+        if bytes.len() < 4 {
+            return symbols;
+        }
+
+        let count = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+        let mut offset = 4;
+
+        for _ in 0..count {
+            if offset >= bytes.len() {
+                break;
+            }
+            let len = bytes[offset] as usize;
+            offset += 1;
+            if offset + len > bytes.len() {
+                break;
+            }
+            symbols.push(ByteString::from_bytes(&bytes[offset..offset + len]));
+            offset += len;
+        }
+
+        symbols
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use epicchain_contract::host;
+
+    fn parse(input: &str) -> Array<Int256> {
+        OraclePriceFeed::init().parse_price_list(ByteString::from_literal(input))
+    }
+
+    fn account(byte: u8) -> H160 {
+        H160::from_bytes(&[byte; 20])
+    }
+
+    /// An oracle owned by 0x01 whose reporters bond the token at 0xB7
+    fn deploy() -> OraclePriceFeed {
+        host::set_executing_script_hash(account(0x0C));
+        let oracle = OraclePriceFeed::init();
+        assert!(oracle.initialize(account(1), 1, SECONDS_PER_HOUR, Int256::zero()));
+        assert!(oracle.set_bond_token(account(0xB7)));
+        oracle
+    }
+
+    fn bond(oracle: &OraclePriceFeed, reporter: H160, amount: i64) {
+        host::set_calling_script_hash(account(0xB7));
+        assert!(oracle.bond_reporter(reporter, Int256::new(amount), Any::default()));
+    }
+
+    #[test]
+    fn test_push_feed_needs_a_nonzero_minimum_bond() {
+        let oracle = deploy();
+        let symbol = ByteString::from_literal("EPIC");
+        bond(&oracle, account(0x21), 100);
+
+        assert!(!oracle.submit_price(account(0x21), symbol.clone(), Int256::new(500)));
+        assert!(!oracle.set_min_reporter_bond(Int256::zero()));
+        assert!(!oracle.submit_price(account(0x21), symbol.clone(), Int256::new(500)));
+
+        assert!(oracle.set_min_reporter_bond(Int256::new(50)));
+        assert!(oracle.submit_price(account(0x21), symbol, Int256::new(500)));
+    }
+
+    #[test]
+    fn test_rounds_take_a_bounded_number_of_reporters() {
+        let oracle = deploy();
+        let symbol = ByteString::from_literal("EPIC");
+        assert!(oracle.set_min_reporter_bond(Int256::one()));
+
+        for i in 0..=MAX_ROUND_REPORTERS as u8 {
+            bond(&oracle, account(0x40 + i), 10);
+        }
+        for i in 0..MAX_ROUND_REPORTERS as u8 {
+            assert!(oracle.submit_price(account(0x40 + i), symbol.clone(), Int256::new(500)));
+        }
+        assert!(!oracle.submit_price(account(0x40 + MAX_ROUND_REPORTERS as u8), symbol.clone(), Int256::new(900)));
+
+        // The full round closes early although one bonded reporter is left out
+        assert_eq!(oracle.finalize_round(symbol.clone()), Int256::new(500));
+        assert!(oracle.submit_price(account(0x40 + MAX_ROUND_REPORTERS as u8), symbol, Int256::new(900)));
+    }
+
+    #[test]
+    fn test_legacy_price_record_is_read_without_an_attestation() {
+        let oracle = deploy();
+        let symbol = ByteString::from_literal("EPIC");
+
+        // A price and timestamp whose bytes contain the delimiter, as written before attestations
+        let price = Int256::new(0x7C7C01);
+        let timestamp: u64 = 0x65_7C00_1234;
+        let record = symbol.clone()
+            .concat(&ByteString::from_literal("|"))
+            .concat(&price.into_byte_string())
+            .concat(&ByteString::from_literal("|"))
+            .concat(&ByteString::from_bytes(&timestamp.to_le_bytes()))
+            .concat(&ByteString::from_literal("|"))
+            .concat(&ByteString::from_literal("coinapi"))
+            .concat(&ByteString::from_literal("|"))
+            .concat(&ByteString::from_bytes(&9500u32.to_le_bytes()));
+        Storage::put(Storage::get_context(), oracle.price_prefix.concat(&symbol), record);
+
+        assert!(oracle.migrate_price_record(symbol.clone()));
+        let (packed, source) = oracle.get_latest_price(&symbol).unwrap();
+        assert_eq!(packed.price, price);
+        assert_eq!(packed.timestamp, timestamp);
+        assert_eq!(packed.confidence, 9500);
+        assert_eq!(packed.round_id, 0);
+        assert_eq!(source, ByteString::from_literal("coinapi"));
+    }
+
+    #[test]
+    fn test_parse_price_list() {
+        let prices = parse("[6512345, 301234]");
+        assert_eq!(prices.size(), 2);
+        assert_eq!(prices.get(0), Int256::new(6512345));
+        assert_eq!(prices.get(1), Int256::new(301234));
+    }
+
+    #[test]
+    fn test_parse_price_list_rejects_decimal_entry() {
+        let prices = parse("[65123.45,301234]");
+        assert_eq!(prices.size(), 2);
+        assert_eq!(prices.get(0), Int256::zero());
+        assert_eq!(prices.get(1), Int256::new(301234));
+    }
+
+    #[test]
+    fn test_parse_price_list_rejects_negative_entry() {
+        let prices = parse("[-5,\"7\"]");
+        assert_eq!(prices.size(), 2);
+        assert_eq!(prices.get(0), Int256::zero());
+        assert_eq!(prices.get(1), Int256::new(7));
+    }
 }
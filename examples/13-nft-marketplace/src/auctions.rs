@@ -51,11 +51,11 @@ impl crate::NftMarketplace {
         // Validate duration
         let min_duration = StorageUtils::load_u64_config(
             self.storage_keys.min_auction_duration_key.clone(),
-            3600 // 1 hour
+            SECONDS_PER_HOUR
         );
         let max_duration = StorageUtils::load_u64_config(
             self.storage_keys.max_auction_duration_key.clone(),
-            604800 // 7 days
+            SECONDS_PER_WEEK
         );
 
         if duration < min_duration || duration > max_duration {
@@ -678,7 +678,7 @@ impl crate::NftMarketplace {
             amount,
             auction.payment_token,
             Int256::new(250), // Platform fee
-            Int256::new(250), // Royalty fee
+            Int256::zero(), // Royalty fee, not charged (see `calculate_fees`)
             SaleType::Auction
         );
         
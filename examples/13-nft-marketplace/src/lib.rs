@@ -71,7 +71,7 @@ impl NftMarketplace {
             return false;
         }
 
-        if min_listing_duration < 3600 || max_listing_duration > 2592000 { // 1 hour to 30 days
+        if min_listing_duration < SECONDS_PER_HOUR || max_listing_duration > days(30) {
             Runtime::log(ByteString::from_literal("Invalid listing duration limits"));
             return false;
         }
@@ -47,11 +47,11 @@ impl crate::NftMarketplace {
         // Validate duration
         let min_duration = StorageUtils::load_u64_config(
             self.storage_keys.min_duration_key.clone(),
-            3600
+            SECONDS_PER_HOUR
         );
         let max_duration = StorageUtils::load_u64_config(
             self.storage_keys.max_duration_key.clone(),
-            2592000
+            days(30)
         );
 
         if duration < min_duration || duration > max_duration {
@@ -408,9 +408,9 @@ impl crate::NftMarketplace {
             .checked_mul(&Int256::new(platform_fee_rate as i64))
             .checked_div(&Int256::new(10000));
 
-        // Get royalty information (simplified)
-        let royalty_fees = Vec::new(); // Complete implementation: Query NEP-24 royalty info
-
+        // No royalties are charged: settlement does not move the sale proceeds yet, so there
+        // is nothing to pay NEP-24 royalties out of
+        let royalty_fees = Vec::new();
         let total_fees = platform_fee;
         let seller_proceeds = price.checked_sub(&total_fees);
 
@@ -203,11 +203,11 @@ impl MarketplaceConfig {
     pub fn default() -> Self {
         Self {
             platform_fee_rate: 250,      // 2.5%
-            min_listing_duration: 3600,  // 1 hour
-            max_listing_duration: 2592000, // 30 days
-            min_auction_duration: 3600,  // 1 hour
-            max_auction_duration: 604800, // 7 days
-            bid_extension_time: 600,     // 10 minutes
+            min_listing_duration: SECONDS_PER_HOUR,
+            max_listing_duration: days(30),
+            min_auction_duration: SECONDS_PER_HOUR,
+            max_auction_duration: SECONDS_PER_WEEK,
+            bid_extension_time: 10 * SECONDS_PER_MINUTE,
             min_bid_increment: 500,      // 5%
         }
     }
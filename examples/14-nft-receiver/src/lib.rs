@@ -0,0 +1,141 @@
+//! # NFT Receiver Contract
+//!
+//! A minimal NEP-11 receiver used to exercise safe transfers:
+//! - Implements `onNEP11Payment`, which NEP-11 tokens call when a contract receives an NFT
+//! - Accepts or rejects incoming tokens depending on an owner-controlled switch
+//! - Records the sender of every token it holds
+//!
+//! Deploy it next to the NEP-11 examples to check that transfers into contracts invoke the
+//! callback, and that a rejection aborts the transfer.
+
+#![no_std]
+#![no_main]
+
+use epicchain_contract::prelude::*;
+use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
+
+/// Contract that receives NEP-11 tokens
+#[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
+#[contract_version("1.0.0")]
+#[contract_standards("")]
+#[contract_permission("*", "*")]
+#[contract_meta("description", "Mock NEP-11 receiver for testing safe transfers")]
+#[contract_meta("category", "Testing")]
+pub struct NftReceiver {
+    owner_key: ByteString,
+    rejecting_key: ByteString,         // present while incoming tokens are rejected
+    received_count_key: ByteString,    // number of tokens received
+    received_prefix: ByteString,       // token contract + token_id -> sender
+}
+
+#[contract_impl]
+impl NftReceiver {
+    /// Initialize the receiver
+    pub fn init() -> Self {
+        Self {
+            owner_key: ByteString::from_literal("owner"),
+            rejecting_key: ByteString::from_literal("rejecting"),
+            received_count_key: ByteString::from_literal("received_count"),
+            received_prefix: ByteString::from_literal("received_"),
+        }
+    }
+
+    /// Initialize the receiver with its owner
+    #[method]
+    pub fn initialize(&self, owner: H160) -> bool {
+        let storage = Storage::get_context();
+
+        if Storage::get(storage.clone(), self.owner_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Already initialized"));
+            return false;
+        }
+
+        if !Runtime::check_witness(owner) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        Storage::put(storage, self.owner_key.clone(), owner.into_byte_string());
+        true
+    }
+
+    /// Accept or reject incoming tokens (owner only)
+    #[method]
+    #[only_owner]
+    pub fn set_accepting(&self, accepting: bool) -> bool {
+        let storage = Storage::get_context();
+        if accepting {
+            Storage::delete(storage, self.rejecting_key.clone());
+        } else {
+            Storage::put(storage, self.rejecting_key.clone(), ByteString::from_literal("true"));
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(if accepting { Int256::one() } else { Int256::zero() }.into_any());
+        Runtime::notify(ByteString::from_literal("AcceptingChanged"), event_data);
+        true
+    }
+
+    /// NEP-11 receiver callback, invoked by the token contract after a transfer
+    #[method]
+    #[wasm_export(name = "onNEP11Payment")]
+    pub fn on_nep11_payment(&self, from: H160, amount: Int256, token_id: ByteString, _data: Any) -> bool {
+        if !self.is_accepting() {
+            Runtime::log(ByteString::from_literal("Receiver is not accepting tokens"));
+            return false;
+        }
+
+        // The calling contract is the token contract
+        let token = Runtime::get_calling_script_hash();
+        let storage = Storage::get_context();
+        let received_key = self.received_prefix
+            .concat(&token.into_byte_string())
+            .concat(&token_id);
+        Storage::put(storage.clone(), received_key, from.into_byte_string());
+
+        let count = self.get_received_count().checked_add(&Int256::one());
+        Storage::put(storage, self.received_count_key.clone(), count.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(token.into_any());
+        event_data.push(from.into_any());
+        event_data.push(amount.into_any());
+        event_data.push(token_id.into_any());
+        Runtime::notify(ByteString::from_literal("NftReceived"), event_data);
+
+        true
+    }
+
+    /// Whether incoming tokens are accepted
+    #[method]
+    #[safe]
+    pub fn is_accepting(&self) -> bool {
+        let storage = Storage::get_context();
+        Storage::get(storage, self.rejecting_key.clone()).is_none()
+    }
+
+    /// Number of tokens received so far
+    #[method]
+    #[safe]
+    pub fn get_received_count(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.received_count_key.clone()) {
+            Some(count_bytes) => Int256::from_byte_string(count_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Sender of a received token (zero if the token was never received)
+    #[method]
+    #[safe]
+    pub fn get_sender(&self, token: H160, token_id: ByteString) -> H160 {
+        let storage = Storage::get_context();
+        let received_key = self.received_prefix
+            .concat(&token.into_byte_string())
+            .concat(&token_id);
+        match Storage::get(storage, received_key) {
+            Some(sender_bytes) => H160::from_byte_string(sender_bytes),
+            None => H160::zero(),
+        }
+    }
+}
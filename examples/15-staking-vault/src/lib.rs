@@ -0,0 +1,519 @@
+//! # Staking Vault Contract
+//!
+//! An auto-compounding vault built on top of the staking example:
+//! - Accepts deposits of the pool's stake token through `onNEP17Payment`
+//! - Stakes every deposit into a single staking pool on behalf of all depositors
+//! - Issues shares whose value grows as rewards are compounded
+//! - Compounds through a public `harvest()` crank that pays the caller a small bounty
+//!
+//! The wrapped pool must stake and reward the same token, and should have no lock
+//! period: early withdrawal penalties charged by the pool are not passed on to the vault.
+
+#![no_std]
+#![no_main]
+
+use epicchain_contract::prelude::*;
+use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
+
+/// Maximum harvest bounty (5%)
+const MAX_HARVEST_BOUNTY: u32 = 500;
+
+/// Minimum time between two harvests
+const MIN_HARVEST_INTERVAL: u64 = SECONDS_PER_HOUR;
+
+/// Precision of `get_price_per_share`
+const SHARE_PRECISION: i64 = 100_000_000;
+
+/// Auto-compounding staking vault
+#[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
+#[contract_version("1.0.0")]
+#[contract_standards("")]
+#[contract_permission("*", "unstake", "claim_rewards", "transfer", "balanceOf")]
+#[contract_meta("description", "Auto-compounding vault for staking pools")]
+#[contract_meta("category", "DeFi")]
+pub struct StakingVault {
+    // Configuration
+    owner_key: ByteString,
+    staking_contract_key: ByteString,  // staking contract
+    pool_id_key: ByteString,           // pool the vault stakes into
+    asset_key: ByteString,             // stake and reward token of the pool
+    harvest_bounty_key: ByteString,    // caller bounty in basis points of harvested rewards
+
+    // Accounting
+    total_shares_key: ByteString,      // outstanding shares
+    staked_assets_key: ByteString,     // assets staked in the pool
+    idle_assets_key: ByteString,       // assets held by the vault but not staked yet
+    last_harvest_key: ByteString,      // time of the last harvest
+    shares_prefix: ByteString,         // depositor -> shares
+}
+
+#[contract_impl]
+impl StakingVault {
+    /// Initialize the vault
+    pub fn init() -> Self {
+        Self {
+            owner_key: ByteString::from_literal("owner"),
+            staking_contract_key: ByteString::from_literal("staking_contract"),
+            pool_id_key: ByteString::from_literal("pool_id"),
+            asset_key: ByteString::from_literal("asset"),
+            harvest_bounty_key: ByteString::from_literal("harvest_bounty"),
+            total_shares_key: ByteString::from_literal("total_shares"),
+            staked_assets_key: ByteString::from_literal("staked_assets"),
+            idle_assets_key: ByteString::from_literal("idle_assets"),
+            last_harvest_key: ByteString::from_literal("last_harvest"),
+            shares_prefix: ByteString::from_literal("shares_"),
+        }
+    }
+
+    /// Initialize the vault with the pool it compounds
+    #[method]
+    pub fn initialize(
+        &self,
+        owner: H160,
+        staking_contract: H160,
+        pool_id: Int256,
+        asset: H160,
+        harvest_bounty: u32
+    ) -> bool {
+        let storage = Storage::get_context();
+
+        if Storage::get(storage.clone(), self.owner_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Vault already initialized"));
+            return false;
+        }
+
+        if !Runtime::check_witness(owner) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        if staking_contract == H160::zero() || asset == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid contract hash"));
+            return false;
+        }
+
+        if harvest_bounty > MAX_HARVEST_BOUNTY {
+            Runtime::log(ByteString::from_literal("Harvest bounty too high (max 5%)"));
+            return false;
+        }
+
+        Storage::put(storage.clone(), self.owner_key.clone(), owner.into_byte_string());
+        Storage::put(storage.clone(), self.staking_contract_key.clone(), staking_contract.into_byte_string());
+        Storage::put(storage.clone(), self.pool_id_key.clone(), pool_id.into_byte_string());
+        Storage::put(storage.clone(), self.asset_key.clone(), asset.into_byte_string());
+        Storage::put(storage, self.harvest_bounty_key.clone(), ByteString::from_bytes(&harvest_bounty.to_le_bytes()));
+
+        let mut event_data = Array::new();
+        event_data.push(staking_contract.into_any());
+        event_data.push(pool_id.into_any());
+        event_data.push(asset.into_any());
+        Runtime::notify(ByteString::from_literal("VaultInitialized"), event_data);
+
+        true
+    }
+
+    /// NEP-17 receiver callback: deposits of the asset token mint shares to the sender
+    #[method]
+    #[wasm_export(name = "onNEP17Payment")]
+    pub fn deposit(&self, from: H160, amount: Int256, _data: Any) -> bool {
+        if Runtime::get_calling_script_hash() != self.get_asset() {
+            Runtime::log(ByteString::from_literal("Only the vault asset can be deposited"));
+            return false;
+        }
+
+        // Rewards claimed during a harvest arrive through the same callback
+        if from == self.get_staking_contract() {
+            return true;
+        }
+
+        if from == H160::zero() || amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid deposit"));
+            return false;
+        }
+
+        // Bring in rewards earned so far so the new depositor pays for them
+        self.compound_pending_rewards();
+
+        // Price the shares before the deposit changes the totals
+        let shares = self.convert_to_shares(amount);
+        if shares <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Deposit too small"));
+            return false;
+        }
+
+        self.set_shares(from, self.get_shares(from).checked_add(&shares));
+        self.set_value(&self.total_shares_key, self.get_total_shares().checked_add(&shares));
+        self.set_value(&self.idle_assets_key, self.get_idle_assets().checked_add(&amount));
+        self.stake_idle_assets();
+
+        let mut event_data = Array::new();
+        event_data.push(from.into_any());
+        event_data.push(amount.into_any());
+        event_data.push(shares.into_any());
+        Runtime::notify(ByteString::from_literal("Deposit"), event_data);
+
+        true
+    }
+
+    /// Burn shares and withdraw the assets they are worth
+    #[method]
+    pub fn withdraw(&self, user: H160, shares: Int256) -> Int256 {
+        if !Runtime::check_witness(user) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return Int256::zero();
+        }
+
+        let user_shares = self.get_shares(user);
+        if shares <= Int256::zero() || shares > user_shares {
+            Runtime::log(ByteString::from_literal("Invalid share amount"));
+            return Int256::zero();
+        }
+
+        // Rewards earned while the shares were held belong to their holders
+        self.compound_pending_rewards();
+
+        let assets = self.convert_to_assets(shares);
+        if assets <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Withdrawal too small"));
+            return Int256::zero();
+        }
+
+        // Pay from idle assets first and unstake the rest
+        let idle = self.get_idle_assets();
+        if assets > idle {
+            let shortfall = assets.checked_sub(&idle);
+            if !self.call_staking("unstake", shortfall) {
+                Runtime::log(ByteString::from_literal("Unstake failed"));
+                return Int256::zero();
+            }
+            self.set_value(&self.staked_assets_key, self.get_staked_assets().checked_sub(&shortfall));
+            self.set_value(&self.idle_assets_key, Int256::zero());
+        } else {
+            self.set_value(&self.idle_assets_key, idle.checked_sub(&assets));
+        }
+
+        // Burn before paying out so a re-entrant call sees the reduced balance
+        self.set_shares(user, user_shares.checked_sub(&shares));
+        self.set_value(&self.total_shares_key, self.get_total_shares().checked_sub(&shares));
+
+        if !self.transfer_asset(user, assets) {
+            abort_with_message(ByteString::from_literal("Asset transfer failed"));
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(user.into_any());
+        event_data.push(shares.into_any());
+        event_data.push(assets.into_any());
+        Runtime::notify(ByteString::from_literal("Withdraw"), event_data);
+
+        assets
+    }
+
+    /// Claim the vault's pool rewards and stake them again.
+    ///
+    /// Anyone can call it; the caller receives the harvest bounty out of the claimed rewards.
+    #[method]
+    pub fn harvest(&self, caller: H160) -> Int256 {
+        if !Runtime::check_witness(caller) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return Int256::zero();
+        }
+
+        let now = Runtime::get_time();
+        if elapsed(self.get_last_harvest(), now) < MIN_HARVEST_INTERVAL {
+            Runtime::log(ByteString::from_literal("Harvested too recently"));
+            return Int256::zero();
+        }
+
+        if self.get_staked_assets() <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Nothing staked"));
+            return Int256::zero();
+        }
+
+        let claimed = self.claim_pool_rewards();
+        if claimed <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("No rewards to harvest"));
+            return Int256::zero();
+        }
+
+        let storage = Storage::get_context();
+        Storage::put(storage, self.last_harvest_key.clone(), ByteString::from_bytes(&now.to_le_bytes()));
+
+        let bounty = claimed
+            .checked_mul(&Int256::new(self.get_harvest_bounty() as i64))
+            .checked_div(&Int256::new(10000));
+        let compounded = claimed.checked_sub(&bounty);
+
+        self.set_value(&self.idle_assets_key, self.get_idle_assets().checked_add(&compounded));
+        self.stake_idle_assets();
+
+        if bounty > Int256::zero() && !self.transfer_asset(caller, bounty) {
+            abort_with_message(ByteString::from_literal("Bounty transfer failed"));
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(caller.into_any());
+        event_data.push(claimed.into_any());
+        event_data.push(bounty.into_any());
+        event_data.push(compounded.into_any());
+        Runtime::notify(ByteString::from_literal("Harvested"), event_data);
+
+        compounded
+    }
+
+    /// Set the harvest bounty in basis points (owner only)
+    #[method]
+    #[only_owner]
+    pub fn set_harvest_bounty(&self, harvest_bounty: u32) -> bool {
+        if harvest_bounty > MAX_HARVEST_BOUNTY {
+            Runtime::log(ByteString::from_literal("Harvest bounty too high (max 5%)"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        Storage::put(storage, self.harvest_bounty_key.clone(), ByteString::from_bytes(&harvest_bounty.to_le_bytes()));
+
+        let mut event_data = Array::new();
+        event_data.push(Int256::new(harvest_bounty as i64).into_any());
+        Runtime::notify(ByteString::from_literal("HarvestBountyChanged"), event_data);
+        true
+    }
+
+    /// Total assets managed by the vault (staked and idle)
+    #[method]
+    #[safe]
+    pub fn get_total_assets(&self) -> Int256 {
+        self.get_staked_assets().checked_add(&self.get_idle_assets())
+    }
+
+    /// Total shares outstanding
+    #[method]
+    #[safe]
+    pub fn get_total_shares(&self) -> Int256 {
+        self.get_value(&self.total_shares_key)
+    }
+
+    /// Shares held by a depositor
+    #[method]
+    #[safe]
+    pub fn get_shares(&self, user: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.shares_prefix.concat(&user.into_byte_string())) {
+            Some(share_bytes) => Int256::from_byte_string(share_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Assets one share is worth, scaled by 1e8
+    #[method]
+    #[safe]
+    pub fn get_price_per_share(&self) -> Int256 {
+        self.convert_to_assets(Int256::new(SHARE_PRECISION))
+    }
+
+    /// Shares a deposit of `amount` would mint
+    #[method]
+    #[safe]
+    pub fn preview_deposit(&self, amount: Int256) -> Int256 {
+        self.convert_to_shares(amount)
+    }
+
+    /// Assets burning `shares` would withdraw
+    #[method]
+    #[safe]
+    pub fn preview_withdraw(&self, shares: Int256) -> Int256 {
+        self.convert_to_assets(shares)
+    }
+
+    /// Get the vault configuration
+    #[method]
+    #[safe]
+    pub fn get_config(&self) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        result.put(ByteString::from_literal("staking_contract"), self.get_staking_contract().into_any());
+        result.put(ByteString::from_literal("pool_id"), self.get_pool_id().into_any());
+        result.put(ByteString::from_literal("asset"), self.get_asset().into_any());
+        result.put(ByteString::from_literal("harvest_bounty"), Int256::new(self.get_harvest_bounty() as i64).into_any());
+        result.put(ByteString::from_literal("staked_assets"), self.get_staked_assets().into_any());
+        result.put(ByteString::from_literal("idle_assets"), self.get_idle_assets().into_any());
+        result.put(ByteString::from_literal("last_harvest"), Int256::new(self.get_last_harvest() as i64).into_any());
+        result
+    }
+
+    // Helper functions
+
+    /// Shares minted for `assets`. The +1 virtual share and asset keep the first depositor
+    /// from inflating the share price against later deposits.
+    fn convert_to_shares(&self, assets: Int256) -> Int256 {
+        assets
+            .checked_mul(&self.get_total_shares().checked_add(&Int256::one()))
+            .checked_div(&self.get_total_assets().checked_add(&Int256::one()))
+    }
+
+    fn convert_to_assets(&self, shares: Int256) -> Int256 {
+        shares
+            .checked_mul(&self.get_total_assets().checked_add(&Int256::one()))
+            .checked_div(&self.get_total_shares().checked_add(&Int256::one()))
+    }
+
+    /// Claim the vault's pool rewards and return how much actually arrived
+    fn claim_pool_rewards(&self) -> Int256 {
+        // Count what actually arrived rather than trusting the staking contract's return value
+        let vault = Runtime::get_executing_script_hash();
+        let balance_before = self.asset_balance(vault);
+
+        let mut args = Array::new();
+        args.push(self.get_pool_id().into_any());
+        args.push(vault.into_any());
+        Contract::call(
+            self.get_staking_contract(),
+            ByteString::from_literal("claim_rewards"),
+            CallFlags::All,
+            args,
+        );
+
+        self.asset_balance(vault).checked_sub(&balance_before)
+    }
+
+    /// Add unclaimed pool rewards to the idle assets without paying a bounty, so share
+    /// prices include them before a deposit or withdrawal changes the totals
+    fn compound_pending_rewards(&self) {
+        if self.get_staked_assets() <= Int256::zero() {
+            return;
+        }
+
+        let claimed = self.claim_pool_rewards();
+        if claimed > Int256::zero() {
+            self.set_value(&self.idle_assets_key, self.get_idle_assets().checked_add(&claimed));
+        }
+    }
+
+    /// Move idle assets into the pool; they stay idle if the pool refuses them
+    /// (for example while below its minimum stake)
+    fn stake_idle_assets(&self) {
+        let idle = self.get_idle_assets();
+        if idle <= Int256::zero() {
+            return;
+        }
+
+        // The staking contract stakes tokens sent to it with the pool id as data
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(self.get_staking_contract().into_any());
+        args.push(idle.into_any());
+        args.push(self.get_pool_id().into_any());
+        if !Contract::call_bool(self.get_asset(), ByteString::from_literal("transfer"), CallFlags::All, args) {
+            return;
+        }
+
+        self.set_value(&self.staked_assets_key, self.get_staked_assets().checked_add(&idle));
+        self.set_value(&self.idle_assets_key, Int256::zero());
+    }
+
+    fn call_staking(&self, method: &str, amount: Int256) -> bool {
+        let mut args = Array::new();
+        args.push(self.get_pool_id().into_any());
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(amount.into_any());
+        Contract::call_bool(self.get_staking_contract(), ByteString::from_literal(method), CallFlags::All, args)
+    }
+
+    fn asset_balance(&self, account: H160) -> Int256 {
+        let mut args = Array::new();
+        args.push(account.into_any());
+        Contract::call_as(self.get_asset(), ByteString::from_literal("balanceOf"), CallFlags::ReadOnly, args)
+    }
+
+    fn transfer_asset(&self, to: H160, amount: Int256) -> bool {
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(to.into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+        Contract::call_bool(self.get_asset(), ByteString::from_literal("transfer"), CallFlags::All, args)
+    }
+
+    fn set_shares(&self, user: H160, shares: Int256) {
+        let storage = Storage::get_context();
+        let shares_key = self.shares_prefix.concat(&user.into_byte_string());
+        if shares <= Int256::zero() {
+            Storage::delete(storage, shares_key);
+        } else {
+            Storage::put(storage, shares_key, shares.into_byte_string());
+        }
+    }
+
+    fn get_value(&self, key: &ByteString) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, key.clone()) {
+            Some(value_bytes) => Int256::from_byte_string(value_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    fn set_value(&self, key: &ByteString, value: Int256) {
+        let storage = Storage::get_context();
+        Storage::put(storage, key.clone(), value.into_byte_string());
+    }
+
+    fn get_staked_assets(&self) -> Int256 {
+        self.get_value(&self.staked_assets_key)
+    }
+
+    fn get_idle_assets(&self) -> Int256 {
+        self.get_value(&self.idle_assets_key)
+    }
+
+    fn get_staking_contract(&self) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.staking_contract_key.clone()) {
+            Some(hash_bytes) => H160::from_byte_string(hash_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    fn get_asset(&self) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.asset_key.clone()) {
+            Some(hash_bytes) => H160::from_byte_string(hash_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    fn get_pool_id(&self) -> Int256 {
+        self.get_value(&self.pool_id_key)
+    }
+
+    fn get_harvest_bounty(&self) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.harvest_bounty_key.clone()) {
+            Some(bounty_bytes) => {
+                let bytes = bounty_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    fn get_last_harvest(&self) -> u64 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.last_harvest_key.clone()) {
+            Some(time_bytes) => {
+                let bytes = time_bytes.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([
+                        bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]
+                    ])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+}
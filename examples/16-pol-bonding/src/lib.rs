@@ -0,0 +1,735 @@
+//! # Protocol-Owned Liquidity Bonding Contract
+//!
+//! Sells vested governance tokens at a discount in exchange for liquidity:
+//! - Bond markets for LP tokens of a Simple DEX pool or for plain NEP-17 assets
+//! - Bonded LP tokens and assets go straight to the treasury (e.g. the Governance example)
+//! - Payouts vest linearly and are claimed per bond
+//! - Market capacity and outstanding payouts are checked against the tokens the contract holds
+//!
+//! The bonder's witness is checked by the DEX or the asset token when the contract moves the
+//! bonded funds, so it must be scoped to those contracts (`CustomContracts`) rather than
+//! `CalledByEntry`.
+
+#![no_std]
+#![no_main]
+
+use epicchain_contract::prelude::*;
+use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
+
+/// Bond market kinds
+const MARKET_LP: u8 = 1;
+const MARKET_ASSET: u8 = 2;
+
+/// Fixed-point scale of market prices (8 decimals)
+const PRICE_PRECISION: i64 = 100_000_000;
+
+/// Maximum bond discount (50%)
+const MAX_DISCOUNT: u32 = 5000;
+
+/// Vesting period bounds
+const MIN_VESTING_PERIOD: u64 = SECONDS_PER_DAY;
+const MAX_VESTING_PERIOD: u64 = SECONDS_PER_YEAR;
+
+/// Bond market information
+#[derive(Clone)]
+pub struct BondMarket {
+    pub kind: u8,               // MARKET_LP or MARKET_ASSET
+    pub quote_token: H160,      // Asset token; the DEX for LP markets
+    pub pool_id: Int256,        // DEX pool of LP markets
+    pub price: Int256,          // Governance token price in quote units, scaled by PRICE_PRECISION
+    pub discount: u32,          // Discount in basis points
+    pub capacity: Int256,       // Maximum governance tokens sold by the market
+    pub sold: Int256,           // Governance tokens sold so far
+    pub is_active: bool,
+}
+
+/// A vesting bond position
+#[derive(Clone)]
+pub struct Bond {
+    pub market_id: Int256,
+    pub payout: Int256,         // Governance tokens owed
+    pub claimed: Int256,        // Governance tokens already claimed
+    pub start_time: u64,
+    pub vesting_period: u64,
+}
+
+/// Protocol-owned liquidity bonding contract
+#[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
+#[contract_version("1.0.0")]
+#[contract_standards("")]
+#[contract_permission("*", "transfer", "transfer_lp", "get_lp_value", "balanceOf")]
+#[contract_meta("description", "Discounted governance token bonds for protocol-owned liquidity")]
+#[contract_meta("category", "DeFi")]
+pub struct PolBonding {
+    // Configuration
+    owner_key: ByteString,
+    dex_key: ByteString,                // Simple DEX holding the LP positions
+    payout_token_key: ByteString,       // governance token paid to bonders
+    treasury_key: ByteString,           // receives bonded LP tokens and assets
+    vesting_period_key: ByteString,     // vesting period of new bonds
+
+    // Markets
+    market_prefix: ByteString,          // market_id -> market data
+    market_count_key: ByteString,
+
+    // Bonds
+    bond_prefix: ByteString,            // bonder + index -> bond data
+    bond_count_prefix: ByteString,      // bonder -> number of bonds
+    outstanding_key: ByteString,        // governance tokens owed but not yet claimed
+}
+
+#[contract_impl]
+impl PolBonding {
+    /// Initialize the bonding contract
+    pub fn init() -> Self {
+        Self {
+            owner_key: ByteString::from_literal("owner"),
+            dex_key: ByteString::from_literal("dex"),
+            payout_token_key: ByteString::from_literal("payout_token"),
+            treasury_key: ByteString::from_literal("treasury"),
+            vesting_period_key: ByteString::from_literal("vesting_period"),
+            market_prefix: ByteString::from_literal("market_"),
+            market_count_key: ByteString::from_literal("market_count"),
+            bond_prefix: ByteString::from_literal("bond_"),
+            bond_count_prefix: ByteString::from_literal("bond_count_"),
+            outstanding_key: ByteString::from_literal("outstanding"),
+        }
+    }
+
+    /// Initialize the contract with the DEX, governance token and treasury it wires together
+    #[method]
+    pub fn initialize(
+        &self,
+        owner: H160,
+        dex: H160,
+        payout_token: H160,
+        treasury: H160,
+        vesting_period: u64
+    ) -> bool {
+        let storage = Storage::get_context();
+
+        if Storage::get(storage.clone(), self.owner_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Already initialized"));
+            return false;
+        }
+
+        if !Runtime::check_witness(owner) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        if dex == H160::zero() || payout_token == H160::zero() || treasury == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid contract hash"));
+            return false;
+        }
+
+        if vesting_period < MIN_VESTING_PERIOD || vesting_period > MAX_VESTING_PERIOD {
+            Runtime::log(ByteString::from_literal("Invalid vesting period (1 day to 1 year)"));
+            return false;
+        }
+
+        Storage::put(storage.clone(), self.owner_key.clone(), owner.into_byte_string());
+        Storage::put(storage.clone(), self.dex_key.clone(), dex.into_byte_string());
+        Storage::put(storage.clone(), self.payout_token_key.clone(), payout_token.into_byte_string());
+        Storage::put(storage.clone(), self.treasury_key.clone(), treasury.into_byte_string());
+        Storage::put(storage, self.vesting_period_key.clone(), ByteString::from_bytes(&vesting_period.to_le_bytes()));
+
+        let mut event_data = Array::new();
+        event_data.push(dex.into_any());
+        event_data.push(payout_token.into_any());
+        event_data.push(treasury.into_any());
+        Runtime::notify(ByteString::from_literal("BondingInitialized"), event_data);
+
+        true
+    }
+
+    /// Open a market selling governance tokens for LP tokens of a DEX pool (owner only).
+    ///
+    /// `price` is the governance token price in units of the pool's `token_a`, scaled by 1e8.
+    #[method]
+    #[only_owner]
+    pub fn create_lp_market(&self, pool_id: Int256, price: Int256, discount: u32, capacity: Int256) -> Int256 {
+        self.create_market(MARKET_LP, self.get_dex(), pool_id, price, discount, capacity)
+    }
+
+    /// Open a market selling governance tokens for a NEP-17 asset (owner only).
+    ///
+    /// `price` is the governance token price in units of `asset`, scaled by 1e8.
+    #[method]
+    #[only_owner]
+    pub fn create_asset_market(&self, asset: H160, price: Int256, discount: u32, capacity: Int256) -> Int256 {
+        self.create_market(MARKET_ASSET, asset, Int256::zero(), price, discount, capacity)
+    }
+
+    /// Update the price of a market (owner only)
+    #[method]
+    #[only_owner]
+    pub fn set_market_price(&self, market_id: Int256, price: Int256) -> bool {
+        let mut market = match self.get_market_data(market_id) {
+            Some(market) => market,
+            None => {
+                Runtime::log(ByteString::from_literal("Market not found"));
+                return false;
+            }
+        };
+
+        if price <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid price"));
+            return false;
+        }
+
+        market.price = price;
+        self.store_market(market_id, &market);
+
+        let mut event_data = Array::new();
+        event_data.push(market_id.into_any());
+        event_data.push(price.into_any());
+        Runtime::notify(ByteString::from_literal("MarketPriceChanged"), event_data);
+        true
+    }
+
+    /// Close a market; existing bonds keep vesting (owner only)
+    #[method]
+    #[only_owner]
+    pub fn close_market(&self, market_id: Int256) -> bool {
+        let mut market = match self.get_market_data(market_id) {
+            Some(market) => market,
+            None => {
+                Runtime::log(ByteString::from_literal("Market not found"));
+                return false;
+            }
+        };
+
+        market.is_active = false;
+        self.store_market(market_id, &market);
+
+        let mut event_data = Array::new();
+        event_data.push(market_id.into_any());
+        Runtime::notify(ByteString::from_literal("MarketClosed"), event_data);
+        true
+    }
+
+    /// Bond LP tokens of the market's pool; they are moved to the treasury.
+    ///
+    /// The LP tokens are valued by the DEX's `get_lp_value`, from the pool invariant and an
+    /// average price, so moving the pool price right before bonding does not raise the payout.
+    /// Returns the governance tokens owed by the new bond, or zero on failure.
+    #[method]
+    pub fn bond_lp(&self, bonder: H160, market_id: Int256, lp_amount: Int256, min_payout: Int256) -> Int256 {
+        let market = match self.get_open_market(market_id, MARKET_LP) {
+            Some(market) => market,
+            None => return Int256::zero(),
+        };
+
+        let dex = market.quote_token;
+        let pool_id = market.pool_id;
+        let mut value_args = Array::new();
+        value_args.push(pool_id.into_any());
+        value_args.push(lp_amount.into_any());
+        let value: Int256 = Contract::call_as(
+            dex,
+            ByteString::from_literal("get_lp_value"),
+            CallFlags::ReadStates,
+            value_args,
+        );
+
+        let payout = match self.reserve_payout(bonder, market_id, market, value, min_payout) {
+            Some(payout) => payout,
+            None => return Int256::zero(),
+        };
+
+        let mut args = Array::new();
+        args.push(pool_id.into_any());
+        args.push(bonder.into_any());
+        args.push(self.get_treasury().into_any());
+        args.push(lp_amount.into_any());
+        if !Contract::call_bool(dex, ByteString::from_literal("transfer_lp"), CallFlags::All, args) {
+            abort_with_message(ByteString::from_literal("LP transfer failed"));
+        }
+
+        self.open_bond(bonder, market_id, lp_amount, payout)
+    }
+
+    /// Bond a NEP-17 asset; it is transferred from the bonder to the treasury.
+    ///
+    /// Returns the governance tokens owed by the new bond, or zero on failure.
+    #[method]
+    pub fn bond_asset(&self, bonder: H160, market_id: Int256, amount: Int256, min_payout: Int256) -> Int256 {
+        let market = match self.get_open_market(market_id, MARKET_ASSET) {
+            Some(market) => market,
+            None => return Int256::zero(),
+        };
+
+        let asset = market.quote_token;
+        let payout = match self.reserve_payout(bonder, market_id, market, amount, min_payout) {
+            Some(payout) => payout,
+            None => return Int256::zero(),
+        };
+
+        let mut args = Array::new();
+        args.push(bonder.into_any());
+        args.push(self.get_treasury().into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+        if !Contract::call_bool(asset, ByteString::from_literal("transfer"), CallFlags::All, args) {
+            abort_with_message(ByteString::from_literal("Asset transfer failed"));
+        }
+
+        self.open_bond(bonder, market_id, amount, payout)
+    }
+
+    /// Claim the vested part of a bond
+    #[method]
+    pub fn claim(&self, bonder: H160, bond_index: u32) -> Int256 {
+        if !Runtime::check_witness(bonder) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return Int256::zero();
+        }
+
+        let mut bond = match self.get_bond_data(bonder, bond_index) {
+            Some(bond) => bond,
+            None => {
+                Runtime::log(ByteString::from_literal("Bond not found"));
+                return Int256::zero();
+            }
+        };
+
+        let amount = self.vested_amount(&bond, Runtime::get_time()).checked_sub(&bond.claimed);
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Nothing to claim"));
+            return Int256::zero();
+        }
+
+        bond.claimed = bond.claimed.checked_add(&amount);
+        let storage = Storage::get_context();
+        let bond_key = self.get_bond_key(bonder, bond_index);
+        if bond.claimed >= bond.payout {
+            Storage::delete(storage.clone(), bond_key);
+        } else {
+            Storage::put(storage.clone(), bond_key, self.serialize_bond(&bond));
+        }
+        Storage::put(storage, self.outstanding_key.clone(), self.get_outstanding().checked_sub(&amount).into_byte_string());
+
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(bonder.into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+        if !Contract::call_bool(self.get_payout_token(), ByteString::from_literal("transfer"), CallFlags::All, args) {
+            abort_with_message(ByteString::from_literal("Payout transfer failed"));
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(bonder.into_any());
+        event_data.push(Int256::new(bond_index as i64).into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("BondClaimed"), event_data);
+
+        amount
+    }
+
+    /// Get market information
+    #[method]
+    #[safe]
+    pub fn get_market(&self, market_id: Int256) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        if let Some(market) = self.get_market_data(market_id) {
+            result.put(ByteString::from_literal("kind"), Int256::new(market.kind as i64).into_any());
+            result.put(ByteString::from_literal("quote_token"), market.quote_token.into_any());
+            result.put(ByteString::from_literal("pool_id"), market.pool_id.into_any());
+            result.put(ByteString::from_literal("price"), market.price.into_any());
+            result.put(ByteString::from_literal("bond_price"), self.bond_price(&market).into_any());
+            result.put(ByteString::from_literal("discount"), Int256::new(market.discount as i64).into_any());
+            result.put(ByteString::from_literal("capacity"), market.capacity.into_any());
+            result.put(ByteString::from_literal("sold"), market.sold.into_any());
+            let active = if market.is_active { Int256::one() } else { Int256::zero() };
+            result.put(ByteString::from_literal("active"), active.into_any());
+        }
+        result
+    }
+
+    /// Get the number of markets
+    #[method]
+    #[safe]
+    pub fn get_market_count(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.market_count_key.clone()) {
+            Some(count_bytes) => Int256::from_byte_string(count_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Governance tokens a bond of `value` quote units would pay in an asset market,
+    /// or of `value` LP tokens in an LP market
+    #[method]
+    #[safe]
+    pub fn quote(&self, market_id: Int256, value: Int256) -> Int256 {
+        let market = match self.get_market_data(market_id) {
+            Some(market) => market,
+            None => return Int256::zero(),
+        };
+
+        let quote_value = if market.kind == MARKET_LP {
+            let mut args = Array::new();
+            args.push(market.pool_id.into_any());
+            args.push(value.into_any());
+            Contract::call_as(market.quote_token, ByteString::from_literal("get_lp_value"), CallFlags::ReadStates, args)
+        } else {
+            value
+        };
+
+        self.payout_for(&market, quote_value)
+    }
+
+    /// Get a bond of a bonder
+    #[method]
+    #[safe]
+    pub fn get_bond(&self, bonder: H160, bond_index: u32) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        if let Some(bond) = self.get_bond_data(bonder, bond_index) {
+            let vested = self.vested_amount(&bond, Runtime::get_time());
+            result.put(ByteString::from_literal("market_id"), bond.market_id.into_any());
+            result.put(ByteString::from_literal("payout"), bond.payout.into_any());
+            result.put(ByteString::from_literal("claimed"), bond.claimed.into_any());
+            result.put(ByteString::from_literal("claimable"), vested.checked_sub(&bond.claimed).into_any());
+            result.put(ByteString::from_literal("start_time"), Int256::from_u64(bond.start_time).into_any());
+            result.put(
+                ByteString::from_literal("vested_at"),
+                Int256::from_u64(deadline(bond.start_time, bond.vesting_period)).into_any(),
+            );
+        }
+        result
+    }
+
+    /// Get the number of bonds opened by a bonder (fully claimed bonds are deleted)
+    #[method]
+    #[safe]
+    pub fn get_bond_count(&self, bonder: H160) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.bond_count_prefix.concat(&bonder.into_byte_string())) {
+            Some(count_bytes) => {
+                let bytes = count_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            },
+            None => 0,
+        }
+    }
+
+    /// Governance tokens owed to bonders and not yet claimed
+    #[method]
+    #[safe]
+    pub fn get_outstanding(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.outstanding_key.clone()) {
+            Some(amount_bytes) => Int256::from_byte_string(amount_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    // Helper functions
+
+    fn create_market(
+        &self,
+        kind: u8,
+        quote_token: H160,
+        pool_id: Int256,
+        price: Int256,
+        discount: u32,
+        capacity: Int256
+    ) -> Int256 {
+        if quote_token == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid quote token"));
+            return Int256::minus_one();
+        }
+
+        if price <= Int256::zero() || capacity <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid price or capacity"));
+            return Int256::minus_one();
+        }
+
+        if discount > MAX_DISCOUNT {
+            Runtime::log(ByteString::from_literal("Discount too high (max 50%)"));
+            return Int256::minus_one();
+        }
+
+        let market_id = self.get_market_count().checked_add(&Int256::one());
+        let market = BondMarket {
+            kind,
+            quote_token,
+            pool_id,
+            price,
+            discount,
+            capacity,
+            sold: Int256::zero(),
+            is_active: true,
+        };
+        self.store_market(market_id, &market);
+
+        let storage = Storage::get_context();
+        Storage::put(storage, self.market_count_key.clone(), market_id.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(market_id.into_any());
+        event_data.push(Int256::new(kind as i64).into_any());
+        event_data.push(quote_token.into_any());
+        event_data.push(pool_id.into_any());
+        event_data.push(price.into_any());
+        event_data.push(Int256::new(discount as i64).into_any());
+        Runtime::notify(ByteString::from_literal("MarketCreated"), event_data);
+
+        market_id
+    }
+
+    fn get_open_market(&self, market_id: Int256, kind: u8) -> Option<BondMarket> {
+        match self.get_market_data(market_id) {
+            Some(market) if market.kind == kind && market.is_active => Some(market),
+            _ => {
+                Runtime::log(ByteString::from_literal("Market not open for this bond"));
+                None
+            }
+        }
+    }
+
+    /// Check a bond of `value` quote units and record its payout against market capacity
+    /// and the governance tokens held by the contract
+    fn reserve_payout(
+        &self,
+        bonder: H160,
+        market_id: Int256,
+        mut market: BondMarket,
+        value: Int256,
+        min_payout: Int256
+    ) -> Option<Int256> {
+        if !Runtime::check_witness(bonder) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return None;
+        }
+
+        let payout = self.payout_for(&market, value);
+        if payout <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Bond too small"));
+            return None;
+        }
+
+        if payout < min_payout {
+            Runtime::log(ByteString::from_literal("Payout below minimum"));
+            return None;
+        }
+
+        let sold = market.sold.checked_add(&payout);
+        if sold > market.capacity {
+            Runtime::log(ByteString::from_literal("Market capacity exceeded"));
+            return None;
+        }
+
+        let outstanding = self.get_outstanding().checked_add(&payout);
+        if outstanding > self.get_payout_balance() {
+            Runtime::log(ByteString::from_literal("Insufficient governance tokens for payout"));
+            return None;
+        }
+
+        market.sold = sold;
+        if market.sold == market.capacity {
+            market.is_active = false;
+        }
+        self.store_market(market_id, &market);
+
+        let storage = Storage::get_context();
+        Storage::put(storage, self.outstanding_key.clone(), outstanding.into_byte_string());
+
+        Some(payout)
+    }
+
+    fn open_bond(&self, bonder: H160, market_id: Int256, amount: Int256, payout: Int256) -> Int256 {
+        let bond_index = self.get_bond_count(bonder);
+        let bond = Bond {
+            market_id,
+            payout,
+            claimed: Int256::zero(),
+            start_time: Runtime::get_time(),
+            vesting_period: self.get_vesting_period(),
+        };
+
+        let storage = Storage::get_context();
+        Storage::put(storage.clone(), self.get_bond_key(bonder, bond_index), self.serialize_bond(&bond));
+        Storage::put(
+            storage,
+            self.bond_count_prefix.concat(&bonder.into_byte_string()),
+            ByteString::from_bytes(&(bond_index + 1).to_le_bytes()),
+        );
+
+        let mut event_data = Array::new();
+        event_data.push(bonder.into_any());
+        event_data.push(market_id.into_any());
+        event_data.push(Int256::new(bond_index as i64).into_any());
+        event_data.push(amount.into_any());
+        event_data.push(payout.into_any());
+        Runtime::notify(ByteString::from_literal("BondCreated"), event_data);
+
+        payout
+    }
+
+    /// Discounted governance token price in quote units, scaled by PRICE_PRECISION
+    fn bond_price(&self, market: &BondMarket) -> Int256 {
+        market.price
+            .checked_mul(&Int256::new(10000 - market.discount as i64))
+            .checked_div(&Int256::new(10000))
+    }
+
+    fn payout_for(&self, market: &BondMarket, value: Int256) -> Int256 {
+        let price = self.bond_price(market);
+        if price <= Int256::zero() || value <= Int256::zero() {
+            return Int256::zero();
+        }
+        value.checked_mul(&Int256::new(PRICE_PRECISION)).checked_div(&price)
+    }
+
+    fn vested_amount(&self, bond: &Bond, now: u64) -> Int256 {
+        let passed = elapsed(bond.start_time, now);
+        if passed >= bond.vesting_period {
+            return bond.payout;
+        }
+        bond.payout
+            .checked_mul(&Int256::from_u64(passed))
+            .checked_div(&Int256::from_u64(bond.vesting_period))
+    }
+
+    fn get_payout_balance(&self) -> Int256 {
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        Contract::call_as(self.get_payout_token(), ByteString::from_literal("balanceOf"), CallFlags::ReadStates, args)
+    }
+
+    fn get_market_data(&self, market_id: Int256) -> Option<BondMarket> {
+        let storage = Storage::get_context();
+        let bytes = Storage::get(storage, self.market_prefix.concat(&market_id.into_byte_string()))?.to_bytes();
+        if bytes.len() < 27 {
+            return None;
+        }
+
+        let kind = bytes[0];
+        let is_active = bytes[1] == 1;
+        let discount = u32::from_le_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]);
+        let quote_token = H160::from_byte_string(ByteString::from_bytes(&bytes[6..26]));
+
+        let mut offset = 26;
+        let pool_id = self.decode_int(&bytes, &mut offset)?;
+        let price = self.decode_int(&bytes, &mut offset)?;
+        let capacity = self.decode_int(&bytes, &mut offset)?;
+        let sold = self.decode_int(&bytes, &mut offset)?;
+
+        Some(BondMarket { kind, quote_token, pool_id, price, discount, capacity, sold, is_active })
+    }
+
+    /// Stored as kind | active | discount | quote token | pool id | price | capacity | sold
+    fn store_market(&self, market_id: Int256, market: &BondMarket) {
+        let data = ByteString::from_bytes(&[market.kind, market.is_active as u8])
+            .concat(&ByteString::from_bytes(&market.discount.to_le_bytes()))
+            .concat(&market.quote_token.into_byte_string())
+            .concat(&self.encode_int(market.pool_id))
+            .concat(&self.encode_int(market.price))
+            .concat(&self.encode_int(market.capacity))
+            .concat(&self.encode_int(market.sold));
+
+        let storage = Storage::get_context();
+        Storage::put(storage, self.market_prefix.concat(&market_id.into_byte_string()), data);
+    }
+
+    fn get_bond_key(&self, bonder: H160, bond_index: u32) -> ByteString {
+        self.bond_prefix
+            .concat(&bonder.into_byte_string())
+            .concat(&ByteString::from_bytes(&bond_index.to_le_bytes()))
+    }
+
+    fn get_bond_data(&self, bonder: H160, bond_index: u32) -> Option<Bond> {
+        let storage = Storage::get_context();
+        let bytes = Storage::get(storage, self.get_bond_key(bonder, bond_index))?.to_bytes();
+        if bytes.len() < 16 {
+            return None;
+        }
+
+        let start_time = u64::from_le_bytes([
+            bytes[0], bytes[1], bytes[2], bytes[3],
+            bytes[4], bytes[5], bytes[6], bytes[7]
+        ]);
+        let vesting_period = u64::from_le_bytes([
+            bytes[8], bytes[9], bytes[10], bytes[11],
+            bytes[12], bytes[13], bytes[14], bytes[15]
+        ]);
+
+        let mut offset = 16;
+        let market_id = self.decode_int(&bytes, &mut offset)?;
+        let payout = self.decode_int(&bytes, &mut offset)?;
+        let claimed = self.decode_int(&bytes, &mut offset)?;
+
+        Some(Bond { market_id, payout, claimed, start_time, vesting_period })
+    }
+
+    /// Stored as start time | vesting period | market id | payout | claimed
+    fn serialize_bond(&self, bond: &Bond) -> ByteString {
+        ByteString::from_bytes(&bond.start_time.to_le_bytes())
+            .concat(&ByteString::from_bytes(&bond.vesting_period.to_le_bytes()))
+            .concat(&self.encode_int(bond.market_id))
+            .concat(&self.encode_int(bond.payout))
+            .concat(&self.encode_int(bond.claimed))
+    }
+
+    /// One length byte followed by the byte string encoding of `value`
+    fn encode_int(&self, value: Int256) -> ByteString {
+        let encoded = value.into_byte_string();
+        ByteString::from_bytes(&[encoded.len() as u8]).concat(&encoded)
+    }
+
+    fn decode_int(&self, bytes: &[u8], offset: &mut usize) -> Option<Int256> {
+        let len = *bytes.get(*offset)? as usize;
+        let start = *offset + 1;
+        if len == 0 || len > 32 || start + len > bytes.len() {
+            return None;
+        }
+
+        *offset = start + len;
+        Some(Int256::from_byte_string(ByteString::from_bytes(&bytes[start..start + len])))
+    }
+
+    fn get_address(&self, key: &ByteString) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, key.clone()) {
+            Some(hash_bytes) => H160::from_byte_string(hash_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    fn get_dex(&self) -> H160 {
+        self.get_address(&self.dex_key)
+    }
+
+    fn get_payout_token(&self) -> H160 {
+        self.get_address(&self.payout_token_key)
+    }
+
+    fn get_treasury(&self) -> H160 {
+        self.get_address(&self.treasury_key)
+    }
+
+    fn get_vesting_period(&self) -> u64 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.vesting_period_key.clone()) {
+            Some(period_bytes) => {
+                let bytes = period_bytes.to_bytes();
+                if bytes.len() >= 8 {
+                    u64::from_le_bytes([
+                        bytes[0], bytes[1], bytes[2], bytes[3],
+                        bytes[4], bytes[5], bytes[6], bytes[7]
+                    ])
+                } else {
+                    MIN_VESTING_PERIOD
+                }
+            },
+            None => MIN_VESTING_PERIOD,
+        }
+    }
+}
@@ -0,0 +1,535 @@
+//! # Fee-on-Transfer Token Contract
+//!
+//! A NEP-17 token variant that takes a configurable fee on every transfer:
+//! - The fee is split between a burn, a treasury and a reflection to all holders
+//! - Reflections are paid with a per-token accumulator, so no transfer loops over holders
+//! - Accounts can be exempted from the fee or excluded from reflections
+//! - `get_net_amount` lets integrators quote what a recipient will actually receive
+//!
+//! ## Interaction with the other examples
+//!
+//! Recipients receive less than the `amount` passed to `transfer`. The `onNEP17Payment`
+//! callback reports the net amount, so contracts that credit what the callback reports,
+//! like the staking pools, account correctly. Contracts that assume the full amount
+//! arrived do not: the DEX's permit deposits check that their balance grew by the requested
+//! amount and abort, so a DEX listing this token should be made fee-exempt. Payouts from
+//! such contracts are charged too, e.g. an unstake of 100 delivers 100 minus the fee.
+//!
+//! Reflections change balances without `Transfer` events. Custody contracts (DEX, staking,
+//! vaults) track deposits in their own ledgers and would never pass reflections on to
+//! their users, so they should be excluded from reflections.
+
+#![no_std]
+#![no_main]
+
+use epicchain_contract::prelude::*;
+use epicchain_contract::contract::invoke_on_nep17_payment;
+use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
+
+/// Fixed-point scale of the reflection-per-token accumulator
+const REFLECTION_MAGNITUDE: i64 = 1_000_000_000_000_000_000;
+
+/// Basis points denominator of fee rates and shares
+const BASIS_POINTS: u32 = 10000;
+
+/// Maximum transfer fee (10%)
+const MAX_FEE_RATE: u32 = 1000;
+
+/// Fee-on-transfer token with burn, treasury and reflection shares
+#[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
+#[contract_version("1.0.0")]
+#[contract_standards("NEP-17")]
+#[contract_permission("*", "onNEP17Payment")]
+#[contract_meta("description", "NEP-17 token with a fee on transfer split between burn, treasury and holder reflections")]
+#[contract_meta("category", "Token")]
+pub struct FeeOnTransferToken {
+    // Token metadata
+    symbol_key: ByteString,
+    decimals_key: ByteString,
+    total_supply_key: ByteString,
+
+    // Balances
+    balance_prefix: ByteString,             // account -> balance as of its last reflection settlement
+    reflection_snapshot_prefix: ByteString, // account -> reflection per token at its last settlement
+    reflection_per_token_key: ByteString,   // magnified reflections paid per reflecting token
+    reflecting_supply_key: ByteString,      // settled balances of accounts receiving reflections
+
+    // Fees
+    fee_config_key: ByteString,             // fee rate | burn share | treasury share
+    treasury_key: ByteString,
+    fee_exempt_prefix: ByteString,          // account -> exempt from the fee
+    reflection_excluded_prefix: ByteString, // account -> excluded from reflections
+
+    // Administrative
+    owner_key: ByteString,
+}
+
+#[contract_impl]
+impl FeeOnTransferToken {
+    /// Initialize the token contract
+    pub fn init() -> Self {
+        Self {
+            symbol_key: ByteString::from_literal("symbol"),
+            decimals_key: ByteString::from_literal("decimals"),
+            total_supply_key: ByteString::from_literal("total_supply"),
+            balance_prefix: ByteString::from_literal("balance_"),
+            reflection_snapshot_prefix: ByteString::from_literal("refl_snap_"),
+            reflection_per_token_key: ByteString::from_literal("refl_per_token"),
+            reflecting_supply_key: ByteString::from_literal("refl_supply"),
+            fee_config_key: ByteString::from_literal("fee_config"),
+            treasury_key: ByteString::from_literal("treasury"),
+            fee_exempt_prefix: ByteString::from_literal("fee_exempt_"),
+            reflection_excluded_prefix: ByteString::from_literal("refl_excluded_"),
+            owner_key: ByteString::from_literal("owner"),
+        }
+    }
+
+    /// Deploy the token with its initial supply minted to `owner` (one-time setup).
+    ///
+    /// Transfers start without a fee until `set_fee` configures one.
+    #[method]
+    pub fn deploy(
+        &self,
+        owner: H160,
+        symbol: ByteString,
+        decimals: u32,
+        initial_supply: Int256,
+        treasury: H160
+    ) -> bool {
+        let storage = Storage::get_context();
+
+        if Storage::get(storage.clone(), self.owner_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Token already deployed"));
+            return false;
+        }
+
+        if symbol.is_empty() || symbol.len() > 10 {
+            Runtime::log(ByteString::from_literal("Invalid symbol: must be 1-10 characters"));
+            return false;
+        }
+
+        if decimals > 18 {
+            Runtime::log(ByteString::from_literal("Invalid decimals: maximum 18"));
+            return false;
+        }
+
+        if initial_supply < Int256::zero() || treasury == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid deployment parameters"));
+            return false;
+        }
+
+        if !Runtime::check_witness(owner) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        Storage::put(storage.clone(), self.symbol_key.clone(), symbol.clone());
+        Storage::put(storage.clone(), self.decimals_key.clone(), ByteString::from_bytes(&decimals.to_le_bytes()));
+        Storage::put(storage.clone(), self.total_supply_key.clone(), initial_supply.into_byte_string());
+        Storage::put(storage.clone(), self.owner_key.clone(), owner.into_byte_string());
+        Storage::put(storage, self.treasury_key.clone(), treasury.into_byte_string());
+
+        if initial_supply > Int256::zero() {
+            self.set_balance(owner, initial_supply);
+            self.emit_transfer(H160::zero(), owner, initial_supply);
+        }
+
+        let mut event_data = Array::new(); event_data.push(symbol.into_any()); Runtime::notify(ByteString::from_literal("TokenDeployed"), event_data);
+        true
+    }
+
+    /// Get token symbol (NEP-17 required)
+    #[method]
+    #[safe]
+    pub fn symbol(&self) -> ByteString {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.symbol_key.clone()) {
+            Some(symbol) => symbol,
+            None => ByteString::from_literal("UNKNOWN"),
+        }
+    }
+
+    /// Get token decimals (NEP-17 required)
+    #[method]
+    #[safe]
+    pub fn decimals(&self) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.decimals_key.clone()) {
+            Some(decimals_bytes) => {
+                let bytes = decimals_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    8
+                }
+            },
+            None => 8,
+        }
+    }
+
+    /// Get total token supply, net of burned fees (NEP-17 required)
+    #[method]
+    #[safe]
+    pub fn total_supply(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.total_supply_key.clone()) {
+            Some(supply_bytes) => Int256::from_byte_string(supply_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Get balance of an account including reflections not yet settled (NEP-17 required)
+    #[method]
+    #[safe]
+    pub fn balance_of(&self, account: H160) -> Int256 {
+        self.settled_balance(account).checked_add(&self.pending_reflection(account))
+    }
+
+    /// Transfer tokens (NEP-17 required).
+    ///
+    /// `to` receives `amount` minus the transfer fee; the callback reports that net amount.
+    #[method]
+    pub fn transfer(&self, from: H160, to: H160, amount: Int256, data: Any) -> bool {
+        if amount < Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid amount: cannot be negative"));
+            return false;
+        }
+
+        if amount == Int256::zero() || from == to {
+            return true;
+        }
+
+        if !Runtime::check_witness(from) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        let from_balance = self.settle(from);
+        if from_balance < amount {
+            Runtime::log(ByteString::from_literal("Insufficient balance"));
+            return false;
+        }
+
+        let (mut burn, treasury_fee, reflection) = self.split_fee(from, to, amount);
+        let net = amount.checked_sub(&burn).checked_sub(&treasury_fee).checked_sub(&reflection);
+
+        self.set_balance(from, from_balance.checked_sub(&amount));
+        let to_balance = self.settle(to);
+        self.set_balance(to, to_balance.checked_add(&net));
+        self.emit_transfer(from, to, net);
+
+        if treasury_fee > Int256::zero() {
+            let treasury = self.get_treasury();
+            let treasury_balance = self.settle(treasury);
+            self.set_balance(treasury, treasury_balance.checked_add(&treasury_fee));
+            self.emit_transfer(from, treasury, treasury_fee);
+        }
+
+        // With nobody receiving reflections, their share is burned instead
+        if reflection > Int256::zero() && !self.distribute_reflection(reflection) {
+            burn = burn.checked_add(&reflection);
+        }
+
+        if burn > Int256::zero() {
+            let storage = Storage::get_context();
+            Storage::put(storage, self.total_supply_key.clone(), self.total_supply().checked_sub(&burn).into_byte_string());
+            self.emit_transfer(from, H160::zero(), burn);
+        }
+
+        invoke_on_nep17_payment(from, to, net, data);
+        true
+    }
+
+    /// Amount `to` receives when `from` transfers `amount`
+    #[method]
+    #[safe]
+    pub fn get_net_amount(&self, from: H160, to: H160, amount: Int256) -> Int256 {
+        if amount <= Int256::zero() {
+            return Int256::zero();
+        }
+
+        let (burn, treasury_fee, reflection) = self.split_fee(from, to, amount);
+        amount.checked_sub(&burn).checked_sub(&treasury_fee).checked_sub(&reflection)
+    }
+
+    /// Configure the transfer fee (owner only).
+    ///
+    /// `fee_rate` is in basis points of the transferred amount; `burn_share` and
+    /// `treasury_share` are basis points of the fee and the rest is reflected to holders.
+    #[method]
+    #[only_owner]
+    pub fn set_fee(&self, fee_rate: u32, burn_share: u32, treasury_share: u32) -> bool {
+        if fee_rate > MAX_FEE_RATE {
+            Runtime::log(ByteString::from_literal("Fee rate too high (max 10%)"));
+            return false;
+        }
+
+        if burn_share + treasury_share > BASIS_POINTS {
+            Runtime::log(ByteString::from_literal("Fee shares exceed 100%"));
+            return false;
+        }
+
+        let config = ByteString::from_bytes(&fee_rate.to_le_bytes())
+            .concat(&ByteString::from_bytes(&burn_share.to_le_bytes()))
+            .concat(&ByteString::from_bytes(&treasury_share.to_le_bytes()));
+        let storage = Storage::get_context();
+        Storage::put(storage, self.fee_config_key.clone(), config);
+
+        let mut event_data = Array::new();
+        event_data.push(Int256::new(fee_rate as i64).into_any());
+        event_data.push(Int256::new(burn_share as i64).into_any());
+        event_data.push(Int256::new(treasury_share as i64).into_any());
+        event_data.push(Int256::new((BASIS_POINTS - burn_share - treasury_share) as i64).into_any());
+        Runtime::notify(ByteString::from_literal("FeeConfigured"), event_data);
+
+        true
+    }
+
+    /// Get the fee configuration
+    #[method]
+    #[safe]
+    pub fn get_fee_config(&self) -> Map<ByteString, Any> {
+        let (fee_rate, burn_share, treasury_share) = self.get_fee_record();
+        let mut result = Map::new();
+        result.put(ByteString::from_literal("fee_rate"), Int256::new(fee_rate as i64).into_any());
+        result.put(ByteString::from_literal("burn_share"), Int256::new(burn_share as i64).into_any());
+        result.put(ByteString::from_literal("treasury_share"), Int256::new(treasury_share as i64).into_any());
+        result.put(ByteString::from_literal("reflection_share"),
+            Int256::new((BASIS_POINTS - burn_share - treasury_share) as i64).into_any());
+        result.put(ByteString::from_literal("treasury"), self.get_treasury().into_any());
+        result.put(ByteString::from_literal("reflecting_supply"), self.get_reflecting_supply().into_any());
+        result
+    }
+
+    /// Change the account receiving the treasury share of fees (owner only)
+    #[method]
+    #[only_owner]
+    pub fn set_treasury(&self, treasury: H160) -> bool {
+        if treasury == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid treasury"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        Storage::put(storage, self.treasury_key.clone(), treasury.into_byte_string());
+
+        let mut event_data = Array::new(); event_data.push(treasury.into_any()); Runtime::notify(ByteString::from_literal("TreasuryChanged"), event_data);
+        true
+    }
+
+    /// Exempt an account from the transfer fee, as sender or recipient (owner only)
+    #[method]
+    #[only_owner]
+    pub fn set_fee_exempt(&self, account: H160, exempt: bool) -> bool {
+        let storage = Storage::get_context();
+        let exempt_key = self.fee_exempt_prefix.concat(&account.into_byte_string());
+        if exempt {
+            Storage::put(storage, exempt_key, ByteString::from_literal("true"));
+        } else {
+            Storage::delete(storage, exempt_key);
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(account.into_any());
+        event_data.push(if exempt { Int256::one() } else { Int256::zero() }.into_any());
+        Runtime::notify(ByteString::from_literal("FeeExemptionSet"), event_data);
+        true
+    }
+
+    /// Whether an account is exempt from the transfer fee
+    #[method]
+    #[safe]
+    pub fn is_fee_exempt(&self, account: H160) -> bool {
+        let storage = Storage::get_context();
+        Storage::get(storage, self.fee_exempt_prefix.concat(&account.into_byte_string())).is_some()
+    }
+
+    /// Stop or resume paying reflections to an account (owner only).
+    ///
+    /// Reflections earned so far are settled first and stay with the account.
+    #[method]
+    #[only_owner]
+    pub fn set_reflection_excluded(&self, account: H160, excluded: bool) -> bool {
+        if excluded == self.is_reflection_excluded(account) {
+            Runtime::log(ByteString::from_literal("Reflection exclusion unchanged"));
+            return false;
+        }
+
+        let balance = self.settle(account);
+        let storage = Storage::get_context();
+        let excluded_key = self.reflection_excluded_prefix.concat(&account.into_byte_string());
+        let reflecting_supply = self.get_reflecting_supply();
+        if excluded {
+            Storage::put(storage.clone(), excluded_key, ByteString::from_literal("true"));
+            Storage::put(storage, self.reflecting_supply_key.clone(), reflecting_supply.checked_sub(&balance).into_byte_string());
+        } else {
+            Storage::delete(storage.clone(), excluded_key);
+            Storage::put(storage, self.reflecting_supply_key.clone(), reflecting_supply.checked_add(&balance).into_byte_string());
+        }
+
+        let mut event_data = Array::new();
+        event_data.push(account.into_any());
+        event_data.push(if excluded { Int256::one() } else { Int256::zero() }.into_any());
+        Runtime::notify(ByteString::from_literal("ReflectionExclusionSet"), event_data);
+        true
+    }
+
+    /// Whether an account is excluded from reflections
+    #[method]
+    #[safe]
+    pub fn is_reflection_excluded(&self, account: H160) -> bool {
+        let storage = Storage::get_context();
+        Storage::get(storage, self.reflection_excluded_prefix.concat(&account.into_byte_string())).is_some()
+    }
+
+    /// Get contract owner
+    #[method]
+    #[safe]
+    pub fn get_owner(&self) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.owner_key.clone()) {
+            Some(owner_bytes) => H160::from_byte_string(owner_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    // Helper functions
+
+    /// Burn, treasury and reflection parts of the fee on a transfer of `amount`
+    fn split_fee(&self, from: H160, to: H160, amount: Int256) -> (Int256, Int256, Int256) {
+        let (fee_rate, burn_share, treasury_share) = self.get_fee_record();
+        if fee_rate == 0 || self.is_fee_exempt(from) || self.is_fee_exempt(to) {
+            return (Int256::zero(), Int256::zero(), Int256::zero());
+        }
+
+        let basis_points = Int256::new(BASIS_POINTS as i64);
+        let fee = math::mul_div(amount, Int256::new(fee_rate as i64), basis_points);
+        let burn = math::mul_div(fee, Int256::new(burn_share as i64), basis_points);
+        let treasury_fee = math::mul_div(fee, Int256::new(treasury_share as i64), basis_points);
+        (burn, treasury_fee, fee.checked_sub(&burn).checked_sub(&treasury_fee))
+    }
+
+    /// Fee record layout: fee rate (u32 LE) | burn share (u32 LE) | treasury share (u32 LE)
+    fn get_fee_record(&self) -> (u32, u32, u32) {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.fee_config_key.clone()) {
+            Some(config_bytes) => {
+                let bytes = config_bytes.to_bytes();
+                if bytes.len() >= 12 {
+                    (
+                        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+                        u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+                        u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+                    )
+                } else {
+                    (0, 0, 0)
+                }
+            },
+            None => (0, 0, 0),
+        }
+    }
+
+    fn get_treasury(&self) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.treasury_key.clone()) {
+            Some(treasury_bytes) => H160::from_byte_string(treasury_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    fn balances(&self) -> TypedStorageMap<H160, Int256> {
+        TypedStorageMap::with_prefix(self.balance_prefix.clone())
+    }
+
+    fn reflection_snapshots(&self) -> TypedStorageMap<H160, Int256> {
+        TypedStorageMap::with_prefix(self.reflection_snapshot_prefix.clone())
+    }
+
+    fn settled_balance(&self, account: H160) -> Int256 {
+        self.balances().get_or_zero(account)
+    }
+
+    fn get_reflection_per_token(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.reflection_per_token_key.clone()) {
+            Some(value_bytes) => Int256::from_byte_string(value_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    fn get_reflecting_supply(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.reflecting_supply_key.clone()) {
+            Some(supply_bytes) => Int256::from_byte_string(supply_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Reflections earned by `account` since its last settlement
+    fn pending_reflection(&self, account: H160) -> Int256 {
+        if self.is_reflection_excluded(account) {
+            return Int256::zero();
+        }
+
+        let balance = self.settled_balance(account);
+        if balance <= Int256::zero() {
+            return Int256::zero();
+        }
+
+        let earned = self.get_reflection_per_token().checked_sub(&self.reflection_snapshots().get_or_zero(account));
+        math::mul_div(balance, earned, Int256::new(REFLECTION_MAGNITUDE))
+    }
+
+    /// Fold pending reflections into the settled balance and return it
+    fn settle(&self, account: H160) -> Int256 {
+        let pending = self.pending_reflection(account);
+        if pending > Int256::zero() {
+            let balance = self.settled_balance(account);
+            self.set_balance(account, balance.checked_add(&pending));
+        }
+        self.reflection_snapshots().put(account, self.get_reflection_per_token());
+        self.settled_balance(account)
+    }
+
+    /// Write a settled balance, keeping the reflecting supply in step
+    fn set_balance(&self, account: H160, balance: Int256) {
+        if !self.is_reflection_excluded(account) {
+            let delta = balance.checked_sub(&self.settled_balance(account));
+            let storage = Storage::get_context();
+            Storage::put(storage, self.reflecting_supply_key.clone(), self.get_reflecting_supply().checked_add(&delta).into_byte_string());
+        }
+
+        if balance == Int256::zero() {
+            self.balances().delete(account);
+        } else {
+            self.balances().put(account, balance);
+        }
+    }
+
+    /// Pay `amount` to every reflecting holder pro rata; false if nobody receives reflections
+    fn distribute_reflection(&self, amount: Int256) -> bool {
+        let reflecting_supply = self.get_reflecting_supply();
+        if reflecting_supply <= Int256::zero() {
+            return false;
+        }
+
+        let increment = math::mul_div(amount, Int256::new(REFLECTION_MAGNITUDE), reflecting_supply);
+        let reflection_per_token = self.get_reflection_per_token().checked_add(&increment);
+        let storage = Storage::get_context();
+        Storage::put(storage, self.reflection_per_token_key.clone(), reflection_per_token.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(amount.into_any());
+        event_data.push(reflection_per_token.into_any());
+        Runtime::notify(ByteString::from_literal("Reflected"), event_data);
+        true
+    }
+
+    fn emit_transfer(&self, from: H160, to: H160, amount: Int256) {
+        let mut event_data = Array::new();
+        event_data.push(from.into_any());
+        event_data.push(to.into_any());
+        event_data.push(amount.into_any());
+        Runtime::notify(ByteString::from_literal("Transfer"), event_data);
+    }
+}
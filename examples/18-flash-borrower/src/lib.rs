@@ -0,0 +1,252 @@
+//! # Flash Borrower Contract
+//!
+//! A minimal receiver for the DEX's flash swaps:
+//! - `borrow` asks the DEX to lend pool tokens to this contract
+//! - `onFlashSwap` checks the callback comes from the configured DEX for a loan it started,
+//!   then repays each borrowed token plus the pool fee
+//! - Repayment can be switched off to watch the DEX abort an unpaid loan
+//!
+//! The fee is paid from the contract's own balance, so fund it with the pool tokens before
+//! borrowing. A real borrower would put the funds to work (e.g. arbitrage) before repaying.
+
+#![no_std]
+#![no_main]
+
+use epicchain_contract::prelude::*;
+use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
+
+/// Basis points denominator of DEX fee rates
+const BASIS_POINTS: i64 = 10000;
+
+/// Contract that borrows from DEX pools with flash swaps
+#[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
+#[contract_version("1.0.0")]
+#[contract_standards("")]
+#[contract_permission("*", "flash_swap", "transfer")]
+#[contract_meta("description", "Example flash swap borrower for the Simple DEX")]
+#[contract_meta("category", "Testing")]
+pub struct FlashBorrower {
+    owner_key: ByteString,
+    dex_key: ByteString,               // DEX allowed to call back
+    not_repaying_key: ByteString,      // present while loans are deliberately left unpaid
+    loan_count_key: ByteString,        // number of loans repaid
+}
+
+#[contract_impl]
+impl FlashBorrower {
+    /// Initialize the borrower
+    pub fn init() -> Self {
+        Self {
+            owner_key: ByteString::from_literal("owner"),
+            dex_key: ByteString::from_literal("dex"),
+            not_repaying_key: ByteString::from_literal("not_repaying"),
+            loan_count_key: ByteString::from_literal("loan_count"),
+        }
+    }
+
+    /// Initialize the borrower with its owner and the DEX it borrows from
+    #[method]
+    pub fn initialize(&self, owner: H160, dex: H160) -> bool {
+        let storage = Storage::get_context();
+
+        if Storage::get(storage.clone(), self.owner_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Already initialized"));
+            return false;
+        }
+
+        if !Runtime::check_witness(owner) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        Storage::put(storage.clone(), self.owner_key.clone(), owner.into_byte_string());
+        Storage::put(storage, self.dex_key.clone(), dex.into_byte_string());
+        true
+    }
+
+    /// Borrow `amount_a` and `amount_b` from a DEX pool for one callback (owner only)
+    #[method]
+    #[only_owner]
+    pub fn borrow(&self, pool_id: Int256, amount_a: Int256, amount_b: Int256) -> bool {
+        let mut args = Array::new();
+        args.push(pool_id.into_any());
+        args.push(amount_a.into_any());
+        args.push(amount_b.into_any());
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(Any::default());
+        Contract::call_bool(self.get_dex(), ByteString::from_literal("flash_swap"), CallFlags::All, args)
+    }
+
+    /// Repay loans or leave them unpaid (owner only)
+    #[method]
+    #[only_owner]
+    pub fn set_repaying(&self, repaying: bool) -> bool {
+        let storage = Storage::get_context();
+        if repaying {
+            Storage::delete(storage, self.not_repaying_key.clone());
+        } else {
+            Storage::put(storage, self.not_repaying_key.clone(), ByteString::from_literal("true"));
+        }
+        true
+    }
+
+    /// Flash swap callback, invoked by the DEX after sending the borrowed tokens
+    #[method]
+    #[wasm_export(name = "onFlashSwap")]
+    pub fn on_flash_swap(
+        &self,
+        initiator: H160,
+        pool_id: Int256,
+        token_a: H160,
+        token_b: H160,
+        amount_a: Int256,
+        amount_b: Int256,
+        fee_rate: Int256,
+        _data: Any
+    ) -> bool {
+        // Only loans this contract started through its own DEX
+        let dex = Runtime::get_calling_script_hash();
+        if dex != self.get_dex() || initiator != Runtime::get_executing_script_hash() {
+            Runtime::log(ByteString::from_literal("Unexpected flash swap callback"));
+            return false;
+        }
+
+        if Storage::get(Storage::get_context(), self.not_repaying_key.clone()).is_some() {
+            return true;
+        }
+
+        for (token, amount) in [(token_a, amount_a), (token_b, amount_b)] {
+            if amount > Int256::zero() && !self.repay(dex, token, amount, fee_rate) {
+                Runtime::log(ByteString::from_literal("Repayment failed"));
+                return false;
+            }
+        }
+
+        let storage = Storage::get_context();
+        let count = self.get_loan_count().checked_add(&Int256::one());
+        Storage::put(storage, self.loan_count_key.clone(), count.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(pool_id.into_any());
+        event_data.push(amount_a.into_any());
+        event_data.push(amount_b.into_any());
+        Runtime::notify(ByteString::from_literal("LoanRepaid"), event_data);
+        true
+    }
+
+    /// Accept the borrowed tokens and funding for fees
+    #[method]
+    #[wasm_export(name = "onNEP17Payment")]
+    pub fn on_nep17_payment(&self, _from: H160, _amount: Int256, _data: Any) -> bool {
+        true
+    }
+
+    /// Send tokens held by the borrower to `to` (owner only)
+    #[method]
+    #[only_owner]
+    pub fn withdraw(&self, token: H160, to: H160, amount: Int256) -> bool {
+        self.send(token, to, amount)
+    }
+
+    /// Number of loans repaid so far
+    #[method]
+    #[safe]
+    pub fn get_loan_count(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.loan_count_key.clone()) {
+            Some(count_bytes) => Int256::from_byte_string(count_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// DEX the borrower uses
+    #[method]
+    #[safe]
+    pub fn get_dex(&self) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.dex_key.clone()) {
+            Some(dex_bytes) => H160::from_byte_string(dex_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    // Helper functions
+
+    /// Returns `amount` grossed up so that it still covers `amount` once the pool fee is charged
+    fn repay(&self, dex: H160, token: H160, amount: Int256, fee_rate: Int256) -> bool {
+        let basis_points = Int256::new(BASIS_POINTS);
+        let repayment = math::mul_div(amount, basis_points, basis_points.checked_sub(&fee_rate)).checked_add(&Int256::one());
+        self.send(token, dex, repayment)
+    }
+
+    fn send(&self, token: H160, to: H160, amount: Int256) -> bool {
+        let mut args = Array::new();
+        args.push(Runtime::get_executing_script_hash().into_any());
+        args.push(to.into_any());
+        args.push(amount.into_any());
+        args.push(Any::default());
+        Contract::call_bool(token, ByteString::from_literal("transfer"), CallFlags::All, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use epicchain_contract::host;
+
+    fn account(byte: u8) -> H160 {
+        H160::from_bytes(&[byte; 20])
+    }
+
+    /// Borrower at 0xB0 owned by 0x01, borrowing from the DEX at 0xDE
+    fn deploy() -> FlashBorrower {
+        host::set_executing_script_hash(account(0xB0));
+        let borrower = FlashBorrower::init();
+        assert!(borrower.initialize(account(1), account(0xDE)));
+        borrower
+    }
+
+    fn flash_swap_callback(borrower: &FlashBorrower, token: H160, amount: i64) -> bool {
+        borrower.on_flash_swap(
+            account(0xB0),
+            Int256::one(),
+            token,
+            account(0x0B),
+            Int256::new(amount),
+            Int256::zero(),
+            Int256::new(30),
+            Any::default(),
+        )
+    }
+
+    #[test]
+    fn test_borrow_is_repaid_with_the_pool_fee() {
+        let borrower = deploy();
+        let token = account(0x0A);
+        host::register_contract(token, |method, mut args| {
+            assert_eq!(*method, ByteString::from_literal("transfer"));
+            args.pop();
+            let amount: Int256 = args.pop().downcast_into();
+            let to: H160 = args.pop().downcast_into();
+
+            // 10,000 grossed up for a 0.3% fee, rounded up
+            assert!(to == account(0xDE));
+            assert_eq!(amount, Int256::new(10_031));
+            Int256::one().into_any()
+        });
+
+        host::set_calling_script_hash(account(0xDE));
+        assert!(flash_swap_callback(&borrower, token, 10_000));
+        assert_eq!(borrower.get_loan_count(), Int256::one());
+        assert_eq!(host::contract_calls().len(), 1);
+    }
+
+    #[test]
+    fn test_callbacks_from_other_contracts_are_rejected() {
+        let borrower = deploy();
+        host::set_calling_script_hash(account(0xEE));
+        assert!(!flash_swap_callback(&borrower, account(0x0A), 10_000));
+        assert!(host::contract_calls().is_empty());
+        assert_eq!(borrower.get_loan_count(), Int256::zero());
+    }
+}
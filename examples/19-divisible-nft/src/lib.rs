@@ -0,0 +1,423 @@
+//! # Divisible NEP-11 Token Contract
+//!
+//! A divisible variant of the NEP-11 non-fungible token standard, where each token is
+//! split into fractional units that can be held by several accounts at once:
+//! - `transfer(from, to, amount, token_id, data)` moves units of one token
+//! - `balance_of(owner, token_id)` is the units of a token an account holds
+//! - `owners_of(token_id)` enumerates the holders of a token
+//! - Properties are stored once per token and shared by all of its holders
+//! - Holders per token are capped so enumeration stays bounded
+//!
+//! Every token is minted whole, as `10^DECIMALS` units, to a single account, which can then
+//! sell fractions of it (e.g. shares of an artwork or a real-world asset).
+
+#![no_std]
+#![no_main]
+
+use epicchain_contract::prelude::*;
+use epicchain_contract::contract::invoke_on_nep11_payment;
+use epicchain_contract::types::{IntoByteString, FromByteString, builtin::IntoAny};
+
+/// Decimals of every token
+const DECIMALS: u32 = 8;
+
+/// Units a token is minted with (one whole token)
+const UNITS_PER_TOKEN: i64 = 100_000_000;
+
+/// Maximum number of accounts holding units of one token
+const MAX_HOLDERS_PER_TOKEN: u32 = 100;
+
+/// Maximum length of a token id
+const MAX_TOKEN_ID_LENGTH: usize = 64;
+
+/// Maximum length of a shared token property
+const MAX_PROPERTY_LENGTH: usize = 256;
+
+/// Divisible NEP-11 token contract with fractional ownership
+#[contract_author("EpicChain Rust Framework", "devs@epic-chain.org")]
+#[contract_version("1.0.0")]
+#[contract_standards("NEP-11")]
+#[contract_permission("*", "onNEP11Payment")]
+#[contract_meta("description", "Divisible NEP-11 token with fractional ownership")]
+#[contract_meta("category", "NFT")]
+pub struct DivisibleNft {
+    // Token metadata
+    symbol_key: ByteString,
+    total_supply_key: ByteString,      // units of all tokens
+    contract_owner_key: ByteString,
+
+    // Tokens and balances
+    token_prefix: ByteString,          // token_id -> shared properties
+    holder_prefix: ByteString,         // token_id length (u8) + token_id + owner -> units
+    holder_count_prefix: ByteString,   // token_id -> number of holders
+    owned_prefix: ByteString,          // owner + token_id -> held flag
+    owner_balance_prefix: ByteString,  // owner -> units of all tokens
+}
+
+#[contract_impl]
+impl DivisibleNft {
+    /// Initialize the divisible NFT contract
+    pub fn init() -> Self {
+        Self {
+            symbol_key: ByteString::from_literal("symbol"),
+            total_supply_key: ByteString::from_literal("total_supply"),
+            contract_owner_key: ByteString::from_literal("contract_owner"),
+            token_prefix: ByteString::from_literal("token_"),
+            holder_prefix: ByteString::from_literal("holder_"),
+            holder_count_prefix: ByteString::from_literal("holders_"),
+            owned_prefix: ByteString::from_literal("owned_"),
+            owner_balance_prefix: ByteString::from_literal("balance_"),
+        }
+    }
+
+    /// Deploy the contract with its owner and symbol
+    #[method]
+    pub fn deploy(&self, owner: H160, symbol: ByteString) -> bool {
+        let storage = Storage::get_context();
+
+        if Storage::get(storage.clone(), self.contract_owner_key.clone()).is_some() {
+            Runtime::log(ByteString::from_literal("Contract already deployed"));
+            return false;
+        }
+
+        if symbol.is_empty() || symbol.len() > 16 {
+            Runtime::log(ByteString::from_literal("Invalid symbol: must be 1-16 characters"));
+            return false;
+        }
+
+        if !Runtime::check_witness(owner) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        Storage::put(storage.clone(), self.symbol_key.clone(), symbol.clone());
+        Storage::put(storage, self.contract_owner_key.clone(), owner.into_byte_string());
+
+        let mut event_data = Array::new();
+        event_data.push(symbol.into_any());
+        Runtime::notify(ByteString::from_literal("ContractDeployed"), event_data);
+        true
+    }
+
+    /// Get token symbol (NEP-11 required)
+    #[method]
+    #[safe]
+    pub fn symbol(&self) -> ByteString {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.symbol_key.clone()) {
+            Some(symbol) => symbol,
+            None => ByteString::from_literal("DNFT"),
+        }
+    }
+
+    /// Get number of decimals of every token (NEP-11 required)
+    #[method]
+    #[safe]
+    pub fn decimals(&self) -> u32 {
+        DECIMALS
+    }
+
+    /// Get the units of all tokens in circulation (NEP-11 required)
+    #[method]
+    #[safe]
+    pub fn total_supply(&self) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.total_supply_key.clone()) {
+            Some(supply_bytes) => Int256::from_byte_string(supply_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Get the units of a token held by an account (divisible NEP-11)
+    #[method]
+    #[safe]
+    pub fn balance_of(&self, owner: H160, token_id: ByteString) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.get_holder_key(&token_id, owner)) {
+            Some(units_bytes) => Int256::from_byte_string(units_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Get the units of all tokens held by an account
+    #[method]
+    #[safe]
+    pub fn total_balance_of(&self, owner: H160) -> Int256 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.owner_balance_prefix.concat(&owner.into_byte_string())) {
+            Some(units_bytes) => Int256::from_byte_string(units_bytes),
+            None => Int256::zero(),
+        }
+    }
+
+    /// Get the accounts holding units of a token, in ascending hash order (divisible NEP-11)
+    #[method]
+    #[safe]
+    pub fn owners_of(&self, token_id: ByteString) -> Array<H160> {
+        let mut owners = Array::new();
+        for owner in Storage::find_prefix(Storage::get_context(), self.get_holder_base(&token_id)).remove_prefix().keys() {
+            owners.push(H160::from_byte_string(owner));
+        }
+        owners
+    }
+
+    /// Get the number of accounts holding units of a token
+    #[method]
+    #[safe]
+    pub fn holder_count(&self, token_id: ByteString) -> u32 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.holder_count_prefix.concat(&token_id)) {
+            Some(count_bytes) => {
+                let bytes = count_bytes.to_bytes();
+                if bytes.len() >= 4 {
+                    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                } else {
+                    0
+                }
+            }
+            None => 0,
+        }
+    }
+
+    /// Get the tokens an account holds units of, in ascending key order (NEP-11 required)
+    #[method]
+    #[safe]
+    pub fn tokens_of(&self, owner: H160) -> Array<ByteString> {
+        let prefix = self.owned_prefix.concat(&owner.into_byte_string());
+        let mut tokens = Array::new();
+        for token_id in Storage::find_prefix(Storage::get_context(), prefix).remove_prefix().keys() {
+            tokens.push(token_id);
+        }
+        tokens
+    }
+
+    /// Get all minted token IDs, in ascending key order (NEP-11 optional)
+    #[method]
+    #[safe]
+    pub fn tokens(&self) -> Array<ByteString> {
+        let mut tokens = Array::new();
+        for token_id in Storage::find_prefix(Storage::get_context(), self.token_prefix.clone()).remove_prefix().keys() {
+            tokens.push(token_id);
+        }
+        tokens
+    }
+
+    /// Get the properties shared by all holders of a token (NEP-11 optional)
+    #[method]
+    #[safe]
+    pub fn properties(&self, token_id: ByteString) -> Map<ByteString, Any> {
+        let mut result = Map::new();
+        if let Some((name, description, image)) = self.get_token_properties(&token_id) {
+            result.put(ByteString::from_literal("name"), name.into_any());
+            result.put(ByteString::from_literal("description"), description.into_any());
+            result.put(ByteString::from_literal("image"), image.into_any());
+        }
+        result
+    }
+
+    /// Transfer units of a token (divisible NEP-11)
+    #[method]
+    pub fn transfer(&self, from: H160, to: H160, amount: Int256, token_id: ByteString, data: Any) -> bool {
+        if amount <= Int256::zero() {
+            Runtime::log(ByteString::from_literal("Invalid amount"));
+            return false;
+        }
+
+        if to == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid recipient"));
+            return false;
+        }
+
+        if !Runtime::check_witness(from) {
+            Runtime::log(ByteString::from_literal("Unauthorized: Invalid witness"));
+            return false;
+        }
+
+        let from_units = self.balance_of(from, token_id.clone());
+        if from_units < amount {
+            Runtime::log(ByteString::from_literal("Insufficient balance"));
+            return false;
+        }
+
+        if from != to {
+            let to_units = self.balance_of(to, token_id.clone());
+
+            // Rejected if it would add a holder (the recipient holds nothing and the sender keeps
+            // a share) while the token already has the maximum number of holders
+            let adds_holder = to_units.is_zero() && from_units != amount;
+            if adds_holder && self.holder_count(token_id.clone()) >= MAX_HOLDERS_PER_TOKEN {
+                Runtime::log(ByteString::from_literal("Too many holders of this token"));
+                return false;
+            }
+
+            self.set_units(from, &token_id, from_units.checked_sub(&amount));
+            self.set_units(to, &token_id, to_units.checked_add(&amount));
+        }
+
+        self.emit_transfer(from, to, amount, token_id.clone());
+
+        // Calls onNEP11Payment when the recipient is a deployed contract; a rejection aborts the transfer
+        invoke_on_nep11_payment(from, to, amount, token_id, data);
+
+        true
+    }
+
+    /// Mint a whole token to an account (owner only)
+    #[method]
+    pub fn mint(
+        &self,
+        to: H160,
+        token_id: ByteString,
+        name: ByteString,
+        description: ByteString,
+        image: ByteString
+    ) -> bool {
+        if !self.is_owner() {
+            Runtime::log(ByteString::from_literal("Unauthorized: Only owner can mint"));
+            return false;
+        }
+
+        if token_id.is_empty() || token_id.len() > MAX_TOKEN_ID_LENGTH {
+            Runtime::log(ByteString::from_literal("Invalid token ID: must be 1-64 characters"));
+            return false;
+        }
+
+        if to == H160::zero() {
+            Runtime::log(ByteString::from_literal("Invalid recipient"));
+            return false;
+        }
+
+        if name.len() > MAX_PROPERTY_LENGTH || description.len() > MAX_PROPERTY_LENGTH || image.len() > MAX_PROPERTY_LENGTH {
+            Runtime::log(ByteString::from_literal("Token property too long"));
+            return false;
+        }
+
+        if self.get_token_properties(&token_id).is_some() {
+            Runtime::log(ByteString::from_literal("Token already exists"));
+            return false;
+        }
+
+        let storage = Storage::get_context();
+        let properties = self.encode_field(&name)
+            .concat(&self.encode_field(&description))
+            .concat(&self.encode_field(&image));
+        Storage::put(storage.clone(), self.token_prefix.concat(&token_id), properties);
+
+        let units = Int256::new(UNITS_PER_TOKEN);
+        self.set_units(to, &token_id, units);
+
+        let new_supply = self.total_supply().checked_add(&units);
+        Storage::put(storage, self.total_supply_key.clone(), new_supply.into_byte_string());
+
+        self.emit_transfer(H160::zero(), to, units, token_id);
+        true
+    }
+
+    /// Get contract owner
+    #[method]
+    #[safe]
+    pub fn get_owner(&self) -> H160 {
+        let storage = Storage::get_context();
+        match Storage::get(storage, self.contract_owner_key.clone()) {
+            Some(owner_bytes) => H160::from_byte_string(owner_bytes),
+            None => H160::zero(),
+        }
+    }
+
+    // Helper functions
+
+    fn is_owner(&self) -> bool {
+        let owner = self.get_owner();
+        if owner == H160::zero() {
+            return false;
+        }
+        Runtime::check_witness(owner)
+    }
+
+    /// Prefix of the holder entries of a token; the length byte keeps one token id from
+    /// matching the entries of a longer one
+    fn get_holder_base(&self, token_id: &ByteString) -> ByteString {
+        self.holder_prefix
+            .concat(&ByteString::from_bytes(&[token_id.len() as u8]))
+            .concat(token_id)
+    }
+
+    fn get_holder_key(&self, token_id: &ByteString, owner: H160) -> ByteString {
+        self.get_holder_base(token_id).concat(&owner.into_byte_string())
+    }
+
+    /// Sets the units of a token held by an account, keeping the holder index, holder count
+    /// and the account's total balance in step
+    fn set_units(&self, owner: H160, token_id: &ByteString, units: Int256) {
+        let storage = Storage::get_context();
+        let previous = self.balance_of(owner, token_id.clone());
+        let holder_key = self.get_holder_key(token_id, owner);
+        let owned_key = self.owned_prefix.concat(&owner.into_byte_string()).concat(token_id);
+        let holders = self.holder_count(token_id.clone());
+
+        if units.is_zero() {
+            Storage::delete(storage.clone(), holder_key);
+            Storage::delete(storage.clone(), owned_key);
+            if !previous.is_zero() {
+                self.set_holder_count(token_id, holders.saturating_sub(1));
+            }
+        } else {
+            Storage::put(storage.clone(), holder_key, units.into_byte_string());
+            if previous.is_zero() {
+                Storage::put(storage.clone(), owned_key, ByteString::from_literal("true"));
+                self.set_holder_count(token_id, holders + 1);
+            }
+        }
+
+        let balance_key = self.owner_balance_prefix.concat(&owner.into_byte_string());
+        let balance = self.total_balance_of(owner).checked_sub(&previous).checked_add(&units);
+        if balance.is_zero() {
+            Storage::delete(storage, balance_key);
+        } else {
+            Storage::put(storage, balance_key, balance.into_byte_string());
+        }
+    }
+
+    fn set_holder_count(&self, token_id: &ByteString, count: u32) {
+        let storage = Storage::get_context();
+        let count_key = self.holder_count_prefix.concat(token_id);
+        if count == 0 {
+            Storage::delete(storage, count_key);
+        } else {
+            Storage::put(storage, count_key, ByteString::from_bytes(&count.to_le_bytes()));
+        }
+    }
+
+    /// Name, description and image of a token
+    fn get_token_properties(&self, token_id: &ByteString) -> Option<(ByteString, ByteString, ByteString)> {
+        let storage = Storage::get_context();
+        let record = Storage::get(storage, self.token_prefix.concat(token_id))?.to_bytes();
+        let mut offset = 0;
+        let name = self.read_field(&record, &mut offset)?;
+        let description = self.read_field(&record, &mut offset)?;
+        let image = self.read_field(&record, &mut offset)?;
+        Some((name, description, image))
+    }
+
+    /// Length (u16) prefixed field
+    fn encode_field(&self, value: &ByteString) -> ByteString {
+        ByteString::from_bytes(&(value.len() as u16).to_le_bytes()).concat(value)
+    }
+
+    fn read_field(&self, bytes: &[u8], offset: &mut usize) -> Option<ByteString> {
+        let len_bytes = bytes.get(*offset..*offset + 2)?;
+        let len = u16::from_le_bytes([len_bytes[0], len_bytes[1]]) as usize;
+        let value = bytes.get(*offset + 2..*offset + 2 + len)?;
+        *offset += 2 + len;
+        Some(ByteString::from_bytes(value))
+    }
+
+    fn emit_transfer(&self, from: H160, to: H160, amount: Int256, token_id: ByteString) {
+        let mut event_data = Array::new();
+        event_data.push(from.into_any());
+        event_data.push(to.into_any());
+        event_data.push(amount.into_any());
+        event_data.push(token_id.into_any());
+        Runtime::notify(ByteString::from_literal("Transfer"), event_data);
+    }
+}